@@ -1,4 +1,5 @@
-use crate::util::report_error;
+use crate::output_format::{print_structured, report_error_for, OutputFormat};
+use crate::util::run_signal_traps;
 use log::info;
 use miette::Result;
 use nu_engine::{convert_env_values, eval_block};
@@ -16,11 +17,12 @@ pub fn evaluate_commands(
     stack: &mut Stack,
     input: PipelineData,
     table_mode: Option<Value>,
+    output_format: Option<OutputFormat>,
 ) -> Result<Option<i64>> {
     // Translate environment variables from Strings to Values
     if let Some(e) = convert_env_values(engine_state, stack) {
         let working_set = StateWorkingSet::new(engine_state);
-        report_error(&working_set, &e);
+        report_error_for(output_format, &working_set, &e);
         std::process::exit(1);
     }
 
@@ -36,7 +38,7 @@ pub fn evaluate_commands(
 
         let (output, err) = parse(&mut working_set, None, commands.item.as_bytes(), false, &[]);
         if let Some(err) = err {
-            report_error(&working_set, &err);
+            report_error_for(output_format, &working_set, &err);
 
             std::process::exit(1);
         }
@@ -47,22 +49,38 @@ pub fn evaluate_commands(
     // Update permanent state
     if let Err(err) = engine_state.merge_delta(delta) {
         let working_set = StateWorkingSet::new(engine_state);
-        report_error(&working_set, &err);
+        report_error_for(output_format, &working_set, &err);
     }
 
     // Run the block
     let exit_code = match eval_block(engine_state, stack, &block, input, false, false) {
         Ok(pipeline_data) => {
-            let mut config = engine_state.get_config().clone();
-            if let Some(t_mode) = table_mode {
-                config.table_mode = t_mode.as_string()?;
-            }
-            crate::eval_file::print_table_or_error(engine_state, stack, pipeline_data, &mut config)
+            let exit_code = if let Some(format) = output_format {
+                print_structured(engine_state, stack, pipeline_data, format).unwrap_or_else(|err| {
+                    let working_set = StateWorkingSet::new(engine_state);
+                    report_error_for(output_format, &working_set, &err);
+                    std::process::exit(1);
+                })
+            } else {
+                let mut config = engine_state.get_config().clone();
+                if let Some(t_mode) = table_mode {
+                    config.table_mode = t_mode.as_string()?;
+                }
+                crate::eval_file::print_table_or_error(
+                    engine_state,
+                    stack,
+                    pipeline_data,
+                    &mut config,
+                )
+            };
+            run_signal_traps(engine_state, stack);
+            exit_code
         }
         Err(err) => {
             let working_set = StateWorkingSet::new(engine_state);
 
-            report_error(&working_set, &err);
+            report_error_for(output_format, &working_set, &err);
+            run_signal_traps(engine_state, stack);
             std::process::exit(1);
         }
     };
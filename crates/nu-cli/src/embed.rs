@@ -0,0 +1,272 @@
+use nu_engine::eval_block_with_early_return;
+use nu_parser::parse;
+use nu_protocol::{
+    ast::Call,
+    engine::{EngineState, Stack, StateWorkingSet},
+    IntoPipelineData, PipelineData, ShellError, Span, Type, Value,
+};
+use serde_json::json;
+use std::path::Path;
+
+/// An error reported while parsing or evaluating, with the source its labels point at
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub code: Option<String>,
+    pub help: Option<String>,
+    pub labels: Vec<DiagnosticLabel>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagnosticLabel {
+    pub text: Option<String>,
+    pub span: Span,
+    pub source: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        working_set: &StateWorkingSet,
+        error: &(dyn miette::Diagnostic + Send + Sync + 'static),
+    ) -> Self {
+        let labels = error
+            .labels()
+            .into_iter()
+            .flatten()
+            .map(|label| {
+                let span = Span::new(label.offset(), label.offset() + label.len());
+                DiagnosticLabel {
+                    text: label.label().map(|text| text.to_string()),
+                    span,
+                    source: String::from_utf8_lossy(working_set.get_span_contents(span))
+                        .to_string(),
+                }
+            })
+            .collect();
+
+        Diagnostic {
+            message: error.to_string(),
+            code: error.code().map(|code| code.to_string()),
+            help: error.help().map(|help| help.to_string()),
+            labels,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let labels: Vec<_> = self
+            .labels
+            .iter()
+            .map(|label| {
+                json!({
+                    "text": label.text,
+                    "start": label.span.start,
+                    "end": label.span.end,
+                    "source": label.source,
+                })
+            })
+            .collect();
+
+        json!({
+            "error": self.message,
+            "code": self.code,
+            "help": self.help,
+            "labels": labels,
+        })
+    }
+}
+
+/// The result of evaluating source with `eval_captured`: the value it evaluated to, nothing if it
+/// failed, and the errors it failed with
+#[derive(Clone, Debug)]
+pub struct Captured {
+    pub value: Value,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Captured {
+    fn failed(diagnostic: Diagnostic) -> Self {
+        Captured {
+            value: Value::nothing(Span::unknown()),
+            diagnostics: vec![diagnostic],
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// The value and diagnostics as a JSON object with `value` and `diagnostics` fields. The value
+    /// is converted with the `to json` command, which must be in the engine state.
+    pub fn to_json(&self, engine_state: &EngineState) -> Result<String, ShellError> {
+        let decl_id = engine_state
+            .find_decl(b"to json", &[])
+            .ok_or(ShellError::CommandNotFound(Span::unknown()))?;
+        let value = engine_state
+            .get_decl(decl_id)
+            .run(
+                engine_state,
+                &mut Stack::new(),
+                &Call::new(Span::unknown()),
+                self.value.clone().into_pipeline_data(),
+            )?
+            .collect_string("", engine_state.get_config())?;
+        let value: serde_json::Value = serde_json::from_str(&value).map_err(|e| {
+            ShellError::CantConvert(
+                "JSON".into(),
+                "value".into(),
+                Span::unknown(),
+                Some(e.to_string()),
+            )
+        })?;
+
+        let diagnostics: Vec<_> = self.diagnostics.iter().map(Diagnostic::to_json).collect();
+
+        Ok(json!({
+            "value": value,
+            "diagnostics": diagnostics,
+        })
+        .to_string())
+    }
+}
+
+/// Evaluates Nushell source in isolation and returns the value it evaluated to, along with any
+/// errors, instead of printing them. This is the entry point for Rust programs that embed Nushell
+/// as a configuration or query language.
+///
+/// The engine state is cloned, so the definitions of the source don't leak into it. The source
+/// sees the environment of the engine state, with `env` added on top, and gets `input` as its
+/// pipeline input. External streams are collected into the value. To evaluate untrusted source,
+/// set `restrictions` on the engine state first.
+pub fn eval_captured(
+    engine_state: &EngineState,
+    source: &[u8],
+    fname: &str,
+    env: impl IntoIterator<Item = (String, Value)>,
+    input: PipelineData,
+) -> Captured {
+    let mut engine_state = engine_state.clone();
+    let mut stack = Stack::new();
+    for (name, value) in env {
+        stack.add_env_var(name, value);
+    }
+
+    let (block, delta) = {
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        let (output, err) = parse(&mut working_set, Some(fname), source, false, &[]);
+        if let Some(err) = err {
+            return Captured::failed(Diagnostic::new(&working_set, &err));
+        }
+
+        (output, working_set.render())
+    };
+
+    if let Err(err) = engine_state.merge_delta(delta) {
+        return Captured::failed(Diagnostic::new(&StateWorkingSet::new(&engine_state), &err));
+    }
+
+    let value =
+        eval_block_with_early_return(&engine_state, &mut stack, &block, input, false, false)
+            .map(|pipeline_data| pipeline_data.into_value(Span::unknown()));
+
+    match value {
+        Ok(Value::Error { error }) | Err(error) => Captured::failed(Diagnostic::new(
+            &StateWorkingSet::new(&engine_state),
+            &error,
+        )),
+        Ok(value) => Captured {
+            value,
+            diagnostics: vec![],
+        },
+    }
+}
+
+/// Like `eval_captured`, for a script file. When the script defines a `main` command, it is called
+/// with `args`, which must already be escaped for the command line.
+pub fn eval_file_captured(
+    engine_state: &EngineState,
+    path: &Path,
+    args: &[String],
+    env: impl IntoIterator<Item = (String, Value)>,
+    input: PipelineData,
+) -> Captured {
+    let working_set = StateWorkingSet::new(engine_state);
+    let fname = path.to_string_lossy().to_string();
+
+    let mut source = match std::fs::read(path) {
+        Ok(source) => source,
+        Err(e) => {
+            return Captured::failed(Diagnostic::new(
+                &working_set,
+                &ShellError::FileNotFoundCustom(
+                    format!("Could not read file '{fname}': {e}"),
+                    Span::unknown(),
+                ),
+            ))
+        }
+    };
+
+    // Parse on a throwaway working set to know whether to call main
+    let mut scratch = StateWorkingSet::new(engine_state);
+    let _ = parse(&mut scratch, Some(&fname), &source, false, &[]);
+    if scratch.find_decl(b"main", &Type::Any).is_some() {
+        source.extend_from_slice(format!("\nmain {}", args.join(" ")).as_bytes());
+    }
+
+    let mut file_env = vec![];
+    if let Some(parent) = path.parent() {
+        file_env.push((
+            "FILE_PWD".to_string(),
+            Value::string(parent.to_string_lossy(), Span::unknown()),
+        ));
+    }
+
+    eval_captured(
+        engine_state,
+        &source,
+        &fname,
+        file_env.into_iter().chain(env),
+        input,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn captured_failure_has_no_value() {
+        let captured = Captured::failed(Diagnostic {
+            message: "boom".into(),
+            code: None,
+            help: None,
+            labels: vec![],
+        });
+
+        assert!(!captured.is_success());
+        assert_eq!(captured.value, Value::nothing(Span::unknown()));
+    }
+
+    #[test]
+    fn diagnostic_json_has_the_labels() {
+        let diagnostic = Diagnostic {
+            message: "Type mismatch".into(),
+            code: Some("nu::shell::type_mismatch".into()),
+            help: None,
+            labels: vec![DiagnosticLabel {
+                text: Some("expected int".into()),
+                span: Span::new(3, 6),
+                source: "foo".into(),
+            }],
+        };
+
+        assert_eq!(
+            diagnostic.to_json(),
+            json!({
+                "error": "Type mismatch",
+                "code": "nu::shell::type_mismatch",
+                "help": null,
+                "labels": [{"text": "expected int", "start": 3, "end": 6, "source": "foo"}],
+            })
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::output_format::{eval_source_structured, report_error_for, OutputFormat};
 use crate::util::{eval_source, report_error};
 use log::info;
 use log::trace;
@@ -19,31 +20,35 @@ pub fn evaluate_file(
     engine_state: &mut EngineState,
     stack: &mut Stack,
     input: PipelineData,
+    output_format: Option<OutputFormat>,
+    error_exit_code: i32,
 ) -> Result<()> {
     // Translate environment variables from Strings to Values
     if let Some(e) = convert_env_values(engine_state, stack) {
         let working_set = StateWorkingSet::new(engine_state);
-        report_error(&working_set, &e);
-        std::process::exit(1);
+        report_error_for(output_format, &working_set, &e);
+        std::process::exit(error_exit_code);
     }
 
     let cwd = current_dir(engine_state, stack)?;
 
     let file_path = canonicalize_with(&path, cwd).unwrap_or_else(|e| {
         let working_set = StateWorkingSet::new(engine_state);
-        report_error(
+        report_error_for(
+            output_format,
             &working_set,
             &ShellError::FileNotFoundCustom(
                 format!("Could not access file '{}': {:?}", path, e.to_string()),
                 Span::unknown(),
             ),
         );
-        std::process::exit(1);
+        std::process::exit(error_exit_code);
     });
 
     let file_path_str = file_path.to_str().unwrap_or_else(|| {
         let working_set = StateWorkingSet::new(engine_state);
-        report_error(
+        report_error_for(
+            output_format,
             &working_set,
             &ShellError::NonUtf8Custom(
                 format!(
@@ -53,14 +58,15 @@ pub fn evaluate_file(
                 Span::unknown(),
             ),
         );
-        std::process::exit(1);
+        std::process::exit(error_exit_code);
     });
 
     let file = std::fs::read(&file_path)
         .into_diagnostic()
         .unwrap_or_else(|e| {
             let working_set = StateWorkingSet::new(engine_state);
-            report_error(
+            report_error_for(
+                output_format,
                 &working_set,
                 &ShellError::FileNotFoundCustom(
                     format!(
@@ -71,21 +77,22 @@ pub fn evaluate_file(
                     Span::unknown(),
                 ),
             );
-            std::process::exit(1);
+            std::process::exit(error_exit_code);
         });
 
     engine_state.start_in_file(Some(file_path_str));
 
     let parent = file_path.parent().unwrap_or_else(|| {
         let working_set = StateWorkingSet::new(engine_state);
-        report_error(
+        report_error_for(
+            output_format,
             &working_set,
             &ShellError::FileNotFoundCustom(
                 format!("The file path '{file_path_str}' does not have a parent"),
                 Span::unknown(),
             ),
         );
-        std::process::exit(1);
+        std::process::exit(error_exit_code);
     });
 
     stack.add_env_var(
@@ -100,28 +107,35 @@ pub fn evaluate_file(
     if working_set.find_decl(b"main", &Type::Any).is_some() {
         let args = format!("main {}", args.join(" "));
 
-        if !eval_source(
+        if !eval_script_source(
             engine_state,
             stack,
             &file,
             file_path_str,
             PipelineData::empty(),
-            true,
+            output_format,
         ) {
-            std::process::exit(1);
+            std::process::exit(error_exit_code);
         }
-        if !eval_source(
+        if !eval_script_source(
             engine_state,
             stack,
             args.as_bytes(),
             "<commandline>",
             input,
-            true,
+            output_format,
         ) {
-            std::process::exit(1);
+            std::process::exit(error_exit_code);
         }
-    } else if !eval_source(engine_state, stack, &file, file_path_str, input, true) {
-        std::process::exit(1);
+    } else if !eval_script_source(
+        engine_state,
+        stack,
+        &file,
+        file_path_str,
+        input,
+        output_format,
+    ) {
+        std::process::exit(error_exit_code);
     }
 
     info!("evaluate {}:{}:{}", file!(), line!(), column!());
@@ -129,6 +143,20 @@ pub fn evaluate_file(
     Ok(())
 }
 
+fn eval_script_source(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    source: &[u8],
+    fname: &str,
+    input: PipelineData,
+    output_format: Option<OutputFormat>,
+) -> bool {
+    match output_format {
+        Some(format) => eval_source_structured(engine_state, stack, source, fname, input, format),
+        None => eval_source(engine_state, stack, source, fname, input, true),
+    }
+}
+
 pub(crate) fn print_table_or_error(
     engine_state: &mut EngineState,
     stack: &mut Stack,
@@ -0,0 +1,198 @@
+use crate::NuCompleter;
+use nu_ansi_term::Style;
+use nu_protocol::{engine::HintCycle, HintSource};
+use reedline::{
+    CommandLineSearch, Completer, Hinter, History, HistoryItem, SearchDirection, SearchQuery,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// How far back in the history to look for commands to rank
+const HISTORY_SEARCH_LIMIT: i64 = 1000;
+
+/// Shows the commands you are most likely to be typing as an inline hint. The candidates come from
+/// the sources in `$env.config.hint_sources`, in order, and `commandline --next-hint` cycles
+/// through them.
+pub struct NuHinter {
+    style: Style,
+    sources: Vec<HintSource>,
+    cwd: String,
+    cycle: Arc<Mutex<HintCycle>>,
+    completer: Option<NuCompleter>,
+    current_hint: String,
+}
+
+impl NuHinter {
+    pub fn new(
+        style: Style,
+        sources: Vec<HintSource>,
+        cwd: String,
+        cycle: Arc<Mutex<HintCycle>>,
+        completer: Option<NuCompleter>,
+    ) -> Self {
+        NuHinter {
+            style,
+            sources,
+            cwd,
+            cycle,
+            completer,
+            current_hint: String::new(),
+        }
+    }
+
+    fn candidates(&mut self, line: &str, pos: usize, history: &dyn History) -> Vec<String> {
+        let mut candidates: Vec<String> = vec![];
+        for source in self.sources.clone() {
+            let hints = match source {
+                HintSource::Cwd => history_hints(line, history, Some(self.cwd.clone())),
+                HintSource::History => history_hints(line, history, None),
+                HintSource::Completions => self.completion_hint(line, pos).into_iter().collect(),
+            };
+            for hint in hints {
+                if !hint.is_empty() && !candidates.contains(&hint) {
+                    candidates.push(hint);
+                }
+            }
+        }
+        candidates
+    }
+
+    fn completion_hint(&mut self, line: &str, pos: usize) -> Option<String> {
+        // A hint is only shown after the cursor, so there is nothing to complete in the middle
+        if pos != line.len() {
+            return None;
+        }
+        let completer = self.completer.as_mut()?;
+        let suggestion = completer.complete(line, pos).into_iter().next()?;
+        let typed = line.get(suggestion.span.start..pos)?;
+        suggestion
+            .value
+            .strip_prefix(typed)
+            .map(|rest| rest.to_string())
+    }
+}
+
+impl Hinter for NuHinter {
+    fn handle(
+        &mut self,
+        line: &str,
+        pos: usize,
+        history: &dyn History,
+        use_ansi_coloring: bool,
+    ) -> String {
+        self.current_hint = if line.is_empty() {
+            String::new()
+        } else {
+            let offset = {
+                let cycle = self.cycle.lock().expect("hint cycle mutex");
+                if cycle.line == line {
+                    cycle.offset
+                } else {
+                    0
+                }
+            };
+            let candidates = self.candidates(line, pos, history);
+            if candidates.is_empty() {
+                String::new()
+            } else {
+                candidates[offset % candidates.len()].clone()
+            }
+        };
+
+        if use_ansi_coloring && !self.current_hint.is_empty() {
+            self.style.paint(&self.current_hint).to_string()
+        } else {
+            self.current_hint.clone()
+        }
+    }
+
+    fn complete_hint(&self) -> String {
+        self.current_hint.clone()
+    }
+
+    fn next_hint_token(&self) -> String {
+        // The leading whitespace, then everything up to the next whitespace
+        let mut reached_content = false;
+        self.current_hint
+            .chars()
+            .take_while(|c| match (c.is_whitespace(), reached_content) {
+                (true, true) => false,
+                (true, false) => true,
+                (false, _) => {
+                    reached_content = true;
+                    true
+                }
+            })
+            .collect()
+    }
+}
+
+/// The rest of the history entries that start with `line`, run in `cwd` if given, the most
+/// frequently run first
+fn history_hints(line: &str, history: &dyn History, cwd: Option<String>) -> Vec<String> {
+    let mut query = SearchQuery::everything(SearchDirection::Backward);
+    query.limit = Some(HISTORY_SEARCH_LIMIT);
+    query.filter.command_line = Some(CommandLineSearch::Prefix(line.to_string()));
+    query.filter.cwd_exact = cwd;
+
+    // Not every history format knows where a command was run, so a failed search has no hints
+    let items = history.search(query).unwrap_or_default();
+    rank_by_frequency(&items)
+        .into_iter()
+        .filter_map(|command| command.get(line.len()..).map(|rest| rest.to_string()))
+        .collect()
+}
+
+/// The distinct command lines of `items`, which are newest first, ordered by how often they
+/// appear. Ties keep the newest first.
+fn rank_by_frequency(items: &[HistoryItem]) -> Vec<String> {
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (recency, item) in items.iter().enumerate() {
+        counts
+            .entry(item.command_line.as_str())
+            .or_insert((0, recency))
+            .0 += 1;
+    }
+
+    let mut ranked: Vec<_> = counts.into_iter().collect();
+    ranked.sort_by(|(_, (count_a, recency_a)), (_, (count_b, recency_b))| {
+        count_b.cmp(count_a).then(recency_a.cmp(recency_b))
+    });
+    ranked
+        .into_iter()
+        .map(|(command, _)| command.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn item(command_line: &str) -> HistoryItem {
+        HistoryItem::from_command_line(command_line)
+    }
+
+    #[test]
+    fn most_frequent_commands_rank_first() {
+        let items = vec![
+            item("cargo test"),
+            item("cargo build"),
+            item("cargo build"),
+            item("cargo clippy"),
+            item("cargo build"),
+            item("cargo clippy"),
+        ];
+
+        assert_eq!(
+            rank_by_frequency(&items),
+            vec!["cargo build", "cargo clippy", "cargo test"]
+        );
+    }
+
+    #[test]
+    fn ties_rank_the_newest_first() {
+        let items = vec![item("ls -a"), item("ls -l"), item("ls -l"), item("ls -a")];
+
+        assert_eq!(rank_by_frequency(&items), vec!["ls -a", "ls -l"]);
+    }
+}
@@ -1,9 +1,12 @@
 mod commands;
 mod completions;
 mod config_files;
+mod embed;
 mod eval_file;
+mod hinter;
 mod menus;
 mod nu_highlight;
+mod output_format;
 mod print;
 mod prompt;
 mod prompt_update;
@@ -16,9 +19,12 @@ mod validation;
 pub use commands::evaluate_commands;
 pub use completions::{FileCompletion, NuCompleter};
 pub use config_files::eval_config_contents;
+pub use embed::{eval_captured, eval_file_captured, Captured, Diagnostic, DiagnosticLabel};
 pub use eval_file::evaluate_file;
+pub use hinter::NuHinter;
 pub use menus::{DescriptionMenu, NuHelpCompleter};
 pub use nu_highlight::NuHighlight;
+pub use output_format::{report_error_structured, OutputFormat};
 pub use print::Print;
 pub use prompt::NushellPrompt;
 pub use repl::evaluate_repl;
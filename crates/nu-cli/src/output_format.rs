@@ -0,0 +1,145 @@
+use crate::embed::Diagnostic;
+use crate::util::{report_error, run_signal_traps, set_last_exit_code};
+use nu_engine::eval_block_with_early_return;
+use nu_parser::parse;
+use nu_protocol::{
+    ast::Call,
+    engine::{EngineState, Stack, StateWorkingSet},
+    IntoPipelineData, PipelineData, ShellError, Span, Value,
+};
+use nu_utils::{stderr_write_all_and_flush, stdout_write_all_and_flush};
+
+/// A structured format to write the result of `nu -c` or of a script in, instead of a table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Nuon,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(OutputFormat::Json),
+            "nuon" => Some(OutputFormat::Nuon),
+            _ => None,
+        }
+    }
+
+    fn command(&self) -> &'static [u8] {
+        match self {
+            OutputFormat::Json => b"to json",
+            OutputFormat::Nuon => b"to nuon",
+        }
+    }
+}
+
+/// Reports an error on stderr as a single line of JSON, with its message, code, help and the
+/// source of its labels, so programs running nu can tell it apart from the output
+pub fn report_error_structured(
+    working_set: &StateWorkingSet,
+    error: &(dyn miette::Diagnostic + Send + Sync + 'static),
+) {
+    let error = Diagnostic::new(working_set, error).to_json();
+
+    let _ = stderr_write_all_and_flush(format!("{error}\n"));
+}
+
+// Errors are always reported as JSON when a structured output is asked for, even for nuon,
+// since that is what other programs can read
+pub(crate) fn report_error_for(
+    output_format: Option<OutputFormat>,
+    working_set: &StateWorkingSet,
+    error: &(dyn miette::Diagnostic + Send + Sync + 'static),
+) {
+    match output_format {
+        Some(_) => report_error_structured(working_set, error),
+        None => report_error(working_set, error),
+    }
+}
+
+/// Writes the result of an evaluation on stdout in the given format, and returns the exit code
+/// of the external command that produced it, if any. Nothing is written when there is no result
+/// at all, like for a script that only defines commands.
+pub(crate) fn print_structured(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    mut pipeline_data: PipelineData,
+    format: OutputFormat,
+) -> Result<Option<i64>, ShellError> {
+    let exit_code = match &mut pipeline_data {
+        PipelineData::ExternalStream { exit_code, .. } => exit_code.take(),
+        _ => None,
+    };
+
+    if !matches!(pipeline_data, PipelineData::Empty) {
+        let value = pipeline_data.into_value(Span::unknown());
+        if let Value::Error { error } = value {
+            return Err(error);
+        }
+
+        let decl_id = engine_state
+            .find_decl(format.command(), &[])
+            .ok_or(ShellError::CommandNotFound(Span::unknown()))?;
+        let output = engine_state
+            .get_decl(decl_id)
+            .run(
+                engine_state,
+                stack,
+                &Call::new(Span::unknown()),
+                value.into_pipeline_data(),
+            )?
+            .collect_string("", engine_state.get_config())?;
+
+        let _ = stdout_write_all_and_flush(output + "\n").map_err(|err| eprintln!("{err}"));
+    }
+
+    let last_exit_code = exit_code.and_then(|exit_code| exit_code.into_iter().last());
+    Ok(match last_exit_code {
+        Some(Value::Int { val, .. }) => Some(val),
+        _ => None,
+    })
+}
+
+/// Like eval_source, but writes the result in the given format and reports errors as JSON
+pub(crate) fn eval_source_structured(
+    engine_state: &mut EngineState,
+    stack: &mut Stack,
+    source: &[u8],
+    fname: &str,
+    input: PipelineData,
+    format: OutputFormat,
+) -> bool {
+    let (block, delta) = {
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let (output, err) = parse(&mut working_set, Some(fname), source, false, &[]);
+        if let Some(err) = err {
+            set_last_exit_code(stack, 1);
+            report_error_structured(&working_set, &err);
+            return false;
+        }
+
+        (output, working_set.render())
+    };
+
+    if let Err(err) = engine_state.merge_delta(delta) {
+        set_last_exit_code(stack, 1);
+        report_error_structured(&StateWorkingSet::new(engine_state), &err);
+        return false;
+    }
+
+    let result = eval_block_with_early_return(engine_state, stack, &block, input, false, false)
+        .and_then(|pipeline_data| print_structured(engine_state, stack, pipeline_data, format));
+    run_signal_traps(engine_state, stack);
+
+    match result {
+        Ok(exit_code) => {
+            set_last_exit_code(stack, exit_code.unwrap_or(0));
+            true
+        }
+        Err(err) => {
+            set_last_exit_code(stack, 1);
+            report_error_structured(&StateWorkingSet::new(engine_state), &err);
+            false
+        }
+    }
+}
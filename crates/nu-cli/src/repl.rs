@@ -3,7 +3,7 @@ use crate::{
     prompt_update,
     reedline_config::{add_menus, create_keybindings, KeybindingsMode},
     util::{eval_source, get_guaranteed_cwd, report_error, report_error_new},
-    NuHighlighter, NuValidator, NushellPrompt,
+    NuHighlighter, NuHinter, NuValidator, NushellPrompt,
 };
 use crossterm::cursor::CursorShape;
 use log::{trace, warn};
@@ -15,11 +15,11 @@ use nu_protocol::{
     ast::PathMember,
     config::NuCursorShape,
     engine::{EngineState, ReplOperation, Stack, StateWorkingSet},
-    format_duration, BlockId, HistoryFileFormat, PipelineData, PositionalArg, ShellError, Span,
-    Spanned, Type, Value, VarId,
+    format_duration, BlockId, HintSource, HistoryFileFormat, PipelineData, PositionalArg,
+    ShellError, Span, Spanned, Type, Value, VarId,
 };
 use nu_utils::utils::perf;
-use reedline::{CursorConfig, DefaultHinter, EditCommand, Emacs, SqliteBackedHistory, Vi};
+use reedline::{CursorConfig, EditCommand, Emacs, SqliteBackedHistory, Vi};
 use std::{
     io::{self, Write},
     sync::atomic::Ordering,
@@ -256,7 +256,10 @@ pub fn evaluate_repl(
             .with_quick_completions(config.quick_completions)
             .with_partial_completions(config.partial_completions)
             .with_ansi_colors(config.use_ansi_coloring)
-            .with_cursor_config(cursor_config);
+            .with_cursor_config(cursor_config)
+            // A paste comes in as a single edit, instead of one keystroke (and highlight) per
+            // character
+            .use_bracketed_paste(cfg!(not(target_os = "windows")) && config.bracketed_paste);
         perf(
             "reedline builder",
             start_time,
@@ -273,7 +276,17 @@ pub fn evaluate_repl(
             line_editor.with_hinter(Box::new({
                 // As of Nov 2022, "hints" color_config closures only get `null` passed in.
                 let style = style_computer.compute("hints", &Value::nothing(Span::unknown()));
-                DefaultHinter::default().with_style(style)
+                let completer = config
+                    .hint_sources
+                    .contains(&HintSource::Completions)
+                    .then(|| NuCompleter::new(engine_reference.clone(), stack.clone()));
+                NuHinter::new(
+                    style,
+                    config.hint_sources.clone(),
+                    StateWorkingSet::new(engine_state).get_cwd(),
+                    engine_state.hint_cycle.clone(),
+                    completer,
+                )
             }))
         } else {
             line_editor.disable_hints()
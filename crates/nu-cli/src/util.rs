@@ -11,6 +11,7 @@ use nu_protocol::{
 use nu_utils::enable_vt_processing;
 use nu_utils::utils::perf;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 
 // This will collect environment variables from std::env and adds them to a stack.
 //
@@ -267,6 +268,7 @@ pub fn eval_source(
                     let working_set = StateWorkingSet::new(engine_state);
 
                     report_error(&working_set, &err);
+                    run_signal_traps(engine_state, stack);
 
                     return false;
                 }
@@ -287,10 +289,12 @@ pub fn eval_source(
             let working_set = StateWorkingSet::new(engine_state);
 
             report_error(&working_set, &err);
+            run_signal_traps(engine_state, stack);
 
             return false;
         }
     }
+    run_signal_traps(engine_state, stack);
     perf(
         &format!("eval_source {}", &fname),
         start_time,
@@ -303,7 +307,43 @@ pub fn eval_source(
     true
 }
 
-fn set_last_exit_code(stack: &mut Stack, exit_code: i64) {
+/// Run the handlers registered with `signal trap` for the signals received since the last check.
+///
+/// The ctrl-c flag is reset first, so the handlers themselves are not interrupted.
+pub fn run_signal_traps(engine_state: &mut EngineState, stack: &mut Stack) {
+    let triggered: Vec<(String, Value)> = match engine_state.signal_traps.lock() {
+        Ok(traps) => traps
+            .iter()
+            .filter(|(_, trap)| trap.received.swap(false, Ordering::SeqCst))
+            .map(|(name, trap)| (name.clone(), trap.handler.clone()))
+            .collect(),
+        Err(_) => return,
+    };
+
+    if triggered.is_empty() {
+        return;
+    }
+
+    if let Some(ctrlc) = &engine_state.ctrlc {
+        ctrlc.store(false, Ordering::SeqCst);
+    }
+
+    for (name, handler) in triggered {
+        if let Value::Closure { captures, .. } = &handler {
+            for (var_id, value) in captures {
+                stack.add_var(*var_id, value.clone());
+            }
+        }
+
+        let span = handler.span().unwrap_or_else(|_| Span::unknown());
+        let arguments = vec![("signal".to_string(), Value::string(name, span))];
+        if let Err(err) = eval_hook(engine_state, stack, None, arguments, &handler) {
+            report_error_new(engine_state, &err);
+        }
+    }
+}
+
+pub(crate) fn set_last_exit_code(stack: &mut Stack, exit_code: i64) {
     stack.add_env_var(
         "LAST_EXIT_CODE".to_string(),
         Value::int(exit_code, Span::unknown()),
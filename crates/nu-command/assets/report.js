@@ -0,0 +1,51 @@
+// Sorts a table of a report by the column of a clicked header, and hides the rows of a table
+// that don't contain the text typed in the filter above it
+(function () {
+  function cellText(row, index) {
+    var cell = row.children[index];
+    return cell ? cell.textContent.trim() : "";
+  }
+
+  function compare(a, b) {
+    var x = parseFloat(a);
+    var y = parseFloat(b);
+    if (!isNaN(x) && !isNaN(y) && String(x) === a && String(y) === b) {
+      return x - y;
+    }
+    return a.localeCompare(b, undefined, { numeric: true });
+  }
+
+  document.querySelectorAll("table.nu-sortable").forEach(function (table) {
+    var headers = table.tHead.rows[0].cells;
+    Array.prototype.forEach.call(headers, function (header, index) {
+      header.addEventListener("click", function () {
+        var ascending = header.getAttribute("aria-sort") !== "ascending";
+        Array.prototype.forEach.call(headers, function (other) {
+          other.removeAttribute("aria-sort");
+        });
+        header.setAttribute("aria-sort", ascending ? "ascending" : "descending");
+
+        var body = table.tBodies[0];
+        var rows = Array.prototype.slice.call(body.rows);
+        rows.sort(function (a, b) {
+          var order = compare(cellText(a, index), cellText(b, index));
+          return ascending ? order : -order;
+        });
+        rows.forEach(function (row) {
+          body.appendChild(row);
+        });
+      });
+    });
+  });
+
+  document.querySelectorAll("input.nu-filter").forEach(function (input) {
+    var table = input.nextElementSibling;
+    input.addEventListener("input", function () {
+      var text = input.value.toLowerCase();
+      Array.prototype.forEach.call(table.tBodies[0].rows, function (row) {
+        var shown = row.textContent.toLowerCase().indexOf(text) !== -1;
+        row.style.display = shown ? "" : "none";
+      });
+    });
+  });
+})();
@@ -1,4 +1,5 @@
-use crate::input_handler::{operate, CellPathOnlyArgs};
+use super::lenient::lenient_boolean;
+use crate::input_handler::{operate, CmdArgument};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
@@ -9,6 +10,17 @@ use nu_protocol::{
 #[derive(Clone)]
 pub struct SubCommand;
 
+struct Arguments {
+    lenient: bool,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
 impl Command for SubCommand {
     fn name(&self) -> &str {
         "into bool"
@@ -23,6 +35,11 @@ impl Command for SubCommand {
                 (Type::Bool, Type::Bool),
                 (Type::List(Box::new(Type::Any)), Type::Table(vec![])),
             ])
+            .switch(
+                "lenient",
+                "also accept yes, no, on, off, y, n, t and f",
+                Some('l'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -111,6 +128,18 @@ impl Command for SubCommand {
                 example: "'true' | into bool",
                 result: Some(Value::boolean(true, span)),
             },
+            Example {
+                description: "convert answers from a form to booleans",
+                example: "['Yes' 'off' 'n'] | into bool --lenient",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::boolean(true, span),
+                        Value::boolean(false, span),
+                        Value::boolean(false, span),
+                    ],
+                    span,
+                }),
+            },
         ]
     }
 }
@@ -122,11 +151,14 @@ fn into_bool(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
-    let args = CellPathOnlyArgs::from(cell_paths);
+    let args = Arguments {
+        lenient: call.has_flag("lenient"),
+        cell_paths: (!cell_paths.is_empty()).then_some(cell_paths),
+    };
     operate(action, args, input, call.head, engine_state.ctrlc.clone())
 }
 
-fn string_to_boolean(s: &str, span: Span) -> Result<bool, ShellError> {
+pub(crate) fn string_to_boolean(s: &str, span: Span) -> Result<bool, ShellError> {
     match s.trim().to_lowercase().as_str() {
         "true" => Ok(true),
         "false" => Ok(false),
@@ -148,7 +180,7 @@ fn string_to_boolean(s: &str, span: Span) -> Result<bool, ShellError> {
     }
 }
 
-fn action(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
+fn action(input: &Value, args: &Arguments, span: Span) -> Value {
     match input {
         Value::Bool { .. } => input.clone(),
         Value::Int { val, .. } => Value::Bool {
@@ -159,10 +191,17 @@ fn action(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
             val: val.abs() >= f64::EPSILON,
             span,
         },
-        Value::String { val, .. } => match string_to_boolean(val, span) {
-            Ok(val) => Value::Bool { val, span },
-            Err(error) => Value::Error { error },
-        },
+        Value::String { val, .. } => {
+            let lenient = if args.lenient {
+                lenient_boolean(val)
+            } else {
+                None
+            };
+            match lenient.map_or_else(|| string_to_boolean(val, span), Ok) {
+                Ok(val) => Value::Bool { val, span },
+                Err(error) => Value::Error { error },
+            }
+        }
         // Propagate errors by explicitly matching them before the final case.
         Value::Error { .. } => input.clone(),
         other => Value::Error {
@@ -1,4 +1,5 @@
-use crate::input_handler::{operate, CellPathOnlyArgs};
+use super::lenient::lenient_number;
+use crate::input_handler::{operate, CmdArgument};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
@@ -9,6 +10,17 @@ use nu_protocol::{
 #[derive(Clone)]
 pub struct SubCommand;
 
+struct Arguments {
+    lenient: bool,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
 impl Command for SubCommand {
     fn name(&self) -> &str {
         "into decimal"
@@ -20,6 +32,11 @@ impl Command for SubCommand {
                 (Type::String, Type::Number),
                 (Type::Bool, Type::Number),
             ])
+            .switch(
+                "lenient",
+                "ignore currency symbols and thousands separators, and accept decimal commas",
+                Some('l'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -43,7 +60,10 @@ impl Command for SubCommand {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
-        let args = CellPathOnlyArgs::from(cell_paths);
+        let args = Arguments {
+            lenient: call.has_flag("lenient"),
+            cell_paths: (!cell_paths.is_empty()).then_some(cell_paths),
+        };
         operate(action, args, input, call.head, engine_state.ctrlc.clone())
     }
 
@@ -71,6 +91,18 @@ impl Command for SubCommand {
                 example: "'-5.9' | into decimal",
                 result: Some(Value::test_float(-5.9)),
             },
+            Example {
+                description: "Convert prices written for people to decimals",
+                example: "['1.234,50 €' '$ 1,234.50' '(0.5)'] | into decimal --lenient",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_float(1234.5),
+                        Value::test_float(1234.5),
+                        Value::test_float(-0.5),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 description: "Convert boolean to decimal",
                 example: "true | into decimal",
@@ -80,10 +112,14 @@ impl Command for SubCommand {
     }
 }
 
-fn action(input: &Value, _args: &CellPathOnlyArgs, head: Span) -> Value {
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     match input {
         Value::String { val: s, span } => {
-            let other = s.trim();
+            let other = if args.lenient {
+                lenient_number(s)
+            } else {
+                s.trim().to_string()
+            };
 
             match other.parse::<f64>() {
                 Ok(x) => Value::float(x, head),
@@ -137,7 +173,14 @@ mod tests {
         let word = Value::test_string("3.1415");
         let expected = Value::test_float(3.1415);
 
-        let actual = action(&word, &CellPathOnlyArgs::from(vec![]), Span::test_data());
+        let actual = action(
+            &word,
+            &Arguments {
+                lenient: false,
+                cell_paths: None,
+            },
+            Span::test_data(),
+        );
         assert_eq!(actual, expected);
     }
 
@@ -147,7 +190,10 @@ mod tests {
 
         let actual = action(
             &decimal_str,
-            &CellPathOnlyArgs::from(vec![]),
+            &Arguments {
+                lenient: false,
+                cell_paths: None,
+            },
             Span::test_data(),
         );
 
@@ -160,7 +206,10 @@ mod tests {
         let expected = Value::test_float(10.0);
         let actual = action(
             &decimal_str,
-            &CellPathOnlyArgs::from(vec![]),
+            &Arguments {
+                lenient: false,
+                cell_paths: None,
+            },
             Span::test_data(),
         );
 
@@ -7,6 +7,9 @@ use nu_protocol::{
     Value,
 };
 
+const NS_PER_SEC: i128 = 1_000_000_000;
+const NS_PER_DAY: i128 = 24 * 60 * 60 * NS_PER_SEC;
+
 #[derive(Clone)]
 pub struct SubCommand;
 
@@ -43,7 +46,10 @@ impl Command for SubCommand {
     }
 
     fn extra_usage(&self) -> &str {
-        "This command does not take leap years into account, and every month is assumed to have 30 days."
+        r#"Strings can have several parts, like "1hr 30min" or "2 days, 4 hours", with short or long
+unit names and decimals like "1.5hr". They can also be ISO 8601 durations like "PT1H30M".
+
+This command does not take leap years into account, and every month is assumed to have 30 days."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -121,6 +127,22 @@ impl Command for SubCommand {
                     span,
                 }),
             },
+            Example {
+                description: "Convert a string with several units to duration",
+                example: "'1hr 30min' | into duration",
+                result: Some(Value::Duration {
+                    val: 90 * 60 * 1000 * 1000 * 1000,
+                    span,
+                }),
+            },
+            Example {
+                description: "Convert an ISO 8601 duration",
+                example: "'P1DT12H' | into duration",
+                result: Some(Value::Duration {
+                    val: 36 * 60 * 60 * 1000 * 1000 * 1000,
+                    span,
+                }),
+            },
             Example {
                 description: "Convert string to the requested duration as a string",
                 example: "'7min' | into duration --convert sec",
@@ -329,7 +351,7 @@ fn convert_str_from_unit_to_unit(
     }
 }
 
-fn string_to_duration(s: &str, span: Span, value_span: Span) -> Result<i64, ShellError> {
+pub(crate) fn string_to_duration(s: &str, span: Span, value_span: Span) -> Result<i64, ShellError> {
     if let Some(expression) = parse_duration_bytes(s.as_bytes(), span) {
         if let Expr::ValueWithUnit(value, unit) = expression.expr {
             if let Expr::Int(x) = value.expr {
@@ -347,6 +369,9 @@ fn string_to_duration(s: &str, span: Span, value_span: Span) -> Result<i64, Shel
             }
         }
     }
+    if let Some(nanos) = parse_compound_duration(s) {
+        return Ok(nanos);
+    }
 
     Err(ShellError::CantConvertWithValue(
         "duration".to_string(),
@@ -354,12 +379,146 @@ fn string_to_duration(s: &str, span: Span, value_span: Span) -> Result<i64, Shel
         s.to_string(),
         span,
         value_span,
-        Some(
-            "supported units are ns, us, ms, sec, min, hr, day, wk, month, yr and dec".to_string(),
-        ),
+        Some("use a duration like 7min, 1hr 30min, 2 days 4 hours or PT1H30M".to_string()),
     ))
 }
 
+/// Parse a duration made of several numbers with units, like `1hr 30min` or `2 days, 4 hours`,
+/// or written in ISO 8601, like `PT1H30M`. The numbers can have decimals.
+fn parse_compound_duration(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, s),
+    };
+    let nanos = match s.strip_prefix(['P', 'p']) {
+        Some(iso) => parse_iso_duration(iso)?,
+        None => parse_human_duration(s)?,
+    };
+    let nanos = if negative { -nanos } else { nanos };
+    i64::try_from(nanos).ok()
+}
+
+fn parse_human_duration(s: &str) -> Option<i128> {
+    let mut rest = s;
+    let mut total = 0;
+    let mut components = 0;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if let Some(after) = rest.strip_prefix("and ") {
+            rest = after;
+            continue;
+        }
+        if rest.is_empty() {
+            break;
+        }
+        let (number, after) = split_number(rest)?;
+        let after = after.trim_start();
+        let unit_len = after
+            .find(|c: char| !(c.is_alphabetic() || c == 'µ'))
+            .unwrap_or(after.len());
+        let unit = unit_nanos(&after[..unit_len].to_lowercase())?;
+        total += scale(number, unit)?;
+        components += 1;
+        rest = &after[unit_len..];
+    }
+    (components > 0).then_some(total)
+}
+
+fn parse_iso_duration(s: &str) -> Option<i128> {
+    let (date, time) = match s.split_once(['T', 't']) {
+        Some((_, "")) => return None,
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+    let mut total = 0;
+    let mut components = 0;
+    for (part, units) in [
+        (
+            date,
+            &[
+                ('y', 365 * NS_PER_DAY),
+                ('m', 30 * NS_PER_DAY),
+                ('w', 7 * NS_PER_DAY),
+                ('d', NS_PER_DAY),
+            ][..],
+        ),
+        (
+            time.unwrap_or(""),
+            &[
+                ('h', 3600 * NS_PER_SEC),
+                ('m', 60 * NS_PER_SEC),
+                ('s', NS_PER_SEC),
+            ][..],
+        ),
+    ] {
+        let mut rest = part;
+        let mut units = units.iter();
+        while !rest.is_empty() {
+            let (number, after) = split_number(rest)?;
+            let designator = after.chars().next()?.to_ascii_lowercase();
+            // The designators have to come in order, each at most once
+            let (_, unit) = units.find(|(d, _)| *d == designator)?;
+            total += scale(number, *unit)?;
+            components += 1;
+            rest = &after[1..];
+        }
+    }
+    (components > 0).then_some(total)
+}
+
+/// Split the digits of a number, with an optional decimal part, from the start of a string
+fn split_number(s: &str) -> Option<((&str, &str), &str)> {
+    let int_len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let rest = &s[int_len..];
+    let (fraction, rest) = match rest.strip_prefix(['.', ',']) {
+        Some(after) => {
+            let len = after
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(after.len());
+            (&after[..len], &after[len..])
+        }
+        None => ("", rest),
+    };
+    if int_len == 0 && fraction.is_empty() {
+        return None;
+    }
+    Some(((&s[..int_len], fraction), rest))
+}
+
+/// Multiply a number with an optional decimal part by the nanoseconds of a unit
+fn scale((integer, fraction): (&str, &str), unit: i128) -> Option<i128> {
+    let integer: i128 = if integer.is_empty() {
+        0
+    } else {
+        integer.parse().ok()?
+    };
+    let mut nanos = integer.checked_mul(unit)?;
+    // Digits beyond a nanosecond don't matter
+    let fraction = &fraction[..fraction.len().min(18)];
+    if !fraction.is_empty() {
+        let digits: i128 = fraction.parse().ok()?;
+        nanos += digits * unit / 10i128.pow(fraction.len() as u32);
+    }
+    Some(nanos)
+}
+
+fn unit_nanos(unit: &str) -> Option<i128> {
+    Some(match unit {
+        "ns" | "nanosecond" | "nanoseconds" => 1,
+        "us" | "µs" | "microsecond" | "microseconds" => 1_000,
+        "ms" | "millisecond" | "milliseconds" => 1_000_000,
+        "s" | "sec" | "secs" | "second" | "seconds" => NS_PER_SEC,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60 * NS_PER_SEC,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3600 * NS_PER_SEC,
+        "d" | "day" | "days" => NS_PER_DAY,
+        "w" | "wk" | "wks" | "week" | "weeks" => 7 * NS_PER_DAY,
+        "month" | "months" => 30 * NS_PER_DAY,
+        "y" | "yr" | "yrs" | "year" | "years" => 365 * NS_PER_DAY,
+        _ => return None,
+    })
+}
+
 fn string_to_unit_duration(
     s: &str,
     span: Span,
@@ -383,6 +542,9 @@ fn string_to_unit_duration(
             }
         }
     }
+    if let Some(nanos) = parse_compound_duration(s) {
+        return Ok(("ns", nanos));
+    }
 
     Err(ShellError::CantConvertWithValue(
         "duration".to_string(),
@@ -603,6 +765,16 @@ mod test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn turns_compound_strings_to_duration() {
+        let hour = 60 * 60 * 1000 * 1000 * 1000;
+        assert_eq!(parse_compound_duration("2 days, 4 hours"), Some(52 * hour));
+        assert_eq!(parse_compound_duration("1.5h"), Some(hour * 3 / 2));
+        assert_eq!(parse_compound_duration("-PT0.5H"), Some(-hour / 2));
+        assert_eq!(parse_compound_duration("PT1M1H"), None);
+        assert_eq!(parse_compound_duration("10"), None);
+    }
+
     #[test]
     fn turns_wk_to_duration() {
         let span = Span::new(0, 2);
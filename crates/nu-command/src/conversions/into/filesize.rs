@@ -130,7 +130,7 @@ pub fn action(input: &Value, _args: &CellPathOnlyArgs, span: Span) -> Value {
         input.clone()
     }
 }
-fn int_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
+pub(crate) fn int_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
     match a_string.trim().parse::<bytesize::ByteSize>() {
         Ok(n) => Ok(n.0 as i64),
         Err(_) => Err(ShellError::CantConvert(
@@ -1,3 +1,4 @@
+use super::lenient::lenient_number;
 use crate::input_handler::{operate, CmdArgument};
 use nu_engine::CallExt;
 use nu_protocol::{
@@ -10,6 +11,7 @@ struct Arguments {
     radix: u32,
     cell_paths: Option<Vec<CellPath>>,
     little_endian: bool,
+    lenient: bool,
 }
 
 impl CmdArgument for Arguments {
@@ -40,6 +42,11 @@ impl Command for SubCommand {
             .vectorizes_over_list(true)
             .named("radix", SyntaxShape::Number, "radix of integer", Some('r'))
             .switch("little-endian", "use little-endian byte decoding", None)
+            .switch(
+                "lenient",
+                "ignore currency symbols and thousands separators in base 10 strings",
+                Some('l'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -83,6 +90,7 @@ impl Command for SubCommand {
         let args = Arguments {
             radix,
             little_endian: call.has_flag("little-endian"),
+            lenient: call.has_flag("lenient"),
             cell_paths,
         };
         operate(action, args, input, call.head, engine_state.ctrlc.clone())
@@ -100,6 +108,11 @@ impl Command for SubCommand {
                 example: "'2' | into int",
                 result: Some(Value::test_int(2)),
             },
+            Example {
+                description: "Convert an amount of money written for people to integer",
+                example: "'$ 1,234,567' | into int --lenient",
+                result: Some(Value::test_int(1234567)),
+            },
             Example {
                 description: "Convert decimal to integer",
                 example: "5.9 | into int",
@@ -202,7 +215,12 @@ fn action(input: &Value, args: &Arguments, span: Span) -> Value {
         },
         Value::String { val, .. } => {
             if radix == 10 {
-                match int_from_string(val, span) {
+                let val = if args.lenient {
+                    lenient_number(val)
+                } else {
+                    val.clone()
+                };
+                match int_from_string(&val, span) {
                     Ok(val) => Value::Int { val, span },
                     Err(error) => Value::Error { error },
                 }
@@ -311,7 +329,7 @@ fn convert_int(input: &Value, head: Span, radix: u32) -> Value {
     }
 }
 
-fn int_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
+pub(crate) fn int_from_string(a_string: &str, span: Span) -> Result<i64, ShellError> {
     let trimmed = a_string.trim();
     match trimmed {
         b if b.starts_with("0b") => {
@@ -399,6 +417,7 @@ mod test {
                 radix: 10,
                 cell_paths: None,
                 little_endian: false,
+                lenient: false,
             },
             Span::test_data(),
         );
@@ -414,6 +433,7 @@ mod test {
                 radix: 10,
                 cell_paths: None,
                 little_endian: false,
+                lenient: false,
             },
             Span::test_data(),
         );
@@ -429,6 +449,7 @@ mod test {
                 radix: 16,
                 cell_paths: None,
                 little_endian: false,
+                lenient: false,
             },
             Span::test_data(),
         );
@@ -445,6 +466,7 @@ mod test {
                 radix: 10,
                 cell_paths: None,
                 little_endian: false,
+                lenient: false,
             },
             Span::test_data(),
         );
@@ -0,0 +1,87 @@
+//! Cleaning of human-entered values for the --lenient flags of the into commands
+
+const CURRENCY_SYMBOLS: &[char] = &[
+    '$', '€', '£', '¥', '₩', '₹', '₽', '¢', '₺', '₴', '₦', '₱', '฿', '₫', '₪',
+];
+
+/// The words that mean true or false, besides numbers
+pub(super) fn lenient_boolean(s: &str) -> Option<bool> {
+    match s.trim().to_lowercase().as_str() {
+        "true" | "t" | "yes" | "y" | "on" => Some(true),
+        "false" | "f" | "no" | "n" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Rewrite a number like `$ 1,234.50`, `1.234,5 €` or `(12)` as `1234.50`, `1234.5` or `-12`
+///
+/// Currency symbols, spaces, apostrophes and underscores are removed. When a number has both
+/// commas and dots, the last of them is the decimal separator. Several commas or several dots
+/// separate thousands. A single comma is a decimal comma unless exactly three digits follow it.
+pub(super) fn lenient_number(s: &str) -> String {
+    let s = s.trim();
+    let (negative, s) = match s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, s),
+    };
+    let cleaned: String = s
+        .chars()
+        .filter(|c| {
+            !(c.is_whitespace() || CURRENCY_SYMBOLS.contains(c) || matches!(c, '\'' | '’' | '_'))
+        })
+        .collect();
+
+    let commas = cleaned.matches(',').count();
+    let dots = cleaned.matches('.').count();
+    let decimal = match (commas, dots) {
+        (0, _) if dots > 1 => None,
+        (0, _) => Some('.'),
+        (_, 0) if commas > 1 => None,
+        (_, 0) => {
+            let after = cleaned.rsplit(',').next().unwrap_or_default();
+            let digits = after.chars().take_while(char::is_ascii_digit).count();
+            (digits != 3).then_some(',')
+        }
+        _ => {
+            let last_comma = cleaned.rfind(',');
+            let last_dot = cleaned.rfind('.');
+            Some(if last_comma > last_dot { ',' } else { '.' })
+        }
+    };
+
+    let mut number = String::new();
+    if negative {
+        number.push('-');
+    }
+    for c in cleaned.chars() {
+        match c {
+            ',' | '.' if Some(c) == decimal => number.push('.'),
+            ',' | '.' => {}
+            c => number.push(c),
+        }
+    }
+    number
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn separators() {
+        assert_eq!(lenient_number("1,234,567"), "1234567");
+        assert_eq!(lenient_number("1.234.567"), "1234567");
+        assert_eq!(lenient_number("1,234.5"), "1234.5");
+        assert_eq!(lenient_number("1.234,5"), "1234.5");
+        assert_eq!(lenient_number("3,14"), "3.14");
+        assert_eq!(lenient_number("1,234"), "1234");
+        assert_eq!(lenient_number("1 234'567_8"), "12345678");
+    }
+
+    #[test]
+    fn currencies_and_parentheses() {
+        assert_eq!(lenient_number(" $ 12.50 "), "12.50");
+        assert_eq!(lenient_number("1.234,00 €"), "1234.00");
+        assert_eq!(lenient_number("(£42)"), "-42");
+    }
+}
@@ -6,8 +6,10 @@ mod decimal;
 mod duration;
 mod filesize;
 mod int;
+mod lenient;
 mod record;
 mod string;
+mod unit;
 
 pub use self::bool::SubCommand as IntoBool;
 pub use self::filesize::SubCommand as IntoFilesize;
@@ -19,3 +21,9 @@ pub use duration::SubCommand as IntoDuration;
 pub use int::SubCommand as IntoInt;
 pub use record::SubCommand as IntoRecord;
 pub use string::SubCommand as IntoString;
+pub use unit::SubCommand as IntoUnit;
+
+pub(crate) use self::bool::string_to_boolean;
+pub(crate) use duration::string_to_duration;
+pub(crate) use filesize::int_from_string as filesize_from_string;
+pub(crate) use int::int_from_string;
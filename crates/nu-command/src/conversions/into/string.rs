@@ -1,17 +1,22 @@
 use crate::input_handler::{operate, CmdArgument};
+use crate::{format_number, parse_locale, NumberFormat, NumberStyle};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
     into_code, Category, Config, Example, IntoPipelineData, PipelineData, ShellError, Signature,
-    Span, SyntaxShape, Type, Value,
+    Span, Spanned, SyntaxShape, Type, Value,
 };
 use nu_utils::get_system_locale;
-use num_format::ToFormattedString;
+use num_format::{Locale, ToFormattedString};
 
 struct Arguments {
     decimals_value: Option<i64>,
     decimals: bool,
+    group: bool,
+    locale: Locale,
+    // Whether --locale or --group asks to write decimals for people to read
+    localized: bool,
     cell_paths: Option<Vec<CellPath>>,
     config: Config,
 }
@@ -53,6 +58,17 @@ impl Command for SubCommand {
                 "decimal digits to which to round",
                 Some('d'),
             )
+            .switch(
+                "group",
+                "separate groups of digits of numbers, like thousands",
+                Some('g'),
+            )
+            .named(
+                "locale",
+                SyntaxShape::String,
+                "the locale whose separators to use for numbers, like de-DE",
+                Some('l'),
+            )
             .category(Category::Conversions)
     }
 
@@ -108,6 +124,11 @@ impl Command for SubCommand {
                 //     ),
                 // }),
             },
+            Example {
+                description: "convert a number to a string with the separators of a locale",
+                example: "1234567.5 | into string --group --locale de -d 2",
+                result: Some(Value::test_string("1.234.567,50")),
+            },
             Example {
                 description: "convert decimal to string",
                 example: "4.3 | into string",
@@ -160,12 +181,22 @@ fn string_helper(
             ));
         }
     }
+    let group = call.has_flag("group");
+    let locale: Option<Spanned<String>> = call.get_flag(engine_state, stack, "locale")?;
+    let localized = group || locale.is_some();
+    let locale = match locale {
+        Some(locale) => parse_locale(&locale)?,
+        None => get_system_locale(),
+    };
     let cell_paths = call.rest(engine_state, stack, 0)?;
     let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
     let config = engine_state.get_config().clone();
     let args = Arguments {
         decimals_value,
         decimals,
+        group,
+        locale,
+        localized,
         cell_paths,
         config,
     };
@@ -199,9 +230,21 @@ fn action(input: &Value, args: &Arguments, span: Span) -> Value {
     match input {
         Value::Int { val, .. } => {
             let decimal_value = digits.unwrap_or(0) as usize;
-            let res = format_int(*val, false, decimal_value);
+            let res = format_int(*val, args.group, decimal_value, &args.locale);
             Value::String { val: res, span }
         }
+        Value::Float { .. } if args.localized => {
+            let format = NumberFormat {
+                locale: args.locale,
+                precision: decimals.then(|| digits.unwrap_or(2) as usize),
+                group: args.group,
+                style: NumberStyle::Decimal,
+            };
+            Value::String {
+                val: format_number(input, &format).unwrap_or_default(),
+                span,
+            }
+        }
         Value::Float { val, .. } => {
             if decimals {
                 let decimal_value = digits.unwrap_or(2) as usize;
@@ -272,11 +315,9 @@ fn action(input: &Value, args: &Arguments, span: Span) -> Value {
     }
 }
 
-fn format_int(int: i64, group_digits: bool, decimals: usize) -> String {
-    let locale = get_system_locale();
-
+fn format_int(int: i64, group_digits: bool, decimals: usize, locale: &Locale) -> String {
     let str = if group_digits {
-        int.to_formatted_string(&locale)
+        int.to_formatted_string(locale)
     } else {
         int.to_string()
     };
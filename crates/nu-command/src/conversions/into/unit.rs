@@ -0,0 +1,154 @@
+use crate::input_handler::{operate, CmdArgument};
+use crate::units::{convert_value, unit_named, Unit};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
+};
+
+struct Arguments {
+    from: Option<&'static Unit>,
+    to: &'static Unit,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "into unit"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("into unit")
+            .input_output_types(vec![
+                (Type::String, Type::Float),
+                (Type::Number, Type::Float),
+                (Type::Filesize, Type::Float),
+                (Type::Duration, Type::Float),
+                (Type::Table(vec![]), Type::Table(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
+            .required("unit", SyntaxShape::String, "the unit to convert to")
+            .named(
+                "from",
+                SyntaxShape::String,
+                "the unit of bare numbers",
+                Some('f'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "for a data structure input, convert data at the given cell paths",
+            )
+            .category(Category::Conversions)
+    }
+
+    fn usage(&self) -> &str {
+        "Convert quantities with a unit, like '1.5km', into a number of another unit."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "File sizes and durations convert too. Run `math units` to list the units."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["convert", "measure", "quantity"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let to: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let from: Option<Spanned<String>> = call.get_flag(engine_state, stack, "from")?;
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let args = Arguments {
+            from: match from {
+                Some(from) => Some(unit_named(&from)?),
+                None => None,
+            },
+            to: unit_named(&to)?,
+            cell_paths: (!cell_paths.is_empty()).then_some(cell_paths),
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Convert a distance to meters",
+                example: "'1.5km' | into unit m",
+                result: Some(Value::test_float(1500.0)),
+            },
+            Example {
+                description: "Convert a number in meters to kilometers",
+                example: "1500 | into unit km --from m",
+                result: Some(Value::test_float(1.5)),
+            },
+            Example {
+                description: "Convert a file size to kibibytes",
+                example: "3MiB | into unit KiB",
+                result: Some(Value::test_float(3072.0)),
+            },
+            Example {
+                description: "Convert a duration to hours",
+                example: "90min | into unit h",
+                result: Some(Value::test_float(1.5)),
+            },
+            Example {
+                description: "Convert the weight column of a table to kilograms",
+                example: "[[item weight]; [box '2kg'] [bag '500 g']] | into unit kg weight",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["item", "weight"],
+                            vec![Value::test_string("box"), Value::test_float(2.0)],
+                        ),
+                        Value::test_record(
+                            vec!["item", "weight"],
+                            vec![Value::test_string("bag"), Value::test_float(0.5)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Convert a field of a record to grams",
+                example: "{item: box, weight: '2kg'} | into unit g weight",
+                result: Some(Value::test_record(
+                    vec!["item", "weight"],
+                    vec![Value::test_string("box"), Value::test_float(2000.0)],
+                )),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, span: Span) -> Value {
+    convert_value(input, args.from, args.to, span)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -32,6 +32,11 @@ impl Command for Commandline {
                 "replaces the current contents of the buffer (default)",
                 Some('r'),
             )
+            .switch(
+                "next-hint",
+                "shows the next inline hint for the current buffer",
+                None,
+            )
             .optional(
                 "cmd",
                 SyntaxShape::String,
@@ -55,7 +60,22 @@ impl Command for Commandline {
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        if let Some(cmd) = call.opt::<Value>(engine_state, stack, 0)? {
+        if call.has_flag("next-hint") {
+            let buffer = engine_state
+                .repl_buffer_state
+                .lock()
+                .expect("repl buffer state mutex")
+                .clone()
+                .unwrap_or_default();
+            let mut cycle = engine_state.hint_cycle.lock().expect("hint cycle mutex");
+            if cycle.line == buffer {
+                cycle.offset += 1;
+            } else {
+                cycle.line = buffer;
+                cycle.offset = 1;
+            }
+            Ok(Value::Nothing { span: call.head }.into_pipeline_data())
+        } else if let Some(cmd) = call.opt::<Value>(engine_state, stack, 0)? {
             let mut ops = engine_state
                 .repl_operation_queue
                 .lock()
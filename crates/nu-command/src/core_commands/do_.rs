@@ -117,6 +117,7 @@ impl Command for Do {
                 stdout,
                 stderr,
                 exit_code,
+                resource_usage,
                 span,
                 metadata,
                 trim_end_newline,
@@ -203,6 +204,7 @@ impl Command for Do {
                         exit_code.into_iter(),
                         exit_code_ctrlc,
                     )),
+                    resource_usage,
                     span,
                     metadata,
                     trim_end_newline,
@@ -212,6 +214,7 @@ impl Command for Do {
                 stdout,
                 stderr,
                 exit_code: _,
+                resource_usage,
                 span,
                 metadata,
                 trim_end_newline,
@@ -220,6 +223,7 @@ impl Command for Do {
                     stdout,
                     stderr,
                     exit_code: None,
+                    resource_usage,
                     span,
                     metadata,
                     trim_end_newline,
@@ -89,6 +89,16 @@ impl Command for Metadata {
                             cols.push("source".into());
                             vals.push(Value::string("into html --list", head))
                         }
+                        PipelineMetadata {
+                            data_source: DataSource::Http { content_type },
+                        } => {
+                            cols.push("source".into());
+                            vals.push(Value::string("http", head));
+                            if let Some(content_type) = content_type {
+                                cols.push("content_type".into());
+                                vals.push(Value::string(content_type, head))
+                            }
+                        }
                     }
                 }
 
@@ -154,6 +164,16 @@ fn build_metadata_record(arg: &Value, metadata: &Option<PipelineMetadata>, head:
                 cols.push("source".into());
                 vals.push(Value::string("into html --list", head))
             }
+            PipelineMetadata {
+                data_source: DataSource::Http { content_type },
+            } => {
+                cols.push("source".into());
+                vals.push(Value::string("http", head));
+                if let Some(content_type) = content_type {
+                    cols.push("content_type".into());
+                    vals.push(Value::string(content_type, head))
+                }
+            }
         }
     }
 
@@ -35,6 +35,7 @@ mod module;
 mod mut_;
 pub(crate) mod overlay;
 mod return_;
+mod run_with_limit;
 mod try_;
 mod use_;
 mod version;
@@ -77,6 +78,7 @@ pub use module::Module;
 pub use mut_::Mut;
 pub use overlay::*;
 pub use return_::Return;
+pub use run_with_limit::RunWithLimit;
 pub use try_::Try;
 pub use use_::Use;
 pub use version::Version;
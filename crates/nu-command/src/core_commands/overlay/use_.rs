@@ -44,11 +44,27 @@ impl Command for OverlayUse {
                 "If the overlay already exists, reload its definitions and environment.",
                 Some('r'),
             )
+            .named(
+                "hash",
+                SyntaxShape::String,
+                "the sha256 hash a module from a URL must have",
+                None,
+            )
+            .named(
+                "tag",
+                SyntaxShape::String,
+                "the tag or commit of the git repository a module comes from",
+                None,
+            )
             .category(Category::Core)
     }
 
     fn extra_usage(&self) -> &str {
-        r#"This command is a parser keyword. For details, check:
+        r#"Like with `use`, the module can come from an https URL pinned with --hash, or from a git
+repository written as git+<repository>.git/<file>, pinned with --tag, --hash or both, once
+it is fetched into the cache with `fetch-module`, beforehand or from env.nu.
+
+This command is a parser keyword. For details, check:
   https://www.nushell.sh/book/thinking_in_nu.html"#
     }
 
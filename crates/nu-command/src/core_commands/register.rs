@@ -1,5 +1,5 @@
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type};
 
 #[derive(Clone)]
@@ -14,6 +14,10 @@ impl Command for Register {
         "Register a plugin"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("register")
             .input_output_types(vec![(Type::Nothing, Type::Nothing)])
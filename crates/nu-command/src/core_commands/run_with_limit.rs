@@ -0,0 +1,212 @@
+use nu_engine::{eval_block_with_early_return, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Closure, Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
+    Type, Value,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How often the timeout and ctrl-c are checked while the closure runs
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Clone)]
+pub struct RunWithLimit;
+
+impl Command for RunWithLimit {
+    fn name(&self) -> &str {
+        "run-with-limit"
+    }
+
+    fn usage(&self) -> &str {
+        "Run a closure with limits on its running time, its recursion depth and the size of its result"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The closure is stopped when it runs longer than the timeout, the same way ctrl-c stops it,
+and a limit exceeded error is returned right away. The closure runs on its own thread, which
+finishes the command it is in before it stops, so its side effects up to then are kept.
+External commands it started are not killed.
+
+The recursion depth can only be lowered: --max-depth is capped at the recursion limit of the
+shell."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("run-with-limit")
+            .required("closure", SyntaxShape::Closure(None), "the closure to run")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long the closure can run",
+                Some('t'),
+            )
+            .named(
+                "max-depth",
+                SyntaxShape::Int,
+                "how deep recursive commands can call themselves (default and maximum 50)",
+                Some('d'),
+            )
+            .named(
+                "max-size",
+                SyntaxShape::Filesize,
+                "how big the result of the closure can be",
+                Some('s'),
+            )
+            .category(Category::Core)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["timeout", "limit", "deadline", "sandbox"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let closure: Closure = call.req(engine_state, stack, 0)?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let max_depth: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "max-depth")?;
+        let max_size: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "max-size")?;
+
+        for limit in [&timeout, &max_depth, &max_size].into_iter().flatten() {
+            if limit.item <= 0 {
+                return Err(ShellError::NeedsPositiveValue(limit.span));
+            }
+        }
+
+        // The closure runs on a copy of the engine state, which it checks for ctrl-c on its own
+        // flag, so it can be stopped without stopping the rest of the pipeline
+        let mut closure_engine_state = engine_state.clone();
+        let interrupt = Arc::new(AtomicBool::new(false));
+        closure_engine_state.ctrlc = Some(interrupt.clone());
+        // The closure runs on a thread with a smaller stack than the main one, so the limit
+        // can't be raised past the one of the shell
+        if let Some(max_depth) = &max_depth {
+            closure_engine_state.recursion_limit =
+                (max_depth.item as u64).min(engine_state.recursion_limit);
+        }
+
+        let mut closure_stack = stack.captures_to_stack(&closure.captures);
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("run-with-limit".into())
+            .spawn(move || {
+                let block = closure_engine_state.get_block(closure.block_id);
+                // Streams are collected here, so they count towards the timeout too
+                let result = eval_block_with_early_return(
+                    &closure_engine_state,
+                    &mut closure_stack,
+                    block,
+                    input,
+                    false,
+                    false,
+                )
+                .map(|pipeline_data| pipeline_data.into_value(span));
+                let _ = sender.send(result);
+            })
+            .map_err(|e| ShellError::IOErrorSpanned(e.to_string(), span))?;
+
+        let deadline = timeout.map(|timeout| {
+            (
+                Instant::now() + Duration::from_nanos(timeout.item as u64),
+                timeout.span,
+            )
+        });
+
+        let value = loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(result) => break result?,
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(ShellError::NushellFailedSpanned(
+                        "The closure stopped without a result".into(),
+                        "the closure panicked".into(),
+                        span,
+                    ))
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if nu_utils::ctrl_c::was_pressed(&engine_state.ctrlc) {
+                        interrupt.store(true, Ordering::SeqCst);
+                        return Ok(PipelineData::empty());
+                    }
+                    if let Some((deadline, timeout_span)) = deadline {
+                        if Instant::now() >= deadline {
+                            interrupt.store(true, Ordering::SeqCst);
+                            return Err(ShellError::LimitExceeded(
+                                "the closure ran for longer than this".into(),
+                                timeout_span,
+                            ));
+                        }
+                    }
+                }
+            }
+        };
+
+        if let Some(max_size) = max_size {
+            let size = value_size(&value);
+            if size > max_size.item as usize {
+                return Err(ShellError::LimitExceeded(
+                    format!("the result of the closure takes {size} bytes, more than this"),
+                    max_size.span,
+                ));
+            }
+        }
+
+        Ok(value.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Run a closure, giving up after 2 seconds",
+                example: "run-with-limit --timeout 2sec { http get https://example.com }",
+                result: None,
+            },
+            Example {
+                description: "Run a closure that returns quickly",
+                example: "run-with-limit -t 1min { 1 + 2 }",
+                result: Some(Value::test_int(3)),
+            },
+            Example {
+                description:
+                    "Limit how deep a recursive command can go, and how big its result can be",
+                example:
+                    "run-with-limit --max-depth 10 --max-size 1mb { open data.json | flatten }",
+                result: None,
+            },
+        ]
+    }
+}
+
+// A rough count of the bytes a value holds, enough to tell small results from huge ones
+fn value_size(value: &Value) -> usize {
+    match value {
+        Value::String { val, .. } => val.len(),
+        Value::Binary { val, .. } => val.len(),
+        Value::List { vals, .. } => vals.iter().map(value_size).sum(),
+        Value::Record { cols, vals, .. } => {
+            cols.iter().map(String::len).sum::<usize>() + vals.iter().map(value_size).sum::<usize>()
+        }
+        _ => std::mem::size_of::<Value>(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(RunWithLimit {})
+    }
+}
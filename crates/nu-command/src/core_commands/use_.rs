@@ -26,11 +26,29 @@ impl Command for Use {
                 SyntaxShape::Any,
                 "Which members of the module to import",
             )
+            .named(
+                "hash",
+                SyntaxShape::String,
+                "the sha256 hash a module from a URL must have",
+                None,
+            )
+            .named(
+                "tag",
+                SyntaxShape::String,
+                "the tag or commit of the git repository a module comes from",
+                None,
+            )
             .category(Category::Core)
     }
 
     fn extra_usage(&self) -> &str {
-        r#"This command is a parser keyword. For details, check:
+        r#"A module can come from an https URL, pinned with --hash, or from a git repository written
+as git+<repository>.git/<file>, pinned with --tag, --hash or both. Such a module must be
+fetched into the cache with `fetch-module` first, with the same pin, since `use` is parsed
+before anything runs. A file can't fetch the modules it uses itself: run `fetch-module` once
+beforehand, or put it in env.nu, which runs before config.nu is parsed.
+
+This command is a parser keyword. For details, check:
   https://www.nushell.sh/book/thinking_in_nu.html"#
     }
 
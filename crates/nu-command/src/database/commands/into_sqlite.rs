@@ -1,14 +1,18 @@
-use crate::database::values::sqlite::open_sqlite_db;
+use crate::database::values::sqlite::{open_sqlite_db, SQLiteDatabase};
 use itertools::Itertools;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
     SyntaxShape, Type, Value,
 };
+use rusqlite::{params_from_iter, types, Connection};
 use std::iter;
 use std::path::Path;
+use std::sync::{atomic::AtomicBool, Arc};
+
+const DEFAULT_BATCH_SIZE: usize = 10_000;
 
 #[derive(Clone)]
 pub struct IntoSqliteDb;
@@ -20,13 +24,16 @@ impl Command for IntoSqliteDb {
 
     fn signature(&self) -> Signature {
         Signature::build("into sqlite")
-            .input_output_types(vec![(Type::Any, Type::Nothing)])
+            .input_output_types(vec![
+                (Type::Any, Type::Nothing),
+                (Type::Any, Type::Custom("SQLiteDatabase".into())),
+            ])
             .allow_variants_without_examples(true)
             // TODO: narrow disallowed types
-            .required(
+            .optional(
                 "file_name",
                 SyntaxShape::String,
-                "Specify the filename to save the database to",
+                "Specify the filename to save the database to, or leave it out to keep the database in memory",
             )
             .named(
                 "table_name",
@@ -34,6 +41,28 @@ impl Command for IntoSqliteDb {
                 "Specify table name to store the data in",
                 Some('t'),
             )
+            .switch(
+                "replace",
+                "drop the table if it already exists, instead of adding the rows to it",
+                Some('r'),
+            )
+            .named(
+                "columns",
+                SyntaxShape::Record,
+                "a record of column names to the SQL type to store them as, instead of the one inferred from the data",
+                Some('c'),
+            )
+            .named(
+                "batch-size",
+                SyntaxShape::Int,
+                "the number of rows inserted in each transaction (default 10000)",
+                Some('b'),
+            )
+            .switch(
+                "in-memory",
+                "keep the database in memory and return it, so it can be queried",
+                Some('m'),
+            )
             .category(Category::Conversions)
     }
 
@@ -51,8 +80,19 @@ impl Command for IntoSqliteDb {
         "Convert table into a SQLite database"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Without a file name, or with --in-memory, the database is kept in memory and returned, so
+that it can be passed to 'query db' or 'schema'. It lasts as long as the value does.
+
+The rows are added to the table if it already exists, in which case its columns must include
+those of the data, unless --replace is given. They are inserted in transactions of --batch-size
+rows.
+
+In restricted mode, only databases in memory can be made, unless filesystem writes are allowed."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["convert", "database"]
+        vec!["convert", "database", "sql", "memory"]
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -75,6 +115,21 @@ impl Command for IntoSqliteDb {
             description: "Convert a variety of values in table literal form into a SQLite database",
             example: "[one 2 5.2 six true 100mib 25sec] | into sqlite variety.db",
             result: None,
+        },
+        Example {
+            description: "Replace a table of an existing database, rather than adding to it",
+            example: "ls | into sqlite my_ls.db --replace",
+            result: None,
+        },
+        Example {
+            description: "Store a column with a SQL type of your choosing",
+            example: "[[id price]; [1 '9.99']] | into sqlite shop.db --columns {price: REAL}",
+            result: None,
+        },
+        Example {
+            description: "Query a table with SQL without writing it to a file",
+            example: "open data.csv | into sqlite | query db 'select * from main where size > 10'",
+            result: None,
         }]
     }
 }
@@ -86,135 +141,156 @@ fn operate(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let span = call.head;
-    let file_name: Spanned<String> = call.req(engine_state, stack, 0)?;
+    let file_name: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
     let table_name: Option<Spanned<String>> = call.get_flag(engine_state, stack, "table_name")?;
+    let column_types: Option<Value> = call.get_flag(engine_state, stack, "columns")?;
+    let batch_size: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "batch-size")?;
+
+    let options = Options {
+        table_name: table_name.map_or_else(|| "main".to_string(), |name| name.item),
+        replace: call.has_flag("replace"),
+        column_types: column_types
+            .map(|value| parse_column_types(&value))
+            .transpose()?,
+        batch_size: match batch_size {
+            Some(size) if size.item <= 0 => return Err(ShellError::NeedsPositiveValue(size.span)),
+            Some(size) => size.item as usize,
+            None => DEFAULT_BATCH_SIZE,
+        },
+    };
+
+    let ctrlc = engine_state.ctrlc.clone();
+    let in_memory = call.has_flag("in-memory");
+    let (database, target) = match file_name {
+        Some(file) if in_memory => {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "--in-memory can't be used with a file name".into(),
+                file.span,
+            ))
+        }
+        Some(file) => {
+            // Only writing a file needs the capability, so databases in memory can still be made
+            // by a restricted engine
+            let denied = engine_state
+                .restrictions
+                .as_ref()
+                .map_or(false, |restrictions| {
+                    !restrictions.allows("into sqlite", Capability::FilesystemWrite)
+                });
+            if denied {
+                return Err(ShellError::RestrictedCommand(
+                    "into sqlite".into(),
+                    Capability::FilesystemWrite.description().into(),
+                    Capability::FilesystemWrite.name().into(),
+                    file.span,
+                ));
+            }
+            (None, file)
+        }
+        None => {
+            let database = SQLiteDatabase::new_in_memory(span, ctrlc.clone())?;
+            let path = database.path.to_string_lossy().to_string();
+            (Some(database), Spanned { item: path, span })
+        }
+    };
 
     // collect the input into a value
     let table_entries = input.into_value(span);
 
-    match action(&table_entries, table_name, file_name, span) {
-        Ok(val) => Ok(val.into_pipeline_data()),
-        Err(e) => Err(e),
+    let mut conn = open_sqlite_db(Path::new(&target.item), target.span)?;
+    action(&mut conn, &table_entries, &options, target.span, &ctrlc)?;
+
+    Ok(match database {
+        Some(database) => database.into_value(span),
+        None => Value::Nothing { span },
+    }
+    .into_pipeline_data())
+}
+
+struct Options {
+    table_name: String,
+    replace: bool,
+    column_types: Option<Vec<(String, String)>>,
+    batch_size: usize,
+}
+
+fn parse_column_types(value: &Value) -> Result<Vec<(String, String)>, ShellError> {
+    match value {
+        Value::Record { cols, vals, .. } => cols
+            .iter()
+            .zip(vals)
+            .map(|(col, val)| Ok((col.clone(), val.as_string()?)))
+            .collect(),
+        other => Err(ShellError::TypeMismatch(
+            format!("record of SQL types, not {}", other.get_type()),
+            other.span()?,
+        )),
     }
 }
 
 fn action(
+    conn: &mut Connection,
     input: &Value,
-    table: Option<Spanned<String>>,
-    file: Spanned<String>,
+    options: &Options,
     span: Span,
-) -> Result<Value, ShellError> {
-    let table_name = if let Some(table_name) = table {
-        table_name.item
-    } else {
-        "main".to_string()
-    };
-
+    ctrlc: &Option<Arc<AtomicBool>>,
+) -> Result<(), ShellError> {
     match input {
-        Value::List { vals, span } => {
+        Value::List { vals, .. } => {
+            let table_name = quote_identifier(&options.table_name);
+
             // find the column names, and sqlite data types
-            let columns = get_columns_with_sqlite_types(vals);
+            let mut columns = get_columns_with_sqlite_types(vals);
+            if let Some(column_types) = &options.column_types {
+                for (name, sql_type) in column_types {
+                    match columns.iter_mut().find(|(column, _)| column == name) {
+                        Some(column) => column.1 = sql_type.clone(),
+                        None => columns.push((name.clone(), sql_type.clone())),
+                    }
+                }
+            }
+            if columns.is_empty() {
+                return Ok(());
+            }
+
+            if options.replace {
+                conn.execute(&format!("DROP TABLE IF EXISTS {table_name}"), [])
+                    .map_err(|e| sqlite_error("Failed to drop SQLite table", e, span))?;
+            }
 
             let table_columns_creation = columns
                 .iter()
-                .map(|(name, sql_type)| format!("{name} {sql_type}"))
+                .map(|(name, sql_type)| format!("{} {sql_type}", quote_identifier(name)))
                 .join(",");
 
-            // get the values
-            let table_values = vals
-                .iter()
-                .map(|list_value| {
-                    format!(
-                        "({})",
-                        match list_value {
-                            Value::Record {
-                                cols: _,
-                                vals,
-                                span: _,
-                            } => {
-                                vals.iter()
-                                    .map(|rec_val| {
-                                        format!("'{}'", nu_value_to_string(rec_val.clone(), ""))
-                                    })
-                                    .join(",")
-                            }
-                            // Number formats so keep them without quotes
-                            Value::Int { val: _, span: _ }
-                            | Value::Float { val: _, span: _ }
-                            | Value::Filesize { val: _, span: _ }
-                            | Value::Duration { val: _, span: _ } =>
-                                nu_value_to_string(list_value.clone(), ""),
-                            _ =>
-                            // String formats so add quotes around them
-                                format!("'{}'", nu_value_to_string(list_value.clone(), "")),
-                        }
-                    )
-                })
-                .join(",");
+            conn.execute(
+                &format!("CREATE TABLE IF NOT EXISTS {table_name} ({table_columns_creation})"),
+                [],
+            )
+            .map_err(|e| sqlite_error("Failed to create SQLite table", e, span))?;
+
+            // insert the rows in batches, each in its own transaction, so that a large table
+            // neither makes one huge transaction nor pays for one transaction per row
+            for batch in &vals.iter().chunks(options.batch_size) {
+                if nu_utils::ctrl_c::was_pressed(ctrlc) {
+                    return Err(ShellError::IOInterrupted(
+                        "Interrupted while inserting rows".into(),
+                        span,
+                    ));
+                }
 
-            // create the sqlite database table
-            let conn = open_sqlite_db(Path::new(&file.item), file.span)?;
-
-            // create a string for sql table creation
-            let create_statement =
-                format!("CREATE TABLE IF NOT EXISTS {table_name} ({table_columns_creation})");
-
-            // prepare the string as a sqlite statement
-            let mut stmt = conn.prepare(&create_statement).map_err(|e| {
-                ShellError::GenericError(
-                    "Failed to prepare SQLite statement".into(),
-                    e.to_string(),
-                    Some(file.span),
-                    None,
-                    Vec::new(),
-                )
-            })?;
-
-            // execute the statement
-            stmt.execute([]).map_err(|e| {
-                ShellError::GenericError(
-                    "Failed to execute SQLite statement".into(),
-                    e.to_string(),
-                    Some(file.span),
-                    None,
-                    Vec::new(),
-                )
-            })?;
-
-            // use normal sql to create the table
-            // insert into table_name
-            // values
-            // ('xx', 'yy', 'zz'),
-            // ('aa', 'bb', 'cc'),
-            // ('dd', 'ee', 'ff')
-
-            // create the string for inserting data into the table
-            let insert_statement = format!("INSERT INTO {table_name} VALUES {table_values}");
-
-            // prepare the string as a sqlite statement
-            let mut stmt = conn.prepare(&insert_statement).map_err(|e| {
-                ShellError::GenericError(
-                    "Failed to prepare SQLite statement".into(),
-                    e.to_string(),
-                    Some(file.span),
-                    None,
-                    Vec::new(),
-                )
-            })?;
-
-            // execute the statement
-            stmt.execute([]).map_err(|e| {
-                ShellError::GenericError(
-                    "Failed to execute SQLite statement".into(),
-                    e.to_string(),
-                    Some(file.span),
-                    None,
-                    Vec::new(),
-                )
-            })?;
+                let tx = conn
+                    .transaction()
+                    .map_err(|e| sqlite_error("Failed to start SQLite transaction", e, span))?;
+                for row in batch {
+                    insert_row(&tx, &table_name, row, span)?;
+                }
+                tx.commit()
+                    .map_err(|e| sqlite_error("Failed to commit SQLite transaction", e, span))?;
+            }
 
             // and we're done
-            Ok(Value::Nothing { span: *span })
+            Ok(())
         }
         // Propagate errors by explicitly matching them before the final case.
         Value::Error { error } => Err(error.clone()),
@@ -228,6 +304,66 @@ fn action(
     }
 }
 
+fn insert_row(
+    conn: &Connection,
+    table_name: &str,
+    row: &Value,
+    span: Span,
+) -> Result<(), ShellError> {
+    // records are inserted by column name, as their columns may come in any order, while any
+    // other value goes to the single 'value' column
+    let (columns, values): (Vec<String>, Vec<types::Value>) = match row {
+        Value::Record { cols, vals, .. } => (
+            cols.iter().map(|col| quote_identifier(col)).collect(),
+            vals.iter().map(nu_value_to_sqlite_value).collect(),
+        ),
+        other => (
+            vec![quote_identifier("value")],
+            vec![nu_value_to_sqlite_value(other)],
+        ),
+    };
+
+    let insert_statement = format!(
+        "INSERT INTO {table_name} ({}) VALUES ({})",
+        columns.join(","),
+        iter::repeat("?").take(values.len()).join(",")
+    );
+
+    let mut stmt = conn
+        .prepare_cached(&insert_statement)
+        .map_err(|e| sqlite_error("Failed to prepare SQLite statement", e, span))?;
+    stmt.execute(params_from_iter(values))
+        .map_err(|e| sqlite_error("Failed to execute SQLite statement", e, span))?;
+
+    Ok(())
+}
+
+fn sqlite_error(title: &str, error: rusqlite::Error, span: Span) -> ShellError {
+    ShellError::GenericError(
+        title.into(),
+        error.to_string(),
+        Some(span),
+        None,
+        Vec::new(),
+    )
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub fn nu_value_to_sqlite_value(value: &Value) -> types::Value {
+    match value {
+        Value::Int { val, .. } | Value::Filesize { val, .. } | Value::Duration { val, .. } => {
+            types::Value::Integer(*val)
+        }
+        Value::Float { val, .. } => types::Value::Real(*val),
+        Value::Binary { val, .. } => types::Value::Blob(val.clone()),
+        Value::Nothing { .. } => types::Value::Null,
+        other => types::Value::Text(nu_value_to_string(other.clone(), "")),
+    }
+}
+
 // This is taken from to text local_into_string but tweaks it a bit so that certain formatting does not happen
 fn nu_value_to_string(value: Value, separator: &str) -> String {
     match value {
@@ -246,8 +382,7 @@ fn nu_value_to_string(value: Value, separator: &str) -> String {
         }
         Value::String { val, .. } => {
             // don't store ansi escape sequences in the database
-            // escape single quotes
-            nu_utils::strip_ansi_unlikely(&val).replace('\'', "''")
+            nu_utils::strip_ansi_unlikely(&val).to_string()
         }
         Value::List { vals: val, .. } => val
             .iter()
@@ -289,6 +424,7 @@ fn nu_type_to_sqlite_type(nu_type: Type) -> &'static str {
         Type::Nothing => "NULL",
         Type::Filesize => "INTEGER",
         Type::Date => "TEXT",
+        Type::Binary => "BLOB",
         _ => "TEXT",
     }
 }
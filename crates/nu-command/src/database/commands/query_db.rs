@@ -1,12 +1,14 @@
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
-    Type,
+    Type, Value,
 };
 
 use super::super::SQLiteDatabase;
+use super::into_sqlite::nu_value_to_sqlite_value;
+use crate::database::values::sqlite::SqlParams;
 
 #[derive(Clone)]
 pub struct QueryDb;
@@ -24,6 +26,12 @@ impl Command for QueryDb {
                 SyntaxShape::String,
                 "SQL to execute against the database",
             )
+            .named(
+                "params",
+                SyntaxShape::Any,
+                "values for the parameters of the SQL, as a list for ? or a record for :name",
+                Some('p'),
+            )
             .input_type(Type::Any)
             .output_type(Type::Any)
             .category(Category::Custom("database".into()))
@@ -33,12 +41,42 @@ impl Command for QueryDb {
         "Query a database using SQL."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Values given with --params are bound to the parameters of the SQL, rather than pasted
+into it, so they need no quoting or escaping. A list fills '?' and '?NNN' parameters, a record
+fills ':name', '@name' and '$name' ones, with or without the prefix in its keys.
+
+The SQL may hold several statements separated by semicolons, which are run in order. The result
+is then a list with the rows of each statement, and each statement takes the next values of a
+list, as many as it has parameters.
+
+In restricted mode, database files are opened read-only unless filesystem writes are allowed, so
+the SQL can't change them or attach and create other files. Databases in memory can be changed."#
+    }
+
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Execute SQL against a SQLite database",
-            example: r#"open foo.db | query db "SELECT * FROM Bar""#,
-            result: None,
-        }]
+        vec![
+            Example {
+                description: "Execute SQL against a SQLite database",
+                example: r#"open foo.db | query db "SELECT * FROM Bar""#,
+                result: None,
+            },
+            Example {
+                description: "Bind a value to a positional parameter",
+                example: r#"open foo.db | query db "SELECT * FROM Bar WHERE id = ?" --params [5]"#,
+                result: None,
+            },
+            Example {
+                description: "Bind values to named parameters",
+                example: r#"open foo.db | query db "SELECT * FROM Bar WHERE name = :name AND size > :size" --params {name: nu, size: 10}"#,
+                result: None,
+            },
+            Example {
+                description: "Run several statements, getting the rows of each",
+                example: r#"open foo.db | query db "INSERT INTO Bar (id) VALUES (?); SELECT count(*) FROM Bar" --params [6]"#,
+                result: None,
+            },
+        ]
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -53,9 +91,43 @@ impl Command for QueryDb {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let sql: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let params: Option<Value> = call.get_flag(engine_state, stack, "params")?;
+
+        let params = match params {
+            Some(Value::List { vals, span }) => Spanned {
+                item: SqlParams::Positional(vals.iter().map(nu_value_to_sqlite_value).collect()),
+                span,
+            },
+            Some(Value::Record { cols, vals, span }) => Spanned {
+                item: SqlParams::Named(
+                    cols.into_iter()
+                        .zip(vals.iter().map(nu_value_to_sqlite_value))
+                        .collect(),
+                ),
+                span,
+            },
+            Some(other) => {
+                return Err(ShellError::TypeMismatch(
+                    format!("list or record of parameters, not {}", other.get_type()),
+                    other.span()?,
+                ))
+            }
+            None => Spanned {
+                item: SqlParams::Positional(vec![]),
+                span: call.head,
+            },
+        };
+
+        // A restricted engine that denies filesystem writes can still read database files
+        let read_only = engine_state
+            .restrictions
+            .as_ref()
+            .map_or(false, |restrictions| {
+                !restrictions.allows(self.name(), Capability::FilesystemWrite)
+            });
 
         let db = SQLiteDatabase::try_from_pipeline(input, call.head)?;
-        db.query(&sql, call.head)
+        db.query(&sql, &params, read_only, call.head)
             .map(IntoPipelineData::into_pipeline_data)
     }
 }
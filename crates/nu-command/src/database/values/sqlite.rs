@@ -4,13 +4,18 @@ use super::definitions::{
 };
 
 use nu_protocol::{CustomValue, PipelineData, ShellError, Span, Spanned, Value};
-use rusqlite::{types::ValueRef, Connection, Row};
+use rusqlite::{
+    ffi, params_from_iter,
+    types::{self, ValueRef},
+    Connection, OpenFlags, Row, Statement,
+};
 use serde::{Deserialize, Serialize};
 use std::{
+    ffi::CString,
     fs::File,
     io::Read,
     path::{Path, PathBuf},
-    sync::{atomic::AtomicBool, Arc},
+    sync::{atomic::AtomicBool, Arc, Mutex},
 };
 
 const SQLITE_MAGIC_BYTES: &[u8] = "SQLite format 3\0".as_bytes();
@@ -25,6 +30,10 @@ pub struct SQLiteDatabase {
     // this understandably can't be serialized. think that's OK, I'm not aware of a
     // reason why a CustomValue would be serialized outside of a plugin
     ctrlc: Option<Arc<AtomicBool>>,
+    #[serde(skip)]
+    // an in-memory database only lives as long as a connection to it is open, so this one is
+    // held until the last clone of the value is dropped
+    keep_alive: Option<Arc<Mutex<Connection>>>,
 }
 
 impl SQLiteDatabase {
@@ -32,9 +41,26 @@ impl SQLiteDatabase {
         Self {
             path: PathBuf::from(path),
             ctrlc,
+            keep_alive: None,
         }
     }
 
+    /// Create an empty database in memory. Its path is a shared-cache URI, so that the
+    /// connections opened from it all see the same data.
+    pub fn new_in_memory(span: Span, ctrlc: Option<Arc<AtomicBool>>) -> Result<Self, ShellError> {
+        let path = PathBuf::from(format!(
+            "file:nu-{}?mode=memory&cache=shared",
+            uuid::Uuid::new_v4()
+        ));
+        let conn = open_sqlite_db(&path, span)?;
+
+        Ok(Self {
+            path,
+            ctrlc,
+            keep_alive: Some(Arc::new(Mutex::new(conn))),
+        })
+    }
+
     pub fn try_from_path(
         path: &Path,
         span: Span,
@@ -61,6 +87,7 @@ impl SQLiteDatabase {
                 Some(db) => Ok(Self {
                     path: db.path.clone(),
                     ctrlc: db.ctrlc.clone(),
+                    keep_alive: db.keep_alive.clone(),
                 }),
                 None => Err(ShellError::CantConvert(
                     "database".into(),
@@ -90,20 +117,58 @@ impl SQLiteDatabase {
         }
     }
 
-    pub fn query(&self, sql: &Spanned<String>, call_span: Span) -> Result<Value, ShellError> {
-        let db = open_sqlite_db(&self.path, call_span)?;
+    /// Run the statements of `sql` one after the other. A single statement returns its rows,
+    /// several of them return a list with the rows of each. With `read_only`, a database file
+    /// is opened so that the statements can't change it or create other files, while one in
+    /// memory can still be changed.
+    pub fn query(
+        &self,
+        sql: &Spanned<String>,
+        params: &Spanned<SqlParams>,
+        read_only: bool,
+        call_span: Span,
+    ) -> Result<Value, ShellError> {
+        let db = if read_only && self.keep_alive.is_none() {
+            open_sqlite_db_read_only(&self.path, call_span)?
+        } else {
+            open_sqlite_db(&self.path, call_span)?
+        };
 
-        let stream = run_sql_query(db, sql, self.ctrlc.clone()).map_err(|e| {
-            ShellError::GenericError(
-                "Failed to query SQLite database".into(),
-                e.to_string(),
-                Some(sql.span),
-                None,
-                Vec::new(),
-            )
-        })?;
+        let mut offset = 0;
+        let mut results = vec![];
+        for statement in split_statements(&sql.item) {
+            let stmt = db
+                .prepare(statement)
+                .map_err(|e| query_error(e, sql.span))?;
+            let values = params.item.for_statement(&stmt, &mut offset, params.span)?;
+            let rows = prepared_statement_to_nu_list(stmt, values, sql.span, self.ctrlc.clone())
+                .map_err(|e| query_error(e, sql.span))?;
+            results.push(rows);
+        }
 
-        Ok(stream)
+        if let SqlParams::Positional(values) = &params.item {
+            if offset < values.len() {
+                return Err(ShellError::GenericError(
+                    "Too many query parameters".into(),
+                    format!(
+                        "{} parameters given, but the SQL only takes {offset}",
+                        values.len()
+                    ),
+                    Some(params.span),
+                    None,
+                    Vec::new(),
+                ));
+            }
+        }
+
+        Ok(if results.len() == 1 {
+            results.remove(0)
+        } else {
+            Value::List {
+                vals: results,
+                span: call_span,
+            }
+        })
     }
 
     pub fn open_connection(&self) -> Result<Connection, rusqlite::Error> {
@@ -278,6 +343,7 @@ impl CustomValue for SQLiteDatabase {
         let cloned = SQLiteDatabase {
             path: self.path.clone(),
             ctrlc: self.ctrlc.clone(),
+            keep_alive: self.keep_alive.clone(),
         };
 
         Value::CustomValue {
@@ -349,13 +415,112 @@ pub fn open_sqlite_db(path: &Path, call_span: Span) -> Result<Connection, nu_pro
     })
 }
 
-fn run_sql_query(
-    conn: Connection,
-    sql: &Spanned<String>,
-    ctrlc: Option<Arc<AtomicBool>>,
-) -> Result<Value, rusqlite::Error> {
-    let stmt = conn.prepare(&sql.item)?;
-    prepared_statement_to_nu_list(stmt, sql.span, ctrlc)
+/// Like `open_sqlite_db`, but the database can't be changed, and neither can the databases
+/// attached to it
+pub fn open_sqlite_db_read_only(path: &Path, call_span: Span) -> Result<Connection, ShellError> {
+    let flags = OpenFlags::SQLITE_OPEN_READ_ONLY
+        | OpenFlags::SQLITE_OPEN_URI
+        | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+
+    Connection::open_with_flags(path, flags).map_err(|e| {
+        ShellError::GenericError(
+            "Failed to open SQLite database".into(),
+            e.to_string(),
+            Some(call_span),
+            None,
+            Vec::new(),
+        )
+    })
+}
+
+/// The values for the parameters of a query: a list for `?` and `?NNN`, or a record for
+/// `:name`, `@name` and `$name`, where the prefix of a name may be left out.
+pub enum SqlParams {
+    Positional(Vec<types::Value>),
+    Named(Vec<(String, types::Value)>),
+}
+
+impl SqlParams {
+    /// The values to bind to the parameters of a statement, in order. With several statements,
+    /// each one takes the next positional values, as many as it has parameters.
+    fn for_statement(
+        &self,
+        stmt: &Statement,
+        offset: &mut usize,
+        span: Span,
+    ) -> Result<Vec<types::Value>, ShellError> {
+        let count = stmt.parameter_count();
+        let mut values = Vec::with_capacity(count);
+
+        for index in 1..=count {
+            let name = stmt.parameter_name(index);
+            let value = match (self, name) {
+                (SqlParams::Positional(list), name)
+                    if name.map_or(true, |name| name.starts_with('?')) =>
+                {
+                    list.get(*offset + index - 1)
+                }
+                (SqlParams::Named(record), Some(name)) => record
+                    .iter()
+                    .find(|(key, _)| key == name || key == &name[1..])
+                    .map(|(_, value)| value),
+                _ => None,
+            };
+
+            let value = value.ok_or_else(|| {
+                ShellError::GenericError(
+                    "Missing query parameter".into(),
+                    format!("no value for parameter {}", name.unwrap_or("?")),
+                    Some(span),
+                    None,
+                    Vec::new(),
+                )
+            })?;
+            values.push(value.clone());
+        }
+
+        if let SqlParams::Positional(_) = self {
+            *offset += count;
+        }
+        Ok(values)
+    }
+}
+
+fn query_error(error: rusqlite::Error, span: Span) -> ShellError {
+    ShellError::GenericError(
+        "Failed to query SQLite database".into(),
+        error.to_string(),
+        Some(span),
+        None,
+        Vec::new(),
+    )
+}
+
+/// Split SQL into its statements, at the semicolons that end one, which leaves alone those
+/// inside quotes, comments and the body of a trigger
+fn split_statements(sql: &str) -> Vec<&str> {
+    let is_complete = |sql: &str| match CString::new(sql) {
+        // SAFETY: the string is nul-terminated and lives until the call returns
+        Ok(sql) => unsafe { ffi::sqlite3_complete(sql.as_ptr()) != 0 },
+        Err(_) => false,
+    };
+
+    let mut statements = vec![];
+    let mut start = 0;
+    for (end, _) in sql.match_indices(';') {
+        let statement = &sql[start..=end];
+        if is_complete(statement) {
+            if !statement.trim_end_matches(';').trim().is_empty() {
+                statements.push(statement);
+            }
+            start = end + 1;
+        }
+    }
+    if !sql[start..].trim().is_empty() {
+        statements.push(&sql[start..]);
+    }
+
+    statements
 }
 
 fn read_single_table(
@@ -365,11 +530,12 @@ fn read_single_table(
     ctrlc: Option<Arc<AtomicBool>>,
 ) -> Result<Value, rusqlite::Error> {
     let stmt = conn.prepare(&format!("SELECT * FROM {table_name}"))?;
-    prepared_statement_to_nu_list(stmt, call_span, ctrlc)
+    prepared_statement_to_nu_list(stmt, vec![], call_span, ctrlc)
 }
 
 fn prepared_statement_to_nu_list(
     mut stmt: rusqlite::Statement,
+    params: Vec<types::Value>,
     call_span: Span,
     ctrlc: Option<Arc<AtomicBool>>,
 ) -> Result<Value, rusqlite::Error> {
@@ -379,7 +545,7 @@ fn prepared_statement_to_nu_list(
         .map(|c| c.to_string())
         .collect::<Vec<String>>();
 
-    let row_results = stmt.query_map([], |row| {
+    let row_results = stmt.query_map(params_from_iter(params), |row| {
         Ok(convert_sqlite_row_to_nu_value(
             row,
             call_span,
@@ -427,7 +593,7 @@ fn read_entire_sqlite_db(
         table_names.push(table_name.clone());
 
         let table_stmt = conn.prepare(&format!("select * from [{table_name}]"))?;
-        let rows = prepared_statement_to_nu_list(table_stmt, call_span, ctrlc.clone())?;
+        let rows = prepared_statement_to_nu_list(table_stmt, vec![], call_span, ctrlc.clone())?;
         tables.push(rows);
     }
 
@@ -574,6 +740,22 @@ mod test {
 
         assert_eq!(converted_db, expected);
     }
+
+    #[test]
+    fn splits_statements_at_their_end() {
+        let statements = split_statements(
+            "create table t (a); insert into t values ('x;y'); -- a;b\nselect * from t",
+        );
+
+        assert_eq!(
+            statements,
+            vec![
+                "create table t (a);",
+                " insert into t values ('x;y');",
+                " -- a;b\nselect * from t",
+            ]
+        );
+    }
 }
 
 pub fn open_connection_in_memory() -> Result<Connection, ShellError> {
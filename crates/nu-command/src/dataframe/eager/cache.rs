@@ -0,0 +1,194 @@
+use super::super::values::{Column, NuDataFrame};
+
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct CacheDF;
+
+impl Command for CacheDF {
+    fn name(&self) -> &str {
+        "dfr cache"
+    }
+
+    fn usage(&self) -> &str {
+        "Caches a dataframe under a name for the rest of the session, or returns a cached dataframe."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"A lazy frame is collected before it is cached. Cached dataframes can be used as tables
+by name in `dfr query`, and in place of a dataframe in `dfr join`."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional("name", SyntaxShape::String, "name of the cached dataframe")
+            .switch("remove", "remove the dataframe from the cache", Some('r'))
+            .switch("list", "list the cached dataframes", Some('l'))
+            .input_type(Type::Any)
+            .output_type(Type::Any)
+            .category(Category::Custom("dataframe".into()))
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["dataframe", "store", "register", "name"]
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Cache a dataframe and get it back by name",
+                example:
+                    "let df = ([[a b]; [1 2] [3 4]] | dfr into-df | dfr cache nums); dfr cache nums",
+                result: Some(
+                    NuDataFrame::try_from_columns(vec![
+                        Column::new(
+                            "a".to_string(),
+                            vec![Value::test_int(1), Value::test_int(3)],
+                        ),
+                        Column::new(
+                            "b".to_string(),
+                            vec![Value::test_int(2), Value::test_int(4)],
+                        ),
+                    ])
+                    .expect("simple df for test should not fail")
+                    .into_value(Span::test_data()),
+                ),
+            },
+            Example {
+                description: "List the cached dataframes",
+                example: "dfr cache --list",
+                result: None,
+            },
+            Example {
+                description: "Remove a dataframe from the cache",
+                example: "dfr cache nums --remove",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        command(engine_state, stack, call, input)
+    }
+}
+
+fn command(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    if call.has_flag("list") {
+        return list_cached(engine_state, call.head);
+    }
+
+    let name: Spanned<String> = match call.opt(engine_state, stack, 0)? {
+        Some(name) => name,
+        None => return Err(ShellError::MissingParameter("name".into(), call.head)),
+    };
+
+    if call.has_flag("remove") {
+        lock_cache(engine_state, call.head)?.remove(&name.item);
+        return Ok(PipelineData::empty());
+    }
+
+    match input {
+        PipelineData::Empty => cached_dataframe(engine_state, &name)
+            .map(|df| df.into_value(call.head).into_pipeline_data()),
+        input => {
+            let value = NuDataFrame::try_from_pipeline(input, call.head)?.into_value(call.head);
+            lock_cache(engine_state, call.head)?.insert(name.item, value.clone());
+
+            Ok(value.into_pipeline_data())
+        }
+    }
+}
+
+fn lock_cache(
+    engine_state: &EngineState,
+    span: Span,
+) -> Result<std::sync::MutexGuard<std::collections::HashMap<String, Value>>, ShellError> {
+    engine_state.dataframe_cache.lock().map_err(|_| {
+        ShellError::NushellFailedSpanned(
+            "dataframe cache is poisoned".into(),
+            "while accessing the dataframe cache".into(),
+            span,
+        )
+    })
+}
+
+/// Look up a dataframe cached with `dfr cache`
+pub fn cached_dataframe(
+    engine_state: &EngineState,
+    name: &Spanned<String>,
+) -> Result<NuDataFrame, ShellError> {
+    let cache = lock_cache(engine_state, name.span)?;
+    match cache.get(&name.item) {
+        Some(value) => NuDataFrame::try_from_value(value.clone()),
+        None => Err(ShellError::GenericError(
+            format!("Dataframe '{}' is not cached", name.item),
+            "not found in the dataframe cache".into(),
+            Some(name.span),
+            Some(
+                "Cache dataframes with `dfr cache <name>`, and list them with `dfr cache --list`"
+                    .into(),
+            ),
+            Vec::new(),
+        )),
+    }
+}
+
+/// All the cached dataframes, sorted by name
+pub fn cached_dataframes(
+    engine_state: &EngineState,
+    span: Span,
+) -> Result<Vec<(String, NuDataFrame)>, ShellError> {
+    let cache = lock_cache(engine_state, span)?;
+    let mut frames = cache
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), NuDataFrame::try_from_value(value.clone())?)))
+        .collect::<Result<Vec<(String, NuDataFrame)>, ShellError>>()?;
+    frames.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(frames)
+}
+
+fn list_cached(engine_state: &EngineState, span: Span) -> Result<PipelineData, ShellError> {
+    let vals = cached_dataframes(engine_state, span)?
+        .into_iter()
+        .map(|(name, df)| Value::Record {
+            cols: vec!["name".into(), "columns".into(), "rows".into()],
+            vals: vec![
+                Value::string(name, span),
+                Value::int(df.as_ref().width() as i64, span),
+                Value::int(df.as_ref().height() as i64, span),
+            ],
+            span,
+        })
+        .collect();
+
+    Ok(Value::List { vals, span }.into_pipeline_data())
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::test_dataframe::test_dataframe;
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        test_dataframe(vec![Box::new(CacheDF {})])
+    }
+}
@@ -1,4 +1,5 @@
 mod append;
+mod cache;
 mod columns;
 mod drop;
 mod drop_duplicates;
@@ -32,6 +33,7 @@ use nu_protocol::engine::StateWorkingSet;
 
 pub use self::open::OpenDataFrame;
 pub use append::AppendDF;
+pub use cache::{cached_dataframe, cached_dataframes, CacheDF};
 pub use columns::ColumnsDF;
 pub use drop::DropDF;
 pub use drop_duplicates::DropDuplicates;
@@ -73,6 +75,7 @@ pub fn add_eager_decls(working_set: &mut StateWorkingSet) {
     // Dataframe commands
     bind_command!(
         AppendDF,
+        CacheDF,
         ColumnsDF,
         DataTypes,
         Summary,
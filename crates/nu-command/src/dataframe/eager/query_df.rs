@@ -1,6 +1,9 @@
 use super::super::values::NuDataFrame;
 use crate::dataframe::values::Column;
-use crate::dataframe::{eager::SQLContext, values::NuLazyFrame};
+use crate::dataframe::{
+    eager::{cached_dataframes, SQLContext},
+    values::NuLazyFrame,
+};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
@@ -25,10 +28,14 @@ impl Command for QueryDf {
         "Query dataframe using SQL. Note: The dataframe is always named 'df' in your query's from clause."
     }
 
+    fn extra_usage(&self) -> &str {
+        "Dataframes cached with `dfr cache` can be used as tables by their name, in which case the input dataframe is optional."
+    }
+
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .required("sql", SyntaxShape::String, "sql query")
-            .input_type(Type::Custom("dataframe".into()))
+            .input_type(Type::Any)
             .output_type(Type::Custom("dataframe".into()))
             .category(Category::Custom("dataframe".into()))
     }
@@ -38,18 +45,33 @@ impl Command for QueryDf {
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Query dataframe using SQL",
-            example: "[[a b]; [1 2] [3 4]] | dfr into-df | dfr query 'select a from df'",
-            result: Some(
-                NuDataFrame::try_from_columns(vec![Column::new(
-                    "a".to_string(),
-                    vec![Value::test_int(1), Value::test_int(3)],
-                )])
-                .expect("simple df for test should not fail")
-                .into_value(Span::test_data()),
-            ),
-        }]
+        vec![
+            Example {
+                description: "Query dataframe using SQL",
+                example: "[[a b]; [1 2] [3 4]] | dfr into-df | dfr query 'select a from df'",
+                result: Some(
+                    NuDataFrame::try_from_columns(vec![Column::new(
+                        "a".to_string(),
+                        vec![Value::test_int(1), Value::test_int(3)],
+                    )])
+                    .expect("simple df for test should not fail")
+                    .into_value(Span::test_data()),
+                ),
+            },
+            Example {
+                description: "Query a dataframe cached with `dfr cache`",
+                example: r#"let names = ([[a name]; [1 one] [3 three]] | dfr into-df | dfr cache names);
+    dfr query 'select name from names'"#,
+                result: Some(
+                    NuDataFrame::try_from_columns(vec![Column::new(
+                        "name".to_string(),
+                        vec![Value::test_string("one"), Value::test_string("three")],
+                    )])
+                    .expect("simple df for test should not fail")
+                    .into_value(Span::test_data()),
+                ),
+            },
+        ]
     }
 
     fn run(
@@ -70,10 +92,15 @@ fn command(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let sql_query: String = call.req(engine_state, stack, 0)?;
-    let df = NuDataFrame::try_from_pipeline(input, call.head)?;
 
     let mut ctx = SQLContext::new();
-    ctx.register("df", &df.df);
+    for (name, cached) in cached_dataframes(engine_state, call.head)? {
+        ctx.register(&name, cached.as_ref());
+    }
+    if !matches!(input, PipelineData::Empty) {
+        let df = NuDataFrame::try_from_pipeline(input, call.head)?;
+        ctx.register("df", &df.df);
+    }
     let df_sql = ctx.execute(&sql_query).map_err(|e| {
         ShellError::GenericError(
             "Dataframe Error".into(),
@@ -98,9 +125,10 @@ fn command(
 mod test {
     use super::super::super::test_dataframe::test_dataframe;
     use super::*;
+    use crate::dataframe::eager::CacheDF;
 
     #[test]
     fn test_examples() {
-        test_dataframe(vec![Box::new(QueryDf {})])
+        test_dataframe(vec![Box::new(QueryDf {}), Box::new(CacheDF {})])
     }
 }
@@ -4,13 +4,14 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
 };
 use polars::{
     chunked_array::ChunkedArray,
     prelude::{
-        AnyValue, DataFrame, DataType, Float64Type, IntoSeries, NewChunkedArray,
-        QuantileInterpolOptions, Series, Utf8Type,
+        AnyValue, DataFrame, DataType, Float64Type, GroupsIndicator, IdxCa, IntoSeries, NamedFrom,
+        NewChunkedArray, PolarsError, QuantileInterpolOptions, Series, Utf8Type,
     },
 };
 
@@ -37,10 +38,34 @@ impl Command for Summary {
                 "provide optional quantiles",
                 Some('q'),
             )
+            .named(
+                "percentiles",
+                SyntaxShape::List(Box::new(SyntaxShape::Number)),
+                "percentiles to compute, as fractions between 0 and 1 (same as --quantiles)",
+                Some('p'),
+            )
+            .named(
+                "stats",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "statistics to compute: count, sum, mean, median, std, min and max",
+                Some('s'),
+            )
+            .named(
+                "by",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "columns to group by; implies --long",
+                Some('b'),
+            )
+            .switch(
+                "long",
+                "return one row per column and statistic instead of one column per column",
+                Some('l'),
+            )
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
+        vec![
+        Example {
             description: "list dataframe descriptives",
             example: "[[a b]; [1 1] [1 1]] | dfr into-df | dfr summary",
             result: Some(
@@ -94,7 +119,97 @@ impl Command for Summary {
                 .expect("simple df for test should not fail")
                 .into_value(Span::test_data()),
             ),
-        }]
+        },
+        Example {
+            description: "list selected descriptives in long format",
+            example: "[[a b]; [1 1] [1 1]] | dfr into-df | dfr summary --stats [count max] --percentiles [0.5] --long",
+            result: Some(
+                NuDataFrame::try_from_columns(vec![
+                    Column::new(
+                        "column".to_string(),
+                        vec![
+                            Value::test_string("a"),
+                            Value::test_string("a"),
+                            Value::test_string("a"),
+                            Value::test_string("b"),
+                            Value::test_string("b"),
+                            Value::test_string("b"),
+                        ],
+                    ),
+                    Column::new(
+                        "statistic".to_string(),
+                        vec![
+                            Value::test_string("count"),
+                            Value::test_string("50%"),
+                            Value::test_string("max"),
+                            Value::test_string("count"),
+                            Value::test_string("50%"),
+                            Value::test_string("max"),
+                        ],
+                    ),
+                    Column::new(
+                        "value".to_string(),
+                        vec![
+                            Value::test_float(2.0),
+                            Value::test_float(1.0),
+                            Value::test_float(1.0),
+                            Value::test_float(2.0),
+                            Value::test_float(1.0),
+                            Value::test_float(1.0),
+                        ],
+                    ),
+                ])
+                .expect("simple df for test should not fail")
+                .into_value(Span::test_data()),
+            ),
+        },
+        Example {
+            description: "list descriptives per group",
+            example: "[[g a]; [x 1] [x 3] [y 5]] | dfr into-df | dfr summary --by [g] --stats [count mean] --percentiles []",
+            result: Some(
+                NuDataFrame::try_from_columns(vec![
+                    Column::new(
+                        "g".to_string(),
+                        vec![
+                            Value::test_string("x"),
+                            Value::test_string("x"),
+                            Value::test_string("y"),
+                            Value::test_string("y"),
+                        ],
+                    ),
+                    Column::new(
+                        "column".to_string(),
+                        vec![
+                            Value::test_string("a"),
+                            Value::test_string("a"),
+                            Value::test_string("a"),
+                            Value::test_string("a"),
+                        ],
+                    ),
+                    Column::new(
+                        "statistic".to_string(),
+                        vec![
+                            Value::test_string("count"),
+                            Value::test_string("mean"),
+                            Value::test_string("count"),
+                            Value::test_string("mean"),
+                        ],
+                    ),
+                    Column::new(
+                        "value".to_string(),
+                        vec![
+                            Value::test_float(2.0),
+                            Value::test_float(2.0),
+                            Value::test_float(1.0),
+                            Value::test_float(5.0),
+                        ],
+                    ),
+                ])
+                .expect("simple df for test should not fail")
+                .into_value(Span::test_data()),
+            ),
+        },
+        ]
     }
 
     fn run(
@@ -115,148 +230,306 @@ fn command(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let quantiles: Option<Vec<Value>> = call.get_flag(engine_state, stack, "quantiles")?;
-    let quantiles = quantiles.map(|values| {
-        values
-            .iter()
-            .map(|value| match value {
-                Value::Float { val, span } => {
-                    if (&0.0..=&1.0).contains(&val) {
-                        Ok(*val)
-                    } else {
-                        Err(ShellError::GenericError(
-                            "Incorrect value for quantile".to_string(),
-                            "value should be between 0 and 1".to_string(),
-                            Some(*span),
-                            None,
-                            Vec::new(),
-                        ))
-                    }
-                }
-                _ => match value.span() {
-                    Ok(span) => Err(ShellError::GenericError(
-                        "Incorrect value for quantile".to_string(),
-                        "value should be a float".to_string(),
-                        Some(span),
-                        None,
-                        Vec::new(),
-                    )),
-                    Err(e) => Err(e),
-                },
-            })
-            .collect::<Result<Vec<f64>, ShellError>>()
-    });
+    let percentiles: Option<Vec<Value>> = call.get_flag(engine_state, stack, "percentiles")?;
+    let stats: Option<Vec<Spanned<String>>> = call.get_flag(engine_state, stack, "stats")?;
+    let by: Option<Vec<Spanned<String>>> = call.get_flag(engine_state, stack, "by")?;
+    let long = call.has_flag("long");
 
-    let quantiles = match quantiles {
-        Some(quantiles) => quantiles?,
-        None => vec![0.25, 0.50, 0.75],
+    let quantiles = match (quantiles, percentiles) {
+        (Some(_), Some(_)) => {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "--quantiles and --percentiles can't be used together".into(),
+                call.head,
+            ))
+        }
+        (Some(values), None) | (None, Some(values)) => values
+            .iter()
+            .map(quantile_from_value)
+            .collect::<Result<Vec<f64>, ShellError>>()?,
+        (None, None) => vec![0.25, 0.50, 0.75],
     };
 
-    let mut quantiles_labels = quantiles
-        .iter()
-        .map(|q| Some(format!("{}%", q * 100.0)))
-        .collect::<Vec<Option<String>>>();
-    let mut labels = vec![
-        Some("count".to_string()),
-        Some("sum".to_string()),
-        Some("mean".to_string()),
-        Some("median".to_string()),
-        Some("std".to_string()),
-        Some("min".to_string()),
-    ];
-    labels.append(&mut quantiles_labels);
-    labels.push(Some("max".to_string()));
+    let statistics = select_statistics(stats, quantiles)?;
 
     let df = NuDataFrame::try_from_pipeline(input, call.head)?;
 
-    let names = ChunkedArray::<Utf8Type>::from_slice_options("descriptor", &labels).into_series();
-
-    let head = std::iter::once(names);
-
-    let tail = df
-        .as_ref()
-        .get_columns()
-        .iter()
-        .filter(|col| col.dtype() != &DataType::Object("object"))
-        .map(|col| {
-            let count = col.len() as f64;
+    let res = match by {
+        Some(by) => summary_by_group(df.as_ref(), &by, &statistics, call.head)?,
+        None if long => summary_long(df.as_ref(), &statistics, call.head)?,
+        None => summary_wide(df.as_ref(), &statistics, call.head)?,
+    };
 
-            let sum = col
-                .sum_as_series()
-                .cast(&DataType::Float64)
-                .ok()
-                .and_then(|ca| match ca.get(0) {
-                    Ok(AnyValue::Float64(v)) => Some(v),
-                    _ => None,
-                });
+    Ok(PipelineData::Value(
+        NuDataFrame::dataframe_into_value(res, call.head),
+        None,
+    ))
+}
 
-            let mean = match col.mean_as_series().get(0) {
-                Ok(AnyValue::Float64(v)) => Some(v),
-                _ => None,
-            };
+/// A descriptive statistic computed for every described column
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Statistic {
+    Count,
+    Sum,
+    Mean,
+    Median,
+    Std,
+    Min,
+    Quantile(f64),
+    Max,
+}
 
-            let median = match col.median_as_series().get(0) {
-                Ok(AnyValue::Float64(v)) => Some(v),
-                _ => None,
-            };
+impl Statistic {
+    const NAMES: [&'static str; 7] = ["count", "sum", "mean", "median", "std", "min", "max"];
 
-            let std = match col.std_as_series(0).get(0) {
-                Ok(AnyValue::Float64(v)) => Some(v),
-                _ => None,
-            };
+    fn label(&self) -> String {
+        match self {
+            Statistic::Count => "count".into(),
+            Statistic::Sum => "sum".into(),
+            Statistic::Mean => "mean".into(),
+            Statistic::Median => "median".into(),
+            Statistic::Std => "std".into(),
+            Statistic::Min => "min".into(),
+            Statistic::Quantile(q) => format!("{}%", q * 100.0),
+            Statistic::Max => "max".into(),
+        }
+    }
 
-            let min = col
-                .min_as_series()
+    fn compute(&self, col: &Series) -> Option<f64> {
+        let as_f64 = |series: Series| {
+            series
                 .cast(&DataType::Float64)
                 .ok()
                 .and_then(|ca| match ca.get(0) {
                     Ok(AnyValue::Float64(v)) => Some(v),
                     _ => None,
-                });
-
-            let mut quantiles = quantiles
-                .clone()
-                .into_iter()
-                .map(|q| {
-                    col.quantile_as_series(q, QuantileInterpolOptions::default())
-                        .ok()
-                        .and_then(|ca| ca.cast(&DataType::Float64).ok())
-                        .and_then(|ca| match ca.get(0) {
-                            Ok(AnyValue::Float64(v)) => Some(v),
-                            _ => None,
-                        })
                 })
-                .collect::<Vec<Option<f64>>>();
+        };
 
-            let max = col
-                .max_as_series()
-                .cast(&DataType::Float64)
+        match self {
+            Statistic::Count => Some(col.len() as f64),
+            Statistic::Sum => as_f64(col.sum_as_series()),
+            Statistic::Mean => as_f64(col.mean_as_series()),
+            Statistic::Median => as_f64(col.median_as_series()),
+            Statistic::Std => as_f64(col.std_as_series(0)),
+            Statistic::Min => as_f64(col.min_as_series()),
+            Statistic::Quantile(q) => col
+                .quantile_as_series(*q, QuantileInterpolOptions::default())
                 .ok()
-                .and_then(|ca| match ca.get(0) {
-                    Ok(AnyValue::Float64(v)) => Some(v),
-                    _ => None,
-                });
+                .and_then(as_f64),
+            Statistic::Max => as_f64(col.max_as_series()),
+        }
+    }
+}
+
+fn quantile_from_value(value: &Value) -> Result<f64, ShellError> {
+    match value {
+        Value::Float { val, span } => {
+            if (&0.0..=&1.0).contains(&val) {
+                Ok(*val)
+            } else {
+                Err(ShellError::GenericError(
+                    "Incorrect value for quantile".to_string(),
+                    "value should be between 0 and 1".to_string(),
+                    Some(*span),
+                    None,
+                    Vec::new(),
+                ))
+            }
+        }
+        _ => Err(ShellError::GenericError(
+            "Incorrect value for quantile".to_string(),
+            "value should be a float".to_string(),
+            Some(value.span()?),
+            None,
+            Vec::new(),
+        )),
+    }
+}
+
+/// Builds the list of statistics in their canonical order, with the quantiles right before `max`.
+/// When `stats` is given, only the named statistics are kept.
+fn select_statistics(
+    stats: Option<Vec<Spanned<String>>>,
+    quantiles: Vec<f64>,
+) -> Result<Vec<Statistic>, ShellError> {
+    let selected = match stats {
+        Some(stats) => {
+            for stat in &stats {
+                if !Statistic::NAMES.contains(&stat.item.as_str()) {
+                    return Err(ShellError::GenericError(
+                        format!("Unknown statistic '{}'", stat.item),
+                        format!("expected one of {}", Statistic::NAMES.join(", ")),
+                        Some(stat.span),
+                        None,
+                        Vec::new(),
+                    ));
+                }
+            }
+            stats.into_iter().map(|stat| stat.item).collect::<Vec<_>>()
+        }
+        None => Statistic::NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+    };
+    let is_selected = |name: &str| selected.iter().any(|stat| stat == name);
+
+    let mut statistics = [
+        ("count", Statistic::Count),
+        ("sum", Statistic::Sum),
+        ("mean", Statistic::Mean),
+        ("median", Statistic::Median),
+        ("std", Statistic::Std),
+        ("min", Statistic::Min),
+    ]
+    .into_iter()
+    .filter(|(name, _)| is_selected(name))
+    .map(|(_, stat)| stat)
+    .collect::<Vec<Statistic>>();
+
+    statistics.extend(quantiles.into_iter().map(Statistic::Quantile));
+
+    if is_selected("max") {
+        statistics.push(Statistic::Max);
+    }
+
+    Ok(statistics)
+}
+
+fn described_columns<'a>(
+    df: &'a DataFrame,
+    skip: &'a [String],
+) -> impl Iterator<Item = &'a Series> {
+    df.get_columns().iter().filter(move |col| {
+        col.dtype() != &DataType::Object("object") && !skip.iter().any(|name| name == col.name())
+    })
+}
+
+fn dataframe_error(e: PolarsError, span: Span) -> ShellError {
+    ShellError::GenericError(
+        "Dataframe Error".into(),
+        e.to_string(),
+        Some(span),
+        None,
+        Vec::new(),
+    )
+}
+
+/// One row per statistic and one column per described column
+fn summary_wide(
+    df: &DataFrame,
+    statistics: &[Statistic],
+    span: Span,
+) -> Result<DataFrame, ShellError> {
+    let labels = statistics
+        .iter()
+        .map(|stat| Some(stat.label()))
+        .collect::<Vec<Option<String>>>();
+
+    let names = ChunkedArray::<Utf8Type>::from_slice_options("descriptor", &labels).into_series();
 
-            let mut descriptors = vec![Some(count), sum, mean, median, std, min];
-            descriptors.append(&mut quantiles);
-            descriptors.push(max);
+    let head = std::iter::once(names);
 
-            let name = format!("{} ({})", col.name(), col.dtype());
-            ChunkedArray::<Float64Type>::from_slice_options(&name, &descriptors).into_series()
-        });
+    let tail = described_columns(df, &[]).map(|col| {
+        let descriptors = statistics
+            .iter()
+            .map(|stat| stat.compute(col))
+            .collect::<Vec<Option<f64>>>();
+
+        let name = format!("{} ({})", col.name(), col.dtype());
+        ChunkedArray::<Float64Type>::from_slice_options(&name, &descriptors).into_series()
+    });
 
     let res = head.chain(tail).collect::<Vec<Series>>();
 
-    DataFrame::new(res)
-        .map_err(|e| {
-            ShellError::GenericError(
-                "Dataframe Error".into(),
-                e.to_string(),
-                Some(call.head),
-                None,
-                Vec::new(),
-            )
+    DataFrame::new(res).map_err(|e| dataframe_error(e, span))
+}
+
+/// Accumulates the rows of the long (tidy) summary layout
+#[derive(Default)]
+struct LongSummary {
+    columns: Vec<String>,
+    statistics: Vec<String>,
+    values: Vec<Option<f64>>,
+}
+
+impl LongSummary {
+    fn describe(&mut self, df: &DataFrame, statistics: &[Statistic], skip: &[String]) -> usize {
+        let before = self.values.len();
+        for col in described_columns(df, skip) {
+            for stat in statistics {
+                self.columns.push(col.name().to_string());
+                self.statistics.push(stat.label());
+                self.values.push(stat.compute(col));
+            }
+        }
+        self.values.len() - before
+    }
+
+    fn into_series(self) -> Vec<Series> {
+        vec![
+            Series::new("column", self.columns),
+            Series::new("statistic", self.statistics),
+            Series::new("value", self.values),
+        ]
+    }
+}
+
+/// One row per described column and statistic
+fn summary_long(
+    df: &DataFrame,
+    statistics: &[Statistic],
+    span: Span,
+) -> Result<DataFrame, ShellError> {
+    let mut summary = LongSummary::default();
+    summary.describe(df, statistics, &[]);
+
+    DataFrame::new(summary.into_series()).map_err(|e| dataframe_error(e, span))
+}
+
+/// The long layout, computed per group and prefixed with the group key columns
+fn summary_by_group(
+    df: &DataFrame,
+    by: &[Spanned<String>],
+    statistics: &[Statistic],
+    span: Span,
+) -> Result<DataFrame, ShellError> {
+    let by_names = by.iter().map(|name| name.item.clone()).collect::<Vec<_>>();
+
+    let groupby = df
+        .groupby_stable(&by_names)
+        .map_err(|e| dataframe_error(e, span))?;
+    let keys = groupby.keys();
+
+    let mut key_values: Vec<Vec<AnyValue>> = vec![Vec::new(); keys.len()];
+    let mut summary = LongSummary::default();
+
+    for (idx, group) in groupby.get_groups().iter().enumerate() {
+        let group_df = match group {
+            GroupsIndicator::Idx((_, indices)) => df
+                .take(&IdxCa::from_vec("", indices.clone()))
+                .map_err(|e| dataframe_error(e, span))?,
+            GroupsIndicator::Slice([first, len]) => df.slice(first as i64, len as usize),
+        };
+
+        let rows = summary.describe(&group_df, statistics, &by_names);
+
+        for (key, values) in keys.iter().zip(key_values.iter_mut()) {
+            let value = key.get(idx).map_err(|e| dataframe_error(e, span))?;
+            values.extend(std::iter::repeat(value).take(rows));
+        }
+    }
+
+    let mut res = keys
+        .iter()
+        .zip(key_values.iter())
+        .map(|(key, values)| {
+            Series::from_any_values(key.name(), values).map_err(|e| dataframe_error(e, span))
         })
-        .map(|df| PipelineData::Value(NuDataFrame::dataframe_into_value(df, call.head), None))
+        .collect::<Result<Vec<Series>, ShellError>>()?;
+    res.extend(summary.into_series());
+
+    DataFrame::new(res).map_err(|e| dataframe_error(e, span))
 }
 
 #[cfg(test)]
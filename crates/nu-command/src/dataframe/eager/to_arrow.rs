@@ -3,7 +3,7 @@ use std::{fs::File, path::PathBuf};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
 };
 use polars::prelude::{IpcWriter, SerWriter};
@@ -22,6 +22,10 @@ impl Command for ToArrow {
         "Saves dataframe to arrow file"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .required("file", SyntaxShape::Filepath, "file path to save dataframe")
@@ -1,3 +1,4 @@
+use chrono::DateTime;
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
@@ -51,6 +52,53 @@ impl Command for ToNu {
             span: Span::test_data(),
         };
 
+        let cols = vec![
+            "index".into(),
+            "d".into(),
+            "t".into(),
+            "b".into(),
+            "s".into(),
+        ];
+        let date = |date| Value::Date {
+            val: DateTime::parse_from_rfc3339(date).expect("date should parse"),
+            span: Span::test_data(),
+        };
+        let rec_4 = Value::Record {
+            cols: cols.clone(),
+            vals: vec![
+                Value::test_int(0),
+                date("2020-01-01T10:00:00.123456789+02:00"),
+                Value::Duration {
+                    val: 1_000_000_000,
+                    span: Span::test_data(),
+                },
+                Value::Binary {
+                    val: vec![0xff],
+                    span: Span::test_data(),
+                },
+                Value::Nothing {
+                    span: Span::test_data(),
+                },
+            ],
+            span: Span::test_data(),
+        };
+        let rec_5 = Value::Record {
+            cols,
+            vals: vec![
+                Value::test_int(1),
+                date("2021-06-01T00:00:00+02:00"),
+                Value::Nothing {
+                    span: Span::test_data(),
+                },
+                Value::Binary {
+                    val: vec![0x00],
+                    span: Span::test_data(),
+                },
+                Value::test_string(""),
+            ],
+            span: Span::test_data(),
+        };
+
         vec![
             Example {
                 description: "Shows head rows from dataframe",
@@ -68,6 +116,14 @@ impl Command for ToNu {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Keeps dates with their time zone, durations, binary data and nulls",
+                example: "[[d t b s]; [2020-01-01T10:00:00.123456789+02:00 1sec 0x[ff] null] [2021-06-01T00:00:00+02:00 null 0x[00] '']] | dfr into-df | dfr into-nu",
+                result: Some(Value::List {
+                    vals: vec![rec_4, rec_5],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 
@@ -1,9 +1,11 @@
+use crate::dataframe::eager::cached_dataframe;
 use crate::dataframe::values::{Column, NuDataFrame, NuExpression, NuLazyFrame};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
 };
 use polars::prelude::{Expr, JoinType};
 
@@ -21,7 +23,11 @@ impl Command for LazyJoin {
 
     fn signature(&self) -> Signature {
         Signature::build(self.name())
-            .required("other", SyntaxShape::Any, "LazyFrame to join with")
+            .required(
+                "other",
+                SyntaxShape::Any,
+                "LazyFrame to join with, or the name of a dataframe cached with `dfr cache`",
+            )
             .required("left_on", SyntaxShape::Any, "Left column(s) to join on")
             .required("right_on", SyntaxShape::Any, "Right column(s) to join on")
             .switch(
@@ -159,6 +165,29 @@ impl Command for LazyJoin {
                     .into_value(Span::test_data()),
                 ),
             },
+            Example {
+                description: "Join with a dataframe cached with `dfr cache`",
+                example: r#"let names = ([[id name]; [1 one] [2 two]] | dfr into-df | dfr cache names);
+    [[a b]; [1 x] [2 y]] | dfr into-lazy | dfr join names a id | dfr collect"#,
+                result: Some(
+                    NuDataFrame::try_from_columns(vec![
+                        Column::new(
+                            "a".to_string(),
+                            vec![Value::test_int(1), Value::test_int(2)],
+                        ),
+                        Column::new(
+                            "b".to_string(),
+                            vec![Value::test_string("x"), Value::test_string("y")],
+                        ),
+                        Column::new(
+                            "name".to_string(),
+                            vec![Value::test_string("one"), Value::test_string("two")],
+                        ),
+                    ])
+                    .expect("simple df for test should not fail")
+                    .into_value(Span::test_data()),
+                ),
+            },
         ]
     }
 
@@ -184,7 +213,13 @@ impl Command for LazyJoin {
         };
 
         let other: Value = call.req(engine_state, stack, 0)?;
-        let other = NuLazyFrame::try_from_value(other)?;
+        let other = match other {
+            Value::String { val, span } => {
+                let name = Spanned { item: val, span };
+                NuLazyFrame::from_dataframe(cached_dataframe(engine_state, &name)?)
+            }
+            other => NuLazyFrame::try_from_value(other)?,
+        };
         let other = other.into_polars();
 
         let left_on: Value = call.req(engine_state, stack, 1)?;
@@ -240,9 +275,10 @@ impl Command for LazyJoin {
 mod test {
     use super::super::super::test_dataframe::test_dataframe;
     use super::*;
+    use crate::dataframe::eager::CacheDF;
 
     #[test]
     fn test_examples() {
-        test_dataframe(vec![Box::new(LazyJoin {})])
+        test_dataframe(vec![Box::new(LazyJoin {}), Box::new(CacheDF {})])
     }
 }
@@ -1,17 +1,18 @@
 use super::{DataFrameValue, NuDataFrame};
 
-use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone};
 use indexmap::map::{Entry, IndexMap};
 use nu_protocol::{ShellError, Span, Value};
 use polars::chunked_array::object::builder::ObjectChunkedBuilder;
 use polars::chunked_array::ChunkedArray;
 use polars::prelude::{
-    DataFrame, DataType, DatetimeChunked, Int64Type, IntoSeries, NamedFrom, NewChunkedArray,
-    ObjectType, Series, TemporalMethods, TimeUnit,
+    DataFrame, DataType, DatetimeChunked, DurationChunked, Int64Type, IntoSeries, NamedFrom,
+    NewChunkedArray, ObjectType, Series, TemporalMethods, TimeUnit,
 };
 use std::ops::{Deref, DerefMut};
 
 const SECS_PER_DAY: i64 = 86_400;
+const NANOS_PER_SEC: i64 = 1_000_000_000;
 
 #[derive(Debug)]
 pub struct Column {
@@ -59,7 +60,7 @@ impl DerefMut for Column {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputType {
     Integer,
     Float,
@@ -69,6 +70,25 @@ pub enum InputType {
     Date,
     Duration,
     Filesize,
+    Binary,
+}
+
+impl InputType {
+    // Nulls fit in a column of any type, so they don't have one
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Nothing { .. } => None,
+            Value::Int { .. } => Some(InputType::Integer),
+            Value::Float { .. } => Some(InputType::Float),
+            Value::String { .. } => Some(InputType::String),
+            Value::Bool { .. } => Some(InputType::Boolean),
+            Value::Date { .. } => Some(InputType::Date),
+            Value::Duration { .. } => Some(InputType::Duration),
+            Value::Filesize { .. } => Some(InputType::Filesize),
+            Value::Binary { .. } => Some(InputType::Binary),
+            _ => Some(InputType::Object),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -454,52 +474,15 @@ pub fn create_column(
                 .skip(from_row)
                 .take(size)
                 .map(|v| match v {
-                    Some(a) => {
-                        // elapsed time in day since 1970-01-01
-                        let seconds = a as i64 * SECS_PER_DAY;
-                        let naive_datetime = match NaiveDateTime::from_timestamp_opt(seconds, 0) {
-                            Some(val) => val,
-                            None => {
-                                return Value::Error {
-                                    error: ShellError::UnsupportedInput(
-                                        "The given local datetime representation is invalid."
-                                            .to_string(),
-                                        format!("timestamp is {a:?}"),
-                                        span,
-                                        Span::unknown(),
-                                    ),
-                                }
-                            }
-                        };
-                        // Zero length offset
-                        let offset = match FixedOffset::east_opt(0) {
-                            Some(val) => val,
-                            None => {
-                                return Value::Error {
-                                    error: ShellError::UnsupportedInput(
-                                        "The given local datetime representation is invalid."
-                                            .to_string(),
-                                        format!("timestamp is {a:?}"),
-                                        span,
-                                        Span::unknown(),
-                                    ),
-                                }
-                            }
-                        };
-                        let datetime = DateTime::<FixedOffset>::from_utc(naive_datetime, offset);
-
-                        Value::Date {
-                            val: datetime,
-                            span,
-                        }
-                    }
+                    // elapsed time in day since 1970-01-01
+                    Some(a) => datetime_value(a as i64 * SECS_PER_DAY, 0, None, span),
                     None => Value::Nothing { span },
                 })
                 .collect::<Vec<Value>>();
 
             Ok(Column::new(casted.name().into(), values))
         }
-        DataType::Datetime(time_unit, _) => {
+        DataType::Datetime(time_unit, time_zone) => {
             let casted = series.datetime().map_err(|e| {
                 ShellError::GenericError(
                     "Error casting column to datetime".into(),
@@ -515,48 +498,14 @@ pub fn create_column(
                 .take(size)
                 .map(|v| match v {
                     Some(a) => {
-                        let unit_divisor = match time_unit {
-                            TimeUnit::Nanoseconds => 1_000_000_000,
-                            TimeUnit::Microseconds => 1_000_000,
-                            TimeUnit::Milliseconds => 1_000,
-                        };
                         // elapsed time in nano/micro/milliseconds since 1970-01-01
-                        let seconds = a / unit_divisor;
-                        let naive_datetime = match NaiveDateTime::from_timestamp_opt(seconds, 0) {
-                            Some(val) => val,
-                            None => {
-                                return Value::Error {
-                                    error: ShellError::UnsupportedInput(
-                                        "The given local datetime representation is invalid."
-                                            .to_string(),
-                                        format!("timestamp is {a:?}"),
-                                        span,
-                                        Span::unknown(),
-                                    ),
-                                }
-                            }
-                        };
-                        // Zero length offset
-                        let offset = match FixedOffset::east_opt(0) {
-                            Some(val) => val,
-                            None => {
-                                return Value::Error {
-                                    error: ShellError::UnsupportedInput(
-                                        "The given local datetime representation is invalid."
-                                            .to_string(),
-                                        format!("timestamp is {a:?}"),
-                                        span,
-                                        Span::unknown(),
-                                    ),
-                                }
-                            }
-                        };
-                        let datetime = DateTime::<FixedOffset>::from_utc(naive_datetime, offset);
-
-                        Value::Date {
-                            val: datetime,
+                        let units_per_sec = NANOS_PER_SEC / nanos_per_unit(time_unit);
+                        datetime_value(
+                            a.div_euclid(units_per_sec),
+                            a.rem_euclid(units_per_sec) * nanos_per_unit(time_unit),
+                            time_zone.as_deref(),
                             span,
-                        }
+                        )
                     }
                     None => Value::Nothing { span },
                 })
@@ -564,6 +513,65 @@ pub fn create_column(
 
             Ok(Column::new(casted.name().into(), values))
         }
+        DataType::Duration(time_unit) => {
+            let casted = series.duration().map_err(|e| {
+                ShellError::GenericError(
+                    "Error casting column to duration".into(),
+                    "".to_string(),
+                    None,
+                    Some(e.to_string()),
+                    Vec::new(),
+                )
+            })?;
+
+            let values = casted
+                .into_iter()
+                .skip(from_row)
+                .take(size)
+                .map(|v| match v {
+                    Some(a) => match a.checked_mul(nanos_per_unit(time_unit)) {
+                        Some(val) => Value::Duration { val, span },
+                        None => Value::Error {
+                            error: ShellError::UnsupportedInput(
+                                "The duration does not fit in a nushell duration.".to_string(),
+                                format!("duration is {a:?} {time_unit}"),
+                                span,
+                                Span::unknown(),
+                            ),
+                        },
+                    },
+                    None => Value::Nothing { span },
+                })
+                .collect::<Vec<Value>>();
+
+            Ok(Column::new(casted.name().into(), values))
+        }
+        DataType::Binary => {
+            let casted = series.binary().map_err(|e| {
+                ShellError::GenericError(
+                    "Error casting column to binary".into(),
+                    "".to_string(),
+                    None,
+                    Some(e.to_string()),
+                    Vec::new(),
+                )
+            })?;
+
+            let values = casted
+                .into_iter()
+                .skip(from_row)
+                .take(size)
+                .map(|v| match v {
+                    Some(a) => Value::Binary {
+                        val: a.to_vec(),
+                        span,
+                    },
+                    None => Value::Nothing { span },
+                })
+                .collect::<Vec<Value>>();
+
+            Ok(Column::new(casted.name().into(), values))
+        }
         DataType::Time => {
             let casted = series.timestamp(TimeUnit::Nanoseconds).map_err(|e| {
                 ShellError::GenericError(
@@ -600,6 +608,60 @@ pub fn create_column(
     }
 }
 
+fn nanos_per_unit(time_unit: &TimeUnit) -> i64 {
+    match time_unit {
+        TimeUnit::Nanoseconds => 1,
+        TimeUnit::Microseconds => 1_000,
+        TimeUnit::Milliseconds => 1_000_000,
+    }
+}
+
+// Creates a date from the seconds since 1970-01-01 in UTC, shown in the time zone
+// of the column. Polars time zones are either offsets like +02:00, or names like Europe/Paris
+fn datetime_value(seconds: i64, nanoseconds: i64, time_zone: Option<&str>, span: Span) -> Value {
+    let invalid = |msg: &str, input: String| Value::Error {
+        error: ShellError::UnsupportedInput(msg.to_string(), input, span, Span::unknown()),
+    };
+
+    let naive_datetime = match NaiveDateTime::from_timestamp_opt(seconds, nanoseconds as u32) {
+        Some(val) => val,
+        None => {
+            return invalid(
+                "The given local datetime representation is invalid.",
+                format!("timestamp is {seconds:?}"),
+            )
+        }
+    };
+
+    let offset = match time_zone {
+        None => FixedOffset::east_opt(0),
+        Some(tz) => match tz.parse::<chrono_tz::Tz>() {
+            Ok(tz) => Some(tz.offset_from_utc_datetime(&naive_datetime).fix()),
+            Err(_) => parse_offset(tz),
+        },
+    };
+
+    match offset {
+        Some(offset) => Value::Date {
+            val: DateTime::<FixedOffset>::from_utc(naive_datetime, offset),
+            span,
+        },
+        None => invalid(
+            "The time zone of the datetime column is invalid.",
+            format!("time zone is {time_zone:?}"),
+        ),
+    }
+}
+
+fn parse_offset(offset: &str) -> Option<FixedOffset> {
+    DateTime::parse_from_str(
+        &format!("1970-01-01 00:00:00 {offset}"),
+        "%Y-%m-%d %H:%M:%S %:z",
+    )
+    .ok()
+    .map(|datetime| *datetime.offset())
+}
+
 // Adds a separator to the vector of values using the column names from the
 // dataframe to create the Values Row
 pub fn add_separator(values: &mut Vec<Value>, df: &DataFrame, span: Span) {
@@ -649,50 +711,14 @@ pub fn insert_value(
     };
 
     // Checking that the type for the value is the same
-    // for the previous value in the column
-    if col_val.values.is_empty() {
-        match &value {
-            Value::Int { .. } => {
-                col_val.column_type = Some(InputType::Integer);
-            }
-            Value::Float { .. } => {
-                col_val.column_type = Some(InputType::Float);
-            }
-            Value::String { .. } => {
-                col_val.column_type = Some(InputType::String);
-            }
-            Value::Bool { .. } => {
-                col_val.column_type = Some(InputType::Boolean);
-            }
-            Value::Date { .. } => {
-                col_val.column_type = Some(InputType::Date);
-            }
-            Value::Duration { .. } => {
-                col_val.column_type = Some(InputType::Duration);
-            }
-            Value::Filesize { .. } => {
-                col_val.column_type = Some(InputType::Filesize);
-            }
-            _ => col_val.column_type = Some(InputType::Object),
-        }
-        col_val.values.push(value);
-    } else {
-        let prev_value = &col_val.values[col_val.values.len() - 1];
-
-        match (&prev_value, &value) {
-            (Value::Int { .. }, Value::Int { .. })
-            | (Value::Float { .. }, Value::Float { .. })
-            | (Value::String { .. }, Value::String { .. })
-            | (Value::Bool { .. }, Value::Bool { .. })
-            | (Value::Date { .. }, Value::Date { .. })
-            | (Value::Filesize { .. }, Value::Filesize { .. })
-            | (Value::Duration { .. }, Value::Duration { .. }) => col_val.values.push(value),
-            _ => {
-                col_val.column_type = Some(InputType::Object);
-                col_val.values.push(value);
-            }
-        }
+    // for the previous values in the column
+    match (col_val.column_type, InputType::of(&value)) {
+        (_, None) | (Some(InputType::Object), _) => {}
+        (None, value_type) => col_val.column_type = value_type,
+        (Some(column_type), Some(value_type)) if column_type == value_type => {}
+        _ => col_val.column_type = Some(InputType::Object),
     }
+    col_val.values.push(value);
 
     Ok(())
 }
@@ -703,70 +729,88 @@ pub fn insert_value(
 pub fn from_parsed_columns(column_values: ColumnMap) -> Result<NuDataFrame, ShellError> {
     let mut df_series: Vec<Series> = Vec::new();
     for (name, column) in column_values {
-        if let Some(column_type) = &column.column_type {
-            match column_type {
-                InputType::Float => {
-                    let series_values: Result<Vec<_>, _> =
-                        column.values.iter().map(|v| v.as_f64()).collect();
-                    let series = Series::new(&name, series_values?);
-                    df_series.push(series)
-                }
-                InputType::Integer => {
-                    let series_values: Result<Vec<_>, _> =
-                        column.values.iter().map(|v| v.as_i64()).collect();
-                    let series = Series::new(&name, series_values?);
-                    df_series.push(series)
-                }
-                InputType::Filesize => {
-                    let series_values: Result<Vec<_>, _> =
-                        column.values.iter().map(|v| v.as_i64()).collect();
-                    let series = Series::new(&name, series_values?);
-                    df_series.push(series)
-                }
-                InputType::String => {
-                    let series_values: Result<Vec<_>, _> =
-                        column.values.iter().map(|v| v.as_string()).collect();
-                    let series = Series::new(&name, series_values?);
-                    df_series.push(series)
-                }
-                InputType::Boolean => {
-                    let series_values: Result<Vec<_>, _> =
-                        column.values.iter().map(|v| v.as_bool()).collect();
-                    let series = Series::new(&name, series_values?);
-                    df_series.push(series)
+        match &column.column_type {
+            Some(InputType::Float) => {
+                let series_values = optional_values(&column, |v| v.as_f64())?;
+                df_series.push(Series::new(&name, series_values))
+            }
+            Some(InputType::Integer) | Some(InputType::Filesize) => {
+                let series_values = optional_values(&column, |v| v.as_i64())?;
+                df_series.push(Series::new(&name, series_values))
+            }
+            Some(InputType::String) => {
+                let series_values = optional_values(&column, |v| v.as_string())?;
+                df_series.push(Series::new(&name, series_values))
+            }
+            Some(InputType::Boolean) => {
+                let series_values = optional_values(&column, |v| v.as_bool())?;
+                df_series.push(Series::new(&name, series_values))
+            }
+            Some(InputType::Binary) => {
+                let series_values = optional_values(&column, |v| v.as_binary().map(Vec::from))?;
+                let series_values: Vec<Option<&[u8]>> =
+                    series_values.iter().map(|v| v.as_deref()).collect();
+                df_series.push(Series::new(&name, series_values))
+            }
+            Some(InputType::Object) => {
+                let mut builder =
+                    ObjectChunkedBuilder::<DataFrameValue>::new(&name, column.values.len());
+
+                for v in &column.values {
+                    builder.append_value(DataFrameValue::new(v.clone()));
                 }
-                InputType::Object => {
-                    let mut builder =
-                        ObjectChunkedBuilder::<DataFrameValue>::new(&name, column.values.len());
 
-                    for v in &column.values {
-                        builder.append_value(DataFrameValue::new(v.clone()));
-                    }
+                let res = builder.finish();
+                df_series.push(res.into_series())
+            }
+            Some(InputType::Date) => {
+                let series_values = optional_values(&column, |v| match v {
+                    Value::Date { val, span } => val
+                        .timestamp()
+                        .checked_mul(NANOS_PER_SEC)
+                        .and_then(|nanos| nanos.checked_add(val.timestamp_subsec_nanos() as i64))
+                        .ok_or_else(|| {
+                            ShellError::CantConvert(
+                                "datetime in nanoseconds".into(),
+                                "date".into(),
+                                *span,
+                                Some("dataframes support dates between 1677 and 2262".into()),
+                            )
+                        }),
+                    _ => v.as_i64(),
+                })?;
+
+                // The offset of the dates is kept as the time zone of the column when they
+                // all share it; otherwise the dates are shown in UTC
+                let mut offsets = column.values.iter().filter_map(|v| match v {
+                    Value::Date { val, .. } => Some(val.offset().local_minus_utc()),
+                    _ => None,
+                });
+                let first_offset = offsets.next().unwrap_or_default();
+                let time_zone = if first_offset != 0 && offsets.all(|o| o == first_offset) {
+                    FixedOffset::east_opt(first_offset).map(|offset| offset.to_string())
+                } else {
+                    None
+                };
+
+                let res: DatetimeChunked =
+                    ChunkedArray::<Int64Type>::from_iter_options(&name, series_values.into_iter())
+                        .into_datetime(TimeUnit::Nanoseconds, time_zone);
+
+                df_series.push(res.into_series())
+            }
+            Some(InputType::Duration) => {
+                let series_values = optional_values(&column, |v| v.as_i64())?;
+                let res: DurationChunked =
+                    ChunkedArray::<Int64Type>::from_iter_options(&name, series_values.into_iter())
+                        .into_duration(TimeUnit::Nanoseconds);
 
-                    let res = builder.finish();
-                    df_series.push(res.into_series())
-                }
-                InputType::Date => {
-                    let it = column.values.iter().map(|v| {
-                        if let Value::Date { val, .. } = &v {
-                            Some(val.timestamp_millis())
-                        } else {
-                            None
-                        }
-                    });
-
-                    let res: DatetimeChunked =
-                        ChunkedArray::<Int64Type>::from_iter_options(&name, it)
-                            .into_datetime(TimeUnit::Milliseconds, None);
-
-                    df_series.push(res.into_series())
-                }
-                InputType::Duration => {
-                    let series_values: Result<Vec<_>, _> =
-                        column.values.iter().map(|v| v.as_i64()).collect();
-                    let series = Series::new(&name, series_values?);
-                    df_series.push(series)
-                }
+                df_series.push(res.into_series())
+            }
+            None => {
+                let mut series = Series::full_null(&name, column.values.len(), &DataType::Null);
+                series.rename(&name);
+                df_series.push(series)
             }
         }
     }
@@ -783,3 +827,19 @@ pub fn from_parsed_columns(column_values: ColumnMap) -> Result<NuDataFrame, Shel
             )
         })
 }
+
+// Extracts the values of a column with nulls kept as missing values, so that
+// nothing becomes null in the dataframe and not an empty string or a zero
+fn optional_values<T, F>(column: &TypedColumn, extract: F) -> Result<Vec<Option<T>>, ShellError>
+where
+    F: Fn(&Value) -> Result<T, ShellError>,
+{
+    column
+        .values
+        .iter()
+        .map(|v| match v {
+            Value::Nothing { .. } => Ok(None),
+            v => extract(v).map(Some),
+        })
+        .collect()
+}
@@ -501,6 +501,16 @@ impl NuDataFrame {
                     Ok(series) => series,
                     Err(_) => return None,
                 },
+                // Dates created from nushell values are kept in nanoseconds, whereas polars
+                // may create them with other time units
+                DataType::Datetime(..)
+                    if matches!(other_series.dtype(), DataType::Datetime(..)) =>
+                {
+                    match self_series.cast(other_series.dtype()) {
+                        Ok(series) => series,
+                        Err(_) => return None,
+                    }
+                }
                 _ => self_series.clone(),
             };
 
@@ -10,7 +10,7 @@ pub fn create_default_context() -> EngineState {
 
         macro_rules! bind_command {
             ( $( $command:expr ),* $(,)? ) => {
-                $( working_set.add_decl(Box::new($command)); )*
+                $( working_set.add_untyped_decl(Box::new($command)); )*
             };
         }
 
@@ -69,6 +69,7 @@ pub fn create_default_context() -> EngineState {
             Module,
             Mut,
             Return,
+            RunWithLimit,
             Try,
             Use,
             Version,
@@ -82,6 +83,7 @@ pub fn create_default_context() -> EngineState {
 
         // Filters
         bind_command! {
+            Aggregate,
             All,
             Any,
             Append,
@@ -106,12 +108,14 @@ pub fn create_default_context() -> EngineState {
             GroupBy,
             Headers,
             Insert,
+            Join,
             SplitBy,
             Take,
             Merge,
             Move,
             TakeWhile,
             TakeUntil,
+            Tee,
             Last,
             Length,
             Lines,
@@ -136,7 +140,9 @@ pub fn create_default_context() -> EngineState {
             Sort,
             SortBy,
             SplitList,
+            Toposort,
             Transpose,
+            Unflatten,
             Uniq,
             UniqBy,
             Upsert,
@@ -151,6 +157,8 @@ pub fn create_default_context() -> EngineState {
 
         // Misc
         bind_command! {
+            DebugEnvUsage,
+            DebugUsage,
             History,
             Tutor,
             HistorySession,
@@ -159,13 +167,16 @@ pub fn create_default_context() -> EngineState {
         // Path
         bind_command! {
             Path,
+            PathAdd,
             PathBasename,
+            PathDedupe,
             PathDirname,
             PathExists,
             PathExpand,
             PathJoin,
             PathParse,
             PathRelativeTo,
+            PathRemove,
             PathSplit,
             PathType,
         };
@@ -174,6 +185,7 @@ pub fn create_default_context() -> EngineState {
         bind_command! {
             Benchmark,
             Complete,
+            ExecBatch,
             Explain,
             External,
             NuCheck,
@@ -205,9 +217,15 @@ pub fn create_default_context() -> EngineState {
             DecodeBase64,
             EncodeBase64,
             DetectColumns,
+            Diff,
+            DiffText,
             Format,
             FileSize,
+            FormatDuration,
+            FormatNumber,
             Parse,
+            Patch,
+            PatchApply,
             Size,
             Split,
             SplitChars,
@@ -219,23 +237,33 @@ pub fn create_default_context() -> EngineState {
             StrCapitalize,
             StrCollect,
             StrContains,
+            StrDedent,
             StrDistance,
             StrDowncase,
             StrEndswith,
+            StrExpand,
             StrJoin,
+            StrJustify,
             StrReplace,
             StrIndexOf,
+            StrIndent,
             StrKebabCase,
             StrLength,
+            StrMatches,
             StrPascalCase,
             StrReverse,
             StrScreamingSnakeCase,
+            StrSimilarity,
+            StrSlug,
             StrSnakeCase,
             StrStartsWith,
             StrSubstring,
+            StrTemplate,
+            StrTransliterate,
             StrTrim,
             StrTitleCase,
-            StrUpcase
+            StrUpcase,
+            StrWrap
         };
 
         // Bits
@@ -278,6 +306,7 @@ pub fn create_default_context() -> EngineState {
             Start,
             Rm,
             Save,
+            Search,
             Touch,
             Glob,
             Watch,
@@ -289,6 +318,7 @@ pub fn create_default_context() -> EngineState {
             AnsiGradient,
             AnsiStrip,
             AnsiLink,
+            AnsiParse,
             Clear,
             Du,
             KeybindingsDefault,
@@ -297,6 +327,8 @@ pub fn create_default_context() -> EngineState {
             Keybindings,
             Kill,
             KeybindingsList,
+            Signal,
+            SignalTrap,
             Sleep,
             TermSize,
         };
@@ -363,6 +395,7 @@ pub fn create_default_context() -> EngineState {
         bind_command! {
             Griddle,
             Table,
+            Tree,
             Explore,
         };
 
@@ -380,6 +413,7 @@ pub fn create_default_context() -> EngineState {
             IntoInt,
             IntoRecord,
             IntoString,
+            IntoUnit,
         };
 
         // Env
@@ -401,17 +435,50 @@ pub fn create_default_context() -> EngineState {
             Math,
             MathAbs,
             MathAvg,
+            MathBig,
+            MathBigAdd,
+            MathBigDiv,
+            MathBigFactorial,
+            MathBigMul,
+            MathBigPow,
+            MathBigSub,
+            MathBigSum,
+            MathBin,
             MathCeil,
+            MathComplex,
+            MathComplexAbs,
+            MathComplexArg,
+            MathComplexConj,
+            MathComplexExp,
+            MathComplexMul,
+            MathConvert,
+            MathCummax,
+            MathCummin,
+            MathCumprod,
+            MathCumsum,
+            MathFft,
+            MathFit,
             MathFloor,
+            MathIfft,
+            MathInterpolate,
+            MathMatrix,
+            MathMatrixDeterminant,
+            MathMatrixInvert,
+            MathMatrixMultiply,
+            MathMatrixSolve,
+            MathMatrixTranspose,
             MathMax,
             MathMedian,
             MathMin,
             MathMode,
             MathProduct,
+            MathQuantile,
             MathRound,
             MathSqrt,
+            MathStats,
             MathStddev,
             MathSum,
+            MathUnits,
             MathVariance,
             MathSin,
             MathCos,
@@ -434,15 +501,39 @@ pub fn create_default_context() -> EngineState {
 
         // Network
         bind_command! {
+            Dns,
+            DnsQuery,
+            FetchModule,
             Http,
             HttpGet,
             HttpPost,
+            HttpServe,
+            HttpSessionCreate,
             Url,
             UrlBuildQuery,
             UrlEncode,
             UrlJoin,
             UrlParse,
             Port,
+            PortListen,
+            PortScan,
+            Smtp,
+            SmtpSend,
+            Tcp,
+            TcpConnect,
+            TcpListen,
+            Udp,
+            UdpSend,
+            Ws,
+            WsConnect,
+        }
+
+        #[cfg(feature = "sftp")]
+        bind_command! {
+            Sftp,
+            SftpGet,
+            SftpLs,
+            SftpPut,
         }
 
         // Random
@@ -453,6 +544,9 @@ pub fn create_default_context() -> EngineState {
             RandomDecimal,
             RandomDice,
             RandomInteger,
+            RandomNormal,
+            RandomSample,
+            RandomShuffle,
             RandomUuid,
         };
 
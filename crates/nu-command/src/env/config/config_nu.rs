@@ -1,11 +1,11 @@
 use nu_engine::env_to_strings;
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, PipelineData, ShellError, Signature, Span, Spanned, Type,
 };
 
-use crate::ExternalCommand;
+use crate::{ExternalCommand, OutputEncoding};
 
 use super::utils::get_editor;
 
@@ -28,6 +28,10 @@ impl Command for ConfigNu {
         "Edit nu configurations"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![Example {
             description: "allow user to open and update nu config",
@@ -78,6 +82,12 @@ impl Command for ConfigNu {
             redirect_stderr: false,
             env_vars: env_vars_str,
             trim_end_newline: false,
+            stdin_from: None,
+            close_stdin: false,
+            stdout_log: None,
+            stderr_log: None,
+            log_max_size: None,
+            output_encoding: OutputEncoding::default(),
         };
 
         command.run_with_input(engine_state, stack, input, true)
@@ -1,7 +1,7 @@
 use chrono::Local;
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, PipelineData, ShellError, Signature, Type,
 };
 use nu_utils::{get_default_config, get_default_env};
@@ -29,6 +29,10 @@ impl Command for ConfigReset {
         "Reset nushell environment configurations to default, and saves old config files in the config location as oldconfig.nu and oldenv.nu"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![Example {
             description: "reset nushell configuration files",
@@ -1,4 +1,4 @@
-use nu_engine::{eval_expression_with_input, CallExt};
+use nu_engine::{convert_assigned_env_value, eval_expression_with_input, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
@@ -57,6 +57,7 @@ impl Command for LetEnv {
                 env_var.span,
             ));
         } else {
+            let rhs = convert_assigned_env_value(engine_state, stack, &env_var.item, rhs)?;
             stack.add_env_var(env_var.item, rhs);
         }
         Ok(PipelineData::empty())
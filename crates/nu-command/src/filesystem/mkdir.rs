@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 use nu_engine::env::current_dir;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature,
     SyntaxShape, Type, Value,
@@ -33,6 +33,10 @@ impl Command for Mkdir {
         "Make directories, creates intermediary directories as required."
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["directory", "folder", "create", "make_dirs"]
     }
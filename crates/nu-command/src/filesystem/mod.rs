@@ -8,6 +8,7 @@ mod mv;
 mod open;
 mod rm;
 mod save;
+mod search;
 mod start;
 mod touch;
 mod util;
@@ -23,6 +24,7 @@ pub use mkdir::Mkdir;
 pub use mv::Mv;
 pub use rm::Rm;
 pub use save::Save;
+pub use search::Search;
 pub use start::Start;
 pub use touch::Touch;
 pub use watch::Watch;
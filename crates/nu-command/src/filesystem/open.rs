@@ -3,10 +3,11 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::util::BufferedReader;
 use nu_protocol::{
-    Category, Example, PipelineData, RawStream, ShellError, Signature, Spanned, SyntaxShape, Type,
-    Value,
+    Category, Example, ListStream, PipelineData, RawStream, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
 };
-use std::io::BufReader;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
 
 #[cfg(feature = "sqlite")]
 use crate::database::SQLiteDatabase;
@@ -39,9 +40,27 @@ impl Command for Open {
             .input_output_types(vec![(Type::Nothing, Type::Any), (Type::String, Type::Any)])
             .optional("filename", SyntaxShape::Filepath, "the filename to use")
             .switch("raw", "open file as raw binary", Some('r'))
+            .switch(
+                "per-line",
+                "stream the lines of the file as records with their byte offset and length",
+                Some('l'),
+            )
+            .named(
+                "start",
+                SyntaxShape::Int,
+                "with --per-line, start reading at this byte offset",
+                Some('s'),
+            )
             .category(Category::FileSystem)
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --per-line, the file is read lazily one line at a time, so huge files can be
+searched without loading them. Lines that aren't valid UTF-8 are decoded lossily, and the length
+counts the bytes of a line without its line ending. A line ends at `offset + length`, and
+`--start` with the offset of a line resumes reading from it."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -50,6 +69,8 @@ impl Command for Open {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let raw = call.has_flag("raw");
+        let per_line = call.has_flag("per-line");
+        let start: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "start")?;
         let call_span = call.head;
         let ctrlc = engine_state.ctrlc.clone();
         let path = call.opt::<Spanned<String>>(engine_state, stack, 0)?;
@@ -109,8 +130,20 @@ impl Command for Open {
                 Vec::new(),
             ))
         } else {
+            if let Some(start) = &start {
+                if !per_line {
+                    return Err(ShellError::IncompatibleParametersSingle(
+                        "--start needs --per-line".into(),
+                        start.span,
+                    ));
+                }
+                if start.item < 0 {
+                    return Err(ShellError::NeedsPositiveValue(start.span));
+                }
+            }
+
             #[cfg(feature = "sqlite")]
-            if !raw {
+            if !raw && !per_line {
                 let res = SQLiteDatabase::try_from_path(path, arg_span, ctrlc.clone())
                     .map(|db| db.into_value(call.head).into_pipeline_data());
 
@@ -132,6 +165,11 @@ impl Command for Open {
                 }
             };
 
+            if per_line {
+                let start = start.map_or(0, |start| start.item as u64);
+                return open_per_line(file, start, arg_span, call_span, ctrlc);
+            }
+
             let buf_reader = BufReader::new(file);
 
             let output = PipelineData::ExternalStream {
@@ -143,6 +181,7 @@ impl Command for Open {
                 )),
                 stderr: None,
                 exit_code: None,
+                resource_usage: None,
                 span: call_span,
                 metadata: None,
                 trim_end_newline: false,
@@ -205,10 +244,97 @@ impl Command for Open {
                 example: "open myfile.txt --raw | decode utf-8",
                 result: None,
             },
+            Example {
+                description: "Find where errors are logged in a huge file, without loading it",
+                example: "open app.log --per-line | where line =~ ERROR | select offset line",
+                result: None,
+            },
+            Example {
+                description: "Continue reading a file from a known line",
+                example: "open app.log --per-line --start 1048576 | first 10",
+                result: None,
+            },
         ]
     }
 }
 
+fn open_per_line(
+    mut file: File,
+    start: u64,
+    arg_span: Span,
+    call_span: Span,
+    ctrlc: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+) -> Result<PipelineData, ShellError> {
+    file.seek(SeekFrom::Start(start)).map_err(|err| {
+        ShellError::GenericError(
+            "Could not seek in file".into(),
+            err.to_string(),
+            Some(arg_span),
+            None,
+            Vec::new(),
+        )
+    })?;
+
+    let lines = LineOffsets {
+        reader: Some(BufReader::new(file)),
+        offset: start,
+        span: call_span,
+    };
+    Ok(PipelineData::ListStream(
+        ListStream::from_stream(lines, ctrlc),
+        None,
+    ))
+}
+
+/// Reads lines lazily along with the byte offsets they start at
+struct LineOffsets {
+    // Dropped after the end of the file or a read error
+    reader: Option<BufReader<File>>,
+    offset: u64,
+    span: Span,
+}
+
+impl Iterator for LineOffsets {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reader = self.reader.as_mut()?;
+        let mut buf = vec![];
+        let read = match reader.read_until(b'\n', &mut buf) {
+            Ok(0) => {
+                self.reader = None;
+                return None;
+            }
+            Ok(read) => read,
+            Err(err) => {
+                self.reader = None;
+                return Some(Value::Error {
+                    error: ShellError::IOError(err.to_string()),
+                });
+            }
+        };
+
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+            if buf.last() == Some(&b'\r') {
+                buf.pop();
+            }
+        }
+        let offset = self.offset;
+        self.offset += read as u64;
+
+        Some(Value::Record {
+            cols: vec!["line".into(), "offset".into(), "length".into()],
+            vals: vec![
+                Value::string(String::from_utf8_lossy(&buf), self.span),
+                Value::int(offset as i64, self.span),
+                Value::int(buf.len() as i64, self.span),
+            ],
+            span: self.span,
+        })
+    }
+}
+
 fn permission_denied(dir: impl AsRef<Path>) -> bool {
     match dir.as_ref().read_dir() {
         Err(e) => matches!(e.kind(), std::io::ErrorKind::PermissionDenied),
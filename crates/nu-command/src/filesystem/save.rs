@@ -1,13 +1,14 @@
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, RawStream, ShellError, Signature, Span, Spanned, SyntaxShape,
-    Type, Value,
+    Category, Example, IntoPipelineData, PipelineData, RawStream, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Type, Value,
 };
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::thread;
 
 use crate::progress_bar;
@@ -24,6 +25,10 @@ impl Command for Save {
         "Save a file."
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec![
             "write",
@@ -39,8 +44,11 @@ impl Command for Save {
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("save")
-            .input_output_types(vec![(Type::Any, Type::Nothing)])
-            .required("filename", SyntaxShape::Filepath, "the filename to use")
+            .input_output_types(vec![
+                (Type::Any, Type::Nothing),
+                (Type::Table(vec![]), Type::Table(vec![])),
+            ])
+            .optional("filename", SyntaxShape::Filepath, "the filename to use")
             .named(
                 "stderr",
                 SyntaxShape::Filepath,
@@ -51,9 +59,32 @@ impl Command for Save {
             .switch("append", "append input to the end of the file", Some('a'))
             .switch("force", "overwrite the destination", Some('f'))
             .switch("progress", "enable progress bar", Some('p'))
+            .named(
+                "into-dir",
+                SyntaxShape::Directory,
+                "save each row of a table of name and content records to its own file in this directory",
+                Some('d'),
+            )
+            .switch(
+                "skip",
+                "with --into-dir, leave files that exist or were already written untouched",
+                Some('s'),
+            )
+            .switch(
+                "rename",
+                "with --into-dir, save to a numbered name like `a-1.txt` when the name is taken",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --into-dir, the names can contain subdirectories, which are created as needed.
+A name that is taken, by an existing file or by an earlier row, is an error unless one of
+--force, --append, --skip or --rename says what to do about it. Every row is checked and
+converted before anything is written, and a table of what happened to each row is returned."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -68,7 +99,23 @@ impl Command for Save {
 
         let span = call.head;
 
-        let path = call.req::<Spanned<String>>(engine_state, stack, 0)?;
+        let path = call.opt::<Spanned<String>>(engine_state, stack, 0)?;
+        if let Some(dir) = call.get_flag::<Spanned<String>>(engine_state, stack, "into-dir")? {
+            if let Some(path) = path {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "can't save to a single file".into(),
+                    left_span: path.span,
+                    right_message: "when saving into a directory".into(),
+                    right_span: dir.span,
+                });
+            }
+            let conflict = Conflict::from_call(call)?;
+            return save_into_dir(input, dir, conflict, raw, engine_state, stack, span);
+        }
+        let path = match path {
+            Some(path) => path,
+            None => return Err(ShellError::MissingParameter("filename".into(), span)),
+        };
         let stderr_path = call.get_flag::<Spanned<String>>(engine_state, stack, "stderr")?;
 
         match input {
@@ -171,10 +218,242 @@ impl Command for Save {
                 example: r#"do -i {} | save foo.txt --stderr bar.txt"#,
                 result: None,
             },
+            Example {
+                description: "Save each row of a table to its own file, numbering repeated names",
+                example: r#"[[name content]; [a.txt hello] [b/c.json {x: 1}] [a.txt again]] | save --into-dir out --rename"#,
+                result: None,
+            },
         ]
     }
 }
 
+/// What to do when the name of a row is taken by an existing file or an earlier row
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Conflict {
+    Error,
+    Overwrite,
+    Append,
+    Skip,
+    Rename,
+}
+
+impl Conflict {
+    fn from_call(call: &Call) -> Result<Conflict, ShellError> {
+        let flags = [
+            ("force", Conflict::Overwrite),
+            ("append", Conflict::Append),
+            ("skip", Conflict::Skip),
+            ("rename", Conflict::Rename),
+        ];
+        let mut chosen: Option<(Spanned<String>, Conflict)> = None;
+        for (name, conflict) in flags {
+            if let Some(flag) = call.get_named_arg(name) {
+                if let Some((first, _)) = &chosen {
+                    return Err(ShellError::IncompatibleParameters {
+                        left_message: format!("--{} chosen here", first.item),
+                        left_span: first.span,
+                        right_message: format!("so --{} can't be used", flag.item),
+                        right_span: flag.span,
+                    });
+                }
+                chosen = Some((flag, conflict));
+            }
+        }
+        Ok(chosen.map_or(Conflict::Error, |(_, conflict)| conflict))
+    }
+}
+
+struct PlannedFile {
+    name: String,
+    path: PathBuf,
+    status: &'static str,
+    content: Option<Vec<u8>>,
+}
+
+/// Save every `{name, content}` row of the input to its own file in `dir`
+fn save_into_dir(
+    input: PipelineData,
+    dir: Spanned<String>,
+    conflict: Conflict,
+    raw: bool,
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    let dir_path = Path::new(&dir.item);
+    let mut taken = HashSet::new();
+    let mut planned = vec![];
+
+    // Check and convert every row before touching the disk, so a bad row doesn't leave half of
+    // the files written
+    for row in input {
+        let row_span = match &row {
+            Value::Record { span, .. } => *span,
+            Value::Error { error } => return Err(error.clone()),
+            other => {
+                return Err(ShellError::OnlySupportsThisInputType(
+                    "table of name and content records".into(),
+                    other.get_type().to_string(),
+                    span,
+                    other.expect_span(),
+                ))
+            }
+        };
+        let name_value = row
+            .get_data_by_key("name")
+            .ok_or_else(|| ShellError::CantFindColumn("name".into(), span, row_span))?;
+        let content = row
+            .get_data_by_key("content")
+            .ok_or_else(|| ShellError::CantFindColumn("content".into(), span, row_span))?;
+        let mut name = name_value.as_string()?;
+        check_relative_name(&name, name_value.expect_span())?;
+
+        let mut path = dir_path.join(&name);
+        let is_repeated = taken.contains(&path);
+        let status = if !is_repeated && !path.exists() {
+            "created"
+        } else {
+            match conflict {
+                Conflict::Error | Conflict::Overwrite if is_repeated => {
+                    return Err(ShellError::GenericError(
+                        "Name given more than once".into(),
+                        format!("'{name}' was already saved by an earlier row"),
+                        Some(name_value.expect_span()),
+                        Some("you can use --append, --skip or --rename to keep both".into()),
+                        Vec::new(),
+                    ))
+                }
+                Conflict::Error => {
+                    return Err(ShellError::GenericError(
+                        "Destination file already exists".into(),
+                        format!("Destination file '{}' already exists", path.to_string_lossy()),
+                        Some(name_value.expect_span()),
+                        Some(
+                            "you can use --force, --append, --skip or --rename to choose what happens to it"
+                                .into(),
+                        ),
+                        Vec::new(),
+                    ))
+                }
+                Conflict::Overwrite => "overwritten",
+                Conflict::Append => "appended",
+                Conflict::Skip => "skipped",
+                Conflict::Rename => {
+                    name = free_name(&name, dir_path, &taken);
+                    path = dir_path.join(&name);
+                    "created"
+                }
+            }
+        };
+
+        let content = if status == "skipped" {
+            None
+        } else {
+            Some(input_to_bytes(
+                PipelineData::Value(content, None),
+                &path,
+                raw,
+                engine_state,
+                stack,
+                span,
+            )?)
+        };
+        taken.insert(path.clone());
+        planned.push(PlannedFile {
+            name,
+            path,
+            status,
+            content,
+        });
+    }
+
+    let mut results = vec![];
+    for file in planned {
+        let size = match &file.content {
+            Some(content) => {
+                if let Some(parent) = file.path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|err| {
+                        ShellError::GenericError(
+                            "Could not create directory".into(),
+                            err.to_string(),
+                            Some(dir.span),
+                            None,
+                            Vec::new(),
+                        )
+                    })?;
+                }
+                let mut out = open_file(&file.path, dir.span, file.status == "appended")?;
+                out.write_all(content)
+                    .map_err(|err| ShellError::IOError(err.to_string()))?;
+                out.flush()?;
+                content.len() as i64
+            }
+            None => 0,
+        };
+        results.push(Value::Record {
+            cols: vec!["name".into(), "status".into(), "size".into()],
+            vals: vec![
+                Value::string(file.name, span),
+                Value::string(file.status, span),
+                Value::Filesize { val: size, span },
+            ],
+            span,
+        });
+    }
+
+    Ok(Value::List {
+        vals: results,
+        span,
+    }
+    .into_pipeline_data())
+}
+
+/// Names have to stay inside the directory they're saved into
+fn check_relative_name(name: &str, span: Span) -> Result<(), ShellError> {
+    let path = Path::new(name);
+    let escapes = path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if name.is_empty() || escapes {
+        Err(ShellError::GenericError(
+            "Invalid file name".into(),
+            format!("'{name}' isn't a relative path inside the directory"),
+            Some(span),
+            None,
+            Vec::new(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The first of `stem-1.ext`, `stem-2.ext`, ... that is neither on disk nor taken by a row
+fn free_name(name: &str, dir: &Path, taken: &HashSet<PathBuf>) -> String {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let parent = path.parent().unwrap_or_else(|| Path::new(""));
+
+    (1..)
+        .map(|n| {
+            parent
+                .join(format!("{stem}-{n}{extension}"))
+                .to_string_lossy()
+                .to_string()
+        })
+        .find(|candidate| {
+            let candidate = dir.join(candidate);
+            !candidate.exists() && !taken.contains(&candidate)
+        })
+        .expect("there is always a free name")
+}
+
 /// Convert [`PipelineData`] bytes to write in file, possibly converting
 /// to format of output file
 fn input_to_bytes(
@@ -0,0 +1,475 @@
+use fancy_regex::Regex;
+use nu_engine::env::current_dir;
+use nu_engine::CallExt;
+use nu_glob::{MatchOptions, Pattern};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Type, Value,
+};
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+#[derive(Clone)]
+pub struct Search;
+
+impl Command for Search {
+    fn name(&self) -> &str {
+        "search"
+    }
+
+    fn usage(&self) -> &str {
+        "Search the lines of files for a regular expression."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Directories are searched recursively and files are searched in parallel, so the order of
+the matches can change from run to run. Hidden files, binary files and the files ignored by
+.gitignore files are skipped unless asked for. Each matching line gives a record with the
+captures of the first match in the line, named like in `parse --regex`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["grep", "rg", "ripgrep", "find", "regex", "match"]
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("search")
+            .input_output_types(vec![(Type::Nothing, Type::Table(vec![]))])
+            .required(
+                "pattern",
+                SyntaxShape::String,
+                "the regular expression to search for",
+            )
+            .rest(
+                "paths",
+                SyntaxShape::Filepath,
+                "the files and directories to search (default: the current directory)",
+            )
+            .named(
+                "glob",
+                SyntaxShape::String,
+                "only search files whose name, or path if the glob has a /, matches this glob",
+                Some('g'),
+            )
+            .switch("ignore-case", "match case insensitively", Some('i'))
+            .switch(
+                "fixed-strings",
+                "treat the pattern as a literal string instead of a regular expression",
+                Some('f'),
+            )
+            .switch("hidden", "also search hidden files and directories", None)
+            .switch(
+                "no-ignore",
+                "also search files ignored by .gitignore files",
+                None,
+            )
+            .named(
+                "max-depth",
+                SyntaxShape::Int,
+                "how many levels below the given paths to search, 1 being their direct children",
+                Some('d'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let pattern: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let paths: Vec<Spanned<String>> = call.rest(engine_state, stack, 1)?;
+        let glob: Option<Spanned<String>> = call.get_flag(engine_state, stack, "glob")?;
+        let max_depth: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "max-depth")?;
+        let cwd = current_dir(engine_state, stack)?;
+
+        let mut source = if call.has_flag("fixed-strings") {
+            fancy_regex::escape(&pattern.item).to_string()
+        } else {
+            pattern.item.clone()
+        };
+        if call.has_flag("ignore-case") {
+            source = format!("(?i){source}");
+        }
+        let regex = Regex::new(&source).map_err(|e| {
+            ShellError::GenericError(
+                "Error with regular expression".into(),
+                e.to_string(),
+                Some(pattern.span),
+                None,
+                Vec::new(),
+            )
+        })?;
+
+        let glob = match glob {
+            Some(glob) => Some(Pattern::new(&glob.item).map_err(|e| {
+                ShellError::GenericError(
+                    "Error with glob pattern".into(),
+                    e.msg.to_string(),
+                    Some(glob.span),
+                    None,
+                    Vec::new(),
+                )
+            })?),
+            None => None,
+        };
+        let max_depth = match max_depth {
+            Some(depth) if depth.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(depth.span))
+            }
+            depth => depth.map(|depth| depth.item as usize),
+        };
+
+        let roots = if paths.is_empty() {
+            vec![cwd.clone()]
+        } else {
+            paths
+                .into_iter()
+                .map(|path| {
+                    let expanded = nu_path::expand_path_with(&path.item, &cwd);
+                    if expanded.exists() {
+                        Ok(expanded)
+                    } else {
+                        Err(ShellError::DirectoryNotFound(path.span, None))
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let options = WalkOptions {
+            glob,
+            hidden: call.has_flag("hidden"),
+            no_ignore: call.has_flag("no-ignore"),
+            max_depth,
+        };
+        let searcher = Searcher {
+            columns: column_names(&regex),
+            regex,
+            cwd,
+            span: head,
+        };
+        let ctrlc = engine_state.ctrlc.clone();
+        let worker_ctrlc = ctrlc.clone();
+
+        // The walk and the search run on another thread, so the matches stream out as they are
+        // found
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("search".into())
+            .spawn(move || {
+                let mut files = vec![];
+                for root in roots {
+                    // Roots are followed even when they are symlinks, unlike what is under them
+                    let is_dir = root.is_dir();
+                    walk(&root, is_dir, &options, &mut vec![], 0, &mut files);
+                }
+                files.par_iter().for_each_with(sender, |sender, file| {
+                    if nu_utils::ctrl_c::was_pressed(&worker_ctrlc) {
+                        return;
+                    }
+                    for value in searcher.search_file(file) {
+                        if sender.send(value).is_err() {
+                            return;
+                        }
+                    }
+                });
+            })
+            .map_err(|e| ShellError::IOError(e.to_string()))?;
+
+        Ok(receiver.into_iter().into_pipeline_data(ctrlc))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Find the TODOs in the Rust files of a project",
+                example: "search TODO ./src --glob '*.rs'",
+                result: None,
+            },
+            Example {
+                description: "List the names of the functions defined in Rust files",
+                example: r#"search 'fn (?P<name>\w+)' --glob '*.rs' | get captures.name"#,
+                result: None,
+            },
+            Example {
+                description: "Search a literal string case insensitively, including ignored files",
+                example: "search -f -i 'a.b(' --no-ignore",
+                result: None,
+            },
+        ]
+    }
+}
+
+struct WalkOptions {
+    glob: Option<Pattern>,
+    hidden: bool,
+    no_ignore: bool,
+    max_depth: Option<usize>,
+}
+
+/// Collect the files to search under `path`, following the .gitignore files found on the way
+fn walk(
+    path: &Path,
+    is_dir: bool,
+    options: &WalkOptions,
+    rules: &mut Vec<IgnoreRule>,
+    depth: usize,
+    files: &mut Vec<PathBuf>,
+) {
+    if options.max_depth.map_or(false, |max| depth > max) {
+        return;
+    }
+    if !is_dir {
+        // Files given directly are always searched
+        if depth == 0
+            || options
+                .glob
+                .as_ref()
+                .map_or(true, |glob| glob_matches(glob, path))
+        {
+            files.push(path.to_path_buf());
+        }
+        return;
+    }
+
+    let added = if options.no_ignore {
+        0
+    } else {
+        read_gitignore(path, rules)
+    };
+    if let Ok(entries) = std::fs::read_dir(path) {
+        // Symlinks to directories are not followed, so a link to a parent can't loop forever
+        let mut entries: Vec<(PathBuf, bool)> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let file_type = e.file_type().ok()?;
+                if file_type.is_symlink() && e.path().is_dir() {
+                    None
+                } else {
+                    Some((e.path(), file_type.is_dir()))
+                }
+            })
+            .collect();
+        entries.sort();
+        for (entry, is_dir) in entries {
+            let name = entry.file_name().map(|n| n.to_string_lossy().to_string());
+            let name = name.unwrap_or_default();
+            if name == ".git" || (!options.hidden && name.starts_with('.')) {
+                continue;
+            }
+            if is_ignored(rules, &entry) {
+                continue;
+            }
+            walk(&entry, is_dir, options, rules, depth + 1, files);
+        }
+    }
+    rules.truncate(rules.len() - added);
+}
+
+fn glob_matches(glob: &Pattern, path: &Path) -> bool {
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+    if glob.as_str().contains('/') {
+        glob.matches_path_with(path, options)
+            || path.to_str().map_or(false, |p| {
+                glob.matches_with(p.trim_start_matches("./"), options)
+            })
+    } else {
+        path.file_name().map_or(false, |name| {
+            glob.matches_with(&name.to_string_lossy(), options)
+        })
+    }
+}
+
+/// One line of a .gitignore file
+struct IgnoreRule {
+    base: PathBuf,
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    // Patterns with a slash before their end match paths relative to the .gitignore file, the
+    // others match names at any depth
+    anchored: bool,
+}
+
+/// Add the rules of the .gitignore file in `dir`, returning how many were added
+fn read_gitignore(dir: &Path, rules: &mut Vec<IgnoreRule>) -> usize {
+    let contents = match std::fs::read_to_string(dir.join(".gitignore")) {
+        Ok(contents) => contents,
+        Err(_) => return 0,
+    };
+
+    let before = rules.len();
+    for line in contents.lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+        if let Ok(pattern) = Pattern::new(line) {
+            rules.push(IgnoreRule {
+                base: dir.to_path_buf(),
+                pattern,
+                negated,
+                dir_only,
+                anchored,
+            });
+        }
+    }
+    rules.len() - before
+}
+
+fn is_ignored(rules: &[IgnoreRule], path: &Path) -> bool {
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+    let is_dir = path.is_dir();
+
+    // The last rule that matches decides
+    let mut ignored = false;
+    for rule in rules {
+        if rule.dir_only && !is_dir {
+            continue;
+        }
+        let matches = if rule.anchored {
+            path.strip_prefix(&rule.base).map_or(false, |relative| {
+                rule.pattern.matches_path_with(relative, options)
+            })
+        } else {
+            path.file_name().map_or(false, |name| {
+                rule.pattern.matches_with(&name.to_string_lossy(), options)
+            })
+        };
+        if matches {
+            ignored = !rule.negated;
+        }
+    }
+    ignored
+}
+
+struct Searcher {
+    regex: Regex,
+    columns: Vec<String>,
+    cwd: PathBuf,
+    span: Span,
+}
+
+impl Searcher {
+    fn search_file(&self, path: &Path) -> Vec<Value> {
+        let mut reader = match File::open(path) {
+            Ok(file) => BufReader::new(file),
+            Err(_) => return vec![],
+        };
+        if is_binary(&mut reader) {
+            return vec![];
+        }
+
+        let display_path = path
+            .strip_prefix(&self.cwd)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+        let mut matches = vec![];
+        let mut buf = vec![];
+        let mut line_number = 0;
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => line_number += 1,
+            }
+            let line = String::from_utf8_lossy(&buf);
+            let line = line.trim_end_matches(|c| c == '\n' || c == '\r');
+            let captures = match self.regex.captures(line) {
+                Ok(Some(captures)) => captures,
+                Ok(None) => continue,
+                Err(error) => {
+                    matches.push(Value::Error {
+                        error: ShellError::GenericError(
+                            "Error with regular expression captures".into(),
+                            error.to_string(),
+                            Some(self.span),
+                            None,
+                            Vec::new(),
+                        ),
+                    });
+                    break;
+                }
+            };
+
+            let vals = captures
+                .iter()
+                .skip(1)
+                .map(|cap| {
+                    let cap = cap.map(|c| c.as_str()).unwrap_or("");
+                    Value::string(cap, self.span)
+                })
+                .collect();
+            matches.push(Value::Record {
+                cols: vec![
+                    "path".into(),
+                    "line_number".into(),
+                    "line".into(),
+                    "captures".into(),
+                ],
+                vals: vec![
+                    Value::string(display_path.clone(), self.span),
+                    Value::int(line_number, self.span),
+                    Value::string(line, self.span),
+                    Value::Record {
+                        cols: self.columns.clone(),
+                        vals,
+                        span: self.span,
+                    },
+                ],
+                span: self.span,
+            });
+        }
+        matches
+    }
+}
+
+/// Files with a NUL byte near their start are taken to be binary, like git and ripgrep do
+fn is_binary(reader: &mut BufReader<File>) -> bool {
+    let mut start = [0; 8192];
+    let read = match reader.get_mut().read(&mut start) {
+        Ok(read) => read,
+        Err(_) => return true,
+    };
+    let binary = start[..read].contains(&0);
+    // Go back to the start for reading the lines
+    let _ = std::io::Seek::rewind(reader.get_mut());
+    binary
+}
+
+fn column_names(regex: &Regex) -> Vec<String> {
+    regex
+        .capture_names()
+        .enumerate()
+        .skip(1)
+        .map(|(i, name)| {
+            name.map(String::from)
+                .unwrap_or_else(|| format!("capture{}", i - 1))
+        })
+        .collect()
+}
@@ -1,10 +1,17 @@
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
+    Type, Value,
 };
+use std::ffi::OsStr;
 use std::path::Path;
+use std::process::{Child, Stdio};
+use std::time::Duration;
+
+// How often waiting for the application checks for ctrl-c
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Clone)]
 pub struct Start;
@@ -18,14 +25,47 @@ impl Command for Start {
         "Open a folder or file in the default application or viewer."
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Returns a record with the PID of the launched process. With --wait, the command returns once
+that process exits, and the record has its exit code as well.
+
+When no application is given, the file is handed to the launcher of the system: xdg-open (or
+one of its alternatives) and open on macOS. The PID and exit code are those of the launcher
+then, which may return before the application it starts does. On Windows, the file is opened
+through the shell, which gives no PID, so --wait needs --with there."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["load", "folder", "directory", "run", "open"]
+        vec![
+            "load",
+            "folder",
+            "directory",
+            "run",
+            "open",
+            "launch",
+            "application",
+        ]
     }
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("start")
             .input_output_types(vec![(Type::Nothing, Type::Any), (Type::String, Type::Any)])
             .optional("filepath", SyntaxShape::Filepath, "the filepath to open")
+            .switch(
+                "wait",
+                "wait for the application to exit, and return its exit code",
+                Some('w'),
+            )
+            .named(
+                "with",
+                SyntaxShape::String,
+                "the application to open the file with, instead of the default one",
+                None,
+            )
             .category(Category::FileSystem)
     }
 
@@ -37,6 +77,9 @@ impl Command for Start {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let path = call.opt::<Spanned<String>>(engine_state, stack, 0)?;
+        let app: Option<Spanned<String>> = call.get_flag(engine_state, stack, "with")?;
+        let wait = call.has_flag("wait");
+        let span = call.head;
 
         let path = {
             if let Some(path_val) = path {
@@ -72,8 +115,50 @@ impl Command for Start {
         let path_no_whitespace = &path.item.trim_end_matches(|x| matches!(x, '\x09'..='\x0d'));
         let path = Path::new(path_no_whitespace);
 
-        open::that(path)?;
-        Ok(PipelineData::Empty)
+        let cwd = current_dir(engine_state, stack)?;
+
+        let child = match &app {
+            Some(app) => spawn_with(path, &app.item, wait, &cwd).map(Some),
+            None => spawn_default(path, wait, &cwd),
+        }
+        .map_err(|e| {
+            let program = match &app {
+                Some(app) => app.item.as_str(),
+                None => "the default application",
+            };
+            ShellError::ExternalCommand(
+                format!("Cannot start {program}"),
+                e.to_string(),
+                app.as_ref().map(|app| app.span).unwrap_or(span),
+            )
+        })?;
+
+        let (pid, exit_code) = match child {
+            Some(mut child) => {
+                let pid = Value::int(child.id() as i64, span);
+                let exit_code = if wait {
+                    wait_for_exit(&mut child, engine_state)?
+                } else {
+                    None
+                };
+                if exit_code.is_none() {
+                    reap_in_background(child);
+                }
+                (pid, exit_code)
+            }
+            None => (Value::nothing(span), None),
+        };
+        let exit_code = match exit_code {
+            Some(code) => Value::int(code as i64, span),
+            None => Value::nothing(span),
+        };
+
+        Ok(Value::Record {
+            cols: vec!["pid".into(), "exit_code".into()],
+            vals: vec![pid, exit_code],
+            span,
+        }
+        .into_pipeline_data())
     }
 
     fn examples(&self) -> Vec<nu_protocol::Example> {
@@ -98,6 +183,116 @@ impl Command for Start {
                 example: "start file.pdf",
                 result: None,
             },
+            Example {
+                description: "Edit a file in a given application, and continue once it is closed",
+                example: "start --with gedit --wait notes.txt",
+                result: None,
+            },
+            Example {
+                description: "Open a page in a browser, and keep its PID",
+                example: "let pid = (start --with firefox https://www.nushell.sh).pid",
+                result: None,
+            },
         ]
     }
 }
+
+fn command(program: impl AsRef<OsStr>, cwd: &Path) -> std::process::Command {
+    let mut command = std::process::Command::new(program);
+    command
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn spawn_with(path: &Path, app: &str, wait: bool, cwd: &Path) -> std::io::Result<Child> {
+    // Applications are bundles, which only `open` knows how to launch
+    let mut command = command("open", cwd);
+    if wait {
+        command.arg("-W");
+    }
+    command.arg("-a").arg(app).arg(path).spawn()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn spawn_with(path: &Path, app: &str, _wait: bool, cwd: &Path) -> std::io::Result<Child> {
+    command(app, cwd).arg(path).spawn()
+}
+
+#[cfg(target_os = "macos")]
+fn spawn_default(path: &Path, wait: bool, cwd: &Path) -> std::io::Result<Option<Child>> {
+    let mut command = command("open", cwd);
+    if wait {
+        command.arg("-W");
+    }
+    command.arg(path).spawn().map(Some)
+}
+
+#[cfg(windows)]
+fn spawn_default(path: &Path, wait: bool, cwd: &Path) -> std::io::Result<Option<Child>> {
+    // ShellExecute starts the application without a process of ours to wait for. It takes the
+    // path as it is, where `cmd /c start` would read the `&`, `^` and `%` in it.
+    if wait {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "the default application can't be waited for on Windows, name one with --with",
+        ));
+    }
+    let file = cwd.join(path);
+    if file.exists() {
+        open::that(file)?;
+    } else {
+        open::that(path)?;
+    }
+    Ok(None)
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn spawn_default(path: &Path, _wait: bool, cwd: &Path) -> std::io::Result<Option<Child>> {
+    let launchers: [(&str, &[&str]); 5] = [
+        ("xdg-open", &[]),
+        ("gio", &["open"]),
+        ("gnome-open", &[]),
+        ("kde-open", &[]),
+        ("wslview", &[]),
+    ];
+
+    // The first launcher installed is used
+    let mut error = None;
+    for (launcher, args) in launchers {
+        match command(launcher, cwd).args(args).arg(path).spawn() {
+            Ok(child) => return Ok(Some(child)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => error = error.or(Some(e)),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(error.unwrap_or_else(|| std::io::ErrorKind::NotFound.into()))
+}
+
+fn wait_for_exit(child: &mut Child, engine_state: &EngineState) -> Result<Option<i32>, ShellError> {
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status.code()),
+            Ok(None) => {
+                // The application keeps running, only the waiting stops
+                if nu_utils::ctrl_c::was_pressed(&engine_state.ctrlc) {
+                    return Ok(None);
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            Err(e) => return Err(ShellError::IOError(e.to_string())),
+        }
+    }
+}
+
+// The application keeps running after start returns, so its exit status is collected on another
+// thread, rather than leaving a zombie process behind
+fn reap_in_background(mut child: Child) {
+    let _ = std::thread::Builder::new()
+        .name("start reaper".to_string())
+        .spawn(move || child.wait());
+}
@@ -0,0 +1,213 @@
+use nu_engine::{eval_block, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Closure, Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, FromValue, IntoPipelineData, PipelineData, ShellError, Signature, Span,
+    SyntaxShape, Type, Value,
+};
+
+use super::group_by::{group_rows, Group};
+
+#[derive(Clone)]
+pub struct Aggregate;
+
+impl Command for Aggregate {
+    fn name(&self) -> &str {
+        "aggregate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("aggregate")
+            .input_output_types(vec![
+                (Type::Table(vec![]), Type::Table(vec![])),
+                (Type::List(Box::new(Type::Any)), Type::Table(vec![])),
+            ])
+            .required(
+                "aggregations",
+                SyntaxShape::Record,
+                "a record of closures, each computing a column from the rows of a group",
+            )
+            .named(
+                "by",
+                SyntaxShape::List(Box::new(SyntaxShape::Any)),
+                "the columns or closures to group by, as in group-by",
+                Some('b'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Group the rows of a table and compute a column for each group, returning a flat table."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The result has a row for each group, with a column for each part of the key, like
+'group-by --to-table', followed by a column for each closure of the record. A closure gets the
+rows of the group as input and as its parameter. Without --by, all the rows make up one group."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["group", "summarize", "pivot", "rollup"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let aggregations: Value = call.req(engine_state, stack, 0)?;
+        let groupers: Option<Vec<Value>> = call.get_flag(engine_state, stack, "by")?;
+
+        let aggregations = match aggregations {
+            Value::Record { cols, vals, .. } => cols
+                .into_iter()
+                .zip(vals.iter().map(Closure::from_value))
+                .map(|(col, closure)| closure.map(|closure| (col, closure)))
+                .collect::<Result<Vec<_>, _>>()?,
+            other => {
+                return Err(ShellError::TypeMismatch(
+                    format!("record of closures, not {}", other.get_type()),
+                    other.span().unwrap_or(head),
+                ))
+            }
+        };
+
+        let values: Vec<Value> = input.into_iter().collect();
+        let groups: Vec<Group> = match groupers {
+            Some(groupers) if !groupers.is_empty() => {
+                group_rows(engine_state, stack, call, &groupers, values)?
+                    .into_values()
+                    .collect()
+            }
+            _ if values.is_empty() => vec![],
+            _ => vec![Group {
+                key: vec![],
+                rows: values,
+            }],
+        };
+
+        let mut rows = Vec::with_capacity(groups.len());
+        for group in groups {
+            let (mut cols, mut vals): (Vec<String>, Vec<Value>) = group.key.into_iter().unzip();
+            let members = Value::List {
+                vals: group.rows,
+                span: head,
+            };
+            for (col, closure) in &aggregations {
+                let value = aggregate(engine_state, stack, call, closure, members.clone())?;
+                cols.push(col.clone());
+                vals.push(value);
+            }
+            rows.push(Value::Record {
+                cols,
+                vals,
+                span: head,
+            });
+        }
+
+        Ok(Value::List {
+            vals: rows,
+            span: head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Total the sales of each region, and count its rows",
+                example: "[[region sales]; [eu 1] [us 2] [eu 3]] | aggregate --by [region] {total: {|g| $g.sales | math sum}, n: {|g| $g | length}}",
+                result: None,
+            },
+            Example {
+                description: "List the names in each region",
+                example: "[[region name]; [eu ann] [us bob] [eu cid]] | aggregate --by [region] {names: {|g| $g.name | str join ', '}}",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["region", "names"],
+                            vec![Value::test_string("eu"), Value::test_string("ann, cid")],
+                        ),
+                        Value::test_record(
+                            vec!["region", "names"],
+                            vec![Value::test_string("us"), Value::test_string("bob")],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Aggregate all the rows at once",
+                example: "[[name]; [ann] [bob]] | aggregate {names: {|g| $g.name | str join ' '}}",
+                result: Some(Value::List {
+                    vals: vec![Value::test_record(
+                        vec!["names"],
+                        vec![Value::test_string("ann bob")],
+                    )],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Group raw values with a closure",
+                example: "[a b a] | aggregate --by [{|x| $x }] {all: {|g| $g | str join }}",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["group", "all"],
+                            vec![Value::test_string("a"), Value::test_string("aa")],
+                        ),
+                        Value::test_record(
+                            vec!["group", "all"],
+                            vec![Value::test_string("b"), Value::test_string("b")],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// The value of one aggregation for the rows of a group
+fn aggregate(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    closure: &Closure,
+    rows: Value,
+) -> Result<Value, ShellError> {
+    let block = engine_state.get_block(closure.block_id);
+    let mut stack = stack.captures_to_stack(&closure.captures);
+    if let Some(var_id) = block.signature.get_positional(0).and_then(|var| var.var_id) {
+        stack.add_var(var_id, rows.clone());
+    }
+
+    let value = eval_block(
+        engine_state,
+        &mut stack,
+        block,
+        rows.into_pipeline_data(),
+        call.redirect_stdout,
+        call.redirect_stderr,
+    )?
+    .into_value(call.head);
+    match value {
+        Value::Error { error } => Err(error),
+        value => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Aggregate {})
+    }
+}
@@ -26,7 +26,13 @@ iterate over each record, not necessarily each cell within it.
 Avoid passing single records to this command. Since a record is a
 one-row structure, 'each' will only run once, behaving similar to 'do'.
 To iterate over a record's values, try converting it to a table
-with 'transpose' first."#
+with 'transpose' first.
+
+A closure with a second parameter gets the index of each row, starting at 0,
+so there is no need to go through 'enumerate' to know it.
+
+Calling 'break' in the closure ends the list there. No more rows are read from
+the input, so this also works to stop going through an endless stream."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -102,6 +108,26 @@ with 'transpose' first."#
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: r#"[1 2 3] | each {|e, i| if $e == 2 { $"found 2 at ($i)!"} }"#,
+                description: "Read the index of each element from a second closure parameter",
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("found 2 at 1!")],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[1 2 3 4 5] | each {|n| if $n > 3 { break } else { $n * 10 } }",
+                description: "Stop at the first element greater than 3",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(10),
+                        Value::test_int(20),
+                        Value::test_int(30),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 example: r#"[1 2 3] | each --keep-empty {|e| if $e == 2 { "found 2!"} }"#,
                 description: "Iterate over each element, keeping null results",
@@ -142,7 +168,8 @@ with 'transpose' first."#
             | PipelineData::Value(Value::List { .. }, ..)
             | PipelineData::ListStream { .. } => Ok(input
                 .into_iter()
-                .map_while(move |x| {
+                .enumerate()
+                .map_while(move |(index, x)| {
                     // with_env() is used here to ensure that each iteration uses
                     // a different set of environment variables.
                     // Hence, a 'cd' in the first loop won't affect the next loop.
@@ -154,6 +181,13 @@ with 'transpose' first."#
                         }
                     }
 
+                    // Index argument
+                    if let Some(var) = block.signature.get_positional(1) {
+                        if let Some(var_id) = &var.var_id {
+                            stack.add_var(*var_id, Value::int(index as i64, span));
+                        }
+                    }
+
                     let input_span = x.span();
                     match eval_block_with_early_return(
                         &engine_state,
@@ -178,7 +212,8 @@ with 'transpose' first."#
                 ..
             } => Ok(stream
                 .into_iter()
-                .map_while(move |x| {
+                .enumerate()
+                .map_while(move |(index, x)| {
                     // with_env() is used here to ensure that each iteration uses
                     // a different set of environment variables.
                     // Hence, a 'cd' in the first loop won't affect the next loop.
@@ -196,6 +231,13 @@ with 'transpose' first."#
                         }
                     }
 
+                    // Index argument
+                    if let Some(var) = block.signature.get_positional(1) {
+                        if let Some(var_id) = &var.var_id {
+                            stack.add_var(*var_id, Value::int(index as i64, span));
+                        }
+                    }
+
                     let input_span = x.span();
                     match eval_block_with_early_return(
                         &engine_state,
@@ -223,6 +265,13 @@ with 'transpose' first."#
                     }
                 }
 
+                // Index argument
+                if let Some(var) = block.signature.get_positional(1) {
+                    if let Some(var_id) = &var.var_id {
+                        stack.add_var(*var_id, Value::int(0, span));
+                    }
+                }
+
                 eval_block_with_early_return(
                     &engine_state,
                     &mut stack,
@@ -71,6 +71,14 @@ impl Command for EachWhile {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: r#"[a b c d] | each while {|e, i| if $i < 2 { $e } }"#,
+                description: "Read the index of each element from a second closure parameter",
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("a"), Value::test_string("b")],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 
@@ -101,7 +109,8 @@ impl Command for EachWhile {
             | PipelineData::ListStream { .. } => Ok(input
                 // TODO: Could this be changed to .into_interruptible_iter(ctrlc) ?
                 .into_iter()
-                .map_while(move |x| {
+                .enumerate()
+                .map_while(move |(index, x)| {
                     // with_env() is used here to ensure that each iteration uses
                     // a different set of environment variables.
                     // Hence, a 'cd' in the first loop won't affect the next loop.
@@ -113,6 +122,13 @@ impl Command for EachWhile {
                         }
                     }
 
+                    // Index argument
+                    if let Some(var) = block.signature.get_positional(1) {
+                        if let Some(var_id) = &var.var_id {
+                            stack.add_var(*var_id, Value::int(index as i64, span));
+                        }
+                    }
+
                     match eval_block_with_early_return(
                         &engine_state,
                         &mut stack,
@@ -140,7 +156,8 @@ impl Command for EachWhile {
                 ..
             } => Ok(stream
                 .into_iter()
-                .map_while(move |x| {
+                .enumerate()
+                .map_while(move |(index, x)| {
                     // with_env() is used here to ensure that each iteration uses
                     // a different set of environment variables.
                     // Hence, a 'cd' in the first loop won't affect the next loop.
@@ -157,6 +174,13 @@ impl Command for EachWhile {
                         }
                     }
 
+                    // Index argument
+                    if let Some(var) = block.signature.get_positional(1) {
+                        if let Some(var_id) = &var.var_id {
+                            stack.add_var(*var_id, Value::int(index as i64, span));
+                        }
+                    }
+
                     match eval_block_with_early_return(
                         &engine_state,
                         &mut stack,
@@ -187,6 +211,13 @@ impl Command for EachWhile {
                     }
                 }
 
+                // Index argument
+                if let Some(var) = block.signature.get_positional(1) {
+                    if let Some(var_id) = &var.var_id {
+                        stack.add_var(*var_id, Value::int(0, span));
+                    }
+                }
+
                 eval_block_with_early_return(
                     &engine_state,
                     &mut stack,
@@ -72,7 +72,8 @@ a variable. On the other hand, the "row condition" syntax is not supported."#
                 // To enumerate over the input (for the index argument),
                 // it must be converted into an iterator using into_iter().
                 .into_iter()
-                .filter_map(move |x| {
+                .enumerate()
+                .filter_map(move |(index, x)| {
                     // with_env() is used here to ensure that each iteration uses
                     // a different set of environment variables.
                     // Hence, a 'cd' in the first loop won't affect the next loop.
@@ -84,6 +85,13 @@ a variable. On the other hand, the "row condition" syntax is not supported."#
                         }
                     }
 
+                    // Index argument
+                    if let Some(var) = block.signature.get_positional(1) {
+                        if let Some(var_id) = &var.var_id {
+                            stack.add_var(*var_id, Value::int(index as i64, span));
+                        }
+                    }
+
                     match eval_block(
                         &engine_state,
                         &mut stack,
@@ -112,7 +120,8 @@ a variable. On the other hand, the "row condition" syntax is not supported."#
                 ..
             } => Ok(stream
                 .into_iter()
-                .filter_map(move |x| {
+                .enumerate()
+                .filter_map(move |(index, x)| {
                     // see note above about with_env()
                     stack.with_env(&orig_env_vars, &orig_env_hidden);
 
@@ -127,6 +136,13 @@ a variable. On the other hand, the "row condition" syntax is not supported."#
                         }
                     }
 
+                    // Index argument
+                    if let Some(var) = block.signature.get_positional(1) {
+                        if let Some(var_id) = &var.var_id {
+                            stack.add_var(*var_id, Value::int(index as i64, span));
+                        }
+                    }
+
                     match eval_block(
                         &engine_state,
                         &mut stack,
@@ -160,6 +176,13 @@ a variable. On the other hand, the "row condition" syntax is not supported."#
                         stack.add_var(*var_id, x.clone());
                     }
                 }
+
+                // Index argument
+                if let Some(var) = block.signature.get_positional(1) {
+                    if let Some(var_id) = &var.var_id {
+                        stack.add_var(*var_id, Value::int(0, span));
+                    }
+                }
                 Ok(match eval_block(
                     &engine_state,
                     &mut stack,
@@ -196,6 +219,15 @@ a variable. On the other hand, the "row condition" syntax is not supported."#
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: r#"[a b c d] | filter {|x, i| $i mod 2 == 0 }"#,
+                description:
+                    "Keep every other element, using the index given as a second closure parameter",
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("a"), Value::test_string("c")],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 description: "Filter rows of a table according to a condition",
                 example: "[{a: 1} {a: 2}] | filter {|x| $x.a > 1}",
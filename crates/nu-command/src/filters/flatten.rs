@@ -4,7 +4,7 @@ use nu_protocol::ast::{Call, CellPath, PathMember};
 
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -23,6 +23,7 @@ impl Command for Flatten {
                     Type::List(Box::new(Type::Any)),
                 ),
                 (Type::Record(vec![]), Type::Table(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
             ])
             .rest(
                 "rest",
@@ -30,6 +31,23 @@ impl Command for Flatten {
                 "optionally flatten data by column",
             )
             .switch("all", "flatten inner table one level out", Some('a'))
+            .named(
+                "depth",
+                SyntaxShape::Int,
+                "how many levels to flatten (default: 1, or every level with --dotted)",
+                Some('d'),
+            )
+            .switch(
+                "dotted",
+                "flatten nested records into columns named after their path, like a.b.c",
+                None,
+            )
+            .named(
+                "separator",
+                SyntaxShape::String,
+                "the separator between the parts of a --dotted column name (default: .)",
+                Some('s'),
+            )
             .category(Category::Filters)
     }
 
@@ -37,6 +55,12 @@ impl Command for Flatten {
         "Flatten the table."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --dotted, records nested in a column are flattened into columns named after the path
+to their values instead, and lists are kept as they are. `unflatten` turns such columns back into
+nested records."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -79,6 +103,28 @@ impl Command for Flatten {
                 example: "[[origin, crate, versions]; [World, ([[name]; ['nu-cli']]), ['0.21', '0.22']]] | flatten versions --all | last | get versions",
                 result: None, //Some(Value::test_string("0.22")),
             },
+            Example {
+                description: "Flatten nested records into columns named after their path",
+                example: "{a: {b: {c: 1}}, d: 2} | flatten --dotted",
+                result: Some(Value::Record {
+                    cols: vec!["a.b.c".to_string(), "d".to_string()],
+                    vals: vec![Value::test_int(1), Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Flatten a list of lists of lists two levels",
+                example: "[[[1 2] [3]] [[4]]] | flatten --depth 2",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(1),
+                        Value::test_int(2),
+                        Value::test_int(3),
+                        Value::test_int(4),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 description: "Flatten inner table",
                 example: "{ a: b, d: [ 1 2 3 4 ],  e: [ 4 3  ] } | flatten d --all",
@@ -122,15 +168,95 @@ fn flatten(
     let columns: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
     let metadata = input.metadata();
     let flatten_all = call.has_flag("all");
+    let depth: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "depth")?;
+    let depth = match depth {
+        Some(depth) if depth.item < 1 => return Err(ShellError::NeedsPositiveValue(depth.span)),
+        Some(depth) => Some(depth.item as usize),
+        None => None,
+    };
+
+    if call.has_flag("dotted") {
+        let separator: Option<String> = call.get_flag(engine_state, stack, "separator")?;
+        let separator = separator.unwrap_or_else(|| ".".into());
+        let depth = depth.unwrap_or(usize::MAX);
 
+        return input
+            .map(
+                move |item| flat_dotted(&columns, item, depth, &separator),
+                engine_state.ctrlc.clone(),
+            )
+            .map(|x| x.set_metadata(metadata));
+    }
+
+    let depth = depth.unwrap_or(1);
     input
         .flat_map(
-            move |item| flat_value(&columns, &item, tag, flatten_all),
+            move |item| {
+                let mut flattened = flat_value(&columns, &item, tag, flatten_all);
+                for _ in 1..depth {
+                    flattened = flattened
+                        .iter()
+                        .flat_map(|item| flat_value(&columns, item, tag, flatten_all))
+                        .collect();
+                }
+                flattened
+            },
             engine_state.ctrlc.clone(),
         )
         .map(|x| x.set_metadata(metadata))
 }
 
+/// Flatten the records nested in the columns of a record, up to `depth` levels, into columns
+/// named after their path
+fn flat_dotted(columns: &[CellPath], item: Value, depth: usize, separator: &str) -> Value {
+    match item {
+        Value::Record { cols, vals, span } => {
+            let mut out_cols = vec![];
+            let mut out_vals = vec![];
+            for (col, val) in cols.into_iter().zip(vals) {
+                if columns.is_empty() || columns.iter().any(|c| c.into_string() == col) {
+                    push_dotted(&mut out_cols, &mut out_vals, col, val, depth, separator);
+                } else {
+                    out_cols.push(col);
+                    out_vals.push(val);
+                }
+            }
+            Value::Record {
+                cols: out_cols,
+                vals: out_vals,
+                span,
+            }
+        }
+        other => other,
+    }
+}
+
+fn push_dotted(
+    cols: &mut Vec<String>,
+    vals: &mut Vec<Value>,
+    path: String,
+    value: Value,
+    depth: usize,
+    separator: &str,
+) {
+    match value {
+        Value::Record {
+            cols: inner_cols,
+            vals: inner_vals,
+            ..
+        } if depth > 0 && !inner_cols.is_empty() => {
+            for (col, val) in inner_cols.into_iter().zip(inner_vals) {
+                let path = format!("{path}{separator}{col}");
+                push_dotted(cols, vals, path, val, depth - 1, separator);
+            }
+        }
+        value => {
+            cols.push(path);
+            vals.push(value);
+        }
+    }
+}
+
 enum TableInside<'a> {
     // handle for a column which contains a single list(but not list of records)
     // it contains (column, span, values in the column, column index).
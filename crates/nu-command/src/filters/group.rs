@@ -16,15 +16,23 @@ impl Command for Group {
 
     fn signature(&self) -> Signature {
         Signature::build("group")
-            // TODO: It accepts Table also, but currently there is no Table
-            // example. Perhaps Table should be a subtype of List, in which case
-            // the current signature would suffice even when a Table example
-            // exists.
-            .input_output_types(vec![(
-                Type::List(Box::new(Type::Any)),
-                Type::List(Box::new(Type::List(Box::new(Type::Any)))),
-            )])
-            .required("group_size", SyntaxShape::Int, "the size of each group")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::Any)),
+                    Type::List(Box::new(Type::List(Box::new(Type::Any)))),
+                ),
+                (
+                    Type::Table(vec![]),
+                    Type::List(Box::new(Type::Table(vec![]))),
+                ),
+            ])
+            .optional("group_size", SyntaxShape::Int, "the size of each group")
+            .named(
+                "by",
+                SyntaxShape::String,
+                "start a new group whenever the value of this column changes",
+                Some('b'),
+            )
             .category(Category::Filters)
     }
 
@@ -32,6 +40,12 @@ impl Command for Group {
         "Groups input into groups of `group_size`."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --by, a group ends where the value of the column changes, so rows next to each other
+with the same value end up together. A group size can be given as well, to cap how large the
+groups can get."#
+    }
+
     fn examples(&self) -> Vec<Example> {
         let stream_test_1 = vec![
             Value::List {
@@ -44,14 +58,45 @@ impl Command for Group {
             },
         ];
 
-        vec![Example {
-            example: "[1 2 3 4] | group 2",
-            description: "Group the a list by pairs",
-            result: Some(Value::List {
-                vals: stream_test_1,
+        let row = |user: &str, page: &str| {
+            Value::test_record(
+                vec!["user", "page"],
+                vec![Value::test_string(user), Value::test_string(page)],
+            )
+        };
+        let stream_test_2 = vec![
+            Value::List {
+                vals: vec![row("a", "/"), row("a", "/docs")],
                 span: Span::test_data(),
-            }),
-        }]
+            },
+            Value::List {
+                vals: vec![row("b", "/")],
+                span: Span::test_data(),
+            },
+            Value::List {
+                vals: vec![row("a", "/blog")],
+                span: Span::test_data(),
+            },
+        ];
+
+        vec![
+            Example {
+                example: "[1 2 3 4] | group 2",
+                description: "Group the a list by pairs",
+                result: Some(Value::List {
+                    vals: stream_test_1,
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[[user page]; [a /] [a /docs] [b /] [a /blog]] | group --by user",
+                description: "Group the rows where the user stays the same",
+                result: Some(Value::List {
+                    vals: stream_test_2,
+                    span: Span::test_data(),
+                }),
+            },
+        ]
     }
 
     fn run(
@@ -61,16 +106,22 @@ impl Command for Group {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let group_size: Spanned<usize> = call.req(engine_state, stack, 0)?;
+        let group_size: Option<Spanned<usize>> = call.opt(engine_state, stack, 0)?;
+        let by: Option<String> = call.get_flag(engine_state, stack, "by")?;
+        if group_size.is_none() && by.is_none() {
+            return Err(ShellError::MissingParameter("group_size".into(), call.head));
+        }
         let ctrlc = engine_state.ctrlc.clone();
         let metadata = input.metadata();
 
         //FIXME: add in support for external redirection when engine-q supports it generally
 
         let each_group_iterator = EachGroupIterator {
-            group_size: group_size.item,
+            group_size: group_size.map_or(usize::MAX, |size| size.item),
+            by,
             input: Box::new(input.into_iter()),
             span: call.head,
+            pending: None,
         };
 
         Ok(each_group_iterator
@@ -81,8 +132,11 @@ impl Command for Group {
 
 struct EachGroupIterator {
     group_size: usize,
+    by: Option<String>,
     input: Box<dyn Iterator<Item = Value> + Send>,
     span: Span,
+    /// The row that ended the last group because its key changed
+    pending: Option<Value>,
 }
 
 impl Iterator for EachGroupIterator {
@@ -91,12 +145,22 @@ impl Iterator for EachGroupIterator {
     fn next(&mut self) -> Option<Self::Item> {
         let mut group = vec![];
         let mut current_count = 0;
+        let mut key = None;
 
         loop {
-            let item = self.input.next();
+            let item = self.pending.take().or_else(|| self.input.next());
 
             match item {
                 Some(v) => {
+                    if let Some(column) = &self.by {
+                        let item_key = v.get_data_by_key(column);
+                        if key.is_none() {
+                            key = Some(item_key);
+                        } else if key.as_ref() != Some(&item_key) {
+                            self.pending = Some(v);
+                            break;
+                        }
+                    }
                     group.push(v);
 
                     current_count += 1;
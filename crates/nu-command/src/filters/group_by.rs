@@ -2,7 +2,7 @@ use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Closure, Command, EngineState, Stack};
 use nu_protocol::{
-    Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape,
+    Example, FromValue, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
     Type, Value,
 };
 
@@ -18,21 +18,43 @@ impl Command for GroupBy {
 
     fn signature(&self) -> Signature {
         Signature::build("group-by")
-            // TODO: It accepts Table also, but currently there is no Table
-            // example. Perhaps Table should be a subtype of List, in which case
-            // the current signature would suffice even when a Table example
-            // exists.
-            .input_output_types(vec![(
-                Type::List(Box::new(Type::Any)),
-                Type::Record(vec![]),
-            )])
-            .optional("grouper", SyntaxShape::Any, "the grouper value to use")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Any)), Type::Record(vec![])),
+                (Type::List(Box::new(Type::Any)), Type::Table(vec![])),
+                (Type::Table(vec![]), Type::Record(vec![])),
+                (Type::Table(vec![]), Type::Table(vec![])),
+            ])
+            .rest(
+                "grouper",
+                SyntaxShape::Any,
+                "the columns or closures to group by",
+            )
+            .switch(
+                "to-table",
+                "return a table with a column for each part of the key, and the rows in items",
+                None,
+            )
+            .named(
+                "having",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any, SyntaxShape::Any])),
+                "keep only the groups for which the closure returns true",
+                None,
+            )
     }
 
     fn usage(&self) -> &str {
         "Splits a list or table into groups, and returns a record containing those groups."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With several columns or closures, or a closure that returns a record, each group has a key
+of several parts. The groups are then nested, one level for each part, or with --to-table they are
+rows with a column for each part of the key and the rows of the group in an items column.
+
+The closure given to --having gets the list of rows of a group as input and as its first
+parameter, and the key of the group as its second one. A key of several parts is a record."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -44,6 +66,17 @@ impl Command for GroupBy {
     }
 
     fn examples(&self) -> Vec<Example> {
+        let sale = |region: &str, year: i64, sales: i64| {
+            Value::test_record(
+                vec!["region", "year", "sales"],
+                vec![
+                    Value::test_string(region),
+                    Value::test_int(year),
+                    Value::test_int(sales),
+                ],
+            )
+        };
+
         vec![
             Example {
                 description: "Group items by the \"type\" column's values",
@@ -77,13 +110,127 @@ impl Command for GroupBy {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Keep the groups with more than one item",
+                example:
+                    "['a' 'b' 'a' 'c' 'c'] | group-by --having {|group| ($group | length) > 1}",
+                result: Some(Value::Record {
+                    cols: vec!["a".to_string(), "c".to_string()],
+                    vals: vec![
+                        Value::List {
+                            vals: vec![Value::test_string("a"), Value::test_string("a")],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![Value::test_string("c"), Value::test_string("c")],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Group by two columns into nested records",
+                example: "[[region year sales]; [eu 2022 1] [us 2022 2] [eu 2023 3]] | group-by region year",
+                result: Some(Value::test_record(
+                    vec!["eu", "us"],
+                    vec![
+                        Value::test_record(
+                            vec!["2022", "2023"],
+                            vec![
+                                Value::List {
+                                    vals: vec![sale("eu", 2022, 1)],
+                                    span: Span::test_data(),
+                                },
+                                Value::List {
+                                    vals: vec![sale("eu", 2023, 3)],
+                                    span: Span::test_data(),
+                                },
+                            ],
+                        ),
+                        Value::test_record(
+                            vec!["2022"],
+                            vec![Value::List {
+                                vals: vec![sale("us", 2022, 2)],
+                                span: Span::test_data(),
+                            }],
+                        ),
+                    ],
+                )),
+            },
+            Example {
+                description: "Group by two columns into a table, with the rows of each group in items",
+                example: "[[region year sales]; [eu 2022 1] [us 2022 2] [eu 2022 3]] | group-by region year --to-table",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["region", "year", "items"],
+                            vec![
+                                Value::test_string("eu"),
+                                Value::test_int(2022),
+                                Value::List {
+                                    vals: vec![sale("eu", 2022, 1), sale("eu", 2022, 3)],
+                                    span: Span::test_data(),
+                                },
+                            ],
+                        ),
+                        Value::test_record(
+                            vec!["region", "year", "items"],
+                            vec![
+                                Value::test_string("us"),
+                                Value::test_int(2022),
+                                Value::List {
+                                    vals: vec![sale("us", 2022, 2)],
+                                    span: Span::test_data(),
+                                },
+                            ],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Group raw values into a table",
+                example: "['a' 'b' 'a'] | group-by --to-table",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["group", "items"],
+                            vec![
+                                Value::test_string("a"),
+                                Value::List {
+                                    vals: vec![Value::test_string("a"), Value::test_string("a")],
+                                    span: Span::test_data(),
+                                },
+                            ],
+                        ),
+                        Value::test_record(
+                            vec!["group", "items"],
+                            vec![
+                                Value::test_string("b"),
+                                Value::List {
+                                    vals: vec![Value::test_string("b")],
+                                    span: Span::test_data(),
+                                },
+                            ],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Sum the sales of each region and year, with the keys in their own columns",
+                example: "[[region year sales]; [eu 2022 1] [us 2022 2] [eu 2022 3]] | group-by region year --to-table | update items {|group| $group.items.sales | math sum}",
+                result: None,
+            },
         ]
     }
 }
 
-enum Grouper {
-    ByColumn(Option<Spanned<String>>),
-    ByBlock,
+/// The rows of a group, and the parts of its key with their names
+pub struct Group {
+    pub key: Vec<(String, Value)>,
+    pub rows: Vec<Value>,
 }
 
 pub fn group_by(
@@ -94,10 +241,8 @@ pub fn group_by(
 ) -> Result<PipelineData, ShellError> {
     let name = call.head;
 
-    let grouper: Option<Value> = call.opt(engine_state, stack, 0)?;
+    let groupers: Vec<Value> = call.rest(engine_state, stack, 0)?;
     let values: Vec<Value> = input.into_iter().collect();
-    let mut keys: Vec<Result<String, ShellError>> = vec![];
-    let mut group_strategy = Grouper::ByColumn(None);
 
     if values.is_empty() {
         return Err(ShellError::GenericError(
@@ -109,92 +254,269 @@ pub fn group_by(
         ));
     }
 
-    let first = values[0].clone();
+    let span = values[0].span().unwrap_or(name);
+
+    let mut groups = group_rows(engine_state, stack, call, &groupers, values)?;
 
-    let value_list = Value::List {
-        vals: values.clone(),
-        span: name,
+    if let Some(having) = call.get_flag::<Closure>(engine_state, stack, "having")? {
+        groups = filter_groups(engine_state, stack, call, &having, groups)?;
+    }
+
+    let group_value = if call.has_flag("to-table") {
+        Value::List {
+            vals: groups
+                .into_values()
+                .map(|group| {
+                    let (mut cols, mut vals): (Vec<String>, Vec<Value>) =
+                        group.key.into_iter().unzip();
+                    cols.push("items".into());
+                    vals.push(Value::List {
+                        vals: group.rows,
+                        span,
+                    });
+                    Value::Record { cols, vals, span }
+                })
+                .collect(),
+            span,
+        }
+    } else {
+        let mut record = Value::Record {
+            cols: vec![],
+            vals: vec![],
+            span,
+        };
+        for (id, group) in groups {
+            insert_nested(&mut record, &id, group.rows, span)?;
+        }
+        record
     };
 
-    match grouper {
-        Some(Value::Block { .. }) | Some(Value::Closure { .. }) => {
-            let block: Option<Closure> = call.opt(engine_state, stack, 0)?;
-            let error_key = "error";
-
-            for value in values {
-                if let Some(capture_block) = &block {
-                    let mut stack = stack.captures_to_stack(&capture_block.captures);
-                    let block = engine_state.get_block(capture_block.block_id);
-                    let pipeline = eval_block(
-                        engine_state,
-                        &mut stack,
-                        block,
-                        value.into_pipeline_data(),
-                        call.redirect_stdout,
-                        call.redirect_stderr,
-                    );
-
-                    match pipeline {
-                        Ok(s) => {
-                            let collection: Vec<Value> = s.into_iter().collect();
-
-                            if collection.len() > 1 {
-                                return Err(ShellError::GenericError(
-                                    "expected one value from the block".into(),
-                                    "requires a table with one value for grouping".into(),
-                                    Some(name),
-                                    None,
-                                    Vec::new(),
-                                ));
-                            }
-
-                            let value = match collection.get(0) {
-                                Some(Value::Error { .. }) | None => Value::string(error_key, name),
-                                Some(return_value) => return_value.clone(),
-                            };
-
-                            keys.push(value.as_string());
-                        }
-                        Err(_) => {
-                            keys.push(Ok(error_key.into()));
-                        }
+    Ok(PipelineData::Value(group_value, None))
+}
+
+/// Split rows into groups by their key, keeping the groups in the order they first appear
+pub fn group_rows(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    groupers: &[Value],
+    values: Vec<Value>,
+) -> Result<IndexMap<Vec<String>, Group>, ShellError> {
+    let mut groups: IndexMap<Vec<String>, Group> = IndexMap::new();
+    for value in values {
+        let key = group_key(engine_state, stack, call, groupers, &value)?;
+        let id = key
+            .iter()
+            .map(|(_, part)| part.as_string())
+            .collect::<Result<Vec<String>, ShellError>>()?;
+        groups
+            .entry(id)
+            .or_insert_with(|| Group { key, rows: vec![] })
+            .rows
+            .push(value);
+    }
+    Ok(groups)
+}
+
+/// The parts of the key of a row, one for each column or closure, or more for a closure that
+/// returns a record
+fn group_key(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    groupers: &[Value],
+    row: &Value,
+) -> Result<Vec<(String, Value)>, ShellError> {
+    let name = call.head;
+    if let Value::Error { error } = row {
+        return Err(error.clone());
+    }
+    if groupers.is_empty() {
+        return Ok(vec![("group".into(), row.clone())]);
+    }
+
+    let mut key = vec![];
+    for (idx, grouper) in groupers.iter().enumerate() {
+        match grouper {
+            Value::Block { .. } | Value::Closure { .. } => {
+                let closure = Closure::from_value(grouper)?;
+                match eval_grouper(engine_state, stack, call, &closure, row)? {
+                    Value::Record { cols, vals, .. } => key.extend(cols.into_iter().zip(vals)),
+                    part if groupers.len() == 1 => key.push(("group".into(), part)),
+                    part => key.push((format!("group{idx}"), part)),
+                }
+            }
+            column => {
+                let column = column.as_string()?;
+                match row.get_data_by_key(&column) {
+                    Some(part) => key.push((column, part)),
+                    None => {
+                        return Err(ShellError::CantFindColumn(
+                            column,
+                            grouper.span().unwrap_or(name),
+                            row.expect_span(),
+                        ))
                     }
                 }
             }
-
-            group_strategy = Grouper::ByBlock;
         }
-        Some(other) => {
-            group_strategy = Grouper::ByColumn(Some(Spanned {
-                item: other.as_string()?,
-                span: name,
-            }));
+    }
+    Ok(key)
+}
+
+/// The value a closure returns for a row, or "error" if it fails
+fn eval_grouper(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    closure: &Closure,
+    row: &Value,
+) -> Result<Value, ShellError> {
+    let name = call.head;
+    let error_key = "error";
+
+    let mut stack = stack.captures_to_stack(&closure.captures);
+    let block = engine_state.get_block(closure.block_id);
+    let pipeline = eval_block(
+        engine_state,
+        &mut stack,
+        block,
+        row.clone().into_pipeline_data(),
+        call.redirect_stdout,
+        call.redirect_stderr,
+    );
+
+    match pipeline {
+        Ok(s) => {
+            let collection: Vec<Value> = s.into_iter().collect();
+
+            if collection.len() > 1 {
+                return Err(ShellError::GenericError(
+                    "expected one value from the block".into(),
+                    "requires a table with one value for grouping".into(),
+                    Some(name),
+                    None,
+                    Vec::new(),
+                ));
+            }
+
+            Ok(match collection.into_iter().next() {
+                Some(Value::Error { .. }) | None => Value::string(error_key, name),
+                Some(value) => value,
+            })
         }
-        _ => {}
+        Err(_) => Ok(Value::string(error_key, name)),
     }
+}
 
-    let name = if let Ok(span) = first.span() {
-        span
-    } else {
-        name
+/// Put the rows of a group in nested records, one level for each part of its key
+fn insert_nested(
+    record: &mut Value,
+    id: &[String],
+    rows: Vec<Value>,
+    span: Span,
+) -> Result<(), ShellError> {
+    let (cols, vals) = match record {
+        Value::Record { cols, vals, .. } => (cols, vals),
+        _ => {
+            return Err(ShellError::GenericError(
+                "groups can't be nested".into(),
+                "the keys of the groups have different numbers of parts".into(),
+                Some(span),
+                Some("return records with the same columns from the closure".into()),
+                Vec::new(),
+            ))
+        }
+    };
+    let (first, rest) = match id.split_first() {
+        Some(parts) => parts,
+        None => return Ok(()),
     };
 
-    let group_value = match group_strategy {
-        Grouper::ByBlock => {
-            let map = keys;
-
-            let block = Box::new(move |idx: usize, row: &Value| match map.get(idx) {
-                Some(Ok(key)) => Ok(key.clone()),
-                Some(Err(reason)) => Err(reason.clone()),
-                None => row.as_string(),
+    let idx = match cols.iter().position(|col| col == first) {
+        Some(idx) => idx,
+        None => {
+            cols.push(first.clone());
+            vals.push(if rest.is_empty() {
+                Value::List { vals: vec![], span }
+            } else {
+                Value::Record {
+                    cols: vec![],
+                    vals: vec![],
+                    span,
+                }
             });
-
-            data_group(&value_list, &Some(block), name)
+            vals.len() - 1
         }
-        Grouper::ByColumn(column_name) => group(&column_name, &value_list, name),
     };
+    match (&mut vals[idx], rest.is_empty()) {
+        (Value::List { vals, .. }, true) => {
+            vals.extend(rows);
+            Ok(())
+        }
+        (nested, false) => insert_nested(nested, rest, rows, span),
+        (_, true) => Err(ShellError::GenericError(
+            "groups can't be nested".into(),
+            "the keys of the groups have different numbers of parts".into(),
+            Some(span),
+            Some("return records with the same columns from the closure".into()),
+            Vec::new(),
+        )),
+    }
+}
 
-    Ok(PipelineData::Value(group_value?, None))
+/// Keep the groups for which the `having` closure returns true
+fn filter_groups(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    having: &Closure,
+    groups: IndexMap<Vec<String>, Group>,
+) -> Result<IndexMap<Vec<String>, Group>, ShellError> {
+    let block = engine_state.get_block(having.block_id);
+    let mut stack = stack.captures_to_stack(&having.captures);
+    let mut kept = IndexMap::new();
+    for (id, group) in groups {
+        let rows = Value::List {
+            vals: group.rows.clone(),
+            span: call.head,
+        };
+        // A key of one part is passed as a string, and one of several parts as a record
+        let key = match id.as_slice() {
+            [part] => Value::string(part, call.head),
+            _ => {
+                let (cols, vals) = group.key.iter().cloned().unzip();
+                Value::Record {
+                    cols,
+                    vals,
+                    span: call.head,
+                }
+            }
+        };
+        if let Some(var_id) = block.signature.get_positional(0).and_then(|var| var.var_id) {
+            stack.add_var(var_id, rows.clone());
+        }
+        if let Some(var_id) = block.signature.get_positional(1).and_then(|var| var.var_id) {
+            stack.add_var(var_id, key);
+        }
+
+        let keep = eval_block(
+            engine_state,
+            &mut stack,
+            block,
+            rows.into_pipeline_data(),
+            call.redirect_stdout,
+            call.redirect_stderr,
+        )?
+        .into_value(call.head);
+        if let Value::Error { error } = keep {
+            return Err(error);
+        }
+        if keep.is_true() {
+            kept.insert(id, group);
+        }
+    }
+    Ok(kept)
 }
 
 #[allow(clippy::type_complexity)]
@@ -227,48 +549,6 @@ pub fn data_group(
     Ok(Value::Record { cols, vals, span })
 }
 
-pub fn group(
-    column_name: &Option<Spanned<String>>,
-    values: &Value,
-    span: Span,
-) -> Result<Value, ShellError> {
-    let name = span;
-
-    let grouper = if let Some(column_name) = column_name {
-        Grouper::ByColumn(Some(column_name.clone()))
-    } else {
-        Grouper::ByColumn(None)
-    };
-
-    match grouper {
-        Grouper::ByColumn(Some(column_name)) => {
-            let block = Box::new(move |_, row: &Value| {
-                if let Value::Error { error } = row {
-                    return Err(error.clone());
-                };
-                match row.get_data_by_key(&column_name.item) {
-                    Some(group_key) => Ok(group_key.as_string()?),
-                    None => Err(ShellError::CantFindColumn(
-                        column_name.item.to_string(),
-                        column_name.span,
-                        row.expect_span(),
-                    )),
-                }
-            });
-
-            data_group(values, &Some(block), name)
-        }
-        Grouper::ByColumn(None) => {
-            let block = Box::new(move |_, row: &Value| row.as_string());
-
-            data_group(values, &Some(block), name)
-        }
-        Grouper::ByBlock => Err(ShellError::NushellFailed(
-            "Block not implemented: This should never happen.".into(),
-        )),
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
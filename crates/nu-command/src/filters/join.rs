@@ -0,0 +1,458 @@
+use crate::formats::value_to_string;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
+    Type, Value,
+};
+use std::collections::HashMap;
+
+type Row = (Vec<String>, Vec<Value>);
+
+#[derive(Clone, Copy, PartialEq)]
+enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Outer,
+    Cross,
+}
+
+#[derive(Clone)]
+pub struct Join;
+
+impl Command for Join {
+    fn name(&self) -> &str {
+        "join"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("join")
+            .input_output_types(vec![(Type::Table(vec![]), Type::Table(vec![]))])
+            .required(
+                "right-table",
+                SyntaxShape::Any,
+                "the table to join the input with",
+            )
+            .optional(
+                "left-on",
+                SyntaxShape::Any,
+                "the column or list of columns of the input to join on",
+            )
+            .optional(
+                "right-on",
+                SyntaxShape::Any,
+                "the columns of the right table to join on, if they have other names",
+            )
+            .switch("inner", "inner join (default)", Some('i'))
+            .switch("left", "left outer join", Some('l'))
+            .switch("right", "right outer join", Some('r'))
+            .switch("outer", "full outer join", Some('o'))
+            .switch(
+                "cross",
+                "cross join, pairing every row with every row",
+                Some('c'),
+            )
+            .named(
+                "suffix",
+                SyntaxShape::String,
+                "suffix for columns of the right table whose names are already taken (default: _x)",
+                Some('s'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Join two tables on the values of one or more columns."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The columns of the result are the columns of the input followed by the columns of the
+right table, in the same order for every row. A key column with the same name in both tables shows
+up once. Other columns of the right table whose names are taken get the suffix.
+
+Rows that have no match in an outer join get null in the columns of the other table. Null or
+missing keys never match."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["sql", "relational", "merge", "combine", "lookup"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let flags = [
+            ("inner", JoinType::Inner),
+            ("left", JoinType::Left),
+            ("right", JoinType::Right),
+            ("outer", JoinType::Outer),
+            ("cross", JoinType::Cross),
+        ];
+        let chosen: Vec<JoinType> = flags
+            .iter()
+            .filter(|(name, _)| call.has_flag(name))
+            .map(|(_, join_type)| *join_type)
+            .collect();
+        if chosen.len() > 1 {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "only one of --inner, --left, --right, --outer and --cross can be used".into(),
+                call.head,
+            ));
+        }
+        let join_type = chosen.first().copied().unwrap_or(JoinType::Inner);
+
+        let right_table: Value = call.req(engine_state, stack, 0)?;
+        let left_on: Option<Value> = call.opt(engine_state, stack, 1)?;
+        let right_on: Option<Value> = call.opt(engine_state, stack, 2)?;
+
+        let (left_on, right_on) = match (join_type, left_on) {
+            (JoinType::Cross, None) => (vec![], vec![]),
+            (JoinType::Cross, Some(left_on)) => {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    "a cross join doesn't take columns to join on".into(),
+                    left_on.span()?,
+                ))
+            }
+            (_, None) => return Err(ShellError::MissingParameter("left-on".into(), call.head)),
+            (_, Some(left_on)) => {
+                let left_cols = column_names(&left_on)?;
+                let right_cols = match right_on {
+                    Some(right_on) => {
+                        let right_cols = column_names(&right_on)?;
+                        if right_cols.len() != left_cols.len() {
+                            return Err(ShellError::IncompatibleParametersSingle(
+                                "The right column list has a different size to the left column list"
+                                    .into(),
+                                right_on.span()?,
+                            ));
+                        }
+                        right_cols
+                    }
+                    None => left_cols.clone(),
+                };
+                (left_cols, right_cols)
+            }
+        };
+
+        let suffix: Option<String> = call.get_flag(engine_state, stack, "suffix")?;
+        let suffix = suffix.unwrap_or_else(|| "_x".into());
+
+        let metadata = input.metadata();
+        let right_span = right_table.span()?;
+        let right_rows = table_rows(right_table, right_span)?;
+        let left_rows = table_rows(input.into_value(call.head), call.head)?;
+
+        let joiner = Joiner::new(&left_rows, &right_rows, &left_on, &right_on, &suffix);
+        let vals = joiner.join(join_type, call.head)?;
+
+        Ok(Value::List {
+            vals,
+            span: call.head,
+        }
+        .into_pipeline_data()
+        .set_metadata(metadata))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let row = |cols: Vec<&str>, vals: Vec<Value>| Value::test_record(cols, vals);
+        vec![
+            Example {
+                description: "Join two tables on a column",
+                example: "[[id name]; [1 a] [2 b]] | join [[id size]; [2 10] [3 20]] id",
+                result: Some(Value::List {
+                    vals: vec![row(
+                        vec!["id", "name", "size"],
+                        vec![
+                            Value::test_int(2),
+                            Value::test_string("b"),
+                            Value::test_int(10),
+                        ],
+                    )],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Keep every row of both tables, filling the gaps with null",
+                example: "[[id name]; [1 a] [2 b]] | join --outer [[id size]; [2 10] [3 20]] id",
+                result: None,
+            },
+            Example {
+                description: "Join on two columns with other names, suffixing a taken column name",
+                example:
+                    "[[x y v]; [1 1 a] [1 2 b]] | join [[a b v]; [1 2 c]] [x y] [a b] --suffix _r",
+                result: Some(Value::List {
+                    vals: vec![row(
+                        vec!["x", "y", "v", "a", "b", "v_r"],
+                        vec![
+                            Value::test_int(1),
+                            Value::test_int(2),
+                            Value::test_string("b"),
+                            Value::test_int(1),
+                            Value::test_int(2),
+                            Value::test_string("c"),
+                        ],
+                    )],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Pair every row with every row",
+                example: "[[a]; [1] [2]] | join --cross [[b]; [x]]",
+                result: Some(Value::List {
+                    vals: vec![
+                        row(
+                            vec!["a", "b"],
+                            vec![Value::test_int(1), Value::test_string("x")],
+                        ),
+                        row(
+                            vec!["a", "b"],
+                            vec![Value::test_int(2), Value::test_string("x")],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+fn column_names(value: &Value) -> Result<Vec<String>, ShellError> {
+    match value {
+        Value::String { val, .. } => Ok(vec![val.clone()]),
+        Value::List { vals, .. } if !vals.is_empty() => vals.iter().map(Value::as_string).collect(),
+        _ => Err(ShellError::TypeMismatch(
+            "expected a column name or a list of column names".into(),
+            value.span()?,
+        )),
+    }
+}
+
+fn table_rows(value: Value, span: Span) -> Result<Vec<Row>, ShellError> {
+    match value {
+        Value::List { vals, .. } => vals
+            .into_iter()
+            .map(|row| match row {
+                Value::Record { cols, vals, .. } => Ok((cols, vals)),
+                Value::Error { error } => Err(error),
+                other => Err(ShellError::OnlySupportsThisInputType(
+                    "table".into(),
+                    other.get_type().to_string(),
+                    span,
+                    other.expect_span(),
+                )),
+            })
+            .collect(),
+        Value::Record { cols, vals, .. } => Ok(vec![(cols, vals)]),
+        Value::Error { error } => Err(error),
+        other => Err(ShellError::OnlySupportsThisInputType(
+            "table".into(),
+            other.get_type().to_string(),
+            span,
+            other.expect_span(),
+        )),
+    }
+}
+
+fn get_column<'a>(row: &'a Row, column: &str) -> Option<&'a Value> {
+    row.0
+        .iter()
+        .position(|col| col == column)
+        .map(|idx| &row.1[idx])
+}
+
+/// The key of a row as strings, so that rows can be looked up by it, or `None` when one of the
+/// key columns is missing or null
+fn row_key(row: &Row, columns: &[String]) -> Result<Option<Vec<String>>, ShellError> {
+    let mut key = Vec::with_capacity(columns.len());
+    for column in columns {
+        match get_column(row, column) {
+            None | Some(Value::Nothing { .. }) => return Ok(None),
+            Some(value) => key.push(value_to_string(value, Span::unknown())?),
+        }
+    }
+    Ok(Some(key))
+}
+
+fn union_columns(rows: &[Row]) -> Vec<String> {
+    let mut columns: Vec<String> = vec![];
+    for (cols, _) in rows {
+        for col in cols {
+            if !columns.contains(col) {
+                columns.push(col.clone());
+            }
+        }
+    }
+    columns
+}
+
+fn index_rows(
+    rows: &[Row],
+    columns: &[String],
+) -> Result<HashMap<Vec<String>, Vec<usize>>, ShellError> {
+    let mut index: HashMap<Vec<String>, Vec<usize>> = HashMap::new();
+    for (idx, row) in rows.iter().enumerate() {
+        if let Some(key) = row_key(row, columns)? {
+            index.entry(key).or_default().push(idx);
+        }
+    }
+    Ok(index)
+}
+
+struct Joiner<'a> {
+    left_rows: &'a [Row],
+    right_rows: &'a [Row],
+    left_on: &'a [String],
+    right_on: &'a [String],
+    left_columns: Vec<String>,
+    /// The columns of the right table in the result, with their name in the right table
+    right_columns: Vec<(String, String)>,
+    /// Key columns of the input that are shared with the right table, with their name there
+    shared_keys: HashMap<String, String>,
+}
+
+impl<'a> Joiner<'a> {
+    fn new(
+        left_rows: &'a [Row],
+        right_rows: &'a [Row],
+        left_on: &'a [String],
+        right_on: &'a [String],
+        suffix: &str,
+    ) -> Self {
+        let left_columns = union_columns(left_rows);
+        let shared_keys: HashMap<String, String> = left_on
+            .iter()
+            .zip(right_on)
+            .filter(|(left, right)| left == right)
+            .map(|(left, right)| (left.clone(), right.clone()))
+            .collect();
+
+        let right_columns = union_columns(right_rows)
+            .into_iter()
+            .filter(|col| !shared_keys.contains_key(col))
+            .map(|col| {
+                let name = if left_columns.contains(&col) {
+                    format!("{col}{suffix}")
+                } else {
+                    col.clone()
+                };
+                (name, col)
+            })
+            .collect();
+
+        Joiner {
+            left_rows,
+            right_rows,
+            left_on,
+            right_on,
+            left_columns,
+            right_columns,
+            shared_keys,
+        }
+    }
+
+    fn join(&self, join_type: JoinType, head: Span) -> Result<Vec<Value>, ShellError> {
+        let mut joined = vec![];
+        match join_type {
+            JoinType::Cross => {
+                for left in self.left_rows {
+                    for right in self.right_rows {
+                        joined.push(self.row(Some(left), Some(right), head));
+                    }
+                }
+            }
+            JoinType::Right => {
+                let index = index_rows(self.left_rows, self.left_on)?;
+                for right in self.right_rows {
+                    let matches = match row_key(right, self.right_on)? {
+                        Some(key) => index.get(&key).map(Vec::as_slice).unwrap_or_default(),
+                        None => &[],
+                    };
+                    if matches.is_empty() {
+                        joined.push(self.row(None, Some(right), head));
+                    }
+                    for idx in matches {
+                        joined.push(self.row(Some(&self.left_rows[*idx]), Some(right), head));
+                    }
+                }
+            }
+            JoinType::Inner | JoinType::Left | JoinType::Outer => {
+                let index = index_rows(self.right_rows, self.right_on)?;
+                let mut matched = vec![false; self.right_rows.len()];
+                for left in self.left_rows {
+                    let matches = match row_key(left, self.left_on)? {
+                        Some(key) => index.get(&key).map(Vec::as_slice).unwrap_or_default(),
+                        None => &[],
+                    };
+                    if matches.is_empty() && join_type != JoinType::Inner {
+                        joined.push(self.row(Some(left), None, head));
+                    }
+                    for idx in matches {
+                        matched[*idx] = true;
+                        joined.push(self.row(Some(left), Some(&self.right_rows[*idx]), head));
+                    }
+                }
+
+                if join_type == JoinType::Outer {
+                    for (right, _) in self
+                        .right_rows
+                        .iter()
+                        .zip(matched)
+                        .filter(|(_, matched)| !matched)
+                    {
+                        joined.push(self.row(None, Some(right), head));
+                    }
+                }
+            }
+        }
+        Ok(joined)
+    }
+
+    /// Build a row of the result, with null for the columns of a missing side
+    fn row(&self, left: Option<&Row>, right: Option<&Row>, head: Span) -> Value {
+        let mut cols = Vec::with_capacity(self.left_columns.len() + self.right_columns.len());
+        let mut vals = Vec::with_capacity(cols.capacity());
+
+        for col in &self.left_columns {
+            let value = match left {
+                Some(left) => get_column(left, col),
+                // A shared key column still gets its value from the right table
+                None => self
+                    .shared_keys
+                    .get(col)
+                    .and_then(|right_col| right.and_then(|right| get_column(right, right_col))),
+            };
+            cols.push(col.clone());
+            vals.push(value.cloned().unwrap_or_else(|| Value::nothing(head)));
+        }
+
+        for (name, col) in &self.right_columns {
+            let value = right.and_then(|right| get_column(right, col));
+            cols.push(name.clone());
+            vals.push(value.cloned().unwrap_or_else(|| Value::nothing(head)));
+        }
+
+        Value::Record {
+            cols,
+            vals,
+            span: head,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Join {})
+    }
+}
@@ -3,12 +3,20 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Type, Value,
+    Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
 pub struct Merge;
 
+/// How lists found in both values are merged by `merge --deep`
+#[derive(Clone, Copy)]
+enum ListMerge {
+    Replace,
+    Append,
+    IndexMerge,
+}
+
 impl Command for Merge {
     fn name(&self) -> &str {
         "merge"
@@ -23,7 +31,13 @@ impl Command for Merge {
 
 When merging tables, row 0 of the input table is overwritten
 with values from row 0 of the provided table, then
-repeating this process with row 1, and so on."#
+repeating this process with row 1, and so on.
+
+With --deep, records found in both values are merged in turn
+instead of being replaced, at any depth. Lists found in both
+values are replaced by default. With --lists append they are
+joined, and with --lists index-merge their items are merged
+one by one, by position."#
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -38,6 +52,17 @@ repeating this process with row 1, and so on."#
                 SyntaxShape::Any,
                 "the new value to merge with",
             )
+            .switch(
+                "deep",
+                "merge nested records instead of replacing them",
+                Some('d'),
+            )
+            .named(
+                "lists",
+                SyntaxShape::String,
+                "how to merge nested lists with --deep: replace (default), append or index-merge",
+                None,
+            )
             .category(Category::Filters)
     }
 
@@ -84,6 +109,53 @@ repeating this process with row 1, and so on."#
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: "{a: {b: 1, c: 2}, d: [1]} | merge --deep {a: {c: 3}, d: [2]}",
+                description: "Merge nested records, replacing nested lists",
+                result: Some(Value::test_record(
+                    vec!["a", "d"],
+                    vec![
+                        Value::test_record(
+                            vec!["b", "c"],
+                            vec![Value::test_int(1), Value::test_int(3)],
+                        ),
+                        Value::List {
+                            vals: vec![Value::test_int(2)],
+                            span: Span::test_data(),
+                        },
+                    ],
+                )),
+            },
+            Example {
+                example: "{plugins: [a b]} | merge --deep --lists append {plugins: [c]}",
+                description: "Merge records, joining nested lists",
+                result: Some(Value::test_record(
+                    vec!["plugins"],
+                    vec![Value::List {
+                        vals: vec![
+                            Value::test_string("a"),
+                            Value::test_string("b"),
+                            Value::test_string("c"),
+                        ],
+                        span: Span::test_data(),
+                    }],
+                )),
+            },
+            Example {
+                example:
+                    "{hooks: [{x: 1, y: 2}]} | merge --deep --lists index-merge {hooks: [{y: 3}]}",
+                description: "Merge records, merging the items of nested lists by position",
+                result: Some(Value::test_record(
+                    vec!["hooks"],
+                    vec![Value::List {
+                        vals: vec![Value::test_record(
+                            vec!["x", "y"],
+                            vec![Value::test_int(1), Value::test_int(3)],
+                        )],
+                        span: Span::test_data(),
+                    }],
+                )),
+            },
         ]
     }
 
@@ -95,6 +167,31 @@ repeating this process with row 1, and so on."#
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let merge_value: Value = call.req(engine_state, stack, 0)?;
+        let deep = call.has_flag("deep");
+        let lists: Option<Spanned<String>> = call.get_flag(engine_state, stack, "lists")?;
+
+        let lists = match lists {
+            Some(lists) if !deep => {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    "--lists only applies with --deep".into(),
+                    lists.span,
+                ))
+            }
+            Some(lists) => match lists.item.as_str() {
+                "replace" => ListMerge::Replace,
+                "append" => ListMerge::Append,
+                "index-merge" => ListMerge::IndexMerge,
+                _ => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "replace, append or index-merge".into(),
+                        lists.item,
+                        lists.span,
+                    ))
+                }
+            },
+            None => ListMerge::Replace,
+        };
+        let deep = deep.then_some(lists);
 
         let metadata = input.metadata();
         let ctrlc = engine_state.ctrlc.clone();
@@ -118,6 +215,7 @@ repeating this process with row 1, and so on."#
                                         let (cols, vals) = do_merge(
                                             (inp_cols.to_vec(), inp_vals.to_vec()),
                                             (to_merge_cols.to_vec(), to_merge_vals.to_vec()),
+                                            deep,
                                         );
                                         Value::Record {
                                             cols,
@@ -157,6 +255,7 @@ repeating this process with row 1, and so on."#
                 let (cols, vals) = do_merge(
                     (inp_cols.to_vec(), inp_vals.to_vec()),
                     (to_merge_cols.to_vec(), to_merge_vals.to_vec()),
+                    deep,
                 );
                 Ok(Value::Record {
                     cols,
@@ -192,6 +291,7 @@ repeating this process with row 1, and so on."#
 fn do_merge(
     input_record: (Vec<String>, Vec<Value>),
     to_merge_record: (Vec<String>, Vec<Value>),
+    deep: Option<ListMerge>,
 ) -> (Vec<String>, Vec<Value>) {
     let (mut result_cols, mut result_vals) = input_record;
     let (to_merge_cols, to_merge_vals) = to_merge_record;
@@ -201,7 +301,10 @@ fn do_merge(
         // if find, replace existing data, else, push new data.
         match pos {
             Some(index) => {
-                result_vals[index] = val;
+                result_vals[index] = match deep {
+                    Some(lists) => merge_deep(result_vals[index].clone(), val, lists),
+                    None => val,
+                };
             }
             None => {
                 result_cols.push(col);
@@ -212,6 +315,60 @@ fn do_merge(
     (result_cols, result_vals)
 }
 
+/// Merge two values found under the same column with `merge --deep`
+fn merge_deep(input: Value, to_merge: Value, lists: ListMerge) -> Value {
+    match (input, to_merge) {
+        (
+            Value::Record {
+                cols: inp_cols,
+                vals: inp_vals,
+                span,
+            },
+            Value::Record {
+                cols: to_merge_cols,
+                vals: to_merge_vals,
+                ..
+            },
+        ) => {
+            let (cols, vals) = do_merge(
+                (inp_cols, inp_vals),
+                (to_merge_cols, to_merge_vals),
+                Some(lists),
+            );
+            Value::Record { cols, vals, span }
+        }
+        (
+            Value::List {
+                vals: inp_vals,
+                span,
+            },
+            Value::List {
+                vals: to_merge_vals,
+                ..
+            },
+        ) => {
+            let vals = match lists {
+                ListMerge::Replace => to_merge_vals,
+                ListMerge::Append => inp_vals.into_iter().chain(to_merge_vals).collect(),
+                ListMerge::IndexMerge => {
+                    let mut to_merge_iter = to_merge_vals.into_iter();
+                    let mut vals: Vec<Value> = inp_vals
+                        .into_iter()
+                        .map(|inp| match to_merge_iter.next() {
+                            Some(to_merge) => merge_deep(inp, to_merge, lists),
+                            None => inp,
+                        })
+                        .collect();
+                    vals.extend(to_merge_iter);
+                    vals
+                }
+            };
+            Value::List { vals, span }
+        }
+        (_, to_merge) => to_merge,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,3 +1,4 @@
+mod aggregate;
 mod all;
 mod any;
 mod append;
@@ -20,6 +21,7 @@ mod group;
 mod group_by;
 mod headers;
 mod insert;
+mod join;
 mod last;
 mod length;
 mod lines;
@@ -41,7 +43,10 @@ mod sort;
 mod sort_by;
 mod split_by;
 mod take;
+mod tee;
+mod toposort;
 mod transpose;
+mod unflatten;
 mod uniq;
 mod uniq_by;
 mod update;
@@ -54,6 +59,7 @@ mod window;
 mod wrap;
 mod zip;
 
+pub use aggregate::Aggregate;
 pub use all::All;
 pub use any::Any;
 pub use append::Append;
@@ -76,6 +82,7 @@ pub use group::Group;
 pub use group_by::GroupBy;
 pub use headers::Headers;
 pub use insert::Insert;
+pub use join::Join;
 pub use last::Last;
 pub use length::Length;
 pub use lines::Lines;
@@ -97,7 +104,10 @@ pub use sort::Sort;
 pub use sort_by::SortBy;
 pub use split_by::SplitBy;
 pub use take::*;
+pub use tee::Tee;
+pub use toposort::Toposort;
 pub use transpose::Transpose;
+pub use unflatten::Unflatten;
 pub use uniq::*;
 pub use uniq_by::UniqBy;
 pub use update::Update;
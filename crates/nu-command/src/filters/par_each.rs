@@ -3,15 +3,23 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Closure, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Type, Value,
+    Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use super::utils::chain_error_with_input;
 
 #[derive(Clone)]
 pub struct ParEach;
 
+#[derive(Clone, Copy)]
+enum OnError {
+    Collect,
+    Skip,
+    Abort,
+}
+
 impl Command for ParEach {
     fn name(&self) -> &str {
         "par-each"
@@ -35,9 +43,33 @@ impl Command for ParEach {
                 SyntaxShape::Closure(Some(vec![SyntaxShape::Any, SyntaxShape::Int])),
                 "the closure to run",
             )
+            .named(
+                "threads",
+                SyntaxShape::Int,
+                "the number of threads to run the closure on (default: one per CPU)",
+                Some('t'),
+            )
+            .switch(
+                "keep-order",
+                "keep the results in the order of the input",
+                Some('k'),
+            )
+            .named(
+                "on-error",
+                SyntaxShape::String,
+                "what to do with errors: collect them in the output (default), skip them, or abort",
+                None,
+            )
             .category(Category::Filters)
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Errors, whether raised by the closure or read from the input, are kept in the output
+by default. With --on-error skip they are dropped. With --on-error abort, no new row is started
+once one has failed: the rows already running are let finish, and the first error among the
+results is returned."#
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![
             Example {
@@ -75,6 +107,42 @@ impl Command for ParEach {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: r#"[a b c] | par-each --keep-order {|e, i| $"($i): ($e)" }"#,
+                description: "Read the index of each element from a second closure parameter",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_string("0: a"),
+                        Value::test_string("1: b"),
+                        Value::test_string("2: c"),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[1 2 3] | par-each --keep-order { 2 * $in }",
+                description: "Keep the results in the order of the input",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(2), Value::test_int(4), Value::test_int(6)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[1 2 3] | par-each --threads 2 --keep-order {|n| $n * $n }",
+                description: "Run the closure on at most 2 threads at a time",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(1), Value::test_int(4), Value::test_int(9)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[2 0 5] | par-each --keep-order --on-error skip {|n| 10 / $n }",
+                description: "Leave out the rows the closure failed on",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(5), Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 
@@ -86,155 +154,66 @@ impl Command for ParEach {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let capture_block: Closure = call.req(engine_state, stack, 0)?;
+        let threads: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "threads")?;
+        let keep_order = call.has_flag("keep-order");
+        let on_error: Option<Spanned<String>> = call.get_flag(engine_state, stack, "on-error")?;
+
+        let pool = match threads {
+            Some(threads) if threads.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue(threads.span))
+            }
+            Some(threads) => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads.item as usize)
+                    .build()
+                    .map_err(|err| {
+                        ShellError::GenericError(
+                            "Failed to start the threads".into(),
+                            err.to_string(),
+                            Some(threads.span),
+                            None,
+                            Vec::new(),
+                        )
+                    })?,
+            ),
+            None => None,
+        };
+        let on_error = match on_error {
+            None => OnError::Collect,
+            Some(on_error) => match on_error.item.as_str() {
+                "collect" => OnError::Collect,
+                "skip" => OnError::Skip,
+                "abort" => OnError::Abort,
+                _ => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "collect, skip or abort".into(),
+                        on_error.item,
+                        on_error.span,
+                    ))
+                }
+            },
+        };
 
         let metadata = input.metadata();
         let ctrlc = engine_state.ctrlc.clone();
         let block_id = capture_block.block_id;
         let mut stack = stack.captures_to_stack(&capture_block.captures);
+        let span = call.head;
         let redirect_stdout = call.redirect_stdout;
         let redirect_stderr = call.redirect_stderr;
 
-        match input {
-            PipelineData::Empty => Ok(PipelineData::Empty),
-            PipelineData::Value(Value::Range { val, .. }, ..) => Ok(val
-                .into_range_iter(ctrlc.clone())?
-                .par_bridge()
-                .map(move |x| {
-                    let block = engine_state.get_block(block_id);
-
-                    let mut stack = stack.clone();
-
-                    if let Some(var) = block.signature.get_positional(0) {
-                        if let Some(var_id) = &var.var_id {
-                            stack.add_var(*var_id, x.clone());
-                        }
-                    }
-
-                    let val_span = x.span();
-                    match eval_block_with_early_return(
-                        engine_state,
-                        &mut stack,
-                        block,
-                        x.into_pipeline_data(),
-                        redirect_stdout,
-                        redirect_stderr,
-                    ) {
-                        Ok(v) => v,
-                        Err(error) => Value::Error {
-                            error: chain_error_with_input(error, val_span),
-                        }
-                        .into_pipeline_data(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
-                .flatten()
-                .into_pipeline_data(ctrlc)),
-            PipelineData::Value(Value::List { vals: val, .. }, ..) => Ok(val
-                .into_iter()
-                .par_bridge()
-                .map(move |x| {
-                    let block = engine_state.get_block(block_id);
-
-                    let mut stack = stack.clone();
-
-                    if let Some(var) = block.signature.get_positional(0) {
-                        if let Some(var_id) = &var.var_id {
-                            stack.add_var(*var_id, x.clone());
-                        }
-                    }
-
-                    let val_span = x.span();
-                    match eval_block_with_early_return(
-                        engine_state,
-                        &mut stack,
-                        block,
-                        x.into_pipeline_data(),
-                        redirect_stdout,
-                        redirect_stderr,
-                    ) {
-                        Ok(v) => v,
-                        Err(error) => Value::Error {
-                            error: chain_error_with_input(error, val_span),
-                        }
-                        .into_pipeline_data(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
-                .flatten()
-                .into_pipeline_data(ctrlc)),
-            PipelineData::ListStream(stream, ..) => Ok(stream
-                .par_bridge()
-                .map(move |x| {
-                    let block = engine_state.get_block(block_id);
-
-                    let mut stack = stack.clone();
-
-                    if let Some(var) = block.signature.get_positional(0) {
-                        if let Some(var_id) = &var.var_id {
-                            stack.add_var(*var_id, x.clone());
-                        }
-                    }
-
-                    let val_span = x.span();
-                    match eval_block_with_early_return(
-                        engine_state,
-                        &mut stack,
-                        block,
-                        x.into_pipeline_data(),
-                        redirect_stdout,
-                        redirect_stderr,
-                    ) {
-                        Ok(v) => v,
-                        Err(error) => Value::Error {
-                            error: chain_error_with_input(error, val_span),
-                        }
-                        .into_pipeline_data(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
-                .flatten()
-                .into_pipeline_data(ctrlc)),
-            PipelineData::ExternalStream { stdout: None, .. } => Ok(PipelineData::empty()),
+        let values: Box<dyn Iterator<Item = Result<Value, ShellError>> + Send> = match input {
+            PipelineData::Empty => return Ok(PipelineData::Empty),
+            PipelineData::Value(Value::Range { val, .. }, ..) => {
+                Box::new(val.into_range_iter(ctrlc.clone())?.map(Ok))
+            }
+            PipelineData::Value(Value::List { vals, .. }, ..) => Box::new(vals.into_iter().map(Ok)),
+            PipelineData::ListStream(stream, ..) => Box::new(stream.map(Ok)),
+            PipelineData::ExternalStream { stdout: None, .. } => return Ok(PipelineData::empty()),
             PipelineData::ExternalStream {
                 stdout: Some(stream),
                 ..
-            } => Ok(stream
-                .par_bridge()
-                .map(move |x| {
-                    let x = match x {
-                        Ok(x) => x,
-                        Err(err) => return Value::Error { error: err }.into_pipeline_data(),
-                    };
-
-                    let block = engine_state.get_block(block_id);
-
-                    let mut stack = stack.clone();
-
-                    if let Some(var) = block.signature.get_positional(0) {
-                        if let Some(var_id) = &var.var_id {
-                            stack.add_var(*var_id, x.clone());
-                        }
-                    }
-
-                    match eval_block_with_early_return(
-                        engine_state,
-                        &mut stack,
-                        block,
-                        x.into_pipeline_data(),
-                        redirect_stdout,
-                        redirect_stderr,
-                    ) {
-                        Ok(v) => v,
-                        Err(error) => Value::Error { error }.into_pipeline_data(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
-                .flatten()
-                .into_pipeline_data(ctrlc)),
+            } => Box::new(stream),
             // This match allows non-iterables to be accepted,
             // which is currently considered undesirable (Nov 2022).
             PipelineData::Value(x, ..) => {
@@ -246,7 +225,14 @@ impl Command for ParEach {
                     }
                 }
 
-                eval_block_with_early_return(
+                // Index argument
+                if let Some(var) = block.signature.get_positional(1) {
+                    if let Some(var_id) = &var.var_id {
+                        stack.add_var(*var_id, Value::int(0, span));
+                    }
+                }
+
+                return eval_block_with_early_return(
                     engine_state,
                     &mut stack,
                     block,
@@ -254,7 +240,101 @@ impl Command for ParEach {
                     redirect_stdout,
                     redirect_stderr,
                 )
+                .map(|res| res.set_metadata(metadata));
             }
+        };
+
+        // Set on the first error with --on-error abort, so that no more rows are started
+        let stopped = &AtomicBool::new(false);
+        let abort = matches!(on_error, OnError::Abort);
+
+        let run_closure = move |(index, x): (usize, Result<Value, ShellError>)| {
+            if stopped.load(Ordering::Relaxed) {
+                return (index, PipelineData::Empty);
+            }
+            let x = match x {
+                Ok(x) => x,
+                Err(err) => {
+                    if abort {
+                        stopped.store(true, Ordering::Relaxed);
+                    }
+                    return (index, Value::Error { error: err }.into_pipeline_data());
+                }
+            };
+
+            let block = engine_state.get_block(block_id);
+
+            let mut stack = stack.clone();
+
+            if let Some(var) = block.signature.get_positional(0) {
+                if let Some(var_id) = &var.var_id {
+                    stack.add_var(*var_id, x.clone());
+                }
+            }
+
+            // Index argument
+            if let Some(var) = block.signature.get_positional(1) {
+                if let Some(var_id) = &var.var_id {
+                    stack.add_var(*var_id, Value::int(index as i64, span));
+                }
+            }
+
+            let val_span = x.span();
+            let output = match eval_block_with_early_return(
+                engine_state,
+                &mut stack,
+                block,
+                x.into_pipeline_data(),
+                redirect_stdout,
+                redirect_stderr,
+            ) {
+                Ok(v) => v,
+                Err(error) => Value::Error {
+                    error: chain_error_with_input(error, val_span),
+                }
+                .into_pipeline_data(),
+            };
+
+            if abort {
+                // The output is read here, to find an error it may stream
+                let vals: Vec<Value> = output.into_iter().collect();
+                if vals.iter().any(|val| matches!(val, Value::Error { .. })) {
+                    stopped.store(true, Ordering::Relaxed);
+                }
+                return (index, Value::List { vals, span }.into_pipeline_data());
+            }
+            (index, output)
+        };
+        let run_all = || {
+            values
+                .enumerate()
+                .take_while(|_| !stopped.load(Ordering::Relaxed))
+                .par_bridge()
+                .map(run_closure)
+                .collect::<Vec<_>>()
+        };
+
+        let mut outputs = match pool {
+            Some(pool) => pool.install(run_all),
+            None => run_all(),
+        };
+        if keep_order {
+            outputs.sort_by_key(|(index, _)| *index);
+        }
+        let outputs = outputs.into_iter().flat_map(|(_, output)| output);
+
+        match on_error {
+            OnError::Collect => Ok(outputs.into_pipeline_data(ctrlc)),
+            OnError::Skip => Ok(outputs
+                .filter(|value| !matches!(value, Value::Error { .. }))
+                .into_pipeline_data(ctrlc)),
+            OnError::Abort => Ok(outputs
+                .map(|value| match value {
+                    Value::Error { error } => Err(error),
+                    value => Ok(value),
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .into_pipeline_data(ctrlc)),
         }
         .map(|res| res.set_metadata(metadata))
     }
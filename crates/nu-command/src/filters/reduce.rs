@@ -1,4 +1,4 @@
-use nu_engine::{eval_block_with_early_return, CallExt};
+use nu_engine::{eval_block, eval_block_with_early_return, CallExt};
 
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Closure, Command, EngineState, Stack};
@@ -35,6 +35,12 @@ impl Command for Reduce {
                 ])),
                 "reducing function",
             )
+            .named(
+                "until",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
+                "stop reducing once this closure returns true for the accumulator",
+                Some('u'),
+            )
     }
 
     fn usage(&self) -> &str {
@@ -53,8 +59,7 @@ impl Command for Reduce {
                 result: Some(Value::test_int(10)),
             },
             Example {
-                example:
-                    "[ 8 7 6 ] | enumerate | reduce -f 0 {|it, acc| $acc + $it.item + $it.index }",
+                example: "[ 8 7 6 ] | reduce -f 0 {|it, acc, i| $acc + $it + $i }",
                 description: "Sum values of a list, plus their indexes",
                 result: Some(Value::test_int(24)),
             },
@@ -74,6 +79,12 @@ impl Command for Reduce {
                     "Add ascending numbers to each of the filenames, and join with semicolons.",
                 result: Some(Value::test_string("1-foo.gz; 2-bar.gz; 3-baz.gz")),
             },
+            Example {
+                example:
+                    "[1 2 3 4 5 6 7] | reduce --until {|acc| $acc > 10 } {|it, acc| $acc + $it }",
+                description: "Sum numbers until the sum gets above 10",
+                result: Some(Value::test_int(15)),
+            },
         ]
     }
 
@@ -88,6 +99,13 @@ impl Command for Reduce {
 
         let fold: Option<Value> = call.get_flag(engine_state, stack, "fold")?;
         let capture_block: Closure = call.req(engine_state, stack, 0)?;
+        let until: Option<Closure> = call.get_flag(engine_state, stack, "until")?;
+        let mut until = until.map(|until| {
+            (
+                engine_state.get_block(until.block_id),
+                stack.captures_to_stack(&until.captures),
+            )
+        });
         let mut stack = stack.captures_to_stack(&capture_block.captures);
         let block = engine_state.get_block(capture_block.block_id);
         let ctrlc = engine_state.ctrlc.clone();
@@ -100,11 +118,11 @@ impl Command for Reduce {
 
         // To enumerate over the input (for the index argument),
         // it must be converted into an iterator using into_iter().
-        let mut input_iter = input.into_iter();
+        let mut input_iter = input.into_iter().enumerate();
 
         let start_val = if let Some(val) = fold {
             val
-        } else if let Some(val) = input_iter.next() {
+        } else if let Some((_, val)) = input_iter.next() {
             val
         } else {
             return Err(ShellError::GenericError(
@@ -120,7 +138,7 @@ impl Command for Reduce {
 
         let mut input_iter = input_iter.peekable();
 
-        while let Some(x) = input_iter.next() {
+        while let Some((index, x)) = input_iter.next() {
             // with_env() is used here to ensure that each iteration uses
             // a different set of environment variables.
             // Hence, a 'cd' in the first loop won't affect the next loop.
@@ -140,6 +158,13 @@ impl Command for Reduce {
                 }
             }
 
+            // Index argument
+            if let Some(var) = block.signature.get_positional(2) {
+                if let Some(var_id) = &var.var_id {
+                    stack.add_var(*var_id, Value::int(index as i64, span));
+                }
+            }
+
             acc = eval_block_with_early_return(
                 engine_state,
                 &mut stack,
@@ -151,6 +176,30 @@ impl Command for Reduce {
             )?
             .into_value(span);
 
+            // The condition is checked before taking the next element, so an endless
+            // input is not read any further than needed.
+            if let Some((until_block, until_stack)) = &mut until {
+                if let Some(var) = until_block.signature.get_positional(0) {
+                    if let Some(var_id) = &var.var_id {
+                        until_stack.add_var(*var_id, acc.clone());
+                    }
+                }
+
+                let done = eval_block(
+                    engine_state,
+                    until_stack,
+                    until_block,
+                    acc.clone().into_pipeline_data(),
+                    false,
+                    false,
+                )?
+                .into_value(span)
+                .is_true();
+                if done {
+                    break;
+                }
+            }
+
             if nu_utils::ctrl_c::was_pressed(&ctrlc) {
                 break;
             }
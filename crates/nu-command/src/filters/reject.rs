@@ -1,3 +1,4 @@
+use super::utils::ColumnSpec;
 use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
@@ -25,6 +26,16 @@ impl Command for Reject {
                 SyntaxShape::CellPath,
                 "the names of columns to remove from the table",
             )
+            .switch(
+                "ignore-missing",
+                "skip the columns the input doesn't have, instead of erroring out",
+                Some('m'),
+            )
+            .switch(
+                "regex",
+                "match the names of the columns with regular expressions",
+                Some('r'),
+            )
             .category(Category::Filters)
     }
 
@@ -32,6 +43,12 @@ impl Command for Reject {
         "Remove the given columns from the table. To remove rows, use 'drop'."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"A column with a '*', '?' or '[' in its name is a glob pattern, and removes all the
+columns of each row that match it, unless the row has a column of that very name. With --regex,
+every column name is a regular expression instead."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -41,7 +58,9 @@ impl Command for Reject {
     ) -> Result<PipelineData, ShellError> {
         let columns: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
         let span = call.head;
-        reject(engine_state, span, input, columns)
+        let ignore_missing = call.has_flag("ignore-missing");
+        let regex = call.has_flag("regex");
+        reject(engine_state, span, input, columns, ignore_missing, regex)
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -72,6 +91,27 @@ impl Command for Reject {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Reject the columns whose names match a glob pattern",
+                example: "[[name tmp_a tmp_b]; [x 1 2]] | reject 'tmp_*'",
+                result: Some(Value::List {
+                    vals: vec![Value::test_record(
+                        vec!["name"],
+                        vec![Value::test_string("x")],
+                    )],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Reject the columns whose names match a regular expression",
+                example: "{tmp_a: 1, b: 2} | reject --regex '^tmp_'",
+                result: Some(Value::test_record(vec!["b"], vec![Value::test_int(2)])),
+            },
+            Example {
+                description: "Skip the columns that are missing",
+                example: "{a: 1, b: 2} | reject --ignore-missing b c",
+                result: Some(Value::test_record(vec!["a"], vec![Value::test_int(1)])),
+            },
             Example {
                 description: "Reject a nested field in a record",
                 example: "{a: {b: 3, c: 5}} | reject a.b",
@@ -94,6 +134,8 @@ fn reject(
     span: Span,
     input: PipelineData,
     cell_paths: Vec<CellPath>,
+    ignore_missing: bool,
+    regex: bool,
 ) -> Result<PipelineData, ShellError> {
     let val = input.into_value(span);
     let mut val = val;
@@ -103,12 +145,37 @@ fn reject(
             columns.push(c);
         }
     }
-    for cell_path in columns {
-        val.remove_data_at_cell_path(&cell_path.members)?;
+    for column in ColumnSpec::parse_all(columns, regex)? {
+        match column {
+            ColumnSpec::Path(cell_path) => match val.remove_data_at_cell_path(&cell_path.members) {
+                Err(_) if ignore_missing => {}
+                result => result?,
+            },
+            pattern => remove_matching_columns(&mut val, &pattern),
+        }
     }
     Ok(val.into_pipeline_data())
 }
 
+/// Remove the columns that match a pattern, from a record or from each row of a table
+fn remove_matching_columns(val: &mut Value, pattern: &ColumnSpec) {
+    match val {
+        Value::Record { cols, vals, .. } => {
+            let matching: Vec<bool> = cols.iter().map(|col| pattern.matches(col, cols)).collect();
+            let mut keep = matching.iter().map(|matches| !matches);
+            cols.retain(|_| keep.next().unwrap_or(true));
+            let mut keep = matching.iter().map(|matches| !matches);
+            vals.retain(|_| keep.next().unwrap_or(true));
+        }
+        Value::List { vals, .. } => {
+            for row in vals {
+                remove_matching_columns(row, pattern);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
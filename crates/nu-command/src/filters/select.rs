@@ -1,3 +1,4 @@
+use super::utils::ColumnSpec;
 use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath, PathMember};
 use nu_protocol::engine::{Command, EngineState, Stack};
@@ -26,6 +27,16 @@ impl Command for Select {
                 "when an error occurs, instead of erroring out, suppress the error message",
                 Some('i'),
             )
+            .switch(
+                "ignore-missing",
+                "leave out the columns a row doesn't have, instead of erroring out",
+                Some('m'),
+            )
+            .switch(
+                "regex",
+                "match the names of the columns with regular expressions",
+                Some('r'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -38,6 +49,12 @@ impl Command for Select {
         "Down-select table to only these columns."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"A column with a '*', '?' or '[' in its name is a glob pattern, and selects all the
+columns of each row that match it, in their order in the row, unless the row has a column of
+that very name. With --regex, every column name is a regular expression instead."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["pick", "choose", "get"]
     }
@@ -52,8 +69,18 @@ impl Command for Select {
         let columns: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
         let span = call.head;
         let ignore_errors = call.has_flag("ignore-errors");
+        let ignore_missing = call.has_flag("ignore-missing");
+        let regex = call.has_flag("regex");
 
-        select(engine_state, span, columns, input, ignore_errors)
+        select(
+            engine_state,
+            span,
+            columns,
+            input,
+            ignore_errors,
+            ignore_missing,
+            regex,
+        )
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -71,6 +98,27 @@ impl Command for Select {
                 example: "{a: a b: b} | select a",
                 result: Some(Value::test_record(vec!["a"], vec![Value::test_string("a")])),
             },
+            Example {
+                description: "Select the columns whose names match a glob pattern",
+                example: "{metric_a: 1, metric_b: 2, name: x} | select 'metric_*'",
+                result: Some(Value::test_record(
+                    vec!["metric_a", "metric_b"],
+                    vec![Value::test_int(1), Value::test_int(2)],
+                )),
+            },
+            Example {
+                description: "Select the columns whose names match a regular expression",
+                example: "{tmp_a: 1, b: 2, tmp_c: 3} | select --regex '^tmp_'",
+                result: Some(Value::test_record(
+                    vec!["tmp_a", "tmp_c"],
+                    vec![Value::test_int(1), Value::test_int(3)],
+                )),
+            },
+            Example {
+                description: "Leave out the columns that are missing",
+                example: "{a: 1} | select --ignore-missing a b",
+                result: Some(Value::test_record(vec!["a"], vec![Value::test_int(1)])),
+            },
             Example {
                 description: "Select just the name column",
                 example: "ls | select name",
@@ -91,6 +139,8 @@ fn select(
     columns: Vec<CellPath>,
     input: PipelineData,
     ignore_errors: bool,
+    ignore_missing: bool,
+    regex: bool,
 ) -> Result<PipelineData, ShellError> {
     let mut rows = vec![];
 
@@ -118,7 +168,7 @@ fn select(
             _ => new_columns.push(column),
         };
     }
-    let columns = new_columns;
+    let columns = ColumnSpec::parse_all(new_columns, regex)?;
 
     let input = if !rows.is_empty() {
         rows.sort_unstable();
@@ -154,7 +204,7 @@ fn select(
                 if !columns.is_empty() {
                     let mut cols = vec![];
                     let mut vals = vec![];
-                    for path in &columns {
+                    for path in columns.iter().flat_map(|spec| spec.paths_in(&input_val)) {
                         //FIXME: improve implementation to not clone
                         match input_val.clone().follow_cell_path(
                             &path.members,
@@ -169,6 +219,7 @@ fn select(
                                     columns_with_value.push(path);
                                 }
                             }
+                            Err(_) if ignore_missing => {}
                             Err(e) => {
                                 return Err(e);
                             }
@@ -196,7 +247,7 @@ fn select(
                 if !columns.is_empty() {
                     let mut cols = vec![];
                     let mut vals = vec![];
-                    for path in &columns {
+                    for path in columns.iter().flat_map(|spec| spec.paths_in(&x)) {
                         //FIXME: improve implementation to not clone
                         match x
                             .clone()
@@ -206,6 +257,7 @@ fn select(
                                 cols.push(path.into_string().replace('.', "_"));
                                 vals.push(value);
                             }
+                            Err(_) if ignore_missing => {}
                             Err(e) => return Err(e),
                         }
                     }
@@ -228,7 +280,7 @@ fn select(
                 let mut cols = vec![];
                 let mut vals = vec![];
 
-                for cell_path in columns {
+                for cell_path in columns.iter().flat_map(|spec| spec.paths_in(&v)) {
                     // FIXME: remove clone
                     match v
                         .clone()
@@ -238,6 +290,7 @@ fn select(
                             cols.push(cell_path.into_string().replace('.', "_"));
                             vals.push(result);
                         }
+                        Err(_) if ignore_missing => {}
                         Err(e) => return Err(e),
                     }
                 }
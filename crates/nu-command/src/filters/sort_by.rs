@@ -1,3 +1,4 @@
+use crate::SortColumn;
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
@@ -17,7 +18,11 @@ impl Command for SortBy {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("sort-by")
             .input_output_types(vec![(Type::Table(vec![]), Type::Table(vec![]))])
-            .rest("columns", SyntaxShape::Any, "the column(s) to sort by")
+            .rest(
+                "columns",
+                SyntaxShape::Any,
+                "the column(s) to sort by, as names or records like {col: size, dir: desc}",
+            )
             .switch("reverse", "Sort in reverse order", Some('r'))
             .switch(
                 "ignore-case",
@@ -29,6 +34,11 @@ impl Command for SortBy {
                 "Sort alphanumeric string-based columns naturally (1, 9, 10, 99, 100, ...)",
                 Some('n'),
             )
+            .switch(
+                "collate",
+                "Sort strings in dictionary order, where accents and case only break ties",
+                Some('c'),
+            )
             .category(Category::Filters)
     }
 
@@ -36,6 +46,12 @@ impl Command for SortBy {
         "Sort by the given columns, in increasing order."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"A column can be given as a record with its name in `col` and its direction in `dir`,
+either asc or desc, to sort some columns in decreasing order and others in increasing order.
+--reverse reverses the whole result after that."#
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![
             Example {
@@ -69,6 +85,39 @@ impl Command for SortBy {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Sort by size, largest first, then by name",
+                example: "[[name size]; [b 1] [c 2] [a 2]] | sort-by {col: size, dir: desc} name",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("a"), Value::test_int(2)],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("c"), Value::test_int(2)],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("b"), Value::test_int(1)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Sort names in dictionary order",
+                example: "[[name]; [zoë] [Émile] [eve]] | sort-by name --collate",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(vec!["name"], vec![Value::test_string("Émile")]),
+                        Value::test_record(vec!["name"], vec![Value::test_string("eve")]),
+                        Value::test_record(vec!["name"], vec![Value::test_string("zoë")]),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 
@@ -79,10 +128,12 @@ impl Command for SortBy {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let columns: Vec<String> = call.rest(engine_state, stack, 0)?;
+        let columns: Vec<Value> = call.rest(engine_state, stack, 0)?;
+        let columns = sort_columns(columns)?;
         let reverse = call.has_flag("reverse");
         let insensitive = call.has_flag("ignore-case");
         let natural = call.has_flag("natural");
+        let collate = call.has_flag("collate");
         let metadata = &input.metadata();
         let mut vec: Vec<_> = input.into_iter_strict(call.head)?.collect();
 
@@ -90,7 +141,7 @@ impl Command for SortBy {
             return Err(ShellError::MissingParameter("columns".into(), call.head));
         }
 
-        crate::sort(&mut vec, columns, call.head, insensitive, natural)?;
+        crate::sort_by_columns(&mut vec, &columns, call.head, insensitive, natural, collate)?;
 
         if reverse {
             vec.reverse()
@@ -106,6 +157,53 @@ impl Command for SortBy {
     }
 }
 
+fn sort_columns(values: Vec<Value>) -> Result<Vec<SortColumn>, ShellError> {
+    let mut columns = vec![];
+    for value in values {
+        match value {
+            Value::String { val, .. } => columns.push(SortColumn {
+                name: val,
+                descending: false,
+            }),
+            Value::List { vals, .. } => columns.extend(sort_columns(vals)?),
+            Value::Record { .. } => {
+                let name = match value.get_data_by_key("col") {
+                    Some(name) => name.as_string()?,
+                    None => {
+                        return Err(ShellError::CantFindColumn(
+                            "col".into(),
+                            value.span()?,
+                            value.span()?,
+                        ))
+                    }
+                };
+                let descending = match value.get_data_by_key("dir") {
+                    None => false,
+                    Some(dir) => match dir.as_string()?.as_str() {
+                        "asc" => false,
+                        "desc" => true,
+                        other => {
+                            return Err(ShellError::UnsupportedConfigValue(
+                                "asc or desc".into(),
+                                other.into(),
+                                dir.span()?,
+                            ))
+                        }
+                    },
+                };
+                columns.push(SortColumn { name, descending });
+            }
+            other => {
+                return Err(ShellError::TypeMismatch(
+                    "expected a column name or a record like {col: name, dir: desc}".into(),
+                    other.span()?,
+                ))
+            }
+        }
+    }
+    Ok(columns)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2,8 +2,8 @@ use nu_engine::{eval_block, CallExt};
 use nu_protocol::{
     ast::Call,
     engine::{Closure, Command, EngineState, Stack},
-    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
-    SyntaxShape, Type, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
+    Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -100,7 +100,7 @@ impl Command for TakeWhile {
                     &engine_state,
                     &mut stack,
                     &block,
-                    PipelineData::empty(),
+                    value.clone().into_pipeline_data(),
                     redirect_stdout,
                     redirect_stderr,
                 )
@@ -0,0 +1,285 @@
+use nu_engine::{eval_block_with_early_return, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Closure, Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, RawStream,
+    ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+
+#[derive(Clone)]
+pub struct Tee;
+
+impl Command for Tee {
+    fn name(&self) -> &str {
+        "tee"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("tee")
+            .input_output_types(vec![(Type::Any, Type::Any)])
+            .required(
+                "closure",
+                SyntaxShape::Closure(None),
+                "the closure to run on a copy of the input",
+            )
+            .switch(
+                "stderr",
+                "copy the stderr of an external command instead of its stdout",
+                Some('e'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Pass the input through unchanged, while running a closure on a copy of it."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"A stream is copied as it goes by, to a closure running in the background, so it is
+read only once. The output of the closure is thrown away, and an error it returns is added at the
+end of the stream. With --stderr, the closure gets the stderr of an external command, which is
+then passed through as well."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["copy", "split", "log", "save"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let closure: Closure = call.req(engine_state, stack, 0)?;
+        let use_stderr = call.has_flag("stderr");
+
+        let closure_stack = stack.captures_to_stack(&closure.captures);
+        let tee = TeeClosure {
+            engine_state: engine_state.clone(),
+            stack: closure_stack,
+            closure,
+            span,
+        };
+
+        match input {
+            PipelineData::ExternalStream {
+                stdout,
+                stderr,
+                exit_code,
+                resource_usage,
+                span: stream_span,
+                metadata,
+                trim_end_newline,
+            } => {
+                let (stdout, stderr) = if use_stderr {
+                    (
+                        stdout,
+                        stderr.map(|stderr| tee.copy_raw(stderr)).transpose()?,
+                    )
+                } else {
+                    (
+                        stdout.map(|stdout| tee.copy_raw(stdout)).transpose()?,
+                        stderr,
+                    )
+                };
+                Ok(PipelineData::ExternalStream {
+                    stdout,
+                    stderr,
+                    exit_code,
+                    resource_usage,
+                    span: stream_span,
+                    metadata,
+                    trim_end_newline,
+                })
+            }
+            _ if use_stderr => Err(ShellError::IncompatibleParametersSingle(
+                "--stderr only applies to the output of an external command".into(),
+                span,
+            )),
+            PipelineData::Value(Value::List { .. } | Value::Range { .. }, ..)
+            | PipelineData::ListStream(..) => {
+                let metadata = input.metadata();
+                let ctrlc = engine_state.ctrlc.clone();
+                let (sender, receiver) = mpsc::channel();
+                let handle = tee.spawn(receiver.into_iter().into_pipeline_data(ctrlc.clone()))?;
+                Ok(TeeIterator {
+                    input: input.into_iter(),
+                    sender: Some(sender),
+                    handle: Some(handle),
+                    on_error: |error| Value::Error { error },
+                    span,
+                }
+                .into_pipeline_data_with_metadata(metadata, ctrlc))
+            }
+            PipelineData::Value(value, metadata) => {
+                tee.run(value.clone().into_pipeline_data())?;
+                Ok(PipelineData::Value(value, metadata))
+            }
+            PipelineData::Empty => {
+                tee.run(PipelineData::Empty)?;
+                Ok(PipelineData::Empty)
+            }
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Save a copy of a list while passing it on",
+                example: "[1 2 3] | tee { save numbers.nuon } | each {|n| $n * 2 }",
+                result: None,
+            },
+            Example {
+                description: "Log the output of an external command while it runs",
+                example: "^cargo build | tee { save build.log } | lines",
+                result: None,
+            },
+            Example {
+                description: "Save the errors of an external command, and still show them",
+                example: "^cargo build --quiet | tee --stderr { save errors.log }",
+                result: None,
+            },
+            Example {
+                description: "The output of the closure is thrown away",
+                example: "[1 2 3] | tee { each {|n| $n * 10 } }",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(1), Value::test_int(2), Value::test_int(3)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// The closure given to `tee`, with what it needs to run on its own thread
+struct TeeClosure {
+    engine_state: EngineState,
+    stack: Stack,
+    closure: Closure,
+    span: Span,
+}
+
+impl TeeClosure {
+    /// Run the closure on its input, keeping only an error it returns
+    fn run(mut self, input: PipelineData) -> Result<(), ShellError> {
+        let block = self.engine_state.get_block(self.closure.block_id);
+        let output = eval_block_with_early_return(
+            &self.engine_state,
+            &mut self.stack,
+            block,
+            input,
+            false,
+            false,
+        )?
+        .into_value(self.span);
+        match output {
+            Value::Error { error } => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    fn spawn(self, input: PipelineData) -> Result<JoinHandle<Result<(), ShellError>>, ShellError> {
+        let span = self.span;
+        thread::Builder::new()
+            .name("tee".into())
+            .spawn(move || self.run(input))
+            .map_err(|e| ShellError::IOErrorSpanned(e.to_string(), span))
+    }
+
+    /// Pass a raw stream through, running the closure on a copy of its bytes
+    fn copy_raw(self, stream: RawStream) -> Result<RawStream, ShellError> {
+        let span = self.span;
+        let (sender, receiver) = mpsc::channel();
+        let mut copy = RawStream::new(
+            Box::new(receiver.into_iter()),
+            stream.ctrlc.clone(),
+            stream.span,
+            stream.known_size,
+        );
+        copy.is_binary = stream.is_binary;
+        let handle = self.spawn(PipelineData::ExternalStream {
+            stdout: Some(copy),
+            stderr: None,
+            exit_code: None,
+            resource_usage: None,
+            span: stream.span,
+            metadata: None,
+            trim_end_newline: false,
+        })?;
+
+        Ok(RawStream {
+            stream: Box::new(TeeIterator {
+                input: stream.stream,
+                sender: Some(sender),
+                handle: Some(handle),
+                on_error: Err,
+                span,
+            }),
+            ..stream
+        })
+    }
+}
+
+/// Passes the items of a stream through, sending a copy of each one to the closure. Once the
+/// stream ends, it waits for the closure to finish, and adds the error it returned, if any.
+struct TeeIterator<I: Iterator> {
+    input: I,
+    sender: Option<mpsc::Sender<I::Item>>,
+    handle: Option<JoinHandle<Result<(), ShellError>>>,
+    on_error: fn(ShellError) -> I::Item,
+    span: Span,
+}
+
+impl<I> Iterator for TeeIterator<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.input.next() {
+            Some(item) => {
+                // The closure may stop reading before the end, the stream goes on without it
+                let closed = match &self.sender {
+                    Some(sender) => sender.send(item.clone()).is_err(),
+                    None => false,
+                };
+                if closed {
+                    self.sender = None;
+                }
+                Some(item)
+            }
+            None => {
+                // Dropping the sender ends the input of the closure
+                self.sender = None;
+                match self.handle.take()?.join() {
+                    Ok(Ok(())) => None,
+                    Ok(Err(error)) => Some((self.on_error)(error)),
+                    Err(_) => Some((self.on_error)(ShellError::NushellFailedSpanned(
+                        "The closure of tee stopped without a result".into(),
+                        "the closure panicked".into(),
+                        self.span,
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Tee {})
+    }
+}
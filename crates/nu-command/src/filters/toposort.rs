@@ -0,0 +1,362 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Type, Value,
+};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Clone)]
+pub struct Toposort;
+
+impl Command for Toposort {
+    fn name(&self) -> &str {
+        "toposort"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("toposort")
+            .input_output_types(vec![
+                (Type::Table(vec![]), Type::Table(vec![])),
+                (Type::Table(vec![]), Type::List(Box::new(Type::Any))),
+                // A table with nulls or empty lists in a column is a list of mixed records
+                (
+                    Type::List(Box::new(Type::Any)),
+                    Type::List(Box::new(Type::Any)),
+                ),
+            ])
+            .allow_variants_without_examples(true)
+            .named(
+                "key",
+                SyntaxShape::String,
+                "the column naming each item (default: name)",
+                Some('k'),
+            )
+            .named(
+                "depends-on",
+                SyntaxShape::String,
+                "the column listing what each item depends on (default: depends_on)",
+                Some('d'),
+            )
+            .switch(
+                "edges",
+                "read a table of edges with from and to columns, and return the nodes",
+                Some('e'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Order items so that each comes after the items it depends on."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Every row names an item and lists the items it depends on, either as a list or as a
+single value. Items that don't depend on each other keep the order of the input.
+
+With --edges, each row of the input says that the item in `from` has to come before the
+item in `to`, and the items themselves are returned.
+
+A cycle in the dependencies is an error that shows the items in the cycle."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "topological",
+            "dependencies",
+            "order",
+            "graph",
+            "dag",
+            "sort",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let metadata = input.metadata();
+        let rows: Vec<Value> = input.into_iter().collect();
+
+        let graph = if call.has_flag("edges") {
+            Graph::from_edges(rows, head)?
+        } else {
+            let key: Option<Spanned<String>> = call.get_flag(engine_state, stack, "key")?;
+            let depends_on: Option<Spanned<String>> =
+                call.get_flag(engine_state, stack, "depends-on")?;
+            let key = key.unwrap_or_else(|| Spanned {
+                item: "name".into(),
+                span: head,
+            });
+            let depends_on = depends_on.unwrap_or_else(|| Spanned {
+                item: "depends_on".into(),
+                span: head,
+            });
+            Graph::from_items(rows, &key, &depends_on, head)?
+        };
+
+        Ok(graph
+            .sort(head)?
+            .into_iter()
+            .into_pipeline_data(engine_state.ctrlc.clone())
+            .set_metadata(metadata))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let item = |name: &str, depends_on: &[&str]| Value::Record {
+            cols: vec!["name".into(), "depends_on".into()],
+            vals: vec![
+                Value::test_string(name),
+                Value::List {
+                    vals: depends_on.iter().map(|d| Value::test_string(*d)).collect(),
+                    span: Span::test_data(),
+                },
+            ],
+            span: Span::test_data(),
+        };
+
+        vec![
+            Example {
+                description: "Start services after the services they need",
+                example: "[[name depends_on]; [app [db cache]] [db []] [cache [db]]] | toposort",
+                result: Some(Value::List {
+                    vals: vec![
+                        item("db", &[]),
+                        item("cache", &["db"]),
+                        item("app", &["db", "cache"]),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Order the steps of a build given as edges",
+                example:
+                    "[[from to]; [compile link] [link package] [fetch compile]] | toposort --edges",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_string("fetch"),
+                        Value::test_string("compile"),
+                        Value::test_string("link"),
+                        Value::test_string("package"),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Use other columns, with a single dependency per item",
+                example: "[[id after]; [b a] [a null]] | toposort --key id --depends-on after",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["id".into(), "after".into()],
+                            vals: vec![Value::test_string("a"), Value::nothing(Span::test_data())],
+                            span: Span::test_data(),
+                        },
+                        Value::Record {
+                            cols: vec!["id".into(), "after".into()],
+                            vals: vec![Value::test_string("b"), Value::test_string("a")],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// The items to sort, with the indices of the items each of them depends on
+struct Graph {
+    items: Vec<Value>,
+    keys: Vec<String>,
+    dependencies: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    fn from_items(
+        rows: Vec<Value>,
+        key: &Spanned<String>,
+        depends_on: &Spanned<String>,
+        head: Span,
+    ) -> Result<Self, ShellError> {
+        let mut keys = vec![];
+        let mut index = HashMap::new();
+        for row in &rows {
+            if let Value::Error { error } = row {
+                return Err(error.clone());
+            }
+            let name = match row.get_data_by_key(&key.item) {
+                Some(name) => name.as_string()?,
+                None => {
+                    return Err(ShellError::CantFindColumn(
+                        key.item.clone(),
+                        key.span,
+                        row.span().unwrap_or(head),
+                    ))
+                }
+            };
+            if index.insert(name.clone(), keys.len()).is_some() {
+                return Err(ShellError::GenericError(
+                    format!("duplicate item '{name}'"),
+                    "this item was already listed".into(),
+                    Some(row.span().unwrap_or(head)),
+                    Some(format!("each {} has to be unique", key.item)),
+                    vec![],
+                ));
+            }
+            keys.push(name);
+        }
+
+        let mut dependencies = vec![];
+        for row in &rows {
+            let names = match row.get_data_by_key(&depends_on.item) {
+                Some(Value::List { vals, .. }) => vals,
+                Some(Value::Nothing { .. }) | None => vec![],
+                Some(other) => vec![other],
+            };
+            let mut indices = vec![];
+            for name in names {
+                let span = name.span().unwrap_or(head);
+                let name = name.as_string()?;
+                match index.get(&name) {
+                    Some(idx) => indices.push(*idx),
+                    None => {
+                        return Err(ShellError::GenericError(
+                            format!("unknown dependency '{name}'"),
+                            "no item has this name".into(),
+                            Some(span),
+                            None,
+                            vec![],
+                        ))
+                    }
+                }
+            }
+            dependencies.push(indices);
+        }
+
+        Ok(Graph {
+            items: rows,
+            keys,
+            dependencies,
+        })
+    }
+
+    fn from_edges(rows: Vec<Value>, head: Span) -> Result<Self, ShellError> {
+        let mut graph = Graph {
+            items: vec![],
+            keys: vec![],
+            dependencies: vec![],
+        };
+        let mut index = HashMap::new();
+        let mut node = |graph: &mut Graph, value: Value| -> Result<usize, ShellError> {
+            let key = value.as_string()?;
+            Ok(*index.entry(key.clone()).or_insert_with(|| {
+                graph.items.push(value);
+                graph.keys.push(key);
+                graph.dependencies.push(vec![]);
+                graph.items.len() - 1
+            }))
+        };
+
+        for row in rows {
+            if let Value::Error { error } = row {
+                return Err(error);
+            }
+            let column = |name: &str| {
+                row.get_data_by_key(name).ok_or_else(|| {
+                    ShellError::CantFindColumn(name.into(), head, row.span().unwrap_or(head))
+                })
+            };
+            let (from, to) = (column("from")?, column("to")?);
+            let from = node(&mut graph, from)?;
+            let to = node(&mut graph, to)?;
+            graph.dependencies[to].push(from);
+        }
+        Ok(graph)
+    }
+
+    /// Kahn's algorithm, taking the earliest item of the input among those that are ready
+    fn sort(self, head: Span) -> Result<Vec<Value>, ShellError> {
+        let count = self.items.len();
+        let mut waiting_on = vec![0; count];
+        let mut dependents = vec![vec![]; count];
+        for (idx, dependencies) in self.dependencies.iter().enumerate() {
+            let unique: BTreeSet<usize> = dependencies.iter().copied().collect();
+            waiting_on[idx] = unique.len();
+            for dependency in unique {
+                dependents[dependency].push(idx);
+            }
+        }
+
+        let mut ready: BTreeSet<usize> = (0..count).filter(|idx| waiting_on[*idx] == 0).collect();
+        let mut order = vec![];
+        while let Some(&idx) = ready.iter().next() {
+            ready.remove(&idx);
+            order.push(idx);
+            for &dependent in &dependents[idx] {
+                waiting_on[dependent] -= 1;
+                if waiting_on[dependent] == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+
+        if order.len() < count {
+            let cycle = self.find_cycle(&waiting_on);
+            return Err(ShellError::GenericError(
+                "dependency cycle".into(),
+                format!("these items depend on each other: {}", cycle.join(" -> ")),
+                Some(head),
+                Some("remove one of the dependencies in the cycle".into()),
+                vec![],
+            ));
+        }
+
+        let mut items: Vec<Option<Value>> = self.items.into_iter().map(Some).collect();
+        Ok(order
+            .into_iter()
+            .filter_map(|idx| items[idx].take())
+            .collect())
+    }
+
+    /// Follow the unsorted dependencies from an unsorted item until an item comes up again
+    fn find_cycle(&self, waiting_on: &[usize]) -> Vec<String> {
+        let mut path: Vec<usize> = vec![];
+        let mut current = waiting_on.iter().position(|w| *w > 0).unwrap_or_default();
+        loop {
+            if let Some(start) = path.iter().position(|idx| *idx == current) {
+                let mut cycle: Vec<String> = path[start..]
+                    .iter()
+                    .map(|idx| self.keys[*idx].clone())
+                    .collect();
+                cycle.push(self.keys[current].clone());
+                return cycle;
+            }
+            path.push(current);
+            match self.dependencies[current]
+                .iter()
+                .find(|dependency| waiting_on[**dependency] > 0)
+            {
+                Some(next) => current = *next,
+                None => return path.iter().map(|idx| self.keys[*idx].clone()).collect(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Toposort {})
+    }
+}
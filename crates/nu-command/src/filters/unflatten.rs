@@ -0,0 +1,199 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct Unflatten;
+
+impl Command for Unflatten {
+    fn name(&self) -> &str {
+        "unflatten"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("unflatten")
+            .input_output_types(vec![
+                (Type::Record(vec![]), Type::Record(vec![])),
+                (Type::Table(vec![]), Type::Table(vec![])),
+            ])
+            .named(
+                "separator",
+                SyntaxShape::String,
+                "the separator between the parts of a column name (default: .)",
+                Some('s'),
+            )
+            .category(Category::Filters)
+    }
+
+    fn usage(&self) -> &str {
+        "Turn columns named after a path, like a.b.c, into nested records."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"This undoes `flatten --dotted`. Columns that share the start of their path end up in the
+same nested record. A column whose path runs into another column's value is an error."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["nest", "dotted", "expand", "inflate"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let separator: Option<Spanned<String>> = call.get_flag(engine_state, stack, "separator")?;
+        let separator = match separator {
+            Some(separator) if separator.item.is_empty() => {
+                return Err(ShellError::UnsupportedConfigValue(
+                    "a separator that isn't empty".into(),
+                    "an empty string".into(),
+                    separator.span,
+                ))
+            }
+            Some(separator) => separator.item,
+            None => ".".into(),
+        };
+        let metadata = input.metadata();
+
+        input
+            .map(
+                move |value| match value {
+                    Value::Record { cols, vals, span } => {
+                        match unflatten(cols, vals, &separator, span) {
+                            Ok(value) => value,
+                            Err(error) => Value::Error { error },
+                        }
+                    }
+                    other => other,
+                },
+                engine_state.ctrlc.clone(),
+            )
+            .map(|x| x.set_metadata(metadata))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Nest the columns of a record by their dotted names",
+                example: "{'a.b': 1, 'a.c': 2, d: 3} | unflatten",
+                result: Some(Value::Record {
+                    cols: vec!["a".into(), "d".into()],
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["b".into(), "c".into()],
+                            vals: vec![Value::test_int(1), Value::test_int(2)],
+                            span: Span::test_data(),
+                        },
+                        Value::test_int(3),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Nest the columns of a table split by another separator",
+                example: "[['user/name' 'user/id']; [nu 1]] | unflatten --separator /",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["user".into()],
+                        vals: vec![Value::Record {
+                            cols: vec!["name".into(), "id".into()],
+                            vals: vec![Value::test_string("nu"), Value::test_int(1)],
+                            span: Span::test_data(),
+                        }],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+fn unflatten(
+    cols: Vec<String>,
+    vals: Vec<Value>,
+    separator: &str,
+    span: Span,
+) -> Result<Value, ShellError> {
+    let mut out_cols = vec![];
+    let mut out_vals = vec![];
+    for (col, val) in cols.into_iter().zip(vals) {
+        let path: Vec<&str> = col.split(separator).collect();
+        insert_path(&mut out_cols, &mut out_vals, &path, val, &col, span)?;
+    }
+    Ok(Value::Record {
+        cols: out_cols,
+        vals: out_vals,
+        span,
+    })
+}
+
+fn insert_path(
+    cols: &mut Vec<String>,
+    vals: &mut Vec<Value>,
+    path: &[&str],
+    value: Value,
+    column: &str,
+    span: Span,
+) -> Result<(), ShellError> {
+    let conflict = || {
+        ShellError::GenericError(
+            "Conflicting columns".into(),
+            format!("column '{column}' clashes with the value of another column"),
+            Some(span),
+            None,
+            Vec::new(),
+        )
+    };
+
+    let (name, rest) = match path.split_first() {
+        Some(parts) => parts,
+        None => return Ok(()),
+    };
+    let idx = cols.iter().position(|col| col == name);
+
+    if rest.is_empty() {
+        if idx.is_some() {
+            return Err(conflict());
+        }
+        cols.push(name.to_string());
+        vals.push(value);
+        return Ok(());
+    }
+
+    let idx = match idx {
+        Some(idx) => idx,
+        None => {
+            cols.push(name.to_string());
+            vals.push(Value::Record {
+                cols: vec![],
+                vals: vec![],
+                span,
+            });
+            vals.len() - 1
+        }
+    };
+    match &mut vals[idx] {
+        Value::Record { cols, vals, .. } => insert_path(cols, vals, rest, value, column, span),
+        _ => Err(conflict()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Unflatten {})
+    }
+}
@@ -1,13 +1,13 @@
 use crate::formats::value_to_string;
+use indexmap::IndexMap;
 use itertools::Itertools;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, PipelineMetadata, ShellError, Signature,
-    Span, Type, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    PipelineMetadata, ShellError, Signature, Span, Type, Value,
 };
-use std::collections::hash_map::IntoIter;
-use std::collections::HashMap;
+use std::collections::HashSet;
 
 #[derive(Clone)]
 pub struct Uniq;
@@ -57,6 +57,12 @@ impl Command for Uniq {
         "Return the distinct values in the input."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Values are returned as they come in, the first time they are seen, so that long streams
+don't have to be read to the end. --repeated, --count and --unique need the whole input before
+returning anything, and return the values in the order they were first seen in."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["distinct", "deduplicate"]
     }
@@ -77,7 +83,7 @@ impl Command for Uniq {
             engine_state,
             stack,
             call,
-            input.into_iter().collect(),
+            input.into_iter(),
             mapper,
             metadata,
         )
@@ -262,8 +268,8 @@ pub fn uniq(
     engine_state: &EngineState,
     _stack: &mut Stack,
     call: &Call,
-    input: Vec<Value>,
-    item_mapper: Box<dyn Fn(ItemMapperState) -> ValueCounter>,
+    input: impl Iterator<Item = Value> + Send + 'static,
+    item_mapper: Box<dyn Fn(ItemMapperState) -> ValueCounter + Send>,
     metadata: Option<PipelineMetadata>,
 ) -> Result<PipelineData, ShellError> {
     let ctrlc = engine_state.ctrlc.clone();
@@ -273,8 +279,25 @@ pub fn uniq(
     let flag_ignore_case = call.has_flag("ignore-case");
     let flag_only_uniques = call.has_flag("unique");
 
+    if !flag_show_count && !flag_show_repeated && !flag_only_uniques {
+        // Only the keys that were seen are kept, not the values
+        let mut seen: HashSet<String> = HashSet::new();
+        let stream = input.enumerate().filter_map(move |(index, item)| {
+            let item = item_mapper(ItemMapperState {
+                item,
+                flag_ignore_case,
+                index,
+            });
+            let key = match generate_key(&item) {
+                Ok(key) => key,
+                Err(error) => return Some(Value::Error { error }),
+            };
+            seen.insert(key).then_some(item.val)
+        });
+        return Ok(stream.into_pipeline_data(ctrlc).set_metadata(metadata));
+    }
+
     let uniq_values = input
-        .into_iter()
         .enumerate()
         .map_while(|(index, item)| {
             if nu_utils::ctrl_c::was_pressed(&ctrlc) {
@@ -287,26 +310,21 @@ pub fn uniq(
             }))
         })
         .try_fold(
-            HashMap::<String, ValueCounter>::new(),
+            IndexMap::<String, ValueCounter>::new(),
             |mut counter, item| {
-                let key = generate_key(&item);
-
-                match key {
-                    Ok(key) => {
-                        match counter.get_mut(&key) {
-                            Some(x) => x.count += 1,
-                            None => {
-                                counter.insert(key, item);
-                            }
-                        };
-                        Ok(counter)
+                let key = generate_key(&item)?;
+                match counter.get_mut(&key) {
+                    Some(x) => x.count += 1,
+                    None => {
+                        counter.insert(key, item);
                     }
-                    Err(err) => Err(err),
-                }
+                };
+                Ok::<_, ShellError>(counter)
             },
         );
 
-    let mut uniq_values: HashMap<String, ValueCounter> = uniq_values?;
+    // The values keep the order in which they were first seen
+    let mut uniq_values = uniq_values?;
 
     if flag_show_repeated {
         uniq_values.retain(|_v, value_count_pair| value_count_pair.count > 1);
@@ -316,7 +334,7 @@ pub fn uniq(
         uniq_values.retain(|_v, value_count_pair| value_count_pair.count == 1);
     }
 
-    let uniq_values = sort(uniq_values.into_iter());
+    let uniq_values: Vec<ValueCounter> = uniq_values.into_values().collect();
 
     let result = if flag_show_count {
         generate_results_with_count(head, uniq_values)
@@ -332,12 +350,6 @@ pub fn uniq(
     .set_metadata(metadata))
 }
 
-fn sort(iter: IntoIter<String, ValueCounter>) -> Vec<ValueCounter> {
-    iter.map(|item| item.1)
-        .sorted_by(|a, b| a.index.cmp(&b.index))
-        .collect()
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -65,17 +65,13 @@ impl Command for UniqBy {
 
         let metadata = input.metadata();
 
-        let vec: Vec<_> = input.into_iter().collect();
-        match validate(vec.clone(), &columns, call.head) {
-            Ok(_) => {}
-            Err(err) => {
-                return Err(err);
-            }
-        }
+        // Only the first row is checked, so that the rest can be streamed
+        let mut input = input.into_iter().peekable();
+        validate(input.peek(), &columns, call.head)?;
 
         let mapper = Box::new(item_mapper_by_col(columns));
 
-        uniq(engine_state, stack, call, vec, mapper, metadata)
+        uniq(engine_state, stack, call, input, mapper, metadata)
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -103,22 +99,25 @@ impl Command for UniqBy {
     }
 }
 
-fn validate(vec: Vec<Value>, columns: &Vec<String>, span: Span) -> Result<(), ShellError> {
-    if vec.is_empty() {
-        return Err(ShellError::GenericError(
-            "no values to work with".to_string(),
-            "".to_string(),
-            None,
-            Some("no values to work with".to_string()),
-            Vec::new(),
-        ));
-    }
+fn validate(first: Option<&Value>, columns: &Vec<String>, span: Span) -> Result<(), ShellError> {
+    let first = match first {
+        Some(first) => first,
+        None => {
+            return Err(ShellError::GenericError(
+                "no values to work with".to_string(),
+                "".to_string(),
+                None,
+                Some("no values to work with".to_string()),
+                Vec::new(),
+            ))
+        }
+    };
 
     if let Value::Record {
         cols,
         vals: _input_vals,
         span: val_span,
-    } = &vec[0]
+    } = first
     {
         if columns.is_empty() {
             // This uses the same format as the 'requires a column name' error in split_by.rs
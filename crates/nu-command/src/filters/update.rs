@@ -1,3 +1,4 @@
+use super::utils::replace_columns;
 use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::{Call, CellPath, PathMember};
 use nu_protocol::engine::{Closure, Command, EngineState, Stack};
@@ -22,10 +23,10 @@ impl Command for Update {
             ])
             .required(
                 "field",
-                SyntaxShape::CellPath,
-                "the name of the column to update",
+                SyntaxShape::OneOf(vec![SyntaxShape::CellPath, SyntaxShape::Record]),
+                "the name of the column to update, or a record of columns and their new values",
             )
-            .required(
+            .optional(
                 "replacement value",
                 SyntaxShape::Any,
                 "the new value to give the cell(s), or a closure to create the value",
@@ -37,6 +38,12 @@ impl Command for Update {
         "Update an existing column to have a new value."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Several columns can be updated in one pass by giving a record of columns and their new
+values or closures. They are updated in order, so a closure sees the columns before it already
+updated."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -75,6 +82,15 @@ impl Command for Update {
                 example: "[[project, authors]; ['nu', ['Andrés', 'JT', 'Yehuda']]] | update authors {|row| $row.authors | str join ','}",
                 result: Some(Value::List { vals: vec![Value::Record { cols: vec!["project".into(), "authors".into()], vals: vec![Value::test_string("nu"), Value::test_string("Andrés,JT,Yehuda")], span: Span::test_data()}], span: Span::test_data()}),
             },
+            Example {
+                description: "Update several columns at once, with values or closures",
+                example: "{'name': 'nu', 'stars': 5} | update {name: 'Nushell', stars: {|r| $r.stars + 1}}",
+                result: Some(Value::Record {
+                    cols: vec!["name".into(), "stars".into()],
+                    vals: vec![Value::test_string("Nushell"), Value::test_int(6)],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
@@ -87,8 +103,35 @@ fn update(
 ) -> Result<PipelineData, ShellError> {
     let span = call.head;
 
-    let cell_path: CellPath = call.req(engine_state, stack, 0)?;
-    let replacement: Value = call.req(engine_state, stack, 1)?;
+    let field: Value = call.req(engine_state, stack, 0)?;
+    let replacement: Option<Value> = call.opt(engine_state, stack, 1)?;
+
+    let (cell_path, replacement) = match (field, replacement) {
+        (Value::Record { cols, vals, .. }, None) => {
+            return replace_columns(
+                engine_state,
+                stack,
+                call,
+                input,
+                cols,
+                vals,
+                Value::update_data_at_cell_path,
+            )
+        }
+        (Value::Record { .. }, Some(replacement)) => {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "the record already holds the new values of the columns".into(),
+                replacement.span()?,
+            ))
+        }
+        (_, None) => {
+            return Err(ShellError::MissingParameter(
+                "replacement value".into(),
+                span,
+            ))
+        }
+        (field, Some(replacement)) => (CellPath::from_value(&field)?, replacement),
+    };
 
     let redirect_stdout = call.redirect_stdout;
     let redirect_stderr = call.redirect_stderr;
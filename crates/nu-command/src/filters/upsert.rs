@@ -1,3 +1,4 @@
+use super::utils::replace_columns;
 use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::{Call, CellPath, PathMember};
 use nu_protocol::engine::{Closure, Command, EngineState, Stack};
@@ -22,10 +23,10 @@ impl Command for Upsert {
             ])
             .required(
                 "field",
-                SyntaxShape::CellPath,
-                "the name of the column to update or insert",
+                SyntaxShape::OneOf(vec![SyntaxShape::CellPath, SyntaxShape::Record]),
+                "the name of the column to update or insert, or a record of columns and their new values",
             )
-            .required(
+            .optional(
                 "replacement value",
                 SyntaxShape::Any,
                 "the new value to give the cell(s), or a closure to create the value",
@@ -37,6 +38,11 @@ impl Command for Upsert {
         "Update an existing column to have a new value, or insert a new column."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Several columns can be set in one pass by giving a record of columns and their new values
+or closures. They are set in order, so a closure sees the columns before it already set."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
         vec!["add"]
     }
@@ -97,6 +103,11 @@ impl Command for Upsert {
                 span: Span::test_data(),
             }),
         },
+        Example {
+            description: "Update and insert several columns at once",
+            example: "{'name': 'nu', 'stars': 5} | upsert {stars: 6, language: 'Rust'}",
+            result: Some(Value::Record { cols: vec!["name".into(), "stars".into(), "language".into()], vals: vec![Value::test_string("nu"), Value::test_int(6), Value::test_string("Rust")], span: Span::test_data()}),
+        },
         ]
     }
 }
@@ -109,8 +120,35 @@ fn upsert(
 ) -> Result<PipelineData, ShellError> {
     let span = call.head;
 
-    let cell_path: CellPath = call.req(engine_state, stack, 0)?;
-    let replacement: Value = call.req(engine_state, stack, 1)?;
+    let field: Value = call.req(engine_state, stack, 0)?;
+    let replacement: Option<Value> = call.opt(engine_state, stack, 1)?;
+
+    let (cell_path, replacement) = match (field, replacement) {
+        (Value::Record { cols, vals, .. }, None) => {
+            return replace_columns(
+                engine_state,
+                stack,
+                call,
+                input,
+                cols,
+                vals,
+                Value::upsert_data_at_cell_path,
+            )
+        }
+        (Value::Record { .. }, Some(replacement)) => {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "the record already holds the new values of the columns".into(),
+                replacement.span()?,
+            ))
+        }
+        (_, None) => {
+            return Err(ShellError::MissingParameter(
+                "replacement value".into(),
+                span,
+            ))
+        }
+        (field, Some(replacement)) => (CellPath::from_value(&field)?, replacement),
+    };
 
     let redirect_stdout = call.redirect_stdout;
     let redirect_stderr = call.redirect_stderr;
@@ -1,4 +1,9 @@
-use nu_protocol::{ShellError, Span};
+use fancy_regex::Regex;
+use nu_engine::eval_block;
+use nu_glob::Pattern;
+use nu_protocol::ast::{Call, CellPath, PathMember};
+use nu_protocol::engine::{Closure, EngineState, Stack};
+use nu_protocol::{FromValue, IntoPipelineData, PipelineData, ShellError, Span, Value};
 
 pub fn chain_error_with_input(
     error_source: ShellError,
@@ -9,3 +14,153 @@ pub fn chain_error_with_input(
     }
     error_source
 }
+
+/// Set several columns of each row in one pass, for the record form of `update` and `upsert`.
+/// The new values are set in order, so a closure sees the columns before it already replaced.
+pub fn replace_columns(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    input: PipelineData,
+    columns: Vec<String>,
+    replacements: Vec<Value>,
+    replace: fn(&mut Value, &[PathMember], Value) -> Result<(), ShellError>,
+) -> Result<PipelineData, ShellError> {
+    let span = call.head;
+    let redirect_stdout = call.redirect_stdout;
+    let redirect_stderr = call.redirect_stderr;
+
+    let mut setters = vec![];
+    for (column, replacement) in columns.into_iter().zip(replacements) {
+        let path = vec![PathMember::String {
+            val: column,
+            span: replacement.span().unwrap_or(span),
+        }];
+        let setter: Box<dyn FnMut(&Value) -> Result<Value, ShellError> + Send> =
+            if replacement.as_block().is_ok() {
+                let capture_block: Closure = FromValue::from_value(&replacement)?;
+                let block = engine_state.get_block(capture_block.block_id).clone();
+                let engine_state = engine_state.clone();
+
+                let mut stack = stack.captures_to_stack(&capture_block.captures);
+                let orig_env_vars = stack.env_vars.clone();
+                let orig_env_hidden = stack.env_hidden.clone();
+
+                Box::new(move |row: &Value| {
+                    stack.with_env(&orig_env_vars, &orig_env_hidden);
+
+                    if let Some(var) = block.signature.get_positional(0) {
+                        if let Some(var_id) = &var.var_id {
+                            stack.add_var(*var_id, row.clone())
+                        }
+                    }
+
+                    eval_block(
+                        &engine_state,
+                        &mut stack,
+                        &block,
+                        row.clone().into_pipeline_data(),
+                        redirect_stdout,
+                        redirect_stderr,
+                    )
+                    .map(|pd| pd.into_value(span))
+                })
+            } else {
+                Box::new(move |_: &Value| Ok(replacement.clone()))
+            };
+        setters.push((path, setter));
+    }
+
+    input.map(
+        move |mut row| {
+            for (path, setter) in setters.iter_mut() {
+                let result = setter(&row).and_then(|new_val| replace(&mut row, path, new_val));
+                if let Err(error) = result {
+                    return Value::Error { error };
+                }
+            }
+            row
+        },
+        engine_state.ctrlc.clone(),
+    )
+}
+
+/// A column given to `select` or `reject`: a cell path, or a pattern for the names of the
+/// columns of each row
+pub enum ColumnSpec {
+    Path(CellPath),
+    Glob(Pattern, Span),
+    Regex(Regex, Span),
+}
+
+impl ColumnSpec {
+    /// Read the columns given to a command. Unless they are regular expressions, only the ones
+    /// with a `*`, `?` or `[` are patterns, and only for the rows without a column of that name.
+    pub fn parse_all(paths: Vec<CellPath>, regex: bool) -> Result<Vec<ColumnSpec>, ShellError> {
+        paths
+            .into_iter()
+            .map(|path| match path.members.as_slice() {
+                [PathMember::String { val, span }] if regex => Regex::new(val)
+                    .map(|re| ColumnSpec::Regex(re, *span))
+                    .map_err(|err| {
+                        ShellError::GenericError(
+                            "Error with regular expression".into(),
+                            err.to_string(),
+                            Some(*span),
+                            None,
+                            Vec::new(),
+                        )
+                    }),
+                [PathMember::String { val, span }] if val.contains(['*', '?', '[']) => {
+                    Pattern::new(val)
+                        .map(|glob| ColumnSpec::Glob(glob, *span))
+                        .map_err(|err| {
+                            ShellError::GenericError(
+                                "Error with glob pattern".into(),
+                                err.msg.to_string(),
+                                Some(*span),
+                                None,
+                                Vec::new(),
+                            )
+                        })
+                }
+                _ => Ok(ColumnSpec::Path(path)),
+            })
+            .collect()
+    }
+
+    /// Whether a column of a row matches the pattern. A glob only matches itself in a row that
+    /// has a column named like it, as `*`, `?` and `[` may be part of a real column name.
+    pub fn matches(&self, column: &str, row_columns: &[String]) -> bool {
+        match self {
+            ColumnSpec::Path(_) => false,
+            ColumnSpec::Glob(glob, _) if row_columns.iter().any(|col| col == glob.as_str()) => {
+                column == glob.as_str()
+            }
+            ColumnSpec::Glob(glob, _) => glob.matches(column),
+            ColumnSpec::Regex(re, _) => re.is_match(column).unwrap_or(false),
+        }
+    }
+
+    /// The cell paths of a row this stands for: the path itself, or the columns of the row
+    /// that match the pattern, in their order in the row
+    pub fn paths_in(&self, row: &Value) -> Vec<CellPath> {
+        match (self, row) {
+            (ColumnSpec::Path(path), _) => vec![path.clone()],
+            (
+                ColumnSpec::Glob(_, span) | ColumnSpec::Regex(_, span),
+                Value::Record { cols, .. },
+            ) => cols
+                .iter()
+                .filter(|col| self.matches(col, cols))
+                .map(|col| CellPath {
+                    members: vec![PathMember::String {
+                        val: col.clone(),
+                        span: *span,
+                    }],
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
@@ -70,7 +70,8 @@ not supported."#
         let redirect_stderr = call.redirect_stderr;
         Ok(input
             .into_iter_strict(span)?
-            .filter_map(move |value| {
+            .enumerate()
+            .filter_map(move |(index, value)| {
                 stack.with_env(&orig_env_vars, &orig_env_hidden);
 
                 if let Some(var) = block.signature.get_positional(0) {
@@ -78,6 +79,14 @@ not supported."#
                         stack.add_var(*var_id, value.clone());
                     }
                 }
+
+                // Index argument
+                if let Some(var) = block.signature.get_positional(1) {
+                    if let Some(var_id) = &var.var_id {
+                        stack.add_var(*var_id, Value::int(index as i64, span));
+                    }
+                }
+
                 let result = eval_block(
                     &engine_state,
                     &mut stack,
@@ -126,6 +135,14 @@ not supported."#
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Keep every other element, using the index as a second parameter",
+                example: r#"[a b c d] | where {|x, i| $i mod 2 == 1 }"#,
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("b"), Value::test_string("d")],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 description: "List all files in the current directory with sizes greater than 2kb",
                 example: "ls | where size > 2kb",
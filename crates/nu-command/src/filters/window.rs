@@ -2,9 +2,10 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
-    Spanned, SyntaxShape, Type, Value,
+    Category, Example, FromValue, IntoInterruptiblePipelineData, PipelineData, ShellError,
+    Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
+use std::collections::VecDeque;
 
 #[derive(Clone)]
 pub struct Window;
@@ -20,13 +21,25 @@ impl Command for Window {
                 Type::List(Box::new(Type::Any)),
                 Type::List(Box::new(Type::List(Box::new(Type::Any)))),
             )])
-            .required("window_size", SyntaxShape::Int, "the size of each window")
+            .optional("window_size", SyntaxShape::Int, "the size of each window")
             .named(
                 "stride",
-                SyntaxShape::Int,
-                "the number of rows to slide over between windows",
+                SyntaxShape::Any,
+                "the number of rows, or the duration with --by, to slide over between windows",
                 Some('s'),
             )
+            .named(
+                "by",
+                SyntaxShape::String,
+                "the date column to make windows of --period over",
+                Some('b'),
+            )
+            .named(
+                "period",
+                SyntaxShape::Duration,
+                "the length of time each window covers, with --by",
+                Some('p'),
+            )
             .switch(
                 "remainder",
                 "yield last chunks even if they have fewer elements than size",
@@ -39,6 +52,13 @@ impl Command for Window {
         "Creates a sliding window of `window_size` that slide by n rows/elements across input."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --by and --period, windows cover a length of time instead of a number of rows. The
+first window starts at the date of the first row, and each next one starts --stride later (by
+default, the windows don't overlap). The rows must be sorted by the date column. Windows without
+any rows are skipped."#
+    }
+
     fn examples(&self) -> Vec<Example> {
         let stream_test_1 = vec![
             Value::List {
@@ -106,6 +126,11 @@ impl Command for Window {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: "open access.log.json | window --by time --period 5min --stride 1min | each {|w| $w | length }",
+                description: "Count the requests in the last five minutes, every minute",
+                result: None,
+            },
         ]
     }
 
@@ -116,28 +141,83 @@ impl Command for Window {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let group_size: Spanned<usize> = call.req(engine_state, stack, 0)?;
+        let group_size: Option<Spanned<usize>> = call.opt(engine_state, stack, 0)?;
+        let by: Option<String> = call.get_flag(engine_state, stack, "by")?;
+        let period: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "period")?;
+        let stride: Option<Value> = call.get_flag(engine_state, stack, "stride")?;
         let ctrlc = engine_state.ctrlc.clone();
         let metadata = input.metadata();
-        let stride: Option<usize> = call.get_flag(engine_state, stack, "stride")?;
         let remainder = call.has_flag("remainder");
 
-        let stride = stride.unwrap_or(1);
-
         //FIXME: add in support for external redirection when engine-q supports it generally
 
-        let each_group_iterator = EachWindowIterator {
-            group_size: group_size.item,
-            input: Box::new(input.into_iter()),
-            span: call.head,
-            previous: None,
-            stride,
-            remainder,
-        };
-
-        Ok(each_group_iterator
-            .into_pipeline_data(ctrlc)
-            .set_metadata(metadata))
+        match (group_size, by, period) {
+            (Some(group_size), None, None) => {
+                let stride = match stride {
+                    Some(stride) => usize::from_value(&stride)?,
+                    None => 1,
+                };
+
+                let each_group_iterator = EachWindowIterator {
+                    group_size: group_size.item,
+                    input: Box::new(input.into_iter()),
+                    span: call.head,
+                    previous: None,
+                    stride,
+                    remainder,
+                };
+
+                Ok(each_group_iterator
+                    .into_pipeline_data(ctrlc)
+                    .set_metadata(metadata))
+            }
+            (None, Some(column), Some(period)) => {
+                if period.item <= 0 {
+                    return Err(ShellError::NeedsPositiveValue(period.span));
+                }
+                let stride = match stride {
+                    Some(Value::Duration { val, span }) if val <= 0 => {
+                        return Err(ShellError::NeedsPositiveValue(span))
+                    }
+                    Some(Value::Duration { val, .. }) => val,
+                    Some(other) => {
+                        return Err(ShellError::TypeMismatch(
+                            "a duration to slide the windows by".into(),
+                            other.span()?,
+                        ))
+                    }
+                    None => period.item,
+                };
+
+                let each_window_iterator = EachTimeWindowIterator {
+                    column,
+                    period: period.item,
+                    stride,
+                    input: Box::new(input.into_iter()),
+                    span: call.head,
+                    start: None,
+                    buffer: VecDeque::new(),
+                    last: i64::MIN,
+                    done: false,
+                };
+
+                Ok(each_window_iterator
+                    .into_pipeline_data(ctrlc)
+                    .set_metadata(metadata))
+            }
+            (Some(group_size), Some(_), _) | (Some(group_size), _, Some(_)) => {
+                Err(ShellError::IncompatibleParametersSingle(
+                    "a window size can't be used with --by and --period".into(),
+                    group_size.span,
+                ))
+            }
+            (None, Some(_), None) => Err(ShellError::MissingParameter("period".into(), call.head)),
+            (None, None, Some(_)) => Err(ShellError::MissingParameter("by".into(), call.head)),
+            (None, None, None) => Err(ShellError::MissingParameter(
+                "window_size".into(),
+                call.head,
+            )),
+        }
     }
 }
 
@@ -231,6 +311,132 @@ impl Iterator for EachWindowIterator {
     }
 }
 
+/// Windows over a date column, for input sorted by that column. Rows are kept until no window
+/// that is still to come can hold them anymore.
+struct EachTimeWindowIterator {
+    column: String,
+    period: i64,
+    stride: i64,
+    input: Box<dyn Iterator<Item = Value> + Send>,
+    span: Span,
+    start: Option<i64>,
+    buffer: VecDeque<(i64, Value)>,
+    last: i64,
+    done: bool,
+}
+
+impl EachTimeWindowIterator {
+    fn timestamp(&self, row: &Value) -> Result<i64, ShellError> {
+        match row.get_data_by_key(&self.column) {
+            Some(Value::Date { val, .. }) => Ok(val.timestamp_nanos()),
+            Some(other) => Err(ShellError::TypeMismatch(
+                format!("expected a date in column '{}'", self.column),
+                other.span()?,
+            )),
+            None => Err(ShellError::CantFindColumn(
+                self.column.clone(),
+                self.span,
+                row.span()?,
+            )),
+        }
+    }
+
+    /// Read rows until one is past the window starting at `end - period`, or the input ends
+    fn fill(&mut self, end: i64) -> Result<(), ShellError> {
+        while !self.done && self.buffer.back().map_or(true, |(ts, _)| *ts < end) {
+            match self.input.next() {
+                Some(Value::Error { error }) => return Err(error),
+                Some(row) => {
+                    let ts = self.timestamp(&row)?;
+                    if ts < self.last {
+                        return Err(ShellError::GenericError(
+                            "Input is not sorted".into(),
+                            format!("the rows must be sorted by '{}'", self.column),
+                            Some(self.span),
+                            None,
+                            Vec::new(),
+                        ));
+                    }
+                    self.last = ts;
+                    // Rows between windows that don't overlap don't go anywhere
+                    let start = *self.start.get_or_insert(ts);
+                    if ts >= start {
+                        self.buffer.push_back((ts, row));
+                    }
+                }
+                None => self.done = true,
+            }
+        }
+        Ok(())
+    }
+
+    fn next_window(&mut self) -> Result<Option<Vec<Value>>, ShellError> {
+        loop {
+            let start = match self.start {
+                Some(start) => start,
+                None => {
+                    self.fill(i64::MIN)?;
+                    match self.start {
+                        Some(start) => start,
+                        None => return Ok(None),
+                    }
+                }
+            };
+            let end = start.saturating_add(self.period);
+            self.fill(end)?;
+
+            let window: Vec<Value> = self
+                .buffer
+                .iter()
+                .take_while(|(ts, _)| *ts < end)
+                .map(|(_, row)| row.clone())
+                .collect();
+
+            let next_start = match (window.is_empty(), self.buffer.front()) {
+                (_, None) if self.done => return Ok(None),
+                // Skip the windows that would be empty
+                (true, Some((ts, _))) => {
+                    let skipped = (ts - end) / self.stride + 1;
+                    start.saturating_add(skipped.saturating_mul(self.stride))
+                }
+                _ => start.saturating_add(self.stride),
+            };
+            self.start = Some(next_start);
+            while self
+                .buffer
+                .front()
+                .map_or(false, |(ts, _)| *ts < next_start)
+            {
+                self.buffer.pop_front();
+            }
+
+            if !window.is_empty() {
+                return Ok(Some(window));
+            }
+        }
+    }
+}
+
+impl Iterator for EachTimeWindowIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_window() {
+            Ok(Some(vals)) => Some(Value::List {
+                vals,
+                span: self.span,
+            }),
+            Ok(None) => None,
+            Err(error) => {
+                self.done = true;
+                self.buffer.clear();
+                self.start = Some(i64::MAX);
+                Some(Value::Error { error })
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
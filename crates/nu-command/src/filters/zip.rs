@@ -2,8 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Type, Value,
+    Category, Example, FromValue, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    PipelineIterator, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -29,8 +29,28 @@ impl Command for Zip {
                     Type::Range,
                     Type::List(Box::new(Type::List(Box::new(Type::Any)))),
                 ),
+                (Type::List(Box::new(Type::Any)), Type::Table(vec![])),
+                (Type::Range, Type::Table(vec![])),
             ])
             .required("other", SyntaxShape::Any, "the other input")
+            .rest("more", SyntaxShape::Any, "more lists to zip with the input")
+            .switch(
+                "longest",
+                "go on until the longest list ends, instead of the shortest",
+                Some('l'),
+            )
+            .named(
+                "fill",
+                SyntaxShape::Any,
+                "the value to put in place of missing items with --longest (default: null)",
+                None,
+            )
+            .named(
+                "columns",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "return records with these columns, one for each list, instead of lists",
+                Some('c'),
+            )
             .category(Category::Filters)
     }
 
@@ -76,6 +96,94 @@ impl Command for Zip {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: "[1 2 3] | zip [a b c] [x y z]",
+                description: "Zip three lists",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::List {
+                            vals: vec![
+                                Value::test_int(1),
+                                Value::test_string("a"),
+                                Value::test_string("x"),
+                            ],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![
+                                Value::test_int(2),
+                                Value::test_string("b"),
+                                Value::test_string("y"),
+                            ],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![
+                                Value::test_int(3),
+                                Value::test_string("c"),
+                                Value::test_string("z"),
+                            ],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[1 2 3] | zip --longest --fill 0 [4]",
+                description: "Zip until the longest list ends, filling in the missing items",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::List {
+                            vals: vec![Value::test_int(1), Value::test_int(4)],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![Value::test_int(2), Value::test_int(0)],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![Value::test_int(3), Value::test_int(0)],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "[ann bob] | zip [30 40] --columns [name age]",
+                description: "Zip lists into a table",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["name", "age"],
+                            vec![Value::test_string("ann"), Value::test_int(30)],
+                        ),
+                        Value::test_record(
+                            vec!["name", "age"],
+                            vec![Value::test_string("bob"), Value::test_int(40)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: "1..2 | zip [a b] -c [n letter]",
+                description: "Zip a range into a table",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["n", "letter"],
+                            vec![Value::test_int(1), Value::test_string("a")],
+                        ),
+                        Value::test_record(
+                            vec!["n", "letter"],
+                            vec![Value::test_int(2), Value::test_string("b")],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 example: "glob *.ogg | zip ['bang.ogg', 'fanfare.ogg', 'laser.ogg'] | each { mv $in.0 $in.1 }",
                 description: "Rename .ogg files to match an existing list of filenames",
@@ -92,19 +200,74 @@ impl Command for Zip {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let other: Value = call.req(engine_state, stack, 0)?;
+        let more: Vec<Value> = call.rest(engine_state, stack, 1)?;
+        let longest = call.has_flag("longest");
+        let fill: Option<Value> = call.get_flag(engine_state, stack, "fill")?;
+        let columns: Option<Value> = call.get_flag(engine_state, stack, "columns")?;
         let head = call.head;
         let ctrlc = engine_state.ctrlc.clone();
         let metadata = input.metadata();
 
-        Ok(input
-            .into_iter()
-            .zip(other.into_pipeline_data().into_iter())
-            .map(move |(x, y)| Value::List {
-                vals: vec![x, y],
-                span: head,
+        if let (Some(fill), false) = (&fill, longest) {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "--fill only applies with --longest".into(),
+                fill.span().unwrap_or(head),
+            ));
+        }
+        let fill = fill.unwrap_or_else(|| Value::nothing(head));
+
+        let mut iters: Vec<PipelineIterator> = vec![input.into_iter()];
+        iters.extend(
+            std::iter::once(other)
+                .chain(more)
+                .map(|list| list.into_pipeline_data().into_iter()),
+        );
+
+        let columns = match columns {
+            Some(columns) => {
+                let span = columns.span().unwrap_or(head);
+                let columns = Vec::<String>::from_value(&columns)?;
+                if columns.len() != iters.len() {
+                    return Err(ShellError::IncompatibleParametersSingle(
+                        format!(
+                            "{} column names for {} lists, there must be one for each",
+                            columns.len(),
+                            iters.len()
+                        ),
+                        span,
+                    ));
+                }
+                Some(columns)
+            }
+            None => None,
+        };
+
+        Ok(std::iter::from_fn(move || {
+            let row: Vec<Option<Value>> = iters.iter_mut().map(|iter| iter.next()).collect();
+            let done = if longest {
+                row.iter().all(Option::is_none)
+            } else {
+                row.iter().any(Option::is_none)
+            };
+            if done {
+                return None;
+            }
+
+            let vals = row
+                .into_iter()
+                .map(|val| val.unwrap_or_else(|| fill.clone()))
+                .collect();
+            Some(match &columns {
+                Some(cols) => Value::Record {
+                    cols: cols.clone(),
+                    vals,
+                    span: head,
+                },
+                None => Value::List { vals, span: head },
             })
-            .into_pipeline_data(ctrlc)
-            .set_metadata(metadata))
+        })
+        .into_pipeline_data(ctrlc)
+        .set_metadata(metadata))
     }
 }
 
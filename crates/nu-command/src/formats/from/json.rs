@@ -1,8 +1,10 @@
+use super::json_schema::{self, SchemaError};
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, Type, Value,
+    Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -21,9 +23,27 @@ impl Command for FromJson {
         Signature::build("from json")
             .input_output_types(vec![(Type::String, Type::Any)])
             .switch("objects", "treat each line as a separate value", Some('o'))
+            .named(
+                "schema",
+                SyntaxShape::Any,
+                "a JSON Schema to validate against, as a path to a schema file or a record",
+                Some('s'),
+            )
+            .switch(
+                "strict",
+                "fail on schema violations instead of attaching them to the rows",
+                None,
+            )
             .category(Category::Formats)
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --schema, violations are listed in a `schema_errors` column of the offending
+records, or of every row when the value is a table. Each violation has the cell path of the
+value that caused it, relative to its row, and a message. The keywords of JSON Schema that
+refer to other schemas by name, like $ref, aren't supported."#
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![
             Example {
@@ -50,17 +70,65 @@ impl Command for FromJson {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                example: r#"'[{"id": 1}, {"id": "2"}]' | from json --schema {items: {properties: {id: {type: integer}}}}"#,
+                description: "Mark the rows that don't match a schema",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["id".to_string(), "schema_errors".to_string()],
+                            vals: vec![
+                                Value::test_int(1),
+                                Value::List {
+                                    vals: vec![],
+                                    span: Span::test_data(),
+                                },
+                            ],
+                            span: Span::test_data(),
+                        },
+                        Value::Record {
+                            cols: vec!["id".to_string(), "schema_errors".to_string()],
+                            vals: vec![
+                                Value::test_string("2"),
+                                Value::List {
+                                    vals: vec![Value::Record {
+                                        cols: vec!["path".to_string(), "message".to_string()],
+                                        vals: vec![
+                                            Value::test_string("id"),
+                                            Value::test_string("expected integer, found string"),
+                                        ],
+                                        span: Span::test_data(),
+                                    }],
+                                    span: Span::test_data(),
+                                },
+                            ],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                example: r#"open data.json --raw | from json --schema schema.json --strict"#,
+                description: "Stop at the first violation of a schema stored in a file",
+                result: None,
+            },
         ]
     }
 
     fn run(
         &self,
         engine_state: &EngineState,
-        _stack: &mut Stack,
+        stack: &mut Stack,
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let span = call.head;
+        let schema = match call.get_flag::<Value>(engine_state, stack, "schema")? {
+            Some(schema) => Some(load_schema(schema, engine_state, stack)?),
+            None => None,
+        };
+        let strict = call.has_flag("strict");
         let (string_input, span, metadata) = input.collect_string_strict(span)?;
 
         if string_input.is_empty() {
@@ -75,22 +143,165 @@ impl Command for FromJson {
                     if x.trim() == "" {
                         None
                     } else {
-                        match convert_string_to_value(x.to_string(), span) {
-                            Ok(v) => Some(v),
-                            Err(error) => Some(Value::Error { error }),
+                        let value =
+                            convert_string_to_value(x.to_string(), span).and_then(
+                                |v| match &schema {
+                                    Some(schema) => apply_schema(v, schema, strict, span),
+                                    None => Ok(v),
+                                },
+                            );
+                        match value {
+                            Ok(v) => Some(Ok(v)),
+                            Err(error) if strict => Some(Err(error)),
+                            Err(error) => Some(Ok(Value::Error { error })),
                         }
                     }
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
             Ok(converted_lines
                 .into_pipeline_data_with_metadata(metadata, engine_state.ctrlc.clone()))
         } else {
-            Ok(convert_string_to_value(string_input, span)?
-                .into_pipeline_data_with_metadata(metadata))
+            let value = convert_string_to_value(string_input, span)?;
+            let value = match &schema {
+                Some(schema) => apply_schema(value, schema, strict, span)?,
+                None => value,
+            };
+            Ok(value.into_pipeline_data_with_metadata(metadata))
+        }
+    }
+}
+
+fn load_schema(
+    schema: Value,
+    engine_state: &EngineState,
+    stack: &mut Stack,
+) -> Result<Value, ShellError> {
+    match schema {
+        Value::String { val, span } => {
+            let cwd = current_dir(engine_state, stack)?;
+            let path = nu_path::expand_path_with(&val, cwd);
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                ShellError::GenericError(
+                    "Error reading the schema".into(),
+                    e.to_string(),
+                    Some(span),
+                    None,
+                    vec![],
+                )
+            })?;
+            convert_string_to_value(contents, span)
         }
+        Value::Record { .. } | Value::Bool { .. } => Ok(schema),
+        other => Err(ShellError::TypeMismatch(
+            format!(
+                "expected a path or a record for the schema, found {}",
+                other.get_type()
+            ),
+            other.expect_span(),
+        )),
     }
 }
 
+// Attach the violations to the records they were found in, or fail if there is no record to
+// attach them to
+fn apply_schema(
+    value: Value,
+    schema: &Value,
+    strict: bool,
+    span: Span,
+) -> Result<Value, ShellError> {
+    let errors = json_schema::validate(schema, &value, span)?;
+    if errors.is_empty() {
+        return Ok(value);
+    }
+    if strict {
+        return Err(schema_violation(errors, span));
+    }
+
+    match value {
+        Value::Record {
+            mut cols,
+            mut vals,
+            span: record_span,
+        } => {
+            cols.push("schema_errors".into());
+            vals.push(Value::List {
+                vals: errors.into_iter().map(|e| e.into_value(span)).collect(),
+                span,
+            });
+            Ok(Value::Record {
+                cols,
+                vals,
+                span: record_span,
+            })
+        }
+        Value::List {
+            vals,
+            span: list_span,
+        } => {
+            let mut row_errors: Vec<Vec<Value>> = vals.iter().map(|_| vec![]).collect();
+            let mut unattached = vec![];
+            for mut error in errors {
+                let row = error
+                    .path
+                    .first()
+                    .and_then(|index| index.parse::<usize>().ok());
+                match row {
+                    Some(row) if matches!(vals[row], Value::Record { .. }) => {
+                        error.path.remove(0);
+                        row_errors[row].push(error.into_value(span));
+                    }
+                    _ => unattached.push(error),
+                }
+            }
+            if !unattached.is_empty() {
+                return Err(schema_violation(unattached, span));
+            }
+
+            let vals = vals
+                .into_iter()
+                .zip(row_errors)
+                .map(|(row, errors)| match row {
+                    Value::Record {
+                        mut cols,
+                        mut vals,
+                        span: row_span,
+                    } => {
+                        cols.push("schema_errors".into());
+                        vals.push(Value::List { vals: errors, span });
+                        Value::Record {
+                            cols,
+                            vals,
+                            span: row_span,
+                        }
+                    }
+                    other => other,
+                })
+                .collect();
+            Ok(Value::List {
+                vals,
+                span: list_span,
+            })
+        }
+        _ => Err(schema_violation(errors, span)),
+    }
+}
+
+fn schema_violation(errors: Vec<SchemaError>, span: Span) -> ShellError {
+    let help = match errors.len() {
+        1 => None,
+        n => Some(format!("there are {} more violations", n - 1)),
+    };
+    let first = &errors[0];
+    ShellError::GenericError(
+        "JSON doesn't match the schema".into(),
+        format!("{} at {}", first.message, first.path_string()),
+        Some(span),
+        help,
+        vec![],
+    )
+}
+
 fn convert_nujson_to_value(value: &nu_json::Value, span: Span) -> Value {
     match value {
         nu_json::Value::Array(array) => {
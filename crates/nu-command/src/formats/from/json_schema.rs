@@ -0,0 +1,459 @@
+// A validator for the commonly used subset of JSON Schema. It works on nushell values so a schema
+// can be loaded from a file or written inline as a record.
+use fancy_regex::Regex;
+use nu_protocol::{ShellError, Span, Value};
+
+pub struct SchemaError {
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+impl SchemaError {
+    pub fn path_string(&self) -> String {
+        if self.path.is_empty() {
+            "the top level".into()
+        } else {
+            self.path.join(".")
+        }
+    }
+
+    pub fn into_value(self, span: Span) -> Value {
+        Value::Record {
+            cols: vec!["path".into(), "message".into()],
+            vals: vec![
+                Value::string(self.path.join("."), span),
+                Value::string(self.message, span),
+            ],
+            span,
+        }
+    }
+}
+
+/// Collect every violation of `schema` by `value`, or fail if the schema itself is malformed
+pub fn validate(schema: &Value, value: &Value, span: Span) -> Result<Vec<SchemaError>, ShellError> {
+    let mut errors = vec![];
+    check(schema, value, &mut vec![], &mut errors, span)?;
+    Ok(errors)
+}
+
+fn check(
+    schema: &Value,
+    value: &Value,
+    path: &mut Vec<String>,
+    errors: &mut Vec<SchemaError>,
+    span: Span,
+) -> Result<(), ShellError> {
+    let (cols, vals) = match schema {
+        // `true` accepts anything and `false` accepts nothing
+        Value::Bool { val: true, .. } => return Ok(()),
+        Value::Bool { val: false, .. } => {
+            report(path, errors, "no value is allowed here".into());
+            return Ok(());
+        }
+        Value::Record { cols, vals, .. } => (cols, vals),
+        other => {
+            return Err(malformed(
+                "a schema must be a record or a bool",
+                other,
+                span,
+            ))
+        }
+    };
+
+    for (keyword, arg) in cols.iter().zip(vals) {
+        match keyword.as_str() {
+            "type" => {
+                let types = match arg {
+                    Value::List { vals, .. } => vals
+                        .iter()
+                        .map(|t| t.as_string())
+                        .collect::<Result<Vec<_>, _>>()?,
+                    other => vec![other.as_string()?],
+                };
+                if !types.iter().any(|t| has_type(value, t)) {
+                    report(
+                        path,
+                        errors,
+                        format!(
+                            "expected {}, found {}",
+                            types.join(" or "),
+                            json_type(value)
+                        ),
+                    );
+                }
+            }
+            "enum" => {
+                let options = match arg {
+                    Value::List { vals, .. } => vals,
+                    other => return Err(malformed("`enum` must be a list", other, span)),
+                };
+                if !options.iter().any(|option| option == value) {
+                    report(
+                        path,
+                        errors,
+                        "value is not one of the allowed values".into(),
+                    );
+                }
+            }
+            "const" => {
+                if arg != value {
+                    report(path, errors, "value is not the allowed constant".into());
+                }
+            }
+            "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+                let limit = number(arg, span)?;
+                if let Some(number) = as_number(value) {
+                    let ok = match keyword.as_str() {
+                        "minimum" => number >= limit,
+                        "maximum" => number <= limit,
+                        "exclusiveMinimum" => number > limit,
+                        _ => number < limit,
+                    };
+                    if !ok {
+                        report(path, errors, format!("{number} violates {keyword} {limit}"));
+                    }
+                }
+            }
+            "minLength" | "maxLength" => {
+                let limit = count(arg, span)?;
+                if let Value::String { val, .. } = value {
+                    let length = val.chars().count();
+                    if (keyword == "minLength" && length < limit)
+                        || (keyword == "maxLength" && length > limit)
+                    {
+                        report(
+                            path,
+                            errors,
+                            format!("length {length} violates {keyword} {limit}"),
+                        );
+                    }
+                }
+            }
+            "pattern" => {
+                let pattern = arg.as_string()?;
+                let regex = Regex::new(&pattern).map_err(|e| {
+                    ShellError::UnsupportedInput(
+                        format!("invalid `pattern` in schema: {e}"),
+                        "value originates from here".into(),
+                        span,
+                        arg.expect_span(),
+                    )
+                })?;
+                if let Value::String { val, .. } = value {
+                    if !regex.is_match(val).unwrap_or(false) {
+                        report(
+                            path,
+                            errors,
+                            format!("value doesn't match pattern {pattern}"),
+                        );
+                    }
+                }
+            }
+            "minItems" | "maxItems" => {
+                let limit = count(arg, span)?;
+                if let Value::List { vals, .. } = value {
+                    if (keyword == "minItems" && vals.len() < limit)
+                        || (keyword == "maxItems" && vals.len() > limit)
+                    {
+                        report(
+                            path,
+                            errors,
+                            format!("{} items violate {keyword} {limit}", vals.len()),
+                        );
+                    }
+                }
+            }
+            "uniqueItems" => {
+                if let (Value::Bool { val: true, .. }, Value::List { vals, .. }) = (arg, value) {
+                    let duplicate = vals
+                        .iter()
+                        .enumerate()
+                        .any(|(i, a)| vals[i + 1..].iter().any(|b| a == b));
+                    if duplicate {
+                        report(path, errors, "items are not unique".into());
+                    }
+                }
+            }
+            "items" => {
+                if let Value::List { vals, .. } = value {
+                    for (index, item) in vals.iter().enumerate() {
+                        path.push(index.to_string());
+                        check(arg, item, path, errors, span)?;
+                        path.pop();
+                    }
+                }
+            }
+            "required" => {
+                let required = match arg {
+                    Value::List { vals, .. } => vals,
+                    other => return Err(malformed("`required` must be a list", other, span)),
+                };
+                if let Value::Record { cols, .. } = value {
+                    for name in required {
+                        let name = name.as_string()?;
+                        if !cols.contains(&name) {
+                            report(path, errors, format!("missing required field {name}"));
+                        }
+                    }
+                }
+            }
+            "properties" => {
+                if let Value::Record { cols, vals, .. } = value {
+                    for (col, val) in cols.iter().zip(vals) {
+                        if let Some(property) = property_schema(arg, col) {
+                            path.push(col.clone());
+                            check(property, val, path, errors, span)?;
+                            path.pop();
+                        }
+                    }
+                }
+            }
+            "additionalProperties" => {
+                if let Value::Record { cols, vals, .. } = value {
+                    let properties = schema.get_data_by_key("properties");
+                    for (col, val) in cols.iter().zip(vals) {
+                        let known = properties
+                            .as_ref()
+                            .map_or(false, |p| property_schema(p, col).is_some());
+                        if known {
+                            continue;
+                        }
+                        path.push(col.clone());
+                        if let Value::Bool { val: false, .. } = arg {
+                            report(path, errors, "field is not allowed".into());
+                        } else {
+                            check(arg, val, path, errors, span)?;
+                        }
+                        path.pop();
+                    }
+                }
+            }
+            "allOf" | "anyOf" | "oneOf" => {
+                let schemas = match arg {
+                    Value::List { vals, .. } => vals,
+                    other => {
+                        return Err(malformed(
+                            &format!("`{keyword}` must be a list"),
+                            other,
+                            span,
+                        ))
+                    }
+                };
+                let mut matching = 0;
+                for sub_schema in schemas {
+                    let sub_errors = validate(sub_schema, value, span)?;
+                    if sub_errors.is_empty() {
+                        matching += 1;
+                    } else if keyword == "allOf" {
+                        for mut error in sub_errors {
+                            let mut full_path = path.clone();
+                            full_path.append(&mut error.path);
+                            errors.push(SchemaError {
+                                path: full_path,
+                                message: error.message,
+                            });
+                        }
+                    }
+                }
+                if keyword == "anyOf" && matching == 0 {
+                    report(
+                        path,
+                        errors,
+                        "value matches none of the schemas of anyOf".into(),
+                    );
+                } else if keyword == "oneOf" && matching != 1 {
+                    report(
+                        path,
+                        errors,
+                        format!("value matches {matching} schemas of oneOf instead of one"),
+                    );
+                }
+            }
+            "not" => {
+                if validate(arg, value, span)?.is_empty() {
+                    report(
+                        path,
+                        errors,
+                        "value matches a schema it must not match".into(),
+                    );
+                }
+            }
+            "$ref" | "$dynamicRef" | "if" | "patternProperties" | "dependentRequired" => {
+                return Err(ShellError::UnsupportedInput(
+                    format!("the schema keyword `{keyword}` isn't supported"),
+                    "value originates from here".into(),
+                    span,
+                    arg.expect_span(),
+                ))
+            }
+            // Everything else is an annotation, like `title` or `description`
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn report(path: &[String], errors: &mut Vec<SchemaError>, message: String) {
+    errors.push(SchemaError {
+        path: path.to_vec(),
+        message,
+    });
+}
+
+fn property_schema<'a>(properties: &'a Value, name: &str) -> Option<&'a Value> {
+    match properties {
+        Value::Record { cols, vals, .. } => cols.iter().position(|c| c == name).map(|i| &vals[i]),
+        _ => None,
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match (name, value) {
+        ("integer", Value::Int { .. }) => true,
+        ("integer", Value::Float { val, .. }) => val.fract() == 0.0,
+        ("number", Value::Int { .. } | Value::Float { .. }) => true,
+        (name, value) => json_type(value) == name,
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Int { .. } => "integer",
+        Value::Float { .. } => "number",
+        Value::String { .. } => "string",
+        Value::Bool { .. } => "boolean",
+        Value::Nothing { .. } => "null",
+        Value::Record { .. } => "object",
+        Value::List { .. } => "array",
+        _ => "unknown",
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int { val, .. } => Some(*val as f64),
+        Value::Float { val, .. } => Some(*val),
+        _ => None,
+    }
+}
+
+fn number(arg: &Value, span: Span) -> Result<f64, ShellError> {
+    as_number(arg).ok_or_else(|| malformed("a limit must be a number", arg, span))
+}
+
+fn count(arg: &Value, span: Span) -> Result<usize, ShellError> {
+    match arg {
+        Value::Int { val, .. } if *val >= 0 => Ok(*val as usize),
+        other => Err(malformed(
+            "a length must be a non-negative integer",
+            other,
+            span,
+        )),
+    }
+}
+
+fn malformed(message: &str, value: &Value, span: Span) -> ShellError {
+    ShellError::UnsupportedInput(
+        format!("malformed schema: {message}"),
+        "value originates from here".into(),
+        span,
+        value.expect_span(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(cols: &[&str], vals: Vec<Value>) -> Value {
+        Value::Record {
+            cols: cols.iter().map(|c| c.to_string()).collect(),
+            vals,
+            span: Span::test_data(),
+        }
+    }
+
+    fn messages(schema: &Value, value: &Value) -> Vec<String> {
+        validate(schema, value, Span::test_data())
+            .expect("valid schema")
+            .into_iter()
+            .map(|e| format!("{}: {}", e.path.join("."), e.message))
+            .collect()
+    }
+
+    #[test]
+    fn reports_nested_paths() {
+        let schema = record(
+            &["type", "items"],
+            vec![
+                Value::test_string("array"),
+                record(
+                    &["type", "required", "properties"],
+                    vec![
+                        Value::test_string("object"),
+                        Value::List {
+                            vals: vec![Value::test_string("id")],
+                            span: Span::test_data(),
+                        },
+                        record(
+                            &["id"],
+                            vec![record(&["type"], vec![Value::test_string("integer")])],
+                        ),
+                    ],
+                ),
+            ],
+        );
+        let value = Value::List {
+            vals: vec![
+                record(&["id"], vec![Value::test_int(1)]),
+                record(&["id"], vec![Value::test_string("2")]),
+                record(&["name"], vec![Value::test_string("x")]),
+            ],
+            span: Span::test_data(),
+        };
+
+        assert_eq!(
+            messages(&schema, &value),
+            vec![
+                "1.id: expected integer, found string",
+                "2: missing required field id"
+            ]
+        );
+    }
+
+    #[test]
+    fn integral_floats_are_integers() {
+        let schema = record(&["type"], vec![Value::test_string("integer")]);
+
+        assert!(messages(&schema, &Value::test_float(3.0)).is_empty());
+        assert_eq!(messages(&schema, &Value::test_float(3.5)).len(), 1);
+    }
+
+    #[test]
+    fn one_of_needs_exactly_one_match() {
+        let schema = record(
+            &["oneOf"],
+            vec![Value::List {
+                vals: vec![
+                    record(&["type"], vec![Value::test_string("number")]),
+                    record(&["type"], vec![Value::test_string("integer")]),
+                ],
+                span: Span::test_data(),
+            }],
+        );
+
+        assert!(messages(&schema, &Value::test_float(1.5)).is_empty());
+        assert_eq!(
+            messages(&schema, &Value::test_int(1)),
+            vec![": value matches 2 schemas of oneOf instead of one"]
+        );
+    }
+
+    #[test]
+    fn unsupported_keywords_are_errors() {
+        let schema = record(&["$ref"], vec![Value::test_string("#/defs/a")]);
+
+        assert!(validate(&schema, &Value::test_int(1), Span::test_data()).is_err());
+    }
+}
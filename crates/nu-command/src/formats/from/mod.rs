@@ -5,9 +5,11 @@ mod eml;
 mod ics;
 mod ini;
 mod json;
+mod json_schema;
 mod nuon;
 mod ods;
 mod ssv;
+mod text_table;
 mod toml;
 mod tsv;
 mod url;
@@ -27,6 +29,7 @@ pub use json::FromJson;
 pub use nuon::FromNuon;
 pub use ods::FromOds;
 pub use ssv::FromSsv;
+pub(crate) use text_table::{split_text_table, TextTableOptions};
 pub use tsv::FromTsv;
 pub use vcf::FromVcf;
 pub use xlsx::FromXlsx;
@@ -1,3 +1,4 @@
+use super::text_table::{split_text_table, TextTableOptions};
 use indexmap::map::IndexMap;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
@@ -32,6 +33,18 @@ impl Command for FromSsv {
                 "the minimum spaces to separate columns",
                 Some('m'),
             )
+            .named(
+                "skip",
+                SyntaxShape::Int,
+                "number of lines to skip before the table",
+                Some('s'),
+            )
+            .named(
+                "max-splits",
+                SyntaxShape::Int,
+                "the most columns to split a row into, minus one; the last column keeps the rest of the row",
+                None,
+            )
             .category(Category::Formats)
     }
 
@@ -39,6 +52,13 @@ impl Command for FromSsv {
         "Parse text as space-separated values and create a table. The default minimum number of spaces counted as a separator is 2."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"This splits text the same way as `detect columns`, with different defaults: cells are
+separated by two spaces, quotes aren't special, and the columns without headers are numbered
+from 1. With --max-splits, free text at the end of a row, like the arguments of a process, stays
+in one column."#
+    }
+
     fn examples(&self) -> Vec<Example> {
         vec![Example {
             example: r#"'FOO   BAR
@@ -65,195 +85,13 @@ impl Command for FromSsv {
     }
 }
 
-enum HeaderOptions<'a> {
-    WithHeaders(&'a str),
-    WithoutHeaders,
-}
-
-fn parse_aligned_columns<'a>(
-    lines: impl Iterator<Item = &'a str>,
-    headers: HeaderOptions,
-    separator: &str,
-) -> Vec<Vec<(String, String)>> {
-    fn construct<'a>(
-        lines: impl Iterator<Item = &'a str>,
-        headers: Vec<(String, usize)>,
-    ) -> Vec<Vec<(String, String)>> {
-        lines
-            .map(|l| {
-                headers
-                    .iter()
-                    .enumerate()
-                    .map(|(i, (header_name, start_position))| {
-                        let val = match headers.get(i + 1) {
-                            Some((_, end)) => {
-                                if *end < l.len() {
-                                    l.get(*start_position..*end)
-                                } else {
-                                    l.get(*start_position..)
-                                }
-                            }
-                            None => l.get(*start_position..),
-                        }
-                        .unwrap_or("")
-                        .trim()
-                        .into();
-                        (header_name.clone(), val)
-                    })
-                    .collect()
-            })
-            .collect()
-    }
-
-    let find_indices = |line: &str| {
-        let values = line
-            .split(&separator)
-            .map(str::trim)
-            .filter(|s| !s.is_empty());
-        values
-            .fold(
-                (0, vec![]),
-                |(current_pos, mut indices), value| match line[current_pos..].find(value) {
-                    None => (current_pos, indices),
-                    Some(index) => {
-                        let absolute_index = current_pos + index;
-                        indices.push(absolute_index);
-                        (absolute_index + value.len(), indices)
-                    }
-                },
-            )
-            .1
-    };
-
-    let parse_with_headers = |lines, headers_raw: &str| {
-        let indices = find_indices(headers_raw);
-        let headers = headers_raw
-            .split(&separator)
-            .map(str::trim)
-            .filter(|s| !s.is_empty())
-            .map(String::from)
-            .zip(indices);
-
-        let columns = headers.collect::<Vec<(String, usize)>>();
-
-        construct(lines, columns)
-    };
-
-    let parse_without_headers = |ls: Vec<&str>| {
-        let mut indices = ls
-            .iter()
-            .flat_map(|s| find_indices(s))
-            .collect::<Vec<usize>>();
-
-        indices.sort_unstable();
-        indices.dedup();
-
-        let headers: Vec<(String, usize)> = indices
-            .iter()
-            .enumerate()
-            .map(|(i, position)| (format!("column{}", i + 1), *position))
-            .collect();
-
-        construct(ls.iter().map(|s| s.to_owned()), headers)
-    };
-
-    match headers {
-        HeaderOptions::WithHeaders(headers_raw) => parse_with_headers(lines, headers_raw),
-        HeaderOptions::WithoutHeaders => parse_without_headers(lines.collect()),
-    }
-}
-
-fn parse_separated_columns<'a>(
-    lines: impl Iterator<Item = &'a str>,
-    headers: HeaderOptions,
-    separator: &str,
-) -> Vec<Vec<(String, String)>> {
-    fn collect<'a>(
-        headers: Vec<String>,
-        rows: impl Iterator<Item = &'a str>,
-        separator: &str,
-    ) -> Vec<Vec<(String, String)>> {
-        rows.map(|r| {
-            headers
-                .iter()
-                .zip(r.split(separator).map(str::trim).filter(|s| !s.is_empty()))
-                .map(|(a, b)| (a.to_owned(), b.to_owned()))
-                .collect()
-        })
-        .collect()
-    }
-
-    let parse_with_headers = |lines, headers_raw: &str| {
-        let headers = headers_raw
-            .split(&separator)
-            .map(str::trim)
-            .map(str::to_owned)
-            .filter(|s| !s.is_empty())
-            .collect();
-        collect(headers, lines, separator)
-    };
-
-    let parse_without_headers = |ls: Vec<&str>| {
-        let num_columns = ls.iter().map(|r| r.len()).max().unwrap_or(0);
-
-        let headers = (1..=num_columns)
-            .map(|i| format!("column{i}"))
-            .collect::<Vec<String>>();
-        collect(headers, ls.into_iter(), separator)
-    };
-
-    match headers {
-        HeaderOptions::WithHeaders(headers_raw) => parse_with_headers(lines, headers_raw),
-        HeaderOptions::WithoutHeaders => parse_without_headers(lines.collect()),
-    }
-}
-
-fn string_to_table(
-    s: &str,
-    noheaders: bool,
-    aligned_columns: bool,
-    split_at: usize,
-) -> Vec<Vec<(String, String)>> {
-    let mut lines = s.lines().filter(|l| !l.trim().is_empty());
-    let separator = " ".repeat(std::cmp::max(split_at, 1));
-
-    let (ls, header_options) = if noheaders {
-        (lines, HeaderOptions::WithoutHeaders)
-    } else {
-        match lines.next() {
-            Some(header) => (lines, HeaderOptions::WithHeaders(header)),
-            None => return vec![],
-        }
-    };
-
-    let f = if aligned_columns {
-        parse_aligned_columns
-    } else {
-        parse_separated_columns
-    };
-
-    f(ls, header_options, &separator)
-}
-
-fn from_ssv_string_to_value(
-    s: &str,
-    noheaders: bool,
-    aligned_columns: bool,
-    split_at: usize,
-    span: Span,
-) -> Value {
-    let rows = string_to_table(s, noheaders, aligned_columns, split_at)
-        .iter()
+fn from_ssv_string_to_value(s: &str, options: &TextTableOptions, span: Span) -> Value {
+    let rows = split_text_table(s, options)
+        .into_iter()
         .map(|row| {
             let mut dict = IndexMap::new();
             for (col, entry) in row {
-                dict.insert(
-                    col.to_string(),
-                    Value::String {
-                        val: entry.to_string(),
-                        span,
-                    },
-                );
+                dict.insert(col, Value::String { val: entry, span });
             }
             Value::from(Spanned { item: dict, span })
         })
@@ -275,16 +113,25 @@ fn from_ssv(
     let minimum_spaces: Option<Spanned<usize>> =
         call.get_flag(engine_state, stack, "minimum-spaces")?;
 
+    let skip: Option<usize> = call.get_flag(engine_state, stack, "skip")?;
+    let max_splits: Option<usize> = call.get_flag(engine_state, stack, "max-splits")?;
+
     let (concat_string, _span, metadata) = input.collect_string_strict(name)?;
-    let split_at = match minimum_spaces {
-        Some(number) => number.item,
-        None => DEFAULT_MINIMUM_SPACES,
+    let options = TextTableOptions {
+        minimum_spaces: match minimum_spaces {
+            Some(number) => number.item,
+            None => DEFAULT_MINIMUM_SPACES,
+        },
+        skip: skip.unwrap_or_default(),
+        headers: !noheaders,
+        aligned: aligned_columns,
+        max_splits,
+        quoting: false,
+        first_column: 1,
     };
 
-    Ok(
-        from_ssv_string_to_value(&concat_string, noheaders, aligned_columns, split_at, name)
-            .into_pipeline_data_with_metadata(metadata),
-    )
+    Ok(from_ssv_string_to_value(&concat_string, &options, name)
+        .into_pipeline_data_with_metadata(metadata))
 }
 
 #[cfg(test)]
@@ -295,6 +142,22 @@ mod tests {
         (String::from(x), String::from(y))
     }
 
+    fn string_to_table(
+        s: &str,
+        noheaders: bool,
+        aligned_columns: bool,
+        split_at: usize,
+    ) -> Vec<Vec<(String, String)>> {
+        let options = TextTableOptions {
+            minimum_spaces: split_at,
+            headers: !noheaders,
+            aligned: aligned_columns,
+            first_column: 1,
+            ..Default::default()
+        };
+        split_text_table(s, &options)
+    }
+
     #[test]
     fn it_trims_empty_and_whitespace_only_lines() {
         let input = r#"
@@ -491,6 +354,29 @@ mod tests {
         assert_eq!(aligned_columns_with_headers, separator_with_headers);
     }
 
+    #[test]
+    fn max_splits_keeps_trailing_text_together() {
+        let input = r#"
+            PID   CMD
+            1     /sbin/init  splash
+            42    nu  --login
+        "#;
+
+        let options = TextTableOptions {
+            minimum_spaces: 2,
+            max_splits: Some(1),
+            ..Default::default()
+        };
+        let result = split_text_table(input, &options);
+        assert_eq!(
+            result,
+            vec![
+                vec![owned("PID", "1"), owned("CMD", "/sbin/init  splash")],
+                vec![owned("PID", "42"), owned("CMD", "nu  --login")]
+            ]
+        );
+    }
+
     #[test]
     fn test_examples() {
         use crate::test_examples;
@@ -0,0 +1,344 @@
+//! Splits text laid out as a table, like the output of `ps` or `docker ps`, into rows of named
+//! cells. `from ssv` and `detect columns` both use it, with their own defaults.
+
+pub struct TextTableOptions {
+    /// The fewest spaces that separate two cells. A gap with a tab always separates them.
+    pub minimum_spaces: usize,
+    /// The lines to skip before the table starts
+    pub skip: usize,
+    /// Whether the first line of the table holds the column names
+    pub headers: bool,
+    /// Cut every line where the columns start, instead of at the gaps between its own cells
+    pub aligned: bool,
+    /// The most times a line is split, so the last cell keeps the rest of the line
+    pub max_splits: Option<usize>,
+    /// Keep whitespace inside quotes and brackets in the cell
+    pub quoting: bool,
+    /// The number of the first generated column name, like `column0` or `column1`
+    pub first_column: usize,
+}
+
+impl Default for TextTableOptions {
+    fn default() -> Self {
+        TextTableOptions {
+            minimum_spaces: 1,
+            skip: 0,
+            headers: true,
+            aligned: false,
+            max_splits: None,
+            quoting: false,
+            first_column: 0,
+        }
+    }
+}
+
+/// The range of a cell in its line, in bytes, or in characters when it is compared with the
+/// cells of other lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cell {
+    start: usize,
+    end: usize,
+}
+
+impl Cell {
+    /// The cell measured in characters, so it lines up with the cells of other lines even when
+    /// they hold multibyte text
+    fn in_chars(self, line: &str) -> Cell {
+        Cell {
+            start: line[..self.start].chars().count(),
+            end: line[..self.end].chars().count(),
+        }
+    }
+}
+
+/// The byte offset of the character at `index`, or the end of the line when it is shorter
+fn byte_offset(line: &str, index: usize) -> usize {
+    line.char_indices()
+        .nth(index)
+        .map_or(line.len(), |(offset, _)| offset)
+}
+
+/// Splits `text` into rows of `(column name, value)` pairs. Blank lines are left out, and a
+/// missing value is an empty string.
+pub fn split_text_table(text: &str, options: &TextTableOptions) -> Vec<Vec<(String, String)>> {
+    let lines: Vec<(&str, Vec<Cell>)> = text
+        .lines()
+        .skip(options.skip)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| (line, split_line(line, options)))
+        .collect();
+
+    let (names, columns, rows) = if options.headers {
+        let ((header, header_cells), rows) = match lines.split_first() {
+            Some(split) => split,
+            None => return vec![],
+        };
+        let names: Vec<String> = header_cells
+            .iter()
+            .map(|cell| header[cell.start..cell.end].to_string())
+            .collect();
+        let columns = header_cells
+            .iter()
+            .map(|cell| cell.in_chars(header))
+            .collect();
+        (names, columns, rows)
+    } else {
+        let columns: Vec<Cell> = if options.aligned {
+            // Without headers, a column starts wherever a cell starts on any line
+            let mut starts: Vec<usize> = lines
+                .iter()
+                .flat_map(|(line, cells)| cells.iter().map(move |cell| cell.in_chars(line).start))
+                .collect();
+            starts.sort_unstable();
+            starts.dedup();
+            starts
+                .into_iter()
+                .map(|start| Cell {
+                    start,
+                    end: start + 1,
+                })
+                .collect()
+        } else {
+            lines
+                .first()
+                .map(|(line, cells)| cells.iter().map(|cell| cell.in_chars(line)).collect())
+                .unwrap_or_default()
+        };
+        let names: Vec<String> = (0..columns.len())
+            .map(|i| format!("column{}", i + options.first_column))
+            .collect();
+        (names, columns, &lines[..])
+    };
+
+    rows.iter()
+        .map(|(line, cells)| {
+            let values = if options.aligned {
+                cut_at_columns(line, &columns)
+            } else if cells.len() == columns.len() {
+                cells
+                    .iter()
+                    .map(|cell| line[cell.start..cell.end].to_string())
+                    .collect()
+            } else {
+                align_cells(line, cells, &columns)
+            };
+            names.iter().cloned().zip(values).collect()
+        })
+        .collect()
+}
+
+/// The cells of a line, which are separated by gaps of at least `minimum_spaces` spaces
+fn split_line(line: &str, options: &TextTableOptions) -> Vec<Cell> {
+    let minimum_spaces = options.minimum_spaces.max(1);
+    let mut cells = vec![];
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c.is_whitespace() && quote.is_none() && depth == 0 {
+            let mut width = 1;
+            let mut has_tab = c == '\t';
+            while let Some((_, c)) = chars.peek().copied().filter(|(_, c)| c.is_whitespace()) {
+                width += 1;
+                has_tab |= c == '\t';
+                chars.next();
+            }
+
+            let can_split = options.max_splits.map_or(true, |max| cells.len() < max);
+            if let Some(cell_start) = start {
+                if can_split && (has_tab || width >= minimum_spaces) {
+                    cells.push(Cell {
+                        start: cell_start,
+                        end,
+                    });
+                    start = None;
+                }
+            }
+            // A narrower gap is part of the cell
+            continue;
+        }
+
+        if start.is_none() {
+            start = Some(i);
+        }
+        end = i + c.len_utf8();
+
+        if options.quoting {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None => match c {
+                    '"' | '\'' | '`' => quote = Some(c),
+                    '[' | '(' | '{' => depth += 1,
+                    ']' | ')' | '}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    if let Some(start) = start {
+        cells.push(Cell { start, end });
+    }
+    cells
+}
+
+/// Cuts a line where the columns start, counting characters so that multibyte text is never cut
+/// in the middle. The first column keeps what comes before it and the last one the rest of the
+/// line, so no text is left out.
+fn cut_at_columns(line: &str, columns: &[Cell]) -> Vec<String> {
+    let ends = columns
+        .iter()
+        .skip(1)
+        .map(|column| byte_offset(line, column.start))
+        .chain(std::iter::once(line.len()));
+
+    let mut start = 0;
+    ends.map(|end| {
+        let end = end.max(start);
+        let cell = line[start..end].trim().to_string();
+        start = end;
+        cell
+    })
+    .collect()
+}
+
+/// Puts the cells of a line under the columns they overlap, for lines with more or fewer cells
+/// than there are columns. A cell under no column goes to the column before it, and cells under
+/// the same column are joined.
+fn align_cells(line: &str, cells: &[Cell], columns: &[Cell]) -> Vec<String> {
+    let mut ranges: Vec<Option<Cell>> = vec![None; columns.len()];
+    for cell in cells {
+        let position = cell.in_chars(line);
+        let index = columns
+            .iter()
+            .position(|column| position.start < column.end && position.end > column.start)
+            .or_else(|| {
+                columns
+                    .iter()
+                    .rposition(|column| column.start <= position.start)
+            })
+            .unwrap_or(0);
+
+        if let Some(range) = ranges.get_mut(index) {
+            let range = range.get_or_insert(*cell);
+            range.start = range.start.min(cell.start);
+            range.end = range.end.max(cell.end);
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|range| {
+            range
+                .map(|range| line[range.start..range.end].to_string())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn texts(line: &str, options: &TextTableOptions) -> Vec<String> {
+        split_line(line, options)
+            .into_iter()
+            .map(|cell| line[cell.start..cell.end].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn splits_at_wide_gaps_and_tabs() {
+        let options = TextTableOptions {
+            minimum_spaces: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            texts("  a b  c\td", &options),
+            vec!["a b".to_string(), "c".into(), "d".into()]
+        );
+    }
+
+    #[test]
+    fn quotes_and_brackets_keep_their_spaces() {
+        let options = TextTableOptions {
+            quoting: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            texts(r#"a "b c" [d e]"#, &options),
+            vec!["a".to_string(), r#""b c""#.into(), "[d e]".into()]
+        );
+    }
+
+    #[test]
+    fn max_splits_keeps_the_rest_of_the_line() {
+        let options = TextTableOptions {
+            max_splits: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            texts("root 1 /sbin/init splash  quiet", &options),
+            vec![
+                "root".to_string(),
+                "1".into(),
+                "/sbin/init splash  quiet".into()
+            ]
+        );
+    }
+
+    #[test]
+    fn extra_cells_join_the_column_they_overlap() {
+        let options = TextTableOptions::default();
+        let table = split_text_table("NAME   COMMAND\nnu     nu --login -i", &options);
+
+        assert_eq!(
+            table,
+            vec![vec![
+                ("NAME".to_string(), "nu".to_string()),
+                ("COMMAND".to_string(), "nu --login -i".to_string())
+            ]]
+        );
+    }
+
+    #[test]
+    fn aligned_columns_count_characters() {
+        let options = TextTableOptions {
+            aligned: true,
+            ..Default::default()
+        };
+        let table = split_text_table("NAME   CITY\nÄÄÄÄ   Köln\nAnn    Oslo", &options);
+
+        assert_eq!(
+            table,
+            vec![
+                vec![
+                    ("NAME".to_string(), "ÄÄÄÄ".to_string()),
+                    ("CITY".to_string(), "Köln".to_string())
+                ],
+                vec![
+                    ("NAME".to_string(), "Ann".to_string()),
+                    ("CITY".to_string(), "Oslo".to_string())
+                ]
+            ]
+        );
+    }
+
+    #[test]
+    fn aligned_columns_keep_text_before_the_first_column() {
+        let options = TextTableOptions {
+            aligned: true,
+            ..Default::default()
+        };
+        let table = split_text_table("  NAME  CITY\nAnn     Oslo", &options);
+
+        assert_eq!(table[0][0], ("NAME".to_string(), "Ann".to_string()));
+    }
+}
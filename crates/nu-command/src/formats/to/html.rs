@@ -5,7 +5,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Config, DataSource, Example, IntoPipelineData, PipelineData, PipelineMetadata,
-    ShellError, Signature, Spanned, SyntaxShape, Type, Value,
+    ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 use rust_embed::RustEmbed;
 use serde::{Deserialize, Serialize};
@@ -114,6 +114,22 @@ impl Command for ToHtml {
                 "produce a color table of all available themes",
                 Some('l'),
             )
+            .switch(
+                "report",
+                "produce a self-contained page with sortable tables and collapsible nested data",
+                Some('r'),
+            )
+            .named(
+                "title",
+                SyntaxShape::String,
+                "the title of the page produced with --report",
+                None,
+            )
+            .switch(
+                "no-script",
+                "leave out the sorting and filtering script of --report",
+                None,
+            )
             .category(Category::Formats)
     }
 
@@ -140,6 +156,12 @@ impl Command for ToHtml {
                     r#"<html><style>body { background-color:black;color:white; }</style><body><table><thead><tr><th>foo</th><th>bar</th></tr></thead><tbody><tr><td>1</td><td>2</td></tr></tbody></table></body></html>"#,
                 )),
             },
+            Example {
+                description:
+                    "Save a standalone report that can be shared and opened in any browser",
+                example: "ls | to html --report --title 'Files' | save report.html",
+                result: None,
+            },
         ]
     }
 
@@ -148,7 +170,12 @@ impl Command for ToHtml {
     }
 
     fn extra_usage(&self) -> &str {
-        "Screenshots of the themes can be browsed here: https://github.com/mbadolato/iTerm2-Color-Schemes"
+        r#"Screenshots of the themes can be browsed here: https://github.com/mbadolato/iTerm2-Color-Schemes
+
+With --report, the output is a complete page with its styling inlined, which follows the dark
+mode of the browser unless --dark is given. Headers of tables can be clicked to sort the rows, a
+search box above the table filters them, and nested records and lists are folded into
+collapsible sections."#
     }
 
     fn run(
@@ -166,22 +193,7 @@ fn get_theme_from_asset_file(
     is_dark: bool,
     theme: &Option<Spanned<String>>,
 ) -> Result<HashMap<&'static str, String>, ShellError> {
-    let theme_name = match theme {
-        Some(s) => &s.item,
-        None => "default", // There is no theme named "default" so this will be HtmlTheme::default(), which is "nu_default".
-    };
-
-    // 228 themes come from
-    // https://github.com/mbadolato/iTerm2-Color-Schemes/tree/master/windowsterminal
-    // we should find a hit on any name in there
-    let asset = get_html_themes("228_themes.json").unwrap_or_default();
-
-    // Find the theme by theme name
-    let th = asset
-        .themes
-        .into_iter()
-        .find(|n| n.name.to_lowercase() == theme_name.to_lowercase()) // case insensitive search
-        .unwrap_or_default();
+    let th = find_html_theme(theme);
 
     Ok(convert_html_theme_to_hash_map(is_dark, &th))
 }
@@ -243,9 +255,20 @@ fn to_html(
     let dark = call.has_flag("dark");
     let partial = call.has_flag("partial");
     let list = call.has_flag("list");
+    let report = call.has_flag("report");
     let theme: Option<Spanned<String>> = call.get_flag(engine_state, stack, "theme")?;
+    let title: Option<Spanned<String>> = call.get_flag(engine_state, stack, "title")?;
     let config = engine_state.get_config();
 
+    if report && partial {
+        return Err(ShellError::IncompatibleParameters {
+            left_message: "can't produce a whole page".into(),
+            left_span: call.get_named_arg("report").expect("has flag").span,
+            right_message: "because only the content is requested".into(),
+            right_span: call.get_named_arg("partial").expect("has flag").span,
+        });
+    }
+
     let vec_of_values = input.into_iter().collect::<Vec<Value>>();
     let headers = merge_descriptors(&vec_of_values);
     let headers = Some(headers)
@@ -344,53 +367,59 @@ fn to_html(
         };
 
         // change the color of the page
-        if !partial {
-            write!(
-                &mut output_string,
-                r"<html><style>body {{ background-color:{};color:{}; }}</style><body>",
-                color_hm
-                    .get("background")
-                    .expect("Error getting background color"),
-                color_hm
-                    .get("foreground")
-                    .expect("Error getting foreground color")
-            )
-            .unwrap();
+        if report {
+            let title = title.map_or_else(|| "Report".to_string(), |title| title.item);
+            let script = !call.has_flag("no-script");
+            output_string = html_report(vec_of_values, &title, dark, &theme, script, config);
         } else {
-            write!(
-                &mut output_string,
-                "<div style=\"background-color:{};color:{};\">",
-                color_hm
-                    .get("background")
-                    .expect("Error getting background color"),
-                color_hm
-                    .get("foreground")
-                    .expect("Error getting foreground color")
-            )
-            .unwrap();
-        }
-
-        let inner_value = match vec_of_values.len() {
-            0 => String::default(),
-            1 => match headers {
-                Some(headers) => html_table(vec_of_values, headers, config),
-                None => {
-                    let value = &vec_of_values[0];
-                    html_value(value.clone(), config)
-                }
-            },
-            _ => match headers {
-                Some(headers) => html_table(vec_of_values, headers, config),
-                None => html_list(vec_of_values, config),
-            },
-        };
-
-        output_string.push_str(&inner_value);
+            if !partial {
+                write!(
+                    &mut output_string,
+                    r"<html><style>body {{ background-color:{};color:{}; }}</style><body>",
+                    color_hm
+                        .get("background")
+                        .expect("Error getting background color"),
+                    color_hm
+                        .get("foreground")
+                        .expect("Error getting foreground color")
+                )
+                .unwrap();
+            } else {
+                write!(
+                    &mut output_string,
+                    "<div style=\"background-color:{};color:{};\">",
+                    color_hm
+                        .get("background")
+                        .expect("Error getting background color"),
+                    color_hm
+                        .get("foreground")
+                        .expect("Error getting foreground color")
+                )
+                .unwrap();
+            }
 
-        if !partial {
-            output_string.push_str("</body></html>");
-        } else {
-            output_string.push_str("</div>")
+            let inner_value = match vec_of_values.len() {
+                0 => String::default(),
+                1 => match headers {
+                    Some(headers) => html_table(vec_of_values, headers, config),
+                    None => {
+                        let value = &vec_of_values[0];
+                        html_value(value.clone(), config)
+                    }
+                },
+                _ => match headers {
+                    Some(headers) => html_table(vec_of_values, headers, config),
+                    None => html_list(vec_of_values, config),
+                },
+            };
+
+            output_string.push_str(&inner_value);
+
+            if !partial {
+                output_string.push_str("</body></html>");
+            } else {
+                output_string.push_str("</div>")
+            }
         }
 
         // Check to see if we want to remove all color or change ansi to html colors
@@ -467,6 +496,211 @@ fn html_value(value: Value, config: &Config) -> String {
     output_string
 }
 
+fn html_report(
+    values: Vec<Value>,
+    title: &str,
+    is_dark: bool,
+    theme: &Option<Spanned<String>>,
+    script: bool,
+    config: &Config,
+) -> String {
+    let title = htmlescape::encode_minimal(title);
+    let mut output_string = String::new();
+
+    write!(
+        &mut output_string,
+        r#"<!DOCTYPE html><html><head><meta charset="utf-8"><meta name="viewport" content="width=device-width, initial-scale=1"><title>{}</title><style>"#,
+        title
+    )
+    .unwrap();
+
+    // Without --dark, the page follows the color scheme preferred by the browser
+    let light = convert_html_theme_to_hash_map(false, &find_html_theme(theme));
+    let dark = convert_html_theme_to_hash_map(true, &find_html_theme(theme));
+    if is_dark {
+        output_string.push_str(&report_color_variables(&dark));
+    } else {
+        output_string.push_str(&report_color_variables(&light));
+        write!(
+            &mut output_string,
+            "@media (prefers-color-scheme: dark) {{ {} }}",
+            report_color_variables(&dark)
+        )
+        .unwrap();
+    }
+    output_string.push_str(&get_asset_text("report.css"));
+
+    write!(
+        &mut output_string,
+        "</style></head><body><h1>{}</h1>",
+        title
+    )
+    .unwrap();
+
+    let value = match values.len() {
+        1 => values.into_iter().next().expect("one value"),
+        _ => Value::List {
+            vals: values,
+            span: Span::unknown(),
+        },
+    };
+    match value {
+        Value::List { vals, .. } if is_table(&vals) => {
+            // Only the top level table gets a search box, nested ones are usually small
+            output_string
+                .push_str(r#"<input class="nu-filter" type="search" placeholder="Filter rows">"#);
+            output_string.push_str(&report_table(vals, config));
+        }
+        value => output_string.push_str(&report_value(value, config)),
+    }
+
+    if script {
+        output_string.push_str("<script>");
+        output_string.push_str(&get_asset_text("report.js"));
+        output_string.push_str("</script>");
+    }
+    output_string.push_str("</body></html>");
+
+    output_string
+}
+
+fn report_color_variables(color_hm: &HashMap<&str, String>) -> String {
+    format!(
+        ":root {{ --background:{};--foreground:{};--accent:{}; }}",
+        color_hm
+            .get("background")
+            .expect("Error getting background color"),
+        color_hm
+            .get("foreground")
+            .expect("Error getting foreground color"),
+        color_hm.get("blue").expect("Error getting blue text color"),
+    )
+}
+
+fn report_value(value: Value, config: &Config) -> String {
+    match value {
+        Value::Record { cols, vals, .. } => report_record(cols, vals, config),
+        Value::List { vals, .. } if is_table(&vals) => report_table(vals, config),
+        Value::List { vals, .. } => {
+            let mut output_string = String::new();
+            output_string.push_str("<ol>");
+            for value in vals {
+                output_string.push_str("<li>");
+                output_string.push_str(&report_cell(value, config));
+                output_string.push_str("</li>");
+            }
+            output_string.push_str("</ol>");
+            output_string
+        }
+        other => report_cell(other, config),
+    }
+}
+
+fn report_record(cols: Vec<String>, vals: Vec<Value>, config: &Config) -> String {
+    let mut output_string = String::new();
+    output_string.push_str(r#"<table class="nu-record"><tbody>"#);
+    for (col, val) in cols.iter().zip(vals) {
+        output_string.push_str("<tr><th>");
+        output_string.push_str(&htmlescape::encode_minimal(col));
+        output_string.push_str("</th>");
+        output_string.push_str(&report_td(val, config));
+        output_string.push_str("</tr>");
+    }
+    output_string.push_str("</tbody></table>");
+    output_string
+}
+
+fn report_table(table: Vec<Value>, config: &Config) -> String {
+    let headers = merge_descriptors(&table);
+    let mut output_string = String::new();
+
+    output_string.push_str(r#"<table class="nu-sortable"><thead><tr>"#);
+    for header in &headers {
+        output_string.push_str("<th>");
+        output_string.push_str(&htmlescape::encode_minimal(header));
+        output_string.push_str("</th>");
+    }
+    output_string.push_str("</tr></thead><tbody>");
+
+    for row in table {
+        output_string.push_str("<tr>");
+        for header in &headers {
+            let data = row
+                .get_data_by_key(header)
+                .unwrap_or_else(|| Value::nothing(row.span().unwrap_or_else(|_| Span::unknown())));
+            output_string.push_str(&report_td(data, config));
+        }
+        output_string.push_str("</tr>");
+    }
+    output_string.push_str("</tbody></table>");
+
+    output_string
+}
+
+fn report_td(value: Value, config: &Config) -> String {
+    let class = match value {
+        Value::Int { .. } | Value::Float { .. } | Value::Filesize { .. } => " class=\"nu-number\"",
+        Value::Nothing { .. } => " class=\"nu-nothing\"",
+        _ => "",
+    };
+    format!("<td{}>{}</td>", class, report_cell(value, config))
+}
+
+// Nested records and lists are folded away, so that big structures stay readable
+fn report_cell(value: Value, config: &Config) -> String {
+    let summary = match &value {
+        Value::Record { cols, .. } => format!("record {} field{}", cols.len(), plural(cols.len())),
+        Value::List { vals, .. } if is_table(vals) => {
+            format!("table {} row{}", vals.len(), plural(vals.len()))
+        }
+        Value::List { vals, .. } => format!("list {} item{}", vals.len(), plural(vals.len())),
+        _ => return html_value(value, config),
+    };
+    format!(
+        "<details><summary>{}</summary>{}</details>",
+        summary,
+        report_value(value, config)
+    )
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+fn is_table(vals: &[Value]) -> bool {
+    !vals.is_empty() && vals.iter().all(|v| matches!(v, Value::Record { .. }))
+}
+
+fn find_html_theme(theme: &Option<Spanned<String>>) -> HtmlTheme {
+    let theme_name = match theme {
+        Some(s) => &s.item,
+        None => "default", // There is no theme named "default" so this will be HtmlTheme::default(), which is "nu_default".
+    };
+
+    // 228 themes come from
+    // https://github.com/mbadolato/iTerm2-Color-Schemes/tree/master/windowsterminal
+    // we should find a hit on any name in there
+    let asset = get_html_themes("228_themes.json").unwrap_or_default();
+
+    // Find the theme by theme name
+    asset
+        .themes
+        .into_iter()
+        .find(|n| n.name.to_lowercase() == theme_name.to_lowercase()) // case insensitive search
+        .unwrap_or_default()
+}
+
+fn get_asset_text(name: &str) -> String {
+    match Assets::get(name) {
+        Some(content) => String::from_utf8_lossy(&content.data).into_owned(),
+        None => String::new(),
+    }
+}
+
 fn setup_html_color_regexes(
     hash: &mut HashMap<u32, (&'static str, String)>,
     color_hm: &HashMap<&str, String>,
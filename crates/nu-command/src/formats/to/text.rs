@@ -54,6 +54,7 @@ impl Command for ToText {
                 )),
                 stderr: None,
                 exit_code: None,
+                resource_usage: None,
                 span,
                 metadata: None,
                 trim_end_newline: false,
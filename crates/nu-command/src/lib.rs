@@ -26,6 +26,7 @@ mod shells;
 mod sort_utils;
 mod strings;
 mod system;
+mod units;
 mod viewers;
 
 pub use bits::*;
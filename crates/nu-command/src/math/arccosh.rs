@@ -12,6 +12,7 @@ impl Command for SubCommand {
 
     fn signature(&self) -> Signature {
         Signature::build("math arccosh")
+            .switch("degrees", "Return degrees instead of radians", Some('d'))
             .input_output_types(vec![(Type::Number, Type::Float)])
             .vectorizes_over_list(true)
             .category(Category::Math)
@@ -33,26 +34,34 @@ impl Command for SubCommand {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
+        let use_degrees = call.has_flag("degrees");
         // This doesn't match explicit nulls
         if matches!(input, PipelineData::Empty) {
             return Err(ShellError::PipelineEmpty(head));
         }
         input.map(
-            move |value| operate(value, head),
+            move |value| operate(value, head, use_degrees),
             engine_state.ctrlc.clone(),
         )
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Get the arccosh of 1",
-            example: "1 | math arccosh",
-            result: Some(Value::test_float(0.0f64)),
-        }]
+        vec![
+            Example {
+                description: "Get the arccosh of 1",
+                example: "1 | math arccosh",
+                result: Some(Value::test_float(0.0f64)),
+            },
+            Example {
+                description: "Get the arccosh of 2 in degrees",
+                example: "2 | math arccosh -d | math round --precision 2",
+                result: Some(Value::test_float(75.46)),
+            },
+        ]
     }
 }
 
-fn operate(value: Value, head: Span) -> Value {
+fn operate(value: Value, head: Span, use_degrees: bool) -> Value {
     match value {
         numeric @ (Value::Int { .. } | Value::Float { .. }) => {
             let (val, span) = match numeric {
@@ -63,6 +72,8 @@ fn operate(value: Value, head: Span) -> Value {
 
             if (1.0..).contains(&val) {
                 let val = val.acosh();
+                let val = if use_degrees { val.to_degrees() } else { val };
+                let val = if use_degrees { val.to_degrees() } else { val };
 
                 Value::Float { val, span }
             } else {
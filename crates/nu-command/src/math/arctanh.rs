@@ -12,6 +12,7 @@ impl Command for SubCommand {
 
     fn signature(&self) -> Signature {
         Signature::build("math arctanh")
+            .switch("degrees", "Return degrees instead of radians", Some('d'))
             .input_output_types(vec![(Type::Number, Type::Float)])
             .vectorizes_over_list(true)
             .category(Category::Math)
@@ -33,12 +34,13 @@ impl Command for SubCommand {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
+        let use_degrees = call.has_flag("degrees");
         // This doesn't match explicit nulls
         if matches!(input, PipelineData::Empty) {
             return Err(ShellError::PipelineEmpty(head));
         }
         input.map(
-            move |value| operate(value, head),
+            move |value| operate(value, head, use_degrees),
             engine_state.ctrlc.clone(),
         )
     }
@@ -52,7 +54,7 @@ impl Command for SubCommand {
     }
 }
 
-fn operate(value: Value, head: Span) -> Value {
+fn operate(value: Value, head: Span, use_degrees: bool) -> Value {
     match value {
         numeric @ (Value::Int { .. } | Value::Float { .. }) => {
             let (val, span) = match numeric {
@@ -63,6 +65,8 @@ fn operate(value: Value, head: Span) -> Value {
 
             if (-1.0..=1.0).contains(&val) {
                 let val = val.atanh();
+                let val = if use_degrees { val.to_degrees() } else { val };
+                let val = if use_degrees { val.to_degrees() } else { val };
 
                 Value::Float { val, span }
             } else {
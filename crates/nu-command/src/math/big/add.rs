@@ -0,0 +1,85 @@
+use crate::math::big::utils::binary_operation;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math big add"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big add")
+            .input_output_types(vec![
+                (Type::Number, Type::String),
+                (Type::String, Type::String),
+            ])
+            .vectorizes_over_list(true)
+            .required(
+                "operand",
+                SyntaxShape::Any,
+                "the number to add, as an int, float or string",
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Adds a number to big numbers, without losing precision."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["plus", "addition", "decimal"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let operand: Value = call.req(engine_state, stack, 0)?;
+        binary_operation(engine_state, call, input, operand, |a, b, _| Ok(a + b))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Add two decimals exactly",
+                example: "0.1 | math big add 0.2",
+                result: Some(Value::test_string("0.3")),
+            },
+            Example {
+                description: "Add to an int past the largest int",
+                example: "9223372036854775807 | math big add 1",
+                result: Some(Value::test_string("9223372036854775808")),
+            },
+            Example {
+                description: "Add to a list of amounts",
+                example: r#"["19.99" "5.01"] | math big add "0.10""#,
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("20.09"), Value::test_string("5.11")],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
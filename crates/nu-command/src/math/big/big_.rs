@@ -0,0 +1,56 @@
+use nu_engine::get_full_help;
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct MathBig;
+
+impl Command for MathBig {
+    fn name(&self) -> &str {
+        "math big"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big")
+            .category(Category::Math)
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+    }
+
+    fn usage(&self) -> &str {
+        "Arbitrary precision arithmetic, on integers and decimals of any size."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The numbers can be given as ints, floats or strings, and the results are strings holding
+the exact number, so they don't overflow like ints or lose precision like floats.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["bigint", "decimal", "precision", "overflow"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::String {
+            val: get_full_help(
+                &MathBig.signature(),
+                &MathBig.examples(),
+                engine_state,
+                stack,
+                self.is_parser_keyword(),
+            ),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+}
@@ -0,0 +1,124 @@
+use crate::math::big::utils::{from_big, to_big};
+use bigdecimal::{BigDecimal, Zero};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math big div"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big div")
+            .input_output_types(vec![
+                (Type::Number, Type::String),
+                (Type::String, Type::String),
+            ])
+            .vectorizes_over_list(true)
+            .required(
+                "operand",
+                SyntaxShape::Any,
+                "the number to divide by, as an int, float or string",
+            )
+            .named(
+                "precision",
+                SyntaxShape::Int,
+                "round the result to this many digits after the decimal point",
+                Some('p'),
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Divides big numbers by a number, keeping 100 significant digits."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["division", "decimal"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let operand: Value = call.req(engine_state, stack, 0)?;
+        let precision: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "precision")?;
+
+        if let Some(precision) = &precision {
+            if precision.item < 0 {
+                return Err(ShellError::NeedsPositiveValue(precision.span));
+            }
+        }
+
+        let operand_span = operand.span()?;
+        let divisor = to_big(&operand, head)?;
+        if divisor.is_zero() {
+            return Err(ShellError::DivisionByZero(operand_span));
+        }
+
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| {
+                let span = value.span().unwrap_or(head);
+                match to_big(&value, head) {
+                    Ok(dividend) => from_big(divide(dividend, &divisor, &precision), span),
+                    Err(error) => Value::Error { error },
+                }
+            },
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Divide exactly",
+                example: "1 | math big div 8",
+                result: Some(Value::test_string("0.125")),
+            },
+            Example {
+                description: "Split an amount in three, rounded to cents",
+                example: r#""100.00" | math big div 3 --precision 2"#,
+                result: Some(Value::test_string("33.33")),
+            },
+        ]
+    }
+}
+
+fn divide(
+    dividend: BigDecimal,
+    divisor: &BigDecimal,
+    precision: &Option<Spanned<i64>>,
+) -> BigDecimal {
+    let quotient = dividend / divisor;
+    match precision {
+        Some(precision) => quotient.round(precision.item),
+        None => quotient,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,116 @@
+use crate::math::big::utils::{from_big, to_big};
+use bigdecimal::{BigDecimal, One, ToPrimitive};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+// 100000! already has almost half a million digits
+const MAX_FACTORIAL: u64 = 100_000;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math big factorial"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big factorial")
+            .input_output_types(vec![
+                (Type::Int, Type::String),
+                (Type::String, Type::String),
+            ])
+            .vectorizes_over_list(true)
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the factorial of integers, without overflowing."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["bigint", "combinatorics"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| {
+                let span = value.span().unwrap_or(head);
+                match to_big(&value, head).and_then(|value| factorial(value, head, span)) {
+                    Ok(result) => from_big(result, span),
+                    Err(error) => Value::Error { error },
+                }
+            },
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the factorial of 25",
+                example: "25 | math big factorial",
+                result: Some(Value::test_string("15511210043330985984000000")),
+            },
+            Example {
+                description: "Get the factorials of a list of integers",
+                example: "[0 5] | math big factorial",
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("1"), Value::test_string("120")],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Get the factorial of a number given as a string",
+                example: r#""30" | math big factorial"#,
+                result: Some(Value::test_string("265252859812191058636308480000000")),
+            },
+        ]
+    }
+}
+
+fn factorial(value: BigDecimal, head: Span, span: Span) -> Result<BigDecimal, ShellError> {
+    let n = Some(value)
+        .filter(BigDecimal::is_integer)
+        .and_then(|value| value.to_u64())
+        .filter(|n| *n <= MAX_FACTORIAL);
+    let n = match n {
+        Some(n) => n,
+        None => {
+            return Err(ShellError::UnsupportedInput(
+                format!("The factorial is only computed for integers from 0 to {MAX_FACTORIAL}"),
+                "value originates from here".into(),
+                head,
+                span,
+            ))
+        }
+    };
+
+    Ok((2..=n).fold(BigDecimal::one(), |product, i| {
+        product * BigDecimal::from(i)
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
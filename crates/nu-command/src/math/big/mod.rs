@@ -0,0 +1,18 @@
+mod add;
+mod big_;
+mod div;
+mod factorial;
+mod mul;
+mod pow;
+mod sub;
+mod sum;
+mod utils;
+
+pub use add::SubCommand as MathBigAdd;
+pub use big_::MathBig;
+pub use div::SubCommand as MathBigDiv;
+pub use factorial::SubCommand as MathBigFactorial;
+pub use mul::SubCommand as MathBigMul;
+pub use pow::SubCommand as MathBigPow;
+pub use sub::SubCommand as MathBigSub;
+pub use sum::SubCommand as MathBigSum;
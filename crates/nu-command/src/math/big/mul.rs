@@ -0,0 +1,82 @@
+use crate::math::big::utils::binary_operation;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math big mul"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big mul")
+            .input_output_types(vec![
+                (Type::Number, Type::String),
+                (Type::String, Type::String),
+            ])
+            .vectorizes_over_list(true)
+            .required(
+                "operand",
+                SyntaxShape::Any,
+                "the number to multiply by, as an int, float or string",
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Multiplies big numbers by a number, without losing precision."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["times", "multiplication", "decimal"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let operand: Value = call.req(engine_state, stack, 0)?;
+        binary_operation(engine_state, call, input, operand, |a, b, _| Ok(a * b))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Multiply two big ints",
+                example: r#""123456789012345678901234567890" | math big mul 1000"#,
+                result: Some(Value::test_string("123456789012345678901234567890000")),
+            },
+            Example {
+                description: "Multiply a price exactly",
+                example: r#""1.10" | math big mul 3"#,
+                result: Some(Value::test_string("3.3")),
+            },
+            Example {
+                description: "Multiply a float without its rounding error",
+                example: "0.1 | math big mul 3",
+                result: Some(Value::test_string("0.3")),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
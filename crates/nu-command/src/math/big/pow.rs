@@ -0,0 +1,129 @@
+use crate::math::big::utils::binary_operation;
+use bigdecimal::{BigDecimal, One, ToPrimitive, Zero};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+// Past this, the result takes more memory and time than anyone would wait for
+const MAX_EXPONENT: i64 = 1_000_000;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math big pow"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big pow")
+            .input_output_types(vec![
+                (Type::Number, Type::String),
+                (Type::String, Type::String),
+            ])
+            .vectorizes_over_list(true)
+            .required(
+                "exponent",
+                SyntaxShape::Any,
+                "the integer power to raise to",
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Raises big numbers to an integer power, without overflowing."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["power", "exponent", "bigint"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let exponent: Value = call.req(engine_state, stack, 0)?;
+        binary_operation(engine_state, call, input, exponent, power)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Raise 2 to the power of 100",
+                example: "2 | math big pow 100",
+                result: Some(Value::test_string("1267650600228229401496703205376")),
+            },
+            Example {
+                description: "Compound interest over 3 years, exactly",
+                example: r#""1.05" | math big pow 3"#,
+                result: Some(Value::test_string("1.157625")),
+            },
+            Example {
+                description: "Raise to a negative power",
+                example: "2 | math big pow -3",
+                result: Some(Value::test_string("0.125")),
+            },
+        ]
+    }
+}
+
+fn power(base: BigDecimal, exponent: BigDecimal, span: Span) -> Result<BigDecimal, ShellError> {
+    let exponent = Some(exponent)
+        .filter(BigDecimal::is_integer)
+        .and_then(|exponent| exponent.to_i64())
+        .filter(|exponent| exponent.abs() <= MAX_EXPONENT);
+    let exponent = match exponent {
+        Some(exponent) => exponent,
+        None => {
+            return Err(ShellError::UnsupportedInput(
+                format!("The exponent must be an integer from -{MAX_EXPONENT} to {MAX_EXPONENT}"),
+                "value originates from here".into(),
+                span,
+                span,
+            ))
+        }
+    };
+
+    if exponent < 0 && base.is_zero() {
+        return Err(ShellError::DivisionByZero(span));
+    }
+
+    // Exponentiation by squaring
+    let mut result = BigDecimal::one();
+    let mut square = base;
+    let mut remaining = exponent.unsigned_abs();
+    loop {
+        if remaining % 2 == 1 {
+            result = result * &square;
+        }
+        remaining /= 2;
+        if remaining == 0 {
+            break;
+        }
+        square = &square * &square;
+    }
+
+    Ok(if exponent < 0 {
+        BigDecimal::one() / result
+    } else {
+        result
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,77 @@
+use crate::math::big::utils::binary_operation;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math big sub"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big sub")
+            .input_output_types(vec![
+                (Type::Number, Type::String),
+                (Type::String, Type::String),
+            ])
+            .vectorizes_over_list(true)
+            .required(
+                "operand",
+                SyntaxShape::Any,
+                "the number to subtract, as an int, float or string",
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Subtracts a number from big numbers, without losing precision."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["minus", "subtraction", "decimal"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let operand: Value = call.req(engine_state, stack, 0)?;
+        binary_operation(engine_state, call, input, operand, |a, b, _| Ok(a - b))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Subtract two decimals exactly",
+                example: r#""1.00" | math big sub 0.99"#,
+                result: Some(Value::test_string("0.01")),
+            },
+            Example {
+                description: "Subtract from a float without its rounding error",
+                example: "0.3 | math big sub 0.1",
+                result: Some(Value::test_string("0.2")),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
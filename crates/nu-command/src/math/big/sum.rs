@@ -0,0 +1,66 @@
+use crate::math::big::utils::{from_big, to_big};
+use bigdecimal::{BigDecimal, Zero};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math big sum"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math big sum")
+            .input_output_types(vec![(Type::List(Box::new(Type::Any)), Type::String)])
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the sum of a list of big numbers, without losing precision."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["plus", "add", "total", "decimal"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let mut sum = BigDecimal::zero();
+        for value in input {
+            sum += to_big(&value, head)?;
+        }
+
+        Ok(from_big(sum, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Add up amounts without float rounding errors",
+            example: "[0.1 0.2 0.3] | math big sum",
+            result: Some(Value::test_string("0.6")),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
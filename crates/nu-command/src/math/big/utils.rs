@@ -0,0 +1,65 @@
+use bigdecimal::BigDecimal;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::EngineState;
+use nu_protocol::{PipelineData, ShellError, Span, Value};
+use std::str::FromStr;
+
+pub fn to_big(value: &Value, head: Span) -> Result<BigDecimal, ShellError> {
+    match value {
+        Value::Int { val, .. } => Ok(BigDecimal::from(*val)),
+        // Going through the shortest representation of the float, so 0.1 is 0.1 and not the
+        // 0.1000000000000000055511151231257827 it is stored as
+        Value::Float { val, span } => BigDecimal::from_str(&val.to_string()).map_err(|_| {
+            ShellError::CantConvert("big number".into(), val.to_string(), *span, None)
+        }),
+        Value::String { val, span } => BigDecimal::from_str(val.trim()).map_err(|_| {
+            ShellError::CantConvert(
+                "big number".into(),
+                "string".into(),
+                *span,
+                Some("Big numbers are written like 12, -3.5 or 1.2e30".into()),
+            )
+        }),
+        Value::Error { error } => Err(error.clone()),
+        other => Err(ShellError::OnlySupportsThisInputType(
+            "int, float or string".into(),
+            other.get_type().to_string(),
+            head,
+            other.expect_span(),
+        )),
+    }
+}
+
+pub fn from_big(value: BigDecimal, span: Span) -> Value {
+    Value::string(value.normalized().to_string(), span)
+}
+
+/// Applies an operation on every big number of the input, with the given operand
+pub fn binary_operation(
+    engine_state: &EngineState,
+    call: &Call,
+    input: PipelineData,
+    operand: Value,
+    operation: fn(BigDecimal, BigDecimal, Span) -> Result<BigDecimal, ShellError>,
+) -> Result<PipelineData, ShellError> {
+    let head = call.head;
+    let operand_span = operand.span()?;
+    let operand = to_big(&operand, head)?;
+
+    // This doesn't match explicit nulls
+    if matches!(input, PipelineData::Empty) {
+        return Err(ShellError::PipelineEmpty(head));
+    }
+    input.map(
+        move |value| {
+            let span = value.span().unwrap_or(head);
+            match to_big(&value, head)
+                .and_then(|value| operation(value, operand.clone(), operand_span))
+            {
+                Ok(result) => from_big(result, span),
+                Err(error) => Value::Error { error },
+            }
+        },
+        engine_state.ctrlc.clone(),
+    )
+}
@@ -0,0 +1,230 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+
+const DEFAULT_COUNT: i64 = 10;
+// More bins than this can't be charted anyway, and would only take memory
+const MAX_COUNT: usize = 100_000;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math bin"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math bin")
+            .input_output_types(vec![(
+                Type::List(Box::new(Type::Number)),
+                Type::Table(vec![]),
+            )])
+            .named(
+                "count",
+                SyntaxShape::Int,
+                "split the range of the values in this many bins of the same width (default 10)",
+                Some('c'),
+            )
+            .named(
+                "width",
+                SyntaxShape::Number,
+                "use bins of this width, starting at a multiple of it",
+                Some('w'),
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Assigns a list of numbers to bins, and returns the start, end and count of every bin"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"A bin holds the values from its start up to its end, excluded, except for the last bin,
+which also holds its end. Empty bins are kept, so the result can be charted as it is. There can be
+at most 100000 bins, and infinite or NaN values can't be put in bins."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["histogram", "bucket", "binning", "distribution"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let count: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "count")?;
+        let width: Option<Spanned<f64>> = call.get_flag(engine_state, stack, "width")?;
+
+        if let (Some(_), Some(width)) = (&count, &width) {
+            return Err(ShellError::IncompatibleParameters {
+                left_message: "can't use --width".into(),
+                left_span: width.span,
+                right_message: "together with --count".into(),
+                right_span: head,
+            });
+        }
+
+        let values = input
+            .into_iter()
+            .map(|value| match value {
+                Value::Int { .. } => value.as_float(),
+                Value::Float { val, span } if !val.is_finite() => {
+                    Err(ShellError::UnsupportedInput(
+                        "Only finite numbers can be put in bins".into(),
+                        "value originates from here".into(),
+                        head,
+                        span,
+                    ))
+                }
+                Value::Float { val, .. } => Ok(val),
+                Value::Error { error } => Err(error),
+                other => Err(ShellError::UnsupportedInput(
+                    "Only numbers can be put in bins".into(),
+                    "value originates from here".into(),
+                    head,
+                    other.expect_span(),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (min, max) = match values.iter().copied().reduce(f64::min) {
+            Some(min) => (min, values.iter().copied().fold(min, f64::max)),
+            None => {
+                return Err(ShellError::UnsupportedInput(
+                    "Empty input".into(),
+                    "value originates from here".into(),
+                    head,
+                    head,
+                ))
+            }
+        };
+
+        let (start, width, count) = match width {
+            Some(width) if width.item <= 0.0 || !width.item.is_finite() => {
+                return Err(ShellError::NeedsPositiveValue(width.span))
+            }
+            Some(width) => {
+                let start = (min / width.item).floor() * width.item;
+                let bins = ((max - start) / width.item).floor();
+                // NaN when the width is too small for the values
+                if bins.is_nan() || bins >= MAX_COUNT as f64 {
+                    return Err(too_many_bins(width.span));
+                }
+                (start, width.item, bins as usize + 1)
+            }
+            None => {
+                let count = match count {
+                    Some(count) if count.item <= 0 => {
+                        return Err(ShellError::NeedsPositiveValue(count.span))
+                    }
+                    Some(count) => match usize::try_from(count.item) {
+                        Ok(count) if count <= MAX_COUNT => count,
+                        _ => return Err(too_many_bins(count.span)),
+                    },
+                    None => DEFAULT_COUNT as usize,
+                };
+                // All the values are the same, so any width puts them in the first bin
+                let width = if max > min {
+                    (max - min) / count as f64
+                } else {
+                    1.0
+                };
+                (min, width, count)
+            }
+        };
+
+        Ok(bin(&values, start, width, count, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Split a list of numbers in 3 bins",
+                example: "[1 2 2 3 4] | math bin --count 3",
+                result: Some(Value::List {
+                    vals: vec![
+                        bin_record(1.0, 2.0, 1, Span::test_data()),
+                        bin_record(2.0, 3.0, 2, Span::test_data()),
+                        bin_record(3.0, 4.0, 2, Span::test_data()),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Split a list of numbers in bins 5 wide",
+                example: "[1 2 7 12] | math bin --width 5",
+                result: Some(Value::List {
+                    vals: vec![
+                        bin_record(0.0, 5.0, 2, Span::test_data()),
+                        bin_record(5.0, 10.0, 1, Span::test_data()),
+                        bin_record(10.0, 15.0, 1, Span::test_data()),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+fn bin(values: &[f64], start: f64, width: f64, count: usize, head: Span) -> Value {
+    let mut counts = vec![0; count];
+    for value in values {
+        // The maximum falls right on the end of the last bin
+        let index = (((value - start) / width).floor() as usize).min(count.saturating_sub(1));
+        counts[index] += 1;
+    }
+
+    let vals = counts
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| {
+            let bin_start = start + index as f64 * width;
+            bin_record(bin_start, bin_start + width, count, head)
+        })
+        .collect();
+
+    Value::List { vals, span: head }
+}
+
+fn too_many_bins(span: Span) -> ShellError {
+    ShellError::GenericError(
+        "Too many bins".into(),
+        format!("this makes more than {MAX_COUNT} bins"),
+        Some(span),
+        Some("use a smaller --count or a larger --width".into()),
+        vec![],
+    )
+}
+
+fn bin_record(start: f64, end: f64, count: i64, span: Span) -> Value {
+    Value::Record {
+        cols: vec!["start".into(), "end".into(), "count".into()],
+        vals: vec![
+            Value::float(start, span),
+            Value::float(end, span),
+            Value::int(count, span),
+        ],
+        span,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
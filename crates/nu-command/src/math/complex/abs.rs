@@ -0,0 +1,88 @@
+use crate::math::complex::utils::to_complex;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math complex abs"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math complex abs")
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::Record(vec![]), Type::Float),
+            ])
+            .vectorizes_over_list(true)
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the magnitude of a complex number."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["magnitude", "modulus", "absolute"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| operate(value, head),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the magnitude of a complex number",
+                example: "{re: 3, im: -4} | math complex abs",
+                result: Some(Value::test_float(5.0)),
+            },
+            Example {
+                description: "Get the magnitude of a real number",
+                example: "-3 | math complex abs",
+                result: Some(Value::test_float(3.0)),
+            },
+            Example {
+                description: "Get the magnitudes of the frequencies of a signal",
+                example: "[0 1 0 -1] | math fft | math complex abs",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn operate(value: Value, head: Span) -> Value {
+    match to_complex(&value, head) {
+        Ok((re, im)) => Value::float(re.hypot(im), value.expect_span()),
+        Err(error) => Value::Error { error },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,89 @@
+use crate::math::complex::utils::to_complex;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math complex arg"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math complex arg")
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::Record(vec![]), Type::Float),
+            ])
+            .vectorizes_over_list(true)
+            .switch("degrees", "Use degrees instead of radians", Some('d'))
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the argument of a complex number, its angle from the positive real axis."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["phase", "angle", "argument"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let use_degrees = call.has_flag("degrees");
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| operate(value, head, use_degrees),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the argument of i",
+                example: "{re: 0, im: 1} | math complex arg",
+                result: Some(Value::test_float(std::f64::consts::FRAC_PI_2)),
+            },
+            Example {
+                description: "Get the phase of a complex number in degrees",
+                example: "{re: 1, im: 1} | math complex arg --degrees | math round --precision 2",
+                result: Some(Value::test_float(45.0)),
+            },
+        ]
+    }
+}
+
+fn operate(value: Value, head: Span, use_degrees: bool) -> Value {
+    match to_complex(&value, head) {
+        Ok((re, im)) => {
+            let arg = im.atan2(re);
+            let arg = if use_degrees { arg.to_degrees() } else { arg };
+            Value::float(arg, value.expect_span())
+        }
+        Err(error) => Value::Error { error },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,57 @@
+use nu_engine::get_full_help;
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct MathComplex;
+
+impl Command for MathComplex {
+    fn name(&self) -> &str {
+        "math complex"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math complex")
+            .category(Category::Math)
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+    }
+
+    fn usage(&self) -> &str {
+        "Arithmetic on complex numbers."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"A complex number is a record with its real part in `re` and its imaginary part in `im`,
+like {re: 3, im: -4} or the rows `math fft` returns. Numbers are complex numbers without an
+imaginary part. The trigonometric and logarithmic math commands take complex numbers too.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["imaginary", "phasor", "impedance"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::String {
+            val: get_full_help(
+                &MathComplex.signature(),
+                &MathComplex.examples(),
+                engine_state,
+                stack,
+                self.is_parser_keyword(),
+            ),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::math::complex::utils::{complex_record, to_complex};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math complex conj"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math complex conj")
+            .input_output_types(vec![
+                (Type::Number, Type::Record(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
+            .vectorizes_over_list(true)
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the complex conjugate, with the sign of the imaginary part flipped."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["conjugate", "mirror"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| operate(value, head),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the conjugate of a complex number",
+                example: "{re: 1, im: 2} | math complex conj",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![Value::test_float(1.0), Value::test_float(-2.0)],
+                )),
+            },
+            Example {
+                description: "A real number is its own conjugate",
+                example: "2 | math complex conj",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![Value::test_float(2.0), Value::test_float(0.0)],
+                )),
+            },
+        ]
+    }
+}
+
+fn operate(value: Value, head: Span) -> Value {
+    match to_complex(&value, head) {
+        Ok((re, im)) => complex_record((re, -im), value.expect_span()),
+        Err(error) => Value::Error { error },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,98 @@
+use crate::math::complex::utils::{complex_record, exp, to_complex};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math complex exp"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math complex exp")
+            .input_output_types(vec![
+                (Type::Number, Type::Record(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
+            .vectorizes_over_list(true)
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns e raised to the power of a complex number."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["exponential", "euler", "phasor"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| operate(value, head),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Raise e to the power of zero",
+                example: "0 | math complex exp",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![Value::test_float(1.0), Value::test_float(0.0)],
+                )),
+            },
+            Example {
+                description: "Raise e to the power of a complex number with no imaginary part",
+                example: "{re: 1, im: 0} | math complex exp",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![
+                        Value::test_float(std::f64::consts::E),
+                        Value::test_float(0.0),
+                    ],
+                )),
+            },
+            Example {
+                description:
+                    "Get the phasor of a signal with amplitude 2 and a phase of 60 degrees",
+                example: "{re: (2 | math ln), im: ((math pi) / 3)} | math complex exp",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn operate(value: Value, head: Span) -> Value {
+    match to_complex(&value, head) {
+        Ok(z) => complex_record(exp(z), value.expect_span()),
+        Err(error) => Value::Error { error },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,14 @@
+mod abs;
+mod arg;
+mod complex_;
+mod conj;
+mod exp;
+mod mul;
+pub(super) mod utils;
+
+pub use abs::SubCommand as MathComplexAbs;
+pub use arg::SubCommand as MathComplexArg;
+pub use complex_::MathComplex;
+pub use conj::SubCommand as MathComplexConj;
+pub use exp::SubCommand as MathComplexExp;
+pub use mul::SubCommand as MathComplexMul;
@@ -0,0 +1,104 @@
+use crate::math::complex::utils::{complex_record, mul, to_complex, Complex};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math complex mul"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math complex mul")
+            .input_output_types(vec![
+                (Type::Number, Type::Record(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
+            .vectorizes_over_list(true)
+            .required(
+                "factor",
+                SyntaxShape::Any,
+                "the number or complex number to multiply by",
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Multiplies complex numbers."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["multiply", "times", "product"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let factor: Value = call.req(engine_state, stack, 0)?;
+        let factor = to_complex(&factor, head)?;
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| operate(value, head, factor),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Multiply two complex numbers",
+                example: "{re: 1, im: 2} | math complex mul {re: 3, im: -1}",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![Value::test_float(5.0), Value::test_float(5.0)],
+                )),
+            },
+            Example {
+                description: "Multiply a real number by i",
+                example: "2 | math complex mul {re: 0, im: 1}",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![Value::test_float(0.0), Value::test_float(2.0)],
+                )),
+            },
+            Example {
+                description: "Rotate a list of complex numbers by 90 degrees",
+                example: "[{re: 1, im: 0} {re: 0, im: 1}] | math complex mul {re: 0, im: 1}",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn operate(value: Value, head: Span, factor: Complex) -> Value {
+    match to_complex(&value, head) {
+        Ok(z) => complex_record(mul(z, factor), value.expect_span()),
+        Err(error) => Value::Error { error },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,127 @@
+use nu_protocol::{ShellError, Span, Value};
+
+/// A complex number, as its real and imaginary parts
+pub type Complex = (f64, f64);
+
+/// The complex number in a record with `re` and `im` columns, like the rows `math fft` returns
+pub fn as_complex(value: &Value) -> Option<Complex> {
+    if !matches!(value, Value::Record { .. }) {
+        return None;
+    }
+    let part = |column: &str| match value.get_data_by_key(column)? {
+        part @ (Value::Int { .. } | Value::Float { .. }) => part.as_float().ok(),
+        _ => None,
+    };
+    Some((part("re")?, part("im")?))
+}
+
+/// Reads a number or a complex number, where a number is a complex number without an imaginary
+/// part
+pub fn to_complex(value: &Value, head: Span) -> Result<Complex, ShellError> {
+    match value {
+        Value::Int { .. } | Value::Float { .. } => Ok((value.as_float()?, 0.0)),
+        Value::Error { error } => Err(error.clone()),
+        other => as_complex(other).ok_or_else(|| not_complex_error(other, head)),
+    }
+}
+
+pub fn complex_record((re, im): Complex, span: Span) -> Value {
+    Value::Record {
+        cols: vec!["re".into(), "im".into()],
+        vals: vec![Value::float(re, span), Value::float(im, span)],
+        span,
+    }
+}
+
+pub fn not_complex_error(value: &Value, head: Span) -> ShellError {
+    ShellError::OnlySupportsThisInputType(
+        "numeric or complex {re, im} record".into(),
+        value.get_type().to_string(),
+        head,
+        value.expect_span(),
+    )
+}
+
+/// Applies `f` to a complex number in a record, for the commands that also take real numbers
+pub fn operate_complex(value: Value, head: Span, f: impl Fn(Complex) -> Complex) -> Value {
+    match as_complex(&value) {
+        Some(z) => complex_record(f(z), value.expect_span()),
+        None => Value::Error {
+            error: not_complex_error(&value, head),
+        },
+    }
+}
+
+pub fn mul((a, b): Complex, (c, d): Complex) -> Complex {
+    (a * c - b * d, a * d + b * c)
+}
+
+pub fn div((a, b): Complex, (c, d): Complex) -> Complex {
+    let denominator = c * c + d * d;
+    ((a * c + b * d) / denominator, (b * c - a * d) / denominator)
+}
+
+pub fn exp((re, im): Complex) -> Complex {
+    let magnitude = re.exp();
+    (magnitude * im.cos(), magnitude * im.sin())
+}
+
+/// The principal value of the natural logarithm
+pub fn ln((re, im): Complex) -> Complex {
+    (re.hypot(im).ln(), im.atan2(re))
+}
+
+pub fn sin((re, im): Complex) -> Complex {
+    (re.sin() * im.cosh(), re.cos() * im.sinh())
+}
+
+pub fn cos((re, im): Complex) -> Complex {
+    (re.cos() * im.cosh(), -re.sin() * im.sinh())
+}
+
+pub fn tan(z: Complex) -> Complex {
+    div(sin(z), cos(z))
+}
+
+pub fn sinh((re, im): Complex) -> Complex {
+    (re.sinh() * im.cos(), re.cosh() * im.sin())
+}
+
+pub fn cosh((re, im): Complex) -> Complex {
+    (re.cosh() * im.cos(), re.sinh() * im.sin())
+}
+
+pub fn tanh(z: Complex) -> Complex {
+    div(sinh(z), cosh(z))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close(actual: Complex, expected: Complex) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-12 && (actual.1 - expected.1).abs() < 1e-12,
+            "{actual:?} is not {expected:?}"
+        );
+    }
+
+    #[test]
+    fn exp_undoes_ln() {
+        let z = (-1.5, 0.25);
+
+        assert_close(exp(ln(z)), z);
+    }
+
+    #[test]
+    fn identities_hold() {
+        let z = (0.3, -0.7);
+        let (s, c) = (sin(z), cos(z));
+
+        assert_close(
+            (mul(s, s).0 + mul(c, c).0, mul(s, s).1 + mul(c, c).1),
+            (1.0, 0.0),
+        );
+        assert_close(mul(tanh(z), cosh(z)), sinh(z));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::units::{convert_value, unit_named};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math convert"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math convert")
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::String, Type::Float),
+                (Type::Filesize, Type::Float),
+                (Type::Duration, Type::Float),
+            ])
+            .vectorizes_over_list(true)
+            .named(
+                "from",
+                SyntaxShape::String,
+                "the unit of the input numbers",
+                Some('f'),
+            )
+            .required_named(
+                "to",
+                SyntaxShape::String,
+                "the unit to convert to",
+                Some('t'),
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Converts numbers from one unit of measurement to another."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Strings like '1.5km' or '30 psi' carry their own unit, and file sizes and durations are
+converted from bytes and nanoseconds, so --from is only needed for bare numbers. Run `math units`
+to list the units."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["units", "measure", "metric", "imperial", "temperature"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let from: Option<Spanned<String>> = call.get_flag(engine_state, stack, "from")?;
+        let to: Spanned<String> = call
+            .get_flag(engine_state, stack, "to")?
+            .ok_or_else(|| ShellError::MissingParameter("--to".into(), head))?;
+
+        let from = match from {
+            Some(from) => Some(unit_named(&from)?),
+            None => None,
+        };
+        let to = unit_named(&to)?;
+
+        // This doesn't match explicit nulls
+        if matches!(input, PipelineData::Empty) {
+            return Err(ShellError::PipelineEmpty(head));
+        }
+        input.map(
+            move |value| convert_value(&value, from, to, head),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Convert tire pressures from psi to kPa",
+                example: "[32 35] | math convert --from psi --to kPa | math round --precision 1",
+                result: Some(Value::List {
+                    vals: vec![Value::test_float(220.6), Value::test_float(241.3)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Convert quantities with their own units",
+                example: "['1.5km' '300 m'] | math convert --to mi",
+                result: None,
+            },
+            Example {
+                description: "Convert a quantity with its own unit",
+                example: "'1.5km' | math convert --to m",
+                result: Some(Value::test_float(1500.0)),
+            },
+            Example {
+                description: "Convert a file size to bytes",
+                example: "1KiB | math convert --to B",
+                result: Some(Value::test_float(1024.0)),
+            },
+            Example {
+                description: "Convert a duration to hours",
+                example: "90min | math convert --to h",
+                result: Some(Value::test_float(1.5)),
+            },
+            Example {
+                description: "Convert a temperature",
+                example: "-40 | math convert --from C --to F | math round --precision 2",
+                result: Some(Value::test_float(-40.0)),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -1,3 +1,4 @@
+use crate::math::complex::utils::{self as complex, operate_complex};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
@@ -12,7 +13,11 @@ impl Command for SubCommand {
 
     fn signature(&self) -> Signature {
         Signature::build("math cosh")
-            .input_output_types(vec![(Type::Number, Type::Float)])
+            .switch("degrees", "Use degrees instead of radians", Some('d'))
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
             .vectorizes_over_list(true)
             .category(Category::Math)
     }
@@ -33,27 +38,42 @@ impl Command for SubCommand {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
+        let use_degrees = call.has_flag("degrees");
         // This doesn't match explicit nulls
         if matches!(input, PipelineData::Empty) {
             return Err(ShellError::PipelineEmpty(head));
         }
         input.map(
-            move |value| operate(value, head),
+            move |value| operate(value, head, use_degrees),
             engine_state.ctrlc.clone(),
         )
     }
 
     fn examples(&self) -> Vec<Example> {
         let e = std::f64::consts::E;
-        vec![Example {
-            description: "Apply the hyperbolic cosine to 1",
-            example: "1 | math cosh",
-            result: Some(Value::test_float(((e * e) + 1.0) / (2.0 * e))),
-        }]
+        vec![
+            Example {
+                description: "Apply the hyperbolic cosine to 1",
+                example: "1 | math cosh",
+                result: Some(Value::test_float(((e * e) + 1.0) / (2.0 * e))),
+            },
+            Example {
+                description:
+                    "Apply the hyperbolic cosine to the imaginary unit, as a complex number",
+                example: "{re: 0, im: 1} | math cosh",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![
+                        Value::test_float(0.5403023058681398),
+                        Value::test_float(0.0),
+                    ],
+                )),
+            },
+        ]
     }
 }
 
-fn operate(value: Value, head: Span) -> Value {
+fn operate(value: Value, head: Span, use_degrees: bool) -> Value {
     match value {
         numeric @ (Value::Int { .. } | Value::Float { .. }) => {
             let (val, span) = match numeric {
@@ -62,15 +82,25 @@ fn operate(value: Value, head: Span) -> Value {
                 _ => unreachable!(),
             };
 
+            let val = if use_degrees { val.to_radians() } else { val };
+
             Value::Float {
                 val: val.cosh(),
                 span,
             }
         }
+        record @ Value::Record { .. } => operate_complex(record, head, |(re, im)| {
+            let z = if use_degrees {
+                (re.to_radians(), im.to_radians())
+            } else {
+                (re, im)
+            };
+            complex::cosh(z)
+        }),
         Value::Error { .. } => value,
         other => Value::Error {
             error: ShellError::OnlySupportsThisInputType(
-                "numeric".into(),
+                "numeric or complex".into(),
                 other.get_type().to_string(),
                 head,
                 other.expect_span(),
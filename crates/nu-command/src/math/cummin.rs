@@ -0,0 +1,95 @@
+use crate::math::cumulative::run_cumulative;
+use crate::math::reducers::Reduce;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math cummin"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math cummin")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::Any)),
+                    Type::List(Box::new(Type::Any)),
+                ),
+                (Type::Table(vec![]), Type::Table(vec![])),
+            ])
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the running minimum of a list of values, or of each column in a table"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cumulative", "running", "minimum", "lowest"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        run_cumulative(engine_state, call, input, Reduce::Minimum)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the lowest value so far of a list of numbers",
+                example: "[3 5 1 4] | math cummin",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(3),
+                        Value::test_int(3),
+                        Value::test_int(1),
+                        Value::test_int(1),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Get the lowest values so far of the numeric columns of a table",
+                example: "[[day low]; [mon 5] [tue 7] [wed 2]] | math cummin",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["day", "low"],
+                            vec![Value::test_string("mon"), Value::test_int(5)],
+                        ),
+                        Value::test_record(
+                            vec!["day", "low"],
+                            vec![Value::test_string("tue"), Value::test_int(5)],
+                        ),
+                        Value::test_record(
+                            vec!["day", "low"],
+                            vec![Value::test_string("wed"), Value::test_int(2)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
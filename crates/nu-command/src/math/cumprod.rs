@@ -0,0 +1,91 @@
+use crate::math::cumulative::run_cumulative;
+use crate::math::reducers::Reduce;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math cumprod"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math cumprod")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::Number)),
+                    Type::List(Box::new(Type::Number)),
+                ),
+                (Type::Table(vec![]), Type::Table(vec![])),
+            ])
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the running product of a list of numbers, or of each column in a table"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cumulative", "running", "product", "accumulate"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        run_cumulative(engine_state, call, input, Reduce::Product)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the running product of a list of numbers",
+                example: "[1 2 3 4] | math cumprod",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(1),
+                        Value::test_int(2),
+                        Value::test_int(6),
+                        Value::test_int(24),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Get the running product of every column of a table",
+                example: "[[a b]; [1 2] [3 4]] | math cumprod",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["a", "b"],
+                            vec![Value::test_int(1), Value::test_int(2)],
+                        ),
+                        Value::test_record(
+                            vec!["a", "b"],
+                            vec![Value::test_int(3), Value::test_int(8)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,81 @@
+use crate::math::cumulative::run_cumulative;
+use crate::math::reducers::Reduce;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math cumsum"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math cumsum")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::Number)),
+                    Type::List(Box::new(Type::Number)),
+                ),
+                (Type::Table(vec![]), Type::Table(vec![])),
+            ])
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the running sum of a list of numbers, or of each column in a table"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["cumulative", "running", "total", "accumulate"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        run_cumulative(engine_state, call, input, Reduce::Summation)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the running total of a list of numbers",
+                example: "[1 2 3 4] | math cumsum",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(1), Value::test_int(3), Value::test_int(6), Value::test_int(10)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Get the running total of the numeric columns of a table, keeping the other columns",
+                example: "[[day sales]; [mon 3] [tue 5] [wed 2]] | math cumsum",
+                result: Some(Value::List {
+                    vals: vec![
+Value::test_record(vec!["day", "sales"], vec![Value::test_string("mon"), Value::test_int(3)]),
+Value::test_record(vec!["day", "sales"], vec![Value::test_string("tue"), Value::test_int(8)]),
+Value::test_record(vec!["day", "sales"], vec![Value::test_string("wed"), Value::test_int(10)]),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,93 @@
+use crate::math::reducers::{reducer_for, Reduce, ReducerFunction};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::EngineState;
+use nu_protocol::{IntoInterruptiblePipelineData, PipelineData, ShellError, Span, Value};
+use std::collections::HashMap;
+
+/// Maps every value of the input to the aggregate of the values up to and including it. In a
+/// table, every numeric column is aggregated on its own, and the other columns are kept as they
+/// are, so the table keeps its shape. Streams are aggregated as they come.
+pub fn run_cumulative(
+    engine_state: &EngineState,
+    call: &Call,
+    input: PipelineData,
+    reduce: Reduce,
+) -> Result<PipelineData, ShellError> {
+    let head = call.head;
+    match &input {
+        PipelineData::Value(Value::List { .. }, ..)
+        | PipelineData::Value(Value::Range { .. }, ..)
+        | PipelineData::ListStream(..) => {}
+        PipelineData::Empty => return Err(ShellError::PipelineEmpty(head)),
+        PipelineData::Value(other, ..) => {
+            return Err(ShellError::OnlySupportsThisInputType(
+                "list, table or range".into(),
+                other.get_type().to_string(),
+                head,
+                other.expect_span(),
+            ))
+        }
+        PipelineData::ExternalStream { span, .. } => {
+            return Err(ShellError::OnlySupportsThisInputType(
+                "list, table or range".into(),
+                "raw data".into(),
+                head,
+                *span,
+            ))
+        }
+    }
+
+    let reducer = reducer_for(reduce);
+    let mut total: Option<Value> = None;
+    let mut column_totals: HashMap<String, Value> = HashMap::new();
+
+    Ok(input
+        .into_iter()
+        .map(move |value| {
+            let result = match value {
+                Value::Record { cols, vals, span } => cols
+                    .iter()
+                    .zip(vals)
+                    .map(|(col, val)| {
+                        if is_numeric(&val) {
+                            let aggregate =
+                                accumulate(column_totals.remove(col), val, &reducer, head)?;
+                            column_totals.insert(col.clone(), aggregate.clone());
+                            Ok(aggregate)
+                        } else {
+                            Ok(val)
+                        }
+                    })
+                    .collect::<Result<Vec<Value>, ShellError>>()
+                    .map(|vals| Value::Record { cols, vals, span }),
+                value => accumulate(total.take(), value, &reducer, head).map(|aggregate| {
+                    total = Some(aggregate.clone());
+                    aggregate
+                }),
+            };
+
+            result.unwrap_or_else(|error| Value::Error { error })
+        })
+        .into_pipeline_data(engine_state.ctrlc.clone()))
+}
+
+fn accumulate(
+    total: Option<Value>,
+    value: Value,
+    reducer: &ReducerFunction,
+    head: Span,
+) -> Result<Value, ShellError> {
+    let span = value.span().unwrap_or(head);
+    let values = match total {
+        Some(total) => vec![total, value],
+        None => vec![value],
+    };
+    reducer(Value::nothing(head), values, span, head)
+}
+
+fn is_numeric(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Int { .. } | Value::Float { .. } | Value::Filesize { .. } | Value::Duration { .. }
+    )
+}
@@ -0,0 +1,354 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+// Parts smaller than this, relative to the largest part, are rounding errors and reported as zero
+const EPSILON: f64 = 1e-12;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math fft"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math fft")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::Table(vec![])),
+                (Type::List(Box::new(Type::Any)), Type::Table(vec![])),
+            ])
+            .named(
+                "sample-rate",
+                SyntaxShape::Number,
+                "the samples per second of the input, to add the frequency of every bin",
+                Some('s'),
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the discrete Fourier transform of a list of samples, with the fast Fourier transform."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The samples are numbers, or complex numbers written as [re im] pairs or {re: .., im: ..}
+records. Every row of the result is a frequency bin, with the complex value in `re` and `im`, and
+its `magnitude` and `phase`, in radians. Bin k is the frequency k / n of the sample rate, so only
+the first half of the bins is meaningful for real samples.
+
+Lists with a length that is a power of two are transformed fastest."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["fourier", "spectrum", "frequency", "signal", "dft"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let sample_rate: Option<Spanned<f64>> =
+            call.get_flag(engine_state, stack, "sample-rate")?;
+        if let Some(rate) = &sample_rate {
+            if rate.item <= 0.0 {
+                return Err(ShellError::NeedsPositiveValue(rate.span));
+            }
+        }
+
+        let mut data = to_complex_list(input, head)?;
+        transform(&mut data, false);
+
+        let n = data.len() as f64;
+        let vals = data
+            .into_iter()
+            .enumerate()
+            .map(|(k, value)| {
+                let frequency = sample_rate.as_ref().map(|rate| k as f64 * rate.item / n);
+                spectrum_record(value, frequency, head)
+            })
+            .collect();
+        Ok(Value::List { vals, span: head }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let bin = |re, im, magnitude, phase| {
+            Value::test_record(
+                vec!["re", "im", "magnitude", "phase"],
+                vec![
+                    Value::test_float(re),
+                    Value::test_float(im),
+                    Value::test_float(magnitude),
+                    Value::test_float(phase),
+                ],
+            )
+        };
+
+        vec![
+            Example {
+                description: "Transform a sine wave sampled 4 times a cycle",
+                example: "[0 1 0 -1] | math fft",
+                result: Some(Value::List {
+                    vals: vec![
+                        bin(0.0, 0.0, 0.0, 0.0),
+                        bin(0.0, -2.0, 2.0, -FRAC_PI_2),
+                        bin(0.0, 0.0, 0.0, 0.0),
+                        bin(0.0, 2.0, 2.0, FRAC_PI_2),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Transform complex samples given as [re im] pairs",
+                example: "[[1 1] [1 -1]] | math fft",
+                result: Some(Value::List {
+                    vals: vec![bin(2.0, 0.0, 2.0, 0.0), bin(0.0, 2.0, 2.0, FRAC_PI_2)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Transform a signal sampled at 8 Hz",
+                example: "[1 0] | math fft --sample-rate 8",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["frequency", "re", "im", "magnitude", "phase"],
+                            vec![
+                                Value::test_float(0.0),
+                                Value::test_float(1.0),
+                                Value::test_float(0.0),
+                                Value::test_float(1.0),
+                                Value::test_float(0.0),
+                            ],
+                        ),
+                        Value::test_record(
+                            vec!["frequency", "re", "im", "magnitude", "phase"],
+                            vec![
+                                Value::test_float(4.0),
+                                Value::test_float(1.0),
+                                Value::test_float(0.0),
+                                Value::test_float(1.0),
+                                Value::test_float(0.0),
+                            ],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// Reads the samples of the input: numbers, [re im] pairs or records with `re` and `im` columns
+pub fn to_complex_list(input: PipelineData, head: Span) -> Result<Vec<(f64, f64)>, ShellError> {
+    let data = input
+        .into_iter()
+        .map(|value| to_complex(&value, head))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if data.is_empty() {
+        Err(ShellError::UnsupportedInput(
+            "Empty input".into(),
+            "value originates from here".into(),
+            head,
+            head,
+        ))
+    } else {
+        Ok(data)
+    }
+}
+
+fn to_complex(value: &Value, head: Span) -> Result<(f64, f64), ShellError> {
+    let part = |value: &Value| match value {
+        Value::Int { .. } | Value::Float { .. } => value.as_float(),
+        Value::Error { error } => Err(error.clone()),
+        other => Err(ShellError::UnsupportedInput(
+            format!(
+                "Parts of complex numbers must be numbers, found {}",
+                other.get_type()
+            ),
+            "value originates from here".into(),
+            head,
+            other.expect_span(),
+        )),
+    };
+
+    match value {
+        Value::Int { .. } | Value::Float { .. } => Ok((value.as_float()?, 0.0)),
+        Value::List { vals, .. } if vals.len() == 2 => Ok((part(&vals[0])?, part(&vals[1])?)),
+        Value::Record { .. } => {
+            let re = value.get_data_by_key("re");
+            let im = value.get_data_by_key("im");
+            match (re, im) {
+                (Some(re), Some(im)) => Ok((part(&re)?, part(&im)?)),
+                _ => Err(ShellError::UnsupportedInput(
+                    "Complex numbers as records must have `re` and `im` columns".into(),
+                    "value originates from here".into(),
+                    head,
+                    value.expect_span(),
+                )),
+            }
+        }
+        Value::Error { error } => Err(error.clone()),
+        other => Err(ShellError::UnsupportedInput(
+            "Samples must be numbers, [re im] pairs or {re, im} records".into(),
+            "value originates from here".into(),
+            head,
+            other.expect_span(),
+        )),
+    }
+}
+
+/// Replaces `data` with its discrete Fourier transform, or its inverse. Rounding errors are
+/// cleared, so a part that should be zero is zero.
+pub fn transform(data: &mut [(f64, f64)], inverse: bool) {
+    if data.len().is_power_of_two() {
+        radix2(data, inverse);
+    } else {
+        let result = dft(data, inverse);
+        data.copy_from_slice(&result);
+    }
+
+    if inverse {
+        let n = data.len() as f64;
+        for (re, im) in data.iter_mut() {
+            *re /= n;
+            *im /= n;
+        }
+    }
+
+    let scale = data
+        .iter()
+        .fold(0.0_f64, |max, (re, im)| max.max(re.abs()).max(im.abs()));
+    for (re, im) in data.iter_mut() {
+        if re.abs() <= scale * EPSILON {
+            *re = 0.0;
+        }
+        if im.abs() <= scale * EPSILON {
+            *im = 0.0;
+        }
+    }
+}
+
+/// The iterative Cooley-Tukey FFT, for lengths that are a power of two
+fn radix2(data: &mut [(f64, f64)], inverse: bool) {
+    let n = data.len();
+
+    // Put the samples in bit-reversed order, so every pass combines neighbouring blocks
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_re, w_im) = ((angle * k as f64).cos(), (angle * k as f64).sin());
+                let (u_re, u_im) = data[start + k];
+                let (x_re, x_im) = data[start + k + len / 2];
+                let (v_re, v_im) = (x_re * w_re - x_im * w_im, x_re * w_im + x_im * w_re);
+                data[start + k] = (u_re + v_re, u_im + v_im);
+                data[start + k + len / 2] = (u_re - v_re, u_im - v_im);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// The transform by its definition, for the lengths the FFT doesn't handle
+fn dft(data: &[(f64, f64)], inverse: bool) -> Vec<(f64, f64)> {
+    let n = data.len();
+    let sign = if inverse { 1.0 } else { -1.0 };
+    (0..n)
+        .map(|k| {
+            data.iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(sum_re, sum_im), (t, (re, im))| {
+                    // Reducing k * t modulo n keeps the angle small and precise
+                    let angle = sign * 2.0 * PI * ((k * t) % n) as f64 / n as f64;
+                    let (w_re, w_im) = (angle.cos(), angle.sin());
+                    (
+                        sum_re + re * w_re - im * w_im,
+                        sum_im + re * w_im + im * w_re,
+                    )
+                })
+        })
+        .collect()
+}
+
+pub fn spectrum_record((re, im): (f64, f64), frequency: Option<f64>, span: Span) -> Value {
+    let mut cols = vec![];
+    let mut vals = vec![];
+    if let Some(frequency) = frequency {
+        cols.push("frequency".into());
+        vals.push(Value::float(frequency, span));
+    }
+    cols.extend(["re".into(), "im".into(), "magnitude".into(), "phase".into()]);
+    vals.extend([
+        Value::float(re, span),
+        Value::float(im, span),
+        Value::float(re.hypot(im), span),
+        Value::float(im.atan2(re), span),
+    ]);
+
+    Value::Record { cols, vals, span }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn fft_matches_dft() {
+        let data: Vec<(f64, f64)> = (0..8).map(|i| ((i * i) as f64, i as f64)).collect();
+
+        let mut fast = data.clone();
+        transform(&mut fast, false);
+        let slow = dft(&data, false);
+
+        for ((a_re, a_im), (b_re, b_im)) in fast.iter().zip(&slow) {
+            assert!((a_re - b_re).abs() < 1e-9 && (a_im - b_im).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn inverse_undoes_the_transform() {
+        let data = vec![(1.0, 0.0), (2.0, 0.0), (3.0, 0.0), (4.0, 0.0), (5.0, 0.0)];
+
+        let mut result = data.clone();
+        transform(&mut result, false);
+        transform(&mut result, true);
+
+        for ((a_re, a_im), (b_re, b_im)) in result.iter().zip(&data) {
+            assert!((a_re - b_re).abs() < 1e-9 && (a_im - b_im).abs() < 1e-9);
+        }
+    }
+}
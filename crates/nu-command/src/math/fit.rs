@@ -0,0 +1,214 @@
+use super::interpolate::to_points;
+use super::matrix::utils::{gauss_jordan, multiply, transpose};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math fit"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math fit")
+            .input_output_types(vec![(Type::Table(vec![]), Type::Record(vec![]))])
+            .named(
+                "degree",
+                SyntaxShape::Int,
+                "the degree of the polynomial (default 1, a straight line)",
+                Some('d'),
+            )
+            .named(
+                "x",
+                SyntaxShape::String,
+                "the column with the x values (default x)",
+                None,
+            )
+            .named(
+                "y",
+                SyntaxShape::String,
+                "the column with the y values (default y)",
+                None,
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Fits a polynomial to a table of points by least squares."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The result has the `coefficients` of the polynomial, the constant first, so coefficient i
+multiplies x to the power of i. Its `r_squared` tells how much of the variation of y the polynomial
+explains, from 0 to 1 for a perfect fit. There must be more points than the degree."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "regression",
+            "polynomial",
+            "least squares",
+            "trend",
+            "linear",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let degree: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "degree")?;
+        let x_column: Option<String> = call.get_flag(engine_state, stack, "x")?;
+        let y_column: Option<String> = call.get_flag(engine_state, stack, "y")?;
+        let x_column = x_column.unwrap_or_else(|| "x".into());
+        let y_column = y_column.unwrap_or_else(|| "y".into());
+
+        let (degree, degree_span) = match degree {
+            Some(degree) if degree.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(degree.span))
+            }
+            Some(degree) => (degree.item as usize, degree.span),
+            None => (1, head),
+        };
+
+        let points = to_points(input, &x_column, &y_column, head)?;
+        if points.len() <= degree {
+            return Err(ShellError::UnsupportedInput(
+                format!(
+                    "a polynomial of degree {degree} needs at least {} points, got {}",
+                    degree + 1,
+                    points.len()
+                ),
+                "value originates from here".into(),
+                degree_span,
+                head,
+            ));
+        }
+
+        let coefficients = polynomial_fit(&points, degree).ok_or_else(|| {
+            ShellError::GenericError(
+                "Can't fit the polynomial".into(),
+                "there are too few distinct x values".into(),
+                Some(head),
+                Some(format!(
+                    "A polynomial of degree {degree} needs at least {} distinct x values",
+                    degree + 1
+                )),
+                vec![],
+            )
+        })?;
+        let r_squared = r_squared(&points, &coefficients);
+
+        Ok(Value::Record {
+            cols: vec!["coefficients".into(), "r_squared".into()],
+            vals: vec![
+                Value::List {
+                    vals: coefficients
+                        .into_iter()
+                        .map(|coefficient| Value::float(coefficient, head))
+                        .collect(),
+                    span: head,
+                },
+                Value::float(r_squared, head),
+            ],
+            span: head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Fit a line to points, which are on y = 1 + 2x",
+                example: "[[x y]; [0 1] [1 3] [2 5]] | math fit",
+                result: Some(Value::Record {
+                    cols: vec!["coefficients".into(), "r_squared".into()],
+                    vals: vec![
+                        Value::List {
+                            vals: vec![Value::test_float(1.0), Value::test_float(2.0)],
+                            span: Span::test_data(),
+                        },
+                        Value::test_float(1.0),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Find how the run time of a benchmark grows with the input size",
+                example: "open bench.csv | math fit --degree 2 --x size --y ms",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// The coefficients of the least squares polynomial, the constant first, from the normal
+/// equations. `None` when there are too few distinct x values to fix them.
+fn polynomial_fit(points: &[(f64, f64)], degree: usize) -> Option<Vec<f64>> {
+    let vandermonde: Vec<Vec<f64>> = points
+        .iter()
+        .map(|(x, _)| (0..=degree).map(|power| x.powi(power as i32)).collect())
+        .collect();
+    let ys: Vec<Vec<f64>> = points.iter().map(|(_, y)| vec![*y]).collect();
+
+    let transposed = transpose(&vandermonde);
+    let solution = gauss_jordan(
+        multiply(&transposed, &vandermonde),
+        multiply(&transposed, &ys),
+    )?;
+    Some(solution.into_iter().map(|row| row[0]).collect())
+}
+
+fn r_squared(points: &[(f64, f64)], coefficients: &[f64]) -> f64 {
+    let mean = points.iter().map(|(_, y)| y).sum::<f64>() / points.len() as f64;
+    let (residual, total) = points.iter().fold((0.0, 0.0), |(residual, total), (x, y)| {
+        let predicted = coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c);
+        (
+            residual + (y - predicted).powi(2),
+            total + (y - mean).powi(2),
+        )
+    });
+
+    // A constant y is explained perfectly by any fit that goes through it
+    if total == 0.0 {
+        1.0
+    } else {
+        1.0 - residual / total
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn fits_a_parabola() {
+        let points: Vec<(f64, f64)> = (-3..=3)
+            .map(|x| (x as f64, 2.0 - x as f64 + 0.5 * (x * x) as f64))
+            .collect();
+        let coefficients = polynomial_fit(&points, 2).unwrap();
+
+        for (actual, expected) in coefficients.iter().zip([2.0, -1.0, 0.5]) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+        assert!((r_squared(&points, &coefficients) - 1.0).abs() < 1e-9);
+    }
+}
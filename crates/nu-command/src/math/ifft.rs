@@ -0,0 +1,115 @@
+use crate::math::fft::{spectrum_record, to_complex_list, transform};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math ifft"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math ifft")
+            .input_output_types(vec![
+                (Type::Table(vec![]), Type::List(Box::new(Type::Any))),
+                (
+                    Type::List(Box::new(Type::Any)),
+                    Type::List(Box::new(Type::Any)),
+                ),
+            ])
+            .switch(
+                "real",
+                "return only the real part of every sample, as a list of numbers",
+                Some('r'),
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the inverse discrete Fourier transform of a spectrum, back to samples."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The spectrum is the output of `math fft`, or any list of numbers, [re im] pairs or
+{re: .., im: ..} records. The samples have the same columns as the output of `math fft`. A spectrum
+transformed from real samples gives them back with --real."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["fourier", "inverse", "spectrum", "signal", "idft"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let real = call.has_flag("real");
+
+        let mut data = to_complex_list(input, head)?;
+        transform(&mut data, true);
+
+        let vals = data
+            .into_iter()
+            .map(|value| {
+                if real {
+                    Value::float(value.0, head)
+                } else {
+                    spectrum_record(value, None, head)
+                }
+            })
+            .collect();
+        Ok(Value::List { vals, span: head }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get back the real samples of a spectrum",
+                example: "[[re im]; [10 0] [-2 2] [-2 0] [-2 -2]] | math ifft --real",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_float(1.0),
+                        Value::test_float(2.0),
+                        Value::test_float(3.0),
+                        Value::test_float(4.0),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Transform a spectrum given as [re im] pairs",
+                example: "[[4 0] [0 0] [0 0] [0 0]] | math ifft --real",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_float(1.0),
+                        Value::test_float(1.0),
+                        Value::test_float(1.0),
+                        Value::test_float(1.0),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,308 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use std::cmp::Ordering;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math interpolate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math interpolate")
+            .input_output_types(vec![(Type::Table(vec![]), Type::Table(vec![]))])
+            .required(
+                "grid",
+                SyntaxShape::Any,
+                "the x values to interpolate at, as a list or a range",
+            )
+            .named(
+                "method",
+                SyntaxShape::String,
+                "how to connect the points: linear (default) or spline, a natural cubic spline",
+                Some('m'),
+            )
+            .named(
+                "x",
+                SyntaxShape::String,
+                "the column with the x values (default x)",
+                None,
+            )
+            .named(
+                "y",
+                SyntaxShape::String,
+                "the column with the y values (default y)",
+                None,
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Resamples the y values of a table of points onto new x values."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The points don't need to be sorted, but their x values must be distinct. An x value outside
+of the points gets an empty y, as there is nothing to interpolate between."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["resample", "spline", "lerp", "linear", "grid"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let grid: Value = call.req(engine_state, stack, 0)?;
+        let method: Option<Spanned<String>> = call.get_flag(engine_state, stack, "method")?;
+        let x_column: Option<String> = call.get_flag(engine_state, stack, "x")?;
+        let y_column: Option<String> = call.get_flag(engine_state, stack, "y")?;
+        let x_column = x_column.unwrap_or_else(|| "x".into());
+        let y_column = y_column.unwrap_or_else(|| "y".into());
+
+        let spline = match method {
+            None => false,
+            Some(method) => match method.item.as_str() {
+                "linear" => false,
+                "spline" => true,
+                _ => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "linear or spline".into(),
+                        method.item,
+                        method.span,
+                    ))
+                }
+            },
+        };
+
+        let points = to_points(input, &x_column, &y_column, head)?;
+        if let Some(pair) = points.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(ShellError::UnsupportedInput(
+                format!(
+                    "the x values must be distinct, but {} appears twice",
+                    pair[0].0
+                ),
+                "value originates from here".into(),
+                head,
+                head,
+            ));
+        }
+        let second_derivatives = if spline {
+            Some(spline_second_derivatives(&points))
+        } else {
+            None
+        };
+
+        let vals = grid_values(grid, head)?
+            .into_iter()
+            .map(|x| {
+                let y = match x.as_float() {
+                    Ok(at) => evaluate(&points, second_derivatives.as_deref(), at)
+                        .map(|y| Value::float(y, head))
+                        .unwrap_or_else(|| Value::nothing(head)),
+                    Err(error) => Value::Error { error },
+                };
+                Value::Record {
+                    cols: vec![x_column.clone(), y_column.clone()],
+                    vals: vec![x, y],
+                    span: head,
+                }
+            })
+            .collect();
+        Ok(Value::List { vals, span: head }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Fill in the points between two measurements",
+                example: "[[x y]; [0 0] [2 4]] | math interpolate [0 1 2]",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["x", "y"],
+                            vec![Value::test_int(0), Value::test_float(0.0)],
+                        ),
+                        Value::test_record(
+                            vec!["x", "y"],
+                            vec![Value::test_int(1), Value::test_float(2.0)],
+                        ),
+                        Value::test_record(
+                            vec!["x", "y"],
+                            vec![Value::test_int(2), Value::test_float(4.0)],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Resample benchmark timings onto every 10 threads with a smooth curve",
+                example: "open bench.csv | math interpolate 10..10..100 --x threads --y ms --method spline",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// Reads the points of a table with numeric x and y columns, sorted by x
+pub fn to_points(
+    input: PipelineData,
+    x_column: &str,
+    y_column: &str,
+    head: Span,
+) -> Result<Vec<(f64, f64)>, ShellError> {
+    let mut points = vec![];
+    for row in input.into_iter() {
+        if let Value::Error { error } = row {
+            return Err(error);
+        }
+        let get = |column: &str| match row.get_data_by_key(column) {
+            Some(value @ Value::Int { .. }) | Some(value @ Value::Float { .. }) => value.as_float(),
+            Some(Value::Error { error }) => Err(error),
+            Some(other) => Err(ShellError::UnsupportedInput(
+                format!(
+                    "the {column} column must hold numbers, found {}",
+                    other.get_type()
+                ),
+                "value originates from here".into(),
+                head,
+                other.expect_span(),
+            )),
+            None => Err(ShellError::CantFindColumn(
+                column.to_string(),
+                head,
+                row.span().unwrap_or(head),
+            )),
+        };
+        points.push((get(x_column)?, get(y_column)?));
+    }
+
+    if points.len() < 2 {
+        return Err(ShellError::UnsupportedInput(
+            "at least two points are needed".into(),
+            "value originates from here".into(),
+            head,
+            head,
+        ));
+    }
+    points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    Ok(points)
+}
+
+fn grid_values(grid: Value, head: Span) -> Result<Vec<Value>, ShellError> {
+    match grid {
+        Value::List { vals, .. } => Ok(vals),
+        Value::Range { val, .. } => Ok(val.into_range_iter(None)?.collect()),
+        Value::Int { .. } | Value::Float { .. } => Ok(vec![grid]),
+        Value::Error { error } => Err(error),
+        other => Err(ShellError::TypeMismatch(
+            format!("a list or a range of numbers, found {}", other.get_type()),
+            other.span().unwrap_or(head),
+        )),
+    }
+}
+
+/// The second derivatives of the natural cubic spline through the points, which are zero at both
+/// ends. They are solved from a tridiagonal system with the Thomas algorithm.
+fn spline_second_derivatives(points: &[(f64, f64)]) -> Vec<f64> {
+    let n = points.len();
+    let mut second = vec![0.0; n];
+    if n < 3 {
+        return second;
+    }
+
+    let widths: Vec<f64> = points
+        .windows(2)
+        .map(|pair| pair[1].0 - pair[0].0)
+        .collect();
+    let slopes: Vec<f64> = points
+        .windows(2)
+        .zip(&widths)
+        .map(|(pair, width)| (pair[1].1 - pair[0].1) / width)
+        .collect();
+
+    let mut upper = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        let diagonal = 2.0 * (widths[i - 1] + widths[i]) - widths[i - 1] * upper[i - 1];
+        upper[i] = widths[i] / diagonal;
+        rhs[i] = (6.0 * (slopes[i] - slopes[i - 1]) - widths[i - 1] * rhs[i - 1]) / diagonal;
+    }
+    for i in (1..n - 1).rev() {
+        second[i] = rhs[i] - upper[i] * second[i + 1];
+    }
+    second
+}
+
+/// The y value at `x`, on the line between the points around it, or on the spline when its second
+/// derivatives are given. `None` outside of the points.
+fn evaluate(points: &[(f64, f64)], second_derivatives: Option<&[f64]>, x: f64) -> Option<f64> {
+    let (first, last) = (points.first()?.0, points.last()?.0);
+    if x.is_nan() || x < first || x > last {
+        return None;
+    }
+
+    let i =
+        match points.binary_search_by(|point| point.0.partial_cmp(&x).unwrap_or(Ordering::Equal)) {
+            Ok(i) => return Some(points[i].1),
+            Err(i) => i - 1,
+        };
+    let ((x0, y0), (x1, y1)) = (points[i], points[i + 1]);
+    let width = x1 - x0;
+    let a = (x1 - x) / width;
+    let b = (x - x0) / width;
+
+    Some(match second_derivatives {
+        None => a * y0 + b * y1,
+        Some(second) => {
+            a * y0
+                + b * y1
+                + ((a * a * a - a) * second[i] + (b * b * b - b) * second[i + 1]) * width * width
+                    / 6.0
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn spline_goes_through_the_points() {
+        let points = vec![(0.0, 1.0), (1.0, 3.0), (2.5, 2.0), (4.0, 5.0)];
+        let second = spline_second_derivatives(&points);
+
+        for (x, y) in &points {
+            assert!((evaluate(&points, Some(&second), *x).unwrap() - y).abs() < 1e-12);
+        }
+        assert_eq!(second[0], 0.0);
+        assert_eq!(second[3], 0.0);
+    }
+
+    #[test]
+    fn spline_of_a_line_is_the_line() {
+        let points = vec![(0.0, 0.0), (1.0, 2.0), (3.0, 6.0)];
+        let second = spline_second_derivatives(&points);
+
+        assert!((evaluate(&points, Some(&second), 2.0).unwrap() - 4.0).abs() < 1e-12);
+    }
+}
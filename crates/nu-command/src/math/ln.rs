@@ -1,3 +1,4 @@
+use crate::math::complex::utils::{self as complex, operate_complex};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
@@ -12,7 +13,10 @@ impl Command for SubCommand {
 
     fn signature(&self) -> Signature {
         Signature::build("math ln")
-            .input_output_types(vec![(Type::Number, Type::Float)])
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
             .vectorizes_over_list(true)
             .category(Category::Math)
     }
@@ -44,11 +48,24 @@ impl Command for SubCommand {
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Get the natural logarithm of e",
-            example: "math e | math ln",
-            result: Some(Value::test_float(1.0f64)),
-        }]
+        vec![
+            Example {
+                description: "Get the natural logarithm of e",
+                example: "math e | math ln",
+                result: Some(Value::test_float(1.0f64)),
+            },
+            Example {
+                description: "Get the natural logarithm of a negative number, as a complex number",
+                example: "{re: -1, im: 0} | math ln",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![
+                        Value::test_float(0.0),
+                        Value::test_float(std::f64::consts::PI),
+                    ],
+                )),
+            },
+        ]
     }
 }
 
@@ -76,10 +93,23 @@ fn operate(value: Value, head: Span) -> Value {
                 }
             }
         }
+        record @ Value::Record { .. } => {
+            if complex::as_complex(&record) == Some((0.0, 0.0)) {
+                return Value::Error {
+                    error: ShellError::UnsupportedInput(
+                        "'ln' undefined for a complex zero.".into(),
+                        "value originates from here".into(),
+                        head,
+                        record.expect_span(),
+                    ),
+                };
+            }
+            operate_complex(record, head, complex::ln)
+        }
         Value::Error { .. } => value,
         other => Value::Error {
             error: ShellError::OnlySupportsThisInputType(
-                "numeric".into(),
+                "numeric or complex".into(),
                 other.get_type().to_string(),
                 head,
                 other.expect_span(),
@@ -1,3 +1,4 @@
+use crate::math::complex::utils::{self as complex, operate_complex};
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
@@ -20,7 +21,10 @@ impl Command for SubCommand {
                 SyntaxShape::Number,
                 "Base for which the logarithm should be computed",
             )
-            .input_output_types(vec![(Type::Number, Type::Float)])
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
             .vectorizes_over_list(true)
             .category(Category::Math)
     }
@@ -81,6 +85,18 @@ impl Command for SubCommand {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description:
+                    "Get the logarithm of a negative number to the base 10, as a complex number",
+                example: "{re: -100, im: 0} | math log 10",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![
+                        Value::test_float(2.0),
+                        Value::test_float(1.3643763538418412),
+                    ],
+                )),
+            },
         ]
     }
 }
@@ -116,10 +132,26 @@ fn operate(value: Value, head: Span, base: f64) -> Value {
 
             Value::Float { val, span }
         }
+        record @ Value::Record { .. } => {
+            if complex::as_complex(&record) == Some((0.0, 0.0)) {
+                return Value::Error {
+                    error: ShellError::UnsupportedInput(
+                        "'math log' undefined for a complex zero.".into(),
+                        "value originates from here".into(),
+                        head,
+                        record.expect_span(),
+                    ),
+                };
+            }
+            operate_complex(record, head, |z| {
+                let (re, im) = complex::ln(z);
+                (re / base.ln(), im / base.ln())
+            })
+        }
         Value::Error { .. } => value,
         other => Value::Error {
             error: ShellError::OnlySupportsThisInputType(
-                "numeric".into(),
+                "numeric or complex".into(),
                 other.get_type().to_string(),
                 head,
                 other.expect_span(),
@@ -0,0 +1,84 @@
+use crate::math::matrix::utils::{determinant, square_error, to_floats, to_matrix};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math matrix determinant"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math matrix determinant")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::List(Box::new(Type::Number)))),
+                    Type::Float,
+                ),
+                (Type::Table(vec![]), Type::Float),
+            ])
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the determinant of a square matrix."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["det", "linear algebra"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let input = input.into_value(head);
+        let matrix = to_matrix(&input, head)?;
+        if matrix.len() != matrix[0].len() {
+            return Err(square_error(
+                matrix.len(),
+                matrix[0].len(),
+                head,
+                input.span()?,
+            ));
+        }
+
+        Ok(Value::float(determinant(to_floats(&matrix)), head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the determinant of a 2x2 matrix",
+                example: "[[2 1] [4 3]] | math matrix determinant",
+                result: Some(Value::test_float(2.0)),
+            },
+            Example {
+                description: "The determinant of a table with dependent rows is zero",
+                example: "[[x y]; [1 2] [2 4]] | math matrix determinant",
+                result: Some(Value::test_float(0.0)),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,98 @@
+use crate::math::matrix::utils::{
+    floats_to_values, from_rows, gauss_jordan, identity, singular_error, square_error, to_floats,
+    to_matrix,
+};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math matrix invert"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math matrix invert")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::List(Box::new(Type::Number)))),
+                    Type::List(Box::new(Type::List(Box::new(Type::Float)))),
+                ),
+                (
+                    Type::Table(vec![]),
+                    Type::List(Box::new(Type::List(Box::new(Type::Float)))),
+                ),
+            ])
+            .allow_variants_without_examples(true)
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the inverse of a square matrix."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Fails if the matrix is singular, that is, if its determinant is zero."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["inverse", "reciprocal", "linear algebra"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let input = input.into_value(head);
+        let span = input.span()?;
+        let matrix = to_matrix(&input, head)?;
+        if matrix.len() != matrix[0].len() {
+            return Err(square_error(matrix.len(), matrix[0].len(), head, span));
+        }
+
+        let inverse = gauss_jordan(to_floats(&matrix), identity(matrix.len()))
+            .ok_or_else(|| singular_error(span))?;
+        Ok(from_rows(floats_to_values(inverse, head), head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Invert a diagonal matrix",
+            example: "[[2 0] [0 4]] | math matrix invert",
+            result: Some(Value::List {
+                vals: vec![
+                    Value::List {
+                        vals: vec![Value::test_float(0.5), Value::test_float(0.0)],
+                        span: Span::test_data(),
+                    },
+                    Value::List {
+                        vals: vec![Value::test_float(0.0), Value::test_float(0.25)],
+                        span: Span::test_data(),
+                    },
+                ],
+                span: Span::test_data(),
+            }),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,56 @@
+use nu_engine::get_full_help;
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct MathMatrix;
+
+impl Command for MathMatrix {
+    fn name(&self) -> &str {
+        "math matrix"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math matrix")
+            .category(Category::Math)
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+    }
+
+    fn usage(&self) -> &str {
+        "Linear algebra on matrices of numbers."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"A matrix is a list of rows, each a list of numbers, like [[1 2] [3 4]]. A table works too,
+with its columns in order, so benchmark results can be used as they are.
+
+You must use one of the following subcommands. Using this command as-is will only produce this help message."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["linear algebra", "vector"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::String {
+            val: get_full_help(
+                &MathMatrix.signature(),
+                &MathMatrix.examples(),
+                engine_state,
+                stack,
+                self.is_parser_keyword(),
+            ),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+}
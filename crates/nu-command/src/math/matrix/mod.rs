@@ -0,0 +1,14 @@
+mod determinant;
+mod invert;
+mod matrix_;
+mod multiply;
+mod solve;
+mod transpose;
+pub(super) mod utils;
+
+pub use determinant::SubCommand as MathMatrixDeterminant;
+pub use invert::SubCommand as MathMatrixInvert;
+pub use matrix_::MathMatrix;
+pub use multiply::SubCommand as MathMatrixMultiply;
+pub use solve::SubCommand as MathMatrixSolve;
+pub use transpose::SubCommand as MathMatrixTranspose;
@@ -0,0 +1,147 @@
+use crate::math::matrix::utils::{
+    floats_to_values, from_operand, multiply, to_floats, to_matrix, to_operand,
+};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
+    Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math matrix multiply"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math matrix multiply")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::List(Box::new(Type::Number)))),
+                    Type::List(Box::new(Type::Any)),
+                ),
+                (Type::Table(vec![]), Type::List(Box::new(Type::Any))),
+            ])
+            .required(
+                "other",
+                SyntaxShape::Any,
+                "the matrix to multiply by, or a list of numbers as a column vector",
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Multiplies a matrix by another matrix or by a vector."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "The input must have as many columns as the other matrix has rows. The result of multiplying by a vector is a list of numbers. Ints stay ints as long as the products fit."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["product", "dot", "times", "linear algebra"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let other: Value = call.req(engine_state, stack, 0)?;
+        let input = input.into_value(head);
+
+        let a = to_matrix(&input, head)?;
+        let (b, is_vector) = to_operand(&other, head)?;
+        if a[0].len() != b.len() {
+            return Err(ShellError::IncompatibleParametersSingle(
+                format!(
+                    "the input has {} columns, but the other matrix has {} rows",
+                    a[0].len(),
+                    b.len()
+                ),
+                other.span()?,
+            ));
+        }
+
+        let product = multiply_ints(&a, &b, head)
+            .unwrap_or_else(|| floats_to_values(multiply(&to_floats(&a), &to_floats(&b)), head));
+        Ok(from_operand(product, is_vector, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Multiply two matrices",
+                example: "[[1 2] [3 4]] | math matrix multiply [[5 6] [7 8]]",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::List {
+                            vals: vec![Value::test_int(19), Value::test_int(22)],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![Value::test_int(43), Value::test_int(50)],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Multiply a matrix by a vector",
+                example: "[[1 0] [0 2]] | math matrix multiply [1.5 3]",
+                result: Some(Value::List {
+                    vals: vec![Value::test_float(1.5), Value::test_float(6.0)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+/// The product of two int matrices, or `None` if they hold floats or a product doesn't fit an int
+fn multiply_ints(a: &[Vec<Value>], b: &[Vec<Value>], span: Span) -> Option<Vec<Vec<Value>>> {
+    let to_ints = |matrix: &[Vec<Value>]| -> Option<Vec<Vec<i64>>> {
+        matrix
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.as_integer().ok()).collect())
+            .collect()
+    };
+    let (a, b) = (to_ints(a)?, to_ints(b)?);
+
+    a.iter()
+        .map(|row| {
+            (0..b[0].len())
+                .map(|column| {
+                    row.iter()
+                        .zip(&b)
+                        .try_fold(0i64, |sum, (cell, b_row)| {
+                            cell.checked_mul(b_row[column])
+                                .and_then(|product| sum.checked_add(product))
+                        })
+                        .map(|val| Value::int(val, span))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,138 @@
+use crate::math::matrix::utils::{
+    floats_to_values, from_operand, gauss_jordan, multiply, singular_error, to_floats, to_matrix,
+    to_operand, transpose,
+};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
+    Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math matrix solve"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math matrix solve")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::List(Box::new(Type::Number)))),
+                    Type::List(Box::new(Type::Any)),
+                ),
+                (Type::Table(vec![]), Type::List(Box::new(Type::Any))),
+            ])
+            .required(
+                "b",
+                SyntaxShape::Any,
+                "the right-hand side: a list of numbers, or a matrix to solve for several at once",
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Solves the linear system A x = b, where A is the input matrix."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"When A has more rows than columns, like a table of measurements with one row per sample, the
+system usually has no exact solution, and the least squares solution is returned instead: the x
+that minimizes the squared errors of A x against b. This fits a line or polynomial to data."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "equation",
+            "system",
+            "least squares",
+            "regression",
+            "fit",
+            "linear algebra",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let b_value: Value = call.req(engine_state, stack, 0)?;
+        let input = input.into_value(head);
+        let span = input.span()?;
+
+        let a = to_matrix(&input, head)?;
+        let (b, is_vector) = to_operand(&b_value, head)?;
+        if a.len() != b.len() {
+            return Err(ShellError::IncompatibleParametersSingle(
+                format!("the input has {} rows, but b has {} rows", a.len(), b.len()),
+                b_value.span()?,
+            ));
+        }
+        if a.len() < a[0].len() {
+            return Err(ShellError::UnsupportedInput(
+                format!(
+                    "the system has fewer equations ({}) than unknowns ({}), so it has no single solution",
+                    a.len(),
+                    a[0].len()
+                ),
+                "value originates from here".into(),
+                head,
+                span,
+            ));
+        }
+
+        let (a, b) = (to_floats(&a), to_floats(&b));
+        let (a, b) = if a.len() > a[0].len() {
+            // The normal equations, Aᵀ A x = Aᵀ b, have the least squares solution
+            let a_t = transpose(&a);
+            (multiply(&a_t, &a), multiply(&a_t, &b))
+        } else {
+            (a, b)
+        };
+
+        let x = gauss_jordan(a, b).ok_or_else(|| singular_error(span))?;
+        Ok(from_operand(floats_to_values(x, head), is_vector, head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Solve 2x = 4 and 4y = 8",
+                example: "[[2 0] [0 4]] | math matrix solve [4 8]",
+                result: Some(Value::List {
+                    vals: vec![Value::test_float(2.0), Value::test_float(2.0)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description:
+                    "Fit a line y = a + b x to the points (0, 1), (1, 3), (2, 5) and (3, 7)",
+                example: "[[one x]; [1 0] [1 1] [1 2] [1 3]] | math matrix solve [1 3 5 7]",
+                result: Some(Value::List {
+                    vals: vec![Value::test_float(1.0), Value::test_float(2.0)],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
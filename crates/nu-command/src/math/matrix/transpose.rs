@@ -0,0 +1,109 @@
+use crate::math::matrix::utils::{from_rows, to_matrix, transpose};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math matrix transpose"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math matrix transpose")
+            .input_output_types(vec![
+                (
+                    Type::List(Box::new(Type::List(Box::new(Type::Number)))),
+                    Type::List(Box::new(Type::List(Box::new(Type::Number)))),
+                ),
+                (
+                    Type::Table(vec![]),
+                    Type::List(Box::new(Type::List(Box::new(Type::Number)))),
+                ),
+            ])
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Swaps the rows and columns of a matrix."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Unlike `transpose`, this works on lists of lists, and keeps ints as ints."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["flip", "rows", "columns", "linear algebra"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let matrix = to_matrix(&input.into_value(head), head)?;
+        Ok(from_rows(transpose(&matrix), head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Transpose a 2x3 matrix",
+                example: "[[1 2 3] [4 5 6]] | math matrix transpose",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::List {
+                            vals: vec![Value::test_int(1), Value::test_int(4)],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![Value::test_int(2), Value::test_int(5)],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![Value::test_int(3), Value::test_int(6)],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Transpose a table into a list of its columns",
+                example: "[[x y]; [1 2] [3 4]] | math matrix transpose",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::List {
+                            vals: vec![Value::test_int(1), Value::test_int(3)],
+                            span: Span::test_data(),
+                        },
+                        Value::List {
+                            vals: vec![Value::test_int(2), Value::test_int(4)],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,252 @@
+use nu_protocol::{ShellError, Span, Value};
+use std::cmp::Ordering;
+
+/// Reads a matrix from a list of lists of numbers, or from a table, whose rows are the matrix rows
+/// with the columns in order
+pub fn to_matrix(value: &Value, head: Span) -> Result<Vec<Vec<Value>>, ShellError> {
+    let span = value.span().unwrap_or(head);
+    let rows = match value {
+        Value::List { vals, .. } => vals,
+        Value::Error { error } => return Err(error.clone()),
+        other => {
+            return Err(ShellError::OnlySupportsThisInputType(
+                "list of lists or table".into(),
+                other.get_type().to_string(),
+                head,
+                span,
+            ))
+        }
+    };
+
+    let mut matrix = Vec::with_capacity(rows.len());
+    for row in rows {
+        let row = match row {
+            Value::List { vals, .. } => vals.clone(),
+            Value::Record { vals, .. } => vals.clone(),
+            Value::Error { error } => return Err(error.clone()),
+            other => {
+                return Err(ShellError::UnsupportedInput(
+                    "every row of a matrix must be a list or a record".into(),
+                    "value originates from here".into(),
+                    head,
+                    other.expect_span(),
+                ))
+            }
+        };
+        for cell in &row {
+            match cell {
+                Value::Int { .. } | Value::Float { .. } => {}
+                Value::Error { error } => return Err(error.clone()),
+                other => {
+                    return Err(ShellError::UnsupportedInput(
+                        format!("matrices only hold numbers, found {}", other.get_type()),
+                        "value originates from here".into(),
+                        head,
+                        other.expect_span(),
+                    ))
+                }
+            }
+        }
+        matrix.push(row);
+    }
+
+    let columns = matrix.first().map(|row| row.len()).unwrap_or(0);
+    if columns == 0 {
+        return Err(ShellError::UnsupportedInput(
+            "a matrix needs at least one row and one column".into(),
+            "value originates from here".into(),
+            head,
+            span,
+        ));
+    }
+    if matrix.iter().any(|row| row.len() != columns) {
+        return Err(ShellError::UnsupportedInput(
+            "every row of a matrix must have the same length".into(),
+            "value originates from here".into(),
+            head,
+            span,
+        ));
+    }
+
+    Ok(matrix)
+}
+
+pub fn to_floats(matrix: &[Vec<Value>]) -> Vec<Vec<f64>> {
+    matrix
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Value::Int { val, .. } => *val as f64,
+                    Value::Float { val, .. } => *val,
+                    _ => unreachable!("checked by to_matrix"),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn from_rows(rows: Vec<Vec<Value>>, span: Span) -> Value {
+    Value::List {
+        vals: rows
+            .into_iter()
+            .map(|row| Value::List { vals: row, span })
+            .collect(),
+        span,
+    }
+}
+
+pub fn transpose<T: Clone>(matrix: &[Vec<T>]) -> Vec<Vec<T>> {
+    let columns = matrix.first().map(|row| row.len()).unwrap_or(0);
+    (0..columns)
+        .map(|column| matrix.iter().map(|row| row[column].clone()).collect())
+        .collect()
+}
+
+pub fn multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    a.iter()
+        .map(|row| {
+            (0..b[0].len())
+                .map(|column| {
+                    row.iter()
+                        .zip(b)
+                        .map(|(cell, b_row)| cell * b_row[column])
+                        .sum()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Pivots smaller than this are taken as zero, so a singular matrix isn't inverted into garbage
+const EPSILON: f64 = 1e-12;
+
+/// Solves `a * x = b` for `x` by Gauss-Jordan elimination with partial pivoting, where `a` is
+/// square. Returns `None` when `a` is singular.
+pub fn gauss_jordan(mut a: Vec<Vec<f64>>, mut b: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&i, &j| {
+            a[i][column]
+                .abs()
+                .partial_cmp(&a[j][column].abs())
+                .unwrap_or(Ordering::Equal)
+        })?;
+        if a[pivot][column].abs() < EPSILON {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+
+        let divisor = a[column][column];
+        a[column].iter_mut().for_each(|cell| *cell /= divisor);
+        b[column].iter_mut().for_each(|cell| *cell /= divisor);
+
+        let (pivot_a, pivot_b) = (a[column].clone(), b[column].clone());
+        for row in (0..n).filter(|&row| row != column) {
+            let factor = a[row][column];
+            if factor != 0.0 {
+                for (cell, pivot_cell) in a[row].iter_mut().zip(&pivot_a) {
+                    *cell -= factor * pivot_cell;
+                }
+                for (cell, pivot_cell) in b[row].iter_mut().zip(&pivot_b) {
+                    *cell -= factor * pivot_cell;
+                }
+            }
+        }
+    }
+    Some(b)
+}
+
+/// The determinant of the square matrix `a`, by Gaussian elimination with partial pivoting
+pub fn determinant(mut a: Vec<Vec<f64>>) -> f64 {
+    let n = a.len();
+    let mut det = 1.0;
+    for column in 0..n {
+        let pivot = match (column..n).max_by(|&i, &j| {
+            a[i][column]
+                .abs()
+                .partial_cmp(&a[j][column].abs())
+                .unwrap_or(Ordering::Equal)
+        }) {
+            Some(pivot) => pivot,
+            None => return 0.0,
+        };
+        if a[pivot][column] == 0.0 {
+            return 0.0;
+        }
+        if pivot != column {
+            a.swap(column, pivot);
+            det = -det;
+        }
+        det *= a[column][column];
+
+        let pivot_row = a[column].clone();
+        for row in a.iter_mut().skip(column + 1) {
+            let factor = row[column] / pivot_row[column];
+            for (cell, pivot_cell) in row.iter_mut().zip(&pivot_row).skip(column) {
+                *cell -= factor * pivot_cell;
+            }
+        }
+    }
+    det
+}
+
+pub fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect()
+}
+
+pub fn singular_error(span: Span) -> ShellError {
+    ShellError::GenericError(
+        "Singular matrix".into(),
+        "this matrix has no inverse".into(),
+        Some(span),
+        Some("Its determinant is zero: some of its rows are combinations of the others".into()),
+        vec![],
+    )
+}
+
+pub fn square_error(rows: usize, columns: usize, head: Span, span: Span) -> ShellError {
+    ShellError::UnsupportedInput(
+        format!("expected a square matrix, got {rows} rows and {columns} columns"),
+        "value originates from here".into(),
+        head,
+        span,
+    )
+}
+
+/// Reads the operand of `multiply` or `solve`: a matrix, or a list of numbers taken as a column
+/// vector, in which case the `bool` is true
+pub fn to_operand(value: &Value, head: Span) -> Result<(Vec<Vec<Value>>, bool), ShellError> {
+    match value {
+        Value::List { vals, .. }
+            if !vals.is_empty()
+                && vals
+                    .iter()
+                    .all(|v| matches!(v, Value::Int { .. } | Value::Float { .. })) =>
+        {
+            Ok((vals.iter().map(|v| vec![v.clone()]).collect(), true))
+        }
+        _ => Ok((to_matrix(value, head)?, false)),
+    }
+}
+
+/// Turns a matrix back into what the operand was: a list of numbers for a column vector
+pub fn from_operand(rows: Vec<Vec<Value>>, is_vector: bool, span: Span) -> Value {
+    if is_vector {
+        Value::List {
+            vals: rows.into_iter().flatten().collect(),
+            span,
+        }
+    } else {
+        from_rows(rows, span)
+    }
+}
+
+pub fn floats_to_values(rows: Vec<Vec<f64>>, span: Span) -> Vec<Vec<Value>> {
+    rows.into_iter()
+        .map(|row| row.into_iter().map(|val| Value::float(val, span)).collect())
+        .collect()
+}
@@ -6,47 +6,80 @@ mod arcsinh;
 mod arctan;
 mod arctanh;
 mod avg;
+mod big;
+mod bin;
 mod ceil;
+mod complex;
+mod convert;
 mod cos;
 mod cosh;
+mod cummax;
+mod cummin;
+mod cumprod;
+mod cumsum;
+mod cumulative;
 mod euler;
+mod fft;
+mod fit;
 mod floor;
+mod ifft;
+mod interpolate;
 mod ln;
 mod log;
 pub mod math_;
+mod matrix;
 mod max;
 mod median;
 mod min;
 mod mode;
 mod pi;
 mod product;
+mod quantile;
 mod reducers;
 mod round;
 mod sin;
 mod sinh;
 mod sqrt;
+mod stats;
 mod stddev;
 mod sum;
 mod tan;
 mod tanh;
 mod tau;
+mod units;
 mod utils;
 mod variance;
 
 pub use abs::SubCommand as MathAbs;
 pub use avg::SubCommand as MathAvg;
+pub use big::*;
+pub use bin::SubCommand as MathBin;
 pub use ceil::SubCommand as MathCeil;
+pub use complex::*;
+pub use convert::SubCommand as MathConvert;
+pub use cummax::SubCommand as MathCummax;
+pub use cummin::SubCommand as MathCummin;
+pub use cumprod::SubCommand as MathCumprod;
+pub use cumsum::SubCommand as MathCumsum;
+pub use fft::SubCommand as MathFft;
+pub use fit::SubCommand as MathFit;
 pub use floor::SubCommand as MathFloor;
+pub use ifft::SubCommand as MathIfft;
+pub use interpolate::SubCommand as MathInterpolate;
 pub use math_::MathCommand as Math;
+pub use matrix::*;
 pub use max::SubCommand as MathMax;
 pub use median::SubCommand as MathMedian;
 pub use min::SubCommand as MathMin;
 pub use mode::SubCommand as MathMode;
 pub use product::SubCommand as MathProduct;
+pub use quantile::SubCommand as MathQuantile;
 pub use round::SubCommand as MathRound;
 pub use sqrt::SubCommand as MathSqrt;
+pub use stats::SubCommand as MathStats;
 pub use stddev::SubCommand as MathStddev;
 pub use sum::SubCommand as MathSum;
+pub use units::SubCommand as MathUnits;
 pub use variance::SubCommand as MathVariance;
 
 pub use cos::SubCommand as MathCos;
@@ -0,0 +1,155 @@
+use crate::math::utils::{quantile, run_with_function, QuantileMethod};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math quantile"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math quantile")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::Number),
+                (Type::Table(vec![]), Type::Record(vec![])),
+            ])
+            .required(
+                "quantile",
+                SyntaxShape::Number,
+                "the quantile to compute, from 0 to 1",
+            )
+            .named(
+                "method",
+                SyntaxShape::String,
+                "how to pick a value between two ranks: linear (default), nearest or midpoint",
+                Some('m'),
+            )
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the value below which the given fraction of a list of numbers falls, or of each column in a table"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"When the quantile falls between two values, linear interpolates between them, nearest
+takes the closest one, and midpoint takes their mean."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["percentile", "quartile", "latency", "p95", "p99"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let quantile: Spanned<f64> = call.req(engine_state, stack, 0)?;
+        let method: Option<Spanned<String>> = call.get_flag(engine_state, stack, "method")?;
+
+        if !(0.0..=1.0).contains(&quantile.item) {
+            return Err(ShellError::UnsupportedInput(
+                "Quantiles go from 0 to 1".into(),
+                "value originates from here".into(),
+                call.head,
+                quantile.span,
+            ));
+        }
+
+        let method = match method {
+            None => QuantileMethod::Linear,
+            Some(method) => match method.item.as_str() {
+                "linear" => QuantileMethod::Linear,
+                "nearest" => QuantileMethod::Nearest,
+                "midpoint" => QuantileMethod::Midpoint,
+                _ => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "linear, nearest or midpoint".into(),
+                        method.item,
+                        method.span,
+                    ))
+                }
+            },
+        };
+
+        run_with_function(call, input, compute_quantile(quantile.item, method))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the 95th percentile of a list of numbers",
+                example: "[1 2 3 4 5 6 7 8 9 10 11] | math quantile 0.95",
+                result: Some(Value::test_float(10.5)),
+            },
+            Example {
+                description: "Get the median of each column of a table, taking the mean of the two middle values",
+                example: "[{a: 1, b: 10} {a: 2, b: 20}] | math quantile 0.5 --method midpoint",
+                result: Some(Value::Record {
+                    cols: vec!["a".into(), "b".into()],
+                    vals: vec![Value::test_float(1.5), Value::test_float(15.0)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Get the 99th percentile of response times, without interpolating",
+                example: "open requests.csv | get duration_ms | math quantile 0.99 -m nearest",
+                result: None,
+            },
+        ]
+    }
+}
+
+pub fn compute_quantile(
+    q: f64,
+    method: QuantileMethod,
+) -> impl Fn(&[Value], Span, &Span) -> Result<Value, ShellError> {
+    move |values: &[Value], span: Span, head: &Span| {
+        if values.is_empty() {
+            return Err(ShellError::UnsupportedInput(
+                "Empty input".to_string(),
+                "value originates from here".into(),
+                *head,
+                span,
+            ));
+        }
+
+        let mut sorted = values
+            .iter()
+            .map(|value| match value {
+                Value::Int { .. } | Value::Float { .. } => value.as_float(),
+                other => Err(ShellError::UnsupportedInput(
+                    "Attempted to compute a quantile of a non-numeric value".to_string(),
+                    "value originates from here".into(),
+                    *head,
+                    other.expect_span(),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        Ok(Value::float(quantile(&sorted, q, method), *head))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -2,8 +2,9 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
+use std::cmp::Ordering;
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -15,7 +16,11 @@ impl Command for SubCommand {
 
     fn signature(&self) -> Signature {
         Signature::build("math round")
-            .input_output_types(vec![(Type::Number, Type::Number)])
+            .input_output_types(vec![
+                (Type::Number, Type::Number),
+                (Type::Filesize, Type::Filesize),
+                (Type::Duration, Type::Duration),
+            ])
             .vectorizes_over_list(true)
             .named(
                 "precision",
@@ -23,6 +28,18 @@ impl Command for SubCommand {
                 "digits of precision",
                 Some('p'),
             )
+            .named(
+                "significant",
+                SyntaxShape::Int,
+                "round to this many significant figures",
+                Some('s'),
+            )
+            .named(
+                "mode",
+                SyntaxShape::String,
+                "how to round: half-up (default), half-even, floor or ceil",
+                Some('m'),
+            )
             .category(Category::Math)
     }
 
@@ -30,8 +47,24 @@ impl Command for SubCommand {
         "Returns the input number rounded to the specified precision"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"The half-up mode rounds halves away from zero, and the half-even mode, known as banker's
+rounding, rounds them to the even neighbour. A negative precision rounds to tens, hundreds and so
+on.
+
+File sizes and durations are rounded as a number of bytes and of nanoseconds, so round them with
+--significant or a negative --precision. They keep their type, like integers."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["approx", "closest", "nearest"]
+        vec![
+            "approx",
+            "closest",
+            "nearest",
+            "significant",
+            "bankers",
+            "half even",
+        ]
     }
 
     fn run(
@@ -41,14 +74,51 @@ impl Command for SubCommand {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let precision_param: Option<i64> = call.get_flag(engine_state, stack, "precision")?;
+        let precision: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "precision")?;
+        let significant: Option<Spanned<i64>> =
+            call.get_flag(engine_state, stack, "significant")?;
+        let mode: Option<Spanned<String>> = call.get_flag(engine_state, stack, "mode")?;
         let head = call.head;
+
+        let digits = match (precision, significant) {
+            (Some(precision), Some(significant)) => {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "either round to a precision".into(),
+                    left_span: precision.span,
+                    right_message: "or to significant figures".into(),
+                    right_span: significant.span,
+                })
+            }
+            (Some(precision), None) => Some(Digits::Precision(precision.item)),
+            (None, Some(significant)) if significant.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(significant.span))
+            }
+            (None, Some(significant)) => Some(Digits::Significant(significant.item as u32)),
+            (None, None) => None,
+        };
+        let mode = match mode {
+            None => Mode::HalfUp,
+            Some(mode) => match mode.item.as_str() {
+                "half-up" => Mode::HalfUp,
+                "half-even" => Mode::HalfEven,
+                "floor" => Mode::Floor,
+                "ceil" => Mode::Ceil,
+                _ => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "half-up, half-even, floor or ceil".into(),
+                        mode.item,
+                        mode.span,
+                    ))
+                }
+            },
+        };
+
         // This doesn't match explicit nulls
         if matches!(input, PipelineData::Empty) {
             return Err(ShellError::PipelineEmpty(head));
         }
         input.map(
-            move |value| operate(value, head, precision_param),
+            move |value| operate(value, head, digits, mode),
             engine_state.ctrlc.clone(),
         )
     }
@@ -75,24 +145,88 @@ impl Command for SubCommand {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Round to three significant figures",
+                example: "[123456 0.0012345] | math round --significant 3",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(123000), Value::test_float(0.00123)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Round halves to the even neighbour, like banker's rounding",
+                example: "[0.5 1.5 2.5] | math round --mode half-even",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(0), Value::test_int(2), Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Round a file size down to whole kilobytes",
+                example: "1536b | math round --precision -3 --mode floor",
+                result: Some(Value::Filesize {
+                    val: 1000,
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Round a duration to two significant figures",
+                example: "1234567890ns | math round --significant 2",
+                result: Some(Value::Duration {
+                    val: 1_200_000_000,
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
 
-fn operate(value: Value, head: Span, precision: Option<i64>) -> Value {
+/// The digits to round to
+#[derive(Clone, Copy)]
+enum Digits {
+    /// Digits after the decimal point, or before it when negative
+    Precision(i64),
+    Significant(u32),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    HalfUp,
+    HalfEven,
+    Floor,
+    Ceil,
+}
+
+fn operate(value: Value, head: Span, digits: Option<Digits>, mode: Mode) -> Value {
     match value {
-        Value::Float { val, span } => match precision {
-            Some(precision_number) => Value::Float {
-                val: ((val * ((10_f64).powf(precision_number as f64))).round()
-                    / (10_f64).powf(precision_number as f64)),
-                span,
-            },
+        Value::Float { val, span } => match digits {
+            Some(digits) => {
+                let precision = match digits {
+                    Digits::Precision(precision) => precision,
+                    Digits::Significant(significant) => precision_of(val, significant),
+                };
+                Value::Float {
+                    val: round_float_to(val, precision, mode),
+                    span,
+                }
+            }
             None => Value::Int {
-                val: val.round() as i64,
+                val: round_float(val, mode) as i64,
                 span,
             },
         },
-        Value::Int { .. } => value,
+        Value::Int { val, span } => Value::Int {
+            val: round_int(val, digits, mode),
+            span,
+        },
+        Value::Filesize { val, span } => Value::Filesize {
+            val: round_int(val, digits, mode),
+            span,
+        },
+        Value::Duration { val, span } => Value::Duration {
+            val: round_int(val, digits, mode),
+            span,
+        },
         Value::Error { .. } => value,
         other => Value::Error {
             error: ShellError::OnlySupportsThisInputType(
@@ -105,6 +239,62 @@ fn operate(value: Value, head: Span, precision: Option<i64>) -> Value {
     }
 }
 
+fn round_float(val: f64, mode: Mode) -> f64 {
+    match mode {
+        Mode::HalfUp => val.round(),
+        Mode::HalfEven if (val - val.trunc()).abs() == 0.5 => 2.0 * (val / 2.0).round(),
+        Mode::HalfEven => val.round(),
+        Mode::Floor => val.floor(),
+        Mode::Ceil => val.ceil(),
+    }
+}
+
+fn round_float_to(val: f64, precision: i64, mode: Mode) -> f64 {
+    let scale = 10_f64.powf(precision as f64);
+    round_float(val * scale, mode) / scale
+}
+
+/// The precision that keeps `significant` figures of `val`
+fn precision_of(val: f64, significant: u32) -> i64 {
+    if val == 0.0 || !val.is_finite() {
+        return 0;
+    }
+    significant as i64 - val.abs().log10().floor() as i64 - 1
+}
+
+/// Rounds an integer, which only changes when it is rounded to tens or more
+fn round_int(val: i64, digits: Option<Digits>, mode: Mode) -> i64 {
+    let zeros = match digits {
+        None => 0,
+        Some(Digits::Precision(precision)) => -precision,
+        Some(Digits::Significant(significant)) => {
+            let length = val.unsigned_abs().to_string().len() as i64;
+            length - significant as i64
+        }
+    };
+    if zeros <= 0 {
+        return val;
+    }
+
+    // i128 holds every power of ten that can round an i64, and the rounded value before it is
+    // clamped back
+    let unit = 10_i128.pow(zeros.min(20) as u32);
+    let val = val as i128;
+    let (quotient, remainder) = (val.div_euclid(unit), val.rem_euclid(unit));
+    let up = match mode {
+        Mode::Floor => false,
+        Mode::Ceil => remainder > 0,
+        Mode::HalfUp | Mode::HalfEven => match (2 * remainder).cmp(&unit) {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal if mode == Mode::HalfUp => val > 0,
+            Ordering::Equal => quotient % 2 != 0,
+        },
+    };
+    let rounded = (quotient + i128::from(up)) * unit;
+    rounded.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -115,4 +305,22 @@ mod test {
 
         test_examples(SubCommand {})
     }
+
+    #[test]
+    fn rounds_integer_halves_by_mode() {
+        let precision = Some(Digits::Precision(-1));
+
+        assert_eq!(round_int(-25, precision, Mode::HalfUp), -30);
+        assert_eq!(round_int(-25, precision, Mode::HalfEven), -20);
+        assert_eq!(round_int(35, precision, Mode::HalfEven), 40);
+        assert_eq!(round_int(-21, precision, Mode::Floor), -30);
+        assert_eq!(round_int(21, precision, Mode::Ceil), 30);
+    }
+
+    #[test]
+    fn rounds_floats_to_significant_figures() {
+        assert_eq!(precision_of(98765.0, 2), -3);
+        assert_eq!(precision_of(0.00098765, 2), 5);
+        assert_eq!(round_float_to(98765.0, -3, Mode::HalfUp), 99000.0);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::math::complex::utils::{self as complex, operate_complex};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
@@ -13,7 +14,10 @@ impl Command for SubCommand {
     fn signature(&self) -> Signature {
         Signature::build("math sin")
             .switch("degrees", "Use degrees instead of radians", Some('d'))
-            .input_output_types(vec![(Type::Number, Type::Float)])
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
             .vectorizes_over_list(true)
             .category(Category::Math)
     }
@@ -66,6 +70,17 @@ impl Command for SubCommand {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Apply the sine to the imaginary unit, as a complex number",
+                example: "{re: 0, im: 1} | math sin",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![
+                        Value::test_float(0.0),
+                        Value::test_float(1.1752011936438014),
+                    ],
+                )),
+            },
         ]
     }
 }
@@ -86,10 +101,18 @@ fn operate(value: Value, head: Span, use_degrees: bool) -> Value {
                 span,
             }
         }
+        record @ Value::Record { .. } => operate_complex(record, head, |(re, im)| {
+            let z = if use_degrees {
+                (re.to_radians(), im.to_radians())
+            } else {
+                (re, im)
+            };
+            complex::sin(z)
+        }),
         Value::Error { .. } => value,
         other => Value::Error {
             error: ShellError::OnlySupportsThisInputType(
-                "numeric".into(),
+                "numeric or complex".into(),
                 other.get_type().to_string(),
                 head,
                 other.expect_span(),
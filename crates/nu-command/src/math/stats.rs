@@ -0,0 +1,216 @@
+use crate::math::avg::average;
+use crate::math::max::maximum;
+use crate::math::median::median;
+use crate::math::min::minimum;
+use crate::math::mode::mode;
+use crate::math::stddev::compute_stddev;
+use crate::math::utils::{quantile, run_with_function, QuantileMethod};
+use crate::math::variance::compute_variance;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+const DEFAULT_PERCENTILES: [f64; 2] = [25.0, 75.0];
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math stats"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math stats")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::Record(vec![])),
+                (Type::Table(vec![]), Type::Record(vec![])),
+            ])
+            .switch(
+                "sample",
+                "calculate the sample variance and standard deviation (i.e. using N-1 as the denominator)",
+                Some('s'),
+            )
+            .named(
+                "percentiles",
+                SyntaxShape::List(Box::new(SyntaxShape::Number)),
+                "the percentiles to compute, from 0 to 100 (default [25 75])",
+                Some('p'),
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Returns the descriptive statistics of a list of numbers, or of each column in a table"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The record holds the count, mean, median, mode, standard deviation, variance, minimum,
+maximum, and a pN column for every percentile. Percentiles are interpolated between the two
+closest values."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "statistics",
+            "describe",
+            "summary",
+            "percentile",
+            "quartile",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let sample = call.has_flag("sample");
+        let percentiles: Option<Vec<Value>> = call.get_flag(engine_state, stack, "percentiles")?;
+
+        let percentiles = match percentiles {
+            Some(percentiles) => percentiles
+                .iter()
+                .map(|percentile| match percentile.as_float()? {
+                    p if (0.0..=100.0).contains(&p) => Ok(p),
+                    _ => Err(ShellError::UnsupportedInput(
+                        "Percentiles go from 0 to 100".into(),
+                        "value originates from here".into(),
+                        call.head,
+                        percentile.span()?,
+                    )),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => DEFAULT_PERCENTILES.to_vec(),
+        };
+
+        run_with_function(call, input, compute_stats(sample, percentiles))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Get the statistics of a list of numbers",
+                example: "[1 2 2 3] | math stats",
+                result: Some(Value::Record {
+                    cols: vec![
+                        "count".into(),
+                        "mean".into(),
+                        "median".into(),
+                        "mode".into(),
+                        "stddev".into(),
+                        "variance".into(),
+                        "min".into(),
+                        "max".into(),
+                        "p25".into(),
+                        "p75".into(),
+                    ],
+                    vals: vec![
+                        Value::test_int(4),
+                        Value::test_int(2),
+                        Value::test_int(2),
+                        Value::List {
+                            vals: vec![Value::test_int(2)],
+                            span: Span::test_data(),
+                        },
+                        Value::test_float(0.7071067811865476),
+                        Value::test_float(0.5),
+                        Value::test_int(1),
+                        Value::test_int(3),
+                        Value::test_float(1.75),
+                        Value::test_float(2.25),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Get the 90th and 99th percentiles of response times",
+                example: "open requests.csv | get duration_ms | math stats -p [90 99]",
+                result: None,
+            },
+            Example {
+                description: "Get the statistics of every column of a table",
+                example: "[[a b]; [1 10] [3 30]] | math stats",
+                result: None,
+            },
+        ]
+    }
+}
+
+pub fn compute_stats(
+    sample: bool,
+    percentiles: Vec<f64>,
+) -> impl Fn(&[Value], Span, &Span) -> Result<Value, ShellError> {
+    move |values: &[Value], span: Span, head: &Span| {
+        if values.is_empty() {
+            return Err(ShellError::UnsupportedInput(
+                "Empty input".to_string(),
+                "value originates from here".into(),
+                *head,
+                span,
+            ));
+        }
+
+        // compute_variance() checks that every value is a number
+        let variance = compute_variance(sample)(values, span, head)?;
+
+        let mut sorted = values
+            .iter()
+            .map(|value| value.as_float())
+            .collect::<Result<Vec<_>, _>>()?;
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let mut cols = vec![
+            "count".to_string(),
+            "mean".to_string(),
+            "median".to_string(),
+            "mode".to_string(),
+            "stddev".to_string(),
+            "variance".to_string(),
+            "min".to_string(),
+            "max".to_string(),
+        ];
+        let mut vals = vec![
+            Value::int(values.len() as i64, *head),
+            average(values, span, head)?,
+            median(values, span, head)?,
+            mode(values, span, head)?,
+            compute_stddev(sample)(values, span, head)?,
+            variance,
+            minimum(values, span, head)?,
+            maximum(values, span, head)?,
+        ];
+
+        for p in &percentiles {
+            cols.push(format!("p{p}"));
+            vals.push(Value::float(
+                quantile(&sorted, *p / 100.0, QuantileMethod::Linear),
+                *head,
+            ));
+        }
+
+        Ok(Value::Record {
+            cols,
+            vals,
+            span: *head,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
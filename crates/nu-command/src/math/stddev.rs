@@ -1,4 +1,4 @@
-use super::variance::compute_variance as variance;
+use super::variance::{compute_variance as variance, is_sample, run_on_stream};
 use crate::math::utils::run_with_function;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
@@ -20,9 +20,18 @@ impl Command for SubCommand {
                 "calculate sample standard deviation (i.e. using N-1 as the denominator)",
                 Some('s'),
             )
+            .switch(
+                "population",
+                "calculate population standard deviation (i.e. using N as the denominator), the default",
+                Some('p'),
+            )
             .category(Category::Math)
     }
 
+    fn extra_usage(&self) -> &str {
+        "Streams are consumed in a single pass, so they don't need to fit in memory."
+    }
+
     fn usage(&self) -> &str {
         "Returns the standard deviation of a list of numbers, or of each column in a table"
     }
@@ -45,8 +54,13 @@ impl Command for SubCommand {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let sample = call.has_flag("sample");
-        run_with_function(call, input, compute_stddev(sample))
+        let sample = is_sample(call)?;
+        match input {
+            PipelineData::ListStream(stream, ..) => run_on_stream(stream, call.head, |w, head| {
+                Ok(Value::float(w.variance(sample, head)?.sqrt(), head))
+            }),
+            input => run_with_function(call, input, compute_stddev(sample)),
+        }
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -61,6 +75,11 @@ impl Command for SubCommand {
                 example: "[1 2 3 4 5] | math stddev -s",
                 result: Some(Value::test_float(1.5811388300841898)),
             },
+            Example {
+                description: "Compute the standard deviation of a stream without collecting it",
+                example: "1..5 | each { |x| $x * 2 } | math stddev --population",
+                result: Some(Value::test_float(2.0 * std::f64::consts::SQRT_2)),
+            },
         ]
     }
 }
@@ -1,3 +1,4 @@
+use crate::math::complex::utils::{self as complex, operate_complex};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
@@ -12,7 +13,11 @@ impl Command for SubCommand {
 
     fn signature(&self) -> Signature {
         Signature::build("math tanh")
-            .input_output_types(vec![(Type::Number, Type::Float)])
+            .switch("degrees", "Use degrees instead of radians", Some('d'))
+            .input_output_types(vec![
+                (Type::Number, Type::Float),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
             .vectorizes_over_list(true)
             .category(Category::Math)
     }
@@ -33,26 +38,38 @@ impl Command for SubCommand {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
+        let use_degrees = call.has_flag("degrees");
         // This doesn't match explicit nulls
         if matches!(input, PipelineData::Empty) {
             return Err(ShellError::PipelineEmpty(head));
         }
         input.map(
-            move |value| operate(value, head),
+            move |value| operate(value, head, use_degrees),
             engine_state.ctrlc.clone(),
         )
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Apply the hyperbolic tangent to 10*π",
-            example: "(math pi) * 10 | math tanh",
-            result: Some(Value::test_float(1f64)),
-        }]
+        vec![
+            Example {
+                description: "Apply the hyperbolic tangent to 10*π",
+                example: "(math pi) * 10 | math tanh",
+                result: Some(Value::test_float(1f64)),
+            },
+            Example {
+                description:
+                    "Apply the hyperbolic tangent to the imaginary unit, as a complex number",
+                example: "{re: 0, im: 1} | math tanh",
+                result: Some(Value::test_record(
+                    vec!["re", "im"],
+                    vec![Value::test_float(0.0), Value::test_float(1.557407724654902)],
+                )),
+            },
+        ]
     }
 }
 
-fn operate(value: Value, head: Span) -> Value {
+fn operate(value: Value, head: Span, use_degrees: bool) -> Value {
     match value {
         numeric @ (Value::Int { .. } | Value::Float { .. }) => {
             let (val, span) = match numeric {
@@ -61,15 +78,25 @@ fn operate(value: Value, head: Span) -> Value {
                 _ => unreachable!(),
             };
 
+            let val = if use_degrees { val.to_radians() } else { val };
+
             Value::Float {
                 val: val.tanh(),
                 span,
             }
         }
+        record @ Value::Record { .. } => operate_complex(record, head, |(re, im)| {
+            let z = if use_degrees {
+                (re.to_radians(), im.to_radians())
+            } else {
+                (re, im)
+            };
+            complex::tanh(z)
+        }),
         Value::Error { .. } => value,
         other => Value::Error {
             error: ShellError::OnlySupportsThisInputType(
-                "numeric".into(),
+                "numeric or complex".into(),
                 other.get_type().to_string(),
                 head,
                 other.expect_span(),
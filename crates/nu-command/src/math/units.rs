@@ -0,0 +1,89 @@
+use crate::units::UNITS;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "math units"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("math units")
+            .input_output_types(vec![(Type::Nothing, Type::Table(vec![]))])
+            .category(Category::Math)
+    }
+
+    fn usage(&self) -> &str {
+        "Lists the units of measurement that `math convert` and `into unit` know."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Units convert to the other units of the same dimension. A unit is `factor` times the base
+unit of its dimension, which has a factor of 1, plus `offset` of them for temperature scales."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["measure", "dimension", "convert"]
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let vals = UNITS
+            .iter()
+            .map(|unit| Value::Record {
+                cols: vec![
+                    "symbol".into(),
+                    "name".into(),
+                    "dimension".into(),
+                    "aliases".into(),
+                    "factor".into(),
+                    "offset".into(),
+                ],
+                vals: vec![
+                    Value::string(unit.symbol, head),
+                    Value::string(unit.name, head),
+                    Value::string(unit.dimension, head),
+                    Value::List {
+                        vals: unit
+                            .aliases
+                            .iter()
+                            .map(|alias| Value::string(*alias, head))
+                            .collect(),
+                        span: head,
+                    },
+                    Value::float(unit.factor, head),
+                    Value::float(unit.offset, head),
+                ],
+                span: head,
+            })
+            .collect();
+        Ok(Value::List { vals, span: head }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the units of pressure",
+                example: "math units | where dimension == pressure",
+                result: None,
+            },
+            Example {
+                description: "List the dimensions",
+                example: "math units | get dimension | uniq",
+                result: None,
+            },
+        ]
+    }
+}
@@ -117,3 +117,43 @@ pub fn calculate(
         )),
     }
 }
+
+/// How a quantile that falls between two values is computed from them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuantileMethod {
+    Linear,
+    Nearest,
+    Midpoint,
+}
+
+// The value below which the fraction q of the sorted values fall. Used by `math quantile`, and by
+// `math stats` for its percentiles
+pub fn quantile(sorted: &[f64], q: f64, method: QuantileMethod) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    let lower = sorted[rank.floor() as usize];
+    let upper = sorted[rank.ceil() as usize];
+
+    match method {
+        QuantileMethod::Linear => lower + (upper - lower) * rank.fract(),
+        QuantileMethod::Nearest => sorted[rank.round() as usize],
+        QuantileMethod::Midpoint => (lower + upper) / 2.0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quantile_methods() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+
+        assert_eq!(quantile(&sorted, 0.0, QuantileMethod::Linear), 1.0);
+        assert_eq!(quantile(&sorted, 0.5, QuantileMethod::Linear), 2.5);
+        assert_eq!(quantile(&sorted, 1.0, QuantileMethod::Linear), 4.0);
+        assert_eq!(quantile(&sorted, 0.4, QuantileMethod::Nearest), 2.0);
+        assert_eq!(quantile(&sorted, 0.4, QuantileMethod::Midpoint), 2.5);
+        assert_eq!(quantile(&sorted, 1.0, QuantileMethod::Midpoint), 4.0);
+        assert_eq!(quantile(&[7.0], 0.9, QuantileMethod::Linear), 7.0);
+    }
+}
@@ -1,7 +1,11 @@
 use crate::math::utils::run_with_function;
+use indexmap::map::IndexMap;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, ListStream, PipelineData, ShellError, Signature, Span,
+    Type, Value,
+};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -13,15 +17,27 @@ impl Command for SubCommand {
 
     fn signature(&self) -> Signature {
         Signature::build("math variance")
-            .input_output_types(vec![(Type::List(Box::new(Type::Number)), Type::Number)])
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Number)), Type::Number),
+                (Type::Table(vec![]), Type::Record(vec![])),
+            ])
             .switch(
                 "sample",
                 "calculate sample variance (i.e. using N-1 as the denominator)",
                 Some('s'),
             )
+            .switch(
+                "population",
+                "calculate population variance (i.e. using N as the denominator), the default",
+                Some('p'),
+            )
             .category(Category::Math)
     }
 
+    fn extra_usage(&self) -> &str {
+        "Streams are consumed in a single pass, so they don't need to fit in memory."
+    }
+
     fn usage(&self) -> &str {
         "Returns the variance of a list of numbers or of each column in a table"
     }
@@ -37,8 +53,13 @@ impl Command for SubCommand {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let sample = call.has_flag("sample");
-        run_with_function(call, input, compute_variance(sample))
+        let sample = is_sample(call)?;
+        match input {
+            PipelineData::ListStream(stream, ..) => run_on_stream(stream, call.head, |w, head| {
+                Ok(Value::float(w.variance(sample, head)?, head))
+            }),
+            input => run_with_function(call, input, compute_variance(sample)),
+        }
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -53,52 +74,161 @@ impl Command for SubCommand {
                 example: "[1 2 3 4 5] | math variance -s",
                 result: Some(Value::test_float(2.5)),
             },
+            Example {
+                description: "Get the variance of each column of a streamed table",
+                example: "1..4 | each { |x| {a: $x, b: ($x * 2)} } | math variance",
+                result: Some(Value::Record {
+                    cols: vec!["a".to_string(), "b".to_string()],
+                    vals: vec![Value::test_float(1.25), Value::test_float(5.0)],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
 
-fn sum_of_squares(values: &[Value], span: &Span) -> Result<Value, ShellError> {
-    let n = Value::int(values.len() as i64, *span);
-    let mut sum_x = Value::int(0, *span);
-    let mut sum_x2 = Value::int(0, *span);
-    for value in values {
-        let v = match &value {
-            Value::Int { .. } | Value::Float { .. } => Ok(value),
-            Value::Error { error } => Err(error.clone()),
-            _ => Err(ShellError::UnsupportedInput(
-                "Attempted to compute the sum of squares of a non-integer, non-float value"
-                    .to_string(),
-                "value originates from here".into(),
-                *span,
-                value.expect_span(),
-            )),
-        }?;
-        let v_squared = &v.mul(*span, v, *span)?;
-        sum_x2 = sum_x2.add(*span, v_squared, *span)?;
-        sum_x = sum_x.add(*span, v, *span)?;
-    }
-
-    let sum_x_squared = sum_x.mul(*span, &sum_x, *span)?;
-    let sum_x_squared_div_n = sum_x_squared.div(*span, &n, *span)?;
-
-    let ss = sum_x2.sub(*span, &sum_x_squared_div_n, *span)?;
-
-    Ok(ss)
+/// Running mean and sum of squared differences from it, following Welford's algorithm, so the
+/// variance can be computed in one pass without holding on to the values
+#[derive(Default)]
+pub(super) struct Welford {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl Welford {
+    pub(super) fn push(&mut self, value: &Value, head: Span) -> Result<(), ShellError> {
+        let x = match value {
+            Value::Int { val, .. } => *val as f64,
+            Value::Float { val, .. } => *val,
+            Value::Error { error } => return Err(error.clone()),
+            other => {
+                return Err(ShellError::UnsupportedInput(
+                    "Attempted to compute the variance of a non-integer, non-float value"
+                        .to_string(),
+                    "value originates from here".into(),
+                    head,
+                    other.expect_span(),
+                ))
+            }
+        };
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        Ok(())
+    }
+
+    pub(super) fn variance(&self, sample: bool, head: Span) -> Result<f64, ShellError> {
+        let n = if sample {
+            self.count.saturating_sub(1)
+        } else {
+            self.count
+        };
+        if n == 0 {
+            return Err(ShellError::DivisionByZero(head));
+        }
+        Ok(self.m2 / n as f64)
+    }
+}
+
+/// Whether the sample variance was asked for, rejecting contradicting flags
+pub(super) fn is_sample(call: &Call) -> Result<bool, ShellError> {
+    match (
+        call.get_named_arg("sample"),
+        call.get_named_arg("population"),
+    ) {
+        (Some(sample), Some(population)) => Err(ShellError::IncompatibleParameters {
+            left_message: "sample requested here".into(),
+            left_span: sample.span,
+            right_message: "but population requested here".into(),
+            right_span: population.span,
+        }),
+        (sample, _) => Ok(sample.is_some()),
+    }
+}
+
+/// Feed a stream through Welford accumulators, one per column when it's a table, and finish
+/// each of them with `finish`
+pub(super) fn run_on_stream(
+    stream: ListStream,
+    head: Span,
+    finish: impl Fn(&Welford, Span) -> Result<Value, ShellError>,
+) -> Result<PipelineData, ShellError> {
+    let mut stream = stream.peekable();
+    if !matches!(stream.peek(), Some(Value::Record { .. })) {
+        let mut welford = Welford::default();
+        for value in stream {
+            welford.push(&value, head)?;
+        }
+        return Ok(finish(&welford, head)?.into_pipeline_data());
+    }
+
+    // Columns with values that aren't numbers are left out, like for other math commands
+    let mut columns: IndexMap<String, Option<Welford>> = IndexMap::new();
+    for row in stream {
+        match row {
+            Value::Record { cols, vals, .. } => {
+                for (col, val) in cols.into_iter().zip(vals) {
+                    let welford = columns
+                        .entry(col)
+                        .or_insert_with(|| Some(Welford::default()));
+                    let failed = match welford {
+                        Some(welford) => welford.push(&val, head).is_err(),
+                        None => false,
+                    };
+                    if failed {
+                        *welford = None;
+                    }
+                }
+            }
+            Value::Error { error } => return Err(error),
+            other => {
+                return Err(ShellError::UnsupportedInput(
+                    "Only tables of numbers are supported".into(),
+                    "value originates from here".into(),
+                    head,
+                    other.expect_span(),
+                ))
+            }
+        }
+    }
+
+    let mut cols = vec![];
+    let mut vals = vec![];
+    for (col, welford) in columns {
+        if let Some(Ok(val)) = welford.map(|welford| finish(&welford, head)) {
+            cols.push(col);
+            vals.push(val);
+        }
+    }
+    if cols.is_empty() {
+        return Err(ShellError::UnsupportedInput(
+            "Unable to give a result with this input".to_string(),
+            "value originates from here".into(),
+            head,
+            head,
+        ));
+    }
+
+    Ok(Value::Record {
+        cols,
+        vals,
+        span: head,
+    }
+    .into_pipeline_data())
 }
 
 pub fn compute_variance(
     sample: bool,
 ) -> impl Fn(&[Value], Span, &Span) -> Result<Value, ShellError> {
     move |values: &[Value], span: Span, head: &Span| {
-        let n = if sample {
-            values.len() - 1
-        } else {
-            values.len()
-        };
-        // sum_of_squares() needs the span of the original value, not the call head.
-        let ss = sum_of_squares(values, &span)?;
-        let n = Value::int(n as i64, *head);
-        ss.div(*head, &n, *head)
+        let mut welford = Welford::default();
+        for value in values {
+            // push() needs the span of the original value, not the call head.
+            welford.push(value, span)?;
+        }
+        Ok(Value::float(welford.variance(sample, *head)?, *head))
     }
 }
 
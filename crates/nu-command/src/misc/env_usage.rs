@@ -0,0 +1,135 @@
+use super::usage::command_lines;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct DebugEnvUsage;
+
+impl Command for DebugEnvUsage {
+    fn name(&self) -> &str {
+        "debug env-usage"
+    }
+
+    fn usage(&self) -> &str {
+        "Count how often every environment variable is read in the history."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("debug env-usage")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Table(vec![])),
+                (Type::List(Box::new(Type::String)), Type::Table(vec![])),
+            ])
+            .switch(
+                "unused",
+                "only list the environment variables that are never read",
+                Some('u'),
+            )
+            .category(Category::Debug)
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"A variable is read where a command line mentions `$env.NAME`. Externals read their
+environment without mentioning it, so variables like PATH or EDITOR can be in use even when they
+are never read here. Like `debug usage`, this reads the local history, or the piped command lines."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["unused", "environment", "variable", "history", "config"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let unused = call.has_flag("unused");
+        let lines = command_lines(engine_state, input, head)?;
+
+        let mut reads: HashMap<String, usize> = HashMap::new();
+        for line in &lines {
+            for name in env_reads(line) {
+                *reads.entry(name).or_insert(0) += 1;
+            }
+        }
+
+        let mut rows: Vec<(String, usize)> = stack
+            .get_env_var_names(engine_state)
+            .into_iter()
+            .map(|name| {
+                let count = reads.get(&name).copied().unwrap_or(0);
+                (name, count)
+            })
+            .filter(|(_, count)| !unused || *count == 0)
+            .collect();
+        rows.sort_by(|(name_a, count_a), (name_b, count_b)| {
+            count_b.cmp(count_a).then(name_a.cmp(name_b))
+        });
+
+        let vals = rows
+            .into_iter()
+            .map(|(name, count)| Value::Record {
+                cols: vec!["name".into(), "uses".into()],
+                vals: vec![Value::string(name, head), Value::int(count as i64, head)],
+                span: head,
+            })
+            .collect();
+        Ok(Value::List { vals, span: head }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the environment variables that are never read",
+                example: "debug env-usage --unused",
+                result: None,
+            },
+            Example {
+                description: "Count the reads in the commands of the current session only",
+                example: "history --long | where session_id == (history session) | get command | debug env-usage",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// The names of the environment variables read in a command line with `$env.NAME`,
+/// `$env."NAME"` or `$env.'NAME'`
+fn env_reads(line: &str) -> Vec<String> {
+    line.match_indices("$env.")
+        .filter_map(|(start, prefix)| {
+            let rest = &line[start + prefix.len()..];
+            let name = match rest.chars().next()? {
+                quote @ ('"' | '\'' | '`') => rest[1..].split(quote).next()?,
+                _ => rest
+                    .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                    .next()?,
+            };
+            if name.is_empty() {
+                None
+            } else {
+                Some(name.to_string())
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finds_quoted_and_bare_reads() {
+        assert_eq!(
+            env_reads(r#"cd $env.PROJECTS; echo $env."MY VAR" ($env.PATH | length)"#),
+            vec!["PROJECTS", "MY VAR", "PATH"]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, HistoryFileFormat, IntoInterruptiblePipelineData, PipelineData, ShellError,
     Signature, Span, Type, Value,
@@ -21,6 +21,10 @@ impl Command for History {
         "Get the command history"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("history")
             .input_output_types(vec![
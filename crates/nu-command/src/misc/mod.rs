@@ -1,7 +1,11 @@
+mod env_usage;
 mod history;
 mod history_session;
 mod tutor;
+mod usage;
 
+pub use env_usage::DebugEnvUsage;
 pub use history::History;
 pub use history_session::HistorySession;
 pub use tutor::Tutor;
+pub use usage::DebugUsage;
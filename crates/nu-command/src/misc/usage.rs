@@ -0,0 +1,299 @@
+use nu_parser::{lex, lite_parse, LiteElement};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, HistoryFileFormat, IntoPipelineData, PipelineData, ShellError, Signature,
+    Span, Type, Value,
+};
+use reedline::{
+    FileBackedHistory, History as ReedlineHistory, SearchDirection, SearchQuery,
+    SqliteBackedHistory,
+};
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct DebugUsage;
+
+impl Command for DebugUsage {
+    fn name(&self) -> &str {
+        "debug usage"
+    }
+
+    fn usage(&self) -> &str {
+        "Count how often the aliases, custom commands and externals are used in the history."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("debug usage")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Table(vec![])),
+                (Type::List(Box::new(Type::String)), Type::Table(vec![])),
+            ])
+            .switch(
+                "unused",
+                "only list the aliases and custom commands that are never used",
+                Some('u'),
+            )
+            .category(Category::Debug)
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Every alias and custom command in scope is listed, with the externals that were called,
+the most used first. Nothing is sent anywhere: the history file is read locally, or the command
+lines can be piped in instead.
+
+Only the command lines are counted, so a custom command only called by other custom commands
+shows up as unused."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["unused", "alias", "history", "statistics", "config"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let unused = call.has_flag("unused");
+        let lines = command_lines(engine_state, input, head)?;
+
+        let aliases: Vec<String> = engine_state
+            .get_aliases_sorted(false)
+            .map(|(name, _)| String::from_utf8_lossy(&name).to_string())
+            .collect();
+        let mut decls: HashMap<String, &'static str> = HashMap::new();
+        for (name, decl_id) in engine_state.get_decls_sorted(false) {
+            let decl = engine_state.get_decl(decl_id);
+            let kind = if decl.is_custom_command() {
+                "custom"
+            } else if decl.is_known_external() {
+                "external"
+            } else {
+                "builtin"
+            };
+            decls.insert(String::from_utf8_lossy(&name).to_string(), kind);
+        }
+
+        let mut uses: HashMap<(String, &'static str), usize> = HashMap::new();
+        for name in &aliases {
+            uses.insert((name.clone(), "alias"), 0);
+        }
+        for (name, kind) in &decls {
+            if *kind == "custom" {
+                uses.insert((name.clone(), "custom"), 0);
+            }
+        }
+
+        for line in &lines {
+            let mut commands = vec![];
+            commands_of(line.as_bytes(), &mut commands);
+            for words in commands {
+                if let Some(used) = resolve(&words, &aliases, &decls) {
+                    *uses.entry(used).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut rows: Vec<((String, &'static str), usize)> = uses
+            .into_iter()
+            .filter(|(_, count)| !unused || *count == 0)
+            .collect();
+        rows.sort_by(|((name_a, _), count_a), ((name_b, _), count_b)| {
+            count_b.cmp(count_a).then(name_a.cmp(name_b))
+        });
+
+        let vals = rows
+            .into_iter()
+            .map(|((name, kind), count)| Value::Record {
+                cols: vec!["name".into(), "kind".into(), "uses".into()],
+                vals: vec![
+                    Value::string(name, head),
+                    Value::string(kind, head),
+                    Value::int(count as i64, head),
+                ],
+                span: head,
+            })
+            .collect();
+        Ok(Value::List { vals, span: head }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the aliases and custom commands that were never used",
+                example: "debug usage --unused",
+                result: None,
+            },
+            Example {
+                description: "Find the externals called most, to write structured wrappers for",
+                example: "debug usage | where kind == external | first 10",
+                result: None,
+            },
+            Example {
+                description: "Count the uses in the commands of the current session only",
+                example: "history --long | where session_id == (history session) | get command | debug usage",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// The command lines to analyze: the input, or else the whole history
+pub fn command_lines(
+    engine_state: &EngineState,
+    input: PipelineData,
+    head: Span,
+) -> Result<Vec<String>, ShellError> {
+    match input {
+        PipelineData::Empty => read_history(engine_state, head),
+        input => input.into_iter().map(|value| value.as_string()).collect(),
+    }
+}
+
+fn read_history(engine_state: &EngineState, head: Span) -> Result<Vec<String>, ShellError> {
+    let mut history_path = nu_path::config_dir().ok_or(ShellError::FileNotFound(head))?;
+    history_path.push("nushell");
+
+    let history: Box<dyn ReedlineHistory> = match engine_state.config.history_file_format {
+        HistoryFileFormat::Sqlite => {
+            history_path.push("history.sqlite3");
+            Box::new(
+                SqliteBackedHistory::with_file(history_path)
+                    .map_err(|_| ShellError::FileNotFound(head))?,
+            )
+        }
+        HistoryFileFormat::PlainText => {
+            history_path.push("history.txt");
+            Box::new(
+                FileBackedHistory::with_file(
+                    engine_state.config.max_history_size as usize,
+                    history_path,
+                )
+                .map_err(|_| ShellError::FileNotFound(head))?,
+            )
+        }
+    };
+
+    let items = history
+        .search(SearchQuery::everything(SearchDirection::Forward))
+        .map_err(|_| ShellError::FileNotFound(head))?;
+    Ok(items.into_iter().map(|item| item.command_line).collect())
+}
+
+/// Collects the words of every command in `line`, including the commands in blocks, closures and
+/// subexpressions
+fn commands_of(line: &[u8], commands: &mut Vec<Vec<String>>) {
+    let (tokens, _) = lex(line, 0, &[], &[], true);
+    let (block, _) = lite_parse(&tokens);
+
+    for pipeline in block.block {
+        for element in pipeline.commands {
+            let command = match element {
+                LiteElement::Command(_, command) | LiteElement::Redirection(_, _, command) => {
+                    command
+                }
+                LiteElement::SeparateRedirection {
+                    out: (_, command), ..
+                } => command,
+            };
+
+            let words: Vec<&[u8]> = command
+                .parts
+                .iter()
+                .filter_map(|span| line.get(span.start..span.end))
+                .collect();
+            for word in &words {
+                if let Some(inner) = inner_code(word) {
+                    commands_of(inner, commands);
+                }
+            }
+            if !words.is_empty() {
+                commands.push(
+                    words
+                        .iter()
+                        .map(|word| String::from_utf8_lossy(word).to_string())
+                        .collect(),
+                );
+            }
+        }
+    }
+}
+
+/// The code inside a block, a closure without its parameters, or a subexpression
+fn inner_code(word: &[u8]) -> Option<&[u8]> {
+    let inner = match (word.first(), word.last()) {
+        (Some(b'{'), Some(b'}')) | (Some(b'('), Some(b')')) if word.len() >= 2 => {
+            &word[1..word.len() - 1]
+        }
+        _ => return None,
+    };
+
+    let start = inner.iter().position(|c| !c.is_ascii_whitespace())?;
+    let inner = &inner[start..];
+    if inner.first() == Some(&b'|') {
+        let end = inner[1..].iter().position(|c| *c == b'|')?;
+        Some(&inner[end + 2..])
+    } else {
+        Some(inner)
+    }
+}
+
+/// What the command with these words calls: the longest alias or command name its words start
+/// with, or else an external. Builtins and values in command position, like the fields of a
+/// record, aren't counted.
+fn resolve(
+    words: &[String],
+    aliases: &[String],
+    decls: &HashMap<String, &'static str>,
+) -> Option<(String, &'static str)> {
+    let first = words.first()?;
+    if let Some(external) = first.strip_prefix('^') {
+        return Some((external.to_string(), "external"));
+    }
+
+    for n in (1..=words.len()).rev() {
+        let name = words[..n].join(" ");
+        if aliases.contains(&name) {
+            return Some((name, "alias"));
+        }
+        match decls.get(&name) {
+            Some(&"builtin") => return None,
+            Some(kind) => return Some((name, *kind)),
+            None => {}
+        }
+    }
+
+    let is_value = first.starts_with(['$', '"', '\'', '`', '(', '[', '{', '#', '-'])
+        || first.ends_with(':')
+        || first.parse::<f64>().is_ok();
+    if is_value {
+        None
+    } else {
+        Some((first.clone(), "external"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn commands(line: &str) -> Vec<Vec<String>> {
+        let mut commands = vec![];
+        commands_of(line.as_bytes(), &mut commands);
+        commands
+    }
+
+    #[test]
+    fn finds_commands_in_blocks_and_closures() {
+        let found: Vec<String> = commands("ls | each {|it| greet $it.name } | (gs)")
+            .into_iter()
+            .map(|words| words[0].clone())
+            .collect();
+
+        assert_eq!(found, vec!["ls", "greet", "each", "gs", "(gs)"]);
+    }
+}
@@ -0,0 +1,5 @@
+mod dns_;
+mod query;
+
+pub use dns_::Dns;
+pub use query::SubCommand as DnsQuery;
@@ -0,0 +1,332 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::str::FromStr;
+use std::time::Duration;
+use trust_dns_proto::op::{Edns, Message, MessageType, OpCode, Query, ResponseCode};
+use trust_dns_proto::rr::{Name, Record, RecordType};
+
+const DNS_PORT: u16 = 53;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+// The largest answer asked for over UDP, small enough to avoid fragmentation
+const MAX_UDP_PAYLOAD: u16 = 1232;
+// The largest datagram UDP can carry
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "dns query"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("dns query")
+            .input_output_types(vec![(Type::Nothing, Type::Table(vec![]))])
+            .allow_variants_without_examples(true)
+            .required("name", SyntaxShape::String, "the domain name to look up")
+            .named(
+                "type",
+                SyntaxShape::String,
+                "the type of record to ask for, e.g. MX or TXT (default A)",
+                Some('t'),
+            )
+            .named(
+                "server",
+                SyntaxShape::String,
+                "the name server to ask, as an address or address:port (default: the system's)",
+                Some('s'),
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the answer (default 5sec)",
+                None,
+            )
+            .switch("tcp", "ask over TCP instead of UDP", None)
+            .switch(
+                "dnssec",
+                "ask for the DNSSEC signatures of the records as well",
+                Some('d'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Look up the DNS records of a domain name."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Returns a row for every record of the answer, with its name, type, time to live and data.
+The authenticated column is true when the name server has validated the answer with DNSSEC.
+
+Answers too large for UDP are asked for again over TCP. An error is returned when the name
+server does not answer, or answers with an error such as a name that does not exist."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "dig", "nslookup", "resolve", "domain", "record"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let name: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let record_type: Option<Spanned<String>> = call.get_flag(engine_state, stack, "type")?;
+        let server: Option<Spanned<String>> = call.get_flag(engine_state, stack, "server")?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let tcp = call.has_flag("tcp");
+        let dnssec = call.has_flag("dnssec");
+        let span = call.head;
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => DEFAULT_TIMEOUT,
+        };
+
+        let record_type = match record_type {
+            Some(record_type) => {
+                RecordType::from_str(&record_type.item.to_uppercase()).map_err(|_| {
+                    ShellError::UnsupportedInput(
+                        format!("Unknown record type {}", record_type.item),
+                        "value originates from here".into(),
+                        span,
+                        record_type.span,
+                    )
+                })?
+            }
+            None => RecordType::A,
+        };
+
+        let mut domain = Name::from_str(&name.item).map_err(|e| {
+            ShellError::UnsupportedInput(
+                format!("Invalid domain name: {e}"),
+                "value originates from here".into(),
+                span,
+                name.span,
+            )
+        })?;
+        domain.set_fqdn(true);
+
+        let server_address = match &server {
+            Some(server) => server_address(server)?,
+            None => system_server_address(span)?,
+        };
+
+        let mut edns = Edns::new();
+        edns.set_max_payload(MAX_UDP_PAYLOAD).set_dnssec_ok(dnssec);
+
+        let mut message = Message::new();
+        message
+            .set_id(rand::random())
+            .set_message_type(MessageType::Query)
+            .set_op_code(OpCode::Query)
+            .set_recursion_desired(true)
+            // Asks the server to tell whether it validated the answer
+            .set_authentic_data(true)
+            .add_query(Query::query(domain, record_type))
+            .set_edns(edns);
+
+        let network_error = |e: String| {
+            ShellError::NetworkFailure(format!("Cannot query {server_address}: {e}"), span)
+        };
+
+        let request = message.to_vec().map_err(|e| network_error(e.to_string()))?;
+        let response = if tcp {
+            query_tcp(server_address, &request, timeout)
+        } else {
+            query_udp(server_address, &request, message.id(), timeout).and_then(|response| {
+                if response.truncated() {
+                    query_tcp(server_address, &request, timeout)
+                } else {
+                    Ok(response)
+                }
+            })
+        }
+        .map_err(network_error)?;
+
+        if response.response_code() != ResponseCode::NoError {
+            return Err(ShellError::NetworkFailure(
+                format!(
+                    "{record_type} query for {} failed: {}",
+                    name.item,
+                    response.response_code()
+                ),
+                name.span,
+            ));
+        }
+
+        let authenticated = response.authentic_data();
+        let records = response
+            .answers()
+            .iter()
+            .map(|record| record_to_value(record, authenticated, span))
+            .collect();
+
+        Ok(Value::List {
+            vals: records,
+            span,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Look up the addresses of a domain",
+                example: "dns query example.com",
+                result: None,
+            },
+            Example {
+                description: "Ask a given name server for the mail servers of a domain",
+                example: "dns query example.com --type MX --server 1.1.1.1",
+                result: None,
+            },
+            Example {
+                description: "Check that the records of a domain are validated with DNSSEC",
+                example: "dns query --dnssec example.com | all {|record| $record.authenticated }",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn record_to_value(record: &Record, authenticated: bool, span: Span) -> Value {
+    let data = record
+        .data()
+        .map(|data| data.to_string())
+        .unwrap_or_default();
+
+    Value::Record {
+        cols: vec![
+            "name".into(),
+            "type".into(),
+            "ttl".into(),
+            "data".into(),
+            "authenticated".into(),
+        ],
+        vals: vec![
+            Value::string(record.name().to_string(), span),
+            Value::string(record.record_type().to_string(), span),
+            Value::Duration {
+                val: Duration::from_secs(record.ttl() as u64).as_nanos() as i64,
+                span,
+            },
+            Value::string(data, span),
+            Value::boolean(authenticated, span),
+        ],
+        span,
+    }
+}
+
+// A bare address or host name is asked on the DNS port
+fn server_address(server: &Spanned<String>) -> Result<SocketAddr, ShellError> {
+    if let Ok(address) = server.item.parse::<SocketAddr>() {
+        return Ok(address);
+    }
+    if let Ok(ip) = server.item.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, DNS_PORT));
+    }
+
+    let addresses = if server.item.contains(':') {
+        server.item.to_socket_addrs()
+    } else {
+        (server.item.as_str(), DNS_PORT).to_socket_addrs()
+    };
+
+    addresses
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| {
+            ShellError::NetworkFailure(
+                format!("Cannot resolve the name server {}", server.item),
+                server.span,
+            )
+        })
+}
+
+fn system_server_address(span: Span) -> Result<SocketAddr, ShellError> {
+    let (config, _) = trust_dns_resolver::system_conf::read_system_conf().map_err(|e| {
+        ShellError::NetworkFailure(
+            format!("Cannot read the name servers of the system, use --server instead: {e}"),
+            span,
+        )
+    })?;
+
+    config
+        .name_servers()
+        .first()
+        .map(|name_server| name_server.socket_addr)
+        .ok_or_else(|| {
+            ShellError::NetworkFailure(
+                "The system has no name server, use --server instead".into(),
+                span,
+            )
+        })
+}
+
+fn query_udp(
+    server: SocketAddr,
+    request: &[u8],
+    id: u16,
+    timeout: Duration,
+) -> Result<Message, String> {
+    let local: SocketAddr = match server {
+        SocketAddr::V4(_) => ([0, 0, 0, 0], 0).into(),
+        SocketAddr::V6(_) => ([0u16; 8], 0).into(),
+    };
+    let socket = UdpSocket::bind(local).map_err(io_error)?;
+    socket.set_read_timeout(Some(timeout)).map_err(io_error)?;
+    socket.send_to(request, server).map_err(io_error)?;
+
+    let mut buffer = vec![0; MAX_DATAGRAM_SIZE];
+    loop {
+        let (size, from) = socket.recv_from(&mut buffer).map_err(io_error)?;
+        // Datagrams from elsewhere, or answers to other queries, are not ours
+        if from != server {
+            continue;
+        }
+        let response = Message::from_vec(&buffer[..size]).map_err(|e| e.to_string())?;
+        if response.id() == id {
+            return Ok(response);
+        }
+    }
+}
+
+// Over TCP, messages are preceded by their length
+fn query_tcp(server: SocketAddr, request: &[u8], timeout: Duration) -> Result<Message, String> {
+    let mut stream = TcpStream::connect_timeout(&server, timeout).map_err(io_error)?;
+    stream.set_read_timeout(Some(timeout)).map_err(io_error)?;
+
+    let mut framed = (request.len() as u16).to_be_bytes().to_vec();
+    framed.extend_from_slice(request);
+    stream.write_all(&framed).map_err(io_error)?;
+
+    let mut length = [0; 2];
+    stream.read_exact(&mut length).map_err(io_error)?;
+    let mut response = vec![0; u16::from_be_bytes(length) as usize];
+    stream.read_exact(&mut response).map_err(io_error)?;
+
+    Message::from_vec(&response).map_err(|e| e.to_string())
+}
+
+fn io_error(e: std::io::Error) -> String {
+    match e.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => "no answer within the timeout".into(),
+        _ => e.to_string(),
+    }
+}
@@ -0,0 +1,330 @@
+use nu_engine::CallExt;
+use nu_parser::{ModulePin, ParseError, RemoteModule, RemoteSource};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use reqwest::Proxy;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// How the module is downloaded
+struct Download {
+    timeout: Duration,
+    proxy: Option<Spanned<String>>,
+}
+
+#[derive(Clone)]
+pub struct FetchModule;
+
+impl Command for FetchModule {
+    fn name(&self) -> &str {
+        "fetch-module"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("fetch-module")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .required(
+                "URL",
+                SyntaxShape::String,
+                "the https URL of the module file, or git+<repository>.git/<file>",
+            )
+            .named(
+                "hash",
+                SyntaxShape::String,
+                "the sha256 hash the module file must have",
+                None,
+            )
+            .named(
+                "tag",
+                SyntaxShape::String,
+                "the tag or commit to fetch a module from a git repository at",
+                None,
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the server (default 30sec)",
+                Some('t'),
+            )
+            .named(
+                "proxy",
+                SyntaxShape::String,
+                "URL of the proxy to fetch the module through, e.g. http://proxy.corp:3128",
+                None,
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Fetch a remote module into the cache, for use and overlay use."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Modules are fetched from https URLs, pinned with --hash, or from git repositories written
+as git+<repository>.git/<file>, pinned with --tag, --hash or both. The module is only fetched
+again when the pin changes, and the path of the cached file is returned.
+
+`use` and `overlay use` are parsed before anything runs, so they never fetch a module
+themselves: they only find it in the cache, when given the same URL and pin. A module is
+therefore fetched once beforehand, or from env.nu, which runs before config.nu is parsed, so
+that config.nu can use it."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["download", "remote", "use", "git"]
+    }
+
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::Network, Capability::External]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let url: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let pin = ModulePin {
+            hash: call.get_flag(engine_state, stack, "hash")?,
+            tag: call.get_flag(engine_state, stack, "tag")?,
+        };
+
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => DEFAULT_TIMEOUT,
+        };
+        let download = Download {
+            timeout,
+            proxy: call.get_flag(engine_state, stack, "proxy")?,
+        };
+
+        let module = RemoteModule::new(&url.item, &pin, url.span).map_err(into_shell_error)?;
+        let cache_root = RemoteModule::cache_root(url.span).map_err(into_shell_error)?;
+        let path = fetch(&module, &cache_root, &download, url.span)?;
+
+        Ok(Value::string(path.to_string_lossy(), call.head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Fetch a module pinned with its hash, then use it",
+                example: "fetch-module https://example.com/tools.nu --hash 2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae; use https://example.com/tools.nu --hash 2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae",
+                result: None,
+            },
+            Example {
+                description: "Fetch a module from a tag of a git repository",
+                example: "fetch-module git+https://example.com/team/tools.git/tools.nu --tag v1.0.0",
+                result: None,
+            },
+            Example {
+                description: "In env.nu, fetch a module that config.nu uses",
+                example: "fetch-module https://example.com/tools.nu --hash 2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae | ignore",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn fetch(
+    module: &RemoteModule,
+    cache_root: &Path,
+    download: &Download,
+    span: Span,
+) -> Result<PathBuf, ShellError> {
+    let path = module.cached_path(cache_root);
+
+    match &module.source {
+        RemoteSource::File { url, .. } => {
+            // The cache is addressed by the hash, so a cached file is the pinned one unless it
+            // was edited
+            if is_pinned_file(&path, module.hash.as_deref()) {
+                return Ok(path);
+            }
+
+            let contents = http_client(download, span)?
+                .get(url)
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.bytes())
+                .map_err(|e| fetch_failed(e.to_string(), span))?;
+            check_hash(module.hash.as_deref(), &contents, span)?;
+            write_file(&path, &contents, span)?;
+        }
+        RemoteSource::Git {
+            repository,
+            revision,
+            file,
+        } => {
+            if is_pinned_file(&path, module.hash.as_deref()) {
+                return Ok(path);
+            }
+
+            let checkout = module
+                .checkout_dir(cache_root)
+                .ok_or_else(|| fetch_failed("The module has no checkout".into(), span))?;
+            let _ = fs::remove_dir_all(&checkout);
+            fs::create_dir_all(&checkout).map_err(|e| fetch_failed(e.to_string(), span))?;
+            git(&checkout, &["init", "--quiet"], span)?;
+            // git gives up when the transfer stalls for longer than the timeout
+            let mut fetch = vec![
+                "-c".to_string(),
+                "http.lowSpeedLimit=1".to_string(),
+                "-c".to_string(),
+                format!("http.lowSpeedTime={}", download.timeout.as_secs().max(1)),
+            ];
+            if let Some(proxy) = &download.proxy {
+                fetch.push("-c".into());
+                fetch.push(format!("http.proxy={}", proxy.item));
+            }
+            fetch.extend(
+                [
+                    "fetch",
+                    "--quiet",
+                    "--depth",
+                    "1",
+                    "--",
+                    repository.as_str(),
+                    revision.as_str(),
+                ]
+                .map(String::from),
+            );
+            git(&checkout, &fetch, span)?;
+            git(&checkout, &["checkout", "--quiet", "FETCH_HEAD"], span)?;
+
+            let contents = fs::read(&path).map_err(|_| {
+                fetch_failed(
+                    format!("The repository has no file {file} at {revision}"),
+                    span,
+                )
+            })?;
+            if let Err(e) = check_hash(module.hash.as_deref(), &contents, span) {
+                let _ = fs::remove_dir_all(&checkout);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+fn git(dir: &Path, args: &[impl AsRef<std::ffi::OsStr>], span: Span) -> Result<(), ShellError> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| fetch_failed(format!("Can't run git: {e}"), span))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(fetch_failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            span,
+        ))
+    }
+}
+
+// Honors the proxy environment variables like the http commands, unless --proxy is given
+fn http_client(download: &Download, span: Span) -> Result<reqwest::blocking::Client, ShellError> {
+    let mut builder = reqwest::blocking::Client::builder()
+        .user_agent("nushell")
+        .connect_timeout(download.timeout)
+        .timeout(download.timeout);
+
+    if let Some(proxy) = &download.proxy {
+        let proxy = Proxy::all(&proxy.item).map_err(|e| {
+            ShellError::GenericError(
+                "Invalid proxy URL".into(),
+                e.to_string(),
+                Some(proxy.span),
+                None,
+                Vec::new(),
+            )
+        })?;
+        builder = builder.proxy(proxy);
+    }
+
+    builder
+        .build()
+        .map_err(|e| fetch_failed(e.to_string(), span))
+}
+
+/// Writes the file through a temporary one, so an interrupted write doesn't leave half a module
+/// in the cache
+fn write_file(path: &Path, contents: &[u8], span: Span) -> Result<(), ShellError> {
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temporary = path.with_extension("part");
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, path)
+    };
+
+    write().map_err(|e| fetch_failed(e.to_string(), span))
+}
+
+fn is_pinned_file(path: &Path, hash: Option<&str>) -> bool {
+    match (fs::read(path), hash) {
+        (Ok(contents), Some(hash)) => hex_digest(&contents) == hash,
+        (Ok(_), None) => true,
+        (Err(_), _) => false,
+    }
+}
+
+fn check_hash(hash: Option<&str>, contents: &[u8], span: Span) -> Result<(), ShellError> {
+    let actual = hex_digest(contents);
+    match hash {
+        Some(hash) if hash != actual => Err(ShellError::GenericError(
+            "Remote module doesn't match its hash".into(),
+            "module contents changed".into(),
+            Some(span),
+            Some(format!("Expected the sha256 hash {hash}, but the module has {actual}. If the new contents are the ones you want, update the hash.")),
+            Vec::new(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn hex_digest(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+fn fetch_failed(help: String, span: Span) -> ShellError {
+    ShellError::GenericError(
+        "Can't fetch remote module".into(),
+        "can't fetch this module".into(),
+        Some(span),
+        Some(help),
+        Vec::new(),
+    )
+}
+
+fn into_shell_error(error: ParseError) -> ShellError {
+    let span = error.span();
+    let (label, help) = match &error {
+        ParseError::RemoteModuleNotPinned(help, _)
+        | ParseError::RemoteModuleFetchFailed(help, _) => {
+            ("can't fetch this module".to_string(), Some(help.clone()))
+        }
+        ParseError::LabeledError(_, label, _) => (label.clone(), None),
+        _ => (String::new(), None),
+    };
+
+    ShellError::GenericError(error.to_string(), label, Some(span), help, Vec::new())
+}
@@ -1,9 +1,554 @@
+use crate::network::http::session::HttpSession;
+#[cfg(unix)]
+use crate::network::http::unix_socket::UnixSocketProxy;
+use nu_engine::{current_dir, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::util::BufferedReader;
+use nu_protocol::{
+    DataSource, IntoPipelineData, PipelineData, PipelineMetadata, RawStream, ShellError, Signature,
+    Span, Spanned, SyntaxShape, Value,
+};
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Identity, Proxy};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Statuses worth retrying when --retries is given without --retry-on
+const DEFAULT_RETRY_ON: [u16; 4] = [429, 502, 503, 504];
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+
+// How a request reaches the server, how it is retried when it fails, and how long it may
+// wait for the server
+pub struct RequestPolicy {
+    pub retries: u64,
+    pub backoff: Duration,
+    pub retry_on: Vec<u16>,
+    pub connect_timeout: Option<Duration>,
+    pub read_timeout: Option<Duration>,
+    pub proxy: Option<Proxy>,
+    pub identity: Option<Identity>,
+    pub ca_certificate: Option<Certificate>,
+    #[cfg(unix)]
+    pub unix_socket: Option<UnixSocketProxy>,
+}
+
+impl RequestPolicy {
+    // The flags shared by all the http subcommands
+    pub fn add_flags(signature: Signature) -> Signature {
+        signature
+            .named(
+                "retries",
+                SyntaxShape::Int,
+                "number of times to retry a failed request",
+                None,
+            )
+            .named(
+                "backoff",
+                SyntaxShape::Duration,
+                "delay before the first retry, doubled for every other one (default 1sec)",
+                None,
+            )
+            .named(
+                "retry-on",
+                SyntaxShape::List(Box::new(SyntaxShape::Int)),
+                "response statuses to retry (default [429 502 503 504])",
+                None,
+            )
+            .named(
+                "connect-timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the connection to the server",
+                None,
+            )
+            .named(
+                "read-timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the whole response",
+                None,
+            )
+            .named(
+                "session",
+                SyntaxShape::Any,
+                "session created with `http session create`, to keep cookies across requests",
+                Some('s'),
+            )
+            .named(
+                "proxy",
+                SyntaxShape::String,
+                "URL of the proxy to send the request through, e.g. http://proxy.corp:3128",
+                None,
+            )
+            .named(
+                "unix-socket",
+                SyntaxShape::Filepath,
+                "send the request over a unix socket instead of the network (http URLs only)",
+                None,
+            )
+            .named(
+                "cert",
+                SyntaxShape::Filepath,
+                "PEM file of the client certificate to authenticate with",
+                None,
+            )
+            .named(
+                "key",
+                SyntaxShape::Filepath,
+                "PEM file of the PKCS#8 private key of the client certificate (default: the --cert file)",
+                None,
+            )
+            .named(
+                "cacert",
+                SyntaxShape::Filepath,
+                "PEM file of a certificate authority to trust besides the system ones",
+                None,
+            )
+    }
+
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Self, ShellError> {
+        let retries: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "retries")?;
+        let retries = match retries {
+            Some(retries) if retries.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(retries.span))
+            }
+            Some(retries) => retries.item as u64,
+            None => 0,
+        };
+
+        let retry_on: Option<Vec<Value>> = call.get_flag(engine_state, stack, "retry-on")?;
+        let retry_on = match retry_on {
+            Some(statuses) => statuses
+                .iter()
+                .map(|status| match status.as_i64()? {
+                    code @ 100..=599 => Ok(code as u16),
+                    _ => Err(ShellError::TypeMismatch(
+                        "HTTP status code between 100 and 599".into(),
+                        status.span()?,
+                    )),
+                })
+                .collect::<Result<Vec<u16>, ShellError>>()?,
+            None => DEFAULT_RETRY_ON.to_vec(),
+        };
+
+        let proxy: Option<Spanned<String>> = call.get_flag(engine_state, stack, "proxy")?;
+        let unix_socket: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "unix-socket")?;
+        let cert: Option<Spanned<String>> = call.get_flag(engine_state, stack, "cert")?;
+        let key: Option<Spanned<String>> = call.get_flag(engine_state, stack, "key")?;
+        let cacert: Option<Spanned<String>> = call.get_flag(engine_state, stack, "cacert")?;
+        let cwd = current_dir(engine_state, stack)?;
+
+        if let (Some(proxy), Some(unix_socket)) = (&proxy, &unix_socket) {
+            return Err(ShellError::IncompatibleParameters {
+                left_message: "the request can go through a proxy".into(),
+                left_span: proxy.span,
+                right_message: "or over a unix socket, not both".into(),
+                right_span: unix_socket.span,
+            });
+        }
+
+        #[cfg(not(unix))]
+        if let Some(socket) = &unix_socket {
+            return Err(ShellError::UnsupportedInput(
+                "Unix sockets are not supported on this platform".into(),
+                "value originates from here".into(),
+                call.head,
+                socket.span,
+            ));
+        }
+
+        let proxy = proxy
+            .map(|proxy| {
+                Proxy::all(&proxy.item).map_err(|e| {
+                    ShellError::UnsupportedInput(
+                        format!("Invalid proxy URL: {e}"),
+                        "value originates from here".into(),
+                        call.head,
+                        proxy.span,
+                    )
+                })
+            })
+            .transpose()?;
+
+        // The key may be in the same file as the certificate
+        let identity = match (cert, key) {
+            (Some(cert), key) => {
+                let cert_pem = read_pem(&cert, &cwd)?;
+                let key_pem = match &key {
+                    Some(key) => read_pem(key, &cwd)?,
+                    None => cert_pem.clone(),
+                };
+                let identity = Identity::from_pkcs8_pem(&cert_pem, &key_pem).map_err(|e| {
+                    ShellError::UnsupportedInput(
+                        format!("Invalid client certificate or key: {e}"),
+                        "value originates from here".into(),
+                        call.head,
+                        key.as_ref().map(|key| key.span).unwrap_or(cert.span),
+                    )
+                })?;
+                Some(identity)
+            }
+            (None, Some(key)) => {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    "--key needs the client certificate given with --cert".into(),
+                    key.span,
+                ))
+            }
+            (None, None) => None,
+        };
+
+        let ca_certificate = cacert
+            .map(|cacert| {
+                Certificate::from_pem(&read_pem(&cacert, &cwd)?).map_err(|e| {
+                    ShellError::UnsupportedInput(
+                        format!("Invalid certificate authority: {e}"),
+                        "value originates from here".into(),
+                        call.head,
+                        cacert.span,
+                    )
+                })
+            })
+            .transpose()?;
+
+        Ok(RequestPolicy {
+            retries,
+            backoff: duration_flag(engine_state, stack, call, "backoff")?
+                .unwrap_or(DEFAULT_BACKOFF),
+            retry_on,
+            connect_timeout: duration_flag(engine_state, stack, call, "connect-timeout")?,
+            read_timeout: duration_flag(engine_state, stack, call, "read-timeout")?,
+            proxy,
+            identity,
+            ca_certificate,
+            #[cfg(unix)]
+            unix_socket: unix_socket
+                .map(|socket| start_unix_socket_proxy(&socket, &cwd))
+                .transpose()?,
+        })
+    }
+}
+
+fn read_pem(path: &Spanned<String>, cwd: &Path) -> Result<Vec<u8>, ShellError> {
+    std::fs::read(nu_path::expand_path_with(&path.item, cwd)).map_err(|e| {
+        ShellError::IOErrorSpanned(format!("Cannot read {}: {e}", path.item), path.span)
+    })
+}
+
+#[cfg(unix)]
+fn start_unix_socket_proxy(
+    socket: &Spanned<String>,
+    cwd: &Path,
+) -> Result<UnixSocketProxy, ShellError> {
+    UnixSocketProxy::start(nu_path::expand_path_with(&socket.item, cwd)).map_err(|e| {
+        ShellError::IOErrorSpanned(
+            format!("Cannot forward requests to {}: {e}", socket.item),
+            socket.span,
+        )
+    })
+}
+
+fn duration_flag(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    name: &str,
+) -> Result<Option<Duration>, ShellError> {
+    let duration: Option<Spanned<i64>> = call.get_flag(engine_state, stack, name)?;
+    match duration {
+        Some(duration) if duration.item < 0 => Err(ShellError::NeedsPositiveValue(duration.span)),
+        Some(duration) => Ok(Some(Duration::from_nanos(duration.item as u64))),
+        None => Ok(None),
+    }
+}
+
 // Only panics if the user agent is invalid but we define it statically so either
 // it always or never fails
-pub fn http_client(allow_insecure: bool) -> reqwest::blocking::Client {
-    reqwest::blocking::Client::builder()
+pub fn http_client(
+    allow_insecure: bool,
+    policy: &RequestPolicy,
+    session: Option<&HttpSession>,
+) -> Result<reqwest::blocking::Client, ShellError> {
+    let mut builder = reqwest::blocking::Client::builder()
         .user_agent("nushell")
-        .danger_accept_invalid_certs(allow_insecure)
-        .build()
-        .expect("Failed to build reqwest client")
+        .danger_accept_invalid_certs(allow_insecure);
+
+    if let Some(timeout) = policy.connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+
+    if let Some(proxy) = &policy.proxy {
+        builder = builder.proxy(proxy.clone());
+    }
+
+    #[cfg(unix)]
+    if let Some(unix_socket) = &policy.unix_socket {
+        let proxy = unix_socket
+            .proxy()
+            .map_err(|e| ShellError::IOError(e.to_string()))?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(identity) = &policy.identity {
+        builder = builder.identity(identity.clone());
+    }
+
+    if let Some(certificate) = &policy.ca_certificate {
+        builder = builder.add_root_certificate(certificate.clone());
+    }
+
+    if let Some(session) = session {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &session.headers {
+            let name = HeaderName::from_str(name).map_err(|e| invalid_header(name, e))?;
+            let value = HeaderValue::from_str(value).map_err(|e| invalid_header(value, e))?;
+            headers.insert(name, value);
+        }
+
+        builder = builder
+            .default_headers(headers)
+            .cookie_provider(session.cookies.clone());
+    }
+
+    Ok(builder.build().expect("Failed to build reqwest client"))
+}
+
+fn invalid_header(header: &str, error: impl std::fmt::Display) -> ShellError {
+    ShellError::GenericError(
+        format!("Invalid header in session: {header}"),
+        error.to_string(),
+        None,
+        None,
+        Vec::new(),
+    )
+}
+
+// Sends the request, retrying it on connection failures, timeouts and the statuses
+// of the policy. The delay between retries doubles every time
+pub fn send_request(
+    mut request: RequestBuilder,
+    policy: &RequestPolicy,
+    ctrlc: &Option<Arc<AtomicBool>>,
+) -> Result<Response, reqwest::Error> {
+    if let Some(timeout) = policy.read_timeout {
+        request = request.timeout(timeout);
+    }
+
+    let mut backoff = policy.backoff;
+    for _ in 0..policy.retries {
+        // Requests with a streamed body can't be sent again
+        let attempt = match request.try_clone() {
+            Some(attempt) => attempt,
+            None => break,
+        };
+
+        match attempt.send() {
+            Ok(response) if !policy.retry_on.contains(&response.status().as_u16()) => {
+                return Ok(response)
+            }
+            Err(e) if !(e.is_timeout() || e.is_connect()) => return Err(e),
+            _ => {}
+        }
+
+        std::thread::sleep(backoff);
+        backoff *= 2;
+
+        if let Some(ctrlc) = ctrlc {
+            if ctrlc.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+    }
+
+    request.send()
+}
+
+// Streams the body of the response as it arrives, instead of reading it whole.
+// The content type is kept in the metadata of the stream
+pub fn response_to_buffer(
+    response: Response,
+    engine_state: &EngineState,
+    span: Span,
+) -> PipelineData {
+    // Try to get the size of the file to be downloaded.
+    // This is helpful to show the progress of the stream.
+    let buffer_size = response
+        .headers()
+        .get("content-length")
+        .and_then(|content_length| content_length.to_str().ok())
+        .and_then(|content_length| content_length.parse::<u64>().ok())
+        .filter(|content_length| *content_length > 0);
+
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(|content_type| content_type.to_string());
+
+    let buffered_input = BufReader::new(response);
+
+    PipelineData::ExternalStream {
+        stdout: Some(RawStream::new(
+            Box::new(BufferedReader {
+                input: buffered_input,
+            }),
+            engine_state.ctrlc.clone(),
+            span,
+            buffer_size,
+        )),
+        stderr: None,
+        exit_code: None,
+        resource_usage: None,
+        span,
+        metadata: Some(PipelineMetadata {
+            data_source: DataSource::Http { content_type },
+        }),
+        trim_end_newline: false,
+    }
+}
+
+// Reads the body of the response, converting it to structured data based on its content type
+// unless raw is set. A text/plain body is converted based on the extension of the URL instead
+pub fn convert_response(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    response: Response,
+    requested_url: &str,
+    raw: bool,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    let content_type = match response.headers().get("content-type") {
+        Some(content_type) => content_type,
+        None => return Ok(response_to_buffer(response, engine_state, span)),
+    };
+
+    let content_type = content_type.to_str().map_err(|e| {
+        ShellError::GenericError(
+            e.to_string(),
+            "".to_string(),
+            None,
+            Some("MIME type were invalid".to_string()),
+            Vec::new(),
+        )
+    })?;
+    let content_type = mime::Mime::from_str(content_type).map_err(|_| {
+        ShellError::GenericError(
+            format!("MIME type unknown: {content_type}"),
+            "".to_string(),
+            None,
+            Some("given unknown MIME type".to_string()),
+            Vec::new(),
+        )
+    })?;
+    let ext = match (content_type.type_(), content_type.subtype()) {
+        (mime::TEXT, mime::PLAIN) => url::Url::parse(requested_url)
+            .map_err(|_| {
+                ShellError::GenericError(
+                    format!("Cannot parse URL: {requested_url}"),
+                    "".to_string(),
+                    None,
+                    Some("cannot parse".to_string()),
+                    Vec::new(),
+                )
+            })?
+            .path_segments()
+            .and_then(|segments| segments.last())
+            .and_then(|name| if name.is_empty() { None } else { Some(name) })
+            .and_then(|name| {
+                PathBuf::from(name)
+                    .extension()
+                    .map(|name| name.to_string_lossy().to_string())
+            }),
+        _ => Some(content_type.subtype().to_string()),
+    };
+
+    let output = response_to_buffer(response, engine_state, span);
+
+    if raw {
+        return Ok(output);
+    }
+
+    match ext.and_then(|ext| engine_state.find_decl(format!("from {ext}").as_bytes(), &[])) {
+        Some(converter_id) => {
+            engine_state
+                .get_decl(converter_id)
+                .run(engine_state, stack, &Call::new(span), output)
+        }
+        None => Ok(output),
+    }
+}
+
+// What --full returns besides the body: the status, the headers and the final URL
+// of the response, taken before the body is read
+pub struct ResponseInfo {
+    status: u16,
+    headers: Value,
+    url: String,
+    start: Instant,
+}
+
+impl ResponseInfo {
+    pub fn new(response: &Response, start: Instant, span: Span) -> Self {
+        let mut cols: Vec<String> = vec![];
+        let mut vals: Vec<Value> = vec![];
+
+        // Repeated headers are joined, the way HTTP allows them to be combined
+        for (name, value) in response.headers() {
+            let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+            match cols.iter().position(|col| col == name.as_str()) {
+                Some(index) => {
+                    if let Value::String { val, .. } = &mut vals[index] {
+                        val.push_str(", ");
+                        val.push_str(&value);
+                    }
+                }
+                None => {
+                    cols.push(name.to_string());
+                    vals.push(Value::string(value, span));
+                }
+            }
+        }
+
+        ResponseInfo {
+            status: response.status().as_u16(),
+            headers: Value::Record { cols, vals, span },
+            url: response.url().to_string(),
+            start,
+        }
+    }
+
+    // The elapsed time includes reading the body, which is collected into the record
+    pub fn into_full(self, body: PipelineData, span: Span) -> PipelineData {
+        let body = body.into_value(span);
+
+        Value::Record {
+            cols: vec![
+                "status".into(),
+                "headers".into(),
+                "body".into(),
+                "elapsed".into(),
+                "url".into(),
+            ],
+            vals: vec![
+                Value::int(self.status as i64, span),
+                self.headers,
+                body,
+                Value::Duration {
+                    val: self.start.elapsed().as_nanos() as i64,
+                    span,
+                },
+                Value::string(self.url, span),
+            ],
+            span,
+        }
+        .into_pipeline_data()
+    }
 }
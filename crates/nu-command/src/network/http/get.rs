@@ -1,20 +1,17 @@
-use crate::network::http::client::http_client;
+use crate::network::http::client::{
+    convert_response, http_client, send_request, RequestPolicy, ResponseInfo,
+};
+use crate::network::http::session::HttpSession;
 use base64::{alphabet, engine::general_purpose::PAD, engine::GeneralPurpose, Engine};
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::util::BufferedReader;
-use nu_protocol::RawStream;
 use nu_protocol::{
     Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
-use reqwest::blocking::Response;
 use reqwest::StatusCode;
 use std::collections::HashMap;
-use std::io::BufReader;
-use std::path::PathBuf;
-use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -25,7 +22,7 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("http get")
+        let signature = Signature::build("http get")
             .input_output_types(vec![(Type::Nothing, Type::Any)])
             .required(
                 "URL",
@@ -66,6 +63,18 @@ impl Command for SubCommand {
                 "allow insecure server connections when using SSL",
                 Some('k'),
             )
+            .switch(
+                "full",
+                "return a record with the status, headers, body, elapsed time and URL of the response",
+                Some('f'),
+            )
+            .switch(
+                "allow-errors",
+                "return the response of 4xx and 5xx statuses instead of an error",
+                Some('e'),
+            );
+
+        RequestPolicy::add_flags(signature)
             .filter()
             .category(Category::Network)
     }
@@ -75,7 +84,18 @@ impl Command for SubCommand {
     }
 
     fn extra_usage(&self) -> &str {
-        "Performs HTTP GET operation."
+        r#"Performs HTTP GET operation.
+
+The response body is streamed as it arrives, unless it is converted to structured data
+based on its content type. With --raw, the body is always streamed, and its content type
+is kept in the pipeline metadata.
+
+With --full, the body is collected into a record along with the status, headers, elapsed
+time and final URL of the response. With --allow-errors, 4xx and 5xx responses are returned
+like any other instead of failing, so the status can be checked.
+
+With --unix-socket, the request is sent over the socket, the way the Docker API is reached,
+and the host of the URL only fills in the Host header."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -111,6 +131,31 @@ impl Command for SubCommand {
                 example: "http get -H [my-header-key my-header-value] https://www.example.com",
                 result: None,
             },
+            Example {
+                description: "http get content from a flaky API, retrying up to 3 times",
+                example: "http get --retries 3 --backoff 500ms --retry-on [429 503] https://www.example.com",
+                result: None,
+            },
+            Example {
+                description: "Check the status of a response instead of failing on errors",
+                example: "http get --full --allow-errors https://www.example.com | get status",
+                result: None,
+            },
+            Example {
+                description: "List the running containers from the Docker API",
+                example: "http get --unix-socket /var/run/docker.sock http://localhost/containers/json",
+                result: None,
+            },
+            Example {
+                description: "Authenticate with a client certificate, through a proxy",
+                example: "http get --proxy http://proxy.corp:3128 --cert client.pem --key client.key --cacert corp-ca.pem https://internal.corp",
+                result: None,
+            },
+            Example {
+                description: "Download a file to disk without reading it all in memory",
+                example: "http get https://www.example.com/large.iso | save large.iso",
+                result: None,
+            },
         ]
     }
 }
@@ -123,6 +168,10 @@ struct Arguments {
     password: Option<String>,
     timeout: Option<Value>,
     headers: Option<Value>,
+    full: bool,
+    allow_errors: bool,
+    policy: RequestPolicy,
+    session: Option<HttpSession>,
 }
 
 fn run_fetch(
@@ -139,6 +188,10 @@ fn run_fetch(
         password: call.get_flag(engine_state, stack, "password")?,
         timeout: call.get_flag(engine_state, stack, "timeout")?,
         headers: call.get_flag(engine_state, stack, "headers")?,
+        full: call.has_flag("full"),
+        allow_errors: call.has_flag("allow-errors"),
+        policy: RequestPolicy::from_call(engine_state, stack, call)?,
+        session: HttpSession::from_call(engine_state, stack, call)?,
     };
     helper(engine_state, stack, args)
 }
@@ -191,7 +244,7 @@ fn helper(
         _ => None,
     };
 
-    let client = http_client(args.insecure.is_some());
+    let client = http_client(args.insecure.is_some(), &args.policy, args.session.as_ref())?;
     let mut request = client.get(url);
 
     if let Some(timeout) = timeout {
@@ -261,75 +314,25 @@ fn helper(
         }
     }
 
-    // Explicitly turn 4xx and 5xx statuses into errors.
-    match request.send().and_then(|r| r.error_for_status()) {
-        Ok(resp) => match resp.headers().get("content-type") {
-            Some(content_type) => {
-                let content_type = content_type.to_str().map_err(|e| {
-                    ShellError::GenericError(
-                        e.to_string(),
-                        "".to_string(),
-                        None,
-                        Some("MIME type were invalid".to_string()),
-                        Vec::new(),
-                    )
-                })?;
-                let content_type = mime::Mime::from_str(content_type).map_err(|_| {
-                    ShellError::GenericError(
-                        format!("MIME type unknown: {content_type}"),
-                        "".to_string(),
-                        None,
-                        Some("given unknown MIME type".to_string()),
-                        Vec::new(),
-                    )
-                })?;
-                let ext = match (content_type.type_(), content_type.subtype()) {
-                    (mime::TEXT, mime::PLAIN) => {
-                        let path_extension = url::Url::parse(&requested_url)
-                            .map_err(|_| {
-                                ShellError::GenericError(
-                                    format!("Cannot parse URL: {requested_url}"),
-                                    "".to_string(),
-                                    None,
-                                    Some("cannot parse".to_string()),
-                                    Vec::new(),
-                                )
-                            })?
-                            .path_segments()
-                            .and_then(|segments| segments.last())
-                            .and_then(|name| if name.is_empty() { None } else { Some(name) })
-                            .and_then(|name| {
-                                PathBuf::from(name)
-                                    .extension()
-                                    .map(|name| name.to_string_lossy().to_string())
-                            });
-                        path_extension
-                    }
-                    _ => Some(content_type.subtype().to_string()),
-                };
-
-                let output = response_to_buffer(resp, engine_state, span);
-
-                if raw {
-                    return Ok(output);
-                }
+    // Explicitly turn 4xx and 5xx statuses into errors, unless asked not to.
+    let start = Instant::now();
+    match send_request(request, &args.policy, &engine_state.ctrlc).and_then(|r| {
+        if args.allow_errors {
+            Ok(r)
+        } else {
+            r.error_for_status()
+        }
+    }) {
+        Ok(resp) => {
+            let info = ResponseInfo::new(&resp, start, span);
+            let output = convert_response(engine_state, stack, resp, &requested_url, raw, span)?;
 
-                if let Some(ext) = ext {
-                    match engine_state.find_decl(format!("from {ext}").as_bytes(), &[]) {
-                        Some(converter_id) => engine_state.get_decl(converter_id).run(
-                            engine_state,
-                            stack,
-                            &Call::new(span),
-                            output,
-                        ),
-                        None => Ok(output),
-                    }
-                } else {
-                    Ok(output)
-                }
+            if args.full {
+                Ok(info.into_full(output, span))
+            } else {
+                Ok(output)
             }
-            None => Ok(response_to_buffer(resp, engine_state, span)),
-        },
+        }
         Err(e) if e.is_timeout() => Err(ShellError::NetworkFailure(
             format!("Request to {requested_url} has timed out"),
             span,
@@ -371,47 +374,3 @@ fn helper(
         )),
     }
 }
-
-fn response_to_buffer(
-    response: Response,
-    engine_state: &EngineState,
-    span: Span,
-) -> nu_protocol::PipelineData {
-    // Try to get the size of the file to be downloaded.
-    // This is helpful to show the progress of the stream.
-    let buffer_size = match &response.headers().get("content-length") {
-        Some(content_length) => {
-            let content_length = &(*content_length).clone(); // binding
-
-            let content_length = content_length
-                .to_str()
-                .unwrap_or("")
-                .parse::<u64>()
-                .unwrap_or(0);
-
-            if content_length == 0 {
-                None
-            } else {
-                Some(content_length)
-            }
-        }
-        _ => None,
-    };
-    let buffered_input = BufReader::new(response);
-
-    PipelineData::ExternalStream {
-        stdout: Some(RawStream::new(
-            Box::new(BufferedReader {
-                input: buffered_input,
-            }),
-            engine_state.ctrlc.clone(),
-            span,
-            buffer_size,
-        )),
-        stderr: None,
-        exit_code: None,
-        span,
-        metadata: None,
-        trim_end_newline: false,
-    }
-}
@@ -2,7 +2,13 @@ mod client;
 mod get;
 mod http_;
 mod post;
+mod serve;
+mod session;
+#[cfg(unix)]
+mod unix_socket;
 
 pub use get::SubCommand as HttpGet;
 pub use http_::Http;
 pub use post::SubCommand as HttpPost;
+pub use serve::SubCommand as HttpServe;
+pub use session::SubCommand as HttpSessionCreate;
@@ -3,18 +3,18 @@ use base64::{alphabet, engine::general_purpose::PAD, engine::GeneralPurpose, Eng
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::util::BufferedReader;
-use nu_protocol::RawStream;
 use nu_protocol::{
     Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
-use reqwest::{blocking::Response, StatusCode};
+use reqwest::blocking::multipart::{Form, Part};
+use reqwest::StatusCode;
 use std::collections::HashMap;
-use std::io::BufReader;
-use std::path::PathBuf;
-use std::str::FromStr;
+use std::time::Instant;
 
-use crate::network::http::client::http_client;
+use crate::network::http::client::{
+    convert_response, http_client, send_request, RequestPolicy, ResponseInfo,
+};
+use crate::network::http::session::HttpSession;
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -25,10 +25,10 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("http post")
+        let signature = Signature::build("http post")
             .input_output_types(vec![(Type::Nothing, Type::Any)])
             .required("path", SyntaxShape::String, "the URL to post to")
-            .required("body", SyntaxShape::Any, "the contents of the post body")
+            .optional("body", SyntaxShape::Any, "the contents of the post body")
             .named(
                 "user",
                 SyntaxShape::Any,
@@ -47,6 +47,12 @@ impl Command for SubCommand {
                 "the MIME type of content to post",
                 Some('t'),
             )
+            .named(
+                "form",
+                SyntaxShape::Record,
+                "fields to send as a multipart/form-data body, instead of the body",
+                Some('F'),
+            )
             .named(
                 "content-length",
                 SyntaxShape::Any,
@@ -69,6 +75,18 @@ impl Command for SubCommand {
                 "allow insecure server connections when using SSL",
                 Some('k'),
             )
+            .switch(
+                "full",
+                "return a record with the status, headers, body, elapsed time and URL of the response",
+                Some('f'),
+            )
+            .switch(
+                "allow-errors",
+                "return the response of 4xx and 5xx statuses instead of an error",
+                Some('e'),
+            );
+
+        RequestPolicy::add_flags(signature)
             .filter()
             .category(Category::Network)
     }
@@ -78,7 +96,15 @@ impl Command for SubCommand {
     }
 
     fn extra_usage(&self) -> &str {
-        "Performs HTTP POST operation."
+        r#"Performs HTTP POST operation.
+
+With --form, each field of the record is sent as a part of a multipart/form-data body.
+Binary values are sent as files, named after their field. To give a file part its own
+filename or content type, use a record with content, filename and content_type fields.
+
+With --full, the body of the response is collected into a record along with its status,
+headers, elapsed time and final URL. With --allow-errors, 4xx and 5xx responses are returned
+like any other instead of failing."#
     }
 
     fn search_terms(&self) -> Vec<&str> {
@@ -117,13 +143,29 @@ impl Command for SubCommand {
                 example: "http post -t application/json url.com { field: value }",
                 result: None,
             },
+            Example {
+                description: "Upload a file with a multipart form",
+                example: "http post url.com --form {name: photo, file: (open -r photo.jpg)}",
+                result: None,
+            },
+            Example {
+                description: "Upload a file with its own filename and content type",
+                example: "http post url.com --form {file: {content: (open -r data.csv), filename: data.csv, content_type: text/csv}}",
+                result: None,
+            },
+            Example {
+                description: "Post content to url.com, and get the headers of the response",
+                example: "http post --full url.com 'body' | get headers",
+                result: None,
+            },
         ]
     }
 }
 
 struct Arguments {
     path: Value,
-    body: Value,
+    body: Option<Value>,
+    form: Option<Value>,
     headers: Option<Value>,
     raw: bool,
     insecure: Option<bool>,
@@ -131,6 +173,10 @@ struct Arguments {
     password: Option<String>,
     content_type: Option<String>,
     content_length: Option<String>,
+    full: bool,
+    allow_errors: bool,
+    policy: RequestPolicy,
+    session: Option<HttpSession>,
 }
 
 #[derive(PartialEq, Eq)]
@@ -148,7 +194,8 @@ fn run_post(
 ) -> Result<PipelineData, ShellError> {
     let args = Arguments {
         path: call.req(engine_state, stack, 0)?,
-        body: call.req(engine_state, stack, 1)?,
+        body: call.opt(engine_state, stack, 1)?,
+        form: call.get_flag(engine_state, stack, "form")?,
         headers: call.get_flag(engine_state, stack, "headers")?,
         raw: call.has_flag("raw"),
         user: call.get_flag(engine_state, stack, "user")?,
@@ -156,6 +203,10 @@ fn run_post(
         insecure: call.get_flag(engine_state, stack, "insecure")?,
         content_type: call.get_flag(engine_state, stack, "content-type")?,
         content_length: call.get_flag(engine_state, stack, "content-length")?,
+        full: call.has_flag("full"),
+        allow_errors: call.has_flag("allow-errors"),
+        policy: RequestPolicy::from_call(engine_state, stack, call)?,
+        session: HttpSession::from_call(engine_state, stack, call)?,
     };
     helper(engine_state, stack, call, args)
 }
@@ -210,43 +261,59 @@ fn helper(
         _ => BodyType::Unknown,
     };
 
-    let mut request = http_client(args.insecure.is_some()).post(location);
+    let mut request =
+        http_client(args.insecure.is_some(), &args.policy, args.session.as_ref())?.post(location);
 
-    // set the content-type header before using e.g., request.json
-    // because that will avoid duplicating the header value
-    if let Some(val) = args.content_type {
-        request = request.header("Content-Type", val);
-    }
-
-    match body {
-        Value::Binary { val, .. } => {
-            request = request.body(val);
-        }
-        Value::String { val, .. } => {
-            request = request.body(val);
-        }
-        Value::Record { .. } if body_type == BodyType::Json => {
-            let data = value_to_json_value(&body)?;
-            request = request.json(&data);
+    match (body, args.form) {
+        (Some(_), Some(form)) => {
+            return Err(ShellError::IncompatibleParametersSingle(
+                "--form can't be used together with a body".into(),
+                form.span()?,
+            ));
         }
-        Value::Record { .. } if body_type == BodyType::Form => {
-            let data = value_to_json_value(&body)?;
-            request = request.form(&data);
+        // The multipart body sets its own content type, with the boundary of the parts
+        (None, Some(form)) => {
+            request = request.multipart(multipart_form(form)?);
         }
-        Value::List { vals, .. } if body_type == BodyType::Form => {
-            if vals.len() % 2 != 0 {
-                return Err(ShellError::IOError("unsupported body input".into()));
+        (Some(body), None) => {
+            // set the content-type header before using e.g., request.json
+            // because that will avoid duplicating the header value
+            if let Some(val) = args.content_type {
+                request = request.header("Content-Type", val);
             }
-            let data = vals
-                .chunks(2)
-                .map(|it| Ok((it[0].as_string()?, it[1].as_string()?)))
-                .collect::<Result<Vec<(String, String)>, ShellError>>()?;
-            request = request.form(&data)
-        }
-        _ => {
-            return Err(ShellError::IOError("unsupported body input".into()));
+
+            match body {
+                Value::Binary { val, .. } => {
+                    request = request.body(val);
+                }
+                Value::String { val, .. } => {
+                    request = request.body(val);
+                }
+                Value::Record { .. } if body_type == BodyType::Json => {
+                    let data = value_to_json_value(&body)?;
+                    request = request.json(&data);
+                }
+                Value::Record { .. } if body_type == BodyType::Form => {
+                    let data = value_to_json_value(&body)?;
+                    request = request.form(&data);
+                }
+                Value::List { vals, .. } if body_type == BodyType::Form => {
+                    if vals.len() % 2 != 0 {
+                        return Err(ShellError::IOError("unsupported body input".into()));
+                    }
+                    let data = vals
+                        .chunks(2)
+                        .map(|it| Ok((it[0].as_string()?, it[1].as_string()?)))
+                        .collect::<Result<Vec<(String, String)>, ShellError>>()?;
+                    request = request.form(&data)
+                }
+                _ => {
+                    return Err(ShellError::IOError("unsupported body input".into()));
+                }
+            };
         }
-    };
+        (None, None) => return Err(ShellError::MissingParameter("body".into(), call.head)),
+    }
 
     if let Some(val) = args.content_length {
         request = request.header("Content-Length", val);
@@ -305,73 +372,25 @@ fn helper(
         }
     }
 
-    // Explicitly turn 4xx and 5xx statuses into errors.
-    match request.send().and_then(|r| r.error_for_status()) {
-        Ok(resp) => match resp.headers().get("content-type") {
-            Some(content_type) => {
-                let content_type = content_type.to_str().map_err(|e| {
-                    ShellError::GenericError(
-                        e.to_string(),
-                        "".to_string(),
-                        None,
-                        Some("MIME type were invalid".to_string()),
-                        Vec::new(),
-                    )
-                })?;
-                let content_type = mime::Mime::from_str(content_type).map_err(|_| {
-                    ShellError::GenericError(
-                        format!("MIME type unknown: {content_type}"),
-                        "".to_string(),
-                        None,
-                        Some("given unknown MIME type".to_string()),
-                        Vec::new(),
-                    )
-                })?;
-                let ext = match (content_type.type_(), content_type.subtype()) {
-                    (mime::TEXT, mime::PLAIN) => {
-                        let path_extension = url::Url::parse(&requested_url)
-                            .map_err(|_| {
-                                ShellError::GenericError(
-                                    format!("Cannot parse URL: {requested_url}"),
-                                    "".to_string(),
-                                    None,
-                                    Some("cannot parse".to_string()),
-                                    Vec::new(),
-                                )
-                            })?
-                            .path_segments()
-                            .and_then(|segments| segments.last())
-                            .and_then(|name| if name.is_empty() { None } else { Some(name) })
-                            .and_then(|name| {
-                                PathBuf::from(name)
-                                    .extension()
-                                    .map(|name| name.to_string_lossy().to_string())
-                            });
-                        path_extension
-                    }
-                    _ => Some(content_type.subtype().to_string()),
-                };
-                let output = response_to_buffer(resp, engine_state, span);
+    // Explicitly turn 4xx and 5xx statuses into errors, unless asked not to.
+    let start = Instant::now();
+    match send_request(request, &args.policy, &engine_state.ctrlc).and_then(|r| {
+        if args.allow_errors {
+            Ok(r)
+        } else {
+            r.error_for_status()
+        }
+    }) {
+        Ok(resp) => {
+            let info = ResponseInfo::new(&resp, start, span);
+            let output = convert_response(engine_state, stack, resp, &requested_url, raw, span)?;
 
-                if raw {
-                    return Ok(output);
-                }
-                if let Some(ext) = ext {
-                    match engine_state.find_decl(format!("from {ext}").as_bytes(), &[]) {
-                        Some(converter_id) => engine_state.get_decl(converter_id).run(
-                            engine_state,
-                            stack,
-                            &Call::new(span),
-                            output,
-                        ),
-                        None => Ok(output),
-                    }
-                } else {
-                    Ok(output)
-                }
+            if args.full {
+                Ok(info.into_full(output, span))
+            } else {
+                Ok(output)
             }
-            None => Ok(response_to_buffer(resp, engine_state, span)),
-        },
+        }
         Err(e) if e.is_status() => match e.status() {
             Some(err_code) if err_code == StatusCode::NOT_FOUND => Err(ShellError::NetworkFailure(
                 format!("Requested file not found (404): {requested_url:?}"),
@@ -410,26 +429,65 @@ fn helper(
     }
 }
 
-fn response_to_buffer(
-    response: Response,
-    engine_state: &EngineState,
-    span: Span,
-) -> nu_protocol::PipelineData {
-    let buffered_input = BufReader::new(response);
-
-    PipelineData::ExternalStream {
-        stdout: Some(RawStream::new(
-            Box::new(BufferedReader {
-                input: buffered_input,
-            }),
-            engine_state.ctrlc.clone(),
-            span,
-            None,
-        )),
-        stderr: None,
-        exit_code: None,
-        span,
-        metadata: None,
-        trim_end_newline: false,
+// Builds a multipart form out of a record. Binary values and records with a content field
+// become file parts, anything else is sent as text
+fn multipart_form(form: Value) -> Result<Form, ShellError> {
+    let (cols, vals) = match form {
+        Value::Record { cols, vals, .. } => (cols, vals),
+        other => {
+            return Err(ShellError::TypeMismatch(
+                format!("expected record, found {}", other.get_type()),
+                other.span()?,
+            ))
+        }
+    };
+
+    cols.into_iter()
+        .zip(vals)
+        .try_fold(Form::new(), |form, (name, value)| {
+            let part = form_part(&name, value)?;
+            Ok(form.part(name, part))
+        })
+}
+
+fn form_part(name: &str, value: Value) -> Result<Part, ShellError> {
+    match value {
+        Value::Binary { val, .. } => Ok(Part::bytes(val)
+            .file_name(name.to_string())
+            .mime_str("application/octet-stream")
+            .expect("the MIME type is valid")),
+        Value::Record { .. } => {
+            let span = value.span()?;
+            let content = value.get_data_by_key("content").ok_or_else(|| {
+                ShellError::GenericError(
+                    format!("Form field '{name}' has no content"),
+                    "expected a record with a content field".into(),
+                    Some(span),
+                    Some("file parts are records with content, filename and content_type".into()),
+                    Vec::new(),
+                )
+            })?;
+
+            let part = match content {
+                Value::Binary { val, .. } => Part::bytes(val),
+                other => Part::text(other.as_string()?),
+            };
+            let part = match value.get_data_by_key("filename") {
+                Some(filename) => part.file_name(filename.as_string()?),
+                None => part.file_name(name.to_string()),
+            };
+            match value.get_data_by_key("content_type") {
+                Some(content_type) => part.mime_str(&content_type.as_string()?).map_err(|_| {
+                    ShellError::UnsupportedInput(
+                        "Invalid MIME type".into(),
+                        "value originates from here".into(),
+                        span,
+                        content_type.expect_span(),
+                    )
+                }),
+                None => Ok(part),
+            }
+        }
+        other => Ok(Part::text(other.as_string()?)),
     }
 }
@@ -0,0 +1,636 @@
+use crate::formats::value_to_json_value;
+use nu_engine::{current_dir, eval_block_with_early_return, CallExt};
+use nu_protocol::ast::{Block, Call};
+use nu_protocol::engine::{Closure, Command, EngineState, EnvVars, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
+    Signature, Span, Spanned, SyntaxShape, Type, Value,
+};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Request, Response, Server};
+
+const DEFAULT_PORT: u16 = 8080;
+// How often waiting for a request checks for ctrl-c
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+// Characters escaped in the links of directory listings
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "http serve"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("http serve")
+            .input_output_types(vec![(Type::Nothing, Type::Table(vec![]))])
+            .allow_variants_without_examples(true)
+            .optional(
+                "directory",
+                SyntaxShape::Directory,
+                "the directory to serve files from (default: the current directory)",
+            )
+            .named(
+                "port",
+                SyntaxShape::Int,
+                "the port to listen on (default 8080)",
+                Some('p'),
+            )
+            .named(
+                "address",
+                SyntaxShape::String,
+                "the address to listen on (default 127.0.0.1)",
+                Some('a'),
+            )
+            .named(
+                "handler",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::Record])),
+                "closure called with every request, returning the response",
+                None,
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Serve the files of a directory over HTTP, or answer requests with a closure."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Directories are answered with their index.html, or with a listing of their files. The
+listing is JSON when asked for with `?format=json` or an Accept header of application/json,
+and HTML otherwise.
+
+The handler is given a record with the method, path, query, headers and body of the request.
+It returns either the body of the response, or a record with the status, headers and body of
+the response. Strings are sent as text, binary values as they are, and other values as JSON.
+When the handler returns nothing, the request is answered with the files of the directory.
+
+A row is returned for every request answered, until ctrl-c is pressed. Use `first` to stop
+after a number of requests. When the handler fails, the client gets a bare 500 response, and
+the error is in the row of the request.
+
+Only files inside of the directory are served, even through links."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "server", "web", "static", "files", "listen"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let directory: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let port: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "port")?;
+        let address: Option<String> = call.get_flag(engine_state, stack, "address")?;
+        let handler: Option<Closure> = call.get_flag(engine_state, stack, "handler")?;
+        let span = call.head;
+
+        let cwd = current_dir(engine_state, stack)?;
+        let root = match directory {
+            Some(directory) => {
+                let root = nu_path::expand_path_with(&directory.item, &cwd);
+                if !root.is_dir() {
+                    return Err(ShellError::DirectoryNotFound(directory.span, None));
+                }
+                root
+            }
+            None => cwd,
+        };
+        // Served paths are checked against the canonical root, so links can't lead out of it
+        let root = root.canonicalize().map_err(|e| {
+            ShellError::IOErrorSpanned(format!("Cannot serve {}: {e}", root.display()), span)
+        })?;
+
+        let port = match port {
+            Some(port) => u16::try_from(port.item).map_err(|_| {
+                ShellError::UnsupportedInput(
+                    format!(
+                        "{} is not a port number, they go from 0 to 65535",
+                        port.item
+                    ),
+                    "value originates from here".into(),
+                    span,
+                    port.span,
+                )
+            })?,
+            None => DEFAULT_PORT,
+        };
+        let address = address.unwrap_or_else(|| "127.0.0.1".into());
+
+        let server = Server::http((address.as_str(), port)).map_err(|e| {
+            ShellError::NetworkFailure(format!("Cannot listen on {address}:{port}: {e}"), span)
+        })?;
+
+        let mut handler = handler.map(|closure| RequestHandler::new(engine_state, stack, closure));
+        let ctrlc = engine_state.ctrlc.clone();
+        let mut done = false;
+
+        let requests = std::iter::from_fn(move || {
+            while !done {
+                if nu_utils::ctrl_c::was_pressed(&ctrlc) {
+                    return None;
+                }
+
+                match server.recv_timeout(POLL_INTERVAL) {
+                    Ok(Some(request)) => {
+                        return Some(answer(request, &root, handler.as_mut(), span));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        done = true;
+                        return Some(Value::Error {
+                            error: ShellError::NetworkFailure(e.to_string(), span),
+                        });
+                    }
+                }
+            }
+
+            None
+        });
+
+        Ok(requests.into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Serve the files of the current directory on port 8080",
+                example: "http serve",
+                result: None,
+            },
+            Example {
+                description: "Serve a built web site to the whole network",
+                example: "http serve ./public --port 3000 --address 0.0.0.0",
+                result: None,
+            },
+            Example {
+                description: "Answer an API route with a closure, and the other paths with files",
+                example: r#"http serve --handler {|req| if $req.path == "/api/time" { {now: (date now)} } }"#,
+                result: None,
+            },
+            Example {
+                description: "Answer with a status and headers",
+                example: r#"http serve --handler {|req| {status: 404, headers: {x-served-by: nu}, body: "nothing here"} }"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+// The closure given with --handler, with the state it runs in
+struct RequestHandler {
+    engine_state: EngineState,
+    stack: Stack,
+    block: Block,
+    orig_env_vars: Vec<EnvVars>,
+    orig_env_hidden: HashMap<String, HashSet<String>>,
+}
+
+impl RequestHandler {
+    fn new(engine_state: &EngineState, stack: &Stack, closure: Closure) -> Self {
+        let block = engine_state.get_block(closure.block_id).clone();
+        let stack = stack.captures_to_stack(&closure.captures);
+
+        Self {
+            engine_state: engine_state.clone(),
+            orig_env_vars: stack.env_vars.clone(),
+            orig_env_hidden: stack.env_hidden.clone(),
+            stack,
+            block,
+        }
+    }
+
+    fn call(&mut self, request: Value, span: Span) -> Result<Value, ShellError> {
+        // Every request starts from the same environment
+        self.stack
+            .with_env(&self.orig_env_vars, &self.orig_env_hidden);
+
+        if let Some(var) = self.block.signature.get_positional(0) {
+            if let Some(var_id) = &var.var_id {
+                self.stack.add_var(*var_id, request.clone());
+            }
+        }
+
+        eval_block_with_early_return(
+            &self.engine_state,
+            &mut self.stack,
+            &self.block,
+            request.into_pipeline_data(),
+            false,
+            false,
+        )
+        .map(|output| output.into_value(span))
+    }
+}
+
+struct Reply {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    // What went wrong, for the row of the request rather than for the client
+    error: Option<String>,
+}
+
+impl Reply {
+    fn new(status: u16, content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".into(), content_type.into())],
+            body,
+            error: None,
+        }
+    }
+
+    // The client only learns that something went wrong, the details stay in the returned rows
+    fn internal_error(error: String) -> Self {
+        let mut reply = Self::text(500, "Internal server error");
+        reply.error = Some(error);
+        reply
+    }
+
+    fn text(status: u16, text: impl Into<String>) -> Self {
+        Self::new(
+            status,
+            "text/plain; charset=utf-8",
+            text.into().into_bytes(),
+        )
+    }
+
+    fn into_response(self) -> Response<std::io::Cursor<Vec<u8>>> {
+        let mut response = Response::from_data(self.body).with_status_code(self.status);
+        for (name, value) in self.headers {
+            if let Ok(header) = Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+                response.add_header(header);
+            }
+        }
+        response
+    }
+}
+
+// Answers a request, and returns the row describing it
+fn answer(
+    mut request: Request,
+    root: &Path,
+    handler: Option<&mut RequestHandler>,
+    span: Span,
+) -> Value {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let url = request.url().to_string();
+
+    let mut reply = match handler {
+        Some(handler) => {
+            let request_value = request_to_value(&mut request, span);
+            match handler
+                .call(request_value, span)
+                .and_then(|value| value_to_reply(value, span))
+            {
+                Ok(Some(reply)) => reply,
+                Ok(None) => serve_files(&request, root, span),
+                Err(error) => Reply::internal_error(format!("{error:?}")),
+            }
+        }
+        None => serve_files(&request, root, span),
+    };
+
+    let status = reply.status;
+    let error = match reply.error.take() {
+        Some(error) => Value::string(error, span),
+        None => Value::nothing(span),
+    };
+    let _ = request.respond(reply.into_response());
+
+    Value::Record {
+        cols: vec![
+            "method".into(),
+            "url".into(),
+            "status".into(),
+            "elapsed".into(),
+            "error".into(),
+        ],
+        vals: vec![
+            Value::string(method, span),
+            Value::string(url, span),
+            Value::int(status as i64, span),
+            Value::Duration {
+                val: start.elapsed().as_nanos() as i64,
+                span,
+            },
+            error,
+        ],
+        span,
+    }
+}
+
+fn request_to_value(request: &mut Request, span: Span) -> Value {
+    let (path, query) = split_url(request.url());
+    let path = percent_decode_str(path).decode_utf8_lossy().to_string();
+
+    let (query_cols, query_vals) = url::form_urlencoded::parse(query.as_bytes())
+        .map(|(name, value)| (name.to_string(), Value::string(value, span)))
+        .unzip();
+
+    let (header_cols, header_vals) = request
+        .headers()
+        .iter()
+        .map(|header| {
+            (
+                header.field.as_str().as_str().to_lowercase(),
+                Value::string(header.value.as_str(), span),
+            )
+        })
+        .unzip();
+
+    let mut body = vec![];
+    let _ = request.as_reader().read_to_end(&mut body);
+    let body = match String::from_utf8(body) {
+        Ok(val) => Value::String { val, span },
+        Err(e) => Value::Binary {
+            val: e.into_bytes(),
+            span,
+        },
+    };
+
+    Value::Record {
+        cols: vec![
+            "method".into(),
+            "path".into(),
+            "query".into(),
+            "headers".into(),
+            "body".into(),
+        ],
+        vals: vec![
+            Value::string(request.method().to_string(), span),
+            Value::string(path, span),
+            Value::Record {
+                cols: query_cols,
+                vals: query_vals,
+                span,
+            },
+            Value::Record {
+                cols: header_cols,
+                vals: header_vals,
+                span,
+            },
+            body,
+        ],
+        span,
+    }
+}
+
+// A record with a status, headers or body describes the whole response. Any other value
+// is the body
+fn value_to_reply(value: Value, span: Span) -> Result<Option<Reply>, ShellError> {
+    match value {
+        Value::Nothing { .. } => Ok(None),
+        Value::Record { cols, vals, .. }
+            if cols
+                .iter()
+                .any(|col| matches!(col.as_str(), "status" | "headers" | "body")) =>
+        {
+            let mut reply = Reply::text(200, "");
+            for (col, val) in cols.into_iter().zip(vals) {
+                match col.as_str() {
+                    "status" => {
+                        let status_span = val.span()?;
+                        reply.status = u16::try_from(val.as_i64()?)
+                            .ok()
+                            .filter(|status| (100..600).contains(status))
+                            .ok_or_else(|| {
+                                ShellError::UnsupportedInput(
+                                    "Invalid HTTP status".into(),
+                                    "value originates from here".into(),
+                                    span,
+                                    status_span,
+                                )
+                            })?;
+                    }
+                    "headers" => {
+                        if let Value::Record { cols, vals, .. } = val {
+                            for (name, value) in cols.into_iter().zip(vals) {
+                                reply.headers.push((name, value.as_string()?));
+                            }
+                        }
+                    }
+                    "body" => {
+                        let (body, content_type) = value_to_body(&val)?;
+                        reply.body = body;
+                        reply.headers[0].1 = content_type.into();
+                    }
+                    _ => {}
+                }
+            }
+
+            // A content type given in the headers replaces the one of the body
+            let given = |name: &String| name.eq_ignore_ascii_case("content-type");
+            if reply.headers.iter().skip(1).any(|(name, _)| given(name)) {
+                reply.headers.remove(0);
+            }
+            Ok(Some(reply))
+        }
+        other => {
+            let (body, content_type) = value_to_body(&other)?;
+            Ok(Some(Reply::new(200, content_type, body)))
+        }
+    }
+}
+
+fn value_to_body(value: &Value) -> Result<(Vec<u8>, &'static str), ShellError> {
+    match value {
+        Value::String { val, .. } => Ok((val.clone().into_bytes(), "text/plain; charset=utf-8")),
+        Value::Binary { val, .. } => Ok((val.clone(), "application/octet-stream")),
+        Value::Nothing { .. } => Ok((vec![], "text/plain; charset=utf-8")),
+        Value::Error { error } => Err(error.clone()),
+        other => Ok((to_json(other)?.into_bytes(), "application/json")),
+    }
+}
+
+fn to_json(value: &Value) -> Result<String, ShellError> {
+    let json = value_to_json_value(value)?;
+    nu_json::to_string_raw(&json).map_err(|e| {
+        ShellError::CantConvert(
+            "JSON".into(),
+            value.get_type().to_string(),
+            value.span().unwrap_or_else(|_| Span::unknown()),
+            Some(e.to_string()),
+        )
+    })
+}
+
+fn split_url(url: &str) -> (&str, &str) {
+    match url.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (url, ""),
+    }
+}
+
+fn serve_files(request: &Request, root: &Path, span: Span) -> Reply {
+    let method = request.method().as_str();
+    if method != "GET" && method != "HEAD" {
+        return Reply::text(405, "Method not allowed");
+    }
+
+    let (url_path, query) = split_url(request.url());
+    let mut path = root.to_path_buf();
+    for segment in url_path.split('/') {
+        let segment = percent_decode_str(segment).decode_utf8_lossy();
+        match segment.as_ref() {
+            "" | "." => {}
+            // Nothing outside of the directory is served
+            ".." => return Reply::text(403, "Forbidden"),
+            segment if segment.contains(['/', '\\']) => return Reply::text(403, "Forbidden"),
+            segment => path.push(segment),
+        }
+    }
+
+    // Links are followed, but only to files inside of the directory
+    let path = match path.canonicalize() {
+        Ok(path) if path.starts_with(root) => path,
+        Ok(_) => return Reply::text(403, "Forbidden"),
+        Err(_) => return Reply::text(404, "Not found"),
+    };
+
+    if path.is_dir() {
+        // Links in the listing are relative to the directory
+        if !url_path.ends_with('/') {
+            let mut reply = Reply::text(301, "");
+            reply
+                .headers
+                .push(("Location".into(), format!("{url_path}/")));
+            return reply;
+        }
+
+        let index = path.join("index.html");
+        if index.is_file() {
+            return match index.canonicalize() {
+                Ok(index) if index.starts_with(root) => serve_file(&index),
+                _ => Reply::text(403, "Forbidden"),
+            };
+        }
+
+        let json = url::form_urlencoded::parse(query.as_bytes())
+            .any(|(name, value)| name == "format" && value == "json")
+            || request.headers().iter().any(|header| {
+                header.field.equiv("Accept") && header.value.as_str().contains("application/json")
+            });
+        return list_directory(&path, url_path, json, span);
+    }
+
+    if path.is_file() {
+        return serve_file(&path);
+    }
+
+    Reply::text(404, "Not found")
+}
+
+fn serve_file(path: &Path) -> Reply {
+    match std::fs::read(path) {
+        Ok(body) => {
+            let content_type = mime_guess::from_path(path).first_or_octet_stream();
+            Reply::new(200, content_type.as_ref(), body)
+        }
+        Err(e) => Reply::internal_error(e.to_string()),
+    }
+}
+
+fn list_directory(path: &Path, url_path: &str, json: bool, span: Span) -> Reply {
+    let mut entries: Vec<(String, PathBuf)> = match std::fs::read_dir(path) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| {
+                (
+                    entry.file_name().to_string_lossy().to_string(),
+                    entry.path(),
+                )
+            })
+            .collect(),
+        Err(e) => return Reply::internal_error(e.to_string()),
+    };
+    entries.sort();
+
+    if json {
+        let rows = entries
+            .iter()
+            .map(|(name, path)| entry_to_value(name, path, span))
+            .collect();
+        return match to_json(&Value::List { vals: rows, span }) {
+            Ok(body) => Reply::new(200, "application/json", body.into_bytes()),
+            Err(error) => Reply::internal_error(format!("{error:?}")),
+        };
+    }
+
+    let title = htmlescape::encode_minimal(&percent_decode_str(url_path).decode_utf8_lossy());
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Index of {title}</title></head>\n<body>\n<h1>Index of {title}</h1>\n<ul>\n"
+    );
+    if url_path != "/" {
+        html.push_str("<li><a href=\"../\">../</a></li>\n");
+    }
+    for (name, path) in &entries {
+        let slash = if path.is_dir() { "/" } else { "" };
+        html.push_str(&format!(
+            "<li><a href=\"{}{slash}\">{}{slash}</a></li>\n",
+            utf8_percent_encode(name, PATH_SEGMENT),
+            htmlescape::encode_minimal(name),
+        ));
+    }
+    html.push_str("</ul>\n</body>\n</html>\n");
+
+    Reply::new(200, "text/html; charset=utf-8", html.into_bytes())
+}
+
+fn entry_to_value(name: &str, path: &Path, span: Span) -> Value {
+    let metadata = std::fs::metadata(path).ok();
+    let file_type = match &metadata {
+        Some(metadata) if metadata.is_dir() => "dir",
+        Some(_) => "file",
+        None => "unknown",
+    };
+    let size = metadata
+        .as_ref()
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    let modified = metadata
+        .and_then(|metadata| metadata.modified().ok())
+        .map(|modified| Value::Date {
+            val: chrono::DateTime::<chrono::Local>::from(modified).into(),
+            span,
+        })
+        .unwrap_or_else(|| Value::nothing(span));
+
+    Value::Record {
+        cols: vec![
+            "name".into(),
+            "type".into(),
+            "size".into(),
+            "modified".into(),
+        ],
+        vals: vec![
+            Value::string(name, span),
+            Value::string(file_type, span),
+            Value::int(size as i64, span),
+            modified,
+        ],
+        span,
+    }
+}
@@ -0,0 +1,179 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, CustomValue, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span,
+    SyntaxShape, Type, Value,
+};
+use reqwest::cookie::Jar;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+// The cookies and default headers shared by the requests made with --session.
+// Copies of the value share the same cookie jar
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HttpSession {
+    pub headers: Vec<(String, String)>,
+    #[serde(skip)]
+    pub cookies: Arc<Jar>,
+}
+
+impl HttpSession {
+    pub fn try_from_value(value: Value) -> Result<Self, ShellError> {
+        match value {
+            Value::CustomValue { val, span } => match val.as_any().downcast_ref::<Self>() {
+                Some(session) => Ok(Self {
+                    headers: session.headers.clone(),
+                    cookies: session.cookies.clone(),
+                }),
+                None => Err(ShellError::CantConvert(
+                    "http session".into(),
+                    "non-http session".into(),
+                    span,
+                    None,
+                )),
+            },
+            x => Err(ShellError::CantConvert(
+                "http session".into(),
+                x.get_type().to_string(),
+                x.span()?,
+                Some("create a session with `http session create`".into()),
+            )),
+        }
+    }
+
+    // The --session flag shared by the http subcommands
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Option<Self>, ShellError> {
+        let session: Option<Value> = call.get_flag(engine_state, stack, "session")?;
+        session.map(Self::try_from_value).transpose()
+    }
+}
+
+impl CustomValue for HttpSession {
+    fn clone_value(&self, span: Span) -> Value {
+        let cloned = HttpSession {
+            headers: self.headers.clone(),
+            cookies: self.cookies.clone(),
+        };
+
+        Value::CustomValue {
+            val: Box::new(cloned),
+            span,
+        }
+    }
+
+    fn value_string(&self) -> String {
+        self.typetag_name().to_string()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        let (cols, vals) = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::string(value, span)))
+            .unzip();
+
+        Ok(Value::Record {
+            cols: vec!["headers".into()],
+            vals: vec![Value::Record { cols, vals, span }],
+            span,
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn typetag_name(&self) -> &'static str {
+        "HttpSession"
+    }
+
+    fn typetag_deserialize(&self) {
+        unimplemented!("typetag_deserialize")
+    }
+}
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "http session create"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("http session create")
+            .input_output_types(vec![(Type::Nothing, Type::Custom("http session".into()))])
+            .named(
+                "headers",
+                SyntaxShape::Record,
+                "headers to send with every request of the session",
+                Some('H'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Create a session that keeps cookies and headers across http requests."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Pass the session to `http get` or `http post` with --session. The cookies set by the
+responses are sent back with the following requests of the session, like a browser does.
+Copies of a session share its cookies."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "cookie", "login", "jar"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let headers: Option<Value> = call.get_flag(engine_state, stack, "headers")?;
+        let headers = match headers {
+            Some(Value::Record { cols, vals, .. }) => cols
+                .into_iter()
+                .zip(vals)
+                .map(|(name, value)| Ok((name, value.as_string()?)))
+                .collect::<Result<Vec<(String, String)>, ShellError>>()?,
+            _ => vec![],
+        };
+
+        let session = HttpSession {
+            headers,
+            cookies: Arc::new(Jar::default()),
+        };
+
+        Ok(Value::CustomValue {
+            val: Box::new(session),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Log in to a site, then fetch a page with the cookies it set",
+                example: r#"let s = (http session create);
+    http post --session $s -t application/x-www-form-urlencoded https://www.example.com/login {user: me, password: secret};
+    http get --session $s https://www.example.com/account"#,
+                result: None,
+            },
+            Example {
+                description: "Send the same token with every request of a session",
+                example: r#"let s = (http session create -H {Authorization: "Bearer token"}); http get --session $s https://www.example.com"#,
+                result: None,
+            },
+        ]
+    }
+}
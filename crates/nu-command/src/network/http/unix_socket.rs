@@ -0,0 +1,157 @@
+use base64::{alphabet, engine::general_purpose::PAD, engine::GeneralPurpose, Engine};
+use reqwest::Proxy;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+// How often the listener checks whether it is still needed
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+// The largest request head read before giving up on a connection
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+const PROXY_USER: &str = "nu";
+
+// Sends the requests of a client to a unix socket, since reqwest only connects over TCP.
+// The client sees an HTTP proxy on a local port, so the URLs of the requests are kept.
+// Only requests with the random password of the proxy are forwarded, which keeps other
+// local users from reaching the socket through the port
+pub struct UnixSocketProxy {
+    address: SocketAddr,
+    password: String,
+    // The listener stops once the proxy is dropped
+    _alive: Arc<()>,
+}
+
+impl UnixSocketProxy {
+    pub fn start(socket: PathBuf) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+
+        let password = format!("{:032x}", rand::random::<u128>());
+        let credentials = GeneralPurpose::new(&alphabet::STANDARD, PAD)
+            .encode(format!("{PROXY_USER}:{password}"));
+
+        let alive = Arc::new(());
+        let weak = Arc::downgrade(&alive);
+        std::thread::Builder::new()
+            .name("http unix socket proxy".into())
+            .spawn(move || listen(listener, socket, credentials, weak))?;
+
+        Ok(Self {
+            address,
+            password,
+            _alive: alive,
+        })
+    }
+
+    pub fn proxy(&self) -> reqwest::Result<Proxy> {
+        Proxy::all(format!("http://{}", self.address))
+            .map(|proxy| proxy.basic_auth(PROXY_USER, &self.password))
+    }
+}
+
+fn listen(listener: TcpListener, socket: PathBuf, credentials: String, alive: Weak<()>) {
+    while alive.upgrade().is_some() {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let socket = socket.clone();
+                let credentials = credentials.clone();
+                let _ = std::thread::Builder::new()
+                    .name("http unix socket connection".into())
+                    .spawn(move || forward(stream, &socket, &credentials));
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+            Err(_) => return,
+        }
+    }
+}
+
+fn forward(mut client: TcpStream, socket: &Path, credentials: &str) -> std::io::Result<()> {
+    client.set_nonblocking(false)?;
+    client.set_read_timeout(Some(HEAD_TIMEOUT))?;
+
+    let head = match read_head(&mut client)? {
+        Some(head) => head,
+        None => return respond(client, "400 Bad Request", "Invalid request"),
+    };
+
+    if !is_authorized(&head, credentials) {
+        return respond(client, "407 Proxy Authentication Required", "");
+    }
+    // The unix socket takes the place of the server, there is nowhere to tunnel to
+    if head.starts_with(b"CONNECT ") {
+        return respond(
+            client,
+            "501 Not Implemented",
+            "Only http URLs can be requested over a unix socket",
+        );
+    }
+
+    let mut server = match UnixStream::connect(socket) {
+        Ok(server) => server,
+        Err(e) => {
+            let message = format!("Cannot connect to {}: {e}", socket.display());
+            return respond(client, "502 Bad Gateway", &message);
+        }
+    };
+    client.set_read_timeout(None)?;
+    server.write_all(&head)?;
+
+    let mut server_reader = server.try_clone()?;
+    let mut client_writer = client.try_clone()?;
+    let responses = std::thread::Builder::new()
+        .name("http unix socket responses".into())
+        .spawn(move || {
+            let _ = std::io::copy(&mut server_reader, &mut client_writer);
+            let _ = client_writer.shutdown(Shutdown::Write);
+        })?;
+
+    let _ = std::io::copy(&mut client, &mut server);
+    let _ = server.shutdown(Shutdown::Write);
+    let _ = responses.join();
+    Ok(())
+}
+
+// Reads up to the end of the headers of the first request. Whatever was read past them
+// is kept, and forwarded along with the head
+fn read_head(client: &mut TcpStream) -> std::io::Result<Option<Vec<u8>>> {
+    let mut head = vec![];
+    let mut buffer = [0; 4096];
+
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > MAX_HEAD_SIZE {
+            return Ok(None);
+        }
+        match client.read(&mut buffer)? {
+            0 => return Ok(None),
+            read => head.extend_from_slice(&buffer[..read]),
+        }
+    }
+
+    Ok(Some(head))
+}
+
+fn is_authorized(head: &[u8], credentials: &str) -> bool {
+    String::from_utf8_lossy(head).lines().any(|line| {
+        let value = match line.split_once(':') {
+            Some((name, value)) if name.eq_ignore_ascii_case("proxy-authorization") => value,
+            _ => return false,
+        };
+        match value.trim().split_once(' ') {
+            Some((scheme, given)) => scheme.eq_ignore_ascii_case("basic") && given == credentials,
+            None => false,
+        }
+    })
+}
+
+fn respond(mut client: TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        client,
+        "HTTP/1.1 {status}\r\ncontent-type: text/plain\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
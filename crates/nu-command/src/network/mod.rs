@@ -1,8 +1,21 @@
+mod dns;
+mod fetch_module;
 mod http;
 mod port;
+#[cfg(feature = "sftp")]
+mod sftp;
+mod smtp;
+mod socket;
 mod url;
+mod ws;
 
+pub use self::dns::*;
+pub use self::fetch_module::FetchModule;
 pub use self::http::*;
+pub use self::port::*;
+#[cfg(feature = "sftp")]
+pub use self::sftp::*;
+pub use self::smtp::*;
+pub use self::socket::*;
 pub use self::url::*;
-
-pub use port::SubCommand as Port;
+pub use self::ws::*;
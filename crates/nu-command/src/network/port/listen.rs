@@ -0,0 +1,159 @@
+use crate::network::socket::data::write_input;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::util::BufferedReader;
+use nu_protocol::{
+    Category, Example, PipelineData, RawStream, ShellError, Signature, Spanned, SyntaxShape, Type,
+};
+use std::io::{BufReader, ErrorKind};
+use std::net::{Shutdown, SocketAddr, TcpListener};
+use std::time::{Duration, Instant};
+
+// How often waiting for a connection checks for ctrl-c
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "port listen"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("port listen")
+            .input_output_types(vec![(Type::Nothing, Type::Any), (Type::Any, Type::Any)])
+            .allow_variants_without_examples(true)
+            .required("port", SyntaxShape::Int, "the port to listen on")
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the connection",
+                Some('t'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Wait for one TCP connection on a port, and stream what it sends."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The port is listened on every interface. Once a client connects, the port is closed, the
+input is sent to the client, and the data it sends is streamed until it closes the
+connection. Use `tcp listen` to answer several connections."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "netcat", "nc", "accept", "connectivity", "tcp"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let port: Spanned<i64> = call.req(engine_state, stack, 0)?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let span = call.head;
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            timeout => timeout.map(|timeout| Duration::from_nanos(timeout.item as u64)),
+        };
+
+        let port_number = u16::try_from(port.item).map_err(|_| {
+            ShellError::UnsupportedInput(
+                format!(
+                    "{} is not a port number, they go from 0 to 65535",
+                    port.item
+                ),
+                "value originates from here".into(),
+                span,
+                port.span,
+            )
+        })?;
+
+        let network_error = |e: std::io::Error| {
+            ShellError::NetworkFailure(
+                format!("Cannot listen on port {port_number}: {e}"),
+                port.span,
+            )
+        };
+
+        let listener = TcpListener::bind(SocketAddr::from(([0, 0, 0, 0], port_number)))
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+            .map_err(network_error)?;
+
+        let start = Instant::now();
+        let stream = loop {
+            match listener.accept() {
+                Ok((stream, _)) => break stream,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    if nu_utils::ctrl_c::was_pressed(&engine_state.ctrlc) {
+                        return Ok(PipelineData::empty());
+                    }
+                    if matches!(timeout, Some(timeout) if start.elapsed() >= timeout) {
+                        return Err(ShellError::NetworkFailure(
+                            format!("No connection on port {port_number} within the timeout"),
+                            port.span,
+                        ));
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => return Err(network_error(e)),
+            }
+        };
+        stream.set_nonblocking(false).map_err(network_error)?;
+
+        if !input.is_nothing() {
+            let mut writer = stream
+                .try_clone()
+                .map_err(|e| ShellError::IOError(e.to_string()))?;
+
+            std::thread::Builder::new()
+                .name("port listen writer".to_string())
+                .spawn(move || {
+                    let result = write_input(input, &mut writer);
+                    let _ = writer.shutdown(Shutdown::Write);
+                    result
+                })
+                .map_err(|e| ShellError::IOError(e.to_string()))?;
+        }
+
+        Ok(PipelineData::ExternalStream {
+            stdout: Some(RawStream::new(
+                Box::new(BufferedReader::new(BufReader::new(stream))),
+                engine_state.ctrlc.clone(),
+                span,
+                None,
+            )),
+            stderr: None,
+            exit_code: None,
+            resource_usage: None,
+            span,
+            metadata: None,
+            trim_end_newline: false,
+        })
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Print what the first client of port 9000 sends",
+                example: "port listen 9000",
+                result: None,
+            },
+            Example {
+                description: "Check in CI that another job can reach this one",
+                example: "port listen 9000 --timeout 1min | str trim",
+                result: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,7 @@
+mod listen;
+mod port_;
+mod scan;
+
+pub use listen::SubCommand as PortListen;
+pub use port_::SubCommand as Port;
+pub use scan::SubCommand as PortScan;
@@ -0,0 +1,173 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use rayon::prelude::*;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+// How many ports are tried at the same time
+const SCAN_THREADS: usize = 32;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "port scan"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("port scan")
+            .input_output_types(vec![(Type::Nothing, Type::Table(vec![]))])
+            .allow_variants_without_examples(true)
+            .required("host", SyntaxShape::String, "the host to scan")
+            .required(
+                "ports",
+                SyntaxShape::OneOf(vec![SyntaxShape::Range, SyntaxShape::Int]),
+                "the port, or range of ports, to try",
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for each connection (default 1sec)",
+                Some('t'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Check which TCP ports of a host accept connections."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Returns a row for every port, telling whether a connection to it could be opened within
+the timeout, and how long it took. The connections are closed right away."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "nmap", "open", "connectivity", "tcp"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let host: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let ports: Value = call.req(engine_state, stack, 1)?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let span = call.head;
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => DEFAULT_TIMEOUT,
+        };
+
+        let ports = port_numbers(ports, engine_state)?;
+        let address = resolve_host(&host)?;
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(SCAN_THREADS)
+            .build()
+            .map_err(|e| ShellError::IOError(e.to_string()))?;
+
+        let ctrlc = engine_state.ctrlc.clone();
+        let rows: Vec<Value> = pool.install(|| {
+            ports
+                .par_iter()
+                .filter_map(|port| {
+                    if nu_utils::ctrl_c::was_pressed(&ctrlc) {
+                        return None;
+                    }
+                    Some(scan_port(address, *port, timeout, span))
+                })
+                .collect()
+        });
+
+        Ok(Value::List { vals: rows, span }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Check that a web server is up",
+                example: "port scan example.com 443 | get 0.open",
+                result: None,
+            },
+            Example {
+                description: "List the open ports of the first thousand of this machine",
+                example: "port scan localhost 1..1024 --timeout 100ms | where open | get port",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn port_numbers(ports: Value, engine_state: &EngineState) -> Result<Vec<u16>, ShellError> {
+    let values = match ports {
+        Value::Range { val, .. } => val.into_range_iter(engine_state.ctrlc.clone())?.collect(),
+        Value::List { vals, .. } => vals,
+        other => vec![other],
+    };
+
+    values
+        .into_iter()
+        .map(|value| {
+            let span = value.span()?;
+            let port = value.as_i64()?;
+            u16::try_from(port).map_err(|_| {
+                ShellError::UnsupportedInput(
+                    format!("{port} is not a port number, they go from 0 to 65535"),
+                    "value originates from here".into(),
+                    span,
+                    span,
+                )
+            })
+        })
+        .collect()
+}
+
+fn resolve_host(host: &Spanned<String>) -> Result<IpAddr, ShellError> {
+    (host.item.as_str(), 0)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .map(|address| address.ip())
+        .ok_or_else(|| {
+            ShellError::NetworkFailure(format!("Cannot resolve {}", host.item), host.span)
+        })
+}
+
+fn scan_port(address: IpAddr, port: u16, timeout: Duration, span: Span) -> Value {
+    let start = Instant::now();
+    let open = TcpStream::connect_timeout(&SocketAddr::new(address, port), timeout).is_ok();
+
+    Value::Record {
+        cols: vec![
+            "address".into(),
+            "port".into(),
+            "open".into(),
+            "elapsed".into(),
+        ],
+        vals: vec![
+            Value::string(address.to_string(), span),
+            Value::int(port as i64, span),
+            Value::boolean(open, span),
+            Value::Duration {
+                val: start.elapsed().as_nanos() as i64,
+                span,
+            },
+        ],
+        span,
+    }
+}
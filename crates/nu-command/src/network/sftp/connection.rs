@@ -0,0 +1,293 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{ShellError, Signature, Span, Spanned, SyntaxShape};
+use ssh2::{CheckResult, KnownHostFileKind, Session, Sftp};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_PORT: u16 = 22;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+// The keys tried when neither --identity nor the agent authenticates, like ssh does
+const DEFAULT_KEYS: [&str; 3] = ["id_ed25519", "id_ecdsa", "id_rsa"];
+
+// A remote path, written [user@]host:path like scp does
+pub struct Target {
+    pub user: String,
+    pub host: String,
+    pub path: String,
+    pub span: Span,
+}
+
+impl Target {
+    pub fn parse(
+        target: Spanned<String>,
+        engine_state: &EngineState,
+        stack: &Stack,
+    ) -> Result<Self, ShellError> {
+        let invalid = || {
+            ShellError::UnsupportedInput(
+                format!(
+                    "Invalid remote path {}, expected [user@]host:path",
+                    target.item
+                ),
+                "value originates from here".into(),
+                target.span,
+                target.span,
+            )
+        };
+
+        let (user, rest) = match target.item.split_once('@') {
+            Some((user, rest)) => (Some(user.to_string()), rest),
+            None => (None, target.item.as_str()),
+        };
+
+        // IPv6 addresses are written in brackets, since they contain colons
+        let (host, path) = if let Some(rest) = rest.strip_prefix('[') {
+            let (host, path) = rest.split_once(']').ok_or_else(invalid)?;
+            (host, path.strip_prefix(':').ok_or_else(invalid)?)
+        } else {
+            rest.split_once(':').ok_or_else(invalid)?
+        };
+        if host.is_empty() || matches!(&user, Some(user) if user.is_empty()) {
+            return Err(invalid());
+        }
+
+        let user = match user {
+            Some(user) => user,
+            None => local_user(engine_state, stack).ok_or_else(|| {
+                ShellError::UnsupportedInput(
+                    "Cannot tell the local user name, give it as user@host:path".into(),
+                    "value originates from here".into(),
+                    target.span,
+                    target.span,
+                )
+            })?,
+        };
+
+        // An empty path is the home directory of the user
+        let path = if path.is_empty() { "." } else { path };
+
+        Ok(Target {
+            user,
+            host: host.to_string(),
+            path: path.to_string(),
+            span: target.span,
+        })
+    }
+}
+
+fn local_user(engine_state: &EngineState, stack: &Stack) -> Option<String> {
+    ["USER", "USERNAME"]
+        .iter()
+        .find_map(|name| stack.get_env_var(engine_state, name))
+        .and_then(|user| user.as_string().ok())
+}
+
+// How to reach and authenticate with the host
+pub struct ConnectOptions {
+    port: u16,
+    identity: Option<PathBuf>,
+    passphrase: Option<String>,
+    password: Option<String>,
+    timeout: Duration,
+    insecure: bool,
+}
+
+impl ConnectOptions {
+    // The flags shared by all the sftp subcommands
+    pub fn add_flags(signature: Signature) -> Signature {
+        signature
+            .named(
+                "port",
+                SyntaxShape::Int,
+                "the SSH port of the host (default 22)",
+                Some('p'),
+            )
+            .named(
+                "identity",
+                SyntaxShape::Filepath,
+                "private key to authenticate with (default: the SSH agent, then the keys in ~/.ssh)",
+                Some('i'),
+            )
+            .named(
+                "passphrase",
+                SyntaxShape::String,
+                "passphrase of the private key",
+                None,
+            )
+            .named(
+                "password",
+                SyntaxShape::String,
+                "password to authenticate with, instead of a key",
+                None,
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the host (default 30sec)",
+                Some('t'),
+            )
+            .switch(
+                "insecure",
+                "skip checking the key of the host against ~/.ssh/known_hosts",
+                Some('k'),
+            )
+    }
+
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<Self, ShellError> {
+        let port: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "port")?;
+        let identity: Option<Spanned<String>> = call.get_flag(engine_state, stack, "identity")?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+
+        let port = match port {
+            Some(port) => u16::try_from(port.item).map_err(|_| {
+                ShellError::UnsupportedInput(
+                    format!(
+                        "{} is not a port number, they go from 0 to 65535",
+                        port.item
+                    ),
+                    "value originates from here".into(),
+                    call.head,
+                    port.span,
+                )
+            })?,
+            None => DEFAULT_PORT,
+        };
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => DEFAULT_TIMEOUT,
+        };
+
+        let cwd = nu_engine::current_dir(engine_state, stack)?;
+
+        Ok(ConnectOptions {
+            port,
+            identity: identity.map(|identity| nu_path::expand_path_with(identity.item, &cwd)),
+            passphrase: call.get_flag(engine_state, stack, "passphrase")?,
+            password: call.get_flag(engine_state, stack, "password")?,
+            timeout,
+            insecure: call.has_flag("insecure"),
+        })
+    }
+
+    // Opens an SFTP session with the host of the target, once its key is checked and the
+    // user authenticated
+    pub fn connect(&self, target: &Target) -> Result<Sftp, ShellError> {
+        let host = &target.host;
+        let error = |message: String| ShellError::NetworkFailure(message, target.span);
+
+        let address = (host.as_str(), self.port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| error(format!("Cannot resolve {host}")))?;
+
+        let stream = TcpStream::connect_timeout(&address, self.timeout)
+            .map_err(|e| error(format!("Cannot connect to {host}:{}: {e}", self.port)))?;
+
+        let mut session = Session::new().map_err(|e| error(e.to_string()))?;
+        session.set_tcp_stream(stream);
+        session.set_timeout(self.timeout.as_millis().min(u32::MAX as u128) as u32);
+        session
+            .handshake()
+            .map_err(|e| error(format!("SSH handshake with {host} failed: {e}")))?;
+
+        if !self.insecure {
+            self.check_host_key(&session, target)?;
+        }
+
+        self.authenticate(&session, target)?;
+
+        session
+            .sftp()
+            .map_err(|e| error(format!("Cannot start SFTP on {host}: {e}")))
+    }
+
+    fn check_host_key(&self, session: &Session, target: &Target) -> Result<(), ShellError> {
+        let host = &target.host;
+        let error = |message: String| ShellError::NetworkFailure(message, target.span);
+
+        let (key, _) = session
+            .host_key()
+            .ok_or_else(|| error(format!("{host} has no host key")))?;
+
+        let mut known_hosts = session.known_hosts().map_err(|e| error(e.to_string()))?;
+        if let Some(path) = nu_path::home_dir().map(|home| home.join(".ssh").join("known_hosts")) {
+            if path.exists() {
+                known_hosts
+                    .read_file(&path, KnownHostFileKind::OpenSSH)
+                    .map_err(|e| error(format!("Cannot read {}: {e}", path.display())))?;
+            }
+        }
+
+        match known_hosts.check_port(host, self.port, key) {
+            CheckResult::Match => Ok(()),
+            CheckResult::Mismatch => Err(error(format!(
+                "The key of {host} does not match the one in ~/.ssh/known_hosts, the host may be impersonated"
+            ))),
+            CheckResult::NotFound => Err(error(format!(
+                "{host} is not in ~/.ssh/known_hosts, connect with ssh once to check its key, or use --insecure"
+            ))),
+            CheckResult::Failure => Err(error(format!("Cannot check the key of {host}"))),
+        }
+    }
+
+    fn authenticate(&self, session: &Session, target: &Target) -> Result<(), ShellError> {
+        let user = &target.user;
+        let passphrase = self.passphrase.as_deref();
+
+        // Failed attempts are only reported when nothing works
+        let result = if let Some(password) = &self.password {
+            session.userauth_password(user, password)
+        } else if let Some(identity) = &self.identity {
+            session.userauth_pubkey_file(user, None, identity, passphrase)
+        } else {
+            let mut result = session.userauth_agent(user);
+            if !session.authenticated() {
+                let ssh_dir = nu_path::home_dir().map(|home| home.join(".ssh"));
+                for key in ssh_dir
+                    .iter()
+                    .flat_map(|dir| DEFAULT_KEYS.map(|key| dir.join(key)))
+                {
+                    if key.exists() {
+                        result = session.userauth_pubkey_file(user, None, &key, passphrase);
+                        if session.authenticated() {
+                            break;
+                        }
+                    }
+                }
+            }
+            result
+        };
+
+        if session.authenticated() {
+            return Ok(());
+        }
+
+        let reason = match result {
+            Err(e) => e.to_string(),
+            Ok(()) => "no key was accepted".into(),
+        };
+        Err(ShellError::NetworkFailure(
+            format!("Cannot authenticate as {user} on {}: {reason}", target.host),
+            target.span,
+        ))
+    }
+}
+
+pub fn sftp_error(target: &Target, action: &str, error: ssh2::Error) -> ShellError {
+    ShellError::NetworkFailure(
+        format!("Cannot {action} {}:{}: {error}", target.host, target.path),
+        target.span,
+    )
+}
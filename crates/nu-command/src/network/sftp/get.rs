@@ -0,0 +1,106 @@
+use super::connection::{sftp_error, ConnectOptions, Target};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::util::BufferedReader;
+use nu_protocol::{
+    Category, Example, PipelineData, RawStream, ShellError, Signature, Spanned, SyntaxShape, Type,
+};
+use std::io::BufReader;
+use std::path::Path;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "sftp get"
+    }
+
+    fn signature(&self) -> Signature {
+        let signature = Signature::build("sftp get")
+            .input_output_types(vec![(Type::Nothing, Type::Any)])
+            .allow_variants_without_examples(true)
+            .required(
+                "target",
+                SyntaxShape::String,
+                "the remote file, as [user@]host:path",
+            );
+
+        ConnectOptions::add_flags(signature).category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Download a remote file over SFTP."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The contents of the file are streamed as they arrive. Pipe them to save to keep the file,
+or to a from command to read it as structured data."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["ssh", "scp", "remote", "download", "copy", "fetch"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let target: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let options = ConnectOptions::from_call(engine_state, stack, call)?;
+        let target = Target::parse(target, engine_state, stack)?;
+        let span = call.head;
+
+        let sftp = options.connect(&target)?;
+        let mut file = sftp
+            .open(Path::new(&target.path))
+            .map_err(|e| sftp_error(&target, "download", e))?;
+
+        let stat = file
+            .stat()
+            .map_err(|e| sftp_error(&target, "download", e))?;
+        if stat.is_dir() {
+            return Err(ShellError::NetworkFailure(
+                format!(
+                    "Cannot download {}:{}, it is a directory",
+                    target.host, target.path
+                ),
+                target.span,
+            ));
+        }
+
+        Ok(PipelineData::ExternalStream {
+            stdout: Some(RawStream::new(
+                Box::new(BufferedReader::new(BufReader::new(file))),
+                engine_state.ctrlc.clone(),
+                span,
+                stat.size,
+            )),
+            stderr: None,
+            exit_code: None,
+            resource_usage: None,
+            span,
+            metadata: None,
+            trim_end_newline: false,
+        })
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Download a file",
+                example: "sftp get example.com:backup.tar.gz | save backup.tar.gz",
+                result: None,
+            },
+            Example {
+                description: "Read the configuration of a server as structured data",
+                example: "sftp get admin@example.com:/etc/app/config.toml | from toml",
+                result: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,192 @@
+use super::connection::{sftp_error, ConnectOptions, Target};
+use chrono::{Local, TimeZone};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Type, Value,
+};
+use ssh2::{FileStat, FileType};
+use std::path::Path;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "sftp ls"
+    }
+
+    fn signature(&self) -> Signature {
+        let signature = Signature::build("sftp ls")
+            .input_output_types(vec![(Type::Nothing, Type::Table(vec![]))])
+            .allow_variants_without_examples(true)
+            .required(
+                "target",
+                SyntaxShape::String,
+                "the remote directory or file, as [user@]host:path",
+            )
+            .switch("all", "show hidden files", Some('a'));
+
+        ConnectOptions::add_flags(signature).category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "List the files of a remote directory over SFTP."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Returns a row for every file, like ls does. A path that is not a directory is listed
+on its own. Relative paths start from the home directory of the user."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["ssh", "scp", "remote", "dir", "list"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let target: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let all = call.has_flag("all");
+        let options = ConnectOptions::from_call(engine_state, stack, call)?;
+        let target = Target::parse(target, engine_state, stack)?;
+        let span = call.head;
+
+        let sftp = options.connect(&target)?;
+        let path = Path::new(&target.path);
+        let stat = sftp
+            .stat(path)
+            .map_err(|e| sftp_error(&target, "list", e))?;
+
+        let mut entries = if stat.is_dir() {
+            sftp.readdir(path)
+                .map_err(|e| sftp_error(&target, "list", e))?
+        } else {
+            vec![(path.to_path_buf(), stat)]
+        };
+
+        entries.retain(|(path, _)| {
+            all || !path
+                .file_name()
+                .map_or(false, |name| name.to_string_lossy().starts_with('.'))
+        });
+        entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        Ok(entries
+            .into_iter()
+            .map(move |(path, stat)| entry_to_value(&path, &stat, span))
+            .into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "List the files of the home directory on a host",
+                example: "sftp ls example.com:",
+                result: None,
+            },
+            Example {
+                description: "Find the large logs of a server",
+                example: "sftp ls admin@example.com:/var/log | where size > 10mb",
+                result: None,
+            },
+            Example {
+                description: "Authenticate with a given key, on another port",
+                example: "sftp ls -i ~/.ssh/deploy_key -p 2222 deploy@example.com:releases",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn entry_to_value(path: &Path, stat: &FileStat, span: Span) -> Value {
+    let file_type = match stat.file_type() {
+        FileType::Directory => "dir",
+        FileType::RegularFile => "file",
+        FileType::Symlink => "symlink",
+        FileType::BlockDevice => "block device",
+        FileType::CharDevice => "char device",
+        FileType::NamedPipe => "pipe",
+        FileType::Socket => "socket",
+        FileType::Other(_) => "unknown",
+    };
+
+    let size = match stat.size {
+        Some(size) => Value::Filesize {
+            val: size as i64,
+            span,
+        },
+        None => Value::nothing(span),
+    };
+
+    let modified = match stat
+        .mtime
+        .and_then(|mtime| Local.timestamp_opt(mtime as i64, 0).single())
+    {
+        Some(modified) => Value::Date {
+            val: modified.into(),
+            span,
+        },
+        None => Value::nothing(span),
+    };
+
+    let mode = match stat.perm {
+        Some(perm) => Value::string(mode_string(perm), span),
+        None => Value::nothing(span),
+    };
+
+    Value::Record {
+        cols: vec![
+            "name".into(),
+            "type".into(),
+            "size".into(),
+            "modified".into(),
+            "mode".into(),
+        ],
+        vals: vec![
+            Value::string(path.to_string_lossy(), span),
+            Value::string(file_type, span),
+            size,
+            modified,
+            mode,
+        ],
+        span,
+    }
+}
+
+// The permissions of the file, written like ls -l does
+fn mode_string(perm: u32) -> String {
+    let mut mode = String::with_capacity(9);
+    for (shift, special, special_char) in [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')] {
+        let bits = perm >> shift;
+        mode.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        mode.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        mode.push(match (bits & 0o1 != 0, perm & special != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    mode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mode_string;
+
+    #[test]
+    fn writes_modes_like_ls() {
+        assert_eq!(mode_string(0o100644), "rw-r--r--");
+        assert_eq!(mode_string(0o40755), "rwxr-xr-x");
+        assert_eq!(mode_string(0o4755), "rwsr-xr-x");
+        assert_eq!(mode_string(0o1777), "rwxrwxrwt");
+        assert_eq!(mode_string(0o2644), "rw-r-Sr--");
+    }
+}
@@ -0,0 +1,10 @@
+mod connection;
+mod get;
+mod ls;
+mod put;
+mod sftp_;
+
+pub use get::SubCommand as SftpGet;
+pub use ls::SubCommand as SftpLs;
+pub use put::SubCommand as SftpPut;
+pub use sftp_::Sftp;
@@ -0,0 +1,107 @@
+use super::connection::{sftp_error, ConnectOptions, Target};
+use crate::network::socket::data::write_input;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type,
+};
+use ssh2::OpenFlags;
+use std::path::Path;
+
+// The permissions of the files created, before the umask of the host
+const FILE_MODE: i32 = 0o644;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "sftp put"
+    }
+
+    fn signature(&self) -> Signature {
+        let signature = Signature::build("sftp put")
+            .input_output_types(vec![
+                (Type::String, Type::Nothing),
+                (Type::Binary, Type::Nothing),
+                (Type::List(Box::new(Type::Any)), Type::Nothing),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "target",
+                SyntaxShape::String,
+                "the remote file to write, as [user@]host:path",
+            )
+            .switch(
+                "append",
+                "append the input to the file instead of replacing it",
+                Some('a'),
+            );
+
+        ConnectOptions::add_flags(signature).category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Upload the input to a remote file over SFTP."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Strings and binary values are written as they are, and the values of a list are
+written one per line. The file is created when it does not exist."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["ssh", "scp", "remote", "upload", "copy", "save"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let target: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let append = call.has_flag("append");
+        let options = ConnectOptions::from_call(engine_state, stack, call)?;
+        let target = Target::parse(target, engine_state, stack)?;
+
+        let flags = OpenFlags::WRITE
+            | OpenFlags::CREATE
+            | if append {
+                OpenFlags::APPEND
+            } else {
+                OpenFlags::TRUNCATE
+            };
+
+        let sftp = options.connect(&target)?;
+        let mut file = sftp
+            .open_mode(
+                Path::new(&target.path),
+                flags,
+                FILE_MODE,
+                ssh2::OpenType::File,
+            )
+            .map_err(|e| sftp_error(&target, "upload to", e))?;
+
+        write_input(input, &mut file)?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Upload a file",
+                example: "open --raw backup.tar.gz | sftp put example.com:backups/backup.tar.gz",
+                result: None,
+            },
+            Example {
+                description: "Add a line to a remote log",
+                example: r#""deployed\n" | sftp put --append deploy@example.com:deploys.log"#,
+                result: None,
+            },
+        ]
+    }
+}
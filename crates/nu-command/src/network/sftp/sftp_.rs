@@ -0,0 +1,53 @@
+use nu_engine::get_full_help;
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct Sftp;
+
+impl Command for Sftp {
+    fn name(&self) -> &str {
+        "sftp"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("sftp")
+            .input_output_types(vec![(Type::Nothing, Type::String)])
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Various commands for transferring files over SFTP"
+    }
+
+    fn extra_usage(&self) -> &str {
+        "You must use one of the following subcommands. Using this command as-is will only produce this help message."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "ssh", "scp", "remote", "transfer"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        Ok(Value::String {
+            val: get_full_help(
+                &Sftp.signature(),
+                &Sftp.examples(),
+                engine_state,
+                stack,
+                self.is_parser_keyword(),
+            ),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+}
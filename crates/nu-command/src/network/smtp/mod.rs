@@ -0,0 +1,5 @@
+mod send;
+mod smtp_;
+
+pub use send::SubCommand as SmtpSend;
+pub use smtp_::Smtp;
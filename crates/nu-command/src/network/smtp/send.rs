@@ -0,0 +1,336 @@
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use nu_engine::{current_dir, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
+};
+use std::path::Path;
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "smtp send"
+    }
+
+    fn signature(&self) -> Signature {
+        let addresses = SyntaxShape::OneOf(vec![
+            SyntaxShape::List(Box::new(SyntaxShape::String)),
+            SyntaxShape::String,
+        ]);
+
+        Signature::build("smtp send")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Nothing),
+                (Type::String, Type::Nothing),
+                (Type::List(Box::new(Type::Any)), Type::Nothing),
+            ])
+            .allow_variants_without_examples(true)
+            .required_named(
+                "to",
+                addresses.clone(),
+                "address or list of addresses to send the email to",
+                None,
+            )
+            .required_named(
+                "subject",
+                SyntaxShape::String,
+                "the subject of the email",
+                Some('s'),
+            )
+            .named(
+                "cc",
+                addresses.clone(),
+                "address or list of addresses to send a copy to",
+                None,
+            )
+            .named(
+                "bcc",
+                addresses,
+                "address or list of addresses to send a hidden copy to",
+                None,
+            )
+            .named(
+                "attach",
+                SyntaxShape::List(Box::new(SyntaxShape::Filepath)),
+                "files to attach to the email",
+                Some('a'),
+            )
+            .switch("html", "send the body as HTML instead of plain text", None)
+            .named(
+                "from",
+                SyntaxShape::String,
+                "the sender of the email (default: $env.SMTP_FROM, then the user)",
+                Some('f'),
+            )
+            .named(
+                "server",
+                SyntaxShape::String,
+                "the SMTP server to send through (default: $env.SMTP_SERVER)",
+                None,
+            )
+            .named(
+                "port",
+                SyntaxShape::Int,
+                "the port of the server (default: $env.SMTP_PORT, then the port of the security mode)",
+                Some('p'),
+            )
+            .named(
+                "user",
+                SyntaxShape::String,
+                "the user to log in as (default: $env.SMTP_USER)",
+                Some('u'),
+            )
+            .named(
+                "password",
+                SyntaxShape::String,
+                "the password to log in with (default: $env.SMTP_PASSWORD)",
+                None,
+            )
+            .named(
+                "security",
+                SyntaxShape::String,
+                "how the connection is encrypted: starttls (default, port 587), tls (port 465) or none (port 25)",
+                None,
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the server (default 30sec)",
+                Some('t'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Send an email through an SMTP server."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The body of the email is the input, with the values of a list written one per line.
+The server, port, security mode, user, password and sender can be given in the SMTP_SERVER,
+SMTP_PORT, SMTP_SECURITY, SMTP_USER, SMTP_PASSWORD and SMTP_FROM environment variables, so
+scripts don't have to repeat them. The flags take precedence over the environment."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["email", "mail", "send", "alert", "notify", "mailx"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let subject: String = call
+            .get_flag(engine_state, stack, "subject")?
+            .unwrap_or_default();
+        let attachments: Option<Vec<Spanned<String>>> =
+            call.get_flag(engine_state, stack, "attach")?;
+        let html = call.has_flag("html");
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => DEFAULT_TIMEOUT,
+        };
+
+        let server =
+            setting(engine_state, stack, call, "server", "SMTP_SERVER")?.ok_or_else(|| {
+                ShellError::MissingParameter("--server, or $env.SMTP_SERVER".into(), span)
+            })?;
+        let user = setting(engine_state, stack, call, "user", "SMTP_USER")?;
+        let password = setting(engine_state, stack, call, "password", "SMTP_PASSWORD")?;
+        let from = match setting(engine_state, stack, call, "from", "SMTP_FROM")? {
+            Some(from) => from,
+            None => user.clone().ok_or_else(|| {
+                ShellError::MissingParameter("--from, or $env.SMTP_FROM".into(), span)
+            })?,
+        };
+
+        let mut builder = Message::builder()
+            .from(parse_mailbox(&from)?)
+            .subject(subject);
+        for to in addresses(call.get_flag(engine_state, stack, "to")?)? {
+            builder = builder.to(parse_mailbox(&to)?);
+        }
+        for cc in addresses(call.get_flag(engine_state, stack, "cc")?)? {
+            builder = builder.cc(parse_mailbox(&cc)?);
+        }
+        for bcc in addresses(call.get_flag(engine_state, stack, "bcc")?)? {
+            builder = builder.bcc(parse_mailbox(&bcc)?);
+        }
+
+        let body = match input {
+            PipelineData::Empty => String::new(),
+            input => input.collect_string("\n", engine_state.get_config())?,
+        };
+        let body = if html {
+            SinglePart::html(body)
+        } else {
+            SinglePart::plain(body)
+        };
+
+        let cwd = current_dir(engine_state, stack)?;
+        let message = match attachments {
+            Some(attachments) if !attachments.is_empty() => {
+                let mut parts = MultiPart::mixed().singlepart(body);
+                for attachment in attachments {
+                    parts = parts.singlepart(attach(&attachment, &cwd)?);
+                }
+                builder.multipart(parts)
+            }
+            _ => builder.singlepart(body),
+        }
+        .map_err(|e| {
+            ShellError::UnsupportedInput(
+                format!("Cannot write the email: {e}"),
+                "value originates from here".into(),
+                span,
+                span,
+            )
+        })?;
+
+        let security = setting(engine_state, stack, call, "security", "SMTP_SECURITY")?;
+        let transport = match security.as_ref().map(|security| security.item.as_str()) {
+            None | Some("starttls") => SmtpTransport::starttls_relay(&server.item),
+            Some("tls") => SmtpTransport::relay(&server.item),
+            Some("none") => Ok(SmtpTransport::builder_dangerous(&server.item)),
+            Some(other) => {
+                return Err(ShellError::UnsupportedConfigValue(
+                    "starttls, tls or none".into(),
+                    other.into(),
+                    security.map_or(span, |security| security.span),
+                ))
+            }
+        };
+        let mut transport = transport
+            .map_err(|e| {
+                ShellError::NetworkFailure(format!("Cannot use {}: {e}", server.item), server.span)
+            })?
+            .timeout(Some(timeout));
+
+        if let Some(port) = setting(engine_state, stack, call, "port", "SMTP_PORT")? {
+            let port = port.item.parse::<u16>().map_err(|_| {
+                ShellError::UnsupportedInput(
+                    format!(
+                        "{} is not a port number, they go from 0 to 65535",
+                        port.item
+                    ),
+                    "value originates from here".into(),
+                    span,
+                    port.span,
+                )
+            })?;
+            transport = transport.port(port);
+        }
+
+        if let Some(user) = user {
+            let password = password.map(|password| password.item).unwrap_or_default();
+            transport = transport.credentials(Credentials::new(user.item, password));
+        }
+
+        transport.build().send(&message).map_err(|e| {
+            ShellError::NetworkFailure(
+                format!("Cannot send the email through {}: {e}", server.item),
+                server.span,
+            )
+        })?;
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description:
+                    "Send an alert, with the server and credentials set in the environment",
+                example: r#""The disk is almost full" | smtp send --to ops@example.com --subject "Disk usage""#,
+                result: None,
+            },
+            Example {
+                description: "Send a report to several people, with a file attached",
+                example: r#"open summary.txt | smtp send --to [alice@example.com bob@example.com] -s "Weekly report" --attach [report.csv] --server smtp.example.com -u reports@example.com"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+// A setting given as a flag, or else in an environment variable
+fn setting(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    flag: &str,
+    env: &str,
+) -> Result<Option<Spanned<String>>, ShellError> {
+    if let Some(value) = call.get_flag::<Value>(engine_state, stack, flag)? {
+        return value.as_spanned_string().map(Some);
+    }
+
+    stack
+        .get_env_var(engine_state, env)
+        .map(|value| value.as_spanned_string())
+        .transpose()
+}
+
+fn addresses(value: Option<Value>) -> Result<Vec<Spanned<String>>, ShellError> {
+    let values = match value {
+        Some(Value::List { vals, .. }) => vals,
+        Some(value) => vec![value],
+        None => vec![],
+    };
+
+    values
+        .into_iter()
+        .map(|value| value.as_spanned_string())
+        .collect()
+}
+
+fn parse_mailbox(address: &Spanned<String>) -> Result<Mailbox, ShellError> {
+    address.item.parse().map_err(|e| {
+        ShellError::UnsupportedInput(
+            format!("Invalid email address {}: {e}", address.item),
+            "value originates from here".into(),
+            address.span,
+            address.span,
+        )
+    })
+}
+
+fn attach(path: &Spanned<String>, cwd: &Path) -> Result<SinglePart, ShellError> {
+    let full_path = nu_path::expand_path_with(&path.item, cwd);
+    let contents = std::fs::read(&full_path).map_err(|e| {
+        ShellError::FileNotFoundCustom(
+            format!("Cannot read {}: {e}", full_path.display()),
+            path.span,
+        )
+    })?;
+
+    let name = full_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.item.clone());
+    let content_type = mime_guess::from_path(&full_path)
+        .first_or_octet_stream()
+        .to_string();
+    let content_type = ContentType::parse(&content_type)
+        .unwrap_or_else(|_| ContentType::parse("application/octet-stream").expect("valid type"));
+
+    Ok(Attachment::new(name).body(contents, content_type))
+}
@@ -0,0 +1,64 @@
+use nu_protocol::{PipelineData, ShellError, Span, Spanned, Value};
+use std::io::Write;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+// Writes the input to a socket. Strings and binary values are written as they are, and the
+// values of a list are written one per line
+pub fn write_input(input: PipelineData, writer: &mut impl Write) -> Result<(), ShellError> {
+    let lines = matches!(
+        input,
+        PipelineData::ListStream(..) | PipelineData::Value(Value::List { .. }, ..)
+    );
+
+    for value in input {
+        let mut bytes = match value {
+            Value::String { val, .. } => val.into_bytes(),
+            Value::Binary { val, .. } => val,
+            Value::Error { error } => return Err(error),
+            other => other.as_string()?.into_bytes(),
+        };
+        if lines {
+            bytes.push(b'\n');
+        }
+
+        writer
+            .write_all(&bytes)
+            .map_err(|e| ShellError::IOError(e.to_string()))?;
+    }
+
+    writer
+        .flush()
+        .map_err(|e| ShellError::IOError(e.to_string()))
+}
+
+// Data read from a socket is text when it is valid UTF-8
+pub fn bytes_to_value(bytes: Vec<u8>, span: Span) -> Value {
+    match String::from_utf8(bytes) {
+        Ok(val) => Value::String { val, span },
+        Err(e) => Value::Binary {
+            val: e.into_bytes(),
+            span,
+        },
+    }
+}
+
+pub fn resolve(address: &Spanned<String>) -> Result<SocketAddr, ShellError> {
+    address
+        .item
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| {
+            ShellError::NetworkFailure(
+                format!("Cannot resolve {}, expected host:port", address.item),
+                address.span,
+            )
+        })
+}
+
+pub fn address_value(address: std::io::Result<SocketAddr>, span: Span) -> Value {
+    match address {
+        Ok(address) => Value::string(address.to_string(), span),
+        Err(_) => Value::nothing(span),
+    }
+}
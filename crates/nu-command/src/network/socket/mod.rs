@@ -0,0 +1,12 @@
+pub(super) mod data;
+mod tcp_;
+mod tcp_connect;
+mod tcp_listen;
+mod udp_;
+mod udp_send;
+
+pub use tcp_::Tcp;
+pub use tcp_connect::SubCommand as TcpConnect;
+pub use tcp_listen::SubCommand as TcpListen;
+pub use udp_::Udp;
+pub use udp_send::SubCommand as UdpSend;
@@ -0,0 +1,171 @@
+use super::data::{address_value, bytes_to_value, resolve, write_input};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::util::BufferedReader;
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, RawStream, ShellError, Signature, Spanned,
+    SyntaxShape, Type, Value,
+};
+use std::io::{BufReader, Read};
+use std::net::{Shutdown, TcpStream};
+use std::time::{Duration, Instant};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "tcp connect"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("tcp connect")
+            .input_output_types(vec![(Type::Nothing, Type::Any), (Type::Any, Type::Any)])
+            .allow_variants_without_examples(true)
+            .required(
+                "address",
+                SyntaxShape::String,
+                "the host:port to connect to",
+            )
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the connection",
+                Some('t'),
+            )
+            .switch(
+                "full",
+                "return a record with the addresses of the connection, the data received and the elapsed time",
+                Some('f'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Connect to a TCP server, send it the input and stream what it sends back."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Like netcat, the input is sent while the data from the server is read. Strings and binary
+values are sent as they are, and the values of a list are sent one per line. Once all the
+input is sent, the sending side of the connection is closed, so the server sees the end of
+the input. The output ends when the server closes the connection."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "socket", "netcat", "nc", "telnet"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let address: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let full = call.has_flag("full");
+        let span = call.head;
+
+        let socket_address = resolve(&address)?;
+        let start = Instant::now();
+        let stream = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            Some(timeout) => TcpStream::connect_timeout(
+                &socket_address,
+                Duration::from_nanos(timeout.item as u64),
+            ),
+            None => TcpStream::connect(socket_address),
+        }
+        .map_err(|e| {
+            ShellError::NetworkFailure(
+                format!("Cannot connect to {}: {e}", address.item),
+                address.span,
+            )
+        })?;
+
+        if !input.is_nothing() {
+            let mut writer = stream
+                .try_clone()
+                .map_err(|e| ShellError::IOError(e.to_string()))?;
+
+            std::thread::Builder::new()
+                .name("tcp connect writer".to_string())
+                .spawn(move || {
+                    let result = write_input(input, &mut writer);
+                    let _ = writer.shutdown(Shutdown::Write);
+                    result
+                })
+                .map_err(|e| ShellError::IOError(e.to_string()))?;
+        }
+
+        if full {
+            let local_address = address_value(stream.local_addr(), span);
+            let remote_address = address_value(stream.peer_addr(), span);
+
+            let mut data = vec![];
+            BufReader::new(stream)
+                .read_to_end(&mut data)
+                .map_err(|e| ShellError::NetworkFailure(e.to_string(), address.span))?;
+
+            return Ok(Value::Record {
+                cols: vec![
+                    "local_address".into(),
+                    "remote_address".into(),
+                    "data".into(),
+                    "elapsed".into(),
+                ],
+                vals: vec![
+                    local_address,
+                    remote_address,
+                    bytes_to_value(data, span),
+                    Value::Duration {
+                        val: start.elapsed().as_nanos() as i64,
+                        span,
+                    },
+                ],
+                span,
+            }
+            .into_pipeline_data());
+        }
+
+        Ok(PipelineData::ExternalStream {
+            stdout: Some(RawStream::new(
+                Box::new(BufferedReader::new(BufReader::new(stream))),
+                engine_state.ctrlc.clone(),
+                span,
+                None,
+            )),
+            stderr: None,
+            exit_code: None,
+            resource_usage: None,
+            span,
+            metadata: None,
+            trim_end_newline: false,
+        })
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Send a raw HTTP request and show the response",
+                example: r#""GET / HTTP/1.0\r\n\r\n" | tcp connect example.com:80"#,
+                result: None,
+            },
+            Example {
+                description: "Send lines to a server, and get the lines it sends back",
+                example: r#"[PING "INFO server"] | tcp connect localhost:6379 | lines"#,
+                result: None,
+            },
+            Example {
+                description: "Check which address a connection used, and how long it took",
+                example: "tcp connect --full --timeout 2sec localhost:22 | reject data",
+                result: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,160 @@
+use super::data::{address_value, bytes_to_value, resolve, write_input};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Type, Value,
+};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+// How often waiting for a connection checks for ctrl-c
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "tcp listen"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("tcp listen")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Table(vec![])),
+                (Type::Any, Type::Table(vec![])),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "address",
+                SyntaxShape::String,
+                "the port, or address:port, to listen on",
+            )
+            .switch("once", "stop after the first connection", Some('o'))
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Accept TCP connections, and return what every client sends."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The input is sent to every client as soon as it connects, then the sending side of the
+connection is closed. A row with the addresses of the connection and the data received is
+returned once the client closes the connection. Without --once, connections are accepted
+until ctrl-c is pressed."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "socket", "netcat", "server", "accept"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let address: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let once = call.has_flag("once");
+        let span = call.head;
+
+        // A bare port listens on every interface
+        let socket_address = match address.item.parse::<u16>() {
+            Ok(port) => SocketAddr::from(([0, 0, 0, 0], port)),
+            Err(_) => resolve(&address)?,
+        };
+
+        let mut reply = vec![];
+        write_input(input, &mut reply)?;
+
+        let listener = TcpListener::bind(socket_address)
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+            .map_err(|e| {
+                ShellError::NetworkFailure(
+                    format!("Cannot listen on {}: {e}", address.item),
+                    address.span,
+                )
+            })?;
+
+        let ctrlc = engine_state.ctrlc.clone();
+        let mut done = false;
+
+        let connections = std::iter::from_fn(move || {
+            while !done {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        done = once;
+                        return Some(handle_connection(stream, &reply, span));
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        if let Some(ctrlc) = &ctrlc {
+                            if ctrlc.load(Ordering::SeqCst) {
+                                return None;
+                            }
+                        }
+                        std::thread::sleep(POLL_INTERVAL);
+                    }
+                    Err(e) => {
+                        done = true;
+                        return Some(Value::Error {
+                            error: ShellError::NetworkFailure(e.to_string(), span),
+                        });
+                    }
+                }
+            }
+
+            None
+        });
+
+        Ok(connections.into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "See the request an HTTP client sends",
+                example: "tcp listen --once 8080 | get data",
+                result: None,
+            },
+            Example {
+                description: "Answer every connection with the same response",
+                example: r#""HTTP/1.0 200 OK\r\n\r\nhello" | tcp listen 127.0.0.1:8080"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, reply: &[u8], span: Span) -> Value {
+    let local_address = address_value(stream.local_addr(), span);
+    let remote_address = address_value(stream.peer_addr(), span);
+
+    let mut data = vec![];
+    let result = stream
+        .set_nonblocking(false)
+        .and_then(|_| stream.write_all(reply))
+        .and_then(|_| stream.shutdown(Shutdown::Write))
+        .and_then(|_| stream.read_to_end(&mut data));
+
+    if let Err(e) = result {
+        return Value::Error {
+            error: ShellError::NetworkFailure(e.to_string(), span),
+        };
+    }
+
+    Value::Record {
+        cols: vec![
+            "remote_address".into(),
+            "local_address".into(),
+            "data".into(),
+        ],
+        vals: vec![remote_address, local_address, bytes_to_value(data, span)],
+        span,
+    }
+}
@@ -0,0 +1,143 @@
+use super::data::{address_value, bytes_to_value, resolve, write_input};
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
+    Type, Value,
+};
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::Duration;
+
+// The largest datagram UDP can carry
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "udp send"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("udp send")
+            .input_output_types(vec![(Type::Any, Type::Record(vec![]))])
+            .allow_variants_without_examples(true)
+            .required("address", SyntaxShape::String, "the host:port to send to")
+            .switch("reply", "wait for a datagram in reply", Some('r'))
+            .named(
+                "timeout",
+                SyntaxShape::Duration,
+                "how long to wait for the reply (default 1sec)",
+                Some('t'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Send the input as a single UDP datagram."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Returns the addresses the datagram was sent from and to, and how many bytes were sent.
+With --reply, the first datagram received within the timeout is returned as well, or null
+if none arrived."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "socket", "netcat", "datagram"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let address: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let reply = call.has_flag("reply");
+        let timeout: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "timeout")?;
+        let span = call.head;
+
+        let timeout = match timeout {
+            Some(timeout) if timeout.item <= 0 => {
+                return Err(ShellError::NeedsPositiveValue(timeout.span))
+            }
+            Some(timeout) => Duration::from_nanos(timeout.item as u64),
+            None => DEFAULT_TIMEOUT,
+        };
+
+        let socket_address = resolve(&address)?;
+        let mut data = vec![];
+        write_input(input, &mut data)?;
+
+        let network_error = |e: std::io::Error| {
+            ShellError::NetworkFailure(
+                format!("Cannot send to {}: {e}", address.item),
+                address.span,
+            )
+        };
+
+        let local: SocketAddr = match socket_address {
+            SocketAddr::V4(_) => ([0, 0, 0, 0], 0).into(),
+            SocketAddr::V6(_) => ([0u16; 8], 0).into(),
+        };
+        let socket = UdpSocket::bind(local).map_err(network_error)?;
+        let sent = socket
+            .send_to(&data, socket_address)
+            .map_err(network_error)?;
+
+        let mut cols = vec![
+            "local_address".into(),
+            "remote_address".into(),
+            "bytes_sent".into(),
+        ];
+        let mut vals = vec![
+            address_value(socket.local_addr(), span),
+            Value::string(socket_address.to_string(), span),
+            Value::int(sent as i64, span),
+        ];
+
+        if reply {
+            socket
+                .set_read_timeout(Some(timeout))
+                .map_err(network_error)?;
+
+            let mut buffer = vec![0; MAX_DATAGRAM_SIZE];
+            let reply = match socket.recv_from(&mut buffer) {
+                Ok((size, _)) => {
+                    buffer.truncate(size);
+                    bytes_to_value(buffer, span)
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    Value::nothing(span)
+                }
+                Err(e) => return Err(network_error(e)),
+            };
+
+            cols.push("reply".into());
+            vals.push(reply);
+        }
+
+        Ok(Value::Record { cols, vals, span }.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Send a metric to a statsd server",
+                example: r#""requests:1|c" | udp send localhost:8125"#,
+                result: None,
+            },
+            Example {
+                description: "Send a datagram and wait for the reply",
+                example: r#""ping" | udp send --reply --timeout 500ms localhost:9999 | get reply"#,
+                result: None,
+            },
+        ]
+    }
+}
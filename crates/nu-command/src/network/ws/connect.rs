@@ -0,0 +1,206 @@
+use crate::formats::value_to_json_value;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Type, Value,
+};
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tungstenite::client::IntoClientRequest;
+use tungstenite::http::header::{HeaderName, HeaderValue};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+// How often a read waiting for a message checks for ctrl-c
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "ws connect"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ws connect")
+            .input_output_types(vec![
+                (Type::Nothing, Type::List(Box::new(Type::Any))),
+                (Type::Any, Type::List(Box::new(Type::Any))),
+            ])
+            .allow_variants_without_examples(true)
+            .required(
+                "URL",
+                SyntaxShape::String,
+                "the ws:// or wss:// URL to connect to",
+            )
+            .named(
+                "headers",
+                SyntaxShape::Record,
+                "headers to send with the opening handshake, e.g. for authentication",
+                Some('H'),
+            )
+            .category(Category::Network)
+    }
+
+    fn usage(&self) -> &str {
+        "Connect to a WebSocket, and stream the messages it receives."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The values piped in are sent once the connection is open, before the incoming messages
+are streamed. Strings are sent as text messages and binary values as binary messages. Other
+values are sent as JSON text.
+
+The stream ends when the server closes the connection. Use `first` or `take until` to stop
+earlier."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["network", "websocket", "subscribe", "stream"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let url: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let headers: Option<Value> = call.get_flag(engine_state, stack, "headers")?;
+
+        let mut request = url.item.as_str().into_client_request().map_err(|e| {
+            ShellError::TypeMismatch(
+                format!("Incomplete or incorrect WebSocket URL: {e}"),
+                url.span,
+            )
+        })?;
+
+        if let Some(Value::Record { cols, vals, .. }) = headers {
+            for (name, value) in cols.into_iter().zip(vals) {
+                let span = value.span()?;
+                let name = HeaderName::from_str(&name).map_err(|e| invalid_header(e, span))?;
+                let value = HeaderValue::from_str(&value.as_string()?)
+                    .map_err(|e| invalid_header(e, span))?;
+                request.headers_mut().insert(name, value);
+            }
+        }
+
+        let (mut socket, _) = tungstenite::connect(request).map_err(|e| {
+            ShellError::NetworkFailure(format!("Cannot connect to {}: {e}", url.item), url.span)
+        })?;
+
+        for value in input {
+            let message = value_to_message(value)?;
+            socket.write_message(message).map_err(|e| {
+                ShellError::NetworkFailure(format!("Cannot send message: {e}"), url.span)
+            })?;
+        }
+
+        // Reads time out regularly so that ctrl-c is noticed without waiting for a message
+        set_read_timeout(&socket, Some(POLL_INTERVAL));
+
+        let ctrlc = engine_state.ctrlc.clone();
+        let span = call.head;
+        let mut closed = false;
+
+        let messages = std::iter::from_fn(move || {
+            while !closed {
+                match socket.read_message() {
+                    Ok(Message::Text(text)) => return Some(Value::string(text, span)),
+                    Ok(Message::Binary(val)) => return Some(Value::Binary { val, span }),
+                    // Pings are answered by the socket itself
+                    Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_)) => {}
+                    Ok(Message::Close(_))
+                    | Err(
+                        tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed,
+                    ) => closed = true,
+                    Err(tungstenite::Error::Io(e))
+                        if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                    {
+                        if let Some(ctrlc) = &ctrlc {
+                            if ctrlc.load(Ordering::SeqCst) {
+                                closed = true;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        closed = true;
+                        return Some(Value::Error {
+                            error: ShellError::NetworkFailure(e.to_string(), span),
+                        });
+                    }
+                }
+            }
+
+            None
+        });
+
+        Ok(messages.into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Print the messages of a WebSocket as they arrive",
+                example: "ws connect wss://ws.example.com/feed",
+                result: None,
+            },
+            Example {
+                description: "Subscribe to a feed, and keep the first 10 updates",
+                example: r#"{type: subscribe, channel: ticker} | ws connect wss://ws.example.com | first 10 | each { from json }"#,
+                result: None,
+            },
+            Example {
+                description: "Connect with an authorization header",
+                example: r#"ws connect -H {Authorization: "Bearer token"} wss://ws.example.com"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+fn value_to_message(value: Value) -> Result<Message, ShellError> {
+    match value {
+        Value::String { val, .. } => Ok(Message::Text(val)),
+        Value::Binary { val, .. } => Ok(Message::Binary(val)),
+        Value::Error { error } => Err(error),
+        other => {
+            let json = value_to_json_value(&other)?;
+            Ok(Message::Text(nu_json::to_string_raw(&json).map_err(
+                |e| {
+                    ShellError::CantConvert(
+                        "JSON".into(),
+                        other.get_type().to_string(),
+                        other.span().unwrap_or_else(|_| Span::unknown()),
+                        Some(e.to_string()),
+                    )
+                },
+            )?))
+        }
+    }
+}
+
+fn set_read_timeout(socket: &WebSocket<MaybeTlsStream<TcpStream>>, timeout: Option<Duration>) {
+    let stream = match socket.get_ref() {
+        MaybeTlsStream::Plain(stream) => stream,
+        MaybeTlsStream::NativeTls(stream) => stream.get_ref(),
+        _ => return,
+    };
+    let _ = stream.set_read_timeout(timeout);
+}
+
+fn invalid_header(error: impl std::fmt::Display, span: Span) -> ShellError {
+    ShellError::UnsupportedInput(
+        format!("Invalid header: {error}"),
+        "value originates from here".into(),
+        span,
+        span,
+    )
+}
@@ -0,0 +1,107 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type};
+
+use super::env_path::{
+    expand_entry, get_path_entries, path_var_name, same_entry, set_path_entries,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "path add"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("path add")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .rest(
+                "paths",
+                SyntaxShape::Filepath,
+                "the directories to add to PATH",
+            )
+            .switch(
+                "append",
+                "add the directories at the end of PATH instead of the start",
+                Some('a'),
+            )
+            .allow_variants_without_examples(true)
+    }
+
+    fn usage(&self) -> &str {
+        "Add directories to the PATH environment variable."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The directories are made absolute, and put at the start of PATH, so they take precedence. A
+directory that is already in PATH is moved rather than added twice. Works with PATH as a list or as
+a string, and with the Path variable of Windows, where directories are compared ignoring case."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["env", "environment", "prepend", "append"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let paths: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+        let append = call.has_flag("append");
+
+        let name = path_var_name(engine_state, stack);
+        let mut entries = get_path_entries(engine_state, stack, &name, head)?;
+
+        let mut added = vec![];
+        for path in paths {
+            let entry = expand_entry(engine_state, stack, &path.item)?;
+            if !added.iter().any(|seen| same_entry(seen, &entry)) {
+                added.push(entry);
+            }
+        }
+        entries.retain(|entry| !added.iter().any(|new| same_entry(new, entry)));
+
+        let entries = if append {
+            entries.into_iter().chain(added).collect()
+        } else {
+            added.into_iter().chain(entries).collect()
+        };
+        set_path_entries(stack, name, entries, head);
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Put a directory first in PATH",
+                example: "path add ~/.cargo/bin",
+                result: None,
+            },
+            Example {
+                description: "Add a directory to the end of PATH, as a fallback",
+                example: "path add --append /opt/tools/bin",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
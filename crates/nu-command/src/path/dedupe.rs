@@ -0,0 +1,68 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Example, PipelineData, ShellError, Signature, Type};
+
+use super::env_path::{dedupe, get_path_entries, path_var_name, set_path_entries};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "path dedupe"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("path dedupe")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .allow_variants_without_examples(true)
+    }
+
+    fn usage(&self) -> &str {
+        "Remove the repeated directories of the PATH environment variable."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The first entry for each directory is kept, so the order programs are found in doesn't
+change. Entries are compared ignoring trailing separators, and case on Windows."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["env", "environment", "duplicates", "uniq"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let name = path_var_name(engine_state, stack);
+        let entries = get_path_entries(engine_state, stack, &name, head)?;
+        set_path_entries(stack, name, dedupe(entries), head);
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Clean up PATH after sourcing scripts that add to it",
+            example: "path dedupe",
+            result: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
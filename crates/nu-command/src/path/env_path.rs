@@ -0,0 +1,84 @@
+use nu_engine::{current_dir, env_to_string};
+use nu_path::expand_path_with;
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{ShellError, Span, Value};
+use std::path::Path;
+
+/// The name PATH goes by on this platform. On Windows, environment variable names are
+/// case-insensitive, and PATH is usually spelled Path, but either may be set.
+pub fn path_var_name(engine_state: &EngineState, stack: &Stack) -> String {
+    if cfg!(windows) {
+        stack
+            .get_env_var_names(engine_state)
+            .into_iter()
+            .find(|name| name.eq_ignore_ascii_case("path"))
+            .unwrap_or_else(|| "Path".into())
+    } else {
+        "PATH".into()
+    }
+}
+
+/// The entries of PATH, whether it is still a string or already a list
+pub fn get_path_entries(
+    engine_state: &EngineState,
+    stack: &Stack,
+    name: &str,
+    head: Span,
+) -> Result<Vec<String>, ShellError> {
+    match stack.get_env_var(engine_state, name) {
+        Some(Value::List { vals, .. }) => vals.iter().map(|v| v.as_string()).collect(),
+        Some(value @ Value::String { .. }) => {
+            let path = env_to_string(name, &value, engine_state, stack)?;
+            Ok(std::env::split_paths(&path)
+                .map(|p| p.to_string_lossy().to_string())
+                .filter(|p| !p.is_empty())
+                .collect())
+        }
+        Some(other) => Err(ShellError::UnsupportedInput(
+            format!("${name} must be a list or a string"),
+            "value originates from here".into(),
+            head,
+            other.span()?,
+        )),
+        None => Ok(vec![]),
+    }
+}
+
+pub fn set_path_entries(stack: &mut Stack, name: String, entries: Vec<String>, head: Span) {
+    let vals = entries
+        .into_iter()
+        .map(|entry| Value::string(entry, head))
+        .collect();
+    stack.add_env_var(name, Value::List { vals, span: head });
+}
+
+/// Makes an entry absolute, expanding `~`, so it's found whatever the current directory
+pub fn expand_entry(
+    engine_state: &EngineState,
+    stack: &Stack,
+    entry: &str,
+) -> Result<String, ShellError> {
+    let cwd = current_dir(engine_state, stack)?;
+    Ok(expand_path_with(entry, cwd).to_string_lossy().to_string())
+}
+
+/// Whether two entries name the same directory, ignoring trailing separators, and case on Windows
+pub fn same_entry(a: &str, b: &str) -> bool {
+    if cfg!(windows) {
+        let normalize = |entry: &str| entry.trim_end_matches(['\\', '/']).to_lowercase();
+        normalize(a) == normalize(b)
+    } else {
+        Path::new(a).components().eq(Path::new(b).components())
+    }
+}
+
+/// Removes later entries naming the same directory as an earlier one
+pub fn dedupe(entries: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if !result.iter().any(|seen| same_entry(seen, &entry)) {
+            result.push(entry);
+        }
+    }
+    result
+}
@@ -1,17 +1,23 @@
+mod add;
 mod basename;
+mod dedupe;
 mod dirname;
+mod env_path;
 mod exists;
 mod expand;
 mod join;
 mod parse;
 pub mod path_;
 mod relative_to;
+mod remove;
 mod split;
 mod r#type;
 
 use std::path::Path as StdPath;
 
+pub use add::SubCommand as PathAdd;
 pub use basename::SubCommand as PathBasename;
+pub use dedupe::SubCommand as PathDedupe;
 pub use dirname::SubCommand as PathDirname;
 pub use exists::SubCommand as PathExists;
 pub use expand::SubCommand as PathExpand;
@@ -20,6 +26,7 @@ pub use parse::SubCommand as PathParse;
 pub use path_::PathCommand as Path;
 pub use r#type::SubCommand as PathType;
 pub use relative_to::SubCommand as PathRelativeTo;
+pub use remove::SubCommand as PathRemove;
 pub use split::SubCommand as PathSplit;
 
 use nu_protocol::{ShellError, Span, Value};
@@ -0,0 +1,91 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type};
+
+use super::env_path::{
+    expand_entry, get_path_entries, path_var_name, same_entry, set_path_entries,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "path remove"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("path remove")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .rest(
+                "paths",
+                SyntaxShape::Filepath,
+                "the directories to remove from PATH",
+            )
+            .allow_variants_without_examples(true)
+    }
+
+    fn usage(&self) -> &str {
+        "Remove directories from the PATH environment variable."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Every entry naming one of the directories is removed, however it's written: relative or
+absolute, with or without a trailing separator, and in any case on Windows. Directories that aren't
+in PATH are ignored."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["env", "environment", "delete"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let paths: Vec<Spanned<String>> = call.rest(engine_state, stack, 0)?;
+
+        let name = path_var_name(engine_state, stack);
+        let entries = get_path_entries(engine_state, stack, &name, head)?;
+
+        let removed = paths
+            .iter()
+            .map(|path| expand_entry(engine_state, stack, &path.item))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut kept = vec![];
+        for entry in entries {
+            let expanded = expand_entry(engine_state, stack, &entry)?;
+            if !removed.iter().any(|path| same_entry(path, &expanded)) {
+                kept.push(entry);
+            }
+        }
+        set_path_entries(stack, name, kept, head);
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Stop looking for programs in a directory",
+            example: "path remove ~/.local/bin",
+            result: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -1,9 +1,11 @@
 mod ansi_;
 mod gradient;
 mod link;
+mod parse;
 mod strip;
 
 pub use ansi_::AnsiCommand as Ansi;
 pub use gradient::SubCommand as AnsiGradient;
 pub use link::SubCommand as AnsiLink;
+pub use parse::SubCommand as AnsiParse;
 pub use strip::SubCommand as AnsiStrip;
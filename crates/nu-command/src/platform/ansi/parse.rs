@@ -0,0 +1,436 @@
+use nu_ansi_term::{Color, Style};
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "ansi parse"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ansi parse")
+            .input_output_types(vec![(Type::String, Type::Table(vec![]))])
+            .vectorizes_over_list(true)
+            .category(Category::Platform)
+    }
+
+    fn usage(&self) -> &str {
+        "Split a string with ANSI escape sequences into segments of text and their style"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Each style is a record like the ones of the color config, with fg, bg and attr columns
+that can be given back to `ansi --escape`. Unset colors and attributes are null, and colors are
+names like `red` or hex codes like `#ff8700`. Escape sequences other than colors and attributes,
+like cursor movements, window titles and hyperlinks, are left out."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["color", "colour", "style", "escape", "sgr", "segments"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        input.map(
+            move |value| action(&value, head),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let style = |fg: &str| Value::Record {
+            cols: vec!["fg".into(), "bg".into(), "attr".into()],
+            vals: vec![
+                Value::test_string(fg),
+                Value::nothing(Span::test_data()),
+                Value::nothing(Span::test_data()),
+            ],
+            span: Span::test_data(),
+        };
+        let segment = |style: Value, text: &str| Value::Record {
+            cols: vec!["style".into(), "text".into()],
+            vals: vec![style, Value::test_string(text)],
+            span: Span::test_data(),
+        };
+
+        vec![
+            Example {
+                description: "Split colored text into its segments",
+                example: r#"$'(ansi red)error:(ansi green) retrying' | ansi parse"#,
+                result: Some(Value::List {
+                    vals: vec![
+                        segment(style("red"), "error:"),
+                        segment(style("green"), " retrying"),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Find the bold parts of a string",
+                example: r#"$'plain (ansi green_bold)strong(ansi reset)' | ansi parse | get style.attr"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => Value::List {
+            vals: segments(val)
+                .into_iter()
+                .map(|(style, text)| {
+                    Value::record(
+                        vec!["style".into(), "text".into()],
+                        vec![style_record(&style, head), Value::string(text, head)],
+                        head,
+                    )
+                })
+                .collect(),
+            span: head,
+        },
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+/// A part of a string with escape sequences
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Token<'a> {
+    Text(&'a str),
+    /// A control sequence like `ESC [ 1;31 m`, with its parameters and final character
+    Csi(&'a str, char),
+    /// An operating system command like a hyperlink or a window title, with its payload
+    Osc(&'a str),
+    /// Any other escape sequence
+    Escape(&'a str),
+}
+
+/// Split a string into text and escape sequences. Sequences that never end run until the end of
+/// the string.
+pub(super) fn tokenize(s: &str) -> Vec<Token> {
+    let bytes = s.as_bytes();
+    let mut tokens = vec![];
+    let mut text_start = 0;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] != 0x1b {
+            idx += 1;
+            continue;
+        }
+        if text_start < idx {
+            tokens.push(Token::Text(&s[text_start..idx]));
+        }
+
+        let start = idx;
+        idx += 1;
+        match bytes.get(idx) {
+            Some(b'[') => {
+                idx += 1;
+                let params = idx;
+                while idx < bytes.len() && (0x20..=0x3f).contains(&bytes[idx]) {
+                    idx += 1;
+                }
+                match bytes.get(idx) {
+                    Some(&byte) if (0x40..=0x7e).contains(&byte) => {
+                        tokens.push(Token::Csi(&s[params..idx], byte as char));
+                        idx += 1;
+                    }
+                    _ => tokens.push(Token::Escape(&s[start..idx])),
+                }
+            }
+            // OSC, DCS, SOS, PM and APC strings end with BEL or with ESC \
+            Some(&byte @ (b']' | b'P' | b'X' | b'^' | b'_')) => {
+                idx += 1;
+                let payload = idx;
+                let mut end = bytes.len();
+                while idx < bytes.len() {
+                    if bytes[idx] == 0x07 {
+                        end = idx;
+                        idx += 1;
+                        break;
+                    }
+                    if bytes[idx] == 0x1b && bytes.get(idx + 1) == Some(&b'\\') {
+                        end = idx;
+                        idx += 2;
+                        break;
+                    }
+                    idx += 1;
+                }
+                if byte == b']' {
+                    tokens.push(Token::Osc(&s[payload..end]));
+                } else {
+                    tokens.push(Token::Escape(&s[start..idx]));
+                }
+            }
+            _ => {
+                while idx < bytes.len() && (0x20..=0x2f).contains(&bytes[idx]) {
+                    idx += 1;
+                }
+                if idx < bytes.len() && (0x30..=0x7e).contains(&bytes[idx]) {
+                    idx += 1;
+                }
+                tokens.push(Token::Escape(&s[start..idx]));
+            }
+        }
+        text_start = idx;
+    }
+    if text_start < bytes.len() {
+        tokens.push(Token::Text(&s[text_start..]));
+    }
+    tokens
+}
+
+/// The text of a string with the styles it is written in, joining the text of equal styles
+fn segments(s: &str) -> Vec<(Style, String)> {
+    let mut segments: Vec<(Style, String)> = vec![];
+    let mut style = Style::new();
+    for token in tokenize(s) {
+        match token {
+            Token::Text(text) => match segments.last_mut() {
+                Some((last, last_text)) if *last == style => last_text.push_str(text),
+                _ => segments.push((style, text.to_string())),
+            },
+            Token::Csi(params, 'm') => style = apply_sgr(style, params),
+            _ => {}
+        }
+    }
+    segments
+}
+
+/// Change a style with the parameters of a Select Graphic Rendition sequence
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    // Parameters are separated by semicolons, or by colons within one parameter like 38:2::r:g:b.
+    // An empty parameter is 0, and one out of range is ignored.
+    let groups: Vec<Vec<u8>> = params
+        .split(';')
+        .map(|group| {
+            group
+                .split(':')
+                .map(|param| match param {
+                    "" => 0,
+                    _ => param.parse().unwrap_or(u8::MAX),
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut idx = 0;
+    while idx < groups.len() {
+        let group = &groups[idx];
+        idx += 1;
+        match group[0] {
+            0 => style = Style::new(),
+            1 => style.is_bold = true,
+            2 => style.is_dimmed = true,
+            3 => style.is_italic = true,
+            4 => style.is_underline = true,
+            5 | 6 => style.is_blink = true,
+            7 => style.is_reverse = true,
+            8 => style.is_hidden = true,
+            9 => style.is_strikethrough = true,
+            22 => {
+                style.is_bold = false;
+                style.is_dimmed = false;
+            }
+            23 => style.is_italic = false,
+            24 => style.is_underline = false,
+            25 => style.is_blink = false,
+            27 => style.is_reverse = false,
+            28 => style.is_hidden = false,
+            29 => style.is_strikethrough = false,
+            code @ (30..=37 | 90..=97) => style.foreground = Some(basic_color(code)),
+            code @ (40..=47 | 100..=107) => style.background = Some(basic_color(code - 10)),
+            39 => style.foreground = None,
+            49 => style.background = None,
+            code @ (38 | 48) => {
+                let color = if group.len() > 1 {
+                    extended_color(&group[1..])
+                } else {
+                    // The color takes the next parameters, like 38;5;n or 38;2;r;g;b
+                    let rest: Vec<u8> = groups[idx..].iter().map(|group| group[0]).collect();
+                    let used = match rest.first() {
+                        Some(5) => 2,
+                        Some(2) => 4,
+                        _ => 1,
+                    };
+                    idx += used.min(rest.len());
+                    extended_color(&rest[..used.min(rest.len())])
+                };
+                if let Some(color) = color {
+                    if code == 38 {
+                        style.foreground = Some(color);
+                    } else {
+                        style.background = Some(color);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    style
+}
+
+fn extended_color(params: &[u8]) -> Option<Color> {
+    match params {
+        [5, n] => Some(Color::Fixed(*n)),
+        [2, r, g, b] | [2, _, r, g, b] => Some(Color::Rgb(*r, *g, *b)),
+        _ => None,
+    }
+}
+
+/// The color of a foreground code from 30 to 37 or 90 to 97
+fn basic_color(code: u8) -> Color {
+    if code >= 90 {
+        Color::Fixed(code - 90 + 8)
+    } else {
+        Color::Fixed(code - 30)
+    }
+}
+
+fn style_record(style: &Style, head: Span) -> Value {
+    let color = |color: Option<Color>| match color {
+        Some(color) => Value::string(color_name(color), head),
+        None => Value::nothing(head),
+    };
+
+    let attr: String = [
+        (style.is_blink, 'l'),
+        (style.is_bold, 'b'),
+        (style.is_dimmed, 'd'),
+        (style.is_hidden, 'h'),
+        (style.is_italic, 'i'),
+        (style.is_reverse, 'r'),
+        (style.is_strikethrough, 's'),
+        (style.is_underline, 'u'),
+    ]
+    .iter()
+    .filter_map(|(set, letter)| set.then_some(*letter))
+    .collect();
+
+    Value::record(
+        vec!["fg".into(), "bg".into(), "attr".into()],
+        vec![
+            color(style.foreground),
+            color(style.background),
+            if attr.is_empty() {
+                Value::nothing(head)
+            } else {
+                Value::string(attr, head)
+            },
+        ],
+        head,
+    )
+}
+
+/// The name `ansi` has for a color, or its hex code
+fn color_name(color: Color) -> String {
+    const NAMED: [(Color, &str); 16] = [
+        (Color::Black, "black"),
+        (Color::Red, "red"),
+        (Color::Green, "green"),
+        (Color::Yellow, "yellow"),
+        (Color::Blue, "blue"),
+        (Color::Purple, "purple"),
+        (Color::Cyan, "cyan"),
+        (Color::White, "white"),
+        (Color::LightGray, "light_gray"),
+        (Color::DarkGray, "dark_gray"),
+        (Color::LightRed, "light_red"),
+        (Color::LightGreen, "light_green"),
+        (Color::LightYellow, "light_yellow"),
+        (Color::LightBlue, "light_blue"),
+        (Color::LightPurple, "light_purple"),
+        (Color::LightCyan, "light_cyan"),
+    ];
+
+    match color {
+        Color::Fixed(n) if n < 16 => {
+            // Match the code that nu_ansi_term writes for each named color
+            let code = if n < 8 { 30 + n } else { 90 + n - 8 };
+            let prefix = format!("\x1b[{code}m");
+            NAMED
+                .iter()
+                .find(|(named, _)| Style::new().fg(*named).prefix().to_string() == prefix)
+                .map_or_else(|| format!("color {n}"), |(_, name)| name.to_string())
+        }
+        Color::Fixed(n) if n < 232 => {
+            // The 6x6x6 color cube of xterm
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let n = n - 16;
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(n / 36),
+                level(n / 6 % 6),
+                level(n % 6)
+            )
+        }
+        Color::Fixed(n) => {
+            let gray = 8 + (n - 232) * 10;
+            format!("#{gray:02x}{gray:02x}{gray:02x}")
+        }
+        Color::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+        named => NAMED
+            .iter()
+            .find(|(color, _)| *color == named)
+            .map_or_else(|| "default".into(), |(_, name)| name.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn tokenizes_sequences() {
+        assert_eq!(
+            tokenize("a\x1b[1;31mb\x1b]8;;http://x\x1b\\c\x1b]0;title\x07\x1b(Bd"),
+            vec![
+                Token::Text("a"),
+                Token::Csi("1;31", 'm'),
+                Token::Text("b"),
+                Token::Osc("8;;http://x"),
+                Token::Text("c"),
+                Token::Osc("0;title"),
+                Token::Escape("\x1b(B"),
+                Token::Text("d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_extended_colors() {
+        let style = apply_sgr(Style::new(), "38;5;208;48:2::0:135:255;1");
+        assert_eq!(style.foreground, Some(Color::Fixed(208)));
+        assert_eq!(style.background, Some(Color::Rgb(0, 135, 255)));
+        assert!(style.is_bold);
+        assert_eq!(color_name(Color::Fixed(208)), "#ff8700");
+        assert_eq!(color_name(Color::Fixed(1)), "red");
+    }
+}
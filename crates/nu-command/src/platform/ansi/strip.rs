@@ -1,3 +1,4 @@
+use super::parse::{tokenize, Token};
 use crate::input_handler::{operate, CellPathOnlyArgs};
 use nu_engine::CallExt;
 use nu_protocol::{
@@ -28,6 +29,11 @@ impl Command for SubCommand {
         "Strip ANSI escape sequences from a string"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Besides colors and cursor movements, this removes operating system commands like window
+titles and hyperlinks. The text of a hyperlink is kept."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -41,19 +47,24 @@ impl Command for SubCommand {
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            description: "Strip ANSI escape sequences from a string",
-            example: r#"$'(ansi green)(ansi cursor_on)hello' | ansi strip"#,
-            result: Some(Value::test_string("hello")),
-        }]
+        vec![
+            Example {
+                description: "Strip ANSI escape sequences from a string",
+                example: r#"$'(ansi green)(ansi cursor_on)hello' | ansi strip"#,
+                result: Some(Value::test_string("hello")),
+            },
+            Example {
+                description: "Keep only the text of a hyperlink",
+                example: r#""\e]8;;https://www.nushell.sh\e\\Nushell\e]8;;\e\\" | ansi strip"#,
+                result: Some(Value::test_string("Nushell")),
+            },
+        ]
     }
 }
 
 fn action(input: &Value, _args: &CellPathOnlyArgs, command_span: Span) -> Value {
     match input {
-        Value::String { val, span } => {
-            Value::string(nu_utils::strip_ansi_likely(val).to_string(), *span)
-        }
+        Value::String { val, span } => Value::string(strip(val), *span),
         other => {
             let got = format!("value is {}, not string", other.get_type());
 
@@ -64,6 +75,16 @@ fn action(input: &Value, _args: &CellPathOnlyArgs, command_span: Span) -> Value
     }
 }
 
+fn strip(s: &str) -> String {
+    tokenize(s)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text),
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::{action, SubCommand};
@@ -85,4 +106,15 @@ mod tests {
         let actual = action(&input_string, &vec![].into(), Span::test_data());
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_stripping_osc() {
+        let input_string = Value::test_string(
+            "\u{1b}]0;title\u{7}\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\ text",
+        );
+        let expected = Value::test_string("link text");
+
+        let actual = action(&input_string, &vec![].into(), Span::test_data());
+        assert_eq!(actual, expected);
+    }
 }
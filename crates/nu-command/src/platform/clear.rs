@@ -1,5 +1,5 @@
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
 };
@@ -17,6 +17,10 @@ impl Command for Clear {
         "Clear the terminal."
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
     fn signature(&self) -> Signature {
         Signature::build("clear")
             .category(Category::Platform)
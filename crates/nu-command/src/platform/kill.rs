@@ -1,5 +1,5 @@
 use nu_engine::CallExt;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{ast::Call, span};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
@@ -19,6 +19,10 @@ impl Command for Kill {
         "Kill a process using the process id."
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
     fn signature(&self) -> Signature {
         let signature = Signature::build("kill")
             .input_output_types(vec![(Type::Nothing, Type::Any)])
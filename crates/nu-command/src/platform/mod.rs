@@ -5,6 +5,7 @@ mod du;
 mod input;
 mod kill;
 mod reedline_commands;
+mod signal;
 mod sleep;
 mod term_size;
 
@@ -15,5 +16,6 @@ pub use du::Du;
 pub use input::Input;
 pub use kill::Kill;
 pub use reedline_commands::{Keybindings, KeybindingsDefault, KeybindingsList, KeybindingsListen};
+pub use signal::{Signal, SignalTrap};
 pub use sleep::Sleep;
 pub use term_size::TermSize;
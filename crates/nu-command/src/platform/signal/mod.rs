@@ -0,0 +1,5 @@
+mod signal_;
+mod trap;
+
+pub use signal_::Signal;
+pub use trap::SignalTrap;
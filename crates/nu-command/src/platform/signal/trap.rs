@@ -0,0 +1,228 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{self, Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
+};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct SignalTrap;
+
+impl Command for SignalTrap {
+    fn name(&self) -> &str {
+        "signal trap"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("signal trap")
+            .input_output_types(vec![(Type::Nothing, Type::Nothing)])
+            .allow_variants_without_examples(true)
+            .required(
+                "signal",
+                SyntaxShape::String,
+                "the signal to trap: INT, or TERM (unsupported on Windows)",
+            )
+            .optional(
+                "handler",
+                SyntaxShape::Closure(Some(vec![SyntaxShape::String])),
+                "the closure to run once the signal is received",
+            )
+            .switch("remove", "remove the handler for the signal", Some('r'))
+            .category(Category::Platform)
+    }
+
+    fn usage(&self) -> &str {
+        "Run a closure when the shell receives a signal."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The interrupted pipeline is stopped as usual, and the handler runs right after it, with the
+signal name as its only argument. Registering a handler for a signal replaces the previous one."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["sigint", "sigterm", "ctrl-c", "cleanup", "on-signal"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let signal: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let handler: Option<Value> = call.opt(engine_state, stack, 1)?;
+        let remove = call.has_flag("remove");
+
+        let name = normalize_signal_name(&signal)?;
+
+        let mut traps = match engine_state.signal_traps.lock() {
+            Ok(traps) => traps,
+            Err(_) => {
+                return Err(ShellError::NushellFailed(
+                    "signal trap registry is poisoned".into(),
+                ))
+            }
+        };
+
+        if remove {
+            traps.remove(&name);
+            if name == "TERM" {
+                untrap_term();
+            }
+            return Ok(PipelineData::empty());
+        }
+
+        let handler = match handler {
+            Some(handler @ (Value::Closure { .. } | Value::Block { .. })) => handler,
+            Some(other) => {
+                return Err(ShellError::TypeMismatch(
+                    format!("expected closure, found {}", other.get_type()),
+                    other.span()?,
+                ))
+            }
+            None => return Err(ShellError::MissingParameter("handler".into(), call.head)),
+        };
+
+        let received = match name.as_str() {
+            "TERM" => trap_term(engine_state, signal.span)?,
+            _ => engine_state
+                .ctrlc
+                .clone()
+                .unwrap_or_else(|| Arc::new(AtomicBool::new(false))),
+        };
+
+        traps.insert(name, engine::SignalTrap { handler, received });
+
+        Ok(PipelineData::empty())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description:
+                    "Remove a temporary directory if the script is interrupted with ctrl-c",
+                example: "signal trap INT {|| rm -r $tmp_dir }",
+                result: None,
+            },
+            Example {
+                description: "Log which signal stopped the script",
+                example: "signal trap TERM {|sig| $'stopped by ($sig)' | save -a log.txt }",
+                result: None,
+            },
+            Example {
+                description: "Stop trapping ctrl-c",
+                example: "signal trap INT --remove",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn normalize_signal_name(signal: &Spanned<String>) -> Result<String, ShellError> {
+    let upper = signal.item.to_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+
+    match name {
+        "INT" | "TERM" => Ok(name.to_string()),
+        _ => Err(ShellError::GenericError(
+            format!("Unsupported signal '{}'", signal.item),
+            "expected INT or TERM".into(),
+            Some(signal.span),
+            None,
+            Vec::new(),
+        )),
+    }
+}
+
+#[cfg(unix)]
+mod term {
+    use once_cell::sync::OnceCell;
+    use signal_hook::consts::SIGTERM;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    /// Process-wide SIGTERM state: whether a handler is installed, and whether SIGTERM arrived.
+    pub(super) struct TermState {
+        pub untrapped: Arc<AtomicBool>,
+        pub received: Arc<AtomicBool>,
+    }
+
+    pub(super) static TERM_STATE: OnceCell<TermState> = OnceCell::new();
+
+    pub(super) fn register(ctrlc: Option<Arc<AtomicBool>>) -> std::io::Result<TermState> {
+        let untrapped = Arc::new(AtomicBool::new(true));
+        let received = Arc::new(AtomicBool::new(false));
+
+        // Keep the default behavior (terminating nu) whenever no handler is registered
+        signal_hook::flag::register_conditional_default(SIGTERM, untrapped.clone())?;
+        signal_hook::flag::register(SIGTERM, received.clone())?;
+        if let Some(ctrlc) = ctrlc {
+            // Stop the running pipeline the same way ctrl-c does
+            signal_hook::flag::register(SIGTERM, ctrlc)?;
+        }
+
+        Ok(TermState {
+            untrapped,
+            received,
+        })
+    }
+}
+
+#[cfg(unix)]
+fn trap_term(engine_state: &EngineState, span: Span) -> Result<Arc<AtomicBool>, ShellError> {
+    let state = term::TERM_STATE.get_or_try_init(|| {
+        term::register(engine_state.ctrlc.clone()).map_err(|e| {
+            ShellError::GenericError(
+                "Could not install SIGTERM handler".into(),
+                e.to_string(),
+                Some(span),
+                None,
+                Vec::new(),
+            )
+        })
+    })?;
+
+    state
+        .untrapped
+        .store(false, std::sync::atomic::Ordering::SeqCst);
+    Ok(state.received.clone())
+}
+
+#[cfg(not(unix))]
+fn trap_term(_engine_state: &EngineState, span: Span) -> Result<Arc<AtomicBool>, ShellError> {
+    Err(ShellError::GenericError(
+        "Unsupported signal 'TERM'".into(),
+        "trapping TERM is not supported on this platform".into(),
+        Some(span),
+        None,
+        Vec::new(),
+    ))
+}
+
+#[cfg(unix)]
+fn untrap_term() {
+    if let Some(state) = term::TERM_STATE.get() {
+        state
+            .untrapped
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(not(unix))]
+fn untrap_term() {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SignalTrap {})
+    }
+}
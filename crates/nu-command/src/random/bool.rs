@@ -4,7 +4,9 @@ use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
 };
-use rand::prelude::{thread_rng, Rng};
+use rand::Rng;
+
+use super::rng::{get_rng, SEED_USAGE};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -24,6 +26,7 @@ impl Command for SubCommand {
                 "Adjusts the probability of a \"true\" outcome",
                 Some('b'),
             )
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
             .category(Category::Random)
     }
 
@@ -81,7 +84,7 @@ fn bool(
         }
     }
 
-    let mut rng = thread_rng();
+    let mut rng = get_rng(engine_state, stack, call)?;
     let bool_result: bool = rng.gen_bool(probability);
 
     Ok(PipelineData::Value(
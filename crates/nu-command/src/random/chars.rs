@@ -4,10 +4,9 @@ use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
 };
-use rand::{
-    distributions::{Alphanumeric, Distribution},
-    thread_rng,
-};
+use rand::distributions::{Alphanumeric, Distribution};
+
+use super::rng::{get_rng, SEED_USAGE};
 
 const DEFAULT_CHARS_LENGTH: usize = 25;
 
@@ -24,6 +23,7 @@ impl Command for SubCommand {
             .input_output_types(vec![(Type::Nothing, Type::String)])
             .allow_variants_without_examples(true)
             .named("length", SyntaxShape::Int, "Number of chars", Some('l'))
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
             .category(Category::Random)
     }
 
@@ -70,7 +70,7 @@ fn chars(
     let length: Option<usize> = call.get_flag(engine_state, stack, "length")?;
 
     let chars_length = length.unwrap_or(DEFAULT_CHARS_LENGTH);
-    let mut rng = thread_rng();
+    let mut rng = get_rng(engine_state, stack, call)?;
 
     let random_string = Alphanumeric
         .sample_iter(&mut rng)
@@ -4,9 +4,11 @@ use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, PipelineData, Range, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
-use rand::prelude::{thread_rng, Rng};
+use rand::Rng;
 use std::cmp::Ordering;
 
+use super::rng::{get_rng, SEED_USAGE};
+
 #[derive(Clone)]
 pub struct SubCommand;
 
@@ -20,6 +22,7 @@ impl Command for SubCommand {
             .input_output_types(vec![(Type::Nothing, Type::Float)])
             .allow_variants_without_examples(true)
             .optional("range", SyntaxShape::Range, "Range of values")
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
             .category(Category::Random)
     }
 
@@ -101,8 +104,8 @@ fn decimal(
             None,
         )),
         _ => {
-            let mut thread_rng = thread_rng();
-            let result: f64 = thread_rng.gen_range(min..max);
+            let mut rng = get_rng(engine_state, stack, call)?;
+            let result: f64 = rng.gen_range(min..max);
 
             Ok(PipelineData::Value(
                 Value::Float {
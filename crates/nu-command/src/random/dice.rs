@@ -4,7 +4,9 @@ use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, ListStream, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
 };
-use rand::prelude::{thread_rng, Rng};
+use rand::Rng;
+
+use super::rng::{get_rng, SEED_USAGE};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -30,6 +32,7 @@ impl Command for SubCommand {
                 "The amount of sides a die has",
                 Some('s'),
             )
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
             .category(Category::Random)
     }
 
@@ -77,12 +80,11 @@ fn dice(
     let dice: usize = call.get_flag(engine_state, stack, "dice")?.unwrap_or(1);
     let sides: usize = call.get_flag(engine_state, stack, "sides")?.unwrap_or(6);
 
-    let iter = (0..dice).map(move |_| {
-        let mut thread_rng = thread_rng();
-        Value::Int {
-            val: thread_rng.gen_range(1..sides + 1) as i64,
-            span,
-        }
+    let mut rng = get_rng(engine_state, stack, call)?;
+
+    let iter = (0..dice).map(move |_| Value::Int {
+        val: rng.gen_range(1..sides + 1) as i64,
+        span,
     });
 
     Ok(PipelineData::ListStream(
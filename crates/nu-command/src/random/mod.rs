@@ -3,7 +3,11 @@ mod chars;
 mod decimal;
 mod dice;
 mod integer;
+mod normal;
 mod random_;
+mod rng;
+mod sample;
+mod shuffle;
 mod uuid;
 
 pub use self::bool::SubCommand as RandomBool;
@@ -11,5 +15,8 @@ pub use self::chars::SubCommand as RandomChars;
 pub use self::decimal::SubCommand as RandomDecimal;
 pub use self::dice::SubCommand as RandomDice;
 pub use self::integer::SubCommand as RandomInteger;
+pub use self::normal::SubCommand as RandomNormal;
+pub use self::sample::SubCommand as RandomSample;
+pub use self::shuffle::SubCommand as RandomShuffle;
 pub use self::uuid::SubCommand as RandomUuid;
 pub use random_::RandomCommand as Random;
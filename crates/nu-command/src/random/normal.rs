@@ -0,0 +1,139 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
+    Signature, Spanned, SyntaxShape, Type, Value,
+};
+use rand::Rng;
+use std::f64::consts::PI;
+
+use super::rng::{get_rng, SEED_USAGE};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "random normal"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("random normal")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Float),
+                (Type::Nothing, Type::List(Box::new(Type::Float))),
+            ])
+            .allow_variants_without_examples(true)
+            .named(
+                "mean",
+                SyntaxShape::Number,
+                "The mean of the distribution (default 0)",
+                Some('m'),
+            )
+            .named(
+                "stddev",
+                SyntaxShape::Number,
+                "The standard deviation of the distribution (default 1)",
+                Some('s'),
+            )
+            .named(
+                "count",
+                SyntaxShape::Int,
+                "Generate a list of this many values",
+                Some('c'),
+            )
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
+            .category(Category::Random)
+    }
+
+    fn usage(&self) -> &str {
+        "Generate a random decimal from a normal (Gaussian) distribution"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["generate", "gaussian", "bell", "distribution", "float"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let mean: f64 = call.get_flag(engine_state, stack, "mean")?.unwrap_or(0.0);
+        let stddev: Option<Spanned<f64>> = call.get_flag(engine_state, stack, "stddev")?;
+        let count: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "count")?;
+
+        let stddev = match stddev {
+            Some(stddev) if stddev.item < 0.0 => {
+                return Err(ShellError::NeedsPositiveValue(stddev.span))
+            }
+            Some(stddev) => stddev.item,
+            None => 1.0,
+        };
+        let mut rng = get_rng(engine_state, stack, call)?;
+
+        match count {
+            Some(count) if count.item < 0 => Err(ShellError::NeedsPositiveValue(count.span)),
+            Some(count) => Ok((0..count.item)
+                .map(move |_| Value::float(mean + stddev * standard_normal(&mut rng), span))
+                .into_pipeline_data(engine_state.ctrlc.clone())),
+            None => Ok(
+                Value::float(mean + stddev * standard_normal(&mut rng), span).into_pipeline_data(),
+            ),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Generate a value from the standard normal distribution",
+                example: "random normal",
+                result: None,
+            },
+            Example {
+                description: "Simulate 1000 response times around 200ms, the same on every run",
+                example: "random normal --mean 200 --stddev 15 --count 1000 --seed 7",
+                result: None,
+            },
+        ]
+    }
+}
+
+/// A value from the normal distribution with mean 0 and standard deviation 1, by the Box-Muller
+/// transform
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    // 1 - [0, 1) is never 0, whose logarithm is infinite
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn standard_normal_has_mean_0_and_stddev_1() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let values: Vec<f64> = (0..100_000).map(|_| standard_normal(&mut rng)).collect();
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+
+        assert!(mean.abs() < 0.02);
+        assert!((variance.sqrt() - 1.0).abs() < 0.02);
+    }
+}
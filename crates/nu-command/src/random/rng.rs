@@ -0,0 +1,23 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::ShellError;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+pub const SEED_USAGE: &str =
+    "Seed the generator, to get the same values on every run with this version of Nushell";
+
+/// The generator of the random commands: seeded with `--seed` when given, so simulations can be
+/// reproduced, and from the entropy of the operating system otherwise
+pub fn get_rng(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<StdRng, ShellError> {
+    let seed: Option<i64> = call.get_flag(engine_state, stack, "seed")?;
+    Ok(match seed {
+        Some(seed) => StdRng::seed_from_u64(seed as u64),
+        None => StdRng::from_entropy(),
+    })
+}
@@ -0,0 +1,208 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
+    Signature, Spanned, SyntaxShape, Type, Value,
+};
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+use super::rng::{get_rng, SEED_USAGE};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "random sample"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("random sample")
+            .input_output_types(vec![
+                (Type::List(Box::new(Type::Any)), Type::Any),
+                (
+                    Type::List(Box::new(Type::Any)),
+                    Type::List(Box::new(Type::Any)),
+                ),
+            ])
+            .optional(
+                "count",
+                SyntaxShape::Int,
+                "the number of items to pick, which are returned as a list",
+            )
+            .named(
+                "weights",
+                SyntaxShape::List(Box::new(SyntaxShape::Number)),
+                "how likely every item is to be picked, relative to the others",
+                Some('w'),
+            )
+            .switch(
+                "replace",
+                "put every item back after picking it, so it can be picked again",
+                Some('r'),
+            )
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
+            .allow_variants_without_examples(true)
+            .category(Category::Random)
+    }
+
+    fn usage(&self) -> &str {
+        "Pick random items from a list"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Without a count, a single item is picked and returned as it is. Unless --replace is given,
+an item is picked at most once, so the count can't be more than the length of the list."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["choose", "pick", "select", "weighted", "draw"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let count: Option<Spanned<i64>> = call.opt(engine_state, stack, 0)?;
+        let weights: Option<Value> = call.get_flag(engine_state, stack, "weights")?;
+        let replace = call.has_flag("replace");
+        let mut rng = get_rng(engine_state, stack, call)?;
+
+        let items: Vec<Value> = input.into_iter_strict(head)?.collect();
+        let weights = match weights {
+            Some(weights) => Some(to_weights(&weights, items.len())?),
+            None => None,
+        };
+
+        let n = match &count {
+            Some(count) if count.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(count.span))
+            }
+            Some(count) => count.item as usize,
+            None => 1,
+        };
+        let available = match &weights {
+            Some(weights) => weights.iter().filter(|w| **w > 0.0).count(),
+            None => items.len(),
+        };
+        if available == 0 || (!replace && n > available) {
+            return Err(ShellError::IncompatibleParametersSingle(
+                format!("can't pick {n} items out of {available} without --replace"),
+                count.map(|count| count.span).unwrap_or(head),
+            ));
+        }
+
+        let picked: Vec<Value> = match weights {
+            Some(mut weights) => (0..n)
+                .map(|_| {
+                    let index = pick_weighted(&mut rng, &weights);
+                    if !replace {
+                        weights[index] = 0.0;
+                    }
+                    items[index].clone()
+                })
+                .collect(),
+            None if replace => (0..n)
+                .map(|_| items[rng.gen_range(0..items.len())].clone())
+                .collect(),
+            None => items.choose_multiple(&mut rng, n).cloned().collect(),
+        };
+
+        if count.is_some() {
+            Ok(picked.into_pipeline_data(engine_state.ctrlc.clone()))
+        } else {
+            Ok(picked
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| Value::nothing(head))
+                .into_pipeline_data())
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Pick a random item",
+                example: "[rock paper scissors] | random sample",
+                result: None,
+            },
+            Example {
+                description: "Pick 3 different numbers from 1 to 10",
+                example: "1..10 | random sample 3",
+                result: None,
+            },
+            Example {
+                description: "Roll a loaded die 10 times, where 6 comes up half of the time",
+                example: "1..6 | random sample 10 --replace --weights [1 1 1 1 1 5]",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn to_weights(weights: &Value, len: usize) -> Result<Vec<f64>, ShellError> {
+    let span = weights.span()?;
+    let weights = weights
+        .as_list()?
+        .iter()
+        .map(|weight| match weight.as_float() {
+            Ok(w) if w >= 0.0 && w.is_finite() => Ok(w),
+            Ok(_) => Err(ShellError::NeedsPositiveValue(weight.span()?)),
+            Err(e) => Err(e),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if weights.len() != len {
+        return Err(ShellError::IncompatibleParametersSingle(
+            format!("got {} weights for {len} items", weights.len()),
+            span,
+        ));
+    }
+    Ok(weights)
+}
+
+/// The index of an item picked with probability proportional to its weight. At least one weight
+/// must be positive.
+fn pick_weighted(rng: &mut impl Rng, weights: &[f64]) -> usize {
+    let total: f64 = weights.iter().sum();
+    let mut target = rng.gen_range(0.0..total);
+    for (index, weight) in weights.iter().enumerate() {
+        if target < *weight {
+            return index;
+        }
+        target -= weight;
+    }
+    // Rounding errors can take the target past the last weight
+    weights
+        .iter()
+        .rposition(|weight| *weight > 0.0)
+        .unwrap_or(weights.len() - 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn zero_weights_are_never_picked() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..1000 {
+            assert_ne!(pick_weighted(&mut rng, &[1.0, 0.0, 2.0]), 1);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature,
+    SyntaxShape, Type,
+};
+use rand::prelude::SliceRandom;
+
+use super::rng::{get_rng, SEED_USAGE};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "random shuffle"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("random shuffle")
+            .input_output_types(vec![(
+                Type::List(Box::new(Type::Any)),
+                Type::List(Box::new(Type::Any)),
+            )])
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
+            .allow_variants_without_examples(true)
+            .category(Category::Random)
+    }
+
+    fn usage(&self) -> &str {
+        "Shuffle the items of a list randomly"
+    }
+
+    fn extra_usage(&self) -> &str {
+        "Like `shuffle`, with a seed to get the same order every time."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["permutation", "order", "mix"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let mut rng = get_rng(engine_state, stack, call)?;
+        let metadata = input.metadata();
+        let mut v: Vec<_> = input.into_iter_strict(call.head)?.collect();
+        v.shuffle(&mut rng);
+        Ok(v.into_iter()
+            .into_pipeline_data(engine_state.ctrlc.clone())
+            .set_metadata(metadata))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Shuffle a deck of cards",
+                example: "1..52 | random shuffle",
+                result: None,
+            },
+            Example {
+                description: "Shuffle a list the same way every time",
+                example: "[a b c d] | random shuffle --seed 42",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
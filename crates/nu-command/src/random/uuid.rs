@@ -1,7 +1,12 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Type, Value};
-use uuid::Uuid;
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
+};
+use rand::Rng;
+use uuid::Builder;
+
+use super::rng::{get_rng, SEED_USAGE};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -16,6 +21,7 @@ impl Command for SubCommand {
             .category(Category::Random)
             .input_output_types(vec![(Type::Nothing, Type::String)])
             .allow_variants_without_examples(true)
+            .named("seed", SyntaxShape::Int, SEED_USAGE, None)
     }
 
     fn usage(&self) -> &str {
@@ -28,12 +34,12 @@ impl Command for SubCommand {
 
     fn run(
         &self,
-        _engine_state: &EngineState,
-        _stack: &mut Stack,
+        engine_state: &EngineState,
+        stack: &mut Stack,
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        uuid(call)
+        uuid(engine_state, stack, call)
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -45,9 +51,17 @@ impl Command for SubCommand {
     }
 }
 
-fn uuid(call: &Call) -> Result<PipelineData, ShellError> {
+fn uuid(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+) -> Result<PipelineData, ShellError> {
     let span = call.head;
-    let uuid_4 = Uuid::new_v4().hyphenated().to_string();
+    let mut rng = get_rng(engine_state, stack, call)?;
+    let uuid_4 = Builder::from_random_bytes(rng.gen())
+        .into_uuid()
+        .hyphenated()
+        .to_string();
 
     Ok(PipelineData::Value(
         Value::String { val: uuid_4, span },
@@ -1,3 +1,4 @@
+use crate::strings::fold_latin;
 use alphanumeric_sort::compare_str;
 use nu_engine::column::nonexistent_column;
 use nu_protocol::{ShellError, Span, Value};
@@ -54,12 +55,39 @@ pub fn sort_value_in_place(
     Ok(())
 }
 
+/// A column to sort by, in its own direction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortColumn {
+    pub name: String,
+    pub descending: bool,
+}
+
 pub fn sort(
     vec: &mut [Value],
     sort_columns: Vec<String>,
     span: Span,
     insensitive: bool,
     natural: bool,
+) -> Result<(), ShellError> {
+    let sort_columns: Vec<SortColumn> = sort_columns
+        .into_iter()
+        .map(|name| SortColumn {
+            name,
+            descending: false,
+        })
+        .collect();
+    sort_by_columns(vec, &sort_columns, span, insensitive, natural, false)
+}
+
+/// Sort by several columns, each in its own direction. With `collate`, strings are compared
+/// the way a dictionary orders words (see `collate_str`).
+pub fn sort_by_columns(
+    vec: &mut [Value],
+    sort_columns: &[SortColumn],
+    span: Span,
+    insensitive: bool,
+    natural: bool,
+    collate: bool,
 ) -> Result<(), ShellError> {
     if vec.is_empty() {
         return Err(ShellError::GenericError(
@@ -88,7 +116,8 @@ pub fn sort(
                 ));
             }
 
-            if let Some(nonexistent) = nonexistent_column(sort_columns.clone(), cols.to_vec()) {
+            let names: Vec<String> = sort_columns.iter().map(|col| col.name.clone()).collect();
+            if let Some(nonexistent) = nonexistent_column(names, cols.to_vec()) {
                 return Err(ShellError::CantFindColumn(nonexistent, span, *val_span));
             }
 
@@ -98,9 +127,9 @@ pub fn sort(
             // strings.
             let mut vals = vec![];
             for item in vec.iter() {
-                for col in &sort_columns {
+                for col in sort_columns {
                     let val = item
-                        .get_data_by_key(col)
+                        .get_data_by_key(&col.name)
                         .unwrap_or_else(|| Value::nothing(Span::unknown()));
                     vals.push(val);
                 }
@@ -120,16 +149,21 @@ pub fn sort(
                 compare(
                     a,
                     b,
-                    &sort_columns,
+                    sort_columns,
                     span,
                     should_sort_case_insensitively,
                     should_sort_case_naturally,
+                    collate,
                 )
             });
         }
         _ => {
             vec.sort_by(|a, b| {
-                if insensitive {
+                if let (true, Value::String { val: left, .. }, Value::String { val: right, .. }) =
+                    (collate, a, b)
+                {
+                    collate_str(left, right, natural)
+                } else if insensitive {
                     let lowercase_left = match a {
                         Value::String { val, span } => Value::String {
                             val: val.to_ascii_lowercase(),
@@ -173,27 +207,37 @@ pub fn sort(
 pub fn compare(
     left: &Value,
     right: &Value,
-    columns: &[String],
+    columns: &[SortColumn],
     span: Span,
     insensitive: bool,
     natural: bool,
+    collate: bool,
 ) -> Ordering {
     for column in columns {
-        let left_value = left.get_data_by_key(column);
+        let left_value = left.get_data_by_key(&column.name);
 
         let left_res = match left_value {
             Some(left_res) => left_res,
             None => Value::Nothing { span },
         };
 
-        let right_value = right.get_data_by_key(column);
+        let right_value = right.get_data_by_key(&column.name);
 
         let right_res = match right_value {
             Some(right_res) => right_res,
             None => Value::Nothing { span },
         };
 
-        let result = if insensitive {
+        let collated = match (&left_res, &right_res) {
+            (Value::String { val: left, .. }, Value::String { val: right, .. }) if collate => {
+                Some(collate_str(left, right, natural))
+            }
+            _ => None,
+        };
+
+        let result = if let Some(collated) = collated {
+            collated
+        } else if insensitive {
             let lowercase_left = match left_res {
                 Value::String { val, span } => Value::String {
                     val: val.to_ascii_lowercase(),
@@ -228,13 +272,51 @@ pub fn compare(
             left_res.partial_cmp(&right_res).unwrap_or(Ordering::Equal)
         };
         if result != Ordering::Equal {
-            return result;
+            return if column.descending {
+                result.reverse()
+            } else {
+                result
+            };
         }
     }
 
     Ordering::Equal
 }
 
+/// Compare strings the way a dictionary orders words: accented letters sort with their plain
+/// letters and case is ignored, and both only break ties between otherwise equal words.
+pub fn collate_str(left: &str, right: &str, natural: bool) -> Ordering {
+    let primary = |s: &str| fold_latin(s).to_lowercase();
+    let (left_primary, right_primary) = (primary(left), primary(right));
+    let ordering = if natural {
+        compare_str(&left_primary, &right_primary)
+    } else {
+        left_primary.cmp(&right_primary)
+    };
+
+    ordering
+        .then_with(|| left.to_lowercase().cmp(&right.to_lowercase()))
+        .then_with(|| left.cmp(right))
+}
+
+#[test]
+fn test_collate_str() {
+    let mut words = vec![
+        "zebra", "Émile", "apple", "eagle", "Apple", "file10", "file9",
+    ];
+    words.sort_by(|a, b| collate_str(a, b, false));
+    assert_eq!(
+        words,
+        vec!["Apple", "apple", "eagle", "Émile", "file10", "file9", "zebra"]
+    );
+
+    words.sort_by(|a, b| collate_str(a, b, true));
+    assert_eq!(
+        words,
+        vec!["Apple", "apple", "eagle", "Émile", "file9", "file10", "zebra"]
+    );
+}
+
 #[test]
 fn test_sort_value() {
     let val = Value::List {
@@ -0,0 +1,5 @@
+mod command;
+mod text;
+
+pub use command::DiffCommand as Diff;
+pub use text::SubCommand as DiffText;
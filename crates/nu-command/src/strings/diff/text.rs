@@ -0,0 +1,500 @@
+use nu_ansi_term::Color;
+use nu_engine::{current_dir, CallExt};
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "diff text"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("diff text")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Table(vec![])),
+                (Type::String, Type::Table(vec![])),
+                (Type::Nothing, Type::String),
+                (Type::String, Type::String),
+            ])
+            .optional("old", SyntaxShape::Filepath, "the file with the old text")
+            .optional("new", SyntaxShape::Filepath, "the file with the new text")
+            .named(
+                "text",
+                SyntaxShape::String,
+                "the new text, instead of a file",
+                Some('t'),
+            )
+            .named(
+                "context",
+                SyntaxShape::Int,
+                "the number of unchanged lines to show around changes (default 3)",
+                Some('c'),
+            )
+            .switch(
+                "unified",
+                "render the changes as a unified diff instead of a table",
+                Some('u'),
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Compare two texts line by line."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The texts come from the piped string, the files and --text, in that order, and the first
+one is the old text. Each row of the output is a group of nearby changes, with the position and
+length of the group in both texts like in the header of a unified diff, and its lines. The type of
+a line is added, removed or context, and only the lines that are in a text have a number in it."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["compare", "changes", "patch", "unified", "hunk"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let context: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "context")?;
+        let context = match context {
+            Some(context) if context.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(context.span))
+            }
+            Some(context) => context.item as usize,
+            None => 3,
+        };
+
+        let mut texts: Vec<(String, String)> = vec![];
+        match input {
+            PipelineData::Empty | PipelineData::Value(Value::Nothing { .. }, ..) => {}
+            input => {
+                let config = engine_state.get_config();
+                texts.push(("input".into(), input.collect_string("", config)?));
+            }
+        }
+        let cwd = current_dir(engine_state, stack)?;
+        for index in 0..2 {
+            let path: Option<Spanned<String>> = call.opt(engine_state, stack, index)?;
+            if let Some(path) = path {
+                let contents = std::fs::read_to_string(nu_path::expand_path_with(&path.item, &cwd))
+                    .map_err(|e| {
+                        ShellError::GenericError(
+                            "Error reading the file".into(),
+                            e.to_string(),
+                            Some(path.span),
+                            None,
+                            vec![],
+                        )
+                    })?;
+                texts.push((path.item, contents));
+            }
+        }
+        let text: Option<String> = call.get_flag(engine_state, stack, "text")?;
+        if let Some(text) = text {
+            texts.push(("text".into(), text));
+        }
+
+        let (old, new) = match <[(String, String); 2]>::try_from(texts) {
+            Ok([old, new]) => (old, new),
+            Err(texts) => {
+                return Err(ShellError::GenericError(
+                    "Two texts are needed to compare".into(),
+                    format!("found {}", texts.len()),
+                    Some(head),
+                    Some(
+                        "pipe in a string or give a file, then give another file or --text".into(),
+                    ),
+                    vec![],
+                ))
+            }
+        };
+
+        let old_lines: Vec<&str> = old.1.lines().collect();
+        let new_lines: Vec<&str> = new.1.lines().collect();
+        let hunks = hunks(&diff(&old_lines, &new_lines), context);
+
+        if call.has_flag("unified") {
+            let color = engine_state.get_config().use_ansi_coloring;
+            let unified = unified(&old.0, &new.0, &hunks, &old_lines, &new_lines, color);
+            Ok(Value::string(unified, head).into_pipeline_data())
+        } else {
+            let rows = hunks
+                .iter()
+                .map(|hunk| hunk.to_value(&old_lines, &new_lines, head))
+                .collect();
+            Ok(Value::List {
+                vals: rows,
+                span: head,
+            }
+            .into_pipeline_data())
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let line = |kind: &str, old: Option<i64>, new: Option<i64>, content: &str| Value::Record {
+            cols: vec![
+                "type".into(),
+                "old_line".into(),
+                "new_line".into(),
+                "content".into(),
+            ],
+            vals: vec![
+                Value::test_string(kind),
+                old.map_or(Value::test_nothing(), Value::test_int),
+                new.map_or(Value::test_nothing(), Value::test_int),
+                Value::test_string(content),
+            ],
+            span: Span::test_data(),
+        };
+
+        vec![
+            Example {
+                description: "Compare a string with another one",
+                example: "\"a\\nb\\nc\" | diff text --text \"a\\nB\\nc\" --context 0",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec![
+                            "old_start".into(),
+                            "old_lines".into(),
+                            "new_start".into(),
+                            "new_lines".into(),
+                            "lines".into(),
+                        ],
+                        vals: vec![
+                            Value::test_int(2),
+                            Value::test_int(1),
+                            Value::test_int(2),
+                            Value::test_int(1),
+                            Value::List {
+                                vals: vec![
+                                    line("removed", Some(2), None, "b"),
+                                    line("added", None, Some(2), "B"),
+                                ],
+                                span: Span::test_data(),
+                            },
+                        ],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Show the changes between two files as a unified diff",
+                example: "diff text old.toml new.toml --unified",
+                result: None,
+            },
+            Example {
+                description: "Compare the output of a command with a file",
+                example: "ls | to text | diff text listing.txt",
+                result: None,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// The shortest edit script from `old` to `new`, using Myers' algorithm
+fn diff(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    // Lines in common at both ends are left out of the search
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+
+    // v[k] is the furthest x reached on diagonal k, and trace keeps the part of v that each
+    // step of d could read, to walk back through the edits
+    let index = |k: isize| (k + max + 1) as usize;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    let mut trace: Vec<Vec<isize>> = vec![];
+    'search: for d in 0..=max {
+        trace.push(v[index(-d - 1)..=index(d + 1)].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push(Edit::Insert(prev_y as usize));
+            } else {
+                edits.push(Edit::Delete(prev_x as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+
+    let shift = |edit: Edit| match edit {
+        Edit::Equal(x, y) => Edit::Equal(x + prefix, y + prefix),
+        Edit::Delete(x) => Edit::Delete(x + prefix),
+        Edit::Insert(y) => Edit::Insert(y + prefix),
+    };
+    (0..prefix)
+        .map(|i| Edit::Equal(i, i))
+        .chain(edits.into_iter().map(shift))
+        .chain((0..suffix).map(|i| Edit::Equal(old.len() - suffix + i, new.len() - suffix + i)))
+        .collect()
+}
+
+struct Hunk {
+    old_start: usize,
+    old_lines: usize,
+    new_start: usize,
+    new_lines: usize,
+    edits: Vec<Edit>,
+}
+
+impl Hunk {
+    fn to_value(&self, old: &[&str], new: &[&str], span: Span) -> Value {
+        let lines = self
+            .edits
+            .iter()
+            .map(|edit| {
+                let (kind, old_line, new_line, content) = match *edit {
+                    Edit::Equal(x, y) => ("context", Some(x), Some(y), old[x]),
+                    Edit::Delete(x) => ("removed", Some(x), None, old[x]),
+                    Edit::Insert(y) => ("added", None, Some(y), new[y]),
+                };
+                let number = |line: Option<usize>| {
+                    line.map_or(Value::nothing(span), |line| {
+                        Value::int(line as i64 + 1, span)
+                    })
+                };
+                Value::Record {
+                    cols: vec![
+                        "type".into(),
+                        "old_line".into(),
+                        "new_line".into(),
+                        "content".into(),
+                    ],
+                    vals: vec![
+                        Value::string(kind, span),
+                        number(old_line),
+                        number(new_line),
+                        Value::string(content, span),
+                    ],
+                    span,
+                }
+            })
+            .collect();
+
+        Value::Record {
+            cols: vec![
+                "old_start".into(),
+                "old_lines".into(),
+                "new_start".into(),
+                "new_lines".into(),
+                "lines".into(),
+            ],
+            vals: vec![
+                Value::int(self.old_start as i64, span),
+                Value::int(self.old_lines as i64, span),
+                Value::int(self.new_start as i64, span),
+                Value::int(self.new_lines as i64, span),
+                Value::List { vals: lines, span },
+            ],
+            span,
+        }
+    }
+}
+
+/// Group the changes with up to `context` unchanged lines around them
+fn hunks(edits: &[Edit], context: usize) -> Vec<Hunk> {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for (i, edit) in edits.iter().enumerate() {
+        if matches!(edit, Edit::Equal(..)) {
+            continue;
+        }
+        let start = i.saturating_sub(context);
+        let end = (i + context + 1).min(edits.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    // The number of lines of both texts before each edit
+    let mut before = Vec::with_capacity(edits.len());
+    let (mut old_before, mut new_before) = (0, 0);
+    for edit in edits {
+        before.push((old_before, new_before));
+        match edit {
+            Edit::Equal(..) => {
+                old_before += 1;
+                new_before += 1;
+            }
+            Edit::Delete(_) => old_before += 1,
+            Edit::Insert(_) => new_before += 1,
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let edits = edits[start..end].to_vec();
+            let (old_before, new_before) = before[start];
+            let old_lines = edits
+                .iter()
+                .filter(|edit| !matches!(edit, Edit::Insert(_)))
+                .count();
+            let new_lines = edits
+                .iter()
+                .filter(|edit| !matches!(edit, Edit::Delete(_)))
+                .count();
+            // An empty side starts at the line before it, like in a unified diff
+            Hunk {
+                old_start: old_before + usize::from(old_lines > 0),
+                old_lines,
+                new_start: new_before + usize::from(new_lines > 0),
+                new_lines,
+                edits,
+            }
+        })
+        .collect()
+}
+
+fn unified(
+    old_name: &str,
+    new_name: &str,
+    hunks: &[Hunk],
+    old: &[&str],
+    new: &[&str],
+    color: bool,
+) -> String {
+    let paint = |color_of: Color, line: String| {
+        if color {
+            color_of.paint(line).to_string()
+        } else {
+            line
+        }
+    };
+
+    if hunks.is_empty() {
+        return String::new();
+    }
+    let mut lines = vec![
+        paint(Color::White, format!("--- {}", old_name)),
+        paint(Color::White, format!("+++ {}", new_name)),
+    ];
+    for hunk in hunks {
+        lines.push(paint(
+            Color::Cyan,
+            format!(
+                "@@ -{},{} +{},{} @@",
+                hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+            ),
+        ));
+        for edit in &hunk.edits {
+            lines.push(match *edit {
+                Edit::Equal(x, _) => format!(" {}", old[x]),
+                Edit::Delete(x) => paint(Color::Red, format!("-{}", old[x])),
+                Edit::Insert(y) => paint(Color::Green, format!("+{}", new[y])),
+            });
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn diff_is_shortest() {
+        let old = ["a", "b", "c", "a", "b", "b", "a"];
+        let new = ["c", "b", "a", "b", "a", "c"];
+        let edits = diff(&old, &new);
+        let changes = edits
+            .iter()
+            .filter(|edit| !matches!(edit, Edit::Equal(..)))
+            .count();
+        assert_eq!(changes, 5);
+
+        let rebuilt: Vec<&str> = edits
+            .iter()
+            .filter_map(|edit| match *edit {
+                Edit::Equal(x, _) => Some(old[x]),
+                Edit::Insert(y) => Some(new[y]),
+                Edit::Delete(_) => None,
+            })
+            .collect();
+        assert_eq!(rebuilt, new);
+    }
+
+    #[test]
+    fn unified_hunk_headers() {
+        let old = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        let new = ["1", "2", "3", "4", "5", "6", "7", "8", "9", "10"];
+        let hunks = hunks(&diff(&old, &new), 1);
+        assert_eq!(
+            unified("a", "b", &hunks, &old, &new, false),
+            "--- a\n+++ b\n@@ -9,1 +9,2 @@\n 9\n+10"
+        );
+    }
+}
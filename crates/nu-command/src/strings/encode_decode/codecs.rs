@@ -0,0 +1,352 @@
+//! Encodings that turn bytes or text into other text, as opposed to the character sets of
+//! encoding_rs
+
+use data_encoding::{
+    Encoding, BASE32, BASE32HEX, BASE32HEX_NOPAD, BASE32_NOPAD, HEXLOWER, HEXLOWER_PERMISSIVE,
+    HEXUPPER,
+};
+use nu_protocol::{ShellError, Span, Value};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// The unreserved characters of RFC 3986 stay as they are
+const URL_ESCAPED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+#[derive(Clone, Copy)]
+pub enum Codec {
+    Base32,
+    Base32Hex,
+    Base58,
+    Hex,
+    Url,
+    Punycode,
+}
+
+/// How `encode hex` lays out its digits
+pub struct HexOptions {
+    pub upper: bool,
+    pub separator: String,
+}
+
+impl Codec {
+    pub fn from_name(name: &str) -> Option<Codec> {
+        match name.to_lowercase().as_str() {
+            "base32" => Some(Codec::Base32),
+            "base32hex" => Some(Codec::Base32Hex),
+            "base58" => Some(Codec::Base58),
+            "hex" => Some(Codec::Hex),
+            "url" => Some(Codec::Url),
+            "punycode" => Some(Codec::Punycode),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Codec::Base32 => "base32",
+            Codec::Base32Hex => "base32hex",
+            Codec::Base58 => "base58",
+            Codec::Hex => "hex",
+            Codec::Url => "url",
+            Codec::Punycode => "punycode",
+        }
+    }
+
+    /// Whether the codec writes any bytes as text, rather than text as other text
+    pub fn takes_bytes(self) -> bool {
+        !matches!(self, Codec::Url | Codec::Punycode)
+    }
+
+    fn encode(self, bytes: &[u8], options: &HexOptions) -> Result<String, String> {
+        let text = || std::str::from_utf8(bytes).map_err(|e| e.to_string());
+        match self {
+            Codec::Base32 => Ok(BASE32.encode(bytes)),
+            Codec::Base32Hex => Ok(BASE32HEX.encode(bytes)),
+            Codec::Base58 => Ok(bs58::encode(bytes).into_string()),
+            Codec::Hex => Ok(hex_encode(bytes, options)),
+            Codec::Url => Ok(utf8_percent_encode(text()?, URL_ESCAPED).to_string()),
+            Codec::Punycode => punycode_encode_domain(text()?),
+        }
+    }
+
+    fn decode(self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Codec::Base32 => base32_decode(text, &BASE32_NOPAD),
+            Codec::Base32Hex => base32_decode(text, &BASE32HEX_NOPAD),
+            Codec::Base58 => bs58::decode(text.trim())
+                .into_vec()
+                .map_err(|e| e.to_string()),
+            Codec::Hex => hex_decode(text),
+            Codec::Url => Ok(percent_decode_str(text).collect()),
+            Codec::Punycode => punycode_decode_domain(text).map(String::into_bytes),
+        }
+    }
+}
+
+pub fn encode(
+    codec: Codec,
+    head: Span,
+    bytes: &[u8],
+    input_span: Span,
+    options: &HexOptions,
+) -> Result<Value, ShellError> {
+    match codec.encode(bytes, options) {
+        Ok(text) => Ok(Value::string(text, head)),
+        Err(message) => Err(ShellError::GenericError(
+            format!("error while encoding to {}", codec.name()),
+            message,
+            Some(input_span),
+            None,
+            vec![],
+        )),
+    }
+}
+
+/// Decode text, returning a string unless `binary` is set and the codec works on bytes
+pub fn decode(
+    codec: Codec,
+    head: Span,
+    text: &[u8],
+    input_span: Span,
+    binary: bool,
+) -> Result<Value, ShellError> {
+    let error = |message: String, help: Option<String>| {
+        ShellError::GenericError(
+            format!("error while decoding {}", codec.name()),
+            message,
+            Some(input_span),
+            help,
+            vec![],
+        )
+    };
+
+    let text = std::str::from_utf8(text).map_err(|e| error(e.to_string(), None))?;
+    let bytes = codec.decode(text).map_err(|message| error(message, None))?;
+    if binary && codec.takes_bytes() {
+        return Ok(Value::binary(bytes, head));
+    }
+    match String::from_utf8(bytes) {
+        Ok(s) => Ok(Value::string(s, head)),
+        Err(_) if codec.takes_bytes() => Err(error(
+            "decoded bytes aren't valid UTF-8".into(),
+            Some("consider using the `--binary` flag".into()),
+        )),
+        Err(_) => Err(error("decoded text isn't valid UTF-8".into(), None)),
+    }
+}
+
+/// Every name that `encode` and `decode` accept, besides aliases
+pub fn supported_encodings(span: Span) -> Vec<Value> {
+    let row = |name: &str, kind: &str| Value::Record {
+        cols: vec!["name".into(), "type".into()],
+        vals: vec![Value::string(name, span), Value::string(kind, span)],
+        span,
+    };
+
+    let codecs = [
+        Codec::Base32,
+        Codec::Base32Hex,
+        Codec::Base58,
+        Codec::Hex,
+        Codec::Url,
+        Codec::Punycode,
+    ];
+    let charsets = [
+        encoding_rs::UTF_8,
+        encoding_rs::UTF_16LE,
+        encoding_rs::UTF_16BE,
+        encoding_rs::IBM866,
+        encoding_rs::ISO_8859_2,
+        encoding_rs::ISO_8859_3,
+        encoding_rs::ISO_8859_4,
+        encoding_rs::ISO_8859_5,
+        encoding_rs::ISO_8859_6,
+        encoding_rs::ISO_8859_7,
+        encoding_rs::ISO_8859_8,
+        encoding_rs::ISO_8859_8_I,
+        encoding_rs::ISO_8859_10,
+        encoding_rs::ISO_8859_13,
+        encoding_rs::ISO_8859_14,
+        encoding_rs::ISO_8859_15,
+        encoding_rs::ISO_8859_16,
+        encoding_rs::KOI8_R,
+        encoding_rs::KOI8_U,
+        encoding_rs::MACINTOSH,
+        encoding_rs::WINDOWS_874,
+        encoding_rs::WINDOWS_1250,
+        encoding_rs::WINDOWS_1251,
+        encoding_rs::WINDOWS_1252,
+        encoding_rs::WINDOWS_1253,
+        encoding_rs::WINDOWS_1254,
+        encoding_rs::WINDOWS_1255,
+        encoding_rs::WINDOWS_1256,
+        encoding_rs::WINDOWS_1257,
+        encoding_rs::WINDOWS_1258,
+        encoding_rs::X_MAC_CYRILLIC,
+        encoding_rs::GBK,
+        encoding_rs::GB18030,
+        encoding_rs::BIG5,
+        encoding_rs::EUC_JP,
+        encoding_rs::ISO_2022_JP,
+        encoding_rs::SHIFT_JIS,
+        encoding_rs::EUC_KR,
+        encoding_rs::X_USER_DEFINED,
+    ];
+
+    codecs
+        .iter()
+        .map(|codec| row(codec.name(), "codec"))
+        .chain(
+            charsets
+                .iter()
+                .map(|charset| row(charset.name(), "charset")),
+        )
+        .collect()
+}
+
+/// Read base32 in either case, with or without its padding and whitespace
+fn base32_decode(text: &str, encoding: &Encoding) -> Result<Vec<u8>, String> {
+    let mut specification = encoding.specification();
+    for symbol in specification.symbols.clone().chars() {
+        if symbol.is_ascii_uppercase() {
+            specification
+                .translate
+                .from
+                .push(symbol.to_ascii_lowercase());
+            specification.translate.to.push(symbol);
+        }
+    }
+    let encoding = specification.encoding().map_err(|e| e.to_string())?;
+
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '=')
+        .collect();
+    encoding
+        .decode(text.as_bytes())
+        .map_err(|e| format!("invalid base32: {e}"))
+}
+
+fn hex_encode(bytes: &[u8], options: &HexOptions) -> String {
+    let digits = match options.upper {
+        true => HEXUPPER.encode(bytes),
+        false => HEXLOWER.encode(bytes),
+    };
+    if options.separator.is_empty() {
+        return digits;
+    }
+    // Two digits make a byte, and are always ASCII
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| String::from_utf8_lossy(pair))
+        .collect::<Vec<_>>()
+        .join(&options.separator)
+}
+
+/// Read hex digits, allowing a `0x` prefix and the usual separators between bytes
+fn hex_decode(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    let digits: String = text
+        .chars()
+        .filter(|c| !(c.is_whitespace() || matches!(c, ':' | '-' | ',')))
+        .collect();
+    HEXLOWER_PERMISSIVE
+        .decode(digits.as_bytes())
+        .map_err(|e| format!("invalid hexadecimal: {e}"))
+}
+
+/// Encode the labels of a domain that aren't plain ASCII, like `bücher.de` to `xn--bcher-kva.de`
+fn punycode_encode_domain(domain: &str) -> Result<String, String> {
+    idna::domain_to_ascii(domain).map_err(|_| format!("{domain} isn't a valid domain name"))
+}
+
+fn punycode_decode_domain(domain: &str) -> Result<String, String> {
+    match idna::domain_to_unicode(domain) {
+        (decoded, Ok(())) => Ok(decoded),
+        (_, Err(_)) => Err(format!("{domain} isn't a valid domain name")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn options() -> HexOptions {
+        HexOptions {
+            upper: false,
+            separator: String::new(),
+        }
+    }
+
+    #[test]
+    fn base32_round_trips() {
+        for (bytes, encoded) in [
+            ("", ""),
+            ("f", "MY======"),
+            ("fo", "MZXQ===="),
+            ("foob", "MZXW6YQ="),
+            ("foobar", "MZXW6YTBOI======"),
+        ] {
+            assert_eq!(
+                Codec::Base32.encode(bytes.as_bytes(), &options()),
+                Ok(encoded.into())
+            );
+            assert_eq!(Codec::Base32.decode(encoded), Ok(bytes.as_bytes().to_vec()));
+        }
+        assert_eq!(Codec::Base32.decode("mzxw6ytboi"), Ok(b"foobar".to_vec()));
+        assert_eq!(
+            Codec::Base32Hex.encode(b"foobar", &options()),
+            Ok("CPNMUOJ1E8======".into())
+        );
+    }
+
+    #[test]
+    fn base58_keeps_leading_zeros() {
+        assert_eq!(
+            Codec::Base58.encode(b"Hello World!", &options()),
+            Ok("2NEpo7TZRRrLZSi2U".into())
+        );
+        assert_eq!(
+            Codec::Base58.encode(&[0, 0, 1], &options()),
+            Ok("112".into())
+        );
+        assert_eq!(Codec::Base58.decode("112"), Ok(vec![0, 0, 1]));
+        assert!(Codec::Base58.decode("0OIl").is_err());
+    }
+
+    #[test]
+    fn hex_allows_separators() {
+        let options = HexOptions {
+            upper: true,
+            separator: ":".into(),
+        };
+        assert_eq!(hex_encode(&[0xde, 0xad], &options), "DE:AD");
+        assert_eq!(hex_decode("0xdeadBEEF"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(hex_decode("de:ad be-ef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert!(hex_decode("abc").is_err());
+    }
+
+    #[test]
+    fn punycode_domains() {
+        assert_eq!(
+            punycode_encode_domain("bücher.example"),
+            Ok("xn--bcher-kva.example".into())
+        );
+        assert_eq!(
+            punycode_decode_domain("XN--bcher-kva.example"),
+            Ok("bücher.example".into())
+        );
+        assert_eq!(
+            punycode_encode_domain("他们为什么不说中文"),
+            Ok("xn--ihqwcrb4cv8a8dqg056pqjye".into())
+        );
+    }
+}
@@ -1,9 +1,10 @@
+use super::codecs::{self, Codec};
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
-    SyntaxShape, Type, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
+    Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -24,8 +25,20 @@ impl Command for Decode {
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("decode")
-            .input_output_types(vec![(Type::Binary, Type::String)])
-            .required("encoding", SyntaxShape::String, "the text encoding to use")
+            .input_output_types(vec![
+                (Type::Binary, Type::String),
+                (Type::String, Type::String),
+                (Type::String, Type::Binary),
+                (Type::Nothing, Type::Table(vec![])),
+            ])
+            .optional("encoding", SyntaxShape::String, "the text encoding to use")
+            .switch(
+                "binary",
+                "output the bytes decoded from base32, base58 or hex instead of a string",
+                Some('b'),
+            )
+            .switch("list", "list the supported encodings", Some('l'))
+            .allow_variants_without_examples(true)
             .category(Category::Strings)
     }
 
@@ -33,8 +46,10 @@ impl Command for Decode {
         r#"Multiple encodings are supported; here are a few:
 big5, euc-jp, euc-kr, gbk, iso-8859-1, utf-16, cp1252, latin5
 
-For a more complete list of encodings please refer to the encoding_rs
-documentation link at https://docs.rs/encoding_rs/latest/encoding_rs/#statics"#
+Strings can also be decoded from base32, base32hex, base58, hex, url escapes and punycode.
+Hex digits may start with 0x and be separated by spaces, colons, dashes or commas.
+
+For a complete list of encodings, use `decode --list`."#
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -52,6 +67,24 @@ documentation link at https://docs.rs/encoding_rs/latest/encoding_rs/#statics"#
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Decode hex digits separated by colons into bytes",
+                example: "'de:ad:be:ef' | decode hex --binary",
+                result: Some(Value::binary(
+                    vec![0xde, 0xad, 0xbe, 0xef],
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                description: "Decode a string escaped for a URL",
+                example: "'caf%C3%A9%20au%20lait' | decode url",
+                result: Some(Value::test_string("café au lait")),
+            },
+            Example {
+                description: "List the supported encodings",
+                example: "decode --list",
+                result: None,
+            },
         ]
     }
 
@@ -63,7 +96,47 @@ documentation link at https://docs.rs/encoding_rs/latest/encoding_rs/#statics"#
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
-        let encoding: Spanned<String> = call.req(engine_state, stack, 0)?;
+        if call.has_flag("list") {
+            return Ok(codecs::supported_encodings(head)
+                .into_iter()
+                .into_pipeline_data(engine_state.ctrlc.clone()));
+        }
+        let encoding: Spanned<String> = match call.opt(engine_state, stack, 0)? {
+            Some(encoding) => encoding,
+            None => return Err(ShellError::MissingParameter("encoding".into(), head)),
+        };
+
+        if let Some(codec) = Codec::from_name(&encoding.item) {
+            let binary = call.has_flag("binary");
+            return match input {
+                PipelineData::ExternalStream { stdout: None, .. } => Ok(PipelineData::empty()),
+                PipelineData::ExternalStream {
+                    stdout: Some(stream),
+                    ..
+                } => {
+                    let bytes = stream.into_bytes()?;
+                    codecs::decode(codec, head, &bytes.item, bytes.span, binary)
+                        .map(|val| val.into_pipeline_data())
+                }
+                PipelineData::Value(Value::String { val, span }, ..) => {
+                    codecs::decode(codec, head, val.as_bytes(), span, binary)
+                        .map(|val| val.into_pipeline_data())
+                }
+                PipelineData::Value(Value::Error { error }, ..) => Err(error),
+                PipelineData::Value(v, ..) => Err(ShellError::OnlySupportsThisInputType(
+                    "string".into(),
+                    v.get_type().to_string(),
+                    head,
+                    v.expect_span(),
+                )),
+                _ => Err(ShellError::UnsupportedInput(
+                    "non-string input".into(),
+                    "value originates from here".into(),
+                    head,
+                    input.span().unwrap_or(head),
+                )),
+            };
+        }
 
         match input {
             PipelineData::ExternalStream { stdout: None, .. } => Ok(PipelineData::empty()),
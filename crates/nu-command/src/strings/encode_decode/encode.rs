@@ -1,3 +1,4 @@
+use super::codecs::{self, Codec, HexOptions};
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
@@ -28,13 +29,24 @@ impl Command for Encode {
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("encode")
-            .input_output_types(vec![(Type::String, Type::Binary)])
+            .input_output_types(vec![
+                (Type::String, Type::Binary),
+                (Type::String, Type::String),
+                (Type::Binary, Type::String),
+            ])
             .required("encoding", SyntaxShape::String, "the text encoding to use")
             .switch(
                 "ignore-errors",
                 "when a character isn't in the given encoding, replace with a HTML entity (like `&#127880;`)",
                 Some('i'),
             )
+            .switch("upper", "write hex digits in uppercase", Some('u'))
+            .named(
+                "separator",
+                SyntaxShape::String,
+                "put a separator between the bytes written as hex",
+                Some('s'),
+            )
             .category(Category::Strings)
     }
 
@@ -44,6 +56,10 @@ big5, euc-jp, euc-kr, gbk, iso-8859-1, cp1252, latin5
 
 Note that since the Encoding Standard doesn't specify encoders for utf-16le and utf-16be, these are not yet supported.
 
+Bytes or strings can also be written as text with base32, base32hex, base58 or hex, and
+strings can be escaped for URLs with url, or turn into ASCII domain names with punycode.
+These return a string. `decode --list` shows every supported name.
+
 For a more complete list of encodings, please refer to the encoding_rs
 documentation link at https://docs.rs/encoding_rs/latest/encoding_rs/#statics"#
     }
@@ -63,6 +79,16 @@ documentation link at https://docs.rs/encoding_rs/latest/encoding_rs/#statics"#
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Write bytes as uppercase hex separated by colons",
+                example: r#"0x[de ad be ef] | encode hex --upper --separator ':'"#,
+                result: Some(Value::test_string("DE:AD:BE:EF")),
+            },
+            Example {
+                description: "Turn an internationalized domain name into ASCII",
+                example: r#"'bücher.example' | encode punycode"#,
+                result: Some(Value::test_string("xn--bcher-kva.example")),
+            },
             Example {
                 description: "Replace characters with HTML entities if they can't be encoded",
                 example: r#""🎈" | encode -i shift-jis"#,
@@ -85,6 +111,55 @@ documentation link at https://docs.rs/encoding_rs/latest/encoding_rs/#statics"#
         let encoding: Spanned<String> = call.req(engine_state, stack, 0)?;
         let ignore_errors = call.has_flag("ignore-errors");
 
+        if let Some(codec) = Codec::from_name(&encoding.item) {
+            let options = HexOptions {
+                upper: call.has_flag("upper"),
+                separator: call
+                    .get_flag(engine_state, stack, "separator")?
+                    .unwrap_or_default(),
+            };
+            let (bytes, span) = match input {
+                PipelineData::ExternalStream { stdout: None, .. } => {
+                    return Ok(PipelineData::empty())
+                }
+                PipelineData::ExternalStream {
+                    stdout: Some(stream),
+                    ..
+                } => {
+                    let bytes = stream.into_bytes()?;
+                    (bytes.item, bytes.span)
+                }
+                PipelineData::Value(Value::String { val, span }, ..) => (val.into_bytes(), span),
+                PipelineData::Value(Value::Binary { val, span }, ..) if codec.takes_bytes() => {
+                    (val, span)
+                }
+                PipelineData::Value(Value::Error { error }, ..) => return Err(error),
+                PipelineData::Value(v, ..) => {
+                    return Err(ShellError::OnlySupportsThisInputType(
+                        if codec.takes_bytes() {
+                            "string or binary"
+                        } else {
+                            "string"
+                        }
+                        .into(),
+                        v.get_type().to_string(),
+                        head,
+                        v.expect_span(),
+                    ))
+                }
+                _ => {
+                    return Err(ShellError::UnsupportedInput(
+                        "non-string input".into(),
+                        "value originates from here".into(),
+                        head,
+                        input.span().unwrap_or(head),
+                    ))
+                }
+            };
+            return codecs::encode(codec, head, &bytes, span, &options)
+                .map(|val| val.into_pipeline_data());
+        }
+
         match input {
             PipelineData::ExternalStream { stdout: None, .. } => Ok(PipelineData::empty()),
             PipelineData::ExternalStream {
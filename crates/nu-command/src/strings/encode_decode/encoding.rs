@@ -52,14 +52,14 @@ pub fn encode(
     }
 }
 
-fn parse_encoding(span: Span, label: &str) -> Result<&'static Encoding, ShellError> {
+pub fn parse_encoding(span: Span, label: &str) -> Result<&'static Encoding, ShellError> {
     // Workaround for a bug in the Encodings Specification.
     let label = if label.to_lowercase() == "utf16" {
         "utf-16"
     } else {
         label
     };
-    match Encoding::for_label_no_replacement(label.as_bytes()) {
+    match Encoding::for_label_no_replacement(label.as_bytes()).or_else(|| code_page_label(label)) {
         None => Err(ShellError::GenericError(
             format!(
                 r#"{label} is not a valid encoding"#
@@ -73,6 +73,29 @@ fn parse_encoding(span: Span, label: &str) -> Result<&'static Encoding, ShellErr
     }
 }
 
+// Windows code pages, as shown by `chcp`, are also known as cp<number>
+fn code_page_label(label: &str) -> Option<&'static Encoding> {
+    let code_page = label.to_lowercase().strip_prefix("cp")?.parse().ok()?;
+    code_page_encoding(code_page)
+}
+
+pub fn code_page_encoding(code_page: u32) -> Option<&'static Encoding> {
+    match code_page {
+        932 => Some(encoding_rs::SHIFT_JIS),
+        936 => Some(encoding_rs::GBK),
+        949 => Some(encoding_rs::EUC_KR),
+        950 => Some(encoding_rs::BIG5),
+        1200 => Some(encoding_rs::UTF_16LE),
+        1201 => Some(encoding_rs::UTF_16BE),
+        20866 => Some(encoding_rs::KOI8_R),
+        21866 => Some(encoding_rs::KOI8_U),
+        54936 => Some(encoding_rs::GB18030),
+        65001 => Some(encoding_rs::UTF_8),
+        // 874 and 1250 to 1258
+        _ => Encoding::for_label_no_replacement(format!("windows-{code_page}").as_bytes()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -87,6 +110,10 @@ mod test {
     #[case::iso_8859_1("iso-8859-1", "Some ¼½¿ Data µ¶·¸¹º")]
     #[case::cp1252("cp1252", "Some ¼½¿ Data")]
     #[case::latin5("latin5", "Some ¼½¿ Data µ¶·¸¹º")]
+    // Windows code pages
+    #[case::cp949("cp949", "가셨어요?")]
+    #[case::cp932("CP932", "何だと？")]
+    #[case::cp874("cp874", "ภาษาไทย")]
     // Tests for specific renditions of UTF-16 and UTF-8 labels
     #[case::utf16("utf16", "")]
     #[case::utf_hyphen_16("utf-16", "")]
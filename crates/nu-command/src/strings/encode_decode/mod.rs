@@ -1,4 +1,5 @@
 mod base64;
+mod codecs;
 mod decode;
 mod decode_base64;
 mod encode;
@@ -9,3 +10,6 @@ pub use self::decode::Decode;
 pub use self::decode_base64::DecodeBase64;
 pub use self::encode::Encode;
 pub use self::encode_base64::EncodeBase64;
+#[cfg(windows)]
+pub(crate) use self::encoding::code_page_encoding;
+pub(crate) use self::encoding::parse_encoding;
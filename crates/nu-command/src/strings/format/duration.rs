@@ -0,0 +1,334 @@
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::ast::{Call, CellPath};
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    format_duration_as_timeperiod, Category, Example, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, TimePeriod, Type, Value,
+};
+
+const NS_PER_SEC: i64 = 1_000_000_000;
+const NS_PER_DAY: i64 = 24 * 60 * 60 * NS_PER_SEC;
+
+// The units `format duration` accepts, besides `human` and `iso`, with their nanoseconds
+const UNITS: [(&str, i64); 10] = [
+    ("ns", 1),
+    ("us", 1_000),
+    ("ms", 1_000_000),
+    ("sec", NS_PER_SEC),
+    ("min", 60 * NS_PER_SEC),
+    ("hr", 60 * 60 * NS_PER_SEC),
+    ("day", NS_PER_DAY),
+    ("wk", 7 * NS_PER_DAY),
+    ("month", 30 * NS_PER_DAY),
+    ("yr", 365 * NS_PER_DAY),
+];
+
+enum DurationFormat {
+    Unit(&'static str, i64),
+    Human,
+    Iso,
+}
+
+struct Arguments {
+    format: DurationFormat,
+    precision: Option<usize>,
+    float_precision: usize,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct FormatDuration;
+
+impl Command for FormatDuration {
+    fn name(&self) -> &str {
+        "format duration"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format duration")
+            .input_output_types(vec![
+                (Type::Duration, Type::String),
+                (Type::Table(vec![]), Type::Table(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
+            .vectorizes_over_list(true)
+            .required(
+                "format value",
+                SyntaxShape::String,
+                "the unit to show the durations in, like sec or hr, or human or iso",
+            )
+            .named(
+                "precision",
+                SyntaxShape::Int,
+                "the digits after the decimal point of a unit",
+                Some('p'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, format durations at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Format durations in a unit, in words or in ISO 8601."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The units are ns, us, ms, sec, min, hr, day, wk, month and yr, where a month is 30 days and
+a year is 365 days. Without --precision, a duration that isn't a whole number of the unit has at
+most as many decimals as $env.config.float_precision.
+
+human writes the duration in words, like "1 hour 30 minutes", and iso writes it like "PT1H30M".
+Both can be read back with `into duration`."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "convert",
+            "display",
+            "unit",
+            "iso 8601",
+            "human readable",
+            "time",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let format_value: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let format = match format_value.item.to_ascii_lowercase().as_str() {
+            "human" => DurationFormat::Human,
+            "iso" => DurationFormat::Iso,
+            unit => match UNITS.iter().find(|(name, _)| *name == unit) {
+                Some((name, nanos)) => DurationFormat::Unit(name, *nanos),
+                None => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "a unit like sec, min or hr, or human or iso".into(),
+                        format_value.item,
+                        format_value.span,
+                    ))
+                }
+            },
+        };
+
+        let precision: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "precision")?;
+        let precision = match precision {
+            Some(precision) if precision.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(precision.span))
+            }
+            Some(precision) => Some(precision.item as usize),
+            None => None,
+        };
+
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            format,
+            precision,
+            float_precision: engine_state.get_config().float_precision.max(0) as usize,
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Show a duration in hours",
+                example: "90min | format duration hr",
+                result: Some(Value::test_string("1.5 hr")),
+            },
+            Example {
+                description: "Show a duration in whole seconds",
+                example: "1234ms | format duration sec --precision 0",
+                result: Some(Value::test_string("1 sec")),
+            },
+            Example {
+                description: "Write a duration in words",
+                example: "(2day + 4hr + 1min) | format duration human",
+                result: Some(Value::test_string("2 days 4 hours 1 minute")),
+            },
+            Example {
+                description: "Write a duration in ISO 8601",
+                example: "(1day + 90sec + 500ms) | format duration iso",
+                result: Some(Value::test_string("P1DT1M30.5S")),
+            },
+            Example {
+                description: "Show a field of a record in hours",
+                example: "{task: build, took: 45min} | format duration hr took",
+                result: Some(Value::test_record(
+                    vec!["task", "took"],
+                    vec![Value::test_string("build"), Value::test_string("0.75 hr")],
+                )),
+            },
+            Example {
+                description: "Show a column of a table in hours",
+                example: "[[task took]; [build 90min] [test 2hr]] | format duration hr took",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["task", "took"],
+                            vec![Value::test_string("build"), Value::test_string("1.5 hr")],
+                        ),
+                        Value::test_record(
+                            vec!["task", "took"],
+                            vec![Value::test_string("test"), Value::test_string("2 hr")],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, span: Span) -> Value {
+    match input {
+        Value::Duration { val, .. } => {
+            let val = match args.format {
+                DurationFormat::Unit(name, nanos) => {
+                    format_in_unit(*val, name, nanos, args.precision, args.float_precision)
+                }
+                DurationFormat::Human => format_human(*val),
+                DurationFormat::Iso => format_iso(*val),
+            };
+            Value::String { val, span }
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "duration".into(),
+                input.get_type().to_string(),
+                span,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+fn format_in_unit(
+    nanos: i64,
+    name: &str,
+    unit: i64,
+    precision: Option<usize>,
+    float_precision: usize,
+) -> String {
+    let amount = nanos as f64 / unit as f64;
+    match precision {
+        Some(precision) => format!("{amount:.precision$} {name}"),
+        None if nanos % unit == 0 => format!("{} {name}", nanos / unit),
+        None => {
+            let amount = format!("{amount:.float_precision$}");
+            if amount.contains('.') {
+                format!(
+                    "{} {name}",
+                    amount.trim_end_matches('0').trim_end_matches('.')
+                )
+            } else {
+                format!("{amount} {name}")
+            }
+        }
+    }
+}
+
+fn format_human(nanos: i64) -> String {
+    let (sign, periods) = format_duration_as_timeperiod(nanos);
+    let words: Vec<String> = periods
+        .into_iter()
+        .map(|period| {
+            let (count, unit) = match period {
+                TimePeriod::Nanos(n) => (n, "nanosecond"),
+                TimePeriod::Micros(n) => (n, "microsecond"),
+                TimePeriod::Millis(n) => (n, "millisecond"),
+                TimePeriod::Seconds(n) => (n, "second"),
+                TimePeriod::Minutes(n) => (n, "minute"),
+                TimePeriod::Hours(n) => (n, "hour"),
+                TimePeriod::Days(n) => (n, "day"),
+                TimePeriod::Weeks(n) => (n, "week"),
+                TimePeriod::Months(n) => (n, "month"),
+                TimePeriod::Years(n) => (n, "year"),
+            };
+            let plural = if count == 1 { "" } else { "s" };
+            format!("{count} {unit}{plural}")
+        })
+        .collect();
+
+    let sign = if sign < 0 { "-" } else { "" };
+    format!("{sign}{}", words.join(" "))
+}
+
+/// Write a duration in days and time, leaving out weeks, months and years which ISO 8601
+/// doesn't give a fixed length
+fn format_iso(nanos: i64) -> String {
+    let sign = if nanos < 0 { "-" } else { "" };
+    let nanos = nanos.unsigned_abs();
+    let days = nanos / NS_PER_DAY as u64;
+    let hours = nanos / (3600 * NS_PER_SEC as u64) % 24;
+    let minutes = nanos / (60 * NS_PER_SEC as u64) % 60;
+    let seconds = nanos / NS_PER_SEC as u64 % 60;
+    let fraction = nanos % NS_PER_SEC as u64;
+
+    let mut iso = format!("{sign}P");
+    if days > 0 {
+        iso.push_str(&format!("{days}D"));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || fraction > 0 || days == 0 {
+        iso.push('T');
+        if hours > 0 {
+            iso.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            iso.push_str(&format!("{minutes}M"));
+        }
+        if seconds > 0 || fraction > 0 || (hours == 0 && minutes == 0) {
+            iso.push_str(&seconds.to_string());
+            if fraction > 0 {
+                let digits = format!("{fraction:09}");
+                iso.push('.');
+                iso.push_str(digits.trim_end_matches('0'));
+            }
+            iso.push('S');
+        }
+    }
+    iso
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FormatDuration)
+    }
+
+    #[test]
+    fn formats_iso() {
+        assert_eq!(format_iso(0), "PT0S");
+        assert_eq!(format_iso(2 * NS_PER_DAY), "P2D");
+        assert_eq!(format_iso(-90 * 60 * NS_PER_SEC), "-PT1H30M");
+        assert_eq!(format_iso(1), "PT0.000000001S");
+    }
+
+    #[test]
+    fn formats_words() {
+        assert_eq!(format_human(0), "0 seconds");
+        assert_eq!(format_human(-NS_PER_DAY - 1), "-1 day 1 nanosecond");
+    }
+}
@@ -3,12 +3,19 @@ use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    format_filesize, Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape,
-    Type, Value,
+    format_filesize, Category, Example, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
 };
 
+// The units `format filesize` accepts, besides `auto`
+const UNITS: [&str; 15] = [
+    "b", "kb", "kib", "mb", "mib", "gb", "gib", "tb", "tib", "pb", "pib", "eb", "eib", "zb", "zib",
+];
+
 struct Arguments {
     format_value: String,
+    filesize_metric: Option<bool>,
+    precision: usize,
     cell_paths: Option<Vec<CellPath>>,
 }
 
@@ -28,11 +35,31 @@ impl Command for FileSize {
 
     fn signature(&self) -> Signature {
         Signature::build("format filesize")
-            .input_output_types(vec![(Type::Filesize, Type::String)])
+            .input_output_types(vec![
+                (Type::Filesize, Type::String),
+                (Type::Table(vec![]), Type::Table(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
             .required(
                 "format value",
                 SyntaxShape::String,
-                "the format into which convert the file sizes",
+                "the unit to convert the file sizes to, like KB or MiB, or auto",
+            )
+            .named(
+                "precision",
+                SyntaxShape::Int,
+                "the digits after the decimal point (default 1)",
+                Some('p'),
+            )
+            .switch(
+                "metric",
+                "with auto, pick a metric unit, like KB or GB",
+                Some('m'),
+            )
+            .switch(
+                "binary",
+                "with auto, pick a binary unit, like KiB or GiB",
+                Some('b'),
             )
             .rest(
                 "rest",
@@ -46,8 +73,21 @@ impl Command for FileSize {
         "Converts a column of filesizes to some specified format"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"The auto unit picks the largest unit the size is at least one of. It is metric or binary
+like $env.config.filesize.metric, unless --metric or --binary is given. The same settings format
+file sizes in tables: $env.config.filesize.format and $env.config.filesize.precision."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["convert", "display", "pattern", "human readable"]
+        vec![
+            "convert",
+            "display",
+            "pattern",
+            "human readable",
+            "unit",
+            "precision",
+        ]
     }
 
     fn run(
@@ -57,14 +97,46 @@ impl Command for FileSize {
         call: &Call,
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let format_value = call
-            .req::<Value>(engine_state, stack, 0)?
-            .as_string()?
-            .to_ascii_lowercase();
+        let format_value = call.req::<Value>(engine_state, stack, 0)?;
+        let format_span = format_value.span()?;
+        let format_value = format_value.as_string()?.to_ascii_lowercase();
+        if format_value != "auto" && !UNITS.contains(&format_value.as_str()) {
+            return Err(ShellError::UnsupportedConfigValue(
+                "a unit like B, KB, KiB, MB or MiB, or auto".into(),
+                format_value,
+                format_span,
+            ));
+        }
+
+        let precision: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "precision")?;
+        let precision = match precision {
+            Some(precision) if precision.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(precision.span))
+            }
+            Some(precision) => precision.item as usize,
+            None => 1,
+        };
+
+        // An explicit unit already says whether it is metric, and overrides the choice
+        let filesize_metric = match (call.has_flag("metric"), call.has_flag("binary")) {
+            (true, true) => {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    "choose either --metric or --binary".into(),
+                    call.head,
+                ))
+            }
+            _ if format_value != "auto" => None,
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            (false, false) => Some(engine_state.get_config().filesize_metric),
+        };
+
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
         let arg = Arguments {
             format_value,
+            filesize_metric,
+            precision,
             cell_paths,
         };
         operate(
@@ -93,6 +165,41 @@ impl Command for FileSize {
                 example: "4Gb | format filesize MB",
                 result: Some(Value::test_string("4000.0 MB")),
             },
+            Example {
+                description: "Show the size in the largest metric unit, with two decimals",
+                example: "2040109465b | format filesize auto --metric --precision 2",
+                result: Some(Value::test_string("2.04 GB")),
+            },
+            Example {
+                description: "Convert a field of a record to KB",
+                example: "{name: a.txt, size: 2MB} | format filesize KB size",
+                result: Some(Value::test_record(
+                    vec!["name", "size"],
+                    vec![Value::test_string("a.txt"), Value::test_string("2000.0 KB")],
+                )),
+            },
+            Example {
+                description: "Convert the size column of a table to KB",
+                example: "[[name size]; [a.txt 1MB] [b.txt 3MB]] | format filesize KB size",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("a.txt"), Value::test_string("1000.0 KB")],
+                        ),
+                        Value::test_record(
+                            vec!["name", "size"],
+                            vec![Value::test_string("b.txt"), Value::test_string("3000.0 KB")],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Convert the size and apparent columns of a table to GiB",
+                example: "du | format filesize GiB size apparent",
+                result: None,
+            },
         ]
     }
 }
@@ -100,8 +207,7 @@ impl Command for FileSize {
 fn format_value_impl(val: &Value, arg: &Arguments, span: Span) -> Value {
     match val {
         Value::Filesize { val, span } => Value::String {
-            // don't need to concern about metric, we just format units by what user input.
-            val: format_filesize(*val, &arg.format_value, None),
+            val: format_filesize(*val, &arg.format_value, arg.filesize_metric, arg.precision),
             span: *span,
         },
         Value::Error { .. } => val.clone(),
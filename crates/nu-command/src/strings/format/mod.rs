@@ -1,5 +1,10 @@
 pub mod command;
+mod duration;
 mod filesize;
+mod number;
 
+pub use self::duration::FormatDuration;
 pub use self::filesize::FileSize;
+pub use self::number::FormatNumber;
+pub(crate) use self::number::{format_number, parse_locale, NumberFormat, NumberStyle};
 pub use command::Format;
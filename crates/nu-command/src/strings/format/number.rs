@@ -0,0 +1,341 @@
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::ast::{Call, CellPath};
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
+};
+use nu_utils::{get_system_locale, locale::locale_from_name};
+use num_format::{Grouping, Locale};
+
+pub(crate) enum NumberStyle {
+    Decimal,
+    Percent,
+    Currency(String),
+}
+
+/// How to write a number for people to read
+pub(crate) struct NumberFormat {
+    pub locale: Locale,
+    pub precision: Option<usize>,
+    pub group: bool,
+    pub style: NumberStyle,
+}
+
+struct Arguments {
+    format: NumberFormat,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct FormatNumber;
+
+impl Command for FormatNumber {
+    fn name(&self) -> &str {
+        "format number"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format number")
+            .input_output_types(vec![
+                (Type::Int, Type::String),
+                (Type::Number, Type::String),
+                (Type::Table(vec![]), Type::Table(vec![])),
+                (Type::Record(vec![]), Type::Record(vec![])),
+            ])
+            .vectorizes_over_list(true)
+            .named(
+                "locale",
+                SyntaxShape::String,
+                "the locale whose separators to use, like de-DE (default: the system locale)",
+                Some('l'),
+            )
+            .named(
+                "precision",
+                SyntaxShape::Int,
+                "the digits after the decimal point",
+                Some('p'),
+            )
+            .switch(
+                "group",
+                "separate groups of digits, like thousands",
+                Some('g'),
+            )
+            .switch("percent", "multiply by 100 and add a percent sign", None)
+            .named(
+                "currency",
+                SyntaxShape::String,
+                "write the number as an amount in the currency with the given symbol",
+                Some('c'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, format numbers at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Format numbers with the separators of a locale."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Without --precision, integers have no decimals, amounts of a currency have two and other
+decimals keep all of theirs. The currency symbol comes before the number, or after it for
+locales with a decimal comma."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "locale",
+            "thousands",
+            "separator",
+            "currency",
+            "percent",
+            "money",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let locale: Option<Spanned<String>> = call.get_flag(engine_state, stack, "locale")?;
+        let precision: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "precision")?;
+        let currency: Option<Spanned<String>> = call.get_flag(engine_state, stack, "currency")?;
+        let percent = call.get_named_arg("percent");
+
+        let style = match (currency, percent) {
+            (Some(currency), Some(percent)) => {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "an amount of a currency".into(),
+                    left_span: currency.span,
+                    right_message: "can't also be a percentage".into(),
+                    right_span: percent.span,
+                })
+            }
+            (Some(currency), None) => NumberStyle::Currency(currency.item),
+            (None, Some(_)) => NumberStyle::Percent,
+            (None, None) => NumberStyle::Decimal,
+        };
+        let format = NumberFormat {
+            locale: match locale {
+                Some(locale) => parse_locale(&locale)?,
+                None => get_system_locale(),
+            },
+            precision: match precision {
+                Some(p) if p.item < 0 => return Err(ShellError::NeedsPositiveValue(p.span)),
+                Some(p) => Some(p.item as usize),
+                None => None,
+            },
+            group: call.has_flag("group"),
+            style,
+        };
+
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments { format, cell_paths };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Format a number the German way",
+                example: "1234567.891 | format number --locale de-DE --precision 2 --group",
+                result: Some(Value::test_string("1.234.567,89")),
+            },
+            Example {
+                description: "Format an amount of dollars",
+                example: "-1234.5 | format number --currency '$' --group --locale en",
+                result: Some(Value::test_string("-$1,234.50")),
+            },
+            Example {
+                description: "Format an amount of euros in a German report",
+                example: "1234.5 | format number --currency € --group --locale de",
+                result: Some(Value::test_string("1.234,50 €")),
+            },
+            Example {
+                description: "Format a ratio as a percentage",
+                example: "0.256 | format number --percent --precision 1 --locale en",
+                result: Some(Value::test_string("25.6%")),
+            },
+            Example {
+                description: "Group the digits of an integer",
+                example: "1234567 | format number --group --locale en",
+                result: Some(Value::test_string("1,234,567")),
+            },
+            Example {
+                description: "Format a field of a record as dollars",
+                example: "{item: tea, price: 4.5} | format number price --currency '$' --locale en",
+                result: Some(Value::test_record(
+                    vec!["item", "price"],
+                    vec![Value::test_string("tea"), Value::test_string("$4.50")],
+                )),
+            },
+            Example {
+                description: "Format a column of a table as dollars",
+                example: "[[item price]; [tea 4.5] [cake 12]] | format number price --currency '$' --locale en",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_record(
+                            vec!["item", "price"],
+                            vec![Value::test_string("tea"), Value::test_string("$4.50")],
+                        ),
+                        Value::test_record(
+                            vec!["item", "price"],
+                            vec![Value::test_string("cake"), Value::test_string("$12.00")],
+                        ),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+pub(crate) fn parse_locale(name: &Spanned<String>) -> Result<Locale, ShellError> {
+    locale_from_name(&name.item).ok_or_else(|| {
+        ShellError::GenericError(
+            format!("unknown locale '{}'", name.item),
+            "no number format for this locale".into(),
+            Some(name.span),
+            Some("use a name like en, de-DE or fr_CA".into()),
+            vec![],
+        )
+    })
+}
+
+fn action(input: &Value, args: &Arguments, span: Span) -> Value {
+    match input {
+        Value::Error { .. } => input.clone(),
+        _ => match format_number(input, &args.format) {
+            Some(val) => Value::String { val, span },
+            None => Value::Error {
+                error: ShellError::OnlySupportsThisInputType(
+                    "int or decimal".into(),
+                    input.get_type().to_string(),
+                    span,
+                    input.expect_span(),
+                ),
+            },
+        },
+    }
+}
+
+/// Write an int or a decimal, or return None for anything else
+pub(crate) fn format_number(value: &Value, format: &NumberFormat) -> Option<String> {
+    let locale = &format.locale;
+    let (negative, digits) = match (value, &format.style) {
+        // Integers keep all their digits, which a float might not hold
+        (Value::Int { val, .. }, NumberStyle::Decimal | NumberStyle::Currency(_)) => {
+            let precision = format.precision.unwrap_or(match format.style {
+                NumberStyle::Currency(_) => 2,
+                _ => 0,
+            });
+            let mut digits = val.unsigned_abs().to_string();
+            if precision > 0 {
+                digits = format!("{digits}.{:0<precision$}", "");
+            }
+            (*val < 0, digits)
+        }
+        (Value::Int { .. } | Value::Float { .. }, style) => {
+            let mut val = value.as_float().ok()?;
+            if let NumberStyle::Percent = style {
+                val *= 100.0;
+            }
+            if !val.is_finite() {
+                return Some(val.to_string());
+            }
+            let precision = match (format.precision, style) {
+                (Some(precision), _) => Some(precision),
+                (None, NumberStyle::Currency(_)) => Some(2),
+                (None, _) => None,
+            };
+            let digits = match precision {
+                Some(precision) => format!("{:.precision$}", val.abs()),
+                None => val.abs().to_string(),
+            };
+            // Leave out the sign of a number that rounds to zero
+            let zero = digits.chars().all(|c| c == '0' || c == '.');
+            (val < 0.0 && !zero, digits)
+        }
+        _ => return None,
+    };
+
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits.as_str(), None),
+    };
+    let mut number = if format.group {
+        group_digits(integer, locale)
+    } else {
+        integer.to_string()
+    };
+    if let Some(fraction) = fraction {
+        number.push_str(locale.decimal());
+        number.push_str(fraction);
+    }
+
+    let sign = if negative { locale.minus_sign() } else { "" };
+    Some(match &format.style {
+        NumberStyle::Decimal => format!("{sign}{number}"),
+        NumberStyle::Percent => format!("{sign}{number}%"),
+        NumberStyle::Currency(symbol) if locale.decimal() == "," => {
+            format!("{sign}{number} {symbol}")
+        }
+        NumberStyle::Currency(symbol) => format!("{sign}{symbol}{number}"),
+    })
+}
+
+/// Put the separator of the locale between the groups of the integer part of a number
+pub(crate) fn group_digits(integer: &str, locale: &Locale) -> String {
+    let sizes: &[usize] = match locale.grouping() {
+        Grouping::Standard => &[3],
+        // The last three digits, then groups of two, like 12,34,567
+        Grouping::Indian => &[3, 2],
+        Grouping::Posix => return integer.to_string(),
+    };
+
+    let chars: Vec<char> = integer.chars().collect();
+    let mut groups = vec![];
+    let mut end = chars.len();
+    let mut size = sizes[0];
+    while end > size {
+        groups.push(chars[end - size..end].iter().collect::<String>());
+        end -= size;
+        size = *sizes.last().unwrap_or(&3);
+    }
+    groups.push(chars[..end].iter().collect());
+    groups.reverse();
+    groups.join(locale.separator())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(FormatNumber {})
+    }
+
+    #[test]
+    fn groups_digits() {
+        assert_eq!(group_digits("1234567", &Locale::en), "1,234,567");
+        assert_eq!(group_digits("123", &Locale::en), "123");
+        assert_eq!(group_digits("", &Locale::en), "");
+    }
+}
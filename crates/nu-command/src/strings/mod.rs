@@ -1,21 +1,27 @@
 mod char_;
 mod detect_columns;
+mod diff;
 mod encode_decode;
 mod format;
 mod parse;
+mod patch;
 mod size;
 mod split;
 mod str_;
 
 pub use char_::Char;
 pub use detect_columns::*;
+pub use diff::*;
 pub use encode_decode::*;
 pub use format::*;
 pub use parse::*;
+pub use patch::*;
 pub use size::Size;
 pub use split::*;
 pub use str_::*;
 
+pub(crate) use str_::fold_latin;
+
 use nu_protocol::{ast::Call, ShellError};
 
 // For handling the grapheme_cluster related flags on some commands.
@@ -33,7 +39,7 @@ pub fn grapheme_flags(call: &Call) -> Result<bool, ShellError> {
     }
     if g_flag && call.has_flag("code-points") {
         Err(ShellError::IncompatibleParametersSingle(
-            "Incompatible flags: --grapheme-clusters (-g) and --utf-8-bytes (-b)".to_string(),
+            "Incompatible flags: --grapheme-clusters (-g) and --code-points (-c)".to_string(),
             call.head,
         ))?
     }
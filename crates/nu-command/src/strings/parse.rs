@@ -1,3 +1,6 @@
+use crate::conversions::into::{
+    filesize_from_string, int_from_string, string_to_boolean, string_to_duration,
+};
 use fancy_regex::Regex;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
@@ -6,6 +9,7 @@ use nu_protocol::{
     Category, Example, ListStream, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape,
     Type, Value,
 };
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct Parse;
@@ -19,8 +23,15 @@ impl Command for Parse {
         "Parse columns from string data using a simple pattern."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"A column can be given a type after its name, like {size:int} or (?P<size:int>\d+), or
+with --types, to convert its captures. The types are int, float, bool, filesize, duration and
+string. Captures that are empty become null, and captures that can't be converted become errors.
+With --regex, every match of the pattern in each input is returned, not only the first one."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["pattern", "match"]
+        vec!["pattern", "match", "regex", "extract"]
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -32,6 +43,12 @@ impl Command for Parse {
             )
             .input_output_types(vec![(Type::String, Type::Table(vec![]))])
             .switch("regex", "use full regex syntax for patterns", Some('r'))
+            .named(
+                "types",
+                SyntaxShape::Record,
+                "the types to convert columns to, like {size: int}",
+                Some('t'),
+            )
             .category(Category::Strings)
     }
 
@@ -103,6 +120,38 @@ impl Command for Parse {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Convert a column to an int while parsing",
+                example: "\"size: 42\" | parse \"size: {size:int}\"",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["size".to_string()],
+                        vals: vec![Value::test_int(42)],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Parse every match in a string, converting columns with --types",
+                example:
+                    "\"a=1 b=2\" | parse -r '(?P<key>\\w)=(?P<value>\\d)' --types {value: int}",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::Record {
+                            cols: vec!["key".to_string(), "value".to_string()],
+                            vals: vec![Value::test_string("a"), Value::test_int(1)],
+                            span: Span::test_data(),
+                        },
+                        Value::Record {
+                            cols: vec!["key".to_string(), "value".to_string()],
+                            vals: vec![Value::test_string("b"), Value::test_int(2)],
+                            span: Span::test_data(),
+                        },
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
             Example {
                 description: "Parse a string using fancy-regex look ahead atomic group pattern",
                 example: "\"abcd\" | parse -r '^a(bc(?=d)|b)cd$'",
@@ -138,6 +187,7 @@ fn operate(
     let head = call.head;
     let pattern: Spanned<String> = call.req(engine_state, stack, 0)?;
     let regex: bool = call.has_flag("regex");
+    let types_flag: Option<Value> = call.get_flag(engine_state, stack, "types")?;
     let ctrlc = engine_state.ctrlc.clone();
 
     let pattern_item = pattern.item;
@@ -149,6 +199,8 @@ fn operate(
         build_regex(&pattern_item, pattern_span)?
     };
 
+    let (item_to_parse, mut types) = take_capture_types(&item_to_parse, pattern_span)?;
+
     let regex_pattern = Regex::new(&item_to_parse).map_err(|err| {
         ShellError::GenericError(
             "Error with regular expression".into(),
@@ -161,6 +213,23 @@ fn operate(
 
     let columns = column_names(&regex_pattern);
 
+    if let Some(types_flag) = types_flag {
+        let flag_span = types_flag.span()?;
+        let (cols, vals) = types_flag.as_record()?;
+        for (col, val) in cols.iter().zip(vals) {
+            if !columns.contains(col) {
+                return Err(ShellError::CantFindColumn(
+                    col.clone(),
+                    flag_span,
+                    pattern_span,
+                ));
+            }
+            let name = val.as_string()?;
+            types.insert(col.clone(), CaptureType::parse(&name, val.span()?)?);
+        }
+    }
+    let types: Vec<Option<CaptureType>> = columns.iter().map(|c| types.get(c).copied()).collect();
+
     match input {
         PipelineData::Empty => Ok(PipelineData::Empty),
         PipelineData::Value(..) => {
@@ -187,13 +256,11 @@ fn operate(
                             };
                             let mut vals = Vec::with_capacity(captures.len());
 
-                            for (column_name, cap) in columns.iter().zip(captures.iter().skip(1)) {
-                                let cap_string = cap.map(|v| v.as_str()).unwrap_or("").to_string();
+                            for ((column_name, ty), cap) in
+                                columns.iter().zip(&types).zip(captures.iter().skip(1))
+                            {
                                 cols.push(column_name.clone());
-                                vals.push(Value::String {
-                                    val: cap_string,
-                                    span: v.span()?,
-                                });
+                                vals.push(capture_value(cap.map(|v| v.as_str()), *ty, v.span()?));
                             }
 
                             parsed.push(Value::Record {
@@ -225,6 +292,7 @@ fn operate(
                     excess: Vec::new(),
                     regex: regex_pattern,
                     columns,
+                    types,
                     stream: stream.stream,
                 },
                 ctrlc,
@@ -244,6 +312,7 @@ fn operate(
                     excess: Vec::new(),
                     regex: regex_pattern,
                     columns,
+                    types,
                     stream: stream.stream,
                 },
                 ctrlc,
@@ -293,6 +362,7 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
         }
 
         if !column.is_empty() {
+            // A type after the name is left for take_capture_types
             output.push_str("(?P<");
             output.push_str(&column);
             output.push_str(">.*?)");
@@ -307,6 +377,106 @@ fn build_regex(input: &str, span: Span) -> Result<String, ShellError> {
     Ok(output)
 }
 
+#[derive(Clone, Copy)]
+enum CaptureType {
+    Int,
+    Float,
+    Bool,
+    Filesize,
+    Duration,
+    String,
+}
+
+impl CaptureType {
+    fn parse(name: &str, span: Span) -> Result<Self, ShellError> {
+        match name {
+            "int" => Ok(CaptureType::Int),
+            "float" => Ok(CaptureType::Float),
+            "bool" => Ok(CaptureType::Bool),
+            "filesize" => Ok(CaptureType::Filesize),
+            "duration" => Ok(CaptureType::Duration),
+            "string" => Ok(CaptureType::String),
+            _ => Err(ShellError::UnsupportedConfigValue(
+                "int, float, bool, filesize, duration or string".into(),
+                name.into(),
+                span,
+            )),
+        }
+    }
+
+    fn convert(self, text: &str, span: Span) -> Result<Value, ShellError> {
+        Ok(match self {
+            CaptureType::Int => Value::int(int_from_string(text, span)?, span),
+            CaptureType::Float => match text.trim().parse::<f64>() {
+                Ok(val) => Value::float(val, span),
+                Err(_) => {
+                    return Err(ShellError::CantConvert(
+                        "float".into(),
+                        "string".into(),
+                        span,
+                        None,
+                    ))
+                }
+            },
+            CaptureType::Bool => Value::boolean(string_to_boolean(text, span)?, span),
+            CaptureType::Filesize => Value::Filesize {
+                val: filesize_from_string(text, span)?,
+                span,
+            },
+            CaptureType::Duration => Value::Duration {
+                val: string_to_duration(text.trim(), span, span)?,
+                span,
+            },
+            CaptureType::String => Value::string(text, span),
+        })
+    }
+}
+
+/// Remove the types from named groups like `(?P<size:int>`, returning them by column name
+fn take_capture_types(
+    pattern: &str,
+    span: Span,
+) -> Result<(String, HashMap<String, CaptureType>), ShellError> {
+    let typed_group =
+        Regex::new(r"(?<!\\)\(\?P?<(\w+):(\w+)>").expect("typed group regex is valid");
+    let mut types = HashMap::new();
+    let mut output = String::new();
+    let mut last = 0;
+    for captures in typed_group.captures_iter(pattern) {
+        let captures = captures.map_err(|e| {
+            ShellError::GenericError(
+                "Error with regular expression captures".into(),
+                e.to_string(),
+                Some(span),
+                None,
+                Vec::new(),
+            )
+        })?;
+        let (whole, name, ty) = match (captures.get(0), captures.get(1), captures.get(2)) {
+            (Some(whole), Some(name), Some(ty)) => (whole, name.as_str(), ty.as_str()),
+            _ => continue,
+        };
+        types.insert(name.to_string(), CaptureType::parse(ty, span)?);
+        output.push_str(&pattern[last..whole.start()]);
+        output.push_str("(?P<");
+        output.push_str(name);
+        output.push('>');
+        last = whole.end();
+    }
+    output.push_str(&pattern[last..]);
+    Ok((output, types))
+}
+
+fn capture_value(cap: Option<&str>, ty: Option<CaptureType>, span: Span) -> Value {
+    match (cap.unwrap_or(""), ty) {
+        (text, None) => Value::string(text, span),
+        ("", Some(_)) => Value::nothing(span),
+        (text, Some(ty)) => ty
+            .convert(text, span)
+            .unwrap_or_else(|error| Value::Error { error }),
+    }
+}
+
 fn column_names(regex: &Regex) -> Vec<String> {
     regex
         .capture_names()
@@ -324,6 +494,7 @@ pub struct ParseStreamer {
     excess: Vec<Value>,
     regex: Regex,
     columns: Vec<String>,
+    types: Vec<Option<CaptureType>>,
     stream: Box<dyn Iterator<Item = Value> + Send + 'static>,
 }
 
@@ -342,7 +513,8 @@ impl Iterator for ParseStreamer {
                     self.regex.clone(),
                     v.span().unwrap_or(self.span),
                     s,
-                    self.columns.clone(),
+                    &self.columns,
+                    &self.types,
                     &mut self.excess,
                 ),
                 Err(_) => Some(Value::Error {
@@ -364,6 +536,7 @@ pub struct ParseStreamerExternal {
     excess: Vec<Value>,
     regex: Regex,
     columns: Vec<String>,
+    types: Vec<Option<CaptureType>>,
     stream: Box<dyn Iterator<Item = Result<Vec<u8>, ShellError>> + Send + 'static>,
 }
 
@@ -382,7 +555,8 @@ impl Iterator for ParseStreamerExternal {
                     self.regex.clone(),
                     self.span,
                     s,
-                    self.columns.clone(),
+                    &self.columns,
+                    &self.types,
                     &mut self.excess,
                 ),
                 Err(_) => Some(Value::Error {
@@ -401,7 +575,8 @@ fn stream_helper(
     regex: Regex,
     span: Span,
     s: String,
-    columns: Vec<String>,
+    columns: &[String],
+    types: &[Option<CaptureType>],
     excess: &mut Vec<Value>,
 ) -> Option<Value> {
     let results = regex.captures_iter(&s);
@@ -424,13 +599,9 @@ fn stream_helper(
         };
         let mut vals = Vec::with_capacity(captures.len());
 
-        for (column_name, cap) in columns.iter().zip(captures.iter().skip(1)) {
-            let cap_string = cap.map(|v| v.as_str()).unwrap_or("").to_string();
+        for ((column_name, ty), cap) in columns.iter().zip(types).zip(captures.iter().skip(1)) {
             cols.push(column_name.clone());
-            vals.push(Value::String {
-                val: cap_string,
-                span,
-            });
+            vals.push(capture_value(cap.map(|v| v.as_str()), *ty, span));
         }
 
         excess.push(Value::Record { cols, vals, span });
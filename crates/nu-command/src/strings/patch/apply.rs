@@ -0,0 +1,629 @@
+use nu_engine::{current_dir, CallExt};
+use nu_protocol::{
+    ast::Call,
+    engine::{Capability, Command, EngineState, Stack},
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "patch apply"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("patch apply")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Table(vec![])),
+                (Type::String, Type::String),
+                (Type::String, Type::Table(vec![])),
+            ])
+            .optional(
+                "patch",
+                SyntaxShape::Filepath,
+                "the file with the unified diff",
+            )
+            .named(
+                "diff",
+                SyntaxShape::String,
+                "the unified diff, instead of a file",
+                Some('d'),
+            )
+            .named(
+                "strip",
+                SyntaxShape::Int,
+                "the number of leading directories to remove from the file names (default 0)",
+                Some('s'),
+            )
+            .switch(
+                "dry-run",
+                "check where the changes apply without changing anything",
+                Some('n'),
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Apply a unified diff to files or to a piped string."
+    }
+
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Without input, the files named in the diff are changed and a table tells, for each group of
+changes, whether it was applied and where. A group is still applied if its lines moved, and the
+offset tells by how many lines. Groups that don't match the file are rejected and the rest are
+applied. Nothing is written if a file can't be read, or with --dry-run, and files outside the
+current directory are never changed.
+
+With a piped string, the diff must be for a single file, and the string with the changes applied
+is returned, or an error if one of them doesn't match. With --dry-run, the table is returned
+instead."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["diff", "unified", "hunk", "reject", "codemod", "change"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let dry_run = call.has_flag("dry-run");
+        let strip: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "strip")?;
+        let strip = match strip {
+            Some(strip) if strip.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(strip.span))
+            }
+            Some(strip) => Spanned {
+                item: strip.item as usize,
+                span: strip.span,
+            },
+            None => Spanned {
+                item: 0,
+                span: head,
+            },
+        };
+
+        let cwd = current_dir(engine_state, stack)?;
+        let path: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let diff: Option<Spanned<String>> = call.get_flag(engine_state, stack, "diff")?;
+        let patch = match (path, diff) {
+            (Some(path), None) => {
+                let contents = std::fs::read_to_string(nu_path::expand_path_with(&path.item, &cwd))
+                    .map_err(|e| {
+                        ShellError::GenericError(
+                            "Error reading the patch".into(),
+                            e.to_string(),
+                            Some(path.span),
+                            None,
+                            vec![],
+                        )
+                    })?;
+                Spanned {
+                    item: contents,
+                    span: path.span,
+                }
+            }
+            (None, Some(diff)) => diff,
+            (Some(path), Some(diff)) => {
+                return Err(ShellError::IncompatibleParameters {
+                    left_message: "a file with the patch".into(),
+                    left_span: path.span,
+                    right_message: "can't be given with --diff".into(),
+                    right_span: diff.span,
+                })
+            }
+            (None, None) => return Err(ShellError::MissingParameter("patch".into(), head)),
+        };
+        // The output of `diff text --unified` may be colored
+        let text = nu_utils::strip_ansi_string_unlikely(patch.item);
+        let files = parse_patch(&text, patch.span)?;
+
+        match input {
+            PipelineData::Empty | PipelineData::Value(Value::Nothing { .. }, ..) => {
+                apply_to_files(&files, &cwd, strip, dry_run, head)
+            }
+            input => {
+                let config = engine_state.get_config();
+                let text = input.collect_string("", config)?;
+                apply_to_text(&files, text, dry_run, patch.span, head)
+            }
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Apply a change to a string",
+                example: "\"a\\nb\\nc\\n\" | patch apply --diff \"@@ -2,1 +2,1 @@\\n-b\\n+B\"",
+                result: Some(Value::test_string("a\nB\nc\n")),
+            },
+            Example {
+                description: "Check where a patch made by git would apply",
+                example: "patch apply changes.patch --strip 1 --dry-run",
+                result: None,
+            },
+            Example {
+                description: "Apply the changes between two files to the text of a third one",
+                example:
+                    "open --raw other.toml | patch apply --diff (diff text config.toml new.toml -u)",
+                result: None,
+            },
+        ]
+    }
+}
+
+const NO_FILE: &str = "/dev/null";
+
+struct FilePatch {
+    old: Option<String>,
+    new: Option<String>,
+    hunks: Vec<Hunk>,
+}
+
+struct Hunk {
+    old_start: usize,
+    old_count: usize,
+    lines: Vec<(char, String)>,
+}
+
+impl Hunk {
+    fn old_lines(&self) -> Vec<&str> {
+        self.side('+')
+    }
+
+    fn new_lines(&self) -> Vec<&str> {
+        self.side('-')
+    }
+
+    fn side(&self, other: char) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter(|(kind, _)| *kind != other)
+            .map(|(_, line)| line.as_str())
+            .collect()
+    }
+}
+
+fn invalid_patch(line: usize, message: &str, span: Span) -> ShellError {
+    ShellError::GenericError(
+        "Invalid patch".into(),
+        format!("line {}: {}", line + 1, message),
+        Some(span),
+        None,
+        vec![],
+    )
+}
+
+/// Read the files and hunks of a unified diff, skipping any other lines around them
+fn parse_patch(text: &str, span: Span) -> Result<Vec<FilePatch>, ShellError> {
+    let name = |s: &str| {
+        s.split('\t')
+            .next()
+            .unwrap_or_default()
+            .trim_end()
+            .to_string()
+    };
+    let mut files: Vec<FilePatch> = vec![];
+    let mut lines = text.lines().enumerate().peekable();
+    while let Some((index, line)) = lines.next() {
+        if let Some(old) = line.strip_prefix("--- ") {
+            let next = lines.peek().copied();
+            let new = match next.and_then(|(_, next)| next.strip_prefix("+++ ")) {
+                Some(new) => {
+                    lines.next();
+                    Some(name(new))
+                }
+                None => None,
+            };
+            files.push(FilePatch {
+                old: Some(name(old)),
+                new,
+                hunks: vec![],
+            });
+            continue;
+        }
+
+        let header = match line.strip_prefix("@@ ") {
+            Some(header) => header,
+            None => continue,
+        };
+        let mut ranges = header.split_whitespace();
+        let (old_start, old_count) = ranges
+            .next()
+            .and_then(|range| parse_range(range, '-'))
+            .ok_or_else(|| invalid_patch(index, "expected the old range, like -1,2", span))?;
+        let (_, new_count) = ranges
+            .next()
+            .and_then(|range| parse_range(range, '+'))
+            .ok_or_else(|| invalid_patch(index, "expected the new range, like +1,2", span))?;
+
+        let mut hunk = Hunk {
+            old_start,
+            old_count,
+            lines: vec![],
+        };
+        let (mut old_left, mut new_left) = (old_count, new_count);
+        while old_left > 0 || new_left > 0 {
+            let (index, line) = lines
+                .next()
+                .ok_or_else(|| invalid_patch(index, "the hunk ends early", span))?;
+            let mut chars = line.chars();
+            let kind = chars.next().unwrap_or(' ');
+            match kind {
+                ' ' if old_left > 0 && new_left > 0 => {
+                    old_left -= 1;
+                    new_left -= 1;
+                }
+                '-' if old_left > 0 => old_left -= 1,
+                '+' if new_left > 0 => new_left -= 1,
+                '\\' => continue,
+                ' ' | '-' | '+' => {
+                    return Err(invalid_patch(
+                        index,
+                        "the hunk has more lines than its header says",
+                        span,
+                    ))
+                }
+                _ => return Err(invalid_patch(index, "expected ' ', '-' or '+'", span)),
+            }
+            hunk.lines.push((kind, chars.as_str().to_string()));
+        }
+
+        match files.last_mut() {
+            Some(file) => file.hunks.push(hunk),
+            // A diff of a piped string may have no file names
+            None => files.push(FilePatch {
+                old: None,
+                new: None,
+                hunks: vec![hunk],
+            }),
+        }
+    }
+
+    if files.iter().all(|file| file.hunks.is_empty()) {
+        return Err(ShellError::GenericError(
+            "Invalid patch".into(),
+            "no changes found".into(),
+            Some(span),
+            Some(
+                "a unified diff has lines like @@ -1,2 +1,3 @@ before each group of changes".into(),
+            ),
+            vec![],
+        ));
+    }
+    Ok(files)
+}
+
+/// Parse `-start,count`, where the count is 1 when it's left out
+fn parse_range(range: &str, sign: char) -> Option<(usize, usize)> {
+    let range = range.strip_prefix(sign)?;
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// Where each hunk was applied, as its first line and how far it moved, or None if rejected
+fn apply(lines: &mut Vec<String>, hunks: &[Hunk]) -> Vec<Option<(usize, i64)>> {
+    let mut delta = 0i64;
+    let mut min = 0;
+    let mut outcomes = vec![];
+    for hunk in hunks {
+        let old = hunk.old_lines();
+        let new = hunk.new_lines();
+        // A hunk without old lines goes after the line it starts at
+        let start = if hunk.old_count == 0 {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let expected = start as i64 + delta;
+        match find(lines, &old, expected, min) {
+            Some(pos) => {
+                lines.splice(
+                    pos..pos + old.len(),
+                    new.iter().map(|line| line.to_string()),
+                );
+                let offset = pos as i64 - expected;
+                delta += offset + new.len() as i64 - old.len() as i64;
+                min = pos + new.len();
+                outcomes.push(Some((pos + 1, offset)));
+            }
+            None => outcomes.push(None),
+        }
+    }
+    outcomes
+}
+
+/// The position closest to `expected`, but not before `min`, where the lines are
+fn find(lines: &[String], old: &[&str], expected: i64, min: usize) -> Option<usize> {
+    if old.len() > lines.len() {
+        return None;
+    }
+    let (low, high) = (min as i64, (lines.len() - old.len()) as i64);
+    let matches = |pos: i64| {
+        (low..=high).contains(&pos)
+            && lines[pos as usize..pos as usize + old.len()]
+                .iter()
+                .zip(old)
+                .all(|(a, b)| a == b)
+    };
+    let mut distance = 0;
+    while expected - distance >= low || expected + distance <= high {
+        if matches(expected - distance) {
+            return Some((expected - distance) as usize);
+        }
+        if matches(expected + distance) {
+            return Some((expected + distance) as usize);
+        }
+        distance += 1;
+    }
+    None
+}
+
+/// Lines of a text, and what to join them with so its line endings are kept
+fn split_lines(text: &str) -> (Vec<String>, &'static str, bool) {
+    let ending = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let lines = text.lines().map(String::from).collect();
+    // A new text ends with a line ending
+    let final_ending = text.is_empty() || text.ends_with('\n');
+    (lines, ending, final_ending)
+}
+
+fn join_lines(lines: &[String], ending: &str, final_ending: bool) -> String {
+    let mut text = lines.join(ending);
+    if final_ending && !lines.is_empty() {
+        text.push_str(ending);
+    }
+    text
+}
+
+fn outcome_rows(file: Value, outcomes: &[Option<(usize, i64)>], span: Span) -> Vec<Value> {
+    outcomes
+        .iter()
+        .enumerate()
+        .map(|(index, outcome)| {
+            let (status, line, offset) = match outcome {
+                Some((line, offset)) => (
+                    "applied",
+                    Value::int(*line as i64, span),
+                    Value::int(*offset, span),
+                ),
+                None => ("rejected", Value::nothing(span), Value::nothing(span)),
+            };
+            Value::Record {
+                cols: vec![
+                    "file".into(),
+                    "hunk".into(),
+                    "status".into(),
+                    "line".into(),
+                    "offset".into(),
+                ],
+                vals: vec![
+                    file.clone(),
+                    Value::int(index as i64 + 1, span),
+                    Value::string(status, span),
+                    line,
+                    offset,
+                ],
+                span,
+            }
+        })
+        .collect()
+}
+
+fn apply_to_text(
+    files: &[FilePatch],
+    text: String,
+    dry_run: bool,
+    patch_span: Span,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let file = match files {
+        [file] => file,
+        _ => {
+            return Err(ShellError::GenericError(
+                "The patch is for more than one file".into(),
+                format!("found changes for {} files", files.len()),
+                Some(patch_span),
+                Some("apply it without input to change the files".into()),
+                vec![],
+            ))
+        }
+    };
+
+    let (mut lines, ending, final_ending) = split_lines(&text);
+    let outcomes = apply(&mut lines, &file.hunks);
+    if dry_run {
+        let name = file
+            .new
+            .clone()
+            .or_else(|| file.old.clone())
+            .map_or(Value::nothing(head), |name| Value::string(name, head));
+        let rows = outcome_rows(name, &outcomes, head);
+        return Ok(Value::List {
+            vals: rows,
+            span: head,
+        }
+        .into_pipeline_data());
+    }
+
+    if let Some(index) = outcomes.iter().position(Option::is_none) {
+        return Err(ShellError::GenericError(
+            "The patch doesn't apply".into(),
+            format!("hunk {} doesn't match the text", index + 1),
+            Some(head),
+            Some("use --dry-run to see which changes match".into()),
+            vec![],
+        ));
+    }
+    Ok(Value::string(join_lines(&lines, ending, final_ending), head).into_pipeline_data())
+}
+
+fn apply_to_files(
+    files: &[FilePatch],
+    cwd: &Path,
+    strip: Spanned<usize>,
+    dry_run: bool,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let strip_name = |name: &str| -> Result<String, ShellError> {
+        let parts: Vec<&str> = name.split('/').filter(|part| !part.is_empty()).collect();
+        if parts.len() <= strip.item {
+            return Err(ShellError::GenericError(
+                "Can't strip the file name".into(),
+                format!("{} has fewer than {} directories", name, strip.item),
+                Some(strip.span),
+                None,
+                vec![],
+            ));
+        }
+        let stripped = parts[strip.item..].join("/");
+        // The files changed must be inside the current directory. Without --strip, the name is
+        // used as it is, so an absolute one is rejected too.
+        let is_inside = |path: &str| {
+            Path::new(path)
+                .components()
+                .all(|part| matches!(part, Component::Normal(_) | Component::CurDir))
+        };
+        if !is_inside(&stripped) || (strip.item == 0 && !is_inside(name)) {
+            return Err(ShellError::GenericError(
+                "The patch changes a file outside the current directory".into(),
+                format!("{stripped} is not inside the current directory"),
+                Some(head),
+                None,
+                vec![],
+            ));
+        }
+        Ok(stripped)
+    };
+
+    // Everything is read and changed before anything is written
+    let mut planned: Vec<(PathBuf, Option<String>)> = vec![];
+    let mut rows = vec![];
+    for file in files {
+        let created = file.old.as_deref() == Some(NO_FILE);
+        let deleted = file.new.as_deref() == Some(NO_FILE);
+        let name = match (&file.old, &file.new) {
+            (Some(_), Some(new)) if created => new,
+            (Some(old), _) => old,
+            _ => {
+                return Err(ShellError::GenericError(
+                    "The patch has no file names".into(),
+                    "can't tell which files to change".into(),
+                    Some(head),
+                    Some("pipe in the text to change instead".into()),
+                    vec![],
+                ))
+            }
+        };
+        let name = strip_name(name)?;
+        let path = cwd.join(&name);
+
+        let text = if created && !path.exists() {
+            String::new()
+        } else {
+            std::fs::read_to_string(&path).map_err(|e| {
+                ShellError::GenericError(
+                    format!("Error reading {}", name),
+                    e.to_string(),
+                    Some(head),
+                    None,
+                    vec![],
+                )
+            })?
+        };
+        let (mut lines, ending, final_ending) = split_lines(&text);
+        let outcomes = apply(&mut lines, &file.hunks);
+        rows.extend(outcome_rows(Value::string(&name, head), &outcomes, head));
+
+        let contents = if deleted && lines.is_empty() {
+            None
+        } else {
+            Some(join_lines(&lines, ending, final_ending))
+        };
+        planned.push((path, contents));
+    }
+
+    if !dry_run {
+        for (path, contents) in planned {
+            let result = match contents {
+                Some(contents) => path
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|_| std::fs::write(&path, contents)),
+                None => std::fs::remove_file(&path),
+            };
+            result.map_err(|e| {
+                ShellError::GenericError(
+                    format!("Error writing {}", path.display()),
+                    e.to_string(),
+                    Some(head),
+                    None,
+                    vec![],
+                )
+            })?;
+        }
+    }
+
+    Ok(Value::List {
+        vals: rows,
+        span: head,
+    }
+    .into_pipeline_data())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn hunks_apply_where_their_lines_moved() {
+        let patch = "--- a\n+++ b\n@@ -2,2 +2,2 @@\n x\n-y\n+Y\n@@ -10,1 +10,2 @@\n z\n+w\n";
+        let files = parse_patch(patch, Span::test_data()).expect("valid patch");
+        let mut text = lines("new\nfirst\nx\ny\nz");
+        let outcomes = apply(&mut text, &files[0].hunks);
+        assert_eq!(outcomes, vec![Some((3, 1)), Some((5, -6))]);
+        assert_eq!(text, lines("new\nfirst\nx\nY\nz\nw"));
+    }
+
+    #[test]
+    fn mismatched_hunks_are_rejected() {
+        let patch = "@@ -1,1 +1,1 @@\n-gone\n+here\n@@ -3 +3 @@\n-c\n+C\n";
+        let files = parse_patch(patch, Span::test_data()).expect("valid patch");
+        let mut text = lines("a\nb\nc");
+        let outcomes = apply(&mut text, &files[0].hunks);
+        assert_eq!(outcomes, vec![None, Some((3, 0))]);
+        assert_eq!(text, lines("a\nb\nC"));
+    }
+
+    #[test]
+    fn short_hunks_are_invalid() {
+        let patch = "@@ -1,2 +1,2 @@\n-a\n+b\n";
+        assert!(parse_patch(patch, Span::test_data()).is_err());
+    }
+}
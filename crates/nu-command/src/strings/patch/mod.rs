@@ -0,0 +1,5 @@
+mod apply;
+mod command;
+
+pub use apply::SubCommand as PatchApply;
+pub use command::PatchCommand as Patch;
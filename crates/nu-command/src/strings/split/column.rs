@@ -1,3 +1,4 @@
+use super::separator::{separator_shape, Separator, SplitOptions};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
@@ -26,10 +27,22 @@ impl Command for SubCommand {
             ])
             .required(
                 "separator",
-                SyntaxShape::String,
-                "the character or string that denotes what separates columns",
+                separator_shape(),
+                "the string that separates columns, or a list of alternatives",
             )
             .switch("collapse-empty", "remove empty columns", Some('c'))
+            .named(
+                "number",
+                SyntaxShape::Int,
+                "Split into maximum number of columns",
+                Some('n'),
+            )
+            .switch("regex", "the separators are regular expressions", Some('r'))
+            .switch(
+                "keep-separators",
+                "keep the separators as items of their own",
+                Some('k'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::String,
@@ -42,8 +55,13 @@ impl Command for SubCommand {
         "Split a string into multiple columns using a separator"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With a list of separators, the string is split at any of them. Kept separators are
+columns of their own."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["separate", "divide"]
+        vec!["separate", "divide", "regex", "delimiter"]
     }
 
     fn run(
@@ -117,6 +135,21 @@ impl Command for SubCommand {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Split at runs of whitespace into at most two named columns",
+                example: "'GET  /index.html HTTP/1.1' | split column --regex '\\s+' --number 2 method rest",
+                result: Some(Value::List {
+                    vals: vec![Value::Record {
+                        cols: vec!["method".to_string(), "rest".to_string()],
+                        vals: vec![
+                            Value::test_string("GET"),
+                            Value::test_string("/index.html HTTP/1.1"),
+                        ],
+                        span: Span::test_data(),
+                    }],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
@@ -128,28 +161,27 @@ fn split_column(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let name_span = call.head;
-    let separator: Spanned<String> = call.req(engine_state, stack, 0)?;
+    let separator = Separator::from_call(engine_state, stack, call, 0)?;
     let rest: Vec<Spanned<String>> = call.rest(engine_state, stack, 1)?;
-    let collapse_empty = call.has_flag("collapse-empty");
+    let options = SplitOptions::from_call(engine_state, stack, call)?;
 
     input.flat_map(
-        move |x| split_column_helper(&x, &separator, &rest, collapse_empty, name_span),
+        move |x| split_column_helper(&x, &separator, &rest, &options, name_span),
         engine_state.ctrlc.clone(),
     )
 }
 
 fn split_column_helper(
     v: &Value,
-    separator: &Spanned<String>,
+    separator: &Separator,
     rest: &[Spanned<String>],
-    collapse_empty: bool,
+    options: &SplitOptions,
     head: Span,
 ) -> Vec<Value> {
     if let Ok(s) = v.as_string() {
-        let split_result: Vec<_> = if collapse_empty {
-            s.split(&separator.item).filter(|s| !s.is_empty()).collect()
-        } else {
-            s.split(&separator.item).collect()
+        let split_result = match separator.split(&s, options, head) {
+            Ok(split_result) => split_result,
+            Err(error) => return vec![Value::Error { error }],
         };
 
         let positional: Vec<_> = rest.iter().map(|f| f.item.clone()).collect();
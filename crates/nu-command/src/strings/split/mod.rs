@@ -3,6 +3,7 @@ pub mod column;
 pub mod command;
 pub mod list;
 pub mod row;
+mod separator;
 pub mod words;
 
 pub use chars::SubCommand as SplitChars;
@@ -1,9 +1,9 @@
+use super::separator::{separator_shape, Separator, SplitOptions};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
-    Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -20,8 +20,8 @@ impl Command for SubCommand {
             .vectorizes_over_list(true)
             .required(
                 "separator",
-                SyntaxShape::String,
-                "the character that denotes what separates rows",
+                separator_shape(),
+                "the string that separates rows, or a list of alternatives",
             )
             .named(
                 "number",
@@ -29,6 +29,13 @@ impl Command for SubCommand {
                 "Split into maximum number of items",
                 Some('n'),
             )
+            .switch("collapse-empty", "remove empty rows", Some('c'))
+            .switch("regex", "the separators are regular expressions", Some('r'))
+            .switch(
+                "keep-separators",
+                "keep the separators as items of their own",
+                Some('k'),
+            )
             .category(Category::Strings)
     }
 
@@ -36,8 +43,13 @@ impl Command for SubCommand {
         "Split a string into multiple rows using a separator"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With a list of separators, the string is split at any of them. Empty rows removed by
+--collapse-empty don't count towards --number."#
+    }
+
     fn search_terms(&self) -> Vec<&str> {
-        vec!["separate", "divide"]
+        vec!["separate", "divide", "regex", "delimiter"]
     }
 
     fn run(
@@ -92,6 +104,39 @@ impl Command for SubCommand {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Split a string at any of several separators",
+                example: "'a,b;c' | split row [',' ';']",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_string("a"),
+                        Value::test_string("b"),
+                        Value::test_string("c"),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Split a string, ignoring repeated separators",
+                example: "'key=value;;other;' | split row ';' --collapse-empty",
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("key=value"), Value::test_string("other")],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description:
+                    "Split a string at a regex into at most two items, keeping the separator",
+                example: "'a1b22c' | split row --regex '\\d+' --number 2 --keep-separators",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_string("a"),
+                        Value::test_string("1"),
+                        Value::test_string("b22c"),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
         ]
     }
 }
@@ -103,32 +148,29 @@ fn split_row(
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let name_span = call.head;
-    let separator: Spanned<String> = call.req(engine_state, stack, 0)?;
-    let max_split: Option<usize> = call.get_flag(engine_state, stack, "number")?;
+    let separator = Separator::from_call(engine_state, stack, call, 0)?;
+    let options = SplitOptions::from_call(engine_state, stack, call)?;
     input.flat_map(
-        move |x| split_row_helper(&x, &separator, max_split, name_span),
+        move |x| split_row_helper(&x, &separator, &options, name_span),
         engine_state.ctrlc.clone(),
     )
 }
 
 fn split_row_helper(
     v: &Value,
-    separator: &Spanned<String>,
-    max_split: Option<usize>,
+    separator: &Separator,
+    options: &SplitOptions,
     name: Span,
 ) -> Vec<Value> {
     match v.span() {
         Ok(v_span) => {
             if let Ok(s) = v.as_string() {
-                match max_split {
-                    Some(max_split) => s
-                        .splitn(max_split, &separator.item)
-                        .map(|s| Value::string(s, v_span))
-                        .collect(),
-                    None => s
-                        .split(&separator.item)
+                match separator.split(&s, options, v_span) {
+                    Ok(items) => items
+                        .into_iter()
                         .map(|s| Value::string(s, v_span))
                         .collect(),
+                    Err(error) => vec![Value::Error { error }],
                 }
             } else {
                 vec![Value::Error {
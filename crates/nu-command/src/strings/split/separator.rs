@@ -0,0 +1,158 @@
+use fancy_regex::Regex;
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::Call,
+    engine::{EngineState, Stack},
+    ShellError, Span, SyntaxShape, Value,
+};
+
+/// The shape of the separator of `split row` and `split column`: a string, or a list of
+/// alternatives
+pub fn separator_shape() -> SyntaxShape {
+    SyntaxShape::OneOf(vec![
+        SyntaxShape::List(Box::new(SyntaxShape::String)),
+        SyntaxShape::String,
+    ])
+}
+
+/// How `split row` and `split column` split a string, besides the separator
+pub struct SplitOptions {
+    pub max_split: Option<usize>,
+    pub keep: bool,
+    pub collapse_empty: bool,
+}
+
+impl SplitOptions {
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+    ) -> Result<SplitOptions, ShellError> {
+        Ok(SplitOptions {
+            max_split: call.get_flag(engine_state, stack, "number")?,
+            keep: call.has_flag("keep-separators"),
+            collapse_empty: call.has_flag("collapse-empty"),
+        })
+    }
+}
+
+/// Where `split row` and `split column` split a string: at any of the separators, which are
+/// regexes with `--regex` and literal strings otherwise
+pub struct Separator {
+    regex: Regex,
+}
+
+impl Separator {
+    pub fn from_call(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        position: usize,
+    ) -> Result<Separator, ShellError> {
+        let separator: Value = call.req(engine_state, stack, position)?;
+        let span = separator.span()?;
+        let separators = match separator {
+            Value::List { vals, .. } => vals
+                .iter()
+                .map(|val| val.as_string())
+                .collect::<Result<Vec<_>, _>>()?,
+            other => vec![other.as_string()?],
+        };
+        if separators.is_empty() {
+            return Err(ShellError::UnsupportedInput(
+                "at least one separator is needed".into(),
+                "value originates from here".into(),
+                call.head,
+                span,
+            ));
+        }
+
+        let is_regex = call.has_flag("regex");
+        let mut separators = separators;
+        if !is_regex {
+            // Alternatives are tried in order, so a separator that starts with another one is
+            // tried first
+            separators.sort_by_key(|separator| std::cmp::Reverse(separator.len()));
+        }
+        let pattern = separators
+            .iter()
+            .map(|separator| {
+                if is_regex {
+                    format!("(?:{separator})")
+                } else {
+                    fancy_regex::escape(separator).to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+
+        let regex = Regex::new(&pattern).map_err(|e| {
+            ShellError::GenericError(
+                "Invalid separator".into(),
+                e.to_string(),
+                Some(span),
+                Some(
+                    "Separators are regexes with --regex, so special characters must be escaped"
+                        .into(),
+                ),
+                vec![],
+            )
+        })?;
+        Ok(Separator { regex })
+    }
+
+    /// Splits `s` into at most `max_split` parts, the last part keeping the rest of the string.
+    /// With `keep`, every separator is an item of its own between the parts around it. With
+    /// `collapse_empty`, empty parts are left out and don't count towards the maximum, so a run
+    /// of separators splits like a single one.
+    pub fn split<'s>(
+        &self,
+        s: &'s str,
+        options: &SplitOptions,
+        span: Span,
+    ) -> Result<Vec<&'s str>, ShellError> {
+        let max = options.max_split;
+        if max == Some(0) {
+            return Ok(vec![]);
+        }
+
+        let mut items = vec![];
+        let mut parts = 1;
+        let mut last = 0;
+        for found in self.regex.find_iter(s) {
+            let found = found.map_err(|e| {
+                ShellError::GenericError(
+                    "Can't match the separator".into(),
+                    e.to_string(),
+                    Some(span),
+                    None,
+                    vec![],
+                )
+            })?;
+
+            let part = &s[last..found.start()];
+            if options.collapse_empty && part.is_empty() {
+                if options.keep {
+                    items.push(found.as_str());
+                }
+                last = found.end();
+                continue;
+            }
+            if max.map_or(false, |max| parts >= max) {
+                break;
+            }
+
+            items.push(part);
+            if options.keep {
+                items.push(found.as_str());
+            }
+            last = found.end();
+            parts += 1;
+        }
+        let rest = &s[last..];
+        if !(options.collapse_empty && rest.is_empty()) {
+            items.push(rest);
+        }
+        Ok(items)
+    }
+}
@@ -0,0 +1,165 @@
+use super::wrap::{cells, Cell};
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str dedent"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str dedent")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .vectorizes_over_list(true)
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, dedent strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Remove the indentation that all lines of strings have in common"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Indentation is made of spaces and tabs, and lines only share the part where these are the
+same. Blank lines don't count, and lose as much of their whitespace as the others. ANSI escape
+sequences are kept and don't stop the indentation."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["unindent", "indentation", "whitespace", "trim", "heredoc"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments { cell_paths };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Remove the indentation of a block of text",
+                example: "\"    fn main() {\\n        run()\\n    }\" | str dedent",
+                result: Some(Value::test_string("fn main() {\n    run()\n}")),
+            },
+            Example {
+                description: "Only the indentation that is the same on all lines is removed",
+                example: "\"  a\\n\\tb\" | str dedent",
+                result: Some(Value::test_string("  a\n\tb")),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, _args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let lines: Vec<Vec<Cell>> = val.split('\n').map(cells).collect();
+            let common = lines
+                .iter()
+                .filter(|line| !is_blank(line))
+                .map(|line| indentation(line))
+                .reduce(|common, indentation| {
+                    common
+                        .chars()
+                        .zip(indentation.chars())
+                        .take_while(|(a, b)| a == b)
+                        .map(|(a, _)| a)
+                        .collect()
+                })
+                .unwrap_or_default();
+            let width = common.chars().count();
+
+            let dedented: Vec<String> = lines.iter().map(|line| dedent(line, width)).collect();
+            Value::string(dedented.join("\n"), head)
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+pub(super) fn is_blank(line: &[Cell]) -> bool {
+    line.iter()
+        .all(|cell| cell.ch.map_or(true, |c| c.is_whitespace()))
+}
+
+fn indentation(line: &[Cell]) -> String {
+    line.iter()
+        .filter_map(|cell| cell.ch)
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+/// Remove up to `width` spaces and tabs from the start of a line, keeping their escapes
+fn dedent(line: &[Cell], width: usize) -> String {
+    let mut output = String::new();
+    let mut removed = 0;
+    for cell in line {
+        if removed < width && cell.is_space() {
+            output.push_str(&cell.escapes);
+            removed += 1;
+        } else {
+            removed = width;
+            cell.push_to(&mut output);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn escapes_are_kept() {
+        let actual = action(
+            &Value::test_string("\x1b[1m  bold\n    \x1b[0mplain"),
+            &Arguments { cell_paths: None },
+            Span::test_data(),
+        );
+        assert_eq!(actual, Value::test_string("\x1b[1mbold\n  \x1b[0mplain"));
+    }
+}
@@ -3,15 +3,22 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    levenshtein_distance, Category, Example, PipelineData, ShellError, Signature, Span,
+    levenshtein_distance, Category, Example, PipelineData, ShellError, Signature, Span, Spanned,
     SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
 pub struct SubCommand;
 
+#[derive(Clone, Copy)]
+enum Algorithm {
+    Levenshtein,
+    Hamming,
+}
+
 struct Arguments {
     compare_string: String,
+    algorithm: Algorithm,
     cell_paths: Option<Vec<CellPath>>,
 }
 
@@ -29,6 +36,7 @@ impl Command for SubCommand {
     fn signature(&self) -> Signature {
         Signature::build("str distance")
             .input_output_types(vec![(Type::String, Type::Int)])
+            .vectorizes_over_list(true)
             .required(
                 "compare-string",
                 SyntaxShape::String,
@@ -39,6 +47,12 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "For a data structure input, check strings at the given cell paths, and replace with result",
             )
+            .named(
+                "algorithm",
+                SyntaxShape::String,
+                "how to count the differences: levenshtein (the default) or hamming, for strings of equal length",
+                Some('a'),
+            )
             .category(Category::Strings)
     }
 
@@ -47,7 +61,7 @@ impl Command for SubCommand {
     }
 
     fn search_terms(&self) -> Vec<&str> {
-        vec!["edit", "levenshtein"]
+        vec!["edit", "levenshtein", "hamming"]
     }
 
     fn run(
@@ -60,8 +74,24 @@ impl Command for SubCommand {
         let compare_string: String = call.req(engine_state, stack, 0)?;
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
         let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let algorithm: Option<Spanned<String>> = call.get_flag(engine_state, stack, "algorithm")?;
+        let algorithm = match algorithm {
+            None => Algorithm::Levenshtein,
+            Some(algorithm) => match algorithm.item.as_str() {
+                "levenshtein" => Algorithm::Levenshtein,
+                "hamming" => Algorithm::Hamming,
+                _ => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "levenshtein or hamming".into(),
+                        algorithm.item,
+                        algorithm.span,
+                    ))
+                }
+            },
+        };
         let args = Arguments {
             compare_string,
+            algorithm,
             cell_paths,
         };
         operate(action, args, input, call.head, engine_state.ctrlc.clone())
@@ -86,6 +116,11 @@ impl Command for SubCommand {
                 ],
                 span: Span::test_data(),
             }),
+        },
+        Example {
+            description: "Count the positions at which two strings of equal length differ",
+            example: "'karolin' | str distance 'kathrin' --algorithm hamming",
+            result: Some(Value::test_int(3)),
         }]
     }
 }
@@ -93,10 +128,30 @@ impl Command for SubCommand {
 fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     let compare_string = &args.compare_string;
     match input {
-        Value::String { val, .. } => {
-            let distance = levenshtein_distance(val, compare_string);
-            Value::int(distance as i64, head)
-        }
+        Value::String { val, span } => match args.algorithm {
+            Algorithm::Levenshtein => {
+                let distance = levenshtein_distance(val, compare_string);
+                Value::int(distance as i64, head)
+            }
+            Algorithm::Hamming => {
+                if val.chars().count() != compare_string.chars().count() {
+                    return Value::Error {
+                        error: ShellError::UnsupportedInput(
+                            "the hamming distance needs strings of equal length".into(),
+                            "value originates from here".into(),
+                            head,
+                            *span,
+                        ),
+                    };
+                }
+                let distance = val
+                    .chars()
+                    .zip(compare_string.chars())
+                    .filter(|(a, b)| a != b)
+                    .count();
+                Value::int(distance as i64, head)
+            }
+        },
         Value::Error { .. } => input.clone(),
         _ => Value::Error {
             error: ShellError::OnlySupportsThisInputType(
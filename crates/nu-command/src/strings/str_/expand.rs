@@ -0,0 +1,352 @@
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str expand"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str expand")
+            .input_output_types(vec![(Type::String, Type::List(Box::new(Type::String)))])
+            .vectorizes_over_list(true)
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, expand strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Generate a list of strings from a pattern with braces, like in bash"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Braces hold either alternatives separated by commas, like {png,jpg}, or a sequence like
+{1..10}, {10..1..3} or {a..e}. Numbers with a leading zero are padded to the same width, so
+{01..10} gives 01 to 10. Braces can be nested, and other braces are kept as they are. A
+backslash makes the next character plain."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "brace",
+            "expansion",
+            "range",
+            "sequence",
+            "generate",
+            "glob",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments { cell_paths };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let strings = |vals: &[&str]| Value::List {
+            vals: vals.iter().map(|val| Value::test_string(*val)).collect(),
+            span: Span::test_data(),
+        };
+
+        vec![
+            Example {
+                description: "Generate file names from a range and alternatives",
+                example: "'file{1..3}.{png,jpg}' | str expand",
+                result: Some(strings(&[
+                    "file1.png",
+                    "file1.jpg",
+                    "file2.png",
+                    "file2.jpg",
+                    "file3.png",
+                    "file3.jpg",
+                ])),
+            },
+            Example {
+                description: "Count with a step, padding the numbers with zeros",
+                example: "'{01..10..3}' | str expand",
+                result: Some(strings(&["01", "04", "07", "10"])),
+            },
+            Example {
+                description: "Nest alternatives",
+                example: "'src/{lib,{cli,core}/mod}.rs' | str expand",
+                result: Some(strings(&[
+                    "src/lib.rs",
+                    "src/cli/mod.rs",
+                    "src/core/mod.rs",
+                ])),
+            },
+            Example {
+                description: "Count through letters backwards",
+                example: "'{e..a..2}' | str expand",
+                result: Some(strings(&["e", "c", "a"])),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, _args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let chars: Vec<char> = val.chars().collect();
+            let vals = expand(&parse(&chars))
+                .into_iter()
+                .map(|s| Value::string(s, head))
+                .collect();
+            Value::List { vals, span: head }
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+enum Piece {
+    Literal(String),
+    Alternatives(Vec<Vec<Piece>>),
+}
+
+fn parse(chars: &[char]) -> Vec<Piece> {
+    let mut pieces = vec![];
+    let mut literal = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                literal.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            '{' => {
+                if let Some(end) = closing_brace(chars, i) {
+                    let inner = &chars[i + 1..end];
+                    let alternatives = split_alternatives(inner);
+                    let alternatives = if alternatives.len() > 1 {
+                        Some(alternatives.into_iter().map(parse).collect())
+                    } else {
+                        sequence(inner).map(|items| {
+                            items
+                                .into_iter()
+                                .map(|item| vec![Piece::Literal(item)])
+                                .collect()
+                        })
+                    };
+                    if let Some(alternatives) = alternatives {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                        pieces.push(Piece::Alternatives(alternatives));
+                        i = end + 1;
+                        continue;
+                    }
+                }
+                // Anything else in braces is plain text, though braces inside it still expand
+                literal.push('{');
+            }
+            c => literal.push(c),
+        }
+        i += 1;
+    }
+    pieces.push(Piece::Literal(literal));
+    pieces
+}
+
+fn closing_brace(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = open;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Split the inside of braces at the commas that aren't in nested braces
+fn split_alternatives(chars: &[char]) -> Vec<&[char]> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&chars[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(&chars[start..]);
+    parts
+}
+
+/// The items of `start..end` or `start..end..step`, for numbers or single characters
+fn sequence(chars: &[char]) -> Option<Vec<String>> {
+    let inner: String = chars.iter().collect();
+    let parts: Vec<&str> = inner.split("..").collect();
+    let (start, end, step) = match parts[..] {
+        [start, end] => (start, end, 1),
+        [start, end, step] => (start, end, step.parse::<i64>().ok()?),
+        _ => return None,
+    };
+    // The direction comes from the ends, so only the size of the step matters
+    let step = step.unsigned_abs().max(1);
+
+    if let (Ok(first), Ok(last)) = (start.parse::<i64>(), end.parse::<i64>()) {
+        let padded = |s: &str| {
+            let digits = s.trim_start_matches(|c| c == '-' || c == '+');
+            digits.len() > 1 && digits.starts_with('0')
+        };
+        let width = if padded(start) || padded(end) {
+            start.len().max(end.len())
+        } else {
+            0
+        };
+        return Some(
+            steps(first as i128, last as i128, step)
+                .map(|n| format!("{:0width$}", n, width = width))
+                .collect(),
+        );
+    }
+
+    let mut start_chars = start.chars();
+    let mut end_chars = end.chars();
+    match (
+        start_chars.next(),
+        start_chars.next(),
+        end_chars.next(),
+        end_chars.next(),
+    ) {
+        (Some(first), None, Some(last), None)
+            if first.is_ascii_alphabetic() && last.is_ascii_alphabetic() =>
+        {
+            Some(
+                steps(first as i128, last as i128, step)
+                    .filter_map(|n| char::from_u32(n as u32))
+                    .map(String::from)
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn steps(first: i128, last: i128, step: u64) -> impl Iterator<Item = i128> {
+    let count = (first - last).unsigned_abs() / step as u128 + 1;
+    let step = if first <= last {
+        step as i128
+    } else {
+        -(step as i128)
+    };
+    (0..count).map(move |i| first + i as i128 * step)
+}
+
+/// Every combination of the pieces, with the leftmost alternatives changing slowest
+fn expand(pieces: &[Piece]) -> Vec<String> {
+    let mut results = vec![String::new()];
+    for piece in pieces {
+        match piece {
+            Piece::Literal(literal) => {
+                for result in &mut results {
+                    result.push_str(literal);
+                }
+            }
+            Piece::Alternatives(alternatives) => {
+                let expanded: Vec<String> = alternatives
+                    .iter()
+                    .flat_map(|alternative| expand(alternative))
+                    .collect();
+                results = results
+                    .iter()
+                    .flat_map(|result| expanded.iter().map(move |item| format!("{result}{item}")))
+                    .collect();
+            }
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(s: &str) -> Vec<String> {
+        let chars: Vec<char> = s.chars().collect();
+        expand(&parse(&chars))
+    }
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn braces_without_alternatives_are_kept() {
+        assert_eq!(expand_str("{x}{}"), vec!["{x}{}"]);
+        assert_eq!(expand_str("a{b"), vec!["a{b"]);
+        assert_eq!(expand_str("{x{1,2}}"), vec!["{x1}", "{x2}"]);
+        assert_eq!(expand_str("{1..b}"), vec!["{1..b}"]);
+    }
+
+    #[test]
+    fn escapes_are_plain() {
+        assert_eq!(expand_str(r"\{a,b\}"), vec!["{a,b}"]);
+        assert_eq!(expand_str(r"{a\,b,c}"), vec!["a,b", "c"]);
+    }
+
+    #[test]
+    fn negative_and_padded_numbers() {
+        assert_eq!(expand_str("{-1..1}"), vec!["-1", "0", "1"]);
+        assert_eq!(expand_str("{-05..5..5}"), vec!["-05", "000", "005"]);
+        assert_eq!(expand_str("{3..1..0}"), vec!["3", "2", "1"]);
+        assert_eq!(expand_str("{,a}b"), vec!["b", "ab"]);
+    }
+}
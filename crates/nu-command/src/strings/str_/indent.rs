@@ -0,0 +1,142 @@
+use super::dedent::is_blank;
+use super::wrap::cells;
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+struct Arguments {
+    prefix: String,
+    blank: bool,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str indent"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str indent")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .vectorizes_over_list(true)
+            .optional(
+                "prefix",
+                SyntaxShape::String,
+                "the text to put before each line (default: 4 spaces)",
+            )
+            .switch(
+                "blank",
+                "also indent lines that are empty or only whitespace",
+                Some('b'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, indent strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Put a prefix before each line of strings"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Blank lines are left alone unless --blank is given. Lines that only hold ANSI escape
+sequences are blank too."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["indentation", "prefix", "quote", "pad", "whitespace"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let prefix: Option<String> = call.opt(engine_state, stack, 0)?;
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            prefix: prefix.unwrap_or_else(|| " ".repeat(4)),
+            blank: call.has_flag("blank"),
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Indent the lines of a string by 4 spaces",
+                example: "\"a\\nb\" | str indent",
+                result: Some(Value::test_string("    a\n    b")),
+            },
+            Example {
+                description: "Quote a message, leaving the blank line alone",
+                example: "\"hello\\n\\nbye\" | str indent '> '",
+                result: Some(Value::test_string("> hello\n\n> bye")),
+            },
+            Example {
+                description: "Quote a message, blank lines included",
+                example: "\"hello\\n\\nbye\" | str indent '> ' --blank",
+                result: Some(Value::test_string("> hello\n> \n> bye")),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let indented: Vec<String> = val
+                .split('\n')
+                .map(|line| {
+                    if !args.blank && is_blank(&cells(line)) {
+                        line.to_string()
+                    } else {
+                        format!("{}{}", args.prefix, line)
+                    }
+                })
+                .collect();
+            Value::string(indented.join("\n"), head)
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
@@ -0,0 +1,236 @@
+use super::wrap::{cells, escapes_of, render, tokens, Cell};
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+#[derive(Clone, Copy)]
+enum Alignment {
+    Left,
+    Right,
+    Center,
+    Both,
+}
+
+struct Arguments {
+    alignment: Alignment,
+    width: Option<usize>,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str justify"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str justify")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .vectorizes_over_list(true)
+            .required(
+                "alignment",
+                SyntaxShape::String,
+                "where to put the text: left, right, center or both",
+            )
+            .named(
+                "width",
+                SyntaxShape::Int,
+                "the number of columns to fill (default: the width of the longest line)",
+                Some('w'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, justify strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Align each line of strings within a number of columns"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"With both, the spaces between words are stretched so lines reach both edges, except
+for the last line of a paragraph that has more than one line. Lines that are wider than the
+width are left alone. Like `str wrap`, widths are counted in terminal columns and ignore ANSI
+escape sequences, so the two can be combined to lay out text."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["align", "pad", "fmt", "center", "layout"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let alignment: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let alignment = match alignment.item.as_str() {
+            "left" => Alignment::Left,
+            "right" => Alignment::Right,
+            "center" => Alignment::Center,
+            "both" => Alignment::Both,
+            _ => {
+                return Err(ShellError::UnsupportedConfigValue(
+                    "left, right, center or both".into(),
+                    alignment.item,
+                    alignment.span,
+                ))
+            }
+        };
+        let width: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "width")?;
+        let width = match width {
+            Some(width) if width.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue(width.span))
+            }
+            width => width.map(|width| width.item as usize),
+        };
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            alignment,
+            width,
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Right-align a string in 8 columns",
+                example: "'nu' | str justify right -w 8",
+                result: Some(Value::test_string("      nu")),
+            },
+            Example {
+                description: "Center the lines of a string under its longest line",
+                example: "\"title\\nsubtitle\" | str justify center",
+                result: Some(Value::test_string(" title  \nsubtitle")),
+            },
+            Example {
+                description: "Stretch the lines of a paragraph to both edges",
+                example: "\"the quick\\nbrown fox\\njumps\" | str justify both -w 12",
+                result: Some(Value::test_string("the    quick\nbrown    fox\njumps")),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let lines: Vec<Vec<Cell>> = val.split('\n').map(cells).collect();
+            let width = args
+                .width
+                .unwrap_or_else(|| lines.iter().map(|line| line_width(line)).max().unwrap_or(0));
+
+            let justified: Vec<String> = lines
+                .iter()
+                .enumerate()
+                .map(|(index, line)| {
+                    let next_is_blank = lines.get(index + 1).map_or(true, |next| {
+                        next.iter().all(|c| c.is_space() || c.ch.is_none())
+                    });
+                    let ends_paragraph = lines.len() > 1 && next_is_blank;
+                    justify(line, width, args.alignment, ends_paragraph)
+                })
+                .collect();
+            Value::string(justified.join("\n"), head)
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+fn line_width(line: &[Cell]) -> usize {
+    line.iter().map(Cell::width).sum()
+}
+
+fn justify(line: &[Cell], width: usize, alignment: Alignment, ends_paragraph: bool) -> String {
+    let padding = width.saturating_sub(line_width(line));
+    match alignment {
+        Alignment::Left => format!("{}{}", render(line), " ".repeat(padding)),
+        Alignment::Right => format!("{}{}", " ".repeat(padding), render(line)),
+        Alignment::Center => format!(
+            "{}{}{}",
+            " ".repeat(padding / 2),
+            render(line),
+            " ".repeat(padding - padding / 2)
+        ),
+        Alignment::Both if ends_paragraph || padding == 0 => render(line),
+        Alignment::Both => stretch(line, width),
+    }
+}
+
+/// Spread the words of a line over the width, giving the leftmost gaps the extra spaces
+fn stretch(line: &[Cell], width: usize) -> String {
+    let mut escapes = String::new();
+    let mut words = vec![];
+    for (is_space, run) in tokens(line.to_vec()) {
+        if is_space {
+            escapes.push_str(&escapes_of(&run));
+        } else {
+            words.push((std::mem::take(&mut escapes), run));
+        }
+    }
+    if words.len() < 2 {
+        return render(line);
+    }
+
+    let words_width: usize = words.iter().map(|(_, run)| line_width(run)).sum();
+    let spaces = width.saturating_sub(words_width);
+    let gaps = words.len() - 1;
+    let mut output = String::new();
+    for (index, (escapes, run)) in words.iter().enumerate() {
+        if index > 0 {
+            let gap = spaces / gaps + usize::from(index <= spaces % gaps);
+            output.push_str(&" ".repeat(gap));
+        }
+        output.push_str(escapes);
+        output.push_str(&render(run));
+    }
+    output.push_str(&escapes);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn stretch_ignores_escapes() {
+        let line = cells("\x1b[32mok\x1b[0m go");
+        assert_eq!(stretch(&line, 6), "\x1b[32mok   \x1b[0mgo");
+    }
+}
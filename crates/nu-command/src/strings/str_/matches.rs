@@ -0,0 +1,256 @@
+use crate::input_handler::{operate, CmdArgument};
+use fancy_regex::{Captures, Regex};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
+};
+
+struct Arguments {
+    regex: Regex,
+    first: bool,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str matches"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str matches")
+            .input_output_types(vec![(Type::String, Type::Table(vec![]))])
+            .vectorizes_over_list(true)
+            .required(
+                "pattern",
+                SyntaxShape::String,
+                "the regular expression to match",
+            )
+            .switch("first", "only return the first match", Some('f'))
+            .switch("ignore-case", "match letters of any case", Some('i'))
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, match strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Find the matches of a regular expression, with their groups and offsets."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Each match is a record with the matched text, its start and end as UTF-8 byte offsets
+(the end is exclusive, like for `str substring`), and the groups of the pattern. Groups are named
+after their name in the pattern, or their number otherwise, and each has its own text, start and
+end. A group that didn't take part in the match is null."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["regex", "capture", "find", "offset", "position", "scan"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let pattern: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let flags = if call.has_flag("ignore-case") {
+            "(?i)"
+        } else {
+            ""
+        };
+        let regex = Regex::new(&format!("{flags}{}", pattern.item)).map_err(|err| {
+            ShellError::GenericError(
+                "Error with regular expression".into(),
+                err.to_string(),
+                Some(pattern.span),
+                None,
+                Vec::new(),
+            )
+        })?;
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            regex,
+            first: call.has_flag("first"),
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        let span = Span::test_data();
+        let group = |text: &str, start: i64, end: i64| Value::Record {
+            cols: vec!["text".into(), "start".into(), "end".into()],
+            vals: vec![
+                Value::test_string(text),
+                Value::test_int(start),
+                Value::test_int(end),
+            ],
+            span,
+        };
+        let found = |text: &str, start: i64, end: i64, groups: Value| Value::Record {
+            cols: vec![
+                "match".into(),
+                "start".into(),
+                "end".into(),
+                "groups".into(),
+            ],
+            vals: vec![
+                Value::test_string(text),
+                Value::test_int(start),
+                Value::test_int(end),
+                groups,
+            ],
+            span,
+        };
+
+        vec![
+            Example {
+                description: "Find every number with its position",
+                example: "'a1 b22 c333' | str matches '\\d+'",
+                result: Some(Value::List {
+                    vals: vec![
+                        found("1", 1, 2, Value::record(vec![], vec![], span)),
+                        found("22", 4, 6, Value::record(vec![], vec![], span)),
+                        found("333", 8, 11, Value::record(vec![], vec![], span)),
+                    ],
+                    span,
+                }),
+            },
+            Example {
+                description: "Get named and numbered groups of the first match",
+                example: "'key=value' | str matches --first '(?P<key>\\w+)=(\\w+)'",
+                result: Some(Value::List {
+                    vals: vec![found(
+                        "key=value",
+                        0,
+                        9,
+                        Value::record(
+                            vec!["key".into(), "2".into()],
+                            vec![group("key", 0, 3), group("value", 4, 9)],
+                            span,
+                        ),
+                    )],
+                    span,
+                }),
+            },
+            Example {
+                description: "Cut out the text of a match",
+                example: "let s = 'total: 42 items'; let m = ($s | str matches '\\d+' | get 0); $s | str substring [$m.start $m.end]",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let mut matches = vec![];
+            for captures in args.regex.captures_iter(val) {
+                match captures {
+                    Ok(captures) => matches.push(match_record(&args.regex, &captures, head)),
+                    Err(err) => {
+                        return Value::Error {
+                            error: ShellError::GenericError(
+                                "Error with regular expression captures".into(),
+                                err.to_string(),
+                                Some(head),
+                                None,
+                                Vec::new(),
+                            ),
+                        }
+                    }
+                }
+                if args.first {
+                    break;
+                }
+            }
+            Value::List {
+                vals: matches,
+                span: head,
+            }
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+fn match_record(regex: &Regex, captures: &Captures, head: Span) -> Value {
+    let text_range = |text: &str, start: usize, end: usize| {
+        (
+            Value::string(text, head),
+            Value::int(start as i64, head),
+            Value::int(end as i64, head),
+        )
+    };
+
+    let mut cols = vec![];
+    let mut vals = vec![];
+    for (idx, name) in regex.capture_names().enumerate().skip(1) {
+        cols.push(name.map(String::from).unwrap_or_else(|| idx.to_string()));
+        vals.push(match captures.get(idx) {
+            Some(group) => {
+                let (text, start, end) = text_range(group.as_str(), group.start(), group.end());
+                Value::record(
+                    vec!["text".into(), "start".into(), "end".into()],
+                    vec![text, start, end],
+                    head,
+                )
+            }
+            None => Value::nothing(head),
+        });
+    }
+
+    // The whole match is always there
+    let (text, start, end) = match captures.get(0) {
+        Some(whole) => text_range(whole.as_str(), whole.start(), whole.end()),
+        None => text_range("", 0, 0),
+    };
+    Value::record(
+        vec![
+            "match".into(),
+            "start".into(),
+            "end".into(),
+            "groups".into(),
+        ],
+        vec![text, start, end, Value::record(cols, vals, head)],
+        head,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
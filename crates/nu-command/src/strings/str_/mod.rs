@@ -1,27 +1,49 @@
 mod case;
 mod collect;
 mod contains;
+mod dedent;
 mod distance;
 mod ends_with;
+mod expand;
+mod indent;
 mod index_of;
 mod join;
+mod justify;
 mod length;
+mod matches;
 mod replace;
 mod reverse;
+mod similarity;
+mod slug;
 mod starts_with;
 mod substring;
+mod template;
+mod transliterate;
 mod trim;
+mod wrap;
 
 pub use case::*;
 pub use collect::*;
 pub use contains::SubCommand as StrContains;
+pub use dedent::SubCommand as StrDedent;
 pub use distance::SubCommand as StrDistance;
 pub use ends_with::SubCommand as StrEndswith;
+pub use expand::SubCommand as StrExpand;
+pub use indent::SubCommand as StrIndent;
 pub use index_of::SubCommand as StrIndexOf;
 pub use join::*;
+pub use justify::SubCommand as StrJustify;
 pub use length::SubCommand as StrLength;
+pub use matches::SubCommand as StrMatches;
 pub use replace::SubCommand as StrReplace;
 pub use reverse::SubCommand as StrReverse;
+pub use similarity::SubCommand as StrSimilarity;
+pub use slug::SubCommand as StrSlug;
 pub use starts_with::SubCommand as StrStartsWith;
 pub use substring::SubCommand as StrSubstring;
+pub use template::SubCommand as StrTemplate;
+pub use transliterate::SubCommand as StrTransliterate;
 pub use trim::Trim as StrTrim;
+pub use wrap::SubCommand as StrWrap;
+
+pub(crate) use transliterate::fold_latin;
@@ -1,10 +1,23 @@
-use crate::input_handler::{operate, CellPathOnlyArgs};
+use crate::grapheme_flags;
+use crate::input_handler::{operate, CmdArgument};
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::ast::CellPath;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::Category;
 use nu_protocol::{Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value};
+use unicode_segmentation::UnicodeSegmentation;
+
+struct Arguments {
+    cell_paths: Option<Vec<CellPath>>,
+    graphemes: bool,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -18,6 +31,16 @@ impl Command for SubCommand {
         Signature::build("str reverse")
             .input_output_types(vec![(Type::String, Type::String)])
             .vectorizes_over_list(true)
+            .switch(
+                "grapheme-clusters",
+                "reverse grapheme clusters, keeping combined characters and emoji whole",
+                Some('g'),
+            )
+            .switch(
+                "code-points",
+                "reverse code points (default; splits combined characters)",
+                Some('c'),
+            )
             .rest(
                 "rest",
                 SyntaxShape::CellPath,
@@ -42,7 +65,10 @@ impl Command for SubCommand {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
-        let args = CellPathOnlyArgs::from(cell_paths);
+        let args = Arguments {
+            cell_paths: (!cell_paths.is_empty()).then_some(cell_paths),
+            graphemes: grapheme_flags(call)?,
+        };
         operate(action, args, input, call.head, engine_state.ctrlc.clone())
     }
 
@@ -65,14 +91,23 @@ impl Command for SubCommand {
                     span: Span::test_data(),
                 }),
             },
+            Example {
+                description: "Reverse a string, keeping the flag emoji whole",
+                example: "'🇯🇵ほげ' | str reverse -g",
+                result: Some(Value::test_string("げほ🇯🇵")),
+            },
         ]
     }
 }
 
-fn action(input: &Value, _arg: &CellPathOnlyArgs, head: Span) -> Value {
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
     match input {
         Value::String { val, .. } => Value::String {
-            val: val.chars().rev().collect::<String>(),
+            val: if args.graphemes {
+                val.graphemes(true).rev().collect::<String>()
+            } else {
+                val.chars().rev().collect::<String>()
+            },
             span: head,
         },
         Value::Error { .. } => input.clone(),
@@ -97,4 +132,14 @@ mod tests {
 
         test_examples(SubCommand {})
     }
+
+    #[test]
+    fn keeps_combining_characters_with_graphemes() {
+        let args = Arguments {
+            cell_paths: None,
+            graphemes: true,
+        };
+        let actual = action(&Value::test_string("e\u{301}x"), &args, Span::test_data());
+        assert_eq!(actual, Value::test_string("xe\u{301}"));
+    }
 }
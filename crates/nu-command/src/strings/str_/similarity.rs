@@ -0,0 +1,314 @@
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    levenshtein_distance, Category, Example, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+#[derive(Clone, Copy)]
+enum Algorithm {
+    Levenshtein,
+    Jaro,
+    JaroWinkler,
+    Ngram(usize),
+}
+
+struct Arguments {
+    compare_string: String,
+    algorithm: Algorithm,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str similarity"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str similarity")
+            .input_output_types(vec![(Type::String, Type::Float)])
+            .vectorizes_over_list(true)
+            .required(
+                "compare-string",
+                SyntaxShape::String,
+                "the string to compare with",
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, check strings at the given cell paths, and replace with result",
+            )
+            .named(
+                "algorithm",
+                SyntaxShape::String,
+                "how to score the strings: levenshtein (the default), jaro, jaro-winkler or ngram",
+                Some('a'),
+            )
+            .named(
+                "ngram-size",
+                SyntaxShape::Int,
+                "the length of the pieces compared by the ngram algorithm (default 2)",
+                Some('n'),
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Score how similar two strings are, from 0 for nothing in common to 1 for equal strings"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The levenshtein score is one minus the edit distance divided by the length of the longer
+string. Jaro favours strings with the same characters in about the same places, and
+jaro-winkler also rewards a common prefix, which suits short words and names. The ngram score
+is the Sørensen-Dice coefficient of the pieces of both strings."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["fuzzy", "match", "jaro", "levenshtein", "ngram", "compare"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let compare_string: String = call.req(engine_state, stack, 0)?;
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let algorithm: Option<Spanned<String>> = call.get_flag(engine_state, stack, "algorithm")?;
+        let ngram_size: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "ngram-size")?;
+
+        let algorithm = match algorithm {
+            None => Algorithm::Levenshtein,
+            Some(algorithm) => match algorithm.item.as_str() {
+                "levenshtein" => Algorithm::Levenshtein,
+                "jaro" => Algorithm::Jaro,
+                "jaro-winkler" => Algorithm::JaroWinkler,
+                "ngram" => Algorithm::Ngram(2),
+                _ => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "levenshtein, jaro, jaro-winkler or ngram".into(),
+                        algorithm.item,
+                        algorithm.span,
+                    ))
+                }
+            },
+        };
+        let algorithm = match (algorithm, ngram_size) {
+            (_, Some(size)) if size.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue(size.span))
+            }
+            (Algorithm::Ngram(_), Some(size)) => Algorithm::Ngram(size.item as usize),
+            (_, Some(size)) => {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    "--ngram-size only applies to the ngram algorithm".into(),
+                    size.span,
+                ))
+            }
+            (algorithm, None) => algorithm,
+        };
+
+        let args = Arguments {
+            compare_string,
+            algorithm,
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Score the similarity of two strings by their edit distance",
+                example: "'nushell' | str similarity 'nutshell'",
+                result: Some(Value::test_float(0.875)),
+            },
+            Example {
+                description: "Favour a common prefix, like when suggesting a command name",
+                example: "'MARTHA' | str similarity 'MARHTA' -a jaro-winkler",
+                result: Some(Value::test_float(0.9611111111111111)),
+            },
+            Example {
+                description: "Compare the pairs of characters of strings",
+                example: "['night' 'nacht'] | str similarity 'nights' -a ngram",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_float(0.8888888888888888),
+                        Value::test_float(0.2222222222222222),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let a = val.as_str();
+            let b = args.compare_string.as_str();
+            let score = match args.algorithm {
+                Algorithm::Levenshtein => levenshtein_similarity(a, b),
+                Algorithm::Jaro => jaro(a, b),
+                Algorithm::JaroWinkler => jaro_winkler(a, b),
+                Algorithm::Ngram(size) => ngram_similarity(a, b, size),
+            };
+            Value::float(score, head)
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein_distance(a, b) as f64 / longest as f64
+}
+
+fn jaro(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Characters only match when they are at most this far apart
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        for j in start..end {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_order = a
+        .iter()
+        .zip(&a_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let b_order = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let transpositions = a_order.zip(b_order).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
+fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let score = jaro(a, b);
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(x, y)| x == y)
+        .count();
+    score + prefix as f64 * 0.1 * (1.0 - score)
+}
+
+fn ngram_similarity(a: &str, b: &str, size: usize) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let a = ngrams(a, size);
+    let b = ngrams(b, size);
+    let total: usize = a.values().sum::<usize>() + b.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let shared: usize = a
+        .iter()
+        .map(|(gram, count)| (*count).min(*b.get(gram).unwrap_or(&0)))
+        .sum();
+    2.0 * shared as f64 / total as f64
+}
+
+fn ngrams(s: &str, size: usize) -> HashMap<Vec<char>, usize> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut grams = HashMap::new();
+    for gram in chars.windows(size) {
+        *grams.entry(gram.to_vec()).or_insert(0) += 1;
+    }
+    grams
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn jaro_of_known_pairs() {
+        assert!((jaro("MARTHA", "MARHTA") - 0.944).abs() < 1e-3);
+        assert!((jaro("DIXON", "DICKSONX") - 0.767).abs() < 1e-3);
+        assert_eq!(jaro("abc", "xyz"), 0.0);
+        assert_eq!(jaro("", ""), 1.0);
+    }
+
+    #[test]
+    fn scores_of_equal_strings_are_one() {
+        for algorithm in [
+            Algorithm::Levenshtein,
+            Algorithm::Jaro,
+            Algorithm::JaroWinkler,
+            Algorithm::Ngram(3),
+        ] {
+            let args = Arguments {
+                compare_string: "a".into(),
+                algorithm,
+                cell_paths: None,
+            };
+            let actual = action(&Value::test_string("a"), &args, Span::test_data());
+            assert_eq!(actual, Value::test_float(1.0));
+        }
+    }
+}
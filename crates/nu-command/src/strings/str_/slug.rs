@@ -0,0 +1,172 @@
+use super::transliterate::transliterate;
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+struct Arguments {
+    separator: String,
+    max_length: Option<usize>,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str slug"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str slug")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .vectorizes_over_list(true)
+            .named(
+                "separator",
+                SyntaxShape::String,
+                "the text between words (default: -)",
+                Some('s'),
+            )
+            .named(
+                "max-length",
+                SyntaxShape::Int,
+                "the most characters to keep, cutting between words when possible",
+                Some('m'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, make slugs of strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Turn strings into lowercase ASCII words joined by hyphens, for URLs and file names"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Characters are transliterated like with `str transliterate`, and those without an ASCII
+form are dropped. Anything but letters and digits separates words."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["slugify", "url", "permalink", "filename", "kebab"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let separator: Option<String> = call.get_flag(engine_state, stack, "separator")?;
+        let max_length: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "max-length")?;
+        let max_length = match max_length {
+            Some(length) if length.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue(length.span))
+            }
+            Some(length) => Some(length.item as usize),
+            None => None,
+        };
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            separator: separator.unwrap_or_else(|| "-".into()),
+            max_length,
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Make a slug for a blog post",
+                example: "'Ça va? Straße in München!' | str slug",
+                result: Some(Value::test_string("ca-va-strasse-in-munchen")),
+            },
+            Example {
+                description: "Make a file name with underscores, keeping whole words",
+                example: "'Quarterly Report: Q3 2023 (draft)' | str slug -s '_' -m 20",
+                result: Some(Value::test_string("quarterly_report_q3")),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => Value::string(slug(val, args), head),
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+fn slug(s: &str, args: &Arguments) -> String {
+    let ascii = transliterate(s, "").to_ascii_lowercase();
+    let words = ascii
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty());
+
+    let max_length = match args.max_length {
+        Some(max_length) => max_length,
+        None => return words.collect::<Vec<_>>().join(&args.separator),
+    };
+    let mut slug = String::new();
+    for word in words {
+        let separator = if slug.is_empty() { "" } else { &args.separator };
+        if slug.len() + separator.len() + word.len() > max_length {
+            // A first word that is too long is cut, so that the slug isn't empty
+            if slug.is_empty() {
+                slug.push_str(&word[..max_length]);
+            }
+            break;
+        }
+        slug.push_str(separator);
+        slug.push_str(word);
+    }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn cuts_long_first_word() {
+        let args = Arguments {
+            separator: "-".into(),
+            max_length: Some(4),
+            cell_paths: None,
+        };
+        assert_eq!(slug("--Supercalifragilistic word", &args), "supe");
+        assert_eq!(slug("!!!", &args), "");
+    }
+}
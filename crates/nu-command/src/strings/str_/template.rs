@@ -0,0 +1,525 @@
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Config, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+struct Arguments {
+    context: Value,
+    config: Config,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str template"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str template")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .vectorizes_over_list(true)
+            .required(
+                "context",
+                SyntaxShape::Record,
+                "the values the template refers to",
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, render templates at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Fill in a template with the values of a record"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"{{ user.name }} is replaced with a value of the record, where a path of column names and
+list indices leads to nested values.
+
+{% if path %} ... {% elif path %} ... {% else %} ... {% endif %} keeps the text of the first
+path that is true, not empty and not zero. Put `not` before a path to turn it around.
+
+{% for item in path %} ... {% endfor %} repeats the text for each item of a list, or for
+each {key, value} of a record. Inside, loop.index, loop.first and loop.last tell where the
+loop is.
+
+{# ... #} is a comment. A dash right inside the braces, like {%- or -%}, removes the
+whitespace before or after the tag."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "mustache",
+            "jinja",
+            "interpolate",
+            "render",
+            "substitute",
+            "config",
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let context: Value = call.req(engine_state, stack, 0)?;
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 1)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            context,
+            config: engine_state.get_config().clone(),
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Fill in a nested value",
+                example: r#""Hello {{user.name}}" | str template {user: {name: Alice}}"#,
+                result: Some(Value::test_string("Hello Alice")),
+            },
+            Example {
+                description: "Repeat text for each item of a list",
+                example: r#""{% for p in ports %}{{ p }}{% if not loop.last %}, {% endif %}{% endfor %}" | str template {ports: [80 443]}"#,
+                result: Some(Value::test_string("80, 443")),
+            },
+            Example {
+                description: "Pick text depending on a value",
+                example: r#""level = {% if debug %}debug{% else %}info{% endif %}" | str template {debug: false}"#,
+                result: Some(Value::test_string("level = info")),
+            },
+            Example {
+                description: "Generate a config file",
+                example: r#"open hosts.tmpl | str template {hosts: [[name ip]; [db '10.0.0.2']]} | save hosts"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, span } => {
+            let rendered = parse(val).and_then(|nodes| {
+                let mut renderer = Renderer {
+                    context: &args.context,
+                    config: &args.config,
+                    scopes: vec![],
+                    output: String::new(),
+                };
+                renderer.render(&nodes)?;
+                Ok(renderer.output)
+            });
+            match rendered {
+                Ok(val) => Value::String { val, span: head },
+                Err(message) => Value::Error {
+                    error: ShellError::GenericError(
+                        "template error".into(),
+                        message,
+                        Some(*span),
+                        None,
+                        vec![],
+                    ),
+                },
+            }
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+enum Token {
+    Text(String),
+    Expression(String),
+    Tag(String),
+}
+
+/// Split a template into text, {{ expressions }} and {% tags %}, dropping comments and the
+/// whitespace that dashes ask to remove
+fn tokenize(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut rest = template;
+    let mut trim_next = false;
+    loop {
+        let open = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|open| rest.find(open).map(|pos| (pos, *open)))
+            .min();
+        let (text, after) = match open {
+            Some((pos, _)) => (&rest[..pos], &rest[pos..]),
+            None => (rest, ""),
+        };
+
+        let mut text = if trim_next { text.trim_start() } else { text };
+        let trim_before = after.get(2..).map_or(false, |inner| inner.starts_with('-'));
+        if trim_before {
+            text = text.trim_end();
+        }
+        if !text.is_empty() {
+            tokens.push(Token::Text(text.to_string()));
+        }
+
+        let open = match open {
+            Some((_, open)) => open,
+            None => return Ok(tokens),
+        };
+        let close = match open {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let body_start = if trim_before { 3 } else { 2 };
+        let end = after[body_start..]
+            .find(close)
+            .map(|pos| pos + body_start)
+            .ok_or_else(|| format!("{open} isn't closed with {close}"))?;
+        let mut body = &after[body_start..end];
+        trim_next = body.ends_with('-');
+        if trim_next {
+            body = &body[..body.len() - 1];
+        }
+        let body = body.trim().to_string();
+        match open {
+            "{{" => tokens.push(Token::Expression(body)),
+            "{%" => tokens.push(Token::Tag(body)),
+            _ => {}
+        }
+        rest = &after[end + close.len()..];
+    }
+}
+
+struct Condition {
+    negated: bool,
+    path: Vec<String>,
+}
+
+enum Node {
+    Text(String),
+    Value(Vec<String>),
+    If {
+        branches: Vec<(Condition, Vec<Node>)>,
+        otherwise: Vec<Node>,
+    },
+    For {
+        variable: String,
+        path: Vec<String>,
+        body: Vec<Node>,
+    },
+}
+
+fn parse(template: &str) -> Result<Vec<Node>, String> {
+    let tokens = tokenize(template)?;
+    let mut pos = 0;
+    let (nodes, end) = parse_block(&tokens, &mut pos)?;
+    match end {
+        Some(tag) => Err(format!("{{% {tag} %}} doesn't close anything")),
+        None => Ok(nodes),
+    }
+}
+
+/// Parse nodes until a tag that ends a block, returning that tag too
+fn parse_block(tokens: &[Token], pos: &mut usize) -> Result<(Vec<Node>, Option<String>), String> {
+    let mut nodes = vec![];
+    while let Some(token) = tokens.get(*pos) {
+        *pos += 1;
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text.clone())),
+            Token::Expression(expression) => nodes.push(Node::Value(parse_path(expression)?)),
+            Token::Tag(tag) => {
+                let (keyword, rest) = tag.split_once(' ').unwrap_or((tag, ""));
+                match keyword {
+                    "if" => nodes.push(parse_if(rest, tokens, pos)?),
+                    "for" => nodes.push(parse_for(rest, tokens, pos)?),
+                    "elif" | "else" | "endif" | "endfor" => return Ok((nodes, Some(tag.clone()))),
+                    _ => return Err(format!("unknown tag {{% {tag} %}}")),
+                }
+            }
+        }
+    }
+    Ok((nodes, None))
+}
+
+fn parse_if(condition: &str, tokens: &[Token], pos: &mut usize) -> Result<Node, String> {
+    let mut branches = vec![];
+    let mut condition = parse_condition(condition)?;
+    loop {
+        let (body, end) = parse_block(tokens, pos)?;
+        let end = end.ok_or("{% if %} isn't closed with {% endif %}")?;
+        let (keyword, rest) = end.split_once(' ').unwrap_or((&end, ""));
+        match keyword {
+            "elif" => {
+                branches.push((condition, body));
+                condition = parse_condition(rest)?;
+            }
+            "else" => {
+                branches.push((condition, body));
+                let (otherwise, end) = parse_block(tokens, pos)?;
+                return match end.as_deref() {
+                    Some("endif") => Ok(Node::If {
+                        branches,
+                        otherwise,
+                    }),
+                    _ => Err("{% else %} isn't followed by {% endif %}".into()),
+                };
+            }
+            "endif" => {
+                branches.push((condition, body));
+                return Ok(Node::If {
+                    branches,
+                    otherwise: vec![],
+                });
+            }
+            _ => return Err(format!("{{% {end} %}} can't end an {{% if %}}")),
+        }
+    }
+}
+
+fn parse_for(header: &str, tokens: &[Token], pos: &mut usize) -> Result<Node, String> {
+    let (variable, path) = match header.split_whitespace().collect::<Vec<_>>()[..] {
+        [variable, "in", path] => (variable.to_string(), parse_path(path)?),
+        _ => {
+            return Err(format!(
+                "expected {{% for item in path %}}, found {{% for {header} %}}"
+            ))
+        }
+    };
+    let (body, end) = parse_block(tokens, pos)?;
+    match end.as_deref() {
+        Some("endfor") => Ok(Node::For {
+            variable,
+            path,
+            body,
+        }),
+        _ => Err("{% for %} isn't closed with {% endfor %}".into()),
+    }
+}
+
+fn parse_condition(condition: &str) -> Result<Condition, String> {
+    let condition = condition.trim();
+    match condition.strip_prefix("not ") {
+        Some(path) => Ok(Condition {
+            negated: true,
+            path: parse_path(path)?,
+        }),
+        None => Ok(Condition {
+            negated: false,
+            path: parse_path(condition)?,
+        }),
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<String>, String> {
+    let path = path.trim();
+    let members: Vec<String> = path.split('.').map(|m| m.trim().to_string()).collect();
+    if members
+        .iter()
+        .any(|m| m.is_empty() || m.contains(char::is_whitespace))
+    {
+        return Err(format!("'{path}' isn't a path like user.name"));
+    }
+    Ok(members)
+}
+
+struct Renderer<'a> {
+    context: &'a Value,
+    config: &'a Config,
+    /// The loop variables, innermost last
+    scopes: Vec<(String, Value)>,
+    output: String,
+}
+
+impl Renderer<'_> {
+    fn render(&mut self, nodes: &[Node]) -> Result<(), String> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => self.output.push_str(text),
+                Node::Value(path) => {
+                    let value = self
+                        .lookup(path)
+                        .ok_or_else(|| format!("'{}' isn't in the context", path.join(".")))?;
+                    self.output.push_str(&value.into_string(", ", self.config));
+                }
+                Node::If {
+                    branches,
+                    otherwise,
+                } => {
+                    let branch = branches
+                        .iter()
+                        .find(|(condition, _)| self.holds(condition))
+                        .map(|(_, body)| body)
+                        .unwrap_or(otherwise);
+                    self.render(branch)?;
+                }
+                Node::For {
+                    variable,
+                    path,
+                    body,
+                } => {
+                    let items = match self.lookup(path) {
+                        Some(Value::List { vals, .. }) => vals,
+                        Some(Value::Record { cols, vals, span }) => cols
+                            .into_iter()
+                            .zip(vals)
+                            .map(|(key, value)| Value::Record {
+                                cols: vec!["key".into(), "value".into()],
+                                vals: vec![Value::string(key, span), value],
+                                span,
+                            })
+                            .collect(),
+                        Some(Value::Nothing { .. }) | None => vec![],
+                        Some(other) => vec![other],
+                    };
+                    let count = items.len();
+                    for (idx, item) in items.into_iter().enumerate() {
+                        let span = Span::unknown();
+                        let info = Value::Record {
+                            cols: vec![
+                                "index".into(),
+                                "index0".into(),
+                                "first".into(),
+                                "last".into(),
+                                "length".into(),
+                            ],
+                            vals: vec![
+                                Value::int(idx as i64 + 1, span),
+                                Value::int(idx as i64, span),
+                                Value::boolean(idx == 0, span),
+                                Value::boolean(idx + 1 == count, span),
+                                Value::int(count as i64, span),
+                            ],
+                            span,
+                        };
+                        self.scopes.push(("loop".into(), info));
+                        self.scopes.push((variable.clone(), item));
+                        let rendered = self.render(body);
+                        self.scopes.truncate(self.scopes.len() - 2);
+                        rendered?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn holds(&self, condition: &Condition) -> bool {
+        let truthy = match self.lookup(&condition.path) {
+            None | Some(Value::Nothing { .. }) => false,
+            Some(Value::Bool { val, .. }) => val,
+            Some(Value::Int { val, .. }) => val != 0,
+            Some(Value::Float { val, .. }) => val != 0.0,
+            Some(Value::String { val, .. }) => !val.is_empty(),
+            Some(Value::List { vals, .. }) => !vals.is_empty(),
+            Some(Value::Record { cols, .. }) => !cols.is_empty(),
+            Some(_) => true,
+        };
+        truthy != condition.negated
+    }
+
+    fn lookup(&self, path: &[String]) -> Option<Value> {
+        let (first, rest) = path.split_first()?;
+        let mut value = match self.scopes.iter().rev().find(|(name, _)| name == first) {
+            Some((_, value)) => value.clone(),
+            None => self.context.get_data_by_key(first)?,
+        };
+        for member in rest {
+            value = match value {
+                Value::List { vals, .. } => vals.get(member.parse::<usize>().ok()?)?.clone(),
+                other => other.get_data_by_key(member)?,
+            };
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, context: Value) -> Result<String, String> {
+        let nodes = parse(template)?;
+        let config = Config::default();
+        let mut renderer = Renderer {
+            context: &context,
+            config: &config,
+            scopes: vec![],
+            output: String::new(),
+        };
+        renderer.render(&nodes)?;
+        Ok(renderer.output)
+    }
+
+    fn record(cols: &[&str], vals: Vec<Value>) -> Value {
+        Value::Record {
+            cols: cols.iter().map(|c| c.to_string()).collect(),
+            vals,
+            span: Span::test_data(),
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn dashes_trim_whitespace() {
+        let context = record(&["xs"], vec![Value::test_string("a")]);
+        assert_eq!(
+            render(
+                "[\n  {%- for x in xs -%}\n  {{ x }}\n  {%- endfor %}\n]",
+                context
+            ),
+            Ok("[a\n]".into())
+        );
+    }
+
+    #[test]
+    fn elif_and_comments() {
+        let context = record(&["n"], vec![Value::test_int(0)]);
+        assert_eq!(
+            render(
+                "{# zero is false #}{% if n %}some{% elif not n %}none{% endif %}",
+                context
+            ),
+            Ok("none".into())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let context = record(&[], vec![]);
+        assert!(render("{{ missing }}", context.clone()).is_err());
+        assert!(render("{% if x %}", context.clone()).is_err());
+        assert!(render("{% endfor %}", context.clone()).is_err());
+        assert!(render("{{ x ", context).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+struct Arguments {
+    replacement: String,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str transliterate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str transliterate")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .vectorizes_over_list(true)
+            .named(
+                "replacement",
+                SyntaxShape::String,
+                "the text for characters without an ASCII form (default: ?)",
+                Some('r'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, transliterate strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Replace the characters of strings with their closest ASCII forms"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Accents are removed from Latin letters, Greek and Cyrillic are romanized, and typographic
+quotes, dashes and spaces become plain ones. Other characters, like those of Chinese or
+Arabic, are replaced."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["ascii", "accents", "diacritics", "romanize", "unidecode"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let replacement: Option<String> = call.get_flag(engine_state, stack, "replacement")?;
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            replacement: replacement.unwrap_or_else(|| "?".into()),
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Remove accents",
+                example: "'Crème brûlée à la Łódź' | str transliterate",
+                result: Some(Value::test_string("Creme brulee a la Lodz")),
+            },
+            Example {
+                description: "Romanize Greek and Cyrillic",
+                example: "['Ελλάδα' 'Москва'] | str transliterate",
+                result: Some(Value::List {
+                    vals: vec![Value::test_string("Ellada"), Value::test_string("Moskva")],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Drop the characters without an ASCII form",
+                example: "'“Tokyo” — 東京' | str transliterate --replacement ''",
+                result: Some(Value::test_string("\"Tokyo\" - ")),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => Value::string(transliterate(val, &args.replacement), head),
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+/// Write each character as ASCII, using `replacement` for those that have no ASCII form
+pub(super) fn transliterate(s: &str, replacement: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match ascii_form(c) {
+            Some(Ascii::Char(c)) => out.push(c),
+            Some(Ascii::Str(s)) => out.push_str(s),
+            None => out.push_str(replacement),
+        }
+    }
+    out
+}
+
+/// Write accented Latin letters as plain ones and keep every other character, to compare words
+/// the way a dictionary orders them
+pub(crate) fn fold_latin(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        let folded = match c as u32 {
+            0x0300..=0x036F | 0x00C0..=0x024F | 0x1E00..=0x1EFF => ascii_form(c),
+            _ => None,
+        };
+        match folded {
+            Some(Ascii::Char(c)) => out.push(c),
+            Some(Ascii::Str(s)) => out.push_str(s),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+enum Ascii {
+    Char(char),
+    Str(&'static str),
+}
+
+fn ascii_form(c: char) -> Option<Ascii> {
+    let from_table = |table: &[&'static str], start: u32| {
+        table
+            .get((c as u32 - start) as usize)
+            .filter(|s| !s.is_empty())
+            .map(|s| Ascii::Str(s))
+    };
+
+    match c as u32 {
+        0x00..=0x7F => Some(Ascii::Char(c)),
+        // Combining marks, as left by decomposed accents
+        0x0300..=0x036F => Some(Ascii::Str("")),
+        0x00C0..=0x024F => from_table(&LATIN, 0xC0),
+        0x1E00..=0x1EFF => from_table(&LATIN_ADDITIONAL, 0x1E00),
+        0x0370..=0x03FF => from_table(&GREEK, 0x370),
+        // The hard and soft signs have no sound of their own
+        0x042A | 0x042C | 0x044A | 0x044C => Some(Ascii::Str("")),
+        0x0400..=0x045F => from_table(&CYRILLIC, 0x400),
+        // Fullwidth forms of ASCII
+        0xFF01..=0xFF5E => char::from_u32(c as u32 - 0xFEE0).map(Ascii::Char),
+        _ => symbol(c).map(Ascii::Str),
+    }
+}
+
+fn symbol(c: char) -> Option<&'static str> {
+    Some(match c {
+        '\u{a0}' | '\u{2000}'..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}' => " ",
+        '\u{ad}' | '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' => "",
+        '‘' | '’' | '‚' | '‛' | '′' | '´' => "'",
+        '“' | '”' | '„' | '‟' | '″' => "\"",
+        '‐'..='―' | '−' => "-",
+        '…' => "...",
+        '•' | '·' => "*",
+        '«' => "<<",
+        '»' => ">>",
+        '‹' => "<",
+        '›' => ">",
+        '¡' => "!",
+        '¿' => "?",
+        '¢' => "c",
+        '£' => "GBP",
+        '¥' => "JPY",
+        '€' => "EUR",
+        '©' => "(c)",
+        '®' => "(R)",
+        '™' => "TM",
+        '°' => "deg",
+        '±' => "+/-",
+        'µ' => "u",
+        'ª' => "a",
+        'º' => "o",
+        '¹' => "1",
+        '²' => "2",
+        '³' => "3",
+        '¼' => "1/4",
+        '½' => "1/2",
+        '¾' => "3/4",
+        '→' => "->",
+        '←' => "<-",
+        '≤' => "<=",
+        '≥' => ">=",
+        '≠' => "!=",
+        _ => return None,
+    })
+}
+
+/// Latin-1 Supplement letters and Latin Extended-A and B, starting at U+00C0
+const LATIN: [&str; 400] = [
+    "A", "A", "A", "A", "A", "A", "AE", "C", "E", "E", "E", "E", "I", "I", "I", "I", "D", "N", "O",
+    "O", "O", "O", "O", "x", "O", "U", "U", "U", "U", "Y", "Th", "ss", "a", "a", "a", "a", "a",
+    "a", "ae", "c", "e", "e", "e", "e", "i", "i", "i", "i", "d", "n", "o", "o", "o", "o", "o", "/",
+    "o", "u", "u", "u", "u", "y", "th", "y", "A", "a", "A", "a", "A", "a", "C", "c", "C", "c", "C",
+    "c", "C", "c", "D", "d", "D", "d", "E", "e", "E", "e", "E", "e", "E", "e", "E", "e", "G", "g",
+    "G", "g", "G", "g", "G", "g", "H", "h", "H", "h", "I", "i", "I", "i", "I", "i", "I", "i", "I",
+    "i", "IJ", "ij", "J", "j", "K", "k", "k", "L", "l", "L", "l", "L", "l", "L", "l", "L", "l",
+    "N", "n", "N", "n", "N", "n", "'n", "N", "n", "O", "o", "O", "o", "O", "o", "OE", "oe", "R",
+    "r", "R", "r", "R", "r", "S", "s", "S", "s", "S", "s", "S", "s", "T", "t", "T", "t", "T", "t",
+    "U", "u", "U", "u", "U", "u", "U", "u", "U", "u", "U", "u", "W", "w", "Y", "y", "Y", "Z", "z",
+    "Z", "z", "Z", "z", "s", "b", "B", "", "", "", "", "", "C", "c", "", "D", "D", "d", "", "", "",
+    "", "F", "f", "G", "", "", "", "I", "K", "k", "l", "", "", "N", "n", "", "O", "o", "", "", "P",
+    "p", "", "", "", "", "", "t", "T", "t", "T", "U", "u", "", "V", "Y", "y", "Z", "z", "", "", "",
+    "", "", "", "", "", "", "", "", "", "", "DZ", "Dz", "dz", "LJ", "Lj", "lj", "NJ", "Nj", "nj",
+    "A", "a", "I", "i", "O", "o", "U", "u", "U", "u", "U", "u", "U", "u", "U", "u", "", "A", "a",
+    "A", "a", "AE", "ae", "G", "g", "G", "g", "K", "k", "O", "o", "O", "o", "", "", "j", "DZ",
+    "Dz", "dz", "G", "g", "", "", "N", "n", "A", "a", "AE", "ae", "O", "o", "A", "a", "A", "a",
+    "E", "e", "E", "e", "I", "i", "I", "i", "O", "o", "O", "o", "R", "r", "R", "r", "U", "u", "U",
+    "u", "S", "s", "T", "t", "", "", "H", "h", "", "", "", "", "Z", "z", "A", "a", "E", "e", "O",
+    "o", "O", "o", "O", "o", "O", "o", "Y", "y", "l", "n", "t", "j", "", "", "A", "C", "c", "L",
+    "T", "s", "z", "", "", "B", "U", "", "E", "e", "J", "j", "", "", "R", "r", "Y", "y",
+];
+
+/// Latin Extended Additional, starting at U+1E00
+const LATIN_ADDITIONAL: [&str; 256] = [
+    "A", "a", "B", "b", "B", "b", "B", "b", "C", "c", "D", "d", "D", "d", "D", "d", "D", "d", "D",
+    "d", "E", "e", "E", "e", "E", "e", "E", "e", "E", "e", "F", "f", "G", "g", "H", "h", "H", "h",
+    "H", "h", "H", "h", "H", "h", "I", "i", "I", "i", "K", "k", "K", "k", "K", "k", "L", "l", "L",
+    "l", "L", "l", "L", "l", "M", "m", "M", "m", "M", "m", "N", "n", "N", "n", "N", "n", "N", "n",
+    "O", "o", "O", "o", "O", "o", "O", "o", "P", "p", "P", "p", "R", "r", "R", "r", "R", "r", "R",
+    "r", "S", "s", "S", "s", "S", "s", "S", "s", "S", "s", "T", "t", "T", "t", "T", "t", "T", "t",
+    "U", "u", "U", "u", "U", "u", "U", "u", "U", "u", "V", "v", "V", "v", "W", "w", "W", "w", "W",
+    "w", "W", "w", "W", "w", "X", "x", "X", "x", "Y", "y", "Z", "z", "Z", "z", "Z", "z", "h", "t",
+    "w", "y", "a", "s", "", "", "SS", "", "A", "a", "A", "a", "A", "a", "A", "a", "A", "a", "A",
+    "a", "A", "a", "A", "a", "A", "a", "A", "a", "A", "a", "A", "a", "E", "e", "E", "e", "E", "e",
+    "E", "e", "E", "e", "E", "e", "E", "e", "E", "e", "I", "i", "I", "i", "O", "o", "O", "o", "O",
+    "o", "O", "o", "O", "o", "O", "o", "O", "o", "O", "o", "O", "o", "O", "o", "O", "o", "O", "o",
+    "U", "u", "U", "u", "U", "u", "U", "u", "U", "u", "U", "u", "U", "u", "Y", "y", "Y", "y", "Y",
+    "y", "Y", "y", "", "", "", "", "", "",
+];
+
+/// Greek and Coptic, starting at U+0370
+const GREEK: [&str; 144] = [
+    "", "", "", "", "", "", "", "", "", "", "", "", "", "", ";", "", "", "", "", "", "", "", "A",
+    "", "E", "I", "I", "", "O", "", "Y", "O", "i", "A", "V", "G", "D", "E", "Z", "I", "Th", "I",
+    "K", "L", "M", "N", "X", "O", "P", "R", "", "S", "T", "Y", "F", "Ch", "Ps", "O", "I", "Y", "a",
+    "e", "i", "i", "y", "a", "v", "g", "d", "e", "z", "i", "th", "i", "k", "l", "m", "n", "x", "o",
+    "p", "r", "s", "s", "t", "y", "f", "ch", "ps", "o", "i", "y", "o", "y", "o", "", "", "", "",
+    "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
+    "", "", "", "", "", "", "", "", "", "Th", "", "", "", "", "", "", "", "", "", "", "",
+];
+
+/// Cyrillic, starting at U+0400
+const CYRILLIC: [&str; 96] = [
+    "E", "Yo", "Dj", "Gj", "Ye", "Dz", "I", "Yi", "J", "Lj", "Nj", "C", "Kj", "I", "U", "Dz", "A",
+    "B", "V", "G", "D", "E", "Zh", "Z", "I", "Y", "K", "L", "M", "N", "O", "P", "R", "S", "T", "U",
+    "F", "Kh", "Ts", "Ch", "Sh", "Shch", "", "Y", "", "E", "Yu", "Ya", "a", "b", "v", "g", "d",
+    "e", "zh", "z", "i", "y", "k", "l", "m", "n", "o", "p", "r", "s", "t", "u", "f", "kh", "ts",
+    "ch", "sh", "shch", "", "y", "", "e", "yu", "ya", "e", "yo", "dj", "gj", "ye", "dz", "i", "yi",
+    "j", "lj", "nj", "c", "kj", "i", "u", "dz",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn transliterates() {
+        assert_eq!(
+            transliterate("Straße Ærø Œuvre", "?"),
+            "Strasse Aero OEuvre"
+        );
+        assert_eq!(transliterate("Tiếng Việt", "?"), "Tieng Viet");
+        assert_eq!(transliterate("объект Щука", "?"), "obekt Shchuka");
+        assert_eq!(transliterate("e\u{301} ＡＢＣ１", "?"), "e ABC1");
+        assert_eq!(transliterate("日本", "_"), "__");
+    }
+}
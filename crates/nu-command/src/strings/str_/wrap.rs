@@ -0,0 +1,323 @@
+use crate::input_handler::{operate, CmdArgument};
+use nu_engine::CallExt;
+use nu_protocol::{
+    ast::{Call, CellPath},
+    engine::{Command, EngineState, Stack},
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
+};
+use unicode_width::UnicodeWidthChar;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+struct Arguments {
+    width: usize,
+    preserve_words: bool,
+    cell_paths: Option<Vec<CellPath>>,
+}
+
+impl CmdArgument for Arguments {
+    fn take_cell_paths(&mut self) -> Option<Vec<CellPath>> {
+        self.cell_paths.take()
+    }
+}
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "str wrap"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("str wrap")
+            .input_output_types(vec![(Type::String, Type::String)])
+            .vectorizes_over_list(true)
+            .named(
+                "width",
+                SyntaxShape::Int,
+                "the number of columns to fit the lines in (default 80)",
+                Some('w'),
+            )
+            .switch(
+                "preserve-words",
+                "break lines between words only, letting longer words overflow",
+                Some('p'),
+            )
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "For a data structure input, wrap strings at the given cell paths",
+            )
+            .category(Category::Strings)
+    }
+
+    fn usage(&self) -> &str {
+        "Break the lines of strings so they fit in a number of columns"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Widths are counted in terminal columns, so wide characters take two and ANSI escape
+sequences take none. Existing line breaks are kept. With --preserve-words, the whitespace at a
+break is removed."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["fold", "fmt", "line", "break", "width"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let width: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "width")?;
+        let width = match width {
+            Some(width) if width.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue(width.span))
+            }
+            Some(width) => width.item as usize,
+            None => 80,
+        };
+        let cell_paths: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
+        let cell_paths = (!cell_paths.is_empty()).then_some(cell_paths);
+        let args = Arguments {
+            width,
+            preserve_words: call.has_flag("preserve-words"),
+            cell_paths,
+        };
+        operate(action, args, input, call.head, engine_state.ctrlc.clone())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Break a string every 5 columns",
+                example: "'hello world' | str wrap -w 5",
+                result: Some(Value::test_string("hello\n worl\nd")),
+            },
+            Example {
+                description: "Break a string between words",
+                example: "'the quick brown fox' | str wrap -w 10 --preserve-words",
+                result: Some(Value::test_string("the quick\nbrown fox")),
+            },
+        ]
+    }
+}
+
+fn action(input: &Value, args: &Arguments, head: Span) -> Value {
+    match input {
+        Value::String { val, .. } => {
+            let lines: Vec<String> = val
+                .split('\n')
+                .flat_map(|line| {
+                    if args.preserve_words {
+                        wrap_words(line, args.width)
+                    } else {
+                        wrap_columns(line, args.width)
+                    }
+                })
+                .collect();
+            Value::string(lines.join("\n"), head)
+        }
+        Value::Error { .. } => input.clone(),
+        _ => Value::Error {
+            error: ShellError::OnlySupportsThisInputType(
+                "string".into(),
+                input.get_type().to_string(),
+                head,
+                input.expect_span(),
+            ),
+        },
+    }
+}
+
+/// A visible character along with the ANSI escape sequences before it
+#[derive(Clone)]
+pub(super) struct Cell {
+    pub(super) escapes: String,
+    pub(super) ch: Option<char>,
+}
+
+impl Cell {
+    pub(super) fn width(&self) -> usize {
+        self.ch.and_then(|c| c.width()).unwrap_or(0)
+    }
+
+    pub(super) fn is_space(&self) -> bool {
+        self.ch.map_or(false, |c| c == ' ' || c == '\t')
+    }
+
+    pub(super) fn push_to(&self, s: &mut String) {
+        s.push_str(&self.escapes);
+        if let Some(c) = self.ch {
+            s.push(c);
+        }
+    }
+}
+
+/// Split a line into cells, the escape sequences at its end make a cell without a character
+pub(super) fn cells(line: &str) -> Vec<Cell> {
+    let mut cells = vec![];
+    let mut escapes = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            cells.push(Cell {
+                escapes: std::mem::take(&mut escapes),
+                ch: Some(c),
+            });
+            continue;
+        }
+
+        escapes.push(c);
+        match chars.next() {
+            // CSI sequences, like colors, end with a byte in @..~
+            Some('[') => {
+                escapes.push('[');
+                for c in chars.by_ref() {
+                    escapes.push(c);
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC sequences, like links, end with BEL or ESC \
+            Some(']') => {
+                escapes.push(']');
+                while let Some(c) = chars.next() {
+                    escapes.push(c);
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        escapes.push('\\');
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(c) => escapes.push(c),
+            None => {}
+        }
+    }
+    if !escapes.is_empty() {
+        cells.push(Cell { escapes, ch: None });
+    }
+    cells
+}
+
+/// Group cells into runs of whitespace and runs of everything else
+pub(super) fn tokens(cells: Vec<Cell>) -> Vec<(bool, Vec<Cell>)> {
+    let mut tokens: Vec<(bool, Vec<Cell>)> = vec![];
+    for cell in cells {
+        match tokens.last_mut() {
+            // Trailing escape sequences stay with whatever comes before them
+            Some((is_space, run)) if cell.ch.is_none() || *is_space == cell.is_space() => {
+                run.push(cell)
+            }
+            _ => tokens.push((cell.is_space(), vec![cell])),
+        }
+    }
+    tokens
+}
+
+pub(super) fn render(cells: &[Cell]) -> String {
+    let mut s = String::new();
+    for cell in cells {
+        cell.push_to(&mut s);
+    }
+    s
+}
+
+pub(super) fn escapes_of(cells: &[Cell]) -> String {
+    cells.iter().map(|cell| cell.escapes.as_str()).collect()
+}
+
+fn wrap_columns(line: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut current = String::new();
+    let mut current_width = 0;
+    for cell in cells(line) {
+        if current_width > 0 && current_width + cell.width() > width {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        cell.push_to(&mut current);
+        current_width += cell.width();
+    }
+    lines.push(current);
+    lines
+}
+
+fn wrap_words(line: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut current = String::new();
+    let mut current_width = 0;
+    let mut space: Option<Vec<Cell>> = None;
+    for (is_space, run) in tokens(cells(line)) {
+        if is_space {
+            // Indentation at the start is kept
+            if lines.is_empty() && current.is_empty() {
+                current_width = run.iter().map(Cell::width).sum();
+                current = render(&run);
+            } else {
+                space = Some(run);
+            }
+            continue;
+        }
+
+        let word_width: usize = run.iter().map(Cell::width).sum();
+        let space_width: usize = space.iter().flatten().map(Cell::width).sum();
+        if current_width == 0 || current_width + space_width + word_width <= width {
+            if let Some(space) = space.take() {
+                current.push_str(&render(&space));
+            }
+            current_width += space_width + word_width;
+        } else {
+            // Only the escape sequences of the whitespace at a break are kept
+            if let Some(space) = space.take() {
+                current.push_str(&escapes_of(&space));
+            }
+            lines.push(std::mem::take(&mut current));
+            current_width = word_width;
+        }
+        current.push_str(&render(&run));
+    }
+    if let Some(space) = space {
+        current.push_str(&escapes_of(&space));
+    }
+    lines.push(current);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+
+    #[test]
+    fn escapes_take_no_columns() {
+        let line = "\x1b[31mred\x1b[0m blue";
+        assert_eq!(
+            wrap_words(line, 3),
+            vec!["\x1b[31mred\x1b[0m".to_string(), "blue".to_string()]
+        );
+        assert_eq!(
+            wrap_columns("\x1b[1mab\x1b[0m", 1),
+            vec!["\x1b[1ma", "b\x1b[0m"]
+        );
+    }
+
+    #[test]
+    fn wide_characters_take_two_columns() {
+        assert_eq!(wrap_columns("日本語", 4), vec!["日本", "語"]);
+    }
+}
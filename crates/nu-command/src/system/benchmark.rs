@@ -1,11 +1,12 @@
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
 use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Closure, Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, SyntaxShape, Type,
-    Value,
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -20,6 +21,16 @@ impl Command for Benchmark {
         "Time the running time of a closure"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"With --runs, --warmup or --per-run, the closure is run several times and a record with the
+number of runs and the min, max, mean and standard deviation of their durations is returned.
+Warmup runs are not timed."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["timeit", "time", "measure", "performance", "hyperfine"]
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("benchmark")
             .required(
@@ -27,9 +38,28 @@ impl Command for Benchmark {
                 SyntaxShape::Closure(Some(vec![SyntaxShape::Any])),
                 "the closure to run",
             )
+            .named(
+                "runs",
+                SyntaxShape::Int,
+                "number of timed runs of the closure",
+                Some('n'),
+            )
+            .named(
+                "warmup",
+                SyntaxShape::Int,
+                "number of untimed runs before the timed ones",
+                Some('w'),
+            )
+            .switch(
+                "per-run",
+                "include a table with the duration of every run",
+                Some('p'),
+            )
             .input_output_types(vec![
                 (Type::Any, Type::Duration),
                 (Type::Nothing, Type::Duration),
+                (Type::Any, Type::Record(vec![])),
+                (Type::Nothing, Type::Record(vec![])),
             ])
             .allow_variants_without_examples(true)
             .category(Category::System)
@@ -43,11 +73,28 @@ impl Command for Benchmark {
         input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let capture_block: Closure = call.req(engine_state, stack, 0)?;
+        let runs: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "runs")?;
+        let warmup: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "warmup")?;
+        let per_run = call.has_flag("per-run");
         let block = engine_state.get_block(capture_block.block_id);
 
         let redirect_stdout = call.redirect_stdout;
         let redirect_stderr = call.redirect_stderr;
 
+        let summarize = runs.is_some() || warmup.is_some() || per_run;
+        let runs = match runs {
+            Some(runs) if runs.item < 1 => return Err(ShellError::NeedsPositiveValue(runs.span)),
+            Some(runs) => runs.item as usize,
+            None => 1,
+        };
+        let warmup = match warmup {
+            Some(warmup) if warmup.item < 0 => {
+                return Err(ShellError::NeedsPositiveValue(warmup.span))
+            }
+            Some(warmup) => warmup.item as usize,
+            None => 0,
+        };
+
         let mut stack = stack.captures_to_stack(&capture_block.captures);
 
         // In order to provide the pipeline as a positional, it must be converted into a value.
@@ -63,23 +110,44 @@ impl Command for Benchmark {
             }
         }
 
-        // Get the start time after all other computation has been done.
-        let start_time = Instant::now();
-        eval_block(
-            engine_state,
-            &mut stack,
-            block,
-            input_val.into_pipeline_data_with_metadata(input_metadata),
-            redirect_stdout,
-            redirect_stderr,
-        )?
-        .into_value(call.head);
-
-        let end_time = Instant::now();
-
-        let output = Value::Duration {
-            val: (end_time - start_time).as_nanos() as i64,
-            span: call.head,
+        let mut durations = Vec::with_capacity(runs);
+        for run in 0..warmup + runs {
+            if let Some(ctrlc) = &engine_state.ctrlc {
+                if ctrlc.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+
+            let input = input_val
+                .clone()
+                .into_pipeline_data_with_metadata(input_metadata.clone());
+
+            // Get the start time after all other computation has been done.
+            let start_time = Instant::now();
+            eval_block(
+                engine_state,
+                &mut stack,
+                block,
+                input,
+                redirect_stdout,
+                redirect_stderr,
+            )?
+            .into_value(call.head);
+
+            let end_time = Instant::now();
+
+            if run >= warmup {
+                durations.push((end_time - start_time).as_nanos() as i64);
+            }
+        }
+
+        let output = if summarize {
+            summary(&durations, per_run, call.head)
+        } else {
+            Value::Duration {
+                val: durations.first().copied().unwrap_or_default(),
+                span: call.head,
+            }
         };
 
         Ok(output.into_pipeline_data())
@@ -97,10 +165,72 @@ impl Command for Benchmark {
                 example: "fetch https://www.nushell.sh/book/ | benchmark { split chars }",
                 result: None,
             },
+            Example {
+                description:
+                    "Get the min, max, mean and standard deviation of 10 runs, after 2 warmup runs",
+                example: "benchmark --runs 10 --warmup 2 { ls | sort-by size }",
+                result: None,
+            },
+            Example {
+                description: "Compare the duration of every run of an external command",
+                example: "benchmark --runs 5 --per-run { ^git status } | get per_run",
+                result: None,
+            },
         ]
     }
 }
 
+fn summary(durations: &[i64], per_run: bool, span: Span) -> Value {
+    let duration = |val: i64| Value::Duration { val, span };
+
+    let count = durations.len();
+    let min = durations.iter().copied().min().unwrap_or_default();
+    let max = durations.iter().copied().max().unwrap_or_default();
+    let (mean, stddev) = if count == 0 {
+        (0.0, 0.0)
+    } else {
+        let mean = durations.iter().map(|d| *d as f64).sum::<f64>() / count as f64;
+        let variance = durations
+            .iter()
+            .map(|d| (*d as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        (mean, variance.sqrt())
+    };
+
+    let mut cols = vec![
+        "runs".to_string(),
+        "min".to_string(),
+        "max".to_string(),
+        "mean".to_string(),
+        "stddev".to_string(),
+    ];
+    let mut vals = vec![
+        Value::int(count as i64, span),
+        duration(min),
+        duration(max),
+        duration(mean.round() as i64),
+        duration(stddev.round() as i64),
+    ];
+
+    if per_run {
+        let runs = durations
+            .iter()
+            .enumerate()
+            .map(|(index, val)| Value::Record {
+                cols: vec!["run".to_string(), "duration".to_string()],
+                vals: vec![Value::int(index as i64 + 1, span), duration(*val)],
+                span,
+            })
+            .collect();
+
+        cols.push("per_run".to_string());
+        vals.push(Value::List { vals: runs, span });
+    }
+
+    Value::Record { cols, vals, span }
+}
+
 #[test]
 // Due to difficulty in observing side-effects from benchmark closures,
 // checks that the closures have run correctly must use the filesystem.
@@ -130,3 +260,35 @@ fn test_benchmark_closure_2() {
         assert_eq!(actual_repl.out, "{result: [2, 3, 4]}");
     });
 }
+
+#[test]
+fn test_benchmark_runs_and_warmup() {
+    use nu_test_support::{nu, nu_repl_code, playground::Playground};
+    Playground::setup("test_benchmark_runs_and_warmup", |dirs, _| {
+        let inp = [
+            r#"let stats = (benchmark --runs 3 --warmup 2 { 'x' | save -a foo.txt })"#,
+            r#"[$stats.runs (open foo.txt | str length) ($stats | columns | str join ',')] | to nuon"#,
+        ];
+        let actual_repl = nu!(cwd: dirs.test(), nu_repl_code(&inp));
+        assert_eq!(actual_repl.err, "");
+        assert_eq!(actual_repl.out, "[3, 5, \"runs,min,max,mean,stddev\"]");
+    });
+}
+
+#[test]
+fn test_benchmark_per_run() {
+    use nu_test_support::nu;
+    let actual = nu!(
+        cwd: ".",
+        "benchmark --runs 4 --per-run { 1 } | get per_run.run | to nuon"
+    );
+    assert_eq!(actual.err, "");
+    assert_eq!(actual.out, "[1, 2, 3, 4]");
+}
+
+#[test]
+fn test_benchmark_needs_positive_runs() {
+    use nu_test_support::nu;
+    let actual = nu!(cwd: ".", "benchmark --runs 0 { 1 }");
+    assert!(actual.err.contains("use a positive value"));
+}
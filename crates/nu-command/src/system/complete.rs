@@ -1,7 +1,7 @@
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Type, Value,
 };
 
 use std::thread;
@@ -25,7 +25,14 @@ impl Command for Complete {
     }
 
     fn extra_usage(&self) -> &str {
-        r#"In order to capture stdout, stderr, and exit_code, externally piped in commands need to be wrapped with `do`"#
+        r#"In order to capture stdout, stderr, and exit_code, externally piped in commands need to be wrapped with `do`
+
+The status column describes how the command ended. On Unix, a command killed by a signal has
+minus the signal number as exit_code, and the signal and core_dumped columns tell them apart
+from a normal exit. On Windows, crashes are shown with the name of their NTSTATUS code.
+
+The duration, max_rss, user_time and system_time columns give the wall-clock time of the
+command, its peak memory usage, and the CPU time it spent in user and kernel mode."#
     }
 
     fn run(
@@ -40,6 +47,7 @@ impl Command for Complete {
                 stdout,
                 stderr,
                 exit_code,
+                resource_usage,
                 ..
             } => {
                 let mut cols = vec![];
@@ -108,12 +116,37 @@ impl Command for Complete {
                 if let Some(exit_code) = exit_code {
                     let mut v: Vec<_> = exit_code.collect();
 
-                    if let Some(v) = v.pop() {
-                        cols.push("exit_code".to_string());
-                        vals.push(v);
+                    // A core dump is reported as an error right after the exit code
+                    let core_dumped = v.len() > 1 && matches!(v.last(), Some(Value::Error { .. }));
+                    if core_dumped {
+                        v.pop();
+                    }
+
+                    match v.pop() {
+                        Some(Value::Int { val, span }) => {
+                            cols.push("exit_code".to_string());
+                            vals.push(Value::Int { val, span });
+                            add_exit_status(&mut cols, &mut vals, val, core_dumped, span);
+                        }
+                        Some(v) => {
+                            cols.push("exit_code".to_string());
+                            vals.push(v);
+                        }
+                        None => {}
                     }
                 }
 
+                // The resources used are sent once the command exits, before its exit code
+                if let Some(Ok(Value::Record {
+                    cols: usage_cols,
+                    vals: usage_vals,
+                    ..
+                })) = resource_usage.map(|usage| usage.recv())
+                {
+                    cols.extend(usage_cols);
+                    vals.extend(usage_vals);
+                }
+
                 Ok(Value::Record {
                     cols,
                     vals,
@@ -145,6 +178,130 @@ impl Command for Complete {
                 example: "do { ^external arg1 } | complete",
                 result: None,
             },
+            Example {
+                description: "Measure the time and memory an external command takes",
+                example:
+                    "^external arg1 | complete | select duration max_rss user_time system_time",
+                result: None,
+            },
+            Example {
+                description: "Check whether an external command was killed",
+                example: "^external arg1 | complete | if $in.signal == 9 { 'killed' }",
+                result: None,
+            },
         ]
     }
 }
+
+#[cfg(unix)]
+fn add_exit_status(
+    cols: &mut Vec<String>,
+    vals: &mut Vec<Value>,
+    exit_code: i64,
+    core_dumped: bool,
+    span: Span,
+) {
+    let (signal, status) = if exit_code < 0 {
+        let signal = -exit_code;
+        let mut status = match signal_name(signal) {
+            Some(name) => format!("killed by signal {name}"),
+            None => format!("killed by signal {signal}"),
+        };
+        if core_dumped {
+            status.push_str(" (core dumped)");
+        }
+        (Value::int(signal, span), status)
+    } else {
+        (
+            Value::nothing(span),
+            format!("exited with code {exit_code}"),
+        )
+    };
+
+    cols.push("signal".to_string());
+    vals.push(signal);
+    cols.push("core_dumped".to_string());
+    vals.push(Value::boolean(core_dumped, span));
+    cols.push("status".to_string());
+    vals.push(Value::string(status, span));
+}
+
+#[cfg(unix)]
+fn signal_name(signal: i64) -> Option<&'static str> {
+    let name = match signal as libc::c_int {
+        libc::SIGHUP => "SIGHUP",
+        libc::SIGINT => "SIGINT",
+        libc::SIGQUIT => "SIGQUIT",
+        libc::SIGILL => "SIGILL",
+        libc::SIGTRAP => "SIGTRAP",
+        libc::SIGABRT => "SIGABRT",
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGFPE => "SIGFPE",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGUSR1 => "SIGUSR1",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGUSR2 => "SIGUSR2",
+        libc::SIGPIPE => "SIGPIPE",
+        libc::SIGALRM => "SIGALRM",
+        libc::SIGTERM => "SIGTERM",
+        libc::SIGCHLD => "SIGCHLD",
+        libc::SIGCONT => "SIGCONT",
+        libc::SIGSTOP => "SIGSTOP",
+        libc::SIGTSTP => "SIGTSTP",
+        libc::SIGTTIN => "SIGTTIN",
+        libc::SIGTTOU => "SIGTTOU",
+        libc::SIGXCPU => "SIGXCPU",
+        libc::SIGXFSZ => "SIGXFSZ",
+        libc::SIGSYS => "SIGSYS",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(windows)]
+fn add_exit_status(
+    cols: &mut Vec<String>,
+    vals: &mut Vec<Value>,
+    exit_code: i64,
+    _core_dumped: bool,
+    span: Span,
+) {
+    // Crashes exit with an NTSTATUS error code, whose two highest bits are set
+    let status = match ntstatus_name(exit_code as u32) {
+        Some(name) => format!("{name} (0x{:08X})", exit_code as u32),
+        None => format!("exited with code {exit_code}"),
+    };
+
+    cols.push("status".to_string());
+    vals.push(Value::string(status, span));
+}
+
+#[cfg(windows)]
+fn ntstatus_name(code: u32) -> Option<&'static str> {
+    let name = match code {
+        0x8000_0003 => "STATUS_BREAKPOINT",
+        0xC000_0005 => "STATUS_ACCESS_VIOLATION",
+        0xC000_001D => "STATUS_ILLEGAL_INSTRUCTION",
+        0xC000_0094 => "STATUS_INTEGER_DIVIDE_BY_ZERO",
+        0xC000_0095 => "STATUS_INTEGER_OVERFLOW",
+        0xC000_00FD => "STATUS_STACK_OVERFLOW",
+        0xC000_0135 => "STATUS_DLL_NOT_FOUND",
+        0xC000_0139 => "STATUS_ENTRYPOINT_NOT_FOUND",
+        0xC000_013A => "STATUS_CONTROL_C_EXIT",
+        0xC000_0142 => "STATUS_DLL_INIT_FAILED",
+        0xC000_0409 => "STATUS_STACK_BUFFER_OVERRUN",
+        0xC000_0374 => "STATUS_HEAP_CORRUPTION",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn add_exit_status(
+    _cols: &mut Vec<String>,
+    _vals: &mut Vec<Value>,
+    _exit_code: i64,
+    _core_dumped: bool,
+    _span: Span,
+) {
+}
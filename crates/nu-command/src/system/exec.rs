@@ -2,7 +2,7 @@ use super::run_external::create_external_command;
 use nu_engine::{current_dir, CallExt};
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type,
 };
 use std::os::unix::process::CommandExt;
@@ -27,6 +27,10 @@ impl Command for Exec {
         "Execute a command, replacing the current process."
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
     fn extra_usage(&self) -> &str {
         "Currently supported only on Unix-based systems."
     }
@@ -0,0 +1,254 @@
+use nu_engine::{env_to_strings, CallExt};
+use nu_protocol::{
+    ast::Call,
+    engine::{Capability, Command, EngineState, Stack},
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Type, Value,
+};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::process::Command as CommandSys;
+use std::sync::atomic::Ordering;
+
+// Keeps every command line well under the limits of the platform
+#[cfg(windows)]
+const MAX_ARGS_LENGTH: usize = 30_000;
+#[cfg(not(windows))]
+const MAX_ARGS_LENGTH: usize = 100_000;
+
+#[derive(Clone)]
+pub struct ExecBatch;
+
+impl Command for ExecBatch {
+    fn name(&self) -> &str {
+        "exec-batch"
+    }
+
+    fn usage(&self) -> &str {
+        "Run an external command with the input values as its arguments, in batches."
+    }
+
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Like xargs, the values are added after the given arguments, and the command runs as many
+times as needed to keep its command line short enough. Every value is passed as a single
+argument, so spaces and quotes in them need no escaping. Arguments starting with a dash need
+to be quoted, so they are not taken as flags of exec-batch.
+
+Returns one row per run, in the order of the input, with the arguments, exit code, stdout
+and stderr of the run."#
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .input_output_types(vec![(Type::List(Box::new(Type::Any)), Type::Table(vec![]))])
+            .allow_variants_without_examples(true)
+            .required("command", SyntaxShape::String, "external command to run")
+            .rest(
+                "args",
+                SyntaxShape::String,
+                "arguments to give before the input values",
+            )
+            .named(
+                "max-args",
+                SyntaxShape::Int,
+                "most input values to give to a single run",
+                Some('n'),
+            )
+            .named(
+                "parallel",
+                SyntaxShape::Int,
+                "how many runs can happen at once (default 1)",
+                Some('p'),
+            )
+            .category(Category::System)
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["xargs", "each", "parallel", "arguments"]
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Optimize images, 50 at a time with 4 runs at once",
+                example: "ls *.png | get name | exec-batch optipng --max-args 50 --parallel 4",
+                result: None,
+            },
+            Example {
+                description: "Give arguments before the input values",
+                example: r#"[a.txt 'with space.txt'] | exec-batch wc "-l" | get stdout"#,
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let command: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let args: Vec<String> = call.rest(engine_state, stack, 1)?;
+        let max_args: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "max-args")?;
+        let parallel: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "parallel")?;
+
+        let max_args = match max_args {
+            Some(max_args) if max_args.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue(max_args.span))
+            }
+            Some(max_args) => max_args.item as usize,
+            None => usize::MAX,
+        };
+        let parallel = match parallel {
+            Some(parallel) if parallel.item < 1 => {
+                return Err(ShellError::NeedsPositiveValue(parallel.span))
+            }
+            Some(parallel) => parallel.item as usize,
+            None => 1,
+        };
+
+        // `exec-batch ^cmd` reads better, but the caret is not part of the name
+        let command = Spanned {
+            item: command.item.trim_start_matches('^').to_string(),
+            span: command.span,
+        };
+
+        let values = input
+            .into_iter()
+            .map(|value| value.as_string())
+            .collect::<Result<Vec<String>, ShellError>>()?;
+        let batches = batches(values, max_args);
+
+        let env_vars = env_to_strings(engine_state, stack)?;
+        let ctrlc = engine_state.ctrlc.clone();
+        let head = call.head;
+
+        let run = |batch: &Vec<String>| {
+            if let Some(ctrlc) = &ctrlc {
+                if ctrlc.load(Ordering::SeqCst) {
+                    return Ok(Value::nothing(head));
+                }
+            }
+            run_batch(&command, &args, batch, &env_vars, head)
+        };
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(parallel)
+            .build()
+            .map_err(|e| {
+                ShellError::GenericError(
+                    "Could not start the runs".into(),
+                    e.to_string(),
+                    Some(head),
+                    None,
+                    Vec::new(),
+                )
+            })?;
+        let vals = pool.install(|| {
+            batches
+                .par_iter()
+                .map(run)
+                .collect::<Result<Vec<Value>, ShellError>>()
+        })?;
+
+        Ok(Value::List { vals, span: head }.into_pipeline_data())
+    }
+}
+
+// Splits the values into runs of at most max_args values, and at most MAX_ARGS_LENGTH bytes
+fn batches(values: Vec<String>, max_args: usize) -> Vec<Vec<String>> {
+    let mut batches = vec![];
+    let mut batch: Vec<String> = vec![];
+    let mut length = 0;
+
+    for value in values {
+        if !batch.is_empty() && (batch.len() == max_args || length + value.len() > MAX_ARGS_LENGTH)
+        {
+            batches.push(std::mem::take(&mut batch));
+            length = 0;
+        }
+        length += value.len() + 1;
+        batch.push(value);
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    batches
+}
+
+fn run_batch(
+    command: &Spanned<String>,
+    args: &[String],
+    batch: &[String],
+    env_vars: &HashMap<String, String>,
+    span: Span,
+) -> Result<Value, ShellError> {
+    let mut process = CommandSys::new(&command.item);
+    process.args(args).args(batch).envs(env_vars);
+    if let Some(cwd) = env_vars.get("PWD") {
+        process.current_dir(cwd);
+    }
+
+    let output = process.output().map_err(|e| {
+        ShellError::ExternalCommand(
+            format!("can't run '{}'", command.item),
+            e.to_string(),
+            command.span,
+        )
+    })?;
+
+    Ok(Value::Record {
+        cols: vec![
+            "args".into(),
+            "exit_code".into(),
+            "stdout".into(),
+            "stderr".into(),
+        ],
+        vals: vec![
+            Value::List {
+                vals: batch.iter().map(|arg| Value::string(arg, span)).collect(),
+                span,
+            },
+            Value::int(output.status.code().unwrap_or(-1) as i64, span),
+            Value::string(String::from_utf8_lossy(&output.stdout), span),
+            Value::string(String::from_utf8_lossy(&output.stderr), span),
+        ],
+        span,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(ExecBatch {})
+    }
+
+    #[test]
+    fn test_batches_split_on_max_args() {
+        let values = ["a", "b", "c"].iter().map(|v| v.to_string()).collect();
+
+        assert_eq!(batches(values, 2), vec![vec!["a", "b"], vec!["c"]]);
+    }
+
+    #[test]
+    fn test_batches_split_on_length() {
+        // Two of them and their separators fit in a single run
+        let long = "x".repeat(MAX_ARGS_LENGTH / 2 - 1);
+        let values = vec![long.clone(), long.clone(), long];
+
+        assert_eq!(batches(values, usize::MAX).len(), 2);
+    }
+}
@@ -2,6 +2,7 @@ mod benchmark;
 mod complete;
 #[cfg(unix)]
 mod exec;
+mod exec_batch;
 mod explain;
 mod nu_check;
 #[cfg(any(
@@ -21,6 +22,7 @@ pub use benchmark::Benchmark;
 pub use complete::Complete;
 #[cfg(unix)]
 pub use exec::Exec;
+pub use exec_batch::ExecBatch;
 pub use explain::Explain;
 pub use nu_check::NuCheck;
 #[cfg(any(
@@ -32,6 +34,6 @@ pub use nu_check::NuCheck;
 pub use ps::Ps;
 #[cfg(windows)]
 pub use registry_query::RegistryQuery;
-pub use run_external::{External, ExternalCommand};
+pub use run_external::{External, ExternalCommand, OutputEncoding};
 pub use sys::Sys;
 pub use which_::Which;
@@ -1,3 +1,4 @@
+use encoding_rs::{Decoder, Encoding};
 use fancy_regex::Regex;
 use itertools::Itertools;
 use nu_engine::env_to_strings;
@@ -5,7 +6,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, Expr, Expression},
     did_you_mean,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, ListStream, PipelineData, RawStream, ShellError, Signature, Span, Spanned,
     SyntaxShape, Type, Value,
 };
@@ -14,14 +15,17 @@ use pathdiff::diff_paths;
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command as CommandSys, Stdio};
+use std::process::{Child, Command as CommandSys, ExitStatus, Stdio};
 use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{self, SyncSender};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Instant;
 
 const OUTPUT_BUFFER_SIZE: usize = 1024;
 const OUTPUT_BUFFERS_IN_FLIGHT: usize = 3;
+// How many rotated log files are kept besides the current one
+const LOG_FILES_KEPT: usize = 5;
 
 #[derive(Clone)]
 pub struct External;
@@ -35,17 +39,102 @@ impl Command for External {
         "Runs external command"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::External]
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build(self.name())
             .input_output_types(vec![(Type::Any, Type::Any)])
             .switch("redirect-stdout", "redirect stdout to the pipeline", None)
             .switch("redirect-stderr", "redirect stderr to the pipeline", None)
             .switch("trim-end-newline", "trimming end newlines", None)
+            .named(
+                "stdin-from",
+                SyntaxShape::Filepath,
+                "file to use as the stdin of the external command",
+                None,
+            )
+            .named(
+                "env",
+                SyntaxShape::Record,
+                "environment variables to set for this call only",
+                None,
+            )
+            .named(
+                "path-prepend",
+                SyntaxShape::List(Box::new(SyntaxShape::Directory)),
+                "directories to add before the PATH of this call",
+                None,
+            )
+            .named(
+                "path-append",
+                SyntaxShape::List(Box::new(SyntaxShape::Directory)),
+                "directories to add after the PATH of this call",
+                None,
+            )
+            .named(
+                "stdout-log",
+                SyntaxShape::Filepath,
+                "file to append the stdout of the external command to, while it is streamed",
+                None,
+            )
+            .named(
+                "stderr-log",
+                SyntaxShape::Filepath,
+                "file to append the stderr of the external command to, while it is streamed",
+                None,
+            )
+            .named(
+                "log-max-size",
+                SyntaxShape::Filesize,
+                "rotate the log files once they would grow past this size",
+                None,
+            )
+            .named(
+                "output-encoding",
+                SyntaxShape::String,
+                "encoding of the output of the external command, e.g. cp949, shift_jis, utf-16, auto or raw",
+                None,
+            )
+            .switch(
+                "close-stdin",
+                "give the external command an empty stdin, instead of the terminal",
+                None,
+            )
             .required("command", SyntaxShape::Any, "external command to run")
             .rest("args", SyntaxShape::Any, "arguments for external command")
             .category(Category::System)
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"The input of the pipeline is written to the stdin of the external command. Strings and
+binary data are written as they are, other values are rendered as a table first. Without
+input, the external command reads from the terminal, unless --stdin-from or --close-stdin
+is given.
+
+Environment variables given with --env, and directories given with --path-prepend and
+--path-append, only apply to this call. Lists in --env are joined like PATH. For a single
+variable, the `FOO=bar ^cmd` shorthand does the same.
+
+With --stdout-log and --stderr-log, the output is appended to a file as it arrives, and still
+shown or passed down the pipeline. The external command then writes to a pipe rather than to
+the terminal, so it may turn off colors or progress bars. With --log-max-size, a log file that
+is full is renamed to `<file>.1`, the older ones to `<file>.2` and so on, and a new one is
+started. The last 5 rotated files are kept. Both logs can be the same file, which is then
+shared. A log that can't be written anymore is reported once the command is done.
+
+With --output-encoding, the output of the external command is converted from the given
+encoding, rather than read as UTF-8. The output, and its logs, are then proper text even when
+they are shown on the terminal. A byte order mark in the output takes precedence.
+
+With `--output-encoding auto`, which is the default on Windows, output that is piped or logged
+is converted when it starts with a byte order mark or looks like UTF-16. Otherwise, text that
+isn't UTF-8 is converted from the code page of the console, like the output of `ipconfig`.
+Binary output is left as it is. `--output-encoding raw`, the default elsewhere, never converts
+the output."#
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -81,6 +170,37 @@ impl Command for External {
                 example: r#"run-external --redirect-stdout "echo" "-n" "hello" | split chars"#,
                 result: None,
             },
+            Example {
+                description: "Run an external command with extra environment variables",
+                example: r#"run-external --env {RUST_LOG: debug} "cargo" "run""#,
+                result: None,
+            },
+            Example {
+                description: "Run an external command with a local bin directory in its PATH",
+                example: r#"run-external --path-prepend [./node_modules/.bin] "eslint" ".""#,
+                result: None,
+            },
+            Example {
+                description: "Feed a file to the stdin of an external command",
+                example: r#"run-external --stdin-from data.txt "wc" "-l""#,
+                result: None,
+            },
+            Example {
+                description: "Watch a build while keeping its output in rotated log files",
+                example: r#"run-external --stdout-log build.log --stderr-log build.log --log-max-size 10mb "cargo" "build""#,
+                result: None,
+            },
+            Example {
+                description:
+                    "Read the output of a tool that writes in the Windows code page for Korean",
+                example: r#"run-external --redirect-stdout --output-encoding cp949 "ipconfig" | lines"#,
+                result: None,
+            },
+            Example {
+                description: "Run an external command that would otherwise wait for the terminal",
+                example: r#"run-external --close-stdin "cat""#,
+                result: None,
+            },
         ]
     }
 }
@@ -96,9 +216,51 @@ pub fn create_external_command(
 ) -> Result<ExternalCommand, ShellError> {
     let name: Spanned<String> = call.req(engine_state, stack, 0)?;
     let args: Vec<Value> = call.rest(engine_state, stack, 1)?;
+    let stdin_from: Option<Spanned<String>> = call.get_flag(engine_state, stack, "stdin-from")?;
+    let close_stdin = call.has_flag("close-stdin");
+    let stdout_log: Option<Spanned<String>> = call.get_flag(engine_state, stack, "stdout-log")?;
+    let stderr_log: Option<Spanned<String>> = call.get_flag(engine_state, stack, "stderr-log")?;
+    let log_max_size = match call.get_flag::<Spanned<i64>>(engine_state, stack, "log-max-size")? {
+        Some(size) if size.item <= 0 => return Err(ShellError::NeedsPositiveValue(size.span)),
+        size => size.map(|size| size.item as u64),
+    };
+    let output_encoding: Option<Spanned<String>> =
+        call.get_flag(engine_state, stack, "output-encoding")?;
+    let output_encoding = match output_encoding {
+        Some(encoding) if encoding.item == "auto" => OutputEncoding::Auto,
+        Some(encoding) if encoding.item == "raw" => OutputEncoding::Raw,
+        Some(encoding) => {
+            OutputEncoding::Decode(crate::parse_encoding(encoding.span, &encoding.item)?)
+        }
+        None => OutputEncoding::default(),
+    };
+
+    if let (Some(stdin_from), true) = (&stdin_from, close_stdin) {
+        return Err(ShellError::IncompatibleParametersSingle(
+            "--stdin-from can't be used together with --close-stdin".into(),
+            stdin_from.span,
+        ));
+    }
 
     // Translate environment variables from Values to Strings
-    let env_vars_str = env_to_strings(engine_state, stack)?;
+    let mut env_vars_str = env_to_strings(engine_state, stack)?;
+
+    let env: Option<Value> = call.get_flag(engine_state, stack, "env")?;
+    if let Some(Value::Record { cols, vals, .. }) = env {
+        for (name, value) in cols.into_iter().zip(vals) {
+            env_vars_str.insert(name, env_value_to_string(&value)?);
+        }
+    }
+
+    let path_prepend: Option<Vec<Value>> = call.get_flag(engine_state, stack, "path-prepend")?;
+    let path_append: Option<Vec<Value>> = call.get_flag(engine_state, stack, "path-append")?;
+    if path_prepend.is_some() || path_append.is_some() {
+        extend_path(
+            &mut env_vars_str,
+            path_prepend.unwrap_or_default(),
+            path_append.unwrap_or_default(),
+        )?;
+    }
 
     fn value_as_spanned(value: Value) -> Result<Spanned<String>, ShellError> {
         let span = value.span()?;
@@ -152,9 +314,75 @@ pub fn create_external_command(
         redirect_stderr,
         env_vars: env_vars_str,
         trim_end_newline,
+        stdin_from,
+        close_stdin,
+        stdout_log,
+        stderr_log,
+        log_max_size,
+        output_encoding,
     })
 }
 
+// Lists are joined with the separator of PATH, so they can be used for PATH-like variables
+fn env_value_to_string(value: &Value) -> Result<String, ShellError> {
+    match value {
+        Value::List { vals, span } => {
+            let paths = vals
+                .iter()
+                .map(|val| val.as_string())
+                .collect::<Result<Vec<String>, ShellError>>()?;
+
+            std::env::join_paths(paths)
+                .map(|paths| paths.to_string_lossy().to_string())
+                .map_err(|err| {
+                    ShellError::UnsupportedInput(err.to_string(), "".into(), *span, *span)
+                })
+        }
+        other => other.as_string(),
+    }
+}
+
+// Adds directories around the PATH given to the external command. Relative directories
+// are taken from the current directory
+fn extend_path(
+    env_vars: &mut HashMap<String, String>,
+    prepend: Vec<Value>,
+    append: Vec<Value>,
+) -> Result<(), ShellError> {
+    let cwd = env_vars.get("PWD").cloned().unwrap_or_default();
+    // The variable is named Path on Windows
+    let key = env_vars
+        .keys()
+        .find(|key| key.eq_ignore_ascii_case("PATH"))
+        .cloned()
+        .unwrap_or_else(|| "PATH".into());
+
+    let to_paths = |dirs: Vec<Value>| {
+        dirs.into_iter()
+            .map(|dir| Ok(nu_path::expand_path_with(dir.as_string()?, &cwd)))
+            .collect::<Result<Vec<PathBuf>, ShellError>>()
+    };
+
+    let mut paths = to_paths(prepend)?;
+    if let Some(path) = env_vars.get(&key) {
+        paths.extend(std::env::split_paths(path));
+    }
+    paths.extend(to_paths(append)?);
+
+    let path = std::env::join_paths(paths).map_err(|err| {
+        ShellError::GenericError(
+            "Cannot extend PATH".into(),
+            err.to_string(),
+            None,
+            None,
+            Vec::new(),
+        )
+    })?;
+    env_vars.insert(key, path.to_string_lossy().to_string());
+
+    Ok(())
+}
+
 #[derive(Clone)]
 pub struct ExternalCommand {
     pub name: Spanned<String>,
@@ -164,6 +392,34 @@ pub struct ExternalCommand {
     pub redirect_stderr: bool,
     pub env_vars: HashMap<String, String>,
     pub trim_end_newline: bool,
+    pub stdin_from: Option<Spanned<String>>,
+    pub close_stdin: bool,
+    pub stdout_log: Option<Spanned<String>>,
+    pub stderr_log: Option<Spanned<String>>,
+    pub log_max_size: Option<u64>,
+    pub output_encoding: OutputEncoding,
+}
+
+/// How the output of an external command is turned into text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputEncoding {
+    /// The output is read as it is, as UTF-8 or binary
+    Raw,
+    /// UTF-16 and, on Windows, the code page of the console are detected
+    Auto,
+    /// The output is converted from the given encoding
+    Decode(&'static Encoding),
+}
+
+impl Default for OutputEncoding {
+    fn default() -> Self {
+        // Tools built in to Windows write in the code page of the console, or in UTF-16
+        if cfg!(windows) {
+            Self::Auto
+        } else {
+            Self::Raw
+        }
+    }
 }
 
 impl ExternalCommand {
@@ -177,11 +433,15 @@ impl ExternalCommand {
         let head = self.name.span;
 
         let ctrlc = engine_state.ctrlc.clone();
+        let stdout_log = self.open_log(&self.stdout_log, None)?;
+        let output_encoding = self.output_encoding;
+        let mut stderr_log = self.open_log(&self.stderr_log, stdout_log.as_ref())?;
 
         let mut fg_process = ForegroundProcess::new(
             self.create_process(&input, false, head)?,
             engine_state.pipeline_externals_state.clone(),
         );
+        let start_time = Instant::now();
         // mut is used in the windows branch only, suppress warning on other platforms
         #[allow(unused_mut)]
         let mut child;
@@ -359,13 +619,19 @@ impl ExternalCommand {
                                 //
                                 // TODO: should we do something different for list<string> inputs?
                                 // Users often expect those to be piped to *nix tools as raw strings separated by newlines
-                                let input = crate::Table::run(
-                                    &crate::Table,
-                                    &engine_state,
-                                    &mut stack,
-                                    &Call::new(head),
-                                    input,
-                                );
+                                let input = match input {
+                                    // Binary data is written as it is, rather than as a hex dump
+                                    input @ PipelineData::Value(Value::Binary { .. }, ..) => {
+                                        Ok(input)
+                                    }
+                                    input => crate::Table::run(
+                                        &crate::Table,
+                                        &engine_state,
+                                        &mut stack,
+                                        &Call::new(head),
+                                        input,
+                                    ),
+                                };
 
                                 if let Ok(input) = input {
                                     for value in input.into_iter() {
@@ -395,9 +661,27 @@ impl ExternalCommand {
                 let stderr_ctrlc = ctrlc.clone();
                 let (stdout_tx, stdout_rx) = mpsc::sync_channel(OUTPUT_BUFFERS_IN_FLIGHT);
                 let (exit_code_tx, exit_code_rx) = mpsc::channel();
+                let (usage_tx, usage_rx) = mpsc::channel();
 
                 let stdout = child.as_mut().stdout.take();
-                let stderr = child.as_mut().stderr.take();
+                let mut stderr = child.as_mut().stderr.take();
+                let logs: Vec<SharedLog> = stdout_log.iter().chain(&stderr_log).cloned().collect();
+
+                // Without a redirect, stderr is only piped to be decoded or copied to its log
+                let stderr_mirror = if redirect_stderr {
+                    None
+                } else {
+                    stderr.take().map(|stderr| {
+                        let log = stderr_log.take();
+                        thread::Builder::new()
+                            .name("stderr mirror".to_string())
+                            .spawn(move || {
+                                let stderr = DecodedReader::new(stderr, output_encoding);
+                                mirror_output(LoggedReader::new(stderr, log), std::io::stderr())
+                            })
+                            .expect("Failed to create thread")
+                    })
+                };
 
                 // If this external is not the last expression, then its output is piped to a channel
                 // and we create a ListStream that can be consumed
@@ -416,16 +700,42 @@ impl ExternalCommand {
                                 )
                             })?;
 
-                            read_and_redirect_message(stdout, stdout_tx, ctrlc)
+                            let stdout = DecodedReader::new(stdout, output_encoding);
+                            read_and_redirect_message(
+                                LoggedReader::new(stdout, stdout_log),
+                                stdout_tx,
+                                ctrlc,
+                            )
+                        } else if let Some(stdout) = stdout {
+                            // Without a redirect, stdout is only piped to be decoded or copied to its log
+                            let stdout = DecodedReader::new(stdout, output_encoding);
+                            mirror_output(LoggedReader::new(stdout, stdout_log), std::io::stdout())
+                        }
+
+                        // Everything written to the stderr log is there once the command is done
+                        if let Some(stderr_mirror) = stderr_mirror {
+                            let _ = stderr_mirror.join();
+                        }
+
+                    // A log that couldn't be written is reported after the exit code, like a core dump
+                    let report_log_errors = || {
+                        for log in &logs {
+                            let error = log.lock().ok().and_then(|mut log| log.take_error());
+                            if let Some(error) = error {
+                                let _ = exit_code_tx.send(Value::Error { error });
+                            }
                         }
+                    };
 
-                    match child.as_mut().wait() {
+                    match wait_with_usage(child.as_mut()) {
                         Err(err) => Err(ShellError::ExternalCommand(
                             "External command exited with error".into(),
                             err.to_string(),
                             span,
                         )),
-                        Ok(x) => {
+                        Ok((x, usage)) => {
+                            let _ = usage_tx.send(usage.into_value(start_time, head));
+
                             #[cfg(unix)]
                             {
                                 use nu_ansi_term::{Color, Style};
@@ -454,6 +764,10 @@ impl ExternalCommand {
                                             "{cause}: oops, process '{commandname}' core dumped"
                                         ))
                                     );
+                                    // The error comes after the exit code, so `complete` can still report it
+                                    if let Some(sig) = x.signal() {
+                                        let _ = exit_code_tx.send(Value::int(-(sig as i64), head));
+                                    }
                                     let _ = exit_code_tx.send(Value::Error {
                                         error: ShellError::ExternalCommand(
                                             "core dumped".to_string(),
@@ -461,6 +775,14 @@ impl ExternalCommand {
                                             head,
                                         ),
                                     });
+                                    report_log_errors();
+                                    return Ok(());
+                                }
+
+                                // A process killed by a signal has minus the signal number as exit code
+                                if let Some(sig) = x.signal() {
+                                    let _ = exit_code_tx.send(Value::int(-(sig as i64), head));
+                                    report_log_errors();
                                     return Ok(());
                                 }
                             }
@@ -471,6 +793,7 @@ impl ExternalCommand {
                             } else {
                                 let _ = exit_code_tx.send(Value::int(-1, head));
                             }
+                            report_log_errors();
                             Ok(())
                         }
                     }
@@ -490,7 +813,12 @@ impl ExternalCommand {
                                 )
                             })?;
 
-                            read_and_redirect_message(stderr, stderr_tx, stderr_ctrlc);
+                            let stderr = DecodedReader::new(stderr, output_encoding);
+                            read_and_redirect_message(
+                                LoggedReader::new(stderr, stderr_log),
+                                stderr_tx,
+                                stderr_ctrlc,
+                            );
                             Ok::<(), ShellError>(())
                         })
                         .expect("Failed to create thread");
@@ -525,6 +853,7 @@ impl ExternalCommand {
                         Box::new(exit_code_receiver),
                         output_ctrlc,
                     )),
+                    resource_usage: Some(usage_rx),
                     span: head,
                     metadata: None,
                     trim_end_newline: self.trim_end_newline,
@@ -568,23 +897,79 @@ impl ExternalCommand {
 
         // If the external is not the last command, its output will get piped
         // either as a string or binary
-        if self.redirect_stdout {
+        // Output is only decoded on its way to the terminal when its encoding is given
+        let decode = matches!(self.output_encoding, OutputEncoding::Decode(_));
+
+        if self.redirect_stdout || self.stdout_log.is_some() || decode {
             process.stdout(Stdio::piped());
         }
 
-        if self.redirect_stderr {
+        if self.redirect_stderr || self.stderr_log.is_some() || decode {
             process.stderr(Stdio::piped());
         }
 
         // If there is an input from the pipeline. The stdin from the process
         // is piped so it can be used to send the input information
         if !input.is_nothing() {
+            if let Some(stdin_from) = &self.stdin_from {
+                return Err(ShellError::IncompatibleParametersSingle(
+                    "--stdin-from can't be used together with an input".into(),
+                    stdin_from.span,
+                ));
+            }
             process.stdin(Stdio::piped());
+        } else if let Some(stdin_from) = &self.stdin_from {
+            let cwd = self.env_vars.get("PWD").map(String::as_str).unwrap_or("");
+            let path = nu_path::expand_path_with(&stdin_from.item, cwd);
+            let file = std::fs::File::open(&path).map_err(|err| {
+                ShellError::IOErrorSpanned(
+                    format!("Cannot open {}: {err}", path.display()),
+                    stdin_from.span,
+                )
+            })?;
+            process.stdin(file);
+        } else if self.close_stdin {
+            process.stdin(Stdio::null());
         }
 
         Ok(process)
     }
 
+    // Log files are taken from the current directory, and appended to. When stdout and stderr
+    // go to the same file, they share one log, so that they don't rotate it from under each other
+    fn open_log(
+        &self,
+        path: &Option<Spanned<String>>,
+        other: Option<&SharedLog>,
+    ) -> Result<Option<SharedLog>, ShellError> {
+        let log = match path {
+            Some(log) => log,
+            None => return Ok(None),
+        };
+
+        let cwd = self.env_vars.get("PWD").map(String::as_str).unwrap_or("");
+        let path = nu_path::expand_path_with(&log.item, cwd);
+
+        if let Some(other) = other {
+            let other_path = other.lock().ok().map(|other| other.path.clone());
+            let canonical = |path: &Path| std::fs::canonicalize(path).ok();
+            if let (Some(this), Some(other_path)) = (canonical(&path), other_path) {
+                if canonical(&other_path) == Some(this) {
+                    return Ok(Some(other.clone()));
+                }
+            }
+        }
+
+        OutputLog::open(path.clone(), self.log_max_size, log.span)
+            .map(|log| Some(Arc::new(Mutex::new(log))))
+            .map_err(|err| {
+                ShellError::IOErrorSpanned(
+                    format!("Cannot open {}: {err}", path.display()),
+                    log.span,
+                )
+            })
+    }
+
     fn create_command(&self, cwd: &str) -> Result<CommandSys, ShellError> {
         // in all the other cases shell out
         if cfg!(windows) {
@@ -786,6 +1171,149 @@ fn remove_quotes(input: String) -> String {
     }
 }
 
+// Resources used by an external command, reported by `complete`
+struct ResourceUsage {
+    max_rss: Option<i64>,
+    user_time: Option<i64>,
+    system_time: Option<i64>,
+}
+
+impl ResourceUsage {
+    fn into_value(self, start_time: Instant, span: Span) -> Value {
+        let duration = |val: Option<i64>| match val {
+            Some(val) => Value::Duration { val, span },
+            None => Value::nothing(span),
+        };
+
+        Value::Record {
+            cols: vec![
+                "duration".into(),
+                "max_rss".into(),
+                "user_time".into(),
+                "system_time".into(),
+            ],
+            vals: vec![
+                duration(Some(start_time.elapsed().as_nanos() as i64)),
+                match self.max_rss {
+                    Some(val) => Value::Filesize { val, span },
+                    None => Value::nothing(span),
+                },
+                duration(self.user_time),
+                duration(self.system_time),
+            ],
+            span,
+        }
+    }
+}
+
+#[cfg(unix)]
+// The libc field types are narrower than i64 on some targets
+#[allow(clippy::unnecessary_cast)]
+fn wait_with_usage(child: &mut Child) -> std::io::Result<(ExitStatus, ResourceUsage)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut status = 0;
+    // SAFETY: rusage only holds numbers, so all zeroes is a valid value
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        // SAFETY: the child has not been waited for yet, so its pid still refers to it,
+        // and both pointers are valid for the duration of the call
+        let pid = unsafe { libc::wait4(child.id() as libc::pid_t, &mut status, 0, &mut usage) };
+        if pid != -1 {
+            break;
+        }
+
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+
+    let nanos =
+        |time: libc::timeval| time.tv_sec as i64 * 1_000_000_000 + time.tv_usec as i64 * 1_000;
+    // ru_maxrss is in bytes on macOS, and in kilobytes everywhere else
+    let max_rss = if cfg!(target_os = "macos") {
+        usage.ru_maxrss as i64
+    } else {
+        usage.ru_maxrss as i64 * 1024
+    };
+
+    Ok((
+        ExitStatus::from_raw(status),
+        ResourceUsage {
+            max_rss: Some(max_rss),
+            user_time: Some(nanos(usage.ru_utime)),
+            system_time: Some(nanos(usage.ru_stime)),
+        },
+    ))
+}
+
+#[cfg(windows)]
+fn wait_with_usage(child: &mut Child) -> std::io::Result<(ExitStatus, ResourceUsage)> {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::{FILETIME, HANDLE};
+    use windows::Win32::System::ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows::Win32::System::Threading::GetProcessTimes;
+
+    let status = child.wait()?;
+    let handle = HANDLE(child.as_raw_handle() as isize);
+
+    let mut creation_time = FILETIME::default();
+    let mut exit_time = FILETIME::default();
+    let mut kernel_time = FILETIME::default();
+    let mut user_time = FILETIME::default();
+    // SAFETY: the handle of the child stays open until it is dropped,
+    // and the pointers are valid for the duration of the call
+    let has_times = unsafe {
+        GetProcessTimes(
+            handle,
+            &mut creation_time,
+            &mut exit_time,
+            &mut kernel_time,
+            &mut user_time,
+        )
+    }
+    .as_bool();
+
+    let mut counters = PROCESS_MEMORY_COUNTERS::default();
+    // SAFETY: same as above
+    let has_counters = unsafe {
+        K32GetProcessMemoryInfo(
+            handle,
+            &mut counters,
+            std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+        )
+    }
+    .as_bool();
+
+    // FILETIME counts intervals of 100 nanoseconds
+    let nanos = |time: FILETIME| {
+        ((((time.dwHighDateTime as u64) << 32) | time.dwLowDateTime as u64) * 100) as i64
+    };
+
+    Ok((
+        status,
+        ResourceUsage {
+            max_rss: has_counters.then_some(counters.PeakWorkingSetSize as i64),
+            user_time: has_times.then(|| nanos(user_time)),
+            system_time: has_times.then(|| nanos(kernel_time)),
+        },
+    ))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn wait_with_usage(child: &mut Child) -> std::io::Result<(ExitStatus, ResourceUsage)> {
+    let status = child.wait()?;
+    Ok((
+        status,
+        ResourceUsage {
+            max_rss: None,
+            user_time: None,
+            system_time: None,
+        },
+    ))
+}
+
 // read message from given `reader`, and send out through `sender`.
 //
 // `ctrlc` is used to control the process, if ctrl-c is pressed, the read and redirect
@@ -824,6 +1352,274 @@ fn read_and_redirect_message<R>(
     }
 }
 
+// Copies the output of an external command to nu's own stdout or stderr, as it arrives
+fn mirror_output(mut reader: impl Read, mut writer: impl Write) {
+    let mut buf = [0; OUTPUT_BUFFER_SIZE];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(length) => {
+                // Flushed right away, so partial lines like progress bars show up too
+                if writer
+                    .write_all(&buf[..length])
+                    .and_then(|_| writer.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        }
+    }
+}
+
+// A file the output of an external command is appended to. When it would grow past
+// `max_size`, it is rotated: `file` becomes `file.1`, `file.1` becomes `file.2` and so on
+struct OutputLog {
+    path: PathBuf,
+    file: std::fs::File,
+    size: u64,
+    max_size: Option<u64>,
+    span: Span,
+    // The first write that failed. Nothing more is written after it
+    error: Option<std::io::Error>,
+}
+
+type SharedLog = Arc<Mutex<OutputLog>>;
+
+impl OutputLog {
+    fn open(path: PathBuf, max_size: Option<u64>, span: Span) -> std::io::Result<Self> {
+        let file = Self::open_file(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size,
+            span,
+            error: None,
+        })
+    }
+
+    fn open_file(path: &Path) -> std::io::Result<std::fs::File> {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+    }
+
+    // Appends to the log, unless a write already failed
+    fn append(&mut self, bytes: &[u8]) {
+        if self.error.is_none() {
+            if let Err(err) = self.write(bytes) {
+                self.error = Some(err);
+            }
+        }
+    }
+
+    fn take_error(&mut self) -> Option<ShellError> {
+        self.error.take().map(|err| {
+            ShellError::IOErrorSpanned(
+                format!("Cannot write to {}: {err}", self.path.display()),
+                self.span,
+            )
+        })
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        if let Some(max_size) = self.max_size {
+            if self.size > 0 && self.size + bytes.len() as u64 > max_size {
+                self.rotate()?;
+            }
+        }
+
+        self.file.write_all(bytes)?;
+        self.size += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        for n in (1..LOG_FILES_KEPT).rev() {
+            let rotated = self.rotated_path(n);
+            if rotated.exists() {
+                std::fs::rename(rotated, self.rotated_path(n + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, self.rotated_path(1))?;
+
+        self.file = Self::open_file(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{n}"));
+        path.into()
+    }
+}
+
+// Reader that copies what it reads to a log, if there is one. When the log can't be
+// written anymore, the output still goes on without it, and the error is reported once the
+// command is done
+struct LoggedReader<R> {
+    reader: R,
+    log: Option<SharedLog>,
+}
+
+impl<R> LoggedReader<R> {
+    fn new(reader: R, log: Option<SharedLog>) -> Self {
+        Self { reader, log }
+    }
+}
+
+impl<R: Read> Read for LoggedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = self.reader.read(buf)?;
+
+        if let (Some(log), true) = (&self.log, length > 0) {
+            if let Ok(mut log) = log.lock() {
+                log.append(&buf[..length]);
+            }
+        }
+
+        Ok(length)
+    }
+}
+
+// Reader that converts the output of an external command to UTF-8. In auto mode, the
+// encoding is detected from the first read
+struct DecodedReader<R> {
+    reader: R,
+    decoder: Option<Decoder>,
+    detect: bool,
+    decoded: Vec<u8>,
+    position: usize,
+}
+
+impl<R> DecodedReader<R> {
+    fn new(reader: R, encoding: OutputEncoding) -> Self {
+        Self {
+            reader,
+            decoder: match encoding {
+                OutputEncoding::Decode(encoding) => Some(encoding.new_decoder()),
+                OutputEncoding::Raw | OutputEncoding::Auto => None,
+            },
+            detect: encoding == OutputEncoding::Auto,
+            decoded: vec![],
+            position: 0,
+        }
+    }
+
+    fn decode(&mut self, input: &[u8]) {
+        let decoder = match &mut self.decoder {
+            Some(decoder) => decoder,
+            None => {
+                self.decoded = input.to_vec();
+                self.position = 0;
+                return;
+            }
+        };
+        // The decoder keeps the end of a character that is split between two reads
+        let last = input.is_empty();
+
+        // The input is small, so its longest conversion always fits
+        let capacity = decoder
+            .max_utf8_buffer_length(input.len())
+            .unwrap_or(4 * input.len() + 16);
+        self.decoded.resize(capacity, 0);
+        let (_, _, written, _) = decoder.decode_to_utf8(input, &mut self.decoded, last);
+        self.decoded.truncate(written);
+        self.position = 0;
+
+        if last {
+            self.decoder = None;
+        }
+    }
+}
+
+impl<R: Read> Read for DecodedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            if self.position < self.decoded.len() {
+                let length = buf.len().min(self.decoded.len() - self.position);
+                buf[..length].copy_from_slice(&self.decoded[self.position..self.position + length]);
+                self.position += length;
+                return Ok(length);
+            }
+
+            if self.decoder.is_none() && !self.detect {
+                return self.reader.read(buf);
+            }
+
+            let mut input = [0; OUTPUT_BUFFER_SIZE];
+            let length = self.reader.read(&mut input)?;
+            if self.detect && length > 0 {
+                self.detect = false;
+                self.decoder = detect_encoding(&input[..length]).map(Encoding::new_decoder);
+            }
+            if length == 0 && self.decoder.is_none() {
+                return Ok(0);
+            }
+
+            self.decode(&input[..length]);
+        }
+    }
+}
+
+// Guesses the encoding of the start of some output. UTF-8 and binary output are left as
+// they are
+fn detect_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return Some(encoding);
+    }
+
+    // Text in UTF-16 has a zero after most ASCII characters
+    let pairs = bytes.len() / 2;
+    let zeros_after = bytes
+        .chunks_exact(2)
+        .filter(|pair| pair[0] != 0 && pair[1] == 0);
+    if pairs > 0 && zeros_after.count() * 4 >= pairs * 3 {
+        return Some(encoding_rs::UTF_16LE);
+    }
+
+    // A character may be cut at the end of the read
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return None,
+        Err(err) if err.error_len().is_none() => return None,
+        Err(_) => {}
+    }
+
+    let binary = bytes
+        .iter()
+        .any(|byte| matches!(byte, 0..=0x08 | 0x0e..=0x1a | 0x1c..=0x1f));
+    if binary {
+        None
+    } else {
+        console_encoding()
+    }
+}
+
+#[cfg(windows)]
+fn console_encoding() -> Option<&'static Encoding> {
+    use windows::Win32::Globalization::GetOEMCP;
+    use windows::Win32::System::Console::GetConsoleOutputCP;
+
+    // SAFETY: These calls have no preconditions. Without a console, the code page is 0
+    let code_page = match unsafe { GetConsoleOutputCP() } {
+        0 => unsafe { GetOEMCP() },
+        code_page => code_page,
+    };
+    crate::code_page_encoding(code_page)
+}
+
+#[cfg(not(windows))]
+fn console_encoding() -> Option<&'static Encoding> {
+    None
+}
+
 // Receiver used for the RawStream
 // It implements iterator so it can be used as a RawStream
 struct ChannelReceiver {
@@ -905,4 +1701,33 @@ mod test {
 
         assert_eq!("bash -c 'echo a'", res)
     }
+
+    #[test]
+    fn detects_utf16_output() {
+        let output: Vec<u8> = "Windows IP Configuration"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+
+        assert_eq!(detect_encoding(&output), Some(encoding_rs::UTF_16LE));
+    }
+
+    #[test]
+    fn detects_byte_order_mark() {
+        assert_eq!(
+            detect_encoding(&[0xfe, 0xff, 0, b'a']),
+            Some(encoding_rs::UTF_16BE)
+        );
+    }
+
+    #[test]
+    fn leaves_utf8_and_binary_output() {
+        assert_eq!(detect_encoding("héllo wörld".as_bytes()), None);
+        // Cut in the middle of a character
+        assert_eq!(detect_encoding(&"héllo".as_bytes()[..2]), None);
+        assert_eq!(
+            detect_encoding(&[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]),
+            None
+        );
+    }
 }
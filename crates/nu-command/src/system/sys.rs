@@ -3,8 +3,10 @@ use chrono::Local;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Type, Value,
+    Category, Example, IntoPipelineData, LazyRecord, PipelineData, ShellError, Signature, Span,
+    Type, Value,
 };
+use serde::{Deserialize, Serialize};
 use std::time::{Duration, UNIX_EPOCH};
 use sysinfo::{ComponentExt, CpuExt, DiskExt, NetworkExt, System, SystemExt, UserExt};
 
@@ -27,6 +29,11 @@ impl Command for Sys {
         "View information about the system."
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Each section is only gathered when it is accessed, so `(sys).mem` reads the memory
+without scanning the disks, sensors and networks as well."#
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
@@ -54,48 +61,75 @@ impl Command for Sys {
                 example: "(sys).host.name",
                 result: None,
             },
+            Example {
+                description: "Show the free memory, without gathering the other sections",
+                example: "(sys).mem.free",
+                result: None,
+            },
         ]
     }
 }
 
 fn run_sys(call: &Call) -> Result<PipelineData, ShellError> {
     let span = call.head;
-    let mut sys = System::new();
 
-    let mut headers = vec![];
-    let mut values = vec![];
-
-    if let Some(value) = host(&mut sys, span) {
-        headers.push("host".into());
-        values.push(value);
-    }
-    if let Some(value) = cpu(&mut sys, span) {
-        headers.push("cpu".into());
-        values.push(value);
+    Ok(Value::LazyRecord {
+        val: Box::new(SysResult { span }),
+        span,
     }
-    if let Some(value) = disks(&mut sys, span) {
-        headers.push("disks".into());
-        values.push(value);
+    .into_pipeline_data())
+}
+
+// SysResult: a LazyRecord for the output of sys, where each section reads the system when it is
+// accessed. Scanning the disks and sensors is slow, and unneeded when only the memory is asked for
+
+// Note: SysResult is not meaningfully serializable, this #[derive] is a lie to satisfy the type checker.
+// Make sure to collect() the record before serializing it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SysResult {
+    pub span: Span,
+}
+
+impl LazyRecord for SysResult {
+    fn column_names(&self) -> Vec<&'static str> {
+        vec!["host", "cpu", "disks", "mem", "temp", "net"]
     }
-    if let Some(value) = mem(&mut sys, span) {
-        headers.push("mem".into());
-        values.push(value);
+
+    fn get_column_value(&self, column: &str) -> Result<Value, ShellError> {
+        let span = self.span;
+        let mut sys = System::new();
+
+        let value = match column {
+            "host" => host(&mut sys, span),
+            "cpu" => cpu(&mut sys, span),
+            "disks" => disks(&mut sys, span),
+            "mem" => mem(&mut sys, span),
+            "temp" => temp(&mut sys, span),
+            "net" => net(&mut sys, span),
+            _ => {
+                return Err(ShellError::LazyRecordAccessFailed {
+                    message: "There is no such section in sys".into(),
+                    column_name: column.to_string(),
+                    span,
+                })
+            }
+        };
+
+        // Sections without any device are empty
+        Ok(value.unwrap_or(Value::List { vals: vec![], span }))
     }
-    if let Some(value) = temp(&mut sys, span) {
-        headers.push("temp".into());
-        values.push(value);
+
+    fn span(&self) -> Span {
+        self.span
     }
-    if let Some(value) = net(&mut sys, span) {
-        headers.push("net".into());
-        values.push(value);
+
+    fn typetag_name(&self) -> &'static str {
+        "sys"
     }
 
-    Ok(Value::Record {
-        cols: headers,
-        vals: values,
-        span,
+    fn typetag_deserialize(&self) {
+        unimplemented!("typetag_deserialize")
     }
-    .into_pipeline_data())
 }
 
 pub fn trim_cstyle_null(s: String) -> String {
@@ -0,0 +1,393 @@
+//! The units of measurement `math convert`, `math units` and `into unit` know. Every unit is a
+//! multiple of the base unit of its dimension, which is the first unit listed for it, plus an
+//! offset for the temperature scales that don't start at absolute zero.
+
+use nu_protocol::{ShellError, Span, Spanned, Value};
+use std::f64::consts::PI;
+
+pub struct Unit {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub dimension: &'static str,
+    /// Other ways to write the unit
+    pub aliases: &'static [&'static str],
+    /// The base units in one of this unit
+    pub factor: f64,
+    /// The base units at zero of this unit
+    pub offset: f64,
+}
+
+const fn unit(
+    symbol: &'static str,
+    name: &'static str,
+    dimension: &'static str,
+    aliases: &'static [&'static str],
+    factor: f64,
+) -> Unit {
+    Unit {
+        symbol,
+        name,
+        dimension,
+        aliases,
+        factor,
+        offset: 0.0,
+    }
+}
+
+pub const UNITS: &[Unit] = &[
+    unit("m", "meter", "length", &["meters", "metre"], 1.0),
+    unit("km", "kilometer", "length", &["kilometers"], 1e3),
+    unit("cm", "centimeter", "length", &["centimeters"], 1e-2),
+    unit("mm", "millimeter", "length", &["millimeters"], 1e-3),
+    unit("um", "micrometer", "length", &["µm", "micron"], 1e-6),
+    unit("nm", "nanometer", "length", &["nanometers"], 1e-9),
+    unit("in", "inch", "length", &["inches"], 0.0254),
+    unit("ft", "foot", "length", &["feet"], 0.3048),
+    unit("yd", "yard", "length", &["yards"], 0.9144),
+    unit("mi", "mile", "length", &["miles"], 1609.344),
+    unit("nmi", "nautical mile", "length", &[], 1852.0),
+    unit("au", "astronomical unit", "length", &[], 149_597_870_700.0),
+    unit("ly", "light year", "length", &[], 9_460_730_472_580_800.0),
+    unit("kg", "kilogram", "mass", &["kilograms"], 1.0),
+    unit("g", "gram", "mass", &["grams"], 1e-3),
+    unit("mg", "milligram", "mass", &["milligrams"], 1e-6),
+    unit("ug", "microgram", "mass", &["µg"], 1e-9),
+    unit("t", "tonne", "mass", &["tonnes"], 1e3),
+    unit("oz", "ounce", "mass", &["ounces"], 0.028_349_523_125),
+    unit("lb", "pound", "mass", &["lbs", "pounds"], 0.453_592_37),
+    unit("st", "stone", "mass", &[], 6.350_293_18),
+    unit("K", "kelvin", "temperature", &["kelvin"], 1.0),
+    Unit {
+        offset: 273.15,
+        ..unit(
+            "C",
+            "degree Celsius",
+            "temperature",
+            &["°C", "celsius"],
+            1.0,
+        )
+    },
+    Unit {
+        offset: 273.15 - 32.0 * 5.0 / 9.0,
+        ..unit(
+            "F",
+            "degree Fahrenheit",
+            "temperature",
+            &["°F", "fahrenheit"],
+            5.0 / 9.0,
+        )
+    },
+    unit(
+        "R",
+        "degree Rankine",
+        "temperature",
+        &["°R", "rankine"],
+        5.0 / 9.0,
+    ),
+    unit("s", "second", "time", &["sec", "seconds"], 1.0),
+    unit("ns", "nanosecond", "time", &["nanoseconds"], 1e-9),
+    unit("us", "microsecond", "time", &["µs", "microseconds"], 1e-6),
+    unit("ms", "millisecond", "time", &["milliseconds"], 1e-3),
+    unit("min", "minute", "time", &["minutes"], 60.0),
+    unit("h", "hour", "time", &["hr", "hours"], 3600.0),
+    unit("day", "day", "time", &["d", "days"], 86400.0),
+    unit("wk", "week", "time", &["weeks"], 604_800.0),
+    unit("yr", "year", "time", &["years"], 31_557_600.0),
+    unit("m2", "square meter", "area", &["m²"], 1.0),
+    unit("km2", "square kilometer", "area", &["km²"], 1e6),
+    unit("cm2", "square centimeter", "area", &["cm²"], 1e-4),
+    unit("ha", "hectare", "area", &["hectares"], 1e4),
+    unit("acre", "acre", "area", &["acres"], 4046.856_422_4),
+    unit("ft2", "square foot", "area", &["ft²"], 0.092_903_04),
+    unit("m3", "cubic meter", "volume", &["m³"], 1.0),
+    unit("l", "liter", "volume", &["L", "liters", "litre"], 1e-3),
+    unit("ml", "milliliter", "volume", &["mL"], 1e-6),
+    unit("cm3", "cubic centimeter", "volume", &["cm³", "cc"], 1e-6),
+    unit("ft3", "cubic foot", "volume", &["ft³"], 0.028_316_846_592),
+    unit(
+        "gal",
+        "US gallon",
+        "volume",
+        &["gallons"],
+        0.003_785_411_784,
+    ),
+    unit("m/s", "meter per second", "speed", &["mps"], 1.0),
+    unit(
+        "km/h",
+        "kilometer per hour",
+        "speed",
+        &["kph", "kmh"],
+        1.0 / 3.6,
+    ),
+    unit("mph", "mile per hour", "speed", &["mi/h"], 0.447_04),
+    unit("kn", "knot", "speed", &["knots", "kt"], 1852.0 / 3600.0),
+    unit("ft/s", "foot per second", "speed", &["fps"], 0.3048),
+    unit("Pa", "pascal", "pressure", &["pascal"], 1.0),
+    unit("hPa", "hectopascal", "pressure", &[], 1e2),
+    unit("kPa", "kilopascal", "pressure", &[], 1e3),
+    unit("MPa", "megapascal", "pressure", &[], 1e6),
+    unit("bar", "bar", "pressure", &[], 1e5),
+    unit("mbar", "millibar", "pressure", &[], 1e2),
+    unit("atm", "atmosphere", "pressure", &[], 101_325.0),
+    unit(
+        "psi",
+        "pound per square inch",
+        "pressure",
+        &[],
+        6894.757_293_168,
+    ),
+    unit(
+        "mmHg",
+        "millimeter of mercury",
+        "pressure",
+        &[],
+        133.322_387_415,
+    ),
+    unit("torr", "torr", "pressure", &["Torr"], 101_325.0 / 760.0),
+    unit("J", "joule", "energy", &["joules"], 1.0),
+    unit("kJ", "kilojoule", "energy", &[], 1e3),
+    unit("MJ", "megajoule", "energy", &[], 1e6),
+    unit("Wh", "watt hour", "energy", &[], 3600.0),
+    unit("kWh", "kilowatt hour", "energy", &[], 3.6e6),
+    unit("cal", "calorie", "energy", &["calories"], 4.184),
+    unit("kcal", "kilocalorie", "energy", &["Cal"], 4184.0),
+    unit(
+        "BTU",
+        "British thermal unit",
+        "energy",
+        &["btu"],
+        1055.055_852_62,
+    ),
+    unit("eV", "electronvolt", "energy", &[], 1.602_176_634e-19),
+    unit("W", "watt", "power", &["watts"], 1.0),
+    unit("kW", "kilowatt", "power", &[], 1e3),
+    unit("MW", "megawatt", "power", &[], 1e6),
+    unit("hp", "horsepower", "power", &[], 745.699_871_582_270_2),
+    unit("Hz", "hertz", "frequency", &["hz"], 1.0),
+    unit("kHz", "kilohertz", "frequency", &["khz"], 1e3),
+    unit("MHz", "megahertz", "frequency", &["mhz"], 1e6),
+    unit("GHz", "gigahertz", "frequency", &["ghz"], 1e9),
+    unit("rad", "radian", "angle", &["radians"], 1.0),
+    unit("deg", "degree", "angle", &["°", "degrees"], PI / 180.0),
+    unit("turn", "turn", "angle", &["turns", "rev"], 2.0 * PI),
+    unit("B", "byte", "data", &["b", "bytes"], 1.0),
+    unit("bit", "bit", "data", &["bits"], 0.125),
+    unit("kB", "kilobyte", "data", &["KB"], 1e3),
+    unit("MB", "megabyte", "data", &[], 1e6),
+    unit("GB", "gigabyte", "data", &[], 1e9),
+    unit("TB", "terabyte", "data", &[], 1e12),
+    unit("PB", "petabyte", "data", &[], 1e15),
+    unit("KiB", "kibibyte", "data", &[], 1024.0),
+    unit("MiB", "mebibyte", "data", &[], 1_048_576.0),
+    unit("GiB", "gibibyte", "data", &[], 1_073_741_824.0),
+    unit("TiB", "tebibyte", "data", &[], 1_099_511_627_776.0),
+    unit("PiB", "pebibyte", "data", &[], 1_125_899_906_842_624.0),
+    unit("kbit", "kilobit", "data", &["Kbit"], 125.0),
+    unit("Mbit", "megabit", "data", &[], 1.25e5),
+    unit("Gbit", "gigabit", "data", &[], 1.25e8),
+    unit("B/s", "byte per second", "data rate", &["Bps"], 1.0),
+    unit("kB/s", "kilobyte per second", "data rate", &["KB/s"], 1e3),
+    unit("MB/s", "megabyte per second", "data rate", &[], 1e6),
+    unit("GB/s", "gigabyte per second", "data rate", &[], 1e9),
+    unit(
+        "MiB/s",
+        "mebibyte per second",
+        "data rate",
+        &[],
+        1_048_576.0,
+    ),
+    unit("bit/s", "bit per second", "data rate", &["bps"], 0.125),
+    unit(
+        "kbit/s",
+        "kilobit per second",
+        "data rate",
+        &["kbps"],
+        125.0,
+    ),
+    unit(
+        "Mbit/s",
+        "megabit per second",
+        "data rate",
+        &["Mbps"],
+        1.25e5,
+    ),
+    unit(
+        "Gbit/s",
+        "gigabit per second",
+        "data rate",
+        &["Gbps"],
+        1.25e8,
+    ),
+];
+
+/// The unit written as `name`: its symbol, its name or one of its aliases. Case only matters when
+/// it tells units apart, like `mbar` and `Mbit`.
+pub fn find_unit(name: &str) -> Option<&'static Unit> {
+    let names = |unit: &'static Unit| {
+        [unit.symbol, unit.name]
+            .into_iter()
+            .chain(unit.aliases.iter().copied())
+    };
+
+    if let Some(unit) = UNITS.iter().find(|unit| names(*unit).any(|n| n == name)) {
+        return Some(unit);
+    }
+    let mut matches = UNITS
+        .iter()
+        .filter(|unit| names(*unit).any(|n| n.eq_ignore_ascii_case(name)));
+    match (matches.next(), matches.next()) {
+        (Some(unit), None) => Some(unit),
+        _ => None,
+    }
+}
+
+/// Splits a quantity like `1.5km` or `30 psi` into its number and its unit, which is empty for a
+/// bare number
+pub fn parse_quantity(s: &str) -> Option<(f64, &str)> {
+    let s = s.trim();
+    s.char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(s.len()))
+        .rev()
+        .find_map(|end| {
+            let number = s[..end].trim().replace('_', "");
+            // `inf` and `nan` parse as numbers, but are more likely the start of a unit
+            if number.chars().any(|c| c.is_ascii_digit()) {
+                number.parse::<f64>().ok().map(|n| (n, s[end..].trim()))
+            } else {
+                None
+            }
+        })
+}
+
+/// The unit named by a flag or argument
+pub fn unit_named(name: &Spanned<String>) -> Result<&'static Unit, ShellError> {
+    find_unit(&name.item).ok_or_else(|| unknown_unit_error(&name.item, name.span))
+}
+
+fn unknown_unit_error(name: &str, span: Span) -> ShellError {
+    ShellError::GenericError(
+        "Unknown unit".into(),
+        format!("{name} is not a unit"),
+        Some(span),
+        Some("Run `math units` to list the units".into()),
+        vec![],
+    )
+}
+
+/// Converts `value` from `from` to `to`. Strings carry their own unit, like `1.5km`, and file
+/// sizes and durations are in bytes and nanoseconds. Bare numbers are in the `from` unit.
+pub fn convert_value(
+    value: &Value,
+    from: Option<&'static Unit>,
+    to: &'static Unit,
+    head: Span,
+) -> Value {
+    let span = match value.span() {
+        Ok(span) => span,
+        Err(error) => return Value::Error { error },
+    };
+    let quantity = match value {
+        Value::Int { .. } | Value::Float { .. } => value.as_float().map(|number| (number, from)),
+        Value::Filesize { val, .. } => Ok((*val as f64, find_unit("B"))),
+        Value::Duration { val, .. } => Ok((*val as f64, find_unit("ns"))),
+        Value::String { val, .. } => match parse_quantity(val) {
+            Some((number, "")) => Ok((number, from)),
+            Some((number, name)) => match find_unit(name) {
+                Some(unit) => Ok((number, Some(unit))),
+                None => Err(unknown_unit_error(name, span)),
+            },
+            None => Err(ShellError::CantConvert(
+                "quantity".into(),
+                "string".into(),
+                span,
+                Some("write a number with a unit, like 1.5km or 30 psi".into()),
+            )),
+        },
+        Value::Error { .. } => return value.clone(),
+        other => Err(ShellError::OnlySupportsThisInputType(
+            "number, string, filesize or duration".into(),
+            other.get_type().to_string(),
+            head,
+            span,
+        )),
+    };
+
+    match quantity {
+        Ok((number, Some(from))) => match convert(number, from, to) {
+            Some(converted) => Value::float(converted, head),
+            None => Value::Error {
+                error: ShellError::GenericError(
+                    "Incompatible units".into(),
+                    format!(
+                        "can't convert {} in {} to {} in {}",
+                        from.dimension, from.symbol, to.dimension, to.symbol
+                    ),
+                    Some(span),
+                    None,
+                    vec![],
+                ),
+            },
+        },
+        Ok((_, None)) => Value::Error {
+            error: ShellError::GenericError(
+                "Missing unit".into(),
+                "this number has no unit".into(),
+                Some(span),
+                Some("Add --from with the unit of the number".into()),
+                vec![],
+            ),
+        },
+        Err(error) => Value::Error { error },
+    }
+}
+
+/// Converts `number` in `from` to `to`, unless they measure different dimensions
+pub fn convert(number: f64, from: &Unit, to: &Unit) -> Option<f64> {
+    if from.dimension != to.dimension {
+        return None;
+    }
+    let base = number * from.factor + from.offset;
+    Some((base - to.offset) / to.factor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn unit_of(name: &str) -> &'static Unit {
+        find_unit(name).expect("known unit")
+    }
+
+    #[test]
+    fn names_are_unique() {
+        for unit in UNITS {
+            for name in [unit.symbol, unit.name]
+                .into_iter()
+                .chain(unit.aliases.iter().copied())
+            {
+                assert!(
+                    std::ptr::eq(unit_of(name), unit),
+                    "{name} is not only {}",
+                    unit.symbol
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn converts_temperatures() {
+        let boiling = convert(100.0, unit_of("C"), unit_of("F")).expect("same dimension");
+
+        assert!((boiling - 212.0).abs() < 1e-9);
+        assert_eq!(convert(1.0, unit_of("C"), unit_of("kg")), None);
+    }
+
+    #[test]
+    fn parses_quantities() {
+        assert_eq!(parse_quantity("1.5km"), Some((1.5, "km")));
+        assert_eq!(parse_quantity(" -2e3 m/s "), Some((-2000.0, "m/s")));
+        assert_eq!(parse_quantity("42"), Some((42.0, "")));
+        assert_eq!(parse_quantity("km"), None);
+    }
+}
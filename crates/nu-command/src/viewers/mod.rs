@@ -2,7 +2,9 @@ mod explore;
 mod griddle;
 mod icons;
 mod table;
+mod tree;
 
 pub use explore::Explore;
 pub use griddle::Griddle;
 pub use table::Table;
+pub use tree::Tree;
@@ -253,6 +253,7 @@ fn handle_table_command(
             )),
             stderr: None,
             exit_code: None,
+            resource_usage: None,
             span: call.head,
             metadata: None,
             trim_end_newline: false,
@@ -737,6 +738,7 @@ fn handle_row_stream(
         )),
         stderr: None,
         exit_code: None,
+        resource_usage: None,
         span: head,
         metadata: None,
         trim_end_newline: false,
@@ -0,0 +1,396 @@
+use super::icons::icon_for_file;
+use nu_engine::{current_dir, CallExt};
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, Config, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
+};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct Tree;
+
+impl Command for Tree {
+    fn name(&self) -> &str {
+        "tree"
+    }
+
+    fn usage(&self) -> &str {
+        "Render nested records and lists, or a directory, as an indented tree."
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("tree")
+            .input_output_types(vec![
+                (Type::Nothing, Type::String),
+                (Type::Record(vec![]), Type::String),
+                (Type::List(Box::new(Type::Any)), Type::String),
+                (Type::Any, Type::Table(vec![])),
+            ])
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "the directory to render instead of the input",
+            )
+            .named(
+                "depth",
+                SyntaxShape::Int,
+                "how many levels to render, counting the top one as 1",
+                Some('d'),
+            )
+            .switch(
+                "icons",
+                "show file icons before the entries of a directory",
+                Some('i'),
+            )
+            .switch(
+                "counts",
+                "show how many items each nested value or directory holds",
+                Some('c'),
+            )
+            .switch(
+                "data",
+                "return a table with a row per node instead of text",
+                None,
+            )
+            .allow_variants_without_examples(true)
+            .category(Category::Viewers)
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"Without input or a path, the current directory is rendered. Symbolic links to directories
+are not followed.
+
+With --data, every node becomes a row with an id and the id of its parent, so the tree
+can be processed further. The value column holds the values of the leaves, or the full
+path of the entries of a directory."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["hierarchy", "nested", "directory", "outline"]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let head = call.head;
+        let path: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+        let depth: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "depth")?;
+        let options = Options {
+            depth: match depth {
+                Some(depth) if depth.item < 1 => {
+                    return Err(ShellError::NeedsPositiveValue(depth.span))
+                }
+                Some(depth) => Some(depth.item as usize),
+                None => None,
+            },
+            icons: call.has_flag("icons"),
+            counts: call.has_flag("counts"),
+        };
+        let config = engine_state.get_config();
+
+        let input = input.into_value(head);
+        let (title, nodes) = match (path, input) {
+            (Some(path), _) => {
+                let cwd = current_dir(engine_state, stack)?;
+                let full_path = nu_path::expand_path_with(&path.item, cwd);
+                if !full_path.is_dir() {
+                    return Err(ShellError::NotADirectory(path.span));
+                }
+                let nodes = dir_nodes(&full_path, 1, &options, &engine_state.ctrlc, head)
+                    .map_err(|e| ShellError::IOErrorSpanned(e.to_string(), path.span))?;
+                (Some(path.item), nodes)
+            }
+            (None, Value::Nothing { .. }) => {
+                let cwd = current_dir(engine_state, stack)?;
+                let nodes = dir_nodes(&cwd, 1, &options, &engine_state.ctrlc, head)
+                    .map_err(|e| ShellError::IOErrorSpanned(e.to_string(), head))?;
+                (Some(".".to_string()), nodes)
+            }
+            (None, Value::Error { error }) => return Err(error),
+            (None, value @ (Value::Record { .. } | Value::List { .. })) => {
+                (None, value_nodes(&value, 1, &options, config))
+            }
+            (None, other) => {
+                return Err(ShellError::OnlySupportsThisInputType(
+                    "record or list".into(),
+                    other.get_type().to_string(),
+                    head,
+                    other.expect_span(),
+                ))
+            }
+        };
+
+        if call.has_flag("data") {
+            let mut rows = vec![];
+            flatten(nodes, None, 1, &mut rows, head);
+            return Ok(rows
+                .into_iter()
+                .into_pipeline_data(engine_state.ctrlc.clone()));
+        }
+
+        let mut lines: Vec<String> = title.into_iter().collect();
+        render(&nodes, "", &options, &mut lines);
+        Ok(Value::string(lines.join("\n"), head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Render a nested record",
+                example: "{src: {main: 120, lib: 80}, readme: 40} | tree",
+                result: Some(Value::test_string(
+                    "├── src\n│   ├── main: 120\n│   └── lib: 80\n└── readme: 40",
+                )),
+            },
+            Example {
+                description: "Render only the top level, with the number of items in each value",
+                example: "[[1 2 3] {c: 1}] | tree --depth 1 --counts",
+                result: Some(Value::test_string("├── 0 (3)\n└── 1 (1)")),
+            },
+            Example {
+                description: "Render a directory two levels deep with file icons",
+                example: "tree src --depth 2 --icons",
+                result: None,
+            },
+            Example {
+                description: "Get a row per node, with the id of its parent",
+                example: "[10 20] | tree --data",
+                result: Some(Value::List {
+                    vals: vec![
+                        node_row(0, None, 1, "0", "int", Value::test_int(10)),
+                        node_row(1, None, 1, "1", "int", Value::test_int(20)),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+        ]
+    }
+}
+
+struct Options {
+    depth: Option<usize>,
+    icons: bool,
+    counts: bool,
+}
+
+impl Options {
+    fn shows_children_of(&self, level: usize) -> bool {
+        self.depth.map_or(true, |depth| level < depth)
+    }
+}
+
+struct Node {
+    name: String,
+    label: String,
+    kind: String,
+    value: Value,
+    count: Option<usize>,
+    children: Vec<Node>,
+}
+
+fn value_nodes(value: &Value, level: usize, options: &Options, config: &Config) -> Vec<Node> {
+    let node = |name: String, val: &Value, in_list: bool| match val {
+        Value::Record { cols, .. } => {
+            nested_node(name, "record", cols.len(), val, level, options, config)
+        }
+        Value::List { vals, .. } => {
+            nested_node(name, "list", vals.len(), val, level, options, config)
+        }
+        _ => {
+            let text = val.into_string(", ", config);
+            Node {
+                label: if in_list {
+                    text
+                } else {
+                    format!("{name}: {text}")
+                },
+                name,
+                kind: val.get_type().to_string(),
+                value: val.clone(),
+                count: None,
+                children: vec![],
+            }
+        }
+    };
+
+    match value {
+        Value::Record { cols, vals, .. } => cols
+            .iter()
+            .zip(vals)
+            .map(|(col, val)| node(col.clone(), val, false))
+            .collect(),
+        Value::List { vals, .. } => vals
+            .iter()
+            .enumerate()
+            .map(|(idx, val)| node(idx.to_string(), val, true))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn nested_node(
+    name: String,
+    kind: &str,
+    count: usize,
+    value: &Value,
+    level: usize,
+    options: &Options,
+    config: &Config,
+) -> Node {
+    let children = if options.shows_children_of(level) {
+        value_nodes(value, level + 1, options, config)
+    } else {
+        vec![]
+    };
+    Node {
+        label: name.clone(),
+        name,
+        kind: kind.into(),
+        value: Value::nothing(Span::unknown()),
+        count: Some(count),
+        children,
+    }
+}
+
+/// The entries of a directory, sorted by name
+fn dir_nodes(
+    dir: &Path,
+    level: usize,
+    options: &Options,
+    ctrlc: &Option<Arc<AtomicBool>>,
+    span: Span,
+) -> std::io::Result<Vec<Node>> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut nodes = vec![];
+    for entry in entries {
+        if let Some(ctrlc) = ctrlc {
+            if ctrlc.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let file_type = entry.file_type()?;
+        let (kind, count, children) = if file_type.is_dir() {
+            // An unreadable directory is still listed, just without its entries
+            let count = std::fs::read_dir(&path).map(|entries| entries.count()).ok();
+            let children = if count.is_some() && options.shows_children_of(level) {
+                dir_nodes(&path, level + 1, options, ctrlc, span).unwrap_or_default()
+            } else {
+                vec![]
+            };
+            ("dir", count, children)
+        } else if file_type.is_symlink() {
+            ("symlink", None, vec![])
+        } else {
+            ("file", None, vec![])
+        };
+
+        let label = match options.icons {
+            true if kind == "dir" => {
+                format!("{} {name}", icon_for_file(&path, span).unwrap_or(' '))
+            }
+            true => format!(
+                "{} {name}",
+                icon_for_file(Path::new(&name), span).unwrap_or(' ')
+            ),
+            false => name.clone(),
+        };
+        nodes.push(Node {
+            name,
+            label,
+            kind: kind.into(),
+            value: Value::string(path.to_string_lossy(), span),
+            count,
+            children,
+        });
+    }
+    Ok(nodes)
+}
+
+fn render(nodes: &[Node], prefix: &str, options: &Options, lines: &mut Vec<String>) {
+    for (idx, node) in nodes.iter().enumerate() {
+        let last = idx + 1 == nodes.len();
+        let mut line = format!(
+            "{prefix}{}{}",
+            if last { "└── " } else { "├── " },
+            node.label
+        );
+        if let (true, Some(count)) = (options.counts, node.count) {
+            line.push_str(&format!(" ({count})"));
+        }
+        lines.push(line);
+
+        let child_prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+        render(&node.children, &child_prefix, options, lines);
+    }
+}
+
+/// Turn the tree into rows in depth-first order, numbering the nodes as they come
+fn flatten(nodes: Vec<Node>, parent: Option<i64>, depth: i64, rows: &mut Vec<Value>, span: Span) {
+    for node in nodes {
+        let id = rows.len() as i64;
+        let value = match node.value {
+            Value::Nothing { .. } => Value::nothing(span),
+            value => value,
+        };
+        rows.push(node_row(id, parent, depth, &node.name, &node.kind, value));
+        flatten(node.children, Some(id), depth + 1, rows, span);
+    }
+}
+
+fn node_row(
+    id: i64,
+    parent: Option<i64>,
+    depth: i64,
+    name: &str,
+    kind: &str,
+    value: Value,
+) -> Value {
+    let span = value.span().unwrap_or_else(|_| Span::unknown());
+    Value::Record {
+        cols: vec![
+            "id".into(),
+            "parent".into(),
+            "depth".into(),
+            "name".into(),
+            "type".into(),
+            "value".into(),
+        ],
+        vals: vec![
+            Value::int(id, span),
+            match parent {
+                Some(parent) => Value::int(parent, span),
+                None => Value::nothing(span),
+            },
+            Value::int(depth, span),
+            Value::string(name, span),
+            Value::string(kind, span),
+            value,
+        ],
+        span,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Tree {})
+    }
+}
@@ -0,0 +1,39 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn aggregates_each_group() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [[region sales]; [eu 1] [us 2] [eu 3]]
+        | aggregate --by [region] {total: {|g| $g.sales | math sum}, n: {|g| $g | length}}
+        | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[region, total, n]; [eu, 4, 2], [us, 2, 1]]");
+}
+
+#[test]
+fn aggregates_all_rows_without_by() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [[sales]; [1] [2] [3]] | aggregate {total: {|g| $g.sales | math sum}} | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[total]; [6]]");
+}
+
+#[test]
+fn rejects_aggregations_that_are_not_closures() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [[sales]; [1]] | aggregate {total: 1}
+        "#
+    ));
+
+    assert!(actual.err.contains("Can't convert to Closure"));
+}
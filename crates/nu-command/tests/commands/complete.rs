@@ -0,0 +1,48 @@
+use nu_test_support::{nu, pipeline};
+
+#[cfg(unix)]
+#[test]
+fn complete_reports_normal_exit() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            do { sh -c 'exit 3' } | complete | select exit_code signal core_dumped status | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        r#"{exit_code: 3, signal: null, core_dumped: false, status: "exited with code 3"}"#
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn complete_reports_signal() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            do { sh -c 'kill -9 $$' } | complete | select exit_code signal core_dumped status | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        r#"{exit_code: -9, signal: 9, core_dumped: false, status: "killed by signal SIGKILL"}"#
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn complete_reports_resource_usage() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            do { sh -c 'sleep 0.2' } | complete
+            | [($in.duration >= 200ms) ($in.max_rss > 0b) ($in.user_time | describe) ($in.system_time | describe)]
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[true, true, duration, duration]");
+}
@@ -0,0 +1,30 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn counts_env_reads() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        let-env PROJECTS = "~/projects";
+        ['cd $env.PROJECTS' 'ls $env.PROJECTS | length' 'ls']
+        | debug env-usage
+        | where name == PROJECTS
+        | get uses.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "2");
+}
+
+#[test]
+fn lists_unread_variables() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        let-env NEVER_READ = 1;
+        ['echo $env.PWD'] | debug env-usage --unused | get name | any {|name| $name == NEVER_READ }
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
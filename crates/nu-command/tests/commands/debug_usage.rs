@@ -0,0 +1,56 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn counts_alias_uses() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        alias ll = ls -l;
+        ["ll" "ll -a | length" "ls"] | debug usage | where name == ll | get uses.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "2");
+}
+
+#[test]
+fn counts_custom_commands_in_closures() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        def greet [name] { $"hello ($name)" };
+        ["ls | each {|it| greet $it.name }"] | debug usage | where name == greet | get uses.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "1");
+}
+
+#[test]
+fn lists_unused_aliases() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        alias never-used = ls;
+        [] | debug usage --unused | any {|row| $row.name == never-used }
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn ranks_externals_by_calls() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        ["git status" "^git log" "cargo build" "{a: 1} | to json"]
+        | debug usage
+        | where kind == external
+        | get name
+        | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"[git, cargo]"#);
+}
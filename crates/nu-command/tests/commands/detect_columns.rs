@@ -0,0 +1,49 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn detects_headers() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        "name size\na 1\nb 2" | detect columns | get size | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "1,2");
+}
+
+#[test]
+fn max_splits_keeps_the_rest_of_the_row() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        "PID CMD\n1 /sbin/init splash\n42 nu --login" | detect columns --max-splits 1 | get CMD | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["/sbin/init splash", "nu --login"]"#);
+}
+
+#[test]
+fn minimum_spaces_keeps_single_spaces() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        "NAME    STATUS\nweb app    Up 2 hours" | detect columns --minimum-spaces 2 | get 0 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"{NAME: "web app", STATUS: "Up 2 hours"}"#);
+}
+
+#[test]
+fn missing_cells_are_nothing() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        "a   b   c\n1       3" | detect columns | get 0.b | describe
+        "#
+    ));
+
+    assert_eq!(actual.out, "nothing");
+}
@@ -0,0 +1,86 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn compares_two_files_as_unified_diff() {
+    Playground::setup("diff_text_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent("old.txt", "a\nb\nc\n"),
+            FileWithContent("new.txt", "a\nc\nd\n"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                diff text old.txt new.txt --unified | ansi strip | lines | to nuon
+            "#
+        ));
+
+        assert_eq!(
+            actual.out,
+            r#"["--- old.txt", "+++ new.txt", "@@ -1,3 +1,3 @@", " a", "-b", " c", "+d"]"#
+        );
+    })
+}
+
+#[test]
+fn compares_piped_string_with_a_file() {
+    Playground::setup("diff_text_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("expected.txt", "one\ntwo\nthree\n")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                "one\n2\nthree" | diff text expected.txt
+                | get 0.lines
+                | each {|line| $"($line.type) ($line.old_line) ($line.new_line)" }
+                | str join ","
+            "#
+        ));
+
+        assert_eq!(actual.out, "context 1 1,removed 2 ,added  2,context 3 3");
+    })
+}
+
+#[test]
+fn separates_distant_changes_into_hunks() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1..20 | str join "\n"
+            | diff text --text (1..20 | each {|n| if $n in [2 19] { "x" } else { $n | into string } } | str join "\n") --context 1
+            | select old_start old_lines new_start new_lines
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[[old_start, old_lines, new_start, new_lines]; [1, 3, 1, 3], [18, 3, 18, 3]]"
+    );
+}
+
+#[test]
+fn identical_texts_have_no_changes() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "same" | diff text --text "same" | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "0");
+}
+
+#[test]
+fn errors_without_two_texts() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "only one" | diff text
+        "#
+    ));
+
+    assert!(actual.err.contains("Two texts are needed to compare"));
+}
@@ -91,3 +91,27 @@ fn each_while_uses_enumerate_index() {
 
     assert_eq!(actual.out, "[0, 1, 2, 3]");
 }
+
+#[test]
+fn each_passes_the_index_as_second_parameter() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [a b c] | each {|e, i| $"($i)($e)" } | str join ','
+        "#
+    ));
+
+    assert_eq!(actual.out, "0a,1b,2c");
+}
+
+#[test]
+fn each_break_stops_reading_an_endless_stream() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        1.. | each {|n| if $n > 3 { break } else { $n } } | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[1, 2, 3]");
+}
@@ -0,0 +1,50 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn runs_in_batches_of_max_args() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [a b c d e] | exec-batch --max-args 2 nu "--testbin" cococo | get stdout | str trim | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["a b", "c d", e]"#);
+}
+
+#[test]
+fn keeps_input_order_in_parallel() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1..8 | exec-batch -n 1 -p 4 nu "--testbin" cococo | get stdout | str trim | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "1,2,3,4,5,6,7,8");
+}
+
+#[cfg(unix)]
+#[test]
+fn passes_each_value_as_one_argument() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            ["with space" "it's" '"quoted"'] | exec-batch ^sh "-c" 'printf "[%s]" "$@"' sh | get 0.stdout
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"[with space][it's]["quoted"]"#);
+}
+
+#[test]
+fn reports_exit_codes() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [a] | exec-batch nu "--testbin" fail | get 0.exit_code
+        "#
+    ));
+
+    assert_eq!(actual.out, "1");
+}
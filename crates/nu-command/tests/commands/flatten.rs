@@ -182,3 +182,42 @@ fn flatten_more_than_one_column_that_are_subtables_not_supported() {
         assert!(actual.err.contains("but is flattened already"));
     })
 }
+
+#[test]
+fn flatten_dotted_names_nested_records_by_path() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [{a: {b: {c: 1}, d: [1 2]}, e: 2}]
+            | flatten --dotted
+            | columns
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[a.b.c, a.d, e]");
+}
+
+#[test]
+fn flatten_dotted_stops_at_depth() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {a: {b: {c: 1}}} | flatten --dotted --depth 1 --separator _ | columns | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[a_b]");
+}
+
+#[test]
+fn flatten_several_levels() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[[1 [2]]] [[3]]] | flatten --depth 2 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[1, [2], 3]");
+}
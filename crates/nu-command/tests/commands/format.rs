@@ -104,3 +104,111 @@ fn format_filesize_works_with_nonempty_files() {
         },
     )
 }
+
+#[test]
+fn format_filesize_picks_a_metric_unit_with_precision() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name size]; [disk 1999999999b]]
+            | format filesize auto size --metric --precision 3
+            | get 0.size
+        "#
+    ));
+
+    assert_eq!(actual.out, "2.000 GB");
+}
+
+#[test]
+fn format_filesize_rejects_unknown_units() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1kb | format filesize KiloBytes
+        "#
+    ));
+
+    assert!(actual.err.contains("kilobytes"));
+}
+
+#[test]
+fn format_number_uses_system_locale() {
+    let actual = nu!(
+        locale: "de_DE.UTF-8",
+        pipeline(
+            r#"
+            1234.5 | format number --group --precision 1
+            "#
+        )
+    );
+
+    assert_eq!(actual.out, "1.234,5");
+}
+
+#[test]
+fn format_number_drops_sign_of_rounded_zero() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [0.5 -0.001 -3] | format number --precision 2 --locale en | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["0.50", "0.00", "-3.00"]"#);
+}
+
+#[test]
+fn format_number_rejects_unknown_locale() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1 | format number --locale xx-YY
+        "#
+    ));
+
+    assert!(actual.err.contains("unknown locale"));
+}
+
+#[test]
+fn format_duration_round_trips_through_into_duration() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            let d = (3day + 2hr + 15min + 1500ms)
+            [
+                ($d | format duration human | into duration)
+                ($d | format duration iso | into duration)
+                ($d | into string | into duration)
+            ] | all { |it| $it == $d }
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn format_duration_rejects_unknown_unit() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1sec | format duration parsec
+        "#
+    ));
+
+    assert!(actual.err.contains("human or iso"));
+}
+
+#[test]
+fn into_duration_reads_compound_strings() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            ["1h 30m" "2 days, 4 hours" "PT0.25S"] | into duration | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[5400000000000ns, 187200000000000ns, 250000000ns]"
+    );
+}
@@ -110,3 +110,82 @@ fn errors_if_input_empty() {
         assert!(actual.err.contains("expected table from pipeline"));
     });
 }
+
+#[test]
+fn having_keeps_matching_groups() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name type]; [a x] [b y] [c x] [d z] [e x] [f y]]
+            | group-by type --having {|rows, key| ($rows | length) > 1 and $key != y}
+            | columns
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["x"]"#);
+}
+
+#[test]
+fn groups_by_several_columns_into_nested_records() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[region year sales]; [eu 2022 1] [us 2022 2] [eu 2022 3] [eu 2023 4]]
+            | group-by region year
+            | get eu
+            | columns
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["2022", "2023"]"#);
+}
+
+#[test]
+fn groups_to_table_with_key_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[region year sales]; [eu 2022 1] [us 2022 2] [eu 2022 3]]
+            | group-by region year --to-table
+            | update items {|group| $group.items | length}
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[[region, year, items]; [eu, 2022, 2], [us, 2022, 1]]"
+    );
+}
+
+#[test]
+fn groups_by_closure_returning_record() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name size]; [a.txt 10] [b.rs 2000] [c.txt 3000]]
+            | group-by {|| {ext: ($in.name | path parse | get extension), big: ($in.size > 1000)}} --to-table
+            | each {|group| $"($group.ext) ($group.big) ($group.items | length)"}
+            | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "txt false 1,rs true 1,txt true 1");
+}
+
+#[test]
+fn having_gets_record_key_for_several_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[region year]; [eu 2022] [us 2022] [eu 2023]]
+            | group-by region year --to-table --having {|rows, key| $key.region == eu}
+            | get year
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[2022, 2023]");
+}
@@ -47,3 +47,32 @@ fn into_int_binary() {
 
     assert!(actual.out.contains("16843009"));
 }
+
+#[test]
+fn into_int_lenient_cleans_table_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [[item qty price]; [a "1,200" "$ 3"] [b "(15)" "1.000.000"]]
+        | into int --lenient qty price
+        | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[[item, qty, price]; [a, 1200, 3], [b, -15, 1000000]]"
+    );
+}
+
+#[test]
+fn into_int_is_not_lenient_by_default() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        "1,200" | into int
+        "#
+    ));
+
+    assert!(actual.err.contains("Can't convert"));
+}
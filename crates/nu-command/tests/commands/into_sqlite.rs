@@ -0,0 +1,123 @@
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn queries_a_table_in_memory() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name size]; [a 1] [b 20] [c 30]]
+            | into sqlite
+            | query db "select name from main where size > 10"
+            | get name
+            | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "b,c");
+}
+
+#[test]
+fn in_memory_flag_rejects_a_file_name() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[a]; [1]] | into sqlite --in-memory foo.db
+        "#
+    ));
+
+    assert!(actual.err.contains("--in-memory"));
+}
+
+#[test]
+fn inserts_records_by_column_name() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [{a: 1, b: "x"} {b: "y", a: 2}]
+            | into sqlite
+            | query db "select b from main where a = 2"
+            | get b.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "y");
+}
+
+#[test]
+fn keeps_quotes_in_strings() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name]; ["it's"]]
+            | into sqlite
+            | query db "select name from main"
+            | get name.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "it's");
+}
+
+#[test]
+fn uses_the_given_column_types() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[price]; ["9.5"]]
+            | into sqlite --columns {price: REAL}
+            | query db "select typeof(price) as t from main"
+            | get t.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "real");
+}
+
+#[test]
+fn inserts_rows_in_batches() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1..25
+            | each {|i| {i: $i} }
+            | into sqlite --batch-size 10
+            | query db "select count(*) as n from main"
+            | get n.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "25");
+}
+
+#[test]
+fn appends_to_an_existing_table() {
+    Playground::setup("into_sqlite_test_1", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                [[a]; [1] [2]] | into sqlite test.db;
+                [[a]; [3]] | into sqlite test.db;
+                open test.db | query db "select count(*) as n from main" | get n.0
+            "#
+        ));
+
+        assert_eq!(actual.out, "3");
+    })
+}
+
+#[test]
+fn replaces_an_existing_table() {
+    Playground::setup("into_sqlite_test_2", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                [[a]; [1] [2]] | into sqlite test.db;
+                [[b]; [3]] | into sqlite test.db --replace;
+                open test.db | query db "select b from main" | get b.0
+            "#
+        ));
+
+        assert_eq!(actual.out, "3");
+    })
+}
@@ -0,0 +1,113 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn left_join_fills_missing_columns_with_null() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[id name]; [1 a] [2 b]]
+            | join --left [[id size]; [2 10]] id
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[id, name, size]; [1, a, null], [2, b, 10]]");
+}
+
+#[test]
+fn right_join_takes_shared_keys_from_the_right_table() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[id name]; [1 a]]
+            | join --right [[id size]; [1 10] [3 30]] id
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[id, name, size]; [1, a, 10], [3, null, 30]]");
+}
+
+#[test]
+fn outer_join_keeps_the_rows_of_both_tables() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[id name]; [1 a] [2 b]]
+            | join --outer [[id size]; [2 10] [3 20]] id
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[[id, name, size]; [1, a, null], [2, b, 10], [3, null, 20]]"
+    );
+}
+
+#[test]
+fn joins_on_several_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[a b]; [1 x] [1 y]]
+            | join [[a b c]; [1 y true]] [a b]
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[a, b, c]; [1, y, true]]");
+}
+
+#[test]
+fn suffixes_taken_column_names() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[id v]; [1 a]]
+            | join [[id v]; [1 b]] id
+            | columns
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[id, v, v_x]");
+}
+
+#[test]
+fn null_keys_never_match() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[id]; [null]]
+            | join [[id]; [null]] id
+            | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "0");
+}
+
+#[test]
+fn rejects_several_join_types() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[id]; [1]] | join --left --outer [[id]; [1]] id
+        "#
+    ));
+
+    assert!(actual.err.contains("only one of"));
+}
+
+#[test]
+fn rejects_columns_of_different_lengths() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[a b]; [1 2]] | join [[a b]; [1 2]] [a b] [a]
+        "#
+    ));
+
+    assert!(actual.err.contains("different size"));
+}
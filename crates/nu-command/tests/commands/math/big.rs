@@ -0,0 +1,76 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn big_pow_does_not_overflow() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            2 | math big pow 200
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "1606938044258990275541962092341162602522202993782792835301376"
+    );
+}
+
+#[test]
+fn big_sum_of_decimals_is_exact() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            ["0.1" "0.2" 0.3 1] | math big sum
+        "#
+    ));
+
+    assert_eq!(actual.out, "1.6");
+}
+
+#[test]
+fn big_results_can_be_chained() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            30 | math big factorial | math big div (29 | math big factorial)
+        "#
+    ));
+
+    assert_eq!(actual.out, "30");
+}
+
+#[test]
+fn big_div_by_zero() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1 | math big div 0
+        "#
+    ));
+
+    assert!(actual.err.contains("division by zero"));
+}
+
+#[test]
+fn big_rejects_strings_that_are_not_numbers() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "abc" | math big add 1
+        "#
+    ));
+
+    assert!(actual.err.contains("Can't convert to big number"));
+}
+
+#[test]
+fn big_factorial_rejects_fractions() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            2.5 | math big factorial
+        "#
+    ));
+
+    assert!(actual.err.contains("only computed for integers"));
+}
@@ -0,0 +1,82 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn bin_by_count_keeps_empty_bins() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [0 1 9 10] | math bin --count 5 | get count | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[2, 0, 0, 0, 2]");
+}
+
+#[test]
+fn bin_by_width_starts_at_a_multiple() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [12 17 31] | math bin --width 10 | get start | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[10.0, 20.0, 30.0]");
+}
+
+#[test]
+fn bin_of_identical_values() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [4 4 4] | math bin -c 2 | get count | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[3, 0]");
+}
+
+#[test]
+fn bin_rejects_count_and_width_together() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3] | math bin --count 2 --width 1
+        "#
+    ));
+
+    assert!(actual.err.contains("can't use --width"));
+}
+
+#[test]
+fn bin_rejects_non_numbers() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 a] | math bin
+        "#
+    ));
+
+    assert!(actual.err.contains("Only numbers can be put in bins"));
+}
+
+#[test]
+fn bin_rejects_too_many_bins() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [0 1000000] | math bin --width 0.001
+        "#
+    ));
+
+    assert!(actual.err.contains("Too many bins"));
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [0 1] | math bin --count 1000000000000
+        "#
+    ));
+
+    assert!(actual.err.contains("Too many bins"));
+}
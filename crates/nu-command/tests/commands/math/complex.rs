@@ -0,0 +1,66 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn complex_mul_multiplies_by_numbers_and_complex_numbers() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {re: 1, im: 2} | math complex mul 2 | math complex mul {re: 0, im: 1} | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "{re: -4.0, im: 2.0}");
+}
+
+#[test]
+fn complex_abs_and_arg_read_fft_rows() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [0 1 0 -1]
+            | math fft
+            | get 1
+            | [($in | math complex abs) ($in | math complex arg --degrees)]
+            | math round --precision 6
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[2.0, -90.0]");
+}
+
+#[test]
+fn complex_exp_of_an_imaginary_number_is_on_the_unit_circle() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {re: 0, im: 1.2} | math complex exp | math complex abs | math round --precision 9 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "1.0");
+}
+
+#[test]
+fn trigonometry_takes_complex_numbers() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {re: 0, im: 1} | math cos | get re | math round --precision 6
+        "#
+    ));
+
+    assert_eq!(actual.out, "1.543081");
+}
+
+#[test]
+fn complex_commands_reject_other_records() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {a: 1} | math complex conj
+        "#
+    ));
+
+    assert!(actual.err.contains("complex"));
+}
@@ -0,0 +1,100 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn cumsum_of_floats() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [0.5 1 1.5] | math cumsum | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[0.5, 1.5, 3.0]");
+}
+
+#[test]
+fn cumsum_of_filesizes() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1kb 2kb] | math cumsum | last | into int
+        "#
+    ));
+
+    assert_eq!(actual.out, "3000");
+}
+
+#[test]
+fn cumsum_keeps_the_table_shape() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [[name a b]; [x 1 10] [y 2 20] [z 3 30]] | math cumsum | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[[name, a, b]; [x, 1, 10], [y, 3, 30], [z, 6, 60]]"
+    );
+}
+
+#[test]
+fn cumsum_of_a_stream() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        1..5 | each { |it| $it } | math cumsum | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[1, 3, 6, 10, 15]");
+}
+
+#[test]
+fn cumprod_of_a_range() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        1..5 | math cumprod | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[1, 2, 6, 24, 120]");
+}
+
+#[test]
+fn cummax_of_strings() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [b a c] | math cummax | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[b, b, c]");
+}
+
+#[test]
+fn cumsum_rejects_strings() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1 a] | math cumsum | last
+        "#
+    ));
+
+    assert!(actual.err.contains("cannot be summed"));
+}
+
+#[test]
+fn cumsum_needs_a_list() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        5 | math cumsum
+        "#
+    ));
+
+    assert!(actual.err.contains("only_supports_this_input_type"));
+}
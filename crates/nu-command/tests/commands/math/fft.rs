@@ -0,0 +1,79 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn fft_finds_the_frequency_of_a_sine_wave() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            0..7 | each { |t| ($t * 2 * (math pi) / 8 * 2) | math sin }
+            | math fft --sample-rate 8
+            | first 4
+            | sort-by magnitude --reverse
+            | first
+            | get frequency
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "2.0");
+}
+
+#[test]
+fn fft_handles_lengths_that_are_not_powers_of_two() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 1 1] | math fft | get re | math round --precision 6 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[3.0, 0.0, 0.0]");
+}
+
+#[test]
+fn fft_takes_complex_records() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [{re: 0, im: 1} {re: 0, im: 1}] | math fft | get im | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[2.0, 0.0]");
+}
+
+#[test]
+fn ifft_inverts_fft() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [3 -1 4 1 5] | math fft | math ifft --real | math round --precision 6 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[3.0, -1.0, 4.0, 1.0, 5.0]");
+}
+
+#[test]
+fn fft_rejects_strings() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 a] | math fft
+        "#
+    ));
+
+    assert!(actual.err.contains("Samples must be numbers"));
+}
+
+#[test]
+fn fft_fails_on_empty_input() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [] | math fft
+        "#
+    ));
+
+    assert!(actual.err.contains("Empty input"));
+}
@@ -0,0 +1,45 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn fit_finds_the_coefficients_of_a_parabola() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [-2 -1 0 1 2]
+            | each { |x| {x: $x, y: (3 + $x * $x)} }
+            | math fit --degree 2
+            | get coefficients
+            | math round --precision 6
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[3.0, 0.0, 1.0]");
+}
+
+#[test]
+fn fit_reports_how_well_a_line_fits() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[size ms]; [1 1] [2 3] [3 2]]
+            | math fit --x size --y ms
+            | get r_squared
+            | math round --precision 6
+        "#
+    ));
+
+    assert_eq!(actual.out, "0.25");
+}
+
+#[test]
+fn fit_needs_more_points_than_the_degree() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[x y]; [0 1] [1 2]] | math fit --degree 2
+        "#
+    ));
+
+    assert!(actual.err.contains("at least 3 points"));
+}
@@ -0,0 +1,54 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn interpolate_resamples_unsorted_points_on_a_range() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[t v]; [4 8] [0 0] [2 6]]
+            | math interpolate 0..4 --x t --y v
+            | get v
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[0.0, 3.0, 6.0, 7.0, 8.0]");
+}
+
+#[test]
+fn interpolate_leaves_points_outside_of_the_data_empty() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[x y]; [0 0] [1 1]] | math interpolate [-1 0.5 2] | get y | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[null, 0.5, null]");
+}
+
+#[test]
+fn interpolate_with_a_spline_follows_the_curve() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[x y]; [0 0] [1 1] [2 0]]
+            | math interpolate [0.5] --method spline
+            | get 0.y
+        "#
+    ));
+
+    assert_eq!(actual.out, "0.6875");
+}
+
+#[test]
+fn interpolate_rejects_duplicate_x_values() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[x y]; [0 0] [0 1] [1 1]] | math interpolate [0.5]
+        "#
+    ));
+
+    assert!(actual.err.contains("distinct"));
+}
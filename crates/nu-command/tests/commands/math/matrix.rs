@@ -0,0 +1,97 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn multiply_by_identity_keeps_the_matrix() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[1 2] [3 4]] | math matrix multiply [[1 0] [0 1]] | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[1, 2], [3, 4]]");
+}
+
+#[test]
+fn multiply_checks_dimensions() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[1 2 3]] | math matrix multiply [[1 2]]
+        "#
+    ));
+
+    assert!(actual.err.contains("3 columns"));
+}
+
+#[test]
+fn inverting_twice_gives_the_matrix_back() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[4 7] [2 6]] | math matrix invert | math matrix invert | each { math round --precision 6 } | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[4.0, 7.0], [2.0, 6.0]]");
+}
+
+#[test]
+fn invert_fails_on_singular_matrix() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[1 2] [2 4]] | math matrix invert
+        "#
+    ));
+
+    assert!(actual.err.contains("Singular matrix"));
+}
+
+#[test]
+fn determinant_needs_a_square_matrix() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[1 2 3] [4 5 6]] | math matrix determinant
+        "#
+    ));
+
+    assert!(actual.err.contains("expected a square matrix"));
+}
+
+#[test]
+fn determinant_of_a_table() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[a b c]; [2 0 0] [0 3 0] [0 0 4]] | math matrix determinant | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "24.0");
+}
+
+#[test]
+fn solve_least_squares_fits_a_line() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[1 1] [1 2] [1 3] [1 4]] | math matrix solve [6 5 7 10] | math round --precision 2 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[3.5, 1.4]");
+}
+
+#[test]
+fn transpose_rejects_ragged_rows() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[1 2] [3]] | math matrix transpose
+        "#
+    ));
+
+    assert!(actual.err.contains("same length"));
+}
@@ -1,8 +1,20 @@
 mod avg;
+mod big;
+mod bin;
+mod complex;
+mod cumulative;
+mod fft;
+mod fit;
+mod interpolate;
+mod matrix;
 mod median;
+mod quantile;
 mod round;
 mod sqrt;
+mod stats;
 mod sum;
+mod units;
+mod variance;
 
 use nu_test_support::{nu, pipeline};
 
@@ -0,0 +1,61 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn quantile_interpolates_linearly() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [10 20 30 40] | math quantile 0.5 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "25.0");
+}
+
+#[test]
+fn quantile_with_nearest_method() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [10 20 30 40] | math quantile 0.4 --method nearest | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "20.0");
+}
+
+#[test]
+fn quantile_of_table_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [{a: 1, b: 10} {a: 3, b: 30} {a: 5, b: 50}] | math quantile 0.5 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "{a: 3.0, b: 30.0}");
+}
+
+#[test]
+fn quantile_rejects_out_of_range() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3] | math quantile 95
+        "#
+    ));
+
+    assert!(actual.err.contains("Quantiles go from 0 to 1"));
+}
+
+#[test]
+fn quantile_rejects_unknown_method() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3] | math quantile 0.5 -m cubic
+        "#
+    ));
+
+    assert!(actual.err.contains("linear, nearest or midpoint"));
+}
@@ -19,3 +19,43 @@ fn can_round_very_large_numbers_with_precision() {
 
     assert_eq!(actual.out, "18.137254478")
 }
+
+#[test]
+fn can_round_to_significant_figures() {
+    let actual = nu!(
+        cwd: ".",
+        "[987654 -0.00123456] | math round --significant 2 | str join ' '"
+    );
+
+    assert_eq!(actual.out, "990000 -0.0012")
+}
+
+#[test]
+fn can_round_halves_to_even() {
+    let actual = nu!(
+        cwd: ".",
+        "[-2.5 -1.5 3.5 45] | math round --mode half-even --precision 0 | str join ' '"
+    );
+
+    assert_eq!(actual.out, "-2 -2 4 45")
+}
+
+#[test]
+fn can_round_durations() {
+    let actual = nu!(
+        cwd: ".",
+        "1sec + 650ms | math round --precision -9 --mode floor"
+    );
+
+    assert_eq!(actual.out, "1sec")
+}
+
+#[test]
+fn can_not_round_to_precision_and_significant_figures() {
+    let actual = nu!(
+        cwd: ".",
+        "1.5 | math round --precision 1 --significant 2"
+    );
+
+    assert!(actual.err.contains("Incompatible parameters"))
+}
@@ -0,0 +1,55 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn stats_of_numbers() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [2 4 4 4 5 5 7 9]
+            | math stats
+            | select count mean median stddev min max
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "{count: 8, mean: 5, median: 4.5, stddev: 2.0, min: 2, max: 9}"
+    );
+}
+
+#[test]
+fn stats_with_custom_percentiles() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3 4 5 6 7 8 9 10 11] | math stats -p [10 90] | select p10 p90 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "{p10: 2.0, p90: 10.0}");
+}
+
+#[test]
+fn stats_of_table_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [{a: 1, b: 10} {a: 3, b: 30}] | math stats | get b.mean
+        "#
+    ));
+
+    assert_eq!(actual.out, "20");
+}
+
+#[test]
+fn stats_rejects_out_of_range_percentile() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3] | math stats -p [150]
+        "#
+    ));
+
+    assert!(actual.err.contains("Percentiles go from 0 to 100"));
+}
@@ -0,0 +1,76 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn convert_numbers_between_units() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [0 100] | math convert --from C --to K | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[273.15, 373.15]");
+}
+
+#[test]
+fn convert_quantities_with_their_own_units() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            ['2 kbit/s' '1Mbps'] | math convert --to B/s | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[250.0, 125000.0]");
+}
+
+#[test]
+fn convert_fails_across_dimensions() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1 | math convert --from kg --to m
+        "#
+    ));
+
+    assert!(actual.err.contains("Incompatible units"));
+}
+
+#[test]
+fn convert_fails_for_unknown_units() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1 | math convert --from furlong --to m
+        "#
+    ));
+
+    assert!(actual.err.contains("Unknown unit"));
+}
+
+#[test]
+fn into_unit_converts_table_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[item weight]; [box '2kg'] [bag '500 g']]
+            | into unit g weight
+            | get weight
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[2000.0, 500.0]");
+}
+
+#[test]
+fn units_lists_every_dimension_with_a_base_unit() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            math units | where factor == 1.0 and offset == 0.0 | get dimension | uniq | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "14");
+}
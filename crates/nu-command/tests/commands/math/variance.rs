@@ -0,0 +1,73 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn variance_of_a_stream() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1..100000 | each { |x| $x mod 4 } | math variance
+        "#
+    ));
+
+    assert_eq!(actual.out, "1.25");
+}
+
+#[test]
+fn sample_stddev_of_a_stream() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3 4 5] | each { |x| $x } | math stddev -s | math round -p 4
+        "#
+    ));
+
+    assert_eq!(actual.out, "1.5811");
+}
+
+#[test]
+fn population_is_the_default() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            ([1 2 3 4 5] | math variance --population) == ([1 2 3 4 5] | math variance)
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn variance_of_streamed_table_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[a b]; [1 x] [3 y]] | each { |row| $row } | math variance | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "{a: 1.0}");
+}
+
+#[test]
+fn sample_and_population_are_incompatible() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3] | math stddev --sample --population
+        "#
+    ));
+
+    assert!(actual.err.contains("but population requested here"));
+}
+
+#[test]
+fn sample_variance_of_a_single_value_fails() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [5] | each { |x| $x } | math variance -s
+        "#
+    ));
+
+    assert!(actual.err.contains("division by zero"));
+}
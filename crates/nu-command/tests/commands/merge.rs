@@ -124,3 +124,45 @@ fn multi_row_table_overwrite() {
         "[[a, b]; [7, 7], [8, 9], [9, 9]]"
     );
 }
+
+#[test]
+fn deep_merge_of_layered_config() {
+    assert_eq!(
+        nu!(
+            cwd: ".", pipeline(
+            r#"
+            {ls: {use_ls_colors: true, clickable_links: true}, menus: [a]}
+            | merge --deep {ls: {clickable_links: false}, menus: [b]}
+            | to nuon
+            "#
+        ))
+        .out,
+        "{ls: {use_ls_colors: true, clickable_links: false}, menus: [b]}"
+    );
+}
+
+#[test]
+fn deep_merge_of_table_rows() {
+    assert_eq!(
+        nu!(
+            cwd: ".", pipeline(
+            r#"
+            [{a: {b: 1, c: 2}}] | merge --deep [{a: {c: 3}}] | to nuon
+            "#
+        ))
+        .out,
+        "[{a: {b: 1, c: 3}}]"
+    );
+}
+
+#[test]
+fn lists_strategy_needs_deep() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        {a: [1]} | merge --lists append {a: [2]}
+        "#
+    ));
+
+    assert!(actual.err.contains("--lists only applies with --deep"));
+}
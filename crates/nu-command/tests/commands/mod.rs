@@ -1,3 +1,4 @@
+mod aggregate;
 mod alias;
 mod all;
 mod any;
@@ -7,11 +8,16 @@ mod break_;
 mod cal;
 mod cd;
 mod compact;
+mod complete;
 mod continue_;
 mod cp;
 mod date;
+mod debug_env_usage;
+mod debug_usage;
 mod def;
 mod default;
+mod detect_columns;
+mod diff;
 mod do_;
 mod drop;
 mod each;
@@ -22,6 +28,7 @@ mod error_make;
 mod every;
 #[cfg(not(windows))]
 mod exec;
+mod exec_batch;
 mod export_def;
 mod find;
 mod first;
@@ -39,6 +46,9 @@ mod histogram;
 mod insert;
 mod into_filesize;
 mod into_int;
+#[cfg(feature = "sqlite")]
+mod into_sqlite;
+mod join;
 mod last;
 mod length;
 mod let_;
@@ -55,7 +65,9 @@ mod network;
 mod nu_check;
 mod open;
 mod p;
+mod par_each;
 mod parse;
+mod patch;
 mod path;
 mod platform;
 mod prepend;
@@ -74,7 +86,9 @@ mod rm;
 mod roll;
 mod rotate;
 mod run_external;
+mod run_with_limit;
 mod save;
+mod search;
 mod select;
 mod semicolon;
 mod seq;
@@ -87,13 +101,20 @@ mod source_env;
 mod split_by;
 mod split_column;
 mod split_row;
+#[cfg(not(windows))]
+mod start;
 mod str_;
+mod sys;
 mod table;
 mod take;
+mod tee;
 mod to_text;
+mod toposort;
 mod touch;
 mod transpose;
+mod tree;
 mod try_;
+mod unflatten;
 mod uniq;
 mod uniq_by;
 mod update;
@@ -104,6 +125,7 @@ mod where_;
 #[cfg(feature = "which-support")]
 mod which;
 mod while_;
+mod window;
 mod with_env;
 mod wrap;
 mod zip;
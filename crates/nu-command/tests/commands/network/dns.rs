@@ -0,0 +1,166 @@
+use nu_test_support::{nu, pipeline};
+use std::io::{Read, Write};
+use std::net::{TcpListener, UdpSocket};
+use std::thread::JoinHandle;
+
+const NO_ERROR: [u8; 2] = [0x81, 0x80];
+const AUTHENTICATED: [u8; 2] = [0x81, 0xa0];
+const TRUNCATED: [u8; 2] = [0x83, 0x80];
+const NAME_ERROR: [u8; 2] = [0x81, 0x83];
+
+const A: u16 = 1;
+const MX: u16 = 15;
+
+// Answers one query with the given flags and records, all named after the question
+fn answer(query: &[u8], flags: [u8; 2], records: &[(u16, Vec<u8>)]) -> Vec<u8> {
+    let mut end = 12;
+    while query[end] != 0 {
+        end += query[end] as usize + 1;
+    }
+    // The end of the name, then its type and class
+    end += 5;
+
+    let mut response = query[..2].to_vec();
+    response.extend_from_slice(&flags);
+    response.extend_from_slice(&[0, 1, 0, records.len() as u8, 0, 0, 0, 0]);
+    response.extend_from_slice(&query[12..end]);
+    for (record_type, data) in records {
+        response.extend_from_slice(&[0xc0, 0x0c]);
+        response.extend_from_slice(&record_type.to_be_bytes());
+        response.extend_from_slice(&[0, 1]);
+        response.extend_from_slice(&300u32.to_be_bytes());
+        response.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        response.extend_from_slice(data);
+    }
+    response
+}
+
+fn serve_udp(
+    respond: impl FnOnce(&[u8]) -> Vec<u8> + Send + 'static,
+) -> (UdpSocket, u16, JoinHandle<()>) {
+    let server = UdpSocket::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = server.local_addr().expect("socket has an address").port();
+    let socket = server.try_clone().expect("failed to clone socket");
+
+    let handle = std::thread::spawn(move || {
+        let mut buffer = [0; 512];
+        let (size, from) = socket.recv_from(&mut buffer).expect("no query received");
+        socket
+            .send_to(&respond(&buffer[..size]), from)
+            .expect("failed to answer");
+    });
+
+    (server, port, handle)
+}
+
+#[test]
+fn dns_query_returns_records() {
+    let (_server, port, handle) =
+        serve_udp(|query| answer(query, NO_ERROR, &[(A, vec![93, 184, 216, 34])]));
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            dns query --server 127.0.0.1:{port} example.com
+            | get 0
+            | $"($in.name) ($in.type) ($in.ttl) ($in.data) ($in.authenticated)"
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "example.com. A 5min 93.184.216.34 false");
+}
+
+#[test]
+fn dns_query_asks_for_type() {
+    let (_server, port, handle) = serve_udp(|query| {
+        let mut exchange = 10u16.to_be_bytes().to_vec();
+        exchange.extend_from_slice(&[0xc0, 0x0c]);
+        answer(query, AUTHENTICATED, &[(MX, exchange)])
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            dns query --type mx --server 127.0.0.1:{port} example.com
+            | get 0
+            | $"($in.type) ($in.data) ($in.authenticated)"
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "MX 10 example.com. true");
+}
+
+#[test]
+fn dns_query_retries_truncated_answer_over_tcp() {
+    let (_server, port, handle) = serve_udp(|query| answer(query, TRUNCATED, &[]));
+    let listener = TcpListener::bind(("127.0.0.1", port)).expect("failed to listen");
+
+    let tcp_handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("no connection");
+        let mut length = [0; 2];
+        stream.read_exact(&mut length).expect("no query length");
+        let mut query = vec![0; u16::from_be_bytes(length) as usize];
+        stream.read_exact(&mut query).expect("no query");
+
+        let response = answer(&query, NO_ERROR, &[(A, vec![10, 0, 0, 1])]);
+        stream
+            .write_all(&(response.len() as u16).to_be_bytes())
+            .and_then(|_| stream.write_all(&response))
+            .expect("failed to answer");
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            dns query --server 127.0.0.1:{port} example.com | get data | str join ","
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+    tcp_handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "10.0.0.1");
+}
+
+#[test]
+fn dns_query_reports_error_answer() {
+    let (_server, port, handle) = serve_udp(|query| answer(query, NAME_ERROR, &[]));
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            dns query --server 127.0.0.1:{port} nope.example.com
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert!(actual.err.contains("Non-Existent Domain"));
+}
+
+#[test]
+fn dns_query_times_out() {
+    let server = UdpSocket::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = server.local_addr().expect("socket has an address").port();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            dns query --timeout 100ms --server 127.0.0.1:{port} example.com
+        "#)
+    ));
+
+    assert!(actual.err.contains("no answer within the timeout"));
+}
+
+#[test]
+fn dns_query_rejects_unknown_type() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            dns query --type nope --server 127.0.0.1 example.com
+        "#
+    ));
+
+    assert!(actual.err.contains("Unknown record type nope"));
+}
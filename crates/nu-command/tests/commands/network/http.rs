@@ -0,0 +1,686 @@
+use nu_test_support::fs::Stub::{EmptyFile, FileWithContent};
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+// Serves one request per response, in order
+fn serve(responses: Vec<String>) -> (u16, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener
+        .local_addr()
+        .expect("listener has an address")
+        .port();
+
+    let handle = std::thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().expect("failed to accept the request");
+
+            let mut reader =
+                BufReader::new(stream.try_clone().expect("failed to clone the stream"));
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok() && line != "\r\n" {
+                line.clear();
+            }
+
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (port, handle)
+}
+
+// Serves a single request with the given content type and a body sent in chunks
+fn serve_once(content_type: &str, chunks: &[&str]) -> (u16, JoinHandle<()>) {
+    let mut response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: {content_type}\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\n"
+    );
+    for chunk in chunks {
+        response.push_str(&format!("{:x}\r\n{chunk}\r\n", chunk.len()));
+    }
+    response.push_str("0\r\n\r\n");
+
+    serve(vec![response])
+}
+
+// Serves one request per call of respond, which gets the headers and the body of the request
+fn serve_requests(
+    count: usize,
+    respond: impl Fn(&[(String, String)], &[u8]) -> String + Send + 'static,
+) -> (u16, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener
+        .local_addr()
+        .expect("listener has an address")
+        .port();
+
+    let handle = std::thread::spawn(move || {
+        for _ in 0..count {
+            let (mut stream, _) = listener.accept().expect("failed to accept the request");
+
+            let mut reader =
+                BufReader::new(stream.try_clone().expect("failed to clone the stream"));
+            let mut headers = vec![];
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok() && line != "\r\n" {
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.to_lowercase(), value.trim().to_string()));
+                }
+                line.clear();
+            }
+
+            let content_length = header(&headers, "content-length").parse().unwrap_or(0);
+            let mut body = vec![0; content_length];
+            reader
+                .read_exact(&mut body)
+                .expect("failed to read the body");
+
+            let _ = stream.write_all(respond(&headers, &body).as_bytes());
+        }
+    });
+
+    (port, handle)
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> &'a str {
+    headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+        .unwrap_or("")
+}
+
+// Serves a single request, answering with its content type on the first line and its body after
+fn serve_echo() -> (u16, JoinHandle<()>) {
+    serve_requests(1, |headers, body| {
+        let body = format!(
+            "{}\n{}",
+            header(headers, "content-type"),
+            String::from_utf8_lossy(body)
+        );
+        status_response("200 OK", &body)
+    })
+}
+
+fn status_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\ncontent-type: text/plain\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[test]
+fn http_get_converts_body_by_content_type() {
+    let (port, handle) = serve_once("application/json", &[r#"{"a": "#, r#"[1, 2]}"#]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get http://127.0.0.1:{port}/ | get a | math sum
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "3");
+}
+
+#[test]
+fn http_get_raw_streams_body_with_content_type() {
+    let (port, handle) = serve_once("text/event-stream", &["data: one\n", "data: two\n"]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get --raw http://127.0.0.1:{port}/
+            | metadata
+            | get content_type
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "text/event-stream");
+}
+
+#[test]
+fn http_get_streams_body_lines() {
+    let (port, handle) = serve_once("text/event-stream", &["data: one\n", "data: two\n"]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get http://127.0.0.1:{port}/
+            | lines
+            | str join ','
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "data: one,data: two");
+}
+
+#[test]
+fn http_get_retries_on_status() {
+    let (port, handle) = serve(vec![
+        status_response("503 Service Unavailable", "busy"),
+        status_response("429 Too Many Requests", "slow down"),
+        status_response("200 OK", "done"),
+    ]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get --retries 2 --backoff 10ms http://127.0.0.1:{port}/
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "done");
+}
+
+#[test]
+fn http_get_gives_up_after_retries() {
+    let (port, handle) = serve(vec![
+        status_response("503 Service Unavailable", "busy"),
+        status_response("503 Service Unavailable", "busy"),
+    ]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get --retries 1 --backoff 10ms --retry-on [503] http://127.0.0.1:{port}/
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert!(actual.err.contains("503"));
+}
+
+#[test]
+fn http_get_does_not_retry_other_statuses() {
+    let (port, handle) = serve(vec![status_response("404 Not Found", "missing")]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get --retries 3 --backoff 10ms http://127.0.0.1:{port}/
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert!(actual.err.contains("404"));
+}
+
+#[test]
+fn http_get_full_returns_response_record() {
+    let (port, handle) = serve(vec![status_response("200 OK", "hello")]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get --full http://127.0.0.1:{port}/
+            | [$in.status $in.headers.content-type $in.body ($in.elapsed | describe) ($in.url | str ends-with ":{port}/")]
+            | to nuon
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "[200, text/plain, hello, duration, true]");
+}
+
+#[test]
+fn http_get_allow_errors_returns_error_body() {
+    let (port, handle) = serve(vec![status_response("404 Not Found", "missing")]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get --allow-errors http://127.0.0.1:{port}/
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "missing");
+    assert!(actual.err.is_empty());
+}
+
+#[test]
+fn http_post_full_allow_errors_reports_status() {
+    let (port, handle) = serve(vec![status_response("503 Service Unavailable", "busy")]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http post --full --allow-errors http://127.0.0.1:{port}/ 'body' | get status
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "503");
+}
+
+#[test]
+fn http_post_form_sends_multipart_body() {
+    let (port, handle) = serve_echo();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http post --form {{name: nu, data: 0x[61 62 63]}} http://127.0.0.1:{port}/
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert!(actual.out.starts_with("multipart/form-data; boundary="));
+    assert!(actual.out.contains(r#"name="name""#));
+    assert!(actual.out.contains(r#"name="data"; filename="data""#));
+    assert!(actual.out.contains("application/octet-stream"));
+    assert!(actual.out.contains("abc"));
+}
+
+#[test]
+fn http_post_form_sets_part_filename_and_content_type() {
+    let (port, handle) = serve_echo();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http post --form {{file: {{content: "a,b", filename: data.csv, content_type: text/csv}}}} http://127.0.0.1:{port}/
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert!(actual.out.contains(r#"name="file"; filename="data.csv""#));
+    assert!(actual.out.contains("text/csv"));
+    assert!(actual.out.contains("a,b"));
+}
+
+#[test]
+fn http_post_form_with_body_fails() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            http post --form {a: 1} http://127.0.0.1:1/ body
+        "#
+    ));
+
+    assert!(actual
+        .err
+        .contains("--form can't be used together with a body"));
+}
+
+#[test]
+fn http_session_keeps_cookies_and_headers() {
+    let (port, handle) = serve_requests(2, |headers, _| {
+        if header(headers, "cookie").is_empty() {
+            "HTTP/1.1 200 OK\r\nset-cookie: sid=42; Path=/\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".into()
+        } else {
+            let body = format!(
+                "{} {}",
+                header(headers, "cookie"),
+                header(headers, "x-token")
+            );
+            status_response("200 OK", &body)
+        }
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            let s = (http session create -H {{x-token: abc}});
+            http get --session $s http://127.0.0.1:{port}/login | ignore;
+            http get --session $s http://127.0.0.1:{port}/data
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "sid=42 abc");
+}
+
+fn free_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    listener
+        .local_addr()
+        .expect("listener has an address")
+        .port()
+}
+
+// Sends a raw request to `http serve` once it listens, and returns the whole response
+fn request(port: u16, request: String) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        for _ in 0..100 {
+            if let Ok(mut stream) = TcpStream::connect(("127.0.0.1", port)) {
+                stream
+                    .write_all(request.as_bytes())
+                    .expect("failed to send the request");
+                let mut response = String::new();
+                let _ = stream.read_to_string(&mut response);
+                return response;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        panic!("http serve never listened");
+    })
+}
+
+fn get(port: u16, path: &str) -> JoinHandle<String> {
+    request(
+        port,
+        format!("GET {path} HTTP/1.0\r\nhost: localhost\r\n\r\n"),
+    )
+}
+
+#[test]
+fn http_serve_serves_files() {
+    Playground::setup("http_serve_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("hello.txt", "hello from nu")]);
+        let port = free_port();
+        let client = get(port, "/hello.txt");
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            &format!(r#"
+                http serve --port {port} | first 1 | get 0 | $"($in.method) ($in.url) ($in.status)"
+            "#)
+        ));
+        let response = client.join().expect("client thread failed");
+
+        assert_eq!(actual.out, "GET /hello.txt 200");
+        assert!(response.starts_with("HTTP/1.0 200"));
+        assert!(response.to_lowercase().contains("content-type: text/plain"));
+        assert!(response.ends_with("hello from nu"));
+    })
+}
+
+#[test]
+fn http_serve_lists_directories_as_json() {
+    Playground::setup("http_serve_test_2", |dirs, sandbox| {
+        sandbox
+            .mkdir("public")
+            .with_files(vec![EmptyFile("public/b.txt"), EmptyFile("public/a.txt")]);
+        let port = free_port();
+        let client = get(port, "/?format=json");
+
+        nu!(
+            cwd: dirs.test(), pipeline(
+            &format!(r#"
+                http serve public --port {port} | first 1
+            "#)
+        ));
+        let response = client.join().expect("client thread failed");
+
+        assert!(response
+            .to_lowercase()
+            .contains("content-type: application/json"));
+        assert!(response.contains(r#"[{"name": "a.txt","type": "file","size": "#));
+        assert!(response.contains(r#"{"name": "b.txt""#));
+    })
+}
+
+#[test]
+fn http_serve_lists_directories_as_html() {
+    Playground::setup("http_serve_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("a <b>.txt")]);
+        let port = free_port();
+        let client = get(port, "/");
+
+        nu!(
+            cwd: dirs.test(), pipeline(
+            &format!(r#"
+                http serve --port {port} | first 1
+            "#)
+        ));
+        let response = client.join().expect("client thread failed");
+
+        assert!(response.contains(r#"<a href="a%20%3Cb%3E.txt">a &lt;b&gt;.txt</a>"#));
+    })
+}
+
+#[test]
+fn http_serve_refuses_paths_outside_of_the_directory() {
+    Playground::setup("http_serve_test_4", |dirs, sandbox| {
+        sandbox
+            .mkdir("public")
+            .with_files(vec![EmptyFile("secret.txt")]);
+        let port = free_port();
+        let client = std::thread::spawn(move || {
+            let forbidden = get(port, "/%2e%2e/secret.txt").join();
+            let missing = get(port, "/missing.txt").join();
+            (forbidden, missing)
+        });
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            &format!(r#"
+                http serve public --port {port} | first 2 | get status | str join ","
+            "#)
+        ));
+        let (forbidden, missing) = client.join().expect("client thread failed");
+
+        assert_eq!(actual.out, "403,404");
+        assert!(forbidden
+            .expect("client failed")
+            .starts_with("HTTP/1.0 403"));
+        assert!(missing.expect("client failed").starts_with("HTTP/1.0 404"));
+    })
+}
+
+#[test]
+fn http_serve_answers_with_the_handler() {
+    let port = free_port();
+    let client = request(
+        port,
+        "POST /items?name=nu HTTP/1.0\r\nhost: localhost\r\ncontent-length: 5\r\n\r\nhello".into(),
+    );
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http serve --port {port} --handler {{|req|
+                {{status: 201, headers: {{x-served-by: nu}}, body: {{method: $req.method, path: $req.path, name: $req.query.name, body: $req.body}}}}
+            }}
+            | first 1
+            | get 0.status
+        "#)
+    ));
+    let response = client.join().expect("client thread failed");
+
+    assert_eq!(actual.out, "201");
+    assert!(response.starts_with("HTTP/1.0 201"));
+    assert!(response.to_lowercase().contains("x-served-by: nu"));
+    assert!(response
+        .to_lowercase()
+        .contains("content-type: application/json"));
+    assert!(
+        response.ends_with(r#"{"method": "POST","path": "/items","name": "nu","body": "hello"}"#)
+    );
+}
+
+#[test]
+fn http_serve_falls_back_to_files_when_the_handler_returns_nothing() {
+    Playground::setup("http_serve_test_5", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("index.html", "<p>home</p>")]);
+        let port = free_port();
+        let client = std::thread::spawn(move || {
+            let api = get(port, "/api").join();
+            let home = get(port, "/").join();
+            (api, home)
+        });
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            &format!(r#"
+                http serve --port {port} --handler {{|req| if $req.path == "/api" {{ "api" }} }}
+                | first 2
+                | get status
+                | str join ","
+            "#)
+        ));
+        let (api, home) = client.join().expect("client thread failed");
+
+        assert_eq!(actual.out, "200,200");
+        assert!(api.expect("client failed").ends_with("\r\n\r\napi"));
+        let home = home.expect("client failed");
+        assert!(home.to_lowercase().contains("content-type: text/html"));
+        assert!(home.ends_with("<p>home</p>"));
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn http_serve_refuses_links_outside_of_the_directory() {
+    Playground::setup("http_serve_test_6", |dirs, sandbox| {
+        sandbox
+            .mkdir("public")
+            .with_files(vec![FileWithContent("secret.txt", "secret")])
+            .symlink("secret.txt", "public/link.txt");
+        let port = free_port();
+        let client = get(port, "/link.txt");
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            &format!(r#"
+                http serve public --port {port} | first 1 | get 0.status
+            "#)
+        ));
+        let response = client.join().expect("client thread failed");
+
+        assert_eq!(actual.out, "403");
+        assert!(!response.contains("secret"));
+    })
+}
+
+#[test]
+fn http_serve_keeps_handler_errors_out_of_the_response() {
+    let port = free_port();
+    let client = get(port, "/");
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http serve --port {port} --handler {{|req| error make {{msg: "database password is wrong"}} }}
+            | first 1
+            | get 0
+            | $"($in.status) ($in.error)"
+        "#)
+    ));
+    let response = client.join().expect("client thread failed");
+
+    assert!(actual.out.starts_with("500 "));
+    assert!(actual.out.contains("database password is wrong"));
+    assert!(response.starts_with("HTTP/1.0 500"));
+    assert!(response.ends_with("Internal server error"));
+    assert!(!response.contains("password"));
+}
+
+#[test]
+fn http_serve_rejects_invalid_port() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            http serve --port 70000
+        "#
+    ));
+
+    assert!(actual.err.contains("not a port number"));
+}
+
+#[test]
+fn http_get_through_proxy() {
+    // The proxy is asked for the whole URL, which it would fetch from the server
+    let (port, handle) = serve_requests(1, |headers, _| {
+        status_response("200 OK", &format!("via proxy {}", header(headers, "host")))
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            http get --proxy http://127.0.0.1:{port} http://nushell.invalid/data.txt
+        "#)
+    ));
+    handle.join().expect("proxy thread failed");
+
+    assert_eq!(actual.out, "via proxy nushell.invalid");
+}
+
+#[cfg(unix)]
+#[test]
+fn http_get_over_unix_socket() {
+    use std::os::unix::net::UnixListener;
+
+    Playground::setup("http_unix_socket_test_1", |dirs, _| {
+        let listener =
+            UnixListener::bind(dirs.test().join("api.sock")).expect("failed to bind the socket");
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("failed to accept the request");
+            let mut reader =
+                BufReader::new(stream.try_clone().expect("failed to clone the stream"));
+            let mut request_line = String::new();
+            reader
+                .read_line(&mut request_line)
+                .expect("failed to read the request");
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok() && line != "\r\n" {
+                line.clear();
+            }
+
+            let body = format!(r#"{{"request": "{}"}}"#, request_line.trim());
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        });
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                http get --unix-socket api.sock http://localhost/containers/json | get request
+            "#
+        ));
+        handle.join().expect("server thread failed");
+
+        assert_eq!(actual.out, "GET http://localhost/containers/json HTTP/1.1");
+    })
+}
+
+#[test]
+fn http_get_proxy_and_unix_socket_are_incompatible() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            http get --proxy http://127.0.0.1:3128 --unix-socket api.sock http://localhost
+        "#
+    ));
+
+    assert!(actual.err.contains("Incompatible parameters"));
+}
+
+#[test]
+fn http_get_key_needs_cert() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            http get --key client.key https://localhost
+        "#
+    ));
+
+    assert!(actual.err.contains("--key needs the client certificate"));
+}
+
+#[test]
+fn http_get_rejects_invalid_cacert() {
+    Playground::setup("http_cacert_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("ca.pem", "not a certificate")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                http get --cacert ca.pem https://localhost
+            "#
+        ));
+
+        assert!(actual.err.contains("Invalid certificate authority"));
+    })
+}
@@ -1 +1,9 @@
+mod dns;
+mod http;
 mod port;
+#[cfg(feature = "sftp")]
+mod sftp;
+mod smtp;
+mod tcp;
+mod udp;
+mod ws;
@@ -1,6 +1,8 @@
 use nu_test_support::{nu, pipeline};
-use std::net::TcpListener;
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
 use std::sync::mpsc;
+use std::time::Duration;
 
 #[test]
 fn port_with_invalid_range() {
@@ -56,3 +58,79 @@ fn port_from_system_given() {
     // check that we can get an integer port from system.
     assert!(actual.out.parse::<u16>().unwrap() > 0)
 }
+
+fn free_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    listener.local_addr().unwrap().port()
+}
+
+#[test]
+fn port_scan_reports_open_and_closed_ports() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let open_port = listener.local_addr().unwrap().port();
+    let closed_port = free_port();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            [(port scan 127.0.0.1 {open_port}) (port scan 127.0.0.1 {closed_port}..{closed_port})]
+            | flatten
+            | each {{|row| $"($row.port) ($row.open)" }}
+            | str join ","
+        "#)
+    ));
+
+    assert_eq!(actual.out, format!("{open_port} true,{closed_port} false"));
+}
+
+#[test]
+fn port_scan_rejects_invalid_port() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            port scan 127.0.0.1 70000
+        "#
+    ));
+
+    assert!(actual.err.contains("not a port number"));
+}
+
+#[test]
+fn port_listen_streams_received_data() {
+    let port = free_port();
+
+    let client = std::thread::spawn(move || {
+        for _ in 0..50 {
+            if let Ok(mut stream) = TcpStream::connect(("127.0.0.1", port)) {
+                stream.write_all(b"hello").expect("failed to send");
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        panic!("port listen never listened");
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            port listen {port} --timeout 10sec
+        "#)
+    ));
+    client.join().expect("client thread failed");
+
+    assert_eq!(actual.out, "hello");
+}
+
+#[test]
+fn port_listen_times_out() {
+    let port = free_port();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            port listen {port} --timeout 200ms
+        "#)
+    ));
+
+    assert!(actual.err.contains("within the timeout"));
+}
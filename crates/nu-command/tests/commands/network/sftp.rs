@@ -0,0 +1,79 @@
+use nu_test_support::{nu, pipeline};
+use std::io::Write;
+use std::net::TcpListener;
+
+#[test]
+fn sftp_ls_rejects_target_without_path() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            sftp ls example.com
+        "#
+    ));
+
+    assert!(actual.err.contains("expected [user@]host:path"));
+}
+
+#[test]
+fn sftp_get_rejects_empty_user() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            sftp get @example.com:file.txt
+        "#
+    ));
+
+    assert!(actual.err.contains("expected [user@]host:path"));
+}
+
+#[test]
+fn sftp_ls_reports_refused_connection() {
+    let port = {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+        listener.local_addr().unwrap().port()
+    };
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            sftp ls --port {port} me@127.0.0.1:/var/log
+        "#)
+    ));
+
+    assert!(actual.err.contains("Cannot connect to 127.0.0.1"));
+}
+
+#[test]
+fn sftp_put_reports_failed_handshake() {
+    // A server that does not speak SSH
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener.local_addr().unwrap().port();
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("failed to accept the connection");
+        let _ = stream.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n");
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "hello" | sftp put --port {port} --timeout 5sec me@[::ffff:127.0.0.1]:hello.txt
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert!(actual
+        .err
+        .contains("SSH handshake with ::ffff:127.0.0.1 failed"));
+}
+
+#[test]
+fn sftp_rejects_invalid_port() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            sftp ls --port 70000 example.com:
+        "#
+    ));
+
+    assert!(actual.err.contains("not a port number"));
+}
@@ -0,0 +1,124 @@
+use nu_test_support::{nu, pipeline};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+// Accepts a single SMTP session and answers every command with success. Returns the commands
+// received, and the message sent after DATA
+fn serve_smtp() -> (u16, JoinHandle<(Vec<String>, String)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener
+        .local_addr()
+        .expect("listener has an address")
+        .port();
+
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("failed to accept the connection");
+        let mut writer = stream.try_clone().expect("failed to clone the stream");
+        let mut reader = BufReader::new(stream);
+        writer.write_all(b"220 localhost ready\r\n").unwrap();
+
+        let mut commands = vec![];
+        let mut message = String::new();
+        let mut in_data = false;
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap_or(0) > 0 {
+            if in_data {
+                if line == ".\r\n" {
+                    in_data = false;
+                    writer.write_all(b"250 queued\r\n").unwrap();
+                } else {
+                    message.push_str(&line);
+                }
+            } else {
+                let command = line.trim_end().to_string();
+                let reply: &[u8] = if command.starts_with("DATA") {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if command.starts_with("QUIT") {
+                    b"221 bye\r\n"
+                } else {
+                    b"250 ok\r\n"
+                };
+                writer.write_all(reply).unwrap();
+                let quit = command.starts_with("QUIT");
+                commands.push(command);
+                if quit {
+                    break;
+                }
+            }
+            line.clear();
+        }
+
+        (commands, message)
+    });
+
+    (port, handle)
+}
+
+#[test]
+fn smtp_send_sends_the_input_as_body() {
+    let (port, handle) = serve_smtp();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "The disk is almost full"
+            | smtp send --to [ops@example.com dev@example.com] --subject "Disk usage" --from nu@example.com --server 127.0.0.1 --port {port} --security none
+        "#)
+    ));
+    let (commands, message) = handle.join().expect("server thread failed");
+
+    assert!(actual.err.is_empty(), "{}", actual.err);
+    assert!(commands.contains(&"MAIL FROM:<nu@example.com>".to_string()));
+    assert!(commands.contains(&"RCPT TO:<ops@example.com>".to_string()));
+    assert!(commands.contains(&"RCPT TO:<dev@example.com>".to_string()));
+    assert!(message.contains("Subject: Disk usage"));
+    assert!(message.contains("The disk is almost full"));
+}
+
+#[test]
+fn smtp_send_reads_the_server_from_the_environment() {
+    let (port, handle) = serve_smtp();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            let-env SMTP_SERVER = 127.0.0.1
+            let-env SMTP_PORT = {port}
+            let-env SMTP_SECURITY = none
+            let-env SMTP_FROM = nu@example.com
+            [first second] | smtp send --to ops@example.com -s Lines
+        "#)
+    ));
+    let (_, message) = handle.join().expect("server thread failed");
+
+    assert!(actual.err.is_empty(), "{}", actual.err);
+    assert!(message.contains("first"));
+    assert!(message.contains("second"));
+}
+
+#[test]
+fn smtp_send_requires_a_server() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            hide-env -i SMTP_SERVER;
+            "hi" | smtp send --to ops@example.com -s Hello --from nu@example.com
+        "#
+    ));
+
+    assert!(actual.err.contains("SMTP_SERVER"));
+}
+
+#[test]
+fn smtp_send_rejects_invalid_address() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "hi" | smtp send --to not-an-address -s Hello --from nu@example.com --server 127.0.0.1
+        "#
+    ));
+
+    assert!(actual.err.contains("Invalid email address not-an-address"));
+}
@@ -0,0 +1,117 @@
+use nu_test_support::{nu, pipeline};
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+// Accepts a single connection, and answers with what it received once the client is done
+fn serve_echo() -> (u16, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener
+        .local_addr()
+        .expect("listener has an address")
+        .port();
+
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("failed to accept the connection");
+
+        let mut data = String::new();
+        stream
+            .read_to_string(&mut data)
+            .expect("failed to read the data");
+        let _ = stream.write_all(format!("got: {data}").as_bytes());
+    });
+
+    (port, handle)
+}
+
+#[test]
+fn tcp_connect_sends_input_and_streams_reply() {
+    let (port, handle) = serve_echo();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "hello" | tcp connect 127.0.0.1:{port}
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "got: hello");
+}
+
+#[test]
+fn tcp_connect_sends_lists_as_lines() {
+    let (port, handle) = serve_echo();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            [one two] | tcp connect 127.0.0.1:{port} | lines | to nuon
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, r#"["got: one", two]"#);
+}
+
+#[test]
+fn tcp_connect_full_returns_connection_info() {
+    let (port, handle) = serve_echo();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "hi" | tcp connect --full 127.0.0.1:{port}
+            | [$in.remote_address $in.data ($in.local_address | str starts-with "127.0.0.1:") ($in.elapsed | describe)]
+            | to nuon
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(
+        actual.out,
+        format!(r#"["127.0.0.1:{port}", "got: hi", true, duration]"#)
+    );
+}
+
+#[test]
+fn tcp_listen_once_returns_client_data() {
+    // Pick a free port for nu to listen on
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("failed to pick a port")
+        .port();
+
+    let client = std::thread::spawn(move || {
+        let mut stream = loop {
+            match TcpStream::connect(("127.0.0.1", port)) {
+                Ok(stream) => break stream,
+                Err(_) => std::thread::sleep(Duration::from_millis(50)),
+            }
+        };
+        stream
+            .write_all(b"from client")
+            .expect("failed to send data");
+        stream
+            .shutdown(Shutdown::Write)
+            .expect("failed to close the connection");
+
+        let mut reply = String::new();
+        stream
+            .read_to_string(&mut reply)
+            .expect("failed to read the reply");
+        reply
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "from server" | tcp listen --once 127.0.0.1:{port} | get 0.data
+        "#)
+    ));
+    let reply = client.join().expect("client thread failed");
+
+    assert_eq!(actual.out, "from client");
+    assert_eq!(reply, "from server");
+}
@@ -0,0 +1,60 @@
+use nu_test_support::{nu, pipeline};
+use std::net::UdpSocket;
+
+#[test]
+fn udp_send_reports_bytes_sent() {
+    let server = UdpSocket::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = server.local_addr().expect("socket has an address").port();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "hello" | udp send 127.0.0.1:{port} | get bytes_sent
+        "#)
+    ));
+
+    let mut buffer = [0; 16];
+    let (size, _) = server.recv_from(&mut buffer).expect("no datagram received");
+
+    assert_eq!(actual.out, "5");
+    assert_eq!(&buffer[..size], b"hello");
+}
+
+#[test]
+fn udp_send_waits_for_reply() {
+    let server = UdpSocket::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = server.local_addr().expect("socket has an address").port();
+
+    let handle = std::thread::spawn(move || {
+        let mut buffer = [0; 16];
+        let (size, from) = server.recv_from(&mut buffer).expect("no datagram received");
+        let mut reply = b"re: ".to_vec();
+        reply.extend_from_slice(&buffer[..size]);
+        server.send_to(&reply, from).expect("failed to reply");
+    });
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "ping" | udp send --reply 127.0.0.1:{port} | get reply
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "re: ping");
+}
+
+#[test]
+fn udp_send_reply_times_out() {
+    let server = UdpSocket::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = server.local_addr().expect("socket has an address").port();
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            "ping" | udp send --reply --timeout 100ms 127.0.0.1:{port} | get reply | to nuon
+        "#)
+    ));
+
+    assert_eq!(actual.out, "null");
+}
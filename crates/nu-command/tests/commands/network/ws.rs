@@ -0,0 +1,93 @@
+use nu_test_support::{nu, pipeline};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+use tungstenite::handshake::server::{Request, Response};
+use tungstenite::Message;
+
+// Accepts a single WebSocket connection, reads the expected number of messages, then sends the
+// replies and closes it. Returns the authorization header of the handshake and the messages read
+fn serve_ws(expected: usize, replies: Vec<Message>) -> (u16, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener
+        .local_addr()
+        .expect("listener has an address")
+        .port();
+
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("failed to accept the connection");
+
+        let mut received = vec![];
+        let mut socket =
+            tungstenite::accept_hdr(stream, |request: &Request, response: Response| {
+                let authorization = request
+                    .headers()
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or("")
+                    .to_string();
+                received.push(authorization);
+                Ok(response)
+            })
+            .expect("failed to accept the WebSocket");
+
+        for _ in 0..expected {
+            match socket.read_message() {
+                Ok(Message::Text(text)) => received.push(text),
+                Ok(Message::Binary(data)) => received.push(format!("{data:?}")),
+                _ => break,
+            }
+        }
+
+        for reply in replies {
+            socket.write_message(reply).expect("failed to reply");
+        }
+        let _ = socket.close(None);
+        while socket.read_message().is_ok() {}
+
+        received
+    });
+
+    (port, handle)
+}
+
+#[test]
+fn ws_connect_streams_incoming_messages() {
+    let (port, handle) = serve_ws(
+        0,
+        vec![
+            Message::Text("one".into()),
+            Message::Binary(vec![1, 2]),
+            Message::Text("two".into()),
+        ],
+    );
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            ws connect ws://127.0.0.1:{port}/ | to nuon
+        "#)
+    ));
+    handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "[one, 0x[0102], two]");
+}
+
+#[test]
+fn ws_connect_sends_input_and_headers() {
+    let (port, handle) = serve_ws(3, vec![Message::Text("done".into())]);
+
+    let actual = nu!(
+        cwd: ".", pipeline(
+        &format!(r#"
+            ["hello" 0x[03] {{a: 1}}] | ws connect -H {{Authorization: "Bearer token"}} ws://127.0.0.1:{port}/
+            | first
+        "#)
+    ));
+    let received = handle.join().expect("server thread failed");
+
+    assert_eq!(actual.out, "done");
+    assert_eq!(
+        received,
+        vec!["Bearer token", "hello", "[3]", r#"{"a": 1}"#]
+    );
+}
@@ -1,4 +1,5 @@
 use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::fs::Stub::FileWithContent;
 use nu_test_support::fs::Stub::FileWithContentToBeTrimmed;
 use nu_test_support::playground::Playground;
 use nu_test_support::{nu, pipeline};
@@ -311,3 +312,54 @@ fn open_no_parameter() {
 
     assert!(actual.err.contains("needs filename"));
 }
+
+#[test]
+fn open_per_line_gives_byte_offsets() {
+    Playground::setup("open_test_per_line_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("log.txt", "first\r\nsecond\nthird")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                open log.txt --per-line | to nuon
+            "#
+        ));
+
+        assert_eq!(
+            actual.out,
+            "[[line, offset, length]; [first, 0, 5], [second, 7, 6], [third, 14, 5]]"
+        );
+    })
+}
+
+#[test]
+fn open_per_line_resumes_from_an_offset() {
+    Playground::setup("open_test_per_line_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("log.txt", "first\r\nsecond\nthird")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                open log.txt --per-line --start 7 | each { |it| $"($it.offset):($it.line)" } | str join ','
+            "#
+        ));
+
+        assert_eq!(actual.out, "7:second,14:third");
+    })
+}
+
+#[test]
+fn open_start_needs_per_line() {
+    Playground::setup("open_test_per_line_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("log.txt", "first")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                open log.txt --start 2
+            "#
+        ));
+
+        assert!(actual.err.contains("--start needs --per-line"));
+    })
+}
@@ -0,0 +1,84 @@
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn keeps_the_order_of_a_long_input() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        1..1000 | par-each --threads 8 --keep-order {|n| $n * 2 } | enumerate | all {|row| $row.item == ($row.index + 1) * 2 }
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn skips_the_rows_the_closure_fails_on() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [2 0 5 0 10] | par-each -k --on-error skip {|n| 10 / $n } | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[5, 2, 1]");
+}
+
+#[test]
+fn aborts_on_the_first_error() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [2 0 5] | par-each --on-error abort {|n| 10 / $n } | to nuon
+        "#
+    ));
+
+    assert!(actual.out.is_empty());
+    assert!(actual.err.contains("Division by zero"));
+}
+
+#[test]
+fn abort_starts_no_rows_after_the_first_error() {
+    Playground::setup("par_each_abort_test", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+            do -i {
+                1..100 | par-each --threads 1 --on-error abort {|n|
+                    $"($n)\n" | save --append rows.txt;
+                    if $n == 3 { error make {msg: "boom"} } else { $n }
+                }
+            };
+            open rows.txt | lines | length
+            "#
+        ));
+
+        let rows: usize = actual.out.parse().unwrap_or(100);
+        assert!(rows < 100);
+    })
+}
+
+#[test]
+fn rejects_an_unknown_error_policy() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1 2] | par-each --on-error ignore {|n| $n }
+        "#
+    ));
+
+    assert!(actual.err.contains("expected collect, skip or abort"));
+}
+
+#[test]
+fn rejects_zero_threads() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1 2] | par-each --threads 0 {|n| $n }
+        "#
+    ));
+
+    assert!(actual.err.contains("positive"));
+}
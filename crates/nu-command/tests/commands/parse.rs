@@ -199,4 +199,69 @@ mod regex {
 
         assert_eq!(actual.out, "table<letter: string, a: string> (stream)")
     }
+
+    #[test]
+    fn converts_typed_captures() {
+        let actual = nu!(
+            cwd: ".", pipeline(
+            r#"
+                "took 12ms, sent 3KB ok" | parse -r 'took (?P<time:duration>\S+), sent (?P<size:filesize>\S+) (?<ok>\w+)' --types {ok: string}
+                | to nuon
+            "#
+        ));
+
+        assert_eq!(actual.out, "[[time, size, ok]; [12000000ns, 3000b, ok]]");
+    }
+
+    #[test]
+    fn converts_typed_columns_of_simple_patterns() {
+        let actual = nu!(
+            cwd: ".", pipeline(
+            r#"
+                ["a 1.5 true" "b 2 false"] | parse "{name} {score:float} {passed:bool}"
+                | get score passed
+                | to nuon
+            "#
+        ));
+
+        assert_eq!(actual.out, "[[1.5, 2.0], [true, false]]");
+    }
+
+    #[test]
+    fn captures_that_cannot_be_converted_are_errors() {
+        let actual = nu!(
+            cwd: ".", pipeline(
+            r#"
+                "n=abc" | parse "n={n:int}" | get n.0
+            "#
+        ));
+
+        assert!(actual.err.contains("Can't convert"));
+    }
+
+    #[test]
+    fn errors_with_unknown_capture_type() {
+        let actual = nu!(
+            cwd: ".", pipeline(
+            r#"
+                "n=1" | parse -r 'n=(?P<n:number>\d)'
+            "#
+        ));
+
+        assert!(actual
+            .err
+            .contains("int, float, bool, filesize, duration or string"));
+    }
+
+    #[test]
+    fn errors_when_typed_column_is_not_in_pattern() {
+        let actual = nu!(
+            cwd: ".", pipeline(
+            r#"
+                "n=1" | parse "n={n}" --types {m: int}
+            "#
+        ));
+
+        assert!(actual.err.contains("Cannot find column"));
+    }
 }
@@ -0,0 +1,130 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn applies_patch_to_files() {
+    Playground::setup("patch_apply_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent("numbers.txt", "one\ntwo\nthree\n"),
+            FileWithContent(
+                "fix.patch",
+                "--- numbers.txt\n+++ numbers.txt\n@@ -2,1 +2,1 @@\n-two\n+2\n",
+            ),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                patch apply fix.patch | select file hunk status line offset | to nuon
+            "#
+        ));
+
+        assert_eq!(
+            actual.out,
+            "[[file, hunk, status, line, offset]; [numbers.txt, 1, applied, 2, 0]]"
+        );
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                open numbers.txt | lines | str join ","
+            "#
+        ));
+
+        assert_eq!(actual.out, "one,2,three");
+    })
+}
+
+#[test]
+fn dry_run_reports_rejects_without_writing() {
+    Playground::setup("patch_apply_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("numbers.txt", "one\ntwo\nthree\n")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                patch apply --dry-run --diff "--- numbers.txt\n+++ numbers.txt\n@@ -1 +1 @@\n-uno\n+1\n@@ -3 +3 @@\n-three\n+3\n"
+                | get status
+                | str join ","
+            "#
+        ));
+
+        assert_eq!(actual.out, "rejected,applied");
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                open numbers.txt | lines | str join ","
+            "#
+        ));
+
+        assert_eq!(actual.out, "one,two,three");
+    })
+}
+
+#[test]
+fn creates_files_with_stripped_names() {
+    Playground::setup("patch_apply_test_3", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                patch apply --strip 1 --diff "--- /dev/null\n+++ b/new/file.txt\n@@ -0,0 +1,2 @@\n+hello\n+world\n"
+                | ignore;
+                open new/file.txt | lines | str join ","
+            "#
+        ));
+
+        assert_eq!(actual.out, "hello,world");
+    })
+}
+
+#[test]
+fn applies_output_of_diff_text_to_piped_string() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "a\nb\nc" | patch apply --diff ("a\nb\nc" | diff text --text "a\nx\nc" --unified)
+            | lines
+            | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "a,x,c");
+}
+
+#[test]
+fn errors_when_piped_string_does_not_match() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "a" | patch apply --diff "@@ -1 +1 @@\n-b\n+c"
+        "#
+    ));
+
+    assert!(actual.err.contains("The patch doesn't apply"));
+}
+
+#[test]
+fn refuses_files_outside_the_current_directory() {
+    Playground::setup("patch_apply_test_outside", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                patch apply --diff "--- /dev/null\n+++ ../outside.txt\n@@ -0,0 +1 @@\n+hello\n"
+            "#
+        ));
+
+        assert!(actual.err.contains("outside the current directory"));
+        assert!(!dirs.test().join("../outside.txt").exists());
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                patch apply --diff "--- /dev/null\n+++ /tmp/nu_patch_absolute.txt\n@@ -0,0 +1 @@\n+hello\n"
+            "#
+        ));
+
+        assert!(actual.err.contains("outside the current directory"));
+    })
+}
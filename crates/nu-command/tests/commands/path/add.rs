@@ -0,0 +1,77 @@
+use nu_test_support::{nu, pipeline};
+
+#[cfg(unix)]
+#[test]
+fn adds_directories_at_the_start() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.PATH = [/usr/bin /bin];
+            path add /opt/a/bin /opt/b/bin;
+            $env.PATH | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        r#"["/opt/a/bin", "/opt/b/bin", "/usr/bin", "/bin"]"#
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn appends_directories() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.PATH = [/usr/bin /bin];
+            path add --append /opt/bin;
+            $env.PATH | last
+        "#
+    ));
+
+    assert_eq!(actual.out, "/opt/bin");
+}
+
+#[cfg(unix)]
+#[test]
+fn moves_a_directory_already_in_path() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.PATH = [/usr/bin /bin/];
+            path add /bin;
+            $env.PATH | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["/bin", "/usr/bin"]"#);
+}
+
+#[cfg(unix)]
+#[test]
+fn handles_path_as_a_string() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.PATH = "/usr/bin:/bin";
+            path add /opt/bin;
+            $env.PATH | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["/opt/bin", "/usr/bin", "/bin"]"#);
+}
+
+#[test]
+fn makes_directories_absolute() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            path add bin;
+            ($env.PATH | first) == ($env.PWD | path join bin)
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
@@ -0,0 +1,16 @@
+use nu_test_support::{nu, pipeline};
+
+#[cfg(unix)]
+#[test]
+fn keeps_the_first_entry_of_each_directory() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.PATH = [/usr/bin /bin /usr/bin/ /opt/bin /bin];
+            path dedupe;
+            $env.PATH | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["/usr/bin", "/bin", "/opt/bin"]"#);
+}
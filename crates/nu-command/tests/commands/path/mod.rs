@@ -1,9 +1,12 @@
+mod add;
 mod basename;
+mod dedupe;
 mod dirname;
 mod exists;
 mod expand;
 mod join;
 mod parse;
+mod remove;
 mod split;
 mod type_;
 
@@ -0,0 +1,46 @@
+use nu_test_support::{nu, pipeline};
+
+#[cfg(unix)]
+#[test]
+fn removes_every_entry_of_a_directory() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.PATH = [/opt/bin /usr/bin /opt/bin/ /bin];
+            path remove /opt/bin;
+            $env.PATH | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["/usr/bin", "/bin"]"#);
+}
+
+#[cfg(unix)]
+#[test]
+fn ignores_directories_not_in_path() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.PATH = [/usr/bin /bin];
+            path remove /nowhere;
+            $env.PATH | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "2");
+}
+
+#[cfg(windows)]
+#[test]
+fn ignores_case_on_windows() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $env.Path = ['C:\Tools' 'C:\Windows'];
+            path remove 'c:\tools\';
+            $env.Path | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["C:\\Windows"]"#);
+}
@@ -1,2 +1,3 @@
 mod du;
 mod kill;
+mod signal_trap;
@@ -0,0 +1,39 @@
+use nu_test_support::{nu, pipeline};
+
+#[cfg(unix)]
+#[test]
+fn runs_handler_after_interrupt() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            signal trap INT {|sig| print $"trapped ($sig)" };
+            kill -s 2 $nu.pid;
+            sleep 5sec
+        "#
+    ));
+
+    assert_eq!(actual.out, "trapped INT");
+}
+
+#[cfg(unix)]
+#[test]
+fn removed_handler_is_not_run() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            signal trap INT {|sig| print $"trapped ($sig)" };
+            signal trap INT --remove;
+            kill -s 2 $nu.pid;
+            sleep 5sec
+        "#
+    ));
+
+    assert_eq!(actual.out, "");
+}
+
+#[test]
+fn fails_on_unsupported_signal() {
+    let actual = nu!(cwd: ".", "signal trap HUP {|| print trapped }");
+
+    assert!(actual.err.contains("Unsupported signal 'HUP'"));
+}
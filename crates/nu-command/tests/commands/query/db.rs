@@ -42,3 +42,82 @@ fn invalid_input_fails() {
 
     assert!(actual.err.contains("can't convert string"));
 }
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn binds_positional_params() {
+    let actual = nu!(
+    cwd: "tests/fixtures/formats", pipeline(
+        r#"
+            open sample.db
+            | query db "select x from strings where x = ?" --params ["world"]
+            | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "2");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn binds_named_params() {
+    let actual = nu!(
+    cwd: ".", pipeline(
+        r#"
+            [[name size]; [a 1] [b 20] [c 30]]
+            | into sqlite
+            | query db "select name from main where size > :min and name <> @skip" --params {min: 10, "@skip": c}
+            | get name.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "b");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn params_are_not_interpolated() {
+    let actual = nu!(
+    cwd: ".", pipeline(
+        r#"
+            [[name]; [a]]
+            | into sqlite
+            | query db "select count(*) as n from main where name = ?" --params ["a' or '1'='1"]
+            | get n.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "0");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn missing_param_fails() {
+    let actual = nu!(
+    cwd: ".", pipeline(
+        r#"
+            [[name]; [a]]
+            | into sqlite
+            | query db "select * from main where name = :name" --params {other: a}
+        "#
+    ));
+
+    assert!(actual.err.contains("no value for parameter :name"));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn runs_several_statements() {
+    let actual = nu!(
+    cwd: ".", pipeline(
+        r#"
+            [[n]; [1]]
+            | into sqlite
+            | query db "insert into main (n) values (?); insert into main (n) values (?); select sum(n) as total from main" --params [2 3]
+            | last
+            | get total.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "6");
+}
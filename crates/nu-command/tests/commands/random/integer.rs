@@ -35,3 +35,15 @@ fn generates_0() {
 
     assert!(actual.out.contains('0'));
 }
+
+#[test]
+fn generates_the_same_integers_with_the_same_seed() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        (1..10 | each { random integer 1..1000000 --seed 3 }) == (1..10 | each { random integer 1..1000000 --seed 3 })
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
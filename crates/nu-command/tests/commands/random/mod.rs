@@ -3,5 +3,8 @@ mod chars;
 mod decimal;
 mod dice;
 mod integer;
+mod normal;
+mod sample;
+mod shuffle;
 #[cfg(feature = "uuid_crate")]
 mod uuid;
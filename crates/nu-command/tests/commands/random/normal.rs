@@ -0,0 +1,37 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn generates_the_requested_count() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        random normal --count 20 | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "20");
+}
+
+#[test]
+fn zero_stddev_generates_the_mean() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        random normal --mean 5 --stddev 0 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "5.0");
+}
+
+#[test]
+fn rejects_negative_stddev() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        random normal --stddev -1
+        "#
+    ));
+
+    assert!(actual.err.contains("positive"));
+}
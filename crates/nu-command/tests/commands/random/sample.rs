@@ -0,0 +1,49 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn picks_different_items() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        1..10 | random sample 10 | sort | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]");
+}
+
+#[test]
+fn never_picks_items_without_weight() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [a b c] | random sample 50 --replace --weights [0 1 0] | uniq | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[b]");
+}
+
+#[test]
+fn needs_replace_to_pick_more_than_there_are() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [a b c] | random sample 4
+        "#
+    ));
+
+    assert!(actual.err.contains("--replace"));
+}
+
+#[test]
+fn needs_a_weight_for_every_item() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [a b c] | random sample --weights [1 2]
+        "#
+    ));
+
+    assert!(actual.err.contains("2 weights for 3 items"));
+}
@@ -0,0 +1,28 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn keeps_every_item() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        1..20 | random shuffle | sort | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]"
+    );
+}
+
+#[test]
+fn shuffles_the_same_way_with_the_same_seed() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        (1..20 | random shuffle --seed 42) == (1..20 | random shuffle --seed 42)
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
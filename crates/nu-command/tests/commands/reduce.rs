@@ -153,3 +153,27 @@ fn enumerate_reduce_example() {
 
     assert_eq!(actual.out, "1");
 }
+
+#[test]
+fn reduce_passes_the_index_of_the_element() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1 2 3] | reduce {|it, acc, i| $acc + $it * $i }
+        "#
+    ));
+
+    assert_eq!(actual.out, "9");
+}
+
+#[test]
+fn reduce_until_stops_reading_an_endless_stream() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        1.. | reduce --until {|acc| $acc > 10 } {|it, acc| $acc + $it }
+        "#
+    ));
+
+    assert_eq!(actual.out, "15");
+}
@@ -118,3 +118,42 @@ fn reject_nested_field() {
 
     assert_eq!(actual.out, "{a: {c: 5}}");
 }
+
+#[test]
+fn reject_regex_from_each_row() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+            r#"
+            [{tmp_a: 1, b: 2} {b: 3, tmp_c: 4}] | reject -r '^tmp_' | to nuon
+            "#
+        )
+    );
+
+    assert_eq!(actual.out, "[[b]; [2], [3]]");
+}
+
+#[test]
+fn reject_ignore_missing() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+            r#"
+            {a: 1, b: 2} | reject --ignore-missing c b | to nuon
+            "#
+        )
+    );
+
+    assert_eq!(actual.out, "{a: 1}");
+}
+
+#[test]
+fn reject_literal_column_with_glob_characters() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+            r#"
+            {"a[0]": 1, a0: 2} | reject 'a[0]' | columns | str join ","
+            "#
+        )
+    );
+
+    assert_eq!(actual.out, "a0");
+}
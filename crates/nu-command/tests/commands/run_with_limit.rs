@@ -0,0 +1,95 @@
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn returns_the_result_within_limits() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3] | run-with-limit -t 10sec { $in | math sum }
+        "#
+    ));
+
+    assert_eq!(actual.out, "6");
+}
+
+#[test]
+fn stops_a_closure_past_its_timeout() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            run-with-limit --timeout 100ms { loop { sleep 10ms } }
+        "#
+    ));
+
+    assert!(actual.err.contains("Limit exceeded"));
+    assert!(actual.err.contains("ran for longer than this"));
+}
+
+#[test]
+fn lowers_the_recursion_limit() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            def countdown [n: int] { if $n == 0 { 0 } else { countdown ($n - 1) } };
+            run-with-limit --max-depth 5 { countdown 20 }
+        "#
+    ));
+
+    assert!(actual.err.contains("Recursion limit (5) reached"));
+}
+
+#[test]
+fn rejects_a_result_past_its_size() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            run-with-limit --max-size 10b { 1..20 | each { 'abcdef' } }
+        "#
+    ));
+
+    assert!(actual.err.contains("more than this"));
+}
+
+#[test]
+fn does_not_raise_the_recursion_limit() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            def countdown [n: int] { if $n == 0 { 0 } else { countdown ($n - 1) } };
+            run-with-limit --max-depth 100000 { countdown 100000 }
+        "#
+    ));
+
+    assert!(actual.err.contains("Recursion limit (50) reached"));
+}
+
+#[test]
+fn rejects_a_negative_depth() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            run-with-limit --max-depth (-1) { 1 }
+        "#
+    ));
+
+    assert!(actual.err.contains("positive"));
+}
+
+#[test]
+fn stops_the_closure_once_it_times_out() {
+    Playground::setup("run_with_limit_test_1", |dirs, _sandbox| {
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                try { run-with-limit --timeout 100ms { loop { 'x' | save --append out.txt; sleep 10ms } } };
+                sleep 200ms;
+                let before = (open out.txt | str length);
+                sleep 200ms;
+                (open out.txt | str length) == $before
+            "#
+        ));
+
+        assert_eq!(actual.out, "true");
+    })
+}
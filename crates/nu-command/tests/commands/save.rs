@@ -283,3 +283,85 @@ fn save_list_stream() {
         assert_eq!(actual, "a\nb\nc\nd\n")
     })
 }
+
+#[test]
+fn save_into_dir_writes_each_row() {
+    Playground::setup("save_test_into_dir_1", |dirs, sandbox| {
+        sandbox.with_files(vec![]);
+
+        let actual = nu!(
+            cwd: dirs.root(),
+            r#"[[name content]; [a.txt hello] [sub/b.json {x: 1}]] | save --into-dir save_test_into_dir_1/out | get status | str join ','"#
+        );
+
+        assert_eq!(actual.out, "created,created");
+        assert_eq!(file_contents(dirs.test().join("out/a.txt")), "hello");
+        assert!(file_contents(dirs.test().join("out/sub/b.json")).contains("\"x\": 1"));
+    })
+}
+
+#[test]
+fn save_into_dir_refuses_taken_names_by_default() {
+    Playground::setup("save_test_into_dir_2", |dirs, sandbox| {
+        sandbox.with_files(vec![Stub::FileWithContent("a.txt", "old")]);
+
+        let actual = nu!(
+            cwd: dirs.root(),
+            r#"[[name content]; [b.txt new] [a.txt new]] | save --into-dir save_test_into_dir_2"#
+        );
+
+        assert!(actual.err.contains("Destination file already exists"));
+        assert_eq!(file_contents(dirs.test().join("a.txt")), "old");
+        // Nothing is written when any row can't be saved
+        assert!(!dirs.test().join("b.txt").exists());
+    })
+}
+
+#[test]
+fn save_into_dir_renames_repeated_names() {
+    Playground::setup("save_test_into_dir_3", |dirs, sandbox| {
+        sandbox.with_files(vec![Stub::FileWithContent("a.txt", "old")]);
+
+        let actual = nu!(
+            cwd: dirs.root(),
+            r#"[[name content]; [a.txt one] [a.txt two]] | save --into-dir save_test_into_dir_3 --rename | get name | str join ','"#
+        );
+
+        assert_eq!(actual.out, "a-1.txt,a-2.txt");
+        assert_eq!(file_contents(dirs.test().join("a.txt")), "old");
+        assert_eq!(file_contents(dirs.test().join("a-2.txt")), "two");
+    })
+}
+
+#[test]
+fn save_into_dir_appends_and_skips() {
+    Playground::setup("save_test_into_dir_4", |dirs, sandbox| {
+        sandbox.with_files(vec![]);
+
+        nu!(
+            cwd: dirs.root(),
+            r#"[[name content]; [log.txt a] [log.txt b]] | save --into-dir save_test_into_dir_4 --append"#
+        );
+        let actual = nu!(
+            cwd: dirs.root(),
+            r#"[[name content]; [log.txt c] [new.txt d]] | save --into-dir save_test_into_dir_4 --skip | get status | str join ','"#
+        );
+
+        assert_eq!(actual.out, "skipped,created");
+        assert_eq!(file_contents(dirs.test().join("log.txt")), "ab");
+    })
+}
+
+#[test]
+fn save_into_dir_rejects_names_outside_the_dir() {
+    Playground::setup("save_test_into_dir_5", |dirs, sandbox| {
+        sandbox.with_files(vec![]);
+
+        let actual = nu!(
+            cwd: dirs.root(),
+            r#"[[name content]; [../escape.txt x]] | save --into-dir save_test_into_dir_5"#
+        );
+
+        assert!(actual.err.contains("Invalid file name"));
+    })
+}
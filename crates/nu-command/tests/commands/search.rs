@@ -0,0 +1,105 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn search_returns_structured_matches() {
+    Playground::setup("search_test_1", |dirs, sandbox| {
+        sandbox.mkdir("src").with_files(vec![
+            FileWithContent("src/a.rs", "fn main() {}\n// TODO: tests\n"),
+            FileWithContent("src/b.txt", "TODO: docs\n"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                search 'TODO: (?P<what>\w+)' src --glob '*.rs'
+                | each { |m| $"($m.path):($m.line_number):($m.captures.what)" }
+                | str join ','
+            "#
+        ));
+
+        assert_eq!(actual.out, "src/a.rs:2:tests");
+    })
+}
+
+#[test]
+fn search_skips_gitignored_and_hidden_files() {
+    Playground::setup("search_test_2", |dirs, sandbox| {
+        sandbox.mkdir("target").with_files(vec![
+            FileWithContent(".gitignore", "target/\n*.log\n!keep.log\n"),
+            FileWithContent("kept.txt", "needle"),
+            FileWithContent("skipped.log", "needle"),
+            FileWithContent("keep.log", "needle"),
+            FileWithContent(".hidden", "needle"),
+            FileWithContent("target/out.txt", "needle"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                search needle | get path | sort | str join ','
+            "#
+        ));
+        assert_eq!(actual.out, "keep.log,kept.txt");
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                search needle --no-ignore --hidden | length
+            "#
+        ));
+        assert_eq!(actual.out, "5");
+    })
+}
+
+#[test]
+fn search_fixed_strings_ignoring_case() {
+    Playground::setup("search_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "a.txt",
+            "call F(x)\ncall f(y)\ncall fx\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                search -f -i 'f(' a.txt | get line_number | sort | to nuon
+            "#
+        ));
+
+        assert_eq!(actual.out, "[1, 2]");
+    })
+}
+
+#[test]
+fn search_reports_invalid_patterns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            search '(' .
+        "#
+    ));
+
+    assert!(actual.err.contains("Error with regular expression"));
+}
+
+#[cfg(unix)]
+#[test]
+fn search_does_not_follow_symlinked_directories() {
+    Playground::setup("search_test_symlink_loop", |dirs, sandbox| {
+        sandbox
+            .mkdir("src")
+            .with_files(vec![FileWithContent("src/a.txt", "needle\n")])
+            .symlink(".", "src/loop");
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                search needle | get path | str join ','
+            "#
+        ));
+
+        assert_eq!(actual.out, "src/a.txt");
+    })
+}
@@ -272,3 +272,51 @@ fn select_failed3() {
     assert!(actual.out.is_empty());
     assert!(actual.err.contains("cannot find column"));
 }
+
+#[test]
+fn select_glob_matches_the_columns_of_each_row() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [{metric_a: 1, name: x} {metric_b: 2, name: y}] | select 'metric_*' | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[{metric_a: 1}, {metric_b: 2}]");
+}
+
+#[test]
+fn select_literal_column_with_glob_characters() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        {"a[0]": 1, a0: 2, "valid?": 3} | select 'a[0]' 'valid?' | columns | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "a[0],valid?");
+}
+
+#[test]
+fn select_ignore_missing_leaves_out_missing_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [{a: 1, b: 2} {a: 3}] | select --ignore-missing b | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[{b: 2}, {}]");
+}
+
+#[test]
+fn select_rejects_an_invalid_regex() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        {a: 1} | select --regex '('
+        "#
+    ));
+
+    assert!(actual.err.contains("Error with regular expression"));
+}
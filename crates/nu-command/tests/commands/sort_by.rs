@@ -126,3 +126,45 @@ fn fail_on_non_iterator() {
 
     assert!(actual.err.contains("only_supports_this_input_type"));
 }
+
+#[test]
+fn sorts_columns_in_their_own_direction() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name size]; [b 1] [c 2] [a 2] [d 1]]
+            | sort-by [{col: size, dir: desc}, {col: name}]
+            | get name
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[a, c, b, d]");
+}
+
+#[test]
+fn rejects_unknown_directions() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name]; [a]] | sort-by {col: name, dir: down}
+        "#
+    ));
+
+    assert!(actual.err.contains("asc or desc"));
+}
+
+#[test]
+fn collates_accented_letters() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name]; [zed] [Ängel] [anna] [Bert]]
+            | sort-by name --collate
+            | get name
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[Ängel, anna, Bert, zed]");
+}
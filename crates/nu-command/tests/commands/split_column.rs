@@ -26,3 +26,27 @@ fn to_column() {
         assert!(actual.out.contains("shipper"));
     })
 }
+
+#[test]
+fn to_column_with_a_limit() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'key=value=with=equals' | split column '=' --number 2 key value | get 0.value
+        "#
+    ));
+
+    assert_eq!(actual.out, "value=with=equals");
+}
+
+#[test]
+fn to_column_keeping_the_separators() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'a<b>c' | split column ['<' '>'] --keep-separators | get 0 | values | str join ' '
+        "#
+    ));
+
+    assert_eq!(actual.out, "a < b > c");
+}
@@ -26,3 +26,51 @@ fn to_row() {
         assert!(actual.out.contains('5'));
     })
 }
+
+#[test]
+fn to_row_at_any_of_several_separators() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'a, b;c' | split row [', ' ';' ','] | str join '|'
+        "#
+    ));
+
+    assert_eq!(actual.out, "a|b|c");
+}
+
+#[test]
+fn to_row_at_a_regex_keeping_the_separators() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            '1+22-3' | split row --regex --keep-separators '[+-]' | str join ' '
+        "#
+    ));
+
+    assert_eq!(actual.out, "1 + 22 - 3");
+}
+
+#[test]
+fn to_row_with_an_invalid_regex() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'a(b' | split row --regex '('
+        "#
+    ));
+
+    assert!(actual.err.contains("Invalid separator"));
+}
+
+#[test]
+fn to_row_collapsing_empty_rows_before_the_limit() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            ';a;;b;c' | split row ';' --collapse-empty --number 2 | str join '|'
+        "#
+    ));
+
+    assert_eq!(actual.out, "a|b;c");
+}
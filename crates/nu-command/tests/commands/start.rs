@@ -0,0 +1,47 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn start_with_waits_and_returns_exit_code() {
+    Playground::setup("start_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "script.sh",
+            "echo done > finished.txt\nexit 3",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                let result = (start --with sh --wait script.sh);
+                $"($result.exit_code) ($result.pid > 0) (open finished.txt | str trim)"
+            "#
+        ));
+
+        assert_eq!(actual.out, "3 true done");
+    })
+}
+
+#[test]
+fn start_without_wait_returns_pid_only() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            start --with "true" file.txt | $"($in.pid > 0) ($in.exit_code == null)"
+        "#
+    ));
+
+    assert_eq!(actual.out, "true true");
+}
+
+#[test]
+fn start_with_missing_application() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            start --with nu_no_such_application file.txt
+        "#
+    ));
+
+    assert!(actual.err.contains("Cannot start nu_no_such_application"));
+}
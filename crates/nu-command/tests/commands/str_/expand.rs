@@ -0,0 +1,40 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn expands_file_names() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'img{08..10}.{png,jpg}' | str expand | str join ' '
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "img08.png img08.jpg img09.png img09.jpg img10.png img10.jpg"
+    );
+}
+
+#[test]
+fn expands_strings_at_cell_paths() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name pattern]; [a 'x{1..3}'] [b 'y{,z}']] | str expand pattern | get pattern | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"[[x1, x2, x3], [y, yz]]"#);
+}
+
+#[test]
+fn keeps_braces_that_do_not_expand() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            '{a}..{1..b}' | str expand | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["{a}..{1..b}"]"#);
+}
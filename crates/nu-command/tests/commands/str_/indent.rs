@@ -0,0 +1,37 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn dedent_then_indent_reindents_a_block() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "\n        if x {\n            y\n        }" | str dedent | str indent '  ' | lines | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["", "  if x {", "      y", "  }"]"#);
+}
+
+#[test]
+fn indents_strings_at_cell_paths() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {code: "a\nb", name: "x"} | str indent '// ' code | get code | lines | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "// a,// b");
+}
+
+#[test]
+fn dedent_ignores_blank_lines() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "    a\n\n      b" | str dedent | lines | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"[a, "", "  b"]"#);
+}
@@ -255,3 +255,17 @@ fn int_into_string_decimals_respects_system_locale_en() {
 
     assert_eq!(actual.out, "10.0");
 }
+
+#[test]
+fn int_into_string_groups_digits_for_given_locale() {
+    let actual = nu!(
+        locale: "en_US.UTF-8",
+        pipeline(
+            r#"
+            1234567 | into string --group --locale de
+            "#
+        )
+    );
+
+    assert_eq!(actual.out, "1.234.567");
+}
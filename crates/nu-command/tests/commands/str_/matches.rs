@@ -0,0 +1,66 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn returns_byte_offsets_of_matches() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "héllo wörld" | str matches 'w\w+' | select match start end | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"[[match, start, end]; [wörld, 7, 13]]"#);
+}
+
+#[test]
+fn offsets_work_with_str_substring() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            let s = "id: 1234, name: nu"
+            let m = ($s | str matches 'id: (?P<id>\d+)' | get 0.groups.id)
+            $s | str substring [$m.start $m.end]
+        "#
+    ));
+
+    assert_eq!(actual.out, "1234");
+}
+
+#[test]
+fn unmatched_groups_are_null() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "ab" | str matches '(a)(x)?' | get 0.groups | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        r#"{"1": {text: a, start: 0, end: 1}, "2": null}"#
+    );
+}
+
+#[test]
+fn ignores_case() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "Nu nu NU" | str matches -i nu | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "3");
+}
+
+#[test]
+fn reports_bad_patterns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "abc" | str matches '(a'
+        "#
+    ));
+
+    assert!(actual.err.contains("Error with regular expression"));
+}
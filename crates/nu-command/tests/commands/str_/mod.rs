@@ -1,5 +1,12 @@
 mod collect;
+mod expand;
+mod indent;
 mod into_string;
+mod matches;
+mod similarity;
+mod slug;
+mod template;
+mod wrap;
 
 use nu_test_support::fs::Stub::FileWithContent;
 use nu_test_support::playground::Playground;
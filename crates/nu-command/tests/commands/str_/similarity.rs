@@ -0,0 +1,66 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn picks_the_closest_command_name() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [sort sort-by select save]
+            | each { |name| {name: $name, score: ('srot' | str similarity $name -a jaro-winkler)} }
+            | sort-by score --reverse
+            | get name.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "sort");
+}
+
+#[test]
+fn scores_cell_paths() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[a b]; [abcd xyz]] | str similarity abcd a b | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[a, b]; [1.0, 0.0]]");
+}
+
+#[test]
+fn rejects_unknown_algorithms() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'a' | str similarity b -a soundex
+        "#
+    ));
+
+    assert!(actual
+        .err
+        .contains("levenshtein, jaro, jaro-winkler or ngram"));
+}
+
+#[test]
+fn ngram_size_needs_the_ngram_algorithm() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'a' | str similarity b -n 3
+        "#
+    ));
+
+    assert!(actual.err.contains("only applies to the ngram algorithm"));
+}
+
+#[test]
+fn hamming_distance_needs_equal_lengths() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'abc' | str distance abcd -a hamming
+        "#
+    ));
+
+    assert!(actual.err.contains("strings of equal length"));
+}
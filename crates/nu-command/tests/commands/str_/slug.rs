@@ -0,0 +1,52 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn makes_slugs_of_titles() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "Crème Brûlée: 10 Tips & Tricks" | str slug
+        "#
+    ));
+
+    assert_eq!(actual.out, "creme-brulee-10-tips-tricks");
+}
+
+#[test]
+fn keeps_whole_words_within_max_length() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "The quick brown fox" | str slug --max-length 12 --separator _
+        "#
+    ));
+
+    assert_eq!(actual.out, "the_quick");
+}
+
+#[test]
+fn rejects_zero_max_length() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "hello" | str slug -m 0
+        "#
+    ));
+
+    assert!(actual.err.contains("positive"));
+}
+
+#[test]
+fn transliterates_cell_paths() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[city country]; ["Zürich" "Schweiz"] ["Москва" "Россия"]]
+            | str transliterate city
+            | get city
+            | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "Zurich,Moskva");
+}
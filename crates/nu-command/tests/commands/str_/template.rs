@@ -0,0 +1,50 @@
+use nu_test_support::fs::Stub::FileWithContent;
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn renders_template_file_with_loop_over_table() {
+    Playground::setup("str_template_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "hosts.tmpl",
+            "{% for host in hosts -%}\n{{ host.ip }} {{ host.name }}\n{% endfor -%}\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                open hosts.tmpl
+                | str template {hosts: [[name ip]; [db '10.0.0.2'] [web '10.0.0.3']]}
+                | lines
+                | to nuon
+            "#
+        ));
+
+        assert_eq!(actual.out, r#"["10.0.0.2 db", "10.0.0.3 web"]"#);
+    })
+}
+
+#[test]
+fn iterates_over_record_entries() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "{% for e in env %}{{ e.key }}={{ e.value }};{% endfor %}"
+            | str template {env: {A: 1, B: x}}
+        "#
+    ));
+
+    assert_eq!(actual.out, "A=1;B=x;");
+}
+
+#[test]
+fn errors_on_missing_value() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "{{ user.email }}" | str template {user: {name: x}}
+        "#
+    ));
+
+    assert!(actual.err.contains("'user.email' isn't in the context"));
+}
@@ -0,0 +1,69 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn wraps_and_justifies_a_paragraph() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'Lorem ipsum dolor sit amet, consectetur adipiscing elit'
+            | str wrap -w 20 -p
+            | str justify both -w 20
+            | lines
+            | each { |line| $"($line)|" }
+            | str join ';'
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "Lorem   ipsum  dolor|;sit            amet,|;consectetur|;adipiscing elit|"
+    );
+}
+
+#[test]
+fn wrap_keeps_long_words_with_preserve_words() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'a extraordinarily b' | str wrap -w 5 -p | lines | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[a, extraordinarily, b]");
+}
+
+#[test]
+fn wrap_ignores_ansi_colors() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            $"(ansi red)abc(ansi reset) def" | str wrap -w 3 -p | ansi strip | lines | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[abc, def]");
+}
+
+#[test]
+fn justify_cell_paths() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[a b]; [x y]] | str justify right -w 3 a | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"[[a, b]; ["  x", y]]"#);
+}
+
+#[test]
+fn justify_rejects_unknown_alignments() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            'x' | str justify middle
+        "#
+    ));
+
+    assert!(actual.err.contains("left, right, center or both"));
+}
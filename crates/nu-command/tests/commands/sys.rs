@@ -0,0 +1,37 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn sys_lists_all_sections() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            sys | columns | str join ","
+        "#
+    ));
+
+    assert_eq!(actual.out, "host,cpu,disks,mem,temp,net");
+}
+
+#[test]
+fn sys_gathers_accessed_section() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            (sys).mem.total > 0b
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn sys_collects_into_record() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            sys | to json | from json | get mem | columns | first
+        "#
+    ));
+
+    assert_eq!(actual.out, "total");
+}
@@ -57,3 +57,10 @@ fn fail_on_non_iterator() {
 
     assert!(actual.err.contains("only_supports_this_input_type"));
 }
+
+#[test]
+fn stops_reading_an_endless_stream() {
+    let actual = nu!(cwd: ".", pipeline("1.. | take until { $in > 3 } | to nuon"));
+
+    assert_eq!(actual.out, "[1, 2, 3]");
+}
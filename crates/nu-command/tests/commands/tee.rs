@@ -0,0 +1,46 @@
+use nu_test_support::fs::file_contents;
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn passes_the_stream_through_and_saves_a_copy() {
+    Playground::setup("tee_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+            [1 2 3] | tee { to nuon | save numbers.nuon } | math sum
+            "#
+        ));
+
+        assert_eq!(actual.out, "6");
+        assert_eq!(file_contents(dirs.test().join("numbers.nuon")), "[1, 2, 3]");
+    })
+}
+
+#[test]
+fn passes_a_single_value_through() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        {a: 1} | tee { get a } | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "{a: 1}");
+}
+
+#[test]
+fn stderr_needs_an_external_command() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1 2] | tee --stderr { ignore }
+        "#
+    ));
+
+    assert!(actual
+        .err
+        .contains("--stderr only applies to the output of an external command"));
+}
@@ -0,0 +1,41 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn keeps_input_order_of_independent_items() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name depends_on]; [migrate [schema]] [seed [migrate]] [schema []] [logs []]]
+            | toposort
+            | get name
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[schema, migrate, seed, logs]");
+}
+
+#[test]
+fn names_the_cycle() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[from to]; [a b] [b c] [c a] [c d]] | toposort --edges
+        "#
+    ));
+
+    assert!(actual.err.contains("dependency cycle"));
+    assert!(actual.err.contains("a -> c -> b -> a"));
+}
+
+#[test]
+fn errors_on_unknown_dependency() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name depends_on]; [app [db]]] | toposort
+        "#
+    ));
+
+    assert!(actual.err.contains("unknown dependency 'db'"));
+}
@@ -0,0 +1,63 @@
+use nu_test_support::fs::Stub::EmptyFile;
+use nu_test_support::playground::Playground;
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn renders_directory_sorted_by_name() {
+    Playground::setup("tree_test_1", |dirs, sandbox| {
+        sandbox
+            .with_files(vec![EmptyFile("b.txt")])
+            .within("a")
+            .with_files(vec![EmptyFile("inner.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                tree --counts | lines | to nuon
+            "#
+        ));
+
+        assert_eq!(
+            actual.out,
+            r#"[".", "├── a (1)", "│   └── inner.txt", "└── b.txt"]"#
+        );
+    })
+}
+
+#[test]
+fn limits_directory_depth() {
+    Playground::setup("tree_test_2", |dirs, sandbox| {
+        sandbox.within("a").with_files(vec![EmptyFile("inner.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                tree . --depth 1 --data | get name | to nuon
+            "#
+        ));
+
+        assert_eq!(actual.out, r#"["a"]"#);
+    })
+}
+
+#[test]
+fn data_links_children_to_parents() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {a: [x {b: y}]} | tree --data | select parent name | to nuon
+        "#
+    ));
+
+    assert_eq!(
+        actual.out,
+        "[[parent, name]; [null, a], [0, \"0\"], [0, \"1\"], [2, b]]"
+    );
+}
+
+#[test]
+fn errors_on_scalar_input() {
+    let actual = nu!(cwd: ".", "5 | tree");
+
+    assert!(actual.err.contains("record or list"));
+}
@@ -0,0 +1,26 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn unflatten_undoes_flatten_dotted() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            let data = {a: {b: 1, c: {d: 2}}, e: 3};
+            ($data | flatten --dotted | unflatten) == $data
+        "#
+    ));
+
+    assert_eq!(actual.out, "true");
+}
+
+#[test]
+fn unflatten_rejects_clashing_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {a: 1, 'a.b': 2} | unflatten
+        "#
+    ));
+
+    assert!(actual.err.contains("clashes"));
+}
@@ -300,3 +300,51 @@ fn table_with_ignore_case() {
     assert_eq!(actual.out, expected.out);
     assert_eq!(actual.out, expected.out);
 }
+
+#[test]
+fn uniq_keeps_first_seen_order() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [3 1 3 2 1 4] | uniq | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[3, 1, 2, 4]");
+}
+
+#[test]
+fn uniq_streams_values() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            1..10000000 | each {|x| $x mod 3 } | uniq | first 3 | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[1, 2, 0]");
+}
+
+#[test]
+fn uniq_counts_in_first_seen_order() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [b a b c a b] | uniq --count | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[value, count]; [b, 3], [a, 2], [c, 1]]");
+}
+
+#[test]
+fn uniq_repeated_keeps_first_seen_order() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [a b b a] | uniq --repeated | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[a, b]");
+}
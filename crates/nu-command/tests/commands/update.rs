@@ -124,3 +124,33 @@ fn update_uses_enumerate_index() {
 
     assert_eq!(actual.out, "[[index, a]; [0, 8], [1, 8]]");
 }
+
+#[test]
+fn update_several_columns_in_one_pass() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"[[a b c]; [1 x 3] [2 y 4]] | update {a: {|r| $r.a * 10}, b: z, c: {|r| $r.a + $r.c}} | to nuon"#
+    ));
+
+    assert_eq!(actual.out, "[[a, b, c]; [10, z, 13], [20, z, 24]]");
+}
+
+#[test]
+fn update_several_columns_needs_existing_columns() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"{a: 1} | update {a: 2, b: 3}"#
+    ));
+
+    assert!(actual.err.contains("cannot find column 'b'"));
+}
+
+#[test]
+fn update_needs_a_replacement_for_a_column() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"{a: 1} | update a"#
+    ));
+
+    assert!(actual.err.contains("replacement value"));
+}
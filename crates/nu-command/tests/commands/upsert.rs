@@ -107,3 +107,13 @@ fn upsert_empty() {
 
     assert!(actual.err.contains("index too large (max: 0)"));
 }
+
+#[test]
+fn upsert_several_columns_in_one_pass() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"[[a]; [1] [2]] | upsert {a: {|r| $r.a + 1}, b: {|r| $r.a * 2}} | to nuon"#
+    ));
+
+    assert_eq!(actual.out, "[[a, b]; [2, 4], [3, 6]]");
+}
@@ -0,0 +1,63 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn windows_over_a_period() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            let start = ('2022-01-01T00:00:00Z' | into datetime);
+            [0 1 4 6 20]
+            | each {|m| {time: ($start + ($m * 1min)), m: $m} }
+            | window --by time --period 5min
+            | each {|w| $w.m }
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[0, 1, 4], [6], [20]]");
+}
+
+#[test]
+fn windows_slide_by_a_duration() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            let start = ('2022-01-01T00:00:00Z' | into datetime);
+            [0 2 6]
+            | each {|m| {time: ($start + ($m * 1min)), m: $m} }
+            | window --by time --period 5min --stride 2min
+            | each {|w| $w.m }
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[0, 2], [2, 6], [6], [6]]");
+}
+
+#[test]
+fn time_windows_need_sorted_input() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            let start = ('2022-01-01T00:00:00Z' | into datetime);
+            [3 1]
+            | each {|m| {time: ($start + ($m * 1min))} }
+            | window --by time --period 5min
+            | to nuon
+        "#
+    ));
+
+    assert!(actual.err.contains("sorted"));
+}
+
+#[test]
+fn window_needs_a_size_or_a_period() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [1 2 3] | window
+        "#
+    ));
+
+    assert!(actual.err.contains("window_size"));
+}
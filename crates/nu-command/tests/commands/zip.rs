@@ -57,3 +57,27 @@ fn zips_two_lists() {
 
     assert_eq!(actual.out, "0-1-2-3-4-5-6-7-8-9");
 }
+
+#[test]
+fn zip_longest_fills_with_null() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1] | zip --longest [a b] | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[[1, a], [null, b]]");
+}
+
+#[test]
+fn zip_columns_need_one_name_for_each_list() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+        [1 2] | zip [3 4] [5 6] --columns [a b]
+        "#
+    ));
+
+    assert!(actual.err.contains("2 column names for 3 lists"));
+}
@@ -87,3 +87,68 @@ fn test_list() {
         r##"{name: "C64", black: "#090300", red: "#883932", green: "#55a049", yellow: "#bfce72", blue: "#40318d", purple: "#8b3f96", cyan: "#67b6bd", white: "#ffffff", brightBlack: "#000000", brightRed: "#883932", brightGreen: "#55a049", brightYellow: "#bfce72", brightBlue: "#40318d", brightPurple: "#8b3f96", brightCyan: "#67b6bd", brightWhite: "#f7f7f7", background: "#40318d", foreground: "#7869c4"}"##
     );
 }
+
+#[test]
+fn out_html_report_is_a_standalone_page() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            [[name size]; [a 1] [b 2]] | to html --report --title 'Sizes & names'
+        "#
+    ));
+
+    assert!(actual.out.starts_with("<!DOCTYPE html><html><head>"));
+    assert!(actual.out.contains("<title>Sizes &amp; names</title>"));
+    assert!(actual.out.contains("@media (prefers-color-scheme: dark)"));
+    assert!(actual.out.contains(r#"<input class="nu-filter""#));
+    assert!(actual.out.contains(
+        r#"<table class="nu-sortable"><thead><tr><th>name</th><th>size</th></tr></thead>"#
+    ));
+    assert!(actual.out.contains("<script>"));
+    assert!(actual.out.ends_with("</body></html>"));
+}
+
+#[test]
+fn out_html_report_folds_nested_values() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {name: nu, tags: [a b c], meta: {x: 1}} | to html --report --no-script
+        "#
+    ));
+
+    assert!(actual
+        .out
+        .contains("<details><summary>list 3 items</summary><ol><li>a</li>"));
+    assert!(actual
+        .out
+        .contains("<details><summary>record 1 field</summary>"));
+    assert!(!actual.out.contains("<script>"));
+}
+
+#[test]
+fn out_html_report_with_dark_ignores_browser_preference() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            3 | to html --report --dark
+        "#
+    ));
+
+    assert!(actual
+        .out
+        .contains("--background:black;--foreground:white;"));
+    assert!(!actual.out.contains("prefers-color-scheme"));
+}
+
+#[test]
+fn out_html_report_and_partial_are_incompatible() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            3 | to html --report --partial
+        "#
+    ));
+
+    assert!(actual.err.contains("can't produce a whole page"));
+}
@@ -108,3 +108,73 @@ fn top_level_values_from_json() {
         assert_eq!(actual.out, type_name);
     }
 }
+
+#[test]
+fn from_json_attaches_schema_violations_to_rows() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            '[{"id": 1, "tags": ["a"]}, {"id": -2, "tags": [3]}]'
+            | from json --schema {items: {properties: {id: {minimum: 0}, tags: {items: {type: string}}}}}
+            | get schema_errors
+            | each { |row| $row | get path | str join ',' }
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, r#"["", "id,tags.0"]"#);
+}
+
+#[test]
+fn from_json_with_strict_schema_fails() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            '{"name": "nu"}' | from json --schema {required: [version]} --strict
+        "#
+    ));
+
+    assert!(actual
+        .err
+        .contains("missing required field version at the top level"));
+}
+
+#[test]
+fn from_json_reads_schema_from_file() {
+    Playground::setup("from_json_schema_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "schema.json",
+            r#"
+                {"type": "object", "additionalProperties": false, "properties": {"a": {}}}
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                '{"a": 1, "b": 2}'
+                | from json --schema schema.json
+                | get schema_errors.0
+                | values
+                | str join ': '
+            "#
+        ));
+
+        assert_eq!(actual.out, "b: field is not allowed");
+    })
+}
+
+#[test]
+fn from_json_objects_are_validated_one_by_one() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "{\"a\": 1}\n{\"a\": \"x\"}"
+            | from json --objects --schema {properties: {a: {type: integer}}}
+            | each { |row| if 'schema_errors' in ($row | columns) { $row.schema_errors | length } else { 0 } }
+            | to nuon
+        "#
+    ));
+
+    assert_eq!(actual.out, "[0, 1]");
+}
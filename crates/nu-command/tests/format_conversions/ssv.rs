@@ -93,3 +93,27 @@ fn from_ssv_text_treating_first_line_as_data_with_flag() {
         assert_eq!(separator_based.out, "docker-registry");
     })
 }
+
+#[test]
+fn from_ssv_text_with_max_splits_keeps_trailing_text() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "USER    PID  COMMAND\nroot    1    /sbin/init  splash" | from ssv --max-splits 2 | get 0.COMMAND
+        "#
+    ));
+
+    assert_eq!(actual.out, "/sbin/init  splash");
+}
+
+#[test]
+fn from_ssv_text_skips_lines_before_the_table() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            "Services:\nNAME    PORT\nweb     80" | from ssv --skip 1 | get 0.PORT
+        "#
+    ));
+
+    assert_eq!(actual.out, "80");
+}
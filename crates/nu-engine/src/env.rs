@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use nu_protocol::ast::PathMember;
 use nu_protocol::engine::{EngineState, Stack};
-use nu_protocol::{PipelineData, ShellError, Span, Value};
+use nu_protocol::{PipelineData, ShellError, Span, Type, Value};
 
 use nu_path::canonicalize_with;
 
@@ -144,6 +144,48 @@ pub fn env_to_string(
     }
 }
 
+/// Convert a value assigned with `$env.NAME = ...` or `let-env` to the type declared for the
+/// variable in ENV_CONVERSIONS.
+///
+/// A string is converted with the variable's `from_string` closure, so PATH stays a list even when
+/// it's assigned a string. If the entry also declares a `type`, like `list` or `int`, the converted
+/// value must be of that type.
+pub fn convert_assigned_env_value(
+    engine_state: &EngineState,
+    stack: &Stack,
+    name: &str,
+    value: Value,
+) -> Result<Value, ShellError> {
+    if name == ENV_CONVERSIONS {
+        return Ok(value);
+    }
+
+    let declared_type = get_declared_type(engine_state, stack, name)?;
+
+    let value = if matches!(value, Value::String { .. }) && declared_type != Some(Type::String) {
+        match get_converted_value(engine_state, stack, name, &value, "from_string") {
+            ConversionResult::Ok(v) => v,
+            ConversionResult::ConversionError(e) | ConversionResult::GeneralError(e) => {
+                return Err(e)
+            }
+            ConversionResult::CellPathError => value,
+        }
+    } else {
+        value
+    };
+
+    match declared_type {
+        Some(ty) if !value.get_type().is_subtype(&ty) => Err(ShellError::TypeMismatch(
+            format!(
+                "$env.{name} is declared as {ty} in ENV_CONVERSIONS, but got {}",
+                value.get_type()
+            ),
+            value.span()?,
+        )),
+        _ => Ok(value),
+    }
+}
+
 /// Translate all environment variables from Values to Strings
 pub fn env_to_strings(
     engine_state: &EngineState,
@@ -373,6 +415,59 @@ fn get_converted_value(
     }
 }
 
+/// The type in the `type` field of the variable's ENV_CONVERSIONS entry, if it has one
+fn get_declared_type(
+    engine_state: &EngineState,
+    stack: &Stack,
+    name: &str,
+) -> Result<Option<Type>, ShellError> {
+    let env_conversions = match stack.get_env_var(engine_state, ENV_CONVERSIONS) {
+        Some(env_conversions) => env_conversions,
+        None => return Ok(None),
+    };
+    let env_span = env_conversions.span()?;
+
+    let path_members = &[
+        PathMember::String {
+            val: name.to_string(),
+            span: env_span,
+        },
+        PathMember::String {
+            val: "type".to_string(),
+            span: env_span,
+        },
+    ];
+
+    match env_conversions.follow_cell_path_not_from_user_input(path_members, false) {
+        Ok(Value::String { val, span }) => match val.as_str() {
+            "any" => Ok(Some(Type::Any)),
+            "binary" => Ok(Some(Type::Binary)),
+            "bool" => Ok(Some(Type::Bool)),
+            "date" => Ok(Some(Type::Date)),
+            "duration" => Ok(Some(Type::Duration)),
+            "filesize" => Ok(Some(Type::Filesize)),
+            "float" | "decimal" => Ok(Some(Type::Float)),
+            "int" => Ok(Some(Type::Int)),
+            "list" => Ok(Some(Type::List(Box::new(Type::Any)))),
+            "number" => Ok(Some(Type::Number)),
+            "record" => Ok(Some(Type::Record(vec![]))),
+            "string" => Ok(Some(Type::String)),
+            "table" => Ok(Some(Type::Table(vec![]))),
+            _ => Err(ShellError::UnsupportedConfigValue(
+                "any, binary, bool, date, duration, filesize, float, int, list, number, record, string or table".into(),
+                val,
+                span,
+            )),
+        },
+        Ok(other) => Err(ShellError::UnsupportedConfigValue(
+            "a type name".into(),
+            other.get_type().to_string(),
+            other.span()?,
+        )),
+        Err(_) => Ok(None),
+    }
+}
+
 fn ensure_path(scope: &mut HashMap<String, Value>, env_path_name: &str) -> Option<ShellError> {
     let mut error = None;
 
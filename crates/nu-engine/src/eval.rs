@@ -1,11 +1,11 @@
-use crate::{current_dir_str, get_full_help, nu_variable::NuVariable};
+use crate::{convert_assigned_env_value, current_dir_str, get_full_help, nu_variable::NuVariable};
 use nu_path::expand_path_with;
 use nu_protocol::{
     ast::{
         Argument, Assignment, Bits, Block, Boolean, Call, Comparison, Expr, Expression, Math,
         Operator, PathMember, PipelineElement, Redirection,
     },
-    engine::{EngineState, Stack},
+    engine::{Capability, EngineState, Stack},
     Config, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Range, ShellError, Span,
     Spanned, Unit, Value, VarId, ENV_VARIABLE_ID,
 };
@@ -156,6 +156,18 @@ pub fn eval_call(
 
         result
     } else {
+        if let Some(restrictions) = &engine_state.restrictions {
+            let denied = if decl.is_known_external() {
+                let name = decl.name().split(' ').next().unwrap_or_default();
+                restrictions.denied_external(name)
+            } else {
+                restrictions.denied_capability(decl.as_ref())
+            };
+            if let Some(capability) = denied {
+                return Err(restricted_error(decl.name(), capability, call.head));
+            }
+        }
+
         // We pass caller_stack here with the knowledge that internal commands
         // are going to be specifically looking for global state in the stack
         // rather than any local state.
@@ -163,6 +175,15 @@ pub fn eval_call(
     }
 }
 
+fn restricted_error(name: &str, capability: Capability, span: Span) -> ShellError {
+    ShellError::RestrictedCommand(
+        name.into(),
+        capability.description().into(),
+        capability.name().into(),
+        span,
+    )
+}
+
 /// Redirect the environment from callee to the caller.
 pub fn redirect_env(engine_state: &EngineState, caller_stack: &mut Stack, callee_stack: &Stack) {
     // Grab all environment variables from the callee
@@ -197,6 +218,13 @@ fn eval_external(
         .find_decl("run-external".as_bytes(), &[])
         .ok_or(ShellError::ExternalNotSupported(head.span))?;
 
+    if let Some(restrictions) = &engine_state.restrictions {
+        let name = eval_expression(engine_state, stack, head)?.as_string()?;
+        if let Some(capability) = restrictions.denied_external(&name) {
+            return Err(restricted_error(&name, capability, head.span));
+        }
+    }
+
     let command = engine_state.get_decl(decl_id);
 
     let mut call = Call::new(head.span);
@@ -481,15 +509,18 @@ pub fn eval_expression(
                                             false,
                                             false,
                                         )?;
-                                        match &cell_path.tail[0] {
-                                            PathMember::String { val, .. } => {
-                                                stack.add_env_var(val.to_string(), vardata);
-                                            }
+                                        let name = match &cell_path.tail[0] {
+                                            PathMember::String { val, .. } => val.to_string(),
                                             // In case someone really wants an integer env-var
-                                            PathMember::Int { val, .. } => {
-                                                stack.add_env_var(val.to_string(), vardata);
-                                            }
-                                        }
+                                            PathMember::Int { val, .. } => val.to_string(),
+                                        };
+                                        let vardata = convert_assigned_env_value(
+                                            engine_state,
+                                            stack,
+                                            &name,
+                                            vardata,
+                                        )?;
+                                        stack.add_env_var(name, vardata);
                                     } else {
                                         stack.vars.insert(*var_id, lhs);
                                     }
@@ -735,6 +766,7 @@ pub fn eval_element_with_input(
                         PipelineData::ExternalStream {
                             stderr,
                             exit_code,
+                            resource_usage,
                             span,
                             metadata,
                             trim_end_newline,
@@ -744,6 +776,7 @@ pub fn eval_element_with_input(
                         stdout: stderr,
                         stderr: None,
                         exit_code,
+                        resource_usage,
                         span,
                         metadata,
                         trim_end_newline,
@@ -754,6 +787,7 @@ pub fn eval_element_with_input(
                             stdout,
                             stderr,
                             exit_code,
+                            resource_usage,
                             span,
                             metadata,
                             trim_end_newline,
@@ -763,6 +797,7 @@ pub fn eval_element_with_input(
                             stdout: Some(stdout.chain(stderr)),
                             stderr: None,
                             exit_code,
+                            resource_usage,
                             span,
                             metadata,
                             trim_end_newline,
@@ -771,6 +806,7 @@ pub fn eval_element_with_input(
                             stdout: Some(stderr),
                             stderr: None,
                             exit_code,
+                            resource_usage,
                             span,
                             metadata,
                             trim_end_newline,
@@ -779,6 +815,7 @@ pub fn eval_element_with_input(
                             stdout: Some(stdout),
                             stderr: None,
                             exit_code,
+                            resource_usage,
                             span,
                             metadata,
                             trim_end_newline,
@@ -787,6 +824,7 @@ pub fn eval_element_with_input(
                             stdout: None,
                             stderr: None,
                             exit_code,
+                            resource_usage,
                             span,
                             metadata,
                             trim_end_newline,
@@ -836,6 +874,7 @@ pub fn eval_element_with_input(
                                 stdout: None,
                                 stderr: None,
                                 exit_code,
+                                resource_usage: None,
                                 span: *span,
                                 metadata: None,
                                 trim_end_newline: false,
@@ -907,6 +946,7 @@ pub fn eval_element_with_input(
                                 stdout: None,
                                 stderr: None,
                                 exit_code,
+                                resource_usage: None,
                                 span: *out_span,
                                 metadata: None,
                                 trim_end_newline: false,
@@ -976,13 +1016,11 @@ pub fn eval_block(
 ) -> Result<PipelineData, ShellError> {
     // if Block contains recursion, make sure we don't recurse too deeply (to avoid stack overflow)
     if let Some(recursive) = block.recursive {
-        // picked 50 arbitrarily, should work on all architectures
-        const RECURSION_LIMIT: u64 = 50;
         if recursive {
-            if *stack.recursion_count >= RECURSION_LIMIT {
+            if *stack.recursion_count >= engine_state.recursion_limit {
                 stack.recursion_count = Box::new(0);
                 return Err(ShellError::RecursionLimitReached {
-                    recursion_limit: RECURSION_LIMIT,
+                    recursion_limit: engine_state.recursion_limit,
                     span: block.span,
                 });
             }
@@ -213,6 +213,21 @@ pub enum ParseError {
     )]
     ModuleNotFound(#[label = "module not found"] Span),
 
+    #[error("Remote module is not pinned.")]
+    #[diagnostic(code(nu::parser::remote_module_not_pinned), url(docsrs), help("{0}"))]
+    RemoteModuleNotPinned(
+        String,
+        #[label = "the contents of this module could change"] Span,
+    ),
+
+    #[error("Can't fetch remote module.")]
+    #[diagnostic(code(nu::parser::remote_module_fetch_failed), url(docsrs), help("{0}"))]
+    RemoteModuleFetchFailed(String, #[label = "can't fetch this module"] Span),
+
+    #[error("Remote module is not fetched.")]
+    #[diagnostic(code(nu::parser::remote_module_not_fetched), url(docsrs), help("{0}"))]
+    RemoteModuleNotFetched(String, #[label = "this module is not in the cache"] Span),
+
     #[error("Cyclical module import.")]
     #[diagnostic(code(nu::parser::cyclical_module_import), url(docsrs), help("{0}"))]
     CyclicalModuleImport(String, #[label = "detected cyclical module import"] Span),
@@ -478,6 +493,9 @@ impl ParseError {
             ParseError::NamedAsModule(_, _, s) => *s,
             ParseError::ExportMainAliasNotAllowed(s) => *s,
             ParseError::CyclicalModuleImport(_, s) => *s,
+            ParseError::RemoteModuleNotPinned(_, s) => *s,
+            ParseError::RemoteModuleFetchFailed(_, s) => *s,
+            ParseError::RemoteModuleNotFetched(_, s) => *s,
             ParseError::ModuleOrOverlayNotFound(s) => *s,
             ParseError::ActiveOverlayNotFound(s) => *s,
             ParseError::OverlayPrefixMismatch(_, _, s) => *s,
@@ -7,6 +7,7 @@ mod lex;
 mod lite_parser;
 mod parse_keywords;
 mod parser;
+mod remote_module;
 mod type_check;
 
 pub use deparse::{escape_for_script_arg, escape_quote_string};
@@ -18,6 +19,7 @@ pub use known_external::KnownExternal;
 pub use lex::{lex, Token, TokenContents};
 pub use lite_parser::{lite_parse, LiteBlock, LiteElement};
 pub use parse_keywords::*;
+pub use remote_module::{is_remote_module, ModulePin, RemoteModule, RemoteSource};
 
 pub use parser::{
     is_math_expression_like, parse, parse_block, parse_duration_bytes, parse_expression,
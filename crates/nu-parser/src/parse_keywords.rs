@@ -25,6 +25,7 @@ use crate::{
         parse_internal_call, parse_multispan_value, parse_signature, parse_string, parse_value,
         parse_var_with_opt_type, trim_quotes, ParsedInternalCall,
     },
+    remote_module::{find_remote_module, is_remote_module, ModulePin},
     unescape_unquote_string, ParseError, Token, TokenContents,
 };
 
@@ -1778,8 +1779,20 @@ pub fn parse_use(
 
     let mut error = None;
 
+    // The pins of a remote module are flags, which aren't part of the import pattern
+    let pattern_spans: Vec<Span> = args_spans
+        .iter()
+        .filter(|arg_span| {
+            !call.named_iter().any(|(name, _, value)| {
+                let inside = |span: Span| span.start <= arg_span.start && arg_span.start < span.end;
+                inside(name.span) || value.as_ref().map_or(false, |value| inside(value.span))
+            })
+        })
+        .copied()
+        .collect();
+
     let (import_pattern_expr, err) =
-        parse_import_pattern(working_set, args_spans, expand_aliases_denylist);
+        parse_import_pattern(working_set, &pattern_spans, expand_aliases_denylist);
     error = error.or(err);
 
     let import_pattern = if let Expression {
@@ -1813,9 +1826,29 @@ pub fn parse_use(
             unescape_unquote_string(&import_pattern.head.name, import_pattern.head.span);
 
         if err.is_none() {
-            if let Some(module_path) =
-                find_in_dirs(&module_filename, working_set, &cwd, LIB_DIRS_ENV)
-            {
+            let maybe_module_path = match find_module_file(
+                working_set,
+                &call,
+                &module_filename,
+                &cwd,
+                import_pattern.head.span,
+            ) {
+                Ok(path) => path,
+                Err(err) => {
+                    return (
+                        Pipeline::from_vec(vec![Expression {
+                            expr: Expr::Call(call),
+                            span: call_span,
+                            ty: Type::Any,
+                            custom_completion: None,
+                        }]),
+                        vec![],
+                        Some(err),
+                    );
+                }
+            };
+
+            if let Some(module_path) = maybe_module_path {
                 if let Some(i) = working_set
                     .parsed_module_files
                     .iter()
@@ -2718,9 +2751,18 @@ pub fn parse_overlay_use(
             if let Ok(module_filename) =
                 String::from_utf8(trim_quotes(overlay_name.as_bytes()).to_vec())
             {
-                if let Some(module_path) =
-                    find_in_dirs(&module_filename, working_set, &cwd, LIB_DIRS_ENV)
-                {
+                let maybe_module_path = match find_module_file(
+                    working_set,
+                    &call,
+                    &module_filename,
+                    &cwd,
+                    overlay_name_span,
+                ) {
+                    Ok(path) => path,
+                    Err(err) => return (pipeline, Some(err)),
+                };
+
+                if let Some(module_path) = maybe_module_path {
                     let overlay_name = if let Some(stem) = module_path.file_stem() {
                         stem.to_string_lossy().to_string()
                     } else {
@@ -3689,6 +3731,37 @@ pub fn parse_register(
     )
 }
 
+/// Finds the file of a module, like `find_in_dirs`. A remote module is looked up in the cache,
+/// pinned by the `--hash` and `--tag` flags of the call.
+fn find_module_file(
+    working_set: &StateWorkingSet,
+    call: &Call,
+    filename: &str,
+    cwd: &str,
+    span: Span,
+) -> Result<Option<PathBuf>, ParseError> {
+    if is_remote_module(filename) {
+        let pin = ModulePin {
+            hash: string_flag(working_set, call, "hash")?,
+            tag: string_flag(working_set, call, "tag")?,
+        };
+        find_remote_module(filename, &pin, span).map(Some)
+    } else {
+        Ok(find_in_dirs(filename, working_set, cwd, LIB_DIRS_ENV))
+    }
+}
+
+fn string_flag(
+    working_set: &StateWorkingSet,
+    call: &Call,
+    name: &str,
+) -> Result<Option<String>, ParseError> {
+    match call.get_flag_expr(name) {
+        Some(expr) => value_as_string(eval_constant(working_set, &expr)?, expr.span).map(Some),
+        None => Ok(None),
+    }
+}
+
 /// This helper function is used to find files during parsing
 ///
 /// First, the actual current working directory is selected as
@@ -0,0 +1,315 @@
+use crate::ParseError;
+use nu_protocol::Span;
+use std::path::{Component, Path, PathBuf};
+
+const GIT_PREFIX: &str = "git+";
+
+/// What a remote module is pinned to, from the `--hash` and `--tag` flags of `use`,
+/// `overlay use` and `fetch-module`
+#[derive(Debug, Default)]
+pub struct ModulePin {
+    /// The sha256 hash of the module file
+    pub hash: Option<String>,
+    /// A tag or commit of the repository, for modules from git
+    pub tag: Option<String>,
+}
+
+/// Where a remote module comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSource {
+    /// A file downloaded over https, pinned with its hash
+    File { url: String, file_name: String },
+    /// A file in a git repository, pinned with a revision, a hash, or both
+    Git {
+        repository: String,
+        revision: String,
+        file: String,
+    },
+}
+
+/// A remote module with a valid pin, and the place it is cached at.
+///
+/// Remote modules are downloaded by the `fetch-module` command, which checks the pin. The parser
+/// only looks them up in the cache, so parsing `use` never goes to the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteModule {
+    pub source: RemoteSource,
+    /// The sha256 hash of the module file, as lowercase hex digits
+    pub hash: Option<String>,
+}
+
+/// Whether a module name is a URL to fetch the module from, rather than a module or a file
+pub fn is_remote_module(name: &str) -> bool {
+    name.starts_with("https://") || name.starts_with("http://") || name.starts_with(GIT_PREFIX)
+}
+
+impl RemoteModule {
+    /// Checks the URL and the pin of a remote module.
+    ///
+    /// Modules come from https URLs, which must be pinned with the hash of the file, or from git
+    /// repositories, written as `git+<repository>.git/<file in the repository>`, which must be
+    /// pinned with a tag or commit, a hash, or both.
+    pub fn new(url: &str, pin: &ModulePin, span: Span) -> Result<Self, ParseError> {
+        let hash = match &pin.hash {
+            Some(hash) => Some(parse_hash(hash, span)?),
+            None => None,
+        };
+
+        let source = match url.strip_prefix(GIT_PREFIX) {
+            Some(url) => git_source(url, hash.is_some(), pin.tag.as_deref(), span)?,
+            None => file_source(url, hash.is_some(), span)?,
+        };
+
+        Ok(RemoteModule { source, hash })
+    }
+
+    /// The directory all remote modules are cached in
+    pub fn cache_root(span: Span) -> Result<PathBuf, ParseError> {
+        nu_path::cache_dir()
+            .map(|dir| dir.join("nushell").join("modules"))
+            .ok_or_else(|| {
+                ParseError::RemoteModuleFetchFailed(
+                    "Remote modules are cached locally, but there is no cache directory".into(),
+                    span,
+                )
+            })
+    }
+
+    /// The directory a git repository is checked out in. Every pin has its own checkout, so a
+    /// fetched module always matches the pin it was fetched with.
+    pub fn checkout_dir(&self, cache_root: &Path) -> Option<PathBuf> {
+        match &self.source {
+            RemoteSource::Git {
+                repository,
+                revision,
+                ..
+            } => Some(
+                cache_root
+                    .join("git")
+                    .join(encode_path_component(repository))
+                    .join(encode_path_component(revision))
+                    .join(self.hash.as_deref().unwrap_or("unhashed")),
+            ),
+            RemoteSource::File { .. } => None,
+        }
+    }
+
+    /// The path of the module file in the cache
+    pub fn cached_path(&self, cache_root: &Path) -> PathBuf {
+        match (&self.source, &self.hash) {
+            (RemoteSource::Git { file, .. }, _) => self
+                .checkout_dir(cache_root)
+                .unwrap_or_else(|| cache_root.to_path_buf())
+                .join(file),
+            // Files must be pinned with a hash, which `new` checks
+            (RemoteSource::File { file_name, .. }, hash) => cache_root
+                .join("sha256")
+                .join(hash.as_deref().unwrap_or_default())
+                .join(file_name),
+        }
+    }
+}
+
+/// Finds a remote module in the cache. The module must have been fetched with `fetch-module`
+/// first, with the same pin.
+pub fn find_remote_module(url: &str, pin: &ModulePin, span: Span) -> Result<PathBuf, ParseError> {
+    let module = RemoteModule::new(url, pin, span)?;
+    let path = module.cached_path(&RemoteModule::cache_root(span)?);
+
+    if path.is_file() {
+        Ok(path)
+    } else {
+        let mut command = format!("fetch-module {url}");
+        if let Some(tag) = &pin.tag {
+            command.push_str(&format!(" --tag {tag}"));
+        }
+        if let Some(hash) = &module.hash {
+            command.push_str(&format!(" --hash {hash}"));
+        }
+        Err(ParseError::RemoteModuleNotFetched(
+            format!("Fetch it first with `{command}`"),
+            span,
+        ))
+    }
+}
+
+fn file_source(url: &str, has_hash: bool, span: Span) -> Result<RemoteSource, ParseError> {
+    if !url.starts_with("https://") {
+        return Err(ParseError::RemoteModuleFetchFailed(
+            "Modules are only fetched over https, so they can't be changed on the way".into(),
+            span,
+        ));
+    }
+    if !has_hash {
+        return Err(ParseError::RemoteModuleNotPinned(
+            "Add --hash with the sha256 hash of the module file, which `http get <url> | hash sha256` prints".into(),
+            span,
+        ));
+    }
+    let file_name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty() && is_relative_path(name))
+        .ok_or_else(|| {
+            ParseError::RemoteModuleFetchFailed(
+                "The URL must end with the name of the module file, like https://example.com/tools.nu".into(),
+                span,
+            )
+        })?;
+
+    Ok(RemoteSource::File {
+        url: url.to_string(),
+        file_name: file_name.to_string(),
+    })
+}
+
+fn git_source(
+    url: &str,
+    has_hash: bool,
+    tag: Option<&str>,
+    span: Span,
+) -> Result<RemoteSource, ParseError> {
+    let (repository, file) = match url.find(".git/") {
+        Some(end) => (&url[..end + 4], &url[end + 5..]),
+        None => {
+            return Err(ParseError::RemoteModuleFetchFailed(
+                "Name the module file after the repository, like git+https://example.com/team/tools.git/tools.nu".into(),
+                span,
+            ))
+        }
+    };
+    if tag.is_none() && !has_hash {
+        return Err(ParseError::RemoteModuleNotPinned(
+            "Add --tag with a tag or commit of the repository, or --hash with the sha256 hash of the module file".into(),
+            span,
+        ));
+    }
+    // Both are passed to git, which would read them as options
+    if repository.starts_with('-') || tag.map_or(false, |tag| tag.starts_with('-')) {
+        return Err(ParseError::RemoteModuleFetchFailed(
+            "The repository and the tag can't start with `-`".into(),
+            span,
+        ));
+    }
+    if !is_relative_path(file) {
+        return Err(ParseError::RemoteModuleFetchFailed(
+            format!("The module file {file} must be inside the repository"),
+            span,
+        ));
+    }
+
+    Ok(RemoteSource::Git {
+        repository: repository.to_string(),
+        // Without a tag, the default branch is fetched and the hash checks that it hasn't moved
+        revision: tag.unwrap_or("HEAD").to_string(),
+        file: file.to_string(),
+    })
+}
+
+fn is_relative_path(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Turns any string into a single directory name, keeping letters, digits and `-` and writing
+/// every other byte as `_` and two hex digits, so different strings never share a directory
+fn encode_path_component(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' => (byte as char).to_string(),
+            _ => format!("_{byte:02x}"),
+        })
+        .collect()
+}
+
+/// Reads a sha256 hash as lowercase hex digits, optionally prefixed with `sha256:`
+fn parse_hash(hash: &str, span: Span) -> Result<String, ParseError> {
+    let hash = hash.strip_prefix("sha256:").unwrap_or(hash).to_lowercase();
+    if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(hash)
+    } else {
+        Err(ParseError::LabeledError(
+            "Invalid module hash".into(),
+            "expected the 64 hex digits of a sha256 hash".into(),
+            span,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pin(hash: Option<&str>, tag: Option<&str>) -> ModulePin {
+        ModulePin {
+            hash: hash.map(String::from),
+            tag: tag.map(String::from),
+        }
+    }
+
+    #[test]
+    fn hashes_are_normalized() {
+        let hash = "A".repeat(64);
+
+        assert_eq!(
+            parse_hash(&format!("sha256:{hash}"), Span::test_data()).ok(),
+            Some("a".repeat(64))
+        );
+        assert!(parse_hash("abc", Span::test_data()).is_err());
+    }
+
+    #[test]
+    fn urls_are_remote_modules() {
+        assert!(is_remote_module("https://example.com/tools.nu"));
+        assert!(is_remote_module(
+            "git+https://example.com/team/tools.git/tools.nu"
+        ));
+        assert!(!is_remote_module("tools.nu"));
+    }
+
+    #[test]
+    fn options_are_not_passed_to_git() {
+        let url = "git+https://example.com/team/tools.git/tools.nu";
+
+        assert!(
+            RemoteModule::new(url, &pin(None, Some("--upload-pack=x")), Span::test_data()).is_err()
+        );
+        assert!(RemoteModule::new(
+            "git+--upload-pack=x.git/tools.nu",
+            &pin(None, Some("v1")),
+            Span::test_data()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn module_files_stay_in_the_cache() {
+        let url = "git+https://example.com/team/tools.git/../../tools.nu";
+
+        assert!(RemoteModule::new(url, &pin(None, Some("v1")), Span::test_data()).is_err());
+    }
+
+    #[test]
+    fn every_pin_has_its_own_checkout() {
+        let url = "git+https://example.com/team/tools.git/tools.nu";
+        let root = Path::new("cache");
+        let checkout = |pin| {
+            RemoteModule::new(url, &pin, Span::test_data())
+                .ok()
+                .and_then(|module| module.checkout_dir(root))
+        };
+
+        assert_ne!(
+            checkout(pin(None, Some("v1"))),
+            checkout(pin(None, Some("v2")))
+        );
+        assert_ne!(
+            checkout(pin(None, Some("v1"))),
+            checkout(pin(Some(&"0".repeat(64)), Some("v1")))
+        );
+        assert_eq!(encode_path_component("a/b_c"), "a_2fb_5fc");
+    }
+}
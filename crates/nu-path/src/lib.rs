@@ -5,6 +5,6 @@ mod tilde;
 mod util;
 
 pub use expansions::{canonicalize_with, expand_path_with, expand_to_real_path};
-pub use helpers::{config_dir, home_dir};
+pub use helpers::{cache_dir, config_dir, home_dir};
 pub use tilde::expand_tilde;
 pub use util::trim_trailing_slash;
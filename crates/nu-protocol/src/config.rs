@@ -60,6 +60,17 @@ pub enum NuCursorShape {
     Block,
 }
 
+/// Where the inline hints shown as you type come from, in order of preference
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq, Eq)]
+pub enum HintSource {
+    /// The commands run in the current directory, most frequent first
+    Cwd,
+    /// All the commands in the history, most frequent first
+    History,
+    /// The first completion of the word under the cursor
+    Completions,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Config {
     pub external_completer: Option<usize>,
@@ -72,6 +83,7 @@ pub struct Config {
     pub float_precision: i64,
     pub max_external_completion_results: i64,
     pub filesize_format: String,
+    pub filesize_precision: i64,
     pub use_ansi_coloring: bool,
     pub quick_completions: bool,
     pub partial_completions: bool,
@@ -87,6 +99,8 @@ pub struct Config {
     pub rm_always_trash: bool,
     pub shell_integration: bool,
     pub buffer_editor: String,
+    pub bracketed_paste: bool,
+    pub hint_sources: Vec<HintSource>,
     pub table_index_mode: TableIndexMode,
     pub cd_with_abbreviations: bool,
     pub case_sensitive_completions: bool,
@@ -114,6 +128,7 @@ impl Default for Config {
             float_precision: 4,
             max_external_completion_results: 100,
             filesize_format: "auto".into(),
+            filesize_precision: 1,
             use_ansi_coloring: true,
             quick_completions: true,
             partial_completions: true,
@@ -129,6 +144,8 @@ impl Default for Config {
             rm_always_trash: false,
             shell_integration: false,
             buffer_editor: String::new(),
+            bracketed_paste: true,
+            hint_sources: vec![HintSource::Cwd, HintSource::History],
             table_index_mode: TableIndexMode::Always,
             cd_with_abbreviations: false,
             case_sensitive_completions: false,
@@ -931,6 +948,9 @@ impl Value {
                                             );
                                         }
                                     }
+                                    "precision" => {
+                                        try_int!(cols, vals, index, span, filesize_precision)
+                                    }
                                     x => {
                                         invalid_key!(
                                             cols,
@@ -946,10 +966,11 @@ impl Value {
                             invalid!(vals[index].span().ok(), "should be a record");
                             // Reconstruct
                             vals[index] = Value::record(
-                                vec!["metric".into(), "format".into()],
+                                vec!["metric".into(), "format".into(), "precision".into()],
                                 vec![
                                     Value::boolean(config.filesize_metric, *span),
                                     Value::string(config.filesize_format.clone(), *span),
+                                    Value::int(config.filesize_precision, *span),
                                 ],
                                 *span,
                             );
@@ -1145,6 +1166,30 @@ impl Value {
                     "shell_integration" => {
                         try_bool!(cols, vals, index, span, shell_integration);
                     }
+                    "bracketed_paste" => {
+                        try_bool!(cols, vals, index, span, bracketed_paste);
+                    }
+                    "hint_sources" => {
+                        if let Ok(sources) = value.as_list() {
+                            let mut hint_sources = vec![];
+                            for source in sources {
+                                match source.as_string().as_deref() {
+                                    Ok("cwd") => hint_sources.push(HintSource::Cwd),
+                                    Ok("history") => hint_sources.push(HintSource::History),
+                                    Ok("completions") => hint_sources.push(HintSource::Completions),
+                                    _ => {
+                                        invalid!(
+                                            source.span().ok(),
+                                            "unrecognized $env.config.{key} source; expected cwd, history or completions"
+                                        );
+                                    }
+                                }
+                            }
+                            config.hint_sources = hint_sources;
+                        } else {
+                            invalid!(Some(*span), "should be a list of strings");
+                        }
+                    }
                     "buffer_editor" => {
                         if let Ok(v) = value.as_string() {
                             config.buffer_editor = v.to_lowercase();
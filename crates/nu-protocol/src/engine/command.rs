@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use crate::{ast::Call, BlockId, Example, PipelineData, ShellError, Signature};
 
-use super::{EngineState, Stack};
+use super::{Capability, EngineState, Stack};
 
 #[derive(Debug)]
 pub enum CommandType {
@@ -82,6 +82,11 @@ pub trait Command: Send + Sync + CommandClone {
         vec![]
     }
 
+    // Side effects the command has, which a restricted engine denies unless they are allowed
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![]
+    }
+
     fn command_type(&self) -> CommandType {
         match (
             self.is_builtin(),
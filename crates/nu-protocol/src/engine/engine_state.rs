@@ -1,7 +1,10 @@
 use fancy_regex::Regex;
 use lru::LruCache;
 
-use super::{Command, EnvVars, OverlayFrame, ScopeFrame, Stack, Visibility, DEFAULT_OVERLAY_NAME};
+use super::{
+    Command, EnvVars, OverlayFrame, Restrictions, ScopeFrame, Stack, Visibility,
+    DEFAULT_OVERLAY_NAME,
+};
 use crate::Value;
 use crate::{
     ast::Block, AliasId, BlockId, Config, DeclId, Example, Module, ModuleId, OverlayId, ShellError,
@@ -31,6 +34,22 @@ pub enum ReplOperation {
     Replace(String),
 }
 
+/// Which of the hints for the current line the interactive shell shows. `commandline --next-hint`
+/// moves to the next one; typing something else starts over from the first.
+#[derive(Debug, Clone, Default)]
+pub struct HintCycle {
+    pub line: String,
+    pub offset: usize,
+}
+
+/// A closure registered with `signal trap`, together with the flag the signal handler sets
+/// when the signal is received.
+#[derive(Debug, Clone)]
+pub struct SignalTrap {
+    pub handler: Value,
+    pub received: Arc<AtomicBool>,
+}
+
 /// Organizes usage messages for various primitives
 #[derive(Debug, Clone)]
 pub struct Usage {
@@ -136,6 +155,7 @@ pub struct EngineState {
     pub pipeline_externals_state: Arc<(AtomicU32, AtomicU32)>,
     pub repl_buffer_state: Arc<Mutex<Option<String>>>,
     pub repl_operation_queue: Arc<Mutex<VecDeque<ReplOperation>>>,
+    pub hint_cycle: Arc<Mutex<HintCycle>>,
     #[cfg(feature = "plugin")]
     pub plugin_signatures: Option<PathBuf>,
     #[cfg(not(windows))]
@@ -145,11 +165,21 @@ pub struct EngineState {
     // If Nushell was started, e.g., with `nu spam.nu`, the file's parent is stored here
     pub currently_parsed_cwd: Option<PathBuf>,
     pub regex_cache: Arc<Mutex<LruCache<String, Regex>>>,
+    pub signal_traps: Arc<Mutex<HashMap<String, SignalTrap>>>,
+    // Dataframes cached by name with `dfr cache`, kept for the whole session
+    pub dataframe_cache: Arc<Mutex<HashMap<String, Value>>>,
+    // Set when running with `nu --restricted`, to deny commands with side effects
+    pub restrictions: Option<Restrictions>,
+    // How deep a recursive command can call itself, lowered by `run-with-limit --max-depth`
+    pub recursion_limit: u64,
 }
 
 // The max number of compiled regexes to keep around in a LRU cache, arbitrarily chosen
 const REGEX_CACHE_SIZE: usize = 100; // must be nonzero, otherwise will panic
 
+// picked 50 arbitrarily, should work on all architectures
+pub const DEFAULT_RECURSION_LIMIT: u64 = 50;
+
 pub const NU_VARIABLE_ID: usize = 0;
 pub const IN_VARIABLE_ID: usize = 1;
 pub const ENV_VARIABLE_ID: usize = 2;
@@ -185,6 +215,7 @@ impl EngineState {
             pipeline_externals_state: Arc::new((AtomicU32::new(0), AtomicU32::new(0))),
             repl_buffer_state: Arc::new(Mutex::new(None)),
             repl_operation_queue: Arc::new(Mutex::new(VecDeque::new())),
+            hint_cycle: Arc::new(Mutex::new(HintCycle::default())),
             #[cfg(feature = "plugin")]
             plugin_signatures: None,
             #[cfg(not(windows))]
@@ -195,6 +226,10 @@ impl EngineState {
             regex_cache: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(REGEX_CACHE_SIZE).expect("tried to create cache of size zero"),
             ))),
+            signal_traps: Arc::new(Mutex::new(HashMap::new())),
+            dataframe_cache: Arc::new(Mutex::new(HashMap::new())),
+            restrictions: None,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
         }
     }
 
@@ -1264,6 +1299,27 @@ impl<'a> StateWorkingSet<'a> {
         decl_id
     }
 
+    // Adds a declaration that accepts any input without building its signature, which is
+    // only needed for input-typed overloads. The signature is then built when the command is
+    // first parsed or described, so registering hundreds of builtins at startup stays cheap.
+    // Debug builds still build it, to catch typed commands that must go through add_decl.
+    pub fn add_untyped_decl(&mut self, decl: Box<dyn Command>) -> DeclId {
+        debug_assert!(
+            decl.signature().input_type == Type::Any,
+            "{} has an input type, so it must be added with add_decl",
+            decl.name()
+        );
+        let name = decl.name().as_bytes().to_vec();
+
+        self.delta.decls.push(decl);
+        let decl_id = self.num_decls() - 1;
+
+        self.last_overlay_mut()
+            .insert_decl(name, Type::Any, decl_id);
+
+        decl_id
+    }
+
     pub fn use_decls(&mut self, decls: Vec<(Vec<u8>, DeclId)>) {
         let overlay_frame = self.last_overlay_mut();
 
@@ -2379,6 +2435,7 @@ fn build_usage(comment_lines: &[&[u8]]) -> (String, String) {
 #[cfg(test)]
 mod engine_state_tests {
     use super::*;
+    use crate::{ast::Call, PipelineData};
 
     #[test]
     fn add_file_gives_id() {
@@ -2420,4 +2477,52 @@ mod engine_state_tests {
 
         Ok(())
     }
+
+    #[derive(Clone)]
+    struct Typed(Type);
+
+    impl Command for Typed {
+        fn name(&self) -> &str {
+            "typed"
+        }
+
+        fn signature(&self) -> Signature {
+            Signature::build("typed").input_type(self.0.clone())
+        }
+
+        fn usage(&self) -> &str {
+            ""
+        }
+
+        fn run(
+            &self,
+            _engine_state: &EngineState,
+            _stack: &mut Stack,
+            _call: &Call,
+            _input: PipelineData,
+        ) -> Result<PipelineData, ShellError> {
+            Ok(PipelineData::empty())
+        }
+    }
+
+    #[test]
+    fn add_untyped_decl_accepts_any_input() {
+        let engine_state = EngineState::new();
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        let decl_id = working_set.add_untyped_decl(Box::new(Typed(Type::Any)));
+
+        assert_eq!(
+            working_set.find_decl(b"typed", &Type::String),
+            Some(decl_id)
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "must be added with add_decl")]
+    fn add_untyped_decl_rejects_typed_commands() {
+        let engine_state = EngineState::new();
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        working_set.add_untyped_decl(Box::new(Typed(Type::Custom("dataframe".into()))));
+    }
 }
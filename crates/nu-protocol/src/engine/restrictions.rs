@@ -0,0 +1,227 @@
+use super::Command;
+use crate::Category;
+use std::collections::HashSet;
+
+/// A kind of side effect that a restricted engine denies unless it is allowed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    FilesystemWrite,
+    External,
+    Network,
+}
+
+impl Capability {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "filesystem" => Some(Capability::FilesystemWrite),
+            "external" => Some(Capability::External),
+            "network" => Some(Capability::Network),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::FilesystemWrite => "filesystem",
+            Capability::External => "external",
+            Capability::Network => "network",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Capability::FilesystemWrite => "filesystem writes",
+            Capability::External => "external commands",
+            Capability::Network => "network access",
+        }
+    }
+}
+
+/// The side effects denied to the code a restricted engine (`nu --restricted`) runs. Commands
+/// are allowed either by capability or one by one by name.
+#[derive(Clone, Debug, Default)]
+pub struct Restrictions {
+    allowed_capabilities: HashSet<Capability>,
+    allowed_commands: HashSet<String>,
+}
+
+impl Restrictions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_capability(&mut self, capability: Capability) {
+        self.allowed_capabilities.insert(capability);
+    }
+
+    pub fn allow_command(&mut self, name: impl Into<String>) {
+        self.allowed_commands.insert(name.into());
+    }
+
+    /// The capability a command needs that isn't allowed, if any
+    pub fn denied_capability(&self, decl: &dyn Command) -> Option<Capability> {
+        if self.allowed_commands.contains(decl.name()) {
+            return None;
+        }
+
+        required_capabilities(decl)
+            .into_iter()
+            .find(|capability| !self.allowed_capabilities.contains(capability))
+    }
+
+    /// Whether a command may use a capability it only needs for some of its uses, and so
+    /// doesn't declare, like a file written by a command that can also work in memory
+    pub fn allows(&self, name: &str, capability: Capability) -> bool {
+        self.allowed_commands.contains(name) || self.allowed_capabilities.contains(&capability)
+    }
+
+    /// Like `denied_capability`, for an external command, which can be allowed by its own name
+    pub fn denied_external(&self, name: &str) -> Option<Capability> {
+        if self.allowed_commands.contains(name)
+            || self.allowed_capabilities.contains(&Capability::External)
+        {
+            None
+        } else {
+            Some(Capability::External)
+        }
+    }
+}
+
+fn required_capabilities(decl: &dyn Command) -> Vec<Capability> {
+    let mut capabilities = decl.capabilities();
+    // A plugin is a process of its own, which can do anything
+    if decl.is_plugin().is_some() {
+        capabilities.push(Capability::External);
+    }
+    // The url commands only work on strings
+    if matches!(decl.signature().category, Category::Network) && !decl.name().starts_with("url") {
+        capabilities.push(Capability::Network);
+    }
+    capabilities
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::Call;
+    use crate::engine::{EngineState, Stack};
+    use crate::{PipelineData, ShellError, Signature};
+
+    #[derive(Clone)]
+    struct TestCommand {
+        name: &'static str,
+        category: Category,
+        capabilities: Vec<Capability>,
+    }
+
+    impl TestCommand {
+        fn new(name: &'static str, category: Category, capabilities: Vec<Capability>) -> Self {
+            Self {
+                name,
+                category,
+                capabilities,
+            }
+        }
+    }
+
+    impl Command for TestCommand {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn signature(&self) -> Signature {
+            Signature::build(self.name).category(self.category.clone())
+        }
+
+        fn usage(&self) -> &str {
+            ""
+        }
+
+        fn run(
+            &self,
+            _engine_state: &EngineState,
+            _stack: &mut Stack,
+            _call: &Call,
+            _input: PipelineData,
+        ) -> Result<PipelineData, ShellError> {
+            Ok(PipelineData::empty())
+        }
+
+        fn capabilities(&self) -> Vec<Capability> {
+            self.capabilities.clone()
+        }
+    }
+
+    #[test]
+    fn denies_side_effects_by_default() {
+        let restrictions = Restrictions::new();
+        let save = TestCommand::new(
+            "save",
+            Category::FileSystem,
+            vec![Capability::FilesystemWrite],
+        );
+        let kill = TestCommand::new("kill", Category::Platform, vec![Capability::External]);
+        let http_get = TestCommand::new("http get", Category::Network, vec![]);
+        let url_parse = TestCommand::new("url parse", Category::Network, vec![]);
+        let open = TestCommand::new("open", Category::FileSystem, vec![]);
+
+        assert_eq!(
+            restrictions.denied_capability(&save),
+            Some(Capability::FilesystemWrite)
+        );
+        assert_eq!(
+            restrictions.denied_capability(&kill),
+            Some(Capability::External)
+        );
+        assert_eq!(
+            restrictions.denied_capability(&http_get),
+            Some(Capability::Network)
+        );
+        assert_eq!(restrictions.denied_capability(&url_parse), None);
+        assert_eq!(restrictions.denied_capability(&open), None);
+    }
+
+    #[test]
+    fn allows_by_capability_or_name() {
+        let mut restrictions = Restrictions::new();
+        restrictions.allow_capability(Capability::Network);
+        restrictions.allow_command("mkdir");
+        let http_get = TestCommand::new("http get", Category::Network, vec![]);
+        let mkdir = TestCommand::new(
+            "mkdir",
+            Category::FileSystem,
+            vec![Capability::FilesystemWrite],
+        );
+        let rm = TestCommand::new(
+            "rm",
+            Category::FileSystem,
+            vec![Capability::FilesystemWrite],
+        );
+
+        assert_eq!(restrictions.denied_capability(&http_get), None);
+        assert_eq!(restrictions.denied_capability(&mkdir), None);
+        assert_eq!(
+            restrictions.denied_capability(&rm),
+            Some(Capability::FilesystemWrite)
+        );
+        assert_eq!(
+            restrictions.denied_external("git"),
+            Some(Capability::External)
+        );
+
+        restrictions.allow_command("git");
+        assert_eq!(restrictions.denied_external("git"), None);
+    }
+
+    #[test]
+    fn needs_every_capability_of_a_command() {
+        let mut restrictions = Restrictions::new();
+        restrictions.allow_capability(Capability::Network);
+        let fetch = TestCommand::new("fetch", Category::Network, vec![Capability::External]);
+
+        assert_eq!(
+            restrictions.denied_capability(&fetch),
+            Some(Capability::External)
+        );
+    }
+}
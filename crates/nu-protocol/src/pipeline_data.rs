@@ -4,7 +4,7 @@ use crate::{
     format_error, Config, ListStream, RawStream, ShellError, Span, Value,
 };
 use nu_utils::{stderr_write_all_and_flush, stdout_write_all_and_flush};
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::{atomic::AtomicBool, mpsc::Receiver, Arc};
 use std::thread;
 
 const LINE_ENDING_PATTERN: &[char] = &['\r', '\n'];
@@ -46,6 +46,9 @@ pub enum PipelineData {
         stdout: Option<RawStream>,
         stderr: Option<RawStream>,
         exit_code: Option<ListStream>,
+        /// A record of the time and resources an external command used, sent once it exits.
+        /// Only `complete` reads it, so `exit_code` stays a stream of exit codes.
+        resource_usage: Option<Receiver<Value>>,
         span: Span,
         metadata: Option<PipelineMetadata>,
         trim_end_newline: bool,
@@ -62,6 +65,7 @@ pub struct PipelineMetadata {
 pub enum DataSource {
     Ls,
     HtmlThemes,
+    Http { content_type: Option<String> },
 }
 
 impl PipelineData {
@@ -531,6 +535,7 @@ impl PipelineData {
             stdout: None,
             stderr,
             mut exit_code,
+            resource_usage,
             span,
             metadata,
             trim_end_newline,
@@ -578,6 +583,7 @@ impl PipelineData {
                             stdout: None,
                             stderr,
                             exit_code: Some(ListStream::from_stream(exit_code.into_iter(), ctrlc)),
+                            resource_usage,
                             span,
                             metadata,
                             trim_end_newline,
@@ -590,6 +596,7 @@ impl PipelineData {
                         stdout: None,
                         stderr,
                         exit_code: None,
+                        resource_usage,
                         span,
                         metadata,
                         trim_end_newline,
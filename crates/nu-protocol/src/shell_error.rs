@@ -652,6 +652,19 @@ Either make sure {0} is a string, or add a 'to_string' entry for it in ENV_CONVE
     #[diagnostic(code(nu::shell::permission_denied), url(docsrs))]
     PermissionDeniedError(String, #[label("{0}")] Span),
 
+    /// A command with side effects was run in restricted mode.
+    ///
+    /// ## Resolution
+    ///
+    /// Allow the command, or what it needs, with `nu --allow`, or run nu without `--restricted`.
+    #[error("{0} is not allowed in restricted mode")]
+    #[diagnostic(
+        code(nu::shell::restricted),
+        url(docsrs),
+        help("allow it with --allow '{0}' or --allow {2}")
+    )]
+    RestrictedCommand(String, String, String, #[label("needs {1}")] Span),
+
     /// Out of memory.
     ///
     /// ## Resolution
@@ -931,6 +944,15 @@ Either make sure {0} is a string, or add a 'to_string' entry for it in ENV_CONVE
         span: Option<Span>,
     },
 
+    /// A closure run with `run-with-limit` went past one of its limits.
+    ///
+    /// ## Resolution
+    ///
+    /// Make the closure do less work, or raise the limit it went past.
+    #[error("Limit exceeded")]
+    #[diagnostic(code(nu::shell::limit_exceeded), url(docsrs))]
+    LimitExceeded(String, #[label("{0}")] Span),
+
     /// An attempt to access a record column failed.
     #[error("Access failure: {message}")]
     #[diagnostic(code(nu::shell::lazy_record_access_failed), url(docsrs))]
@@ -3470,15 +3470,18 @@ pub fn format_filesize_from_conf(num_bytes: i64, config: &Config) -> String {
         num_bytes,
         config.filesize_format.as_str(),
         Some(config.filesize_metric),
+        config.filesize_precision.max(0) as usize,
     )
 }
 
 // filesize_metric is explicit when printed a value according to user config;
 // other places (such as `format filesize`) don't.
+// precision is the number of fractional digits of every unit but bytes.
 pub fn format_filesize(
     num_bytes: i64,
     format_value: &str,
     filesize_metric: Option<bool>,
+    precision: usize,
 ) -> String {
     // Allow the user to specify how they want their numbers formatted
 
@@ -3514,9 +3517,9 @@ pub fn format_filesize(
         }
         _ => {
             if num_bytes.is_negative() {
-                format!("-{}", adj_byte.format(1))
+                format!("-{}", adj_byte.format(precision))
             } else {
-                adj_byte.format(1)
+                adj_byte.format(precision)
             }
         }
     }
@@ -3549,7 +3552,7 @@ fn get_filesize_format(format_value: &str, filesize_metric: Option<bool>) -> (By
         "mb" | "mib" => either!(format_value, MB, MiB),
         "gb" | "gib" => either!(format_value, GB, GiB),
         "tb" | "tib" => either!(format_value, TB, TiB),
-        "pb" | "pib" => either!(format_value, TB, TiB),
+        "pb" | "pib" => either!(format_value, PB, PiB),
         "eb" | "eib" => either!(format_value, EB, EiB),
         "zb" | "zib" => either!(format_value, ZB, ZiB),
         _ => (byte_unit::ByteUnit::B, "auto"),
@@ -3657,4 +3660,28 @@ mod tests {
             );
         }
     }
+
+    mod format_filesize {
+        use crate::{format_filesize, format_filesize_from_conf, Config};
+
+        #[test]
+        fn test_precision_and_metric_from_config() {
+            let config = Config {
+                filesize_metric: true,
+                filesize_precision: 2,
+                ..Default::default()
+            };
+
+            assert_eq!(format_filesize_from_conf(1234567, &config), "1.23 MB");
+            assert_eq!(format_filesize_from_conf(-1999999999, &config), "-2.00 GB");
+        }
+
+        #[test]
+        fn test_petabytes() {
+            assert_eq!(
+                format_filesize(2_000_000_000_000_000, "pb", None, 1),
+                "2.0 PB"
+            );
+        }
+    }
 }
@@ -4,18 +4,23 @@ pub const LOCALE_OVERRIDE_ENV_VAR: &str = "NU_TEST_LOCALE_OVERRIDE";
 
 pub fn get_system_locale() -> Locale {
     let locale_string = get_system_locale_string().unwrap_or_else(|| String::from("en-US"));
+    locale_from_name(&locale_string).unwrap_or(Locale::en)
+}
+
+/// Find the locale for a name like `de`, `de-DE` or `de_DE`, falling back to the language alone
+pub fn locale_from_name(name: &str) -> Option<Locale> {
     // Since get_locale() and Locale::from_name() don't always return the same items
     // we need to try and parse it to match. For instance, a valid locale is de_DE
     // however Locale::from_name() wants only de so we split and parse it out.
-    let locale_string = locale_string.replace('_', "-"); // en_AU -> en-AU
+    let name = name.replace('_', "-"); // en_AU -> en-AU
 
-    Locale::from_name(&locale_string).unwrap_or_else(|_| {
+    Locale::from_name(&name).ok().or_else(|| {
         let all = num_format::Locale::available_names();
-        let locale_prefix = &locale_string.split('-').collect::<Vec<&str>>();
-        if all.contains(&locale_prefix[0]) {
-            Locale::from_name(locale_prefix[0]).unwrap_or(Locale::en)
+        let language = name.split('-').next().unwrap_or_default();
+        if all.contains(&language) {
+            Locale::from_name(language).ok()
         } else {
-            Locale::en
+            None
         }
     })
 }
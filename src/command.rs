@@ -1,10 +1,10 @@
-use nu_cli::report_error;
+use nu_cli::{report_error, OutputFormat};
 use nu_engine::{get_full_help, CallExt};
+use nu_parser::escape_quote_string;
 use nu_parser::parse;
-use nu_parser::{escape_for_script_arg, escape_quote_string};
 use nu_protocol::{
     ast::{Call, Expr, Expression, PipelineElement},
-    engine::{Command, EngineState, Stack, StateWorkingSet},
+    engine::{Capability, Command, EngineState, Restrictions, Stack, StateWorkingSet},
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
     Value,
 };
@@ -33,10 +33,12 @@ pub(crate) fn gather_commandline_args() -> (Vec<String>, String, Vec<String>) {
 
         let flag_value = match arg.as_ref() {
             "--commands" | "-c" | "--table-mode" | "-m" | "-e" | "--execute" | "--config"
-            | "--env-config" => args.next().map(|a| escape_quote_string(&a)),
+            | "--env-config" | "--output-format" | "-o" | "--stdin-format" | "--args-as"
+            | "--allow" => args.next().map(|a| escape_quote_string(&a)),
             #[cfg(feature = "plugin")]
             "--plugin-config" => args.next().map(|a| escape_quote_string(&a)),
-            "--log-level" | "--log-target" | "--testbin" | "--threads" | "-t" => args.next(),
+            "--log-level" | "--log-target" | "--testbin" | "--threads" | "-t"
+            | "--error-exit-code" => args.next(),
             _ => None,
         };
 
@@ -47,8 +49,9 @@ pub(crate) fn gather_commandline_args() -> (Vec<String>, String, Vec<String>) {
         }
     }
 
+    // The arguments are escaped later, since how depends on --args-as
     let args_to_script = if !script_name.is_empty() {
-        args.map(|arg| escape_for_script_arg(&arg)).collect()
+        args.collect()
     } else {
         Vec::default()
     };
@@ -95,6 +98,9 @@ pub(crate) fn parse_commandline_args(
         )) = pipeline.elements.get(0)
         {
             let redirect_stdin = call.get_named_arg("stdin");
+            let capture = call.has_flag("capture");
+            let restricted = call.has_flag("restricted");
+            let allow: Option<Expression> = call.get_flag_expr("allow");
             let login_shell = call.get_named_arg("login");
             let interactive_shell = call.get_named_arg("interactive");
             let commands: Option<Expression> = call.get_flag_expr("commands");
@@ -106,6 +112,11 @@ pub(crate) fn parse_commandline_args(
             let log_level: Option<Expression> = call.get_flag_expr("log-level");
             let log_target: Option<Expression> = call.get_flag_expr("log-target");
             let execute: Option<Expression> = call.get_flag_expr("execute");
+            let output_format: Option<Expression> = call.get_flag_expr("output-format");
+            let stdin_format: Option<Expression> = call.get_flag_expr("stdin-format");
+            let args_as: Option<Expression> = call.get_flag_expr("args-as");
+            let error_exit_code: Option<Spanned<i64>> =
+                call.get_flag(engine_state, &mut stack, "error-exit-code")?;
             let threads: Option<Value> = call.get_flag(engine_state, &mut stack, "threads")?;
             let table_mode: Option<Value> =
                 call.get_flag(engine_state, &mut stack, "table-mode")?;
@@ -137,6 +148,55 @@ pub(crate) fn parse_commandline_args(
             let log_level = extract_contents(log_level)?;
             let log_target = extract_contents(log_target)?;
             let execute = extract_contents(execute)?;
+            let output_format = match extract_contents(output_format)? {
+                Some(format) => Some(OutputFormat::from_name(&format.item).ok_or_else(|| {
+                    ShellError::UnsupportedConfigValue(
+                        "json or nuon".into(),
+                        format.item.clone(),
+                        format.span,
+                    )
+                })?),
+                None => None,
+            };
+            let stdin_format = extract_contents(stdin_format)?;
+            let args_as_list = match extract_contents(args_as)? {
+                Some(args_as) if args_as.item == "list" => true,
+                Some(args_as) if args_as.item == "parsed" => false,
+                Some(args_as) => {
+                    return Err(ShellError::UnsupportedConfigValue(
+                        "parsed or list".into(),
+                        args_as.item,
+                        args_as.span,
+                    ))
+                }
+                None => false,
+            };
+            let error_exit_code = match error_exit_code {
+                Some(code) => i32::try_from(code.item).map_err(|_| {
+                    ShellError::UnsupportedConfigValue(
+                        "an exit code".into(),
+                        code.item.to_string(),
+                        code.span,
+                    )
+                })?,
+                None => 1,
+            };
+
+            // --allow implies --restricted
+            let restrictions = match extract_contents(allow)? {
+                Some(allow) => {
+                    let mut restrictions = Restrictions::new();
+                    for name in allow.item.split(',').map(str::trim) {
+                        match Capability::from_name(name) {
+                            Some(capability) => restrictions.allow_capability(capability),
+                            None => restrictions.allow_command(name),
+                        }
+                    }
+                    Some(restrictions)
+                }
+                None if restricted => Some(Restrictions::new()),
+                None => None,
+            };
 
             let help = call.has_flag("help");
 
@@ -178,6 +238,12 @@ pub(crate) fn parse_commandline_args(
                 execute,
                 threads,
                 table_mode,
+                output_format,
+                stdin_format,
+                args_as_list,
+                error_exit_code,
+                capture,
+                restrictions,
             });
         }
     }
@@ -209,6 +275,12 @@ pub(crate) struct NushellCliArgs {
     pub(crate) execute: Option<Spanned<String>>,
     pub(crate) threads: Option<Value>,
     pub(crate) table_mode: Option<Value>,
+    pub(crate) output_format: Option<OutputFormat>,
+    pub(crate) stdin_format: Option<Spanned<String>>,
+    pub(crate) args_as_list: bool,
+    pub(crate) error_exit_code: i32,
+    pub(crate) capture: bool,
+    pub(crate) restrictions: Option<Restrictions>,
 }
 
 #[derive(Clone)]
@@ -242,6 +314,17 @@ impl Command for Nu {
                 "the table mode to use. rounded is default.",
                 Some('m'),
             )
+            .named(
+                "output-format",
+                SyntaxShape::String,
+                "write the result of the commands or script as json or nuon, and errors as json",
+                Some('o'),
+            )
+            .switch(
+                "capture",
+                "write the value of the commands or script and its errors as a single json object",
+                None,
+            )
             .named(
                 "threads",
                 SyntaxShape::Int,
@@ -290,6 +373,35 @@ impl Command for Nu {
                 "redirect standard input to a command (with `-c`) or a script file",
                 None,
             )
+            .named(
+                "stdin-format",
+                SyntaxShape::String,
+                "read standard input as raw (default), auto to detect json, or a format with a from command, like csv. Implies --stdin",
+                None,
+            )
+            .named(
+                "args-as",
+                SyntaxShape::String,
+                "how the script arguments are given to main: parsed (default) against its signature, or as a single list of strings",
+                None,
+            )
+            .named(
+                "error-exit-code",
+                SyntaxShape::Int,
+                "the exit code of a script that fails with an error (default 1)",
+                None,
+            )
+            .switch(
+                "restricted",
+                "deny filesystem writes, external commands and network access",
+                None,
+            )
+            .named(
+                "allow",
+                SyntaxShape::String,
+                "comma separated capabilities (filesystem, external, network) or commands to allow in restricted mode. Implies --restricted",
+                None,
+            )
             .named(
                 "testbin",
                 SyntaxShape::String,
@@ -336,6 +448,26 @@ impl Command for Nu {
                 example: "nu myfile.nu",
                 result: None,
             },
+            Example {
+                description: "Get the result of a pipeline as json, for another program to read",
+                example: "nu --output-format json -c 'ls | where size > 1mb'",
+                result: None,
+            },
+            Example {
+                description: "Evaluate a script and get its value and errors in one json object",
+                example: "nu --capture myfile.nu",
+                result: None,
+            },
+            Example {
+                description: "Run a script with its input read as csv, and its arguments given to main as a list",
+                example: "nu --stdin-format csv --args-as list myfile.nu a b",
+                result: None,
+            },
+            Example {
+                description: "Evaluate an untrusted expression, only allowing it to make http requests",
+                example: "nu --restricted --allow network -c $expression",
+                result: None,
+            },
             Example {
                 description: "Run nushell interactively (as a shell or REPL)",
                 example: "nu",
@@ -21,7 +21,7 @@ use nu_cli::{gather_parent_env_vars, get_init_cwd, report_error_new};
 use nu_command::create_default_context;
 use nu_protocol::{util::BufferedReader, PipelineData, RawStream};
 use nu_utils::utils::perf;
-use run::{run_commands, run_file, run_repl};
+use run::{read_stdin_as, run_commands, run_file, run_repl};
 use signals::{ctrlc_protection, sigquit_protection};
 use std::{
     io::BufReader,
@@ -45,8 +45,8 @@ fn main() -> Result<()> {
     // Custom additions
     let delta = {
         let mut working_set = nu_protocol::engine::StateWorkingSet::new(&engine_state);
-        working_set.add_decl(Box::new(nu_cli::NuHighlight));
-        working_set.add_decl(Box::new(nu_cli::Print));
+        working_set.add_untyped_decl(Box::new(nu_cli::NuHighlight));
+        working_set.add_untyped_decl(Box::new(nu_cli::Print));
         working_set.render()
     };
 
@@ -61,7 +61,10 @@ fn main() -> Result<()> {
 
     let (args_to_nushell, script_name, args_to_script) = gather_commandline_args();
     let parsed_nu_cli_args = parse_commandline_args(&args_to_nushell.join(" "), &mut engine_state)
-        .unwrap_or_else(|_| std::process::exit(1));
+        .unwrap_or_else(|err| {
+            report_error_new(&engine_state, &err);
+            std::process::exit(1)
+        });
 
     let use_color = engine_state.get_config().use_ansi_coloring;
     if let Some(level) = parsed_nu_cli_args
@@ -183,7 +186,12 @@ fn main() -> Result<()> {
     );
 
     start_time = std::time::Instant::now();
-    let input = if let Some(redirect_stdin) = &parsed_nu_cli_args.redirect_stdin {
+    // --stdin-format implies --stdin
+    let redirect_stdin = parsed_nu_cli_args
+        .redirect_stdin
+        .as_ref()
+        .or(parsed_nu_cli_args.stdin_format.as_ref());
+    let input = if let Some(redirect_stdin) = redirect_stdin {
         let stdin = std::io::stdin();
         let buf_reader = BufReader::new(stdin);
 
@@ -196,6 +204,7 @@ fn main() -> Result<()> {
             )),
             stderr: None,
             exit_code: None,
+            resource_usage: None,
             span: redirect_stdin.span,
             metadata: None,
             trim_end_newline: false,
@@ -203,6 +212,13 @@ fn main() -> Result<()> {
     } else {
         PipelineData::empty()
     };
+    let input = match &parsed_nu_cli_args.stdin_format {
+        Some(format) => read_stdin_as(&engine_state, input, format).unwrap_or_else(|err| {
+            report_error_new(&engine_state, &err);
+            std::process::exit(1)
+        }),
+        None => input,
+    };
     perf(
         "redirect stdin",
         start_time,
@@ -224,6 +240,9 @@ fn main() -> Result<()> {
         use_color,
     );
 
+    // Restrictions apply from here on, to the config files too
+    engine_state.restrictions = parsed_nu_cli_args.restrictions.clone();
+
     if let Some(commands) = parsed_nu_cli_args.commands.clone() {
         run_commands(
             &mut engine_state,
@@ -6,9 +6,19 @@ use crate::{
 };
 #[cfg(feature = "plugin")]
 use nu_cli::read_plugin_file;
-use nu_cli::{evaluate_commands, evaluate_file, evaluate_repl};
-use nu_protocol::PipelineData;
-use nu_utils::utils::perf;
+use nu_cli::{
+    eval_captured, eval_file_captured, evaluate_commands, evaluate_file, evaluate_repl,
+    report_error_structured, Captured,
+};
+use nu_engine::convert_env_values;
+use nu_parser::{escape_for_script_arg, escape_quote_string};
+use nu_protocol::{
+    ast::Call,
+    engine::{EngineState, Stack, StateWorkingSet},
+    PipelineData, ShellError, Spanned, Value,
+};
+use nu_utils::{stdout_write_all_and_flush, utils::perf};
+use std::collections::HashMap;
 
 pub(crate) fn run_commands(
     engine_state: &mut nu_protocol::engine::EngineState,
@@ -69,6 +79,19 @@ pub(crate) fn run_commands(
         use_color,
     );
 
+    if parsed_nu_cli_args.capture {
+        let env = captured_env(engine_state, &stack, parsed_nu_cli_args.error_exit_code);
+        let captured = eval_captured(
+            engine_state,
+            commands.item.as_bytes(),
+            "<commandline>",
+            env,
+            input,
+        );
+        print_captured(engine_state, captured, parsed_nu_cli_args.error_exit_code);
+        return Ok(());
+    }
+
     let start_time = std::time::Instant::now();
     let ret_val = evaluate_commands(
         commands,
@@ -76,6 +99,7 @@ pub(crate) fn run_commands(
         &mut stack,
         input,
         parsed_nu_cli_args.table_mode,
+        parsed_nu_cli_args.output_format,
     );
     perf(
         "evaluate_commands",
@@ -154,6 +178,28 @@ pub(crate) fn run_file(
         use_color,
     );
 
+    // With --args-as list, main gets all the arguments as strings, flags included
+    let args_to_script = if parsed_nu_cli_args.args_as_list {
+        let args: Vec<_> = args_to_script
+            .iter()
+            .map(|arg| escape_quote_string(arg))
+            .collect();
+        vec![format!("[{}]", args.join(" "))]
+    } else {
+        args_to_script
+            .iter()
+            .map(|arg| escape_for_script_arg(arg))
+            .collect()
+    };
+
+    if parsed_nu_cli_args.capture {
+        let env = captured_env(engine_state, &stack, parsed_nu_cli_args.error_exit_code);
+        let path = std::fs::canonicalize(&script_name).unwrap_or_else(|_| script_name.into());
+        let captured = eval_file_captured(engine_state, &path, &args_to_script, env, input);
+        print_captured(engine_state, captured, parsed_nu_cli_args.error_exit_code);
+        return Ok(());
+    }
+
     let start_time = std::time::Instant::now();
     let ret_val = evaluate_file(
         script_name,
@@ -161,6 +207,8 @@ pub(crate) fn run_file(
         engine_state,
         &mut stack,
         input,
+        parsed_nu_cli_args.output_format,
+        parsed_nu_cli_args.error_exit_code,
     );
     perf(
         "evaluate_file",
@@ -240,3 +288,74 @@ pub(crate) fn run_repl(
 
     ret_val
 }
+
+// The environment read from env.nu and the config, converted to values, for --capture
+fn captured_env(
+    engine_state: &mut EngineState,
+    stack: &Stack,
+    error_exit_code: i32,
+) -> HashMap<String, Value> {
+    if let Some(e) = convert_env_values(engine_state, stack) {
+        report_error_structured(&StateWorkingSet::new(engine_state), &e);
+        std::process::exit(error_exit_code);
+    }
+
+    stack.get_env_vars(engine_state)
+}
+
+// Writes the captured value and errors as one JSON object on stdout, and exits with the error exit
+// code if there were errors
+fn print_captured(engine_state: &EngineState, captured: Captured, error_exit_code: i32) {
+    match captured.to_json(engine_state) {
+        Ok(json) => {
+            let _ = stdout_write_all_and_flush(json + "\n");
+        }
+        Err(e) => {
+            report_error_structured(&StateWorkingSet::new(engine_state), &e);
+            std::process::exit(error_exit_code);
+        }
+    }
+
+    if !captured.is_success() {
+        std::process::exit(error_exit_code);
+    }
+}
+
+/// Reads the redirected standard input with the `from` command of the given format. `raw` keeps
+/// the stream as it is, and `auto` reads it as JSON when it looks like JSON, or as a string.
+pub(crate) fn read_stdin_as(
+    engine_state: &EngineState,
+    input: PipelineData,
+    format: &Spanned<String>,
+) -> Result<PipelineData, ShellError> {
+    let span = format.span;
+    let (format, input) = match format.item.as_str() {
+        "raw" => return Ok(input),
+        "auto" => {
+            let contents = input.collect_string("", engine_state.get_config())?;
+            let trimmed = contents.trim_start();
+            if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+                return Ok(PipelineData::Value(Value::string(contents, span), None));
+            }
+            (
+                "json",
+                PipelineData::Value(Value::string(contents, span), None),
+            )
+        }
+        format => (format, input),
+    };
+
+    let decl_id = engine_state
+        .find_decl(format!("from {format}").as_bytes(), &[])
+        .ok_or_else(|| {
+            ShellError::UnsupportedConfigValue(
+                "raw, auto, or a format with a from command".into(),
+                format.into(),
+                span,
+            )
+        })?;
+
+    engine_state
+        .get_decl(decl_id)
+        .run(engine_state, &mut Stack::new(), &Call::new(span), input)
+}
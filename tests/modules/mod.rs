@@ -562,3 +562,87 @@ fn main_inside_module_is_main() {
 
     assert_eq!(actual.out, "foo");
 }
+
+#[test]
+fn remote_module_needs_a_hash() {
+    let inp = &["use https://example.com/spam.nu"];
+
+    let actual = nu!(cwd: ".", pipeline(&inp.join("; ")));
+
+    assert!(actual.err.contains("remote_module_not_pinned"));
+}
+
+#[test]
+fn remote_module_from_git_needs_a_pin() {
+    let inp = &["use git+https://example.com/team/spam.git/spam.nu"];
+
+    let actual = nu!(cwd: ".", pipeline(&inp.join("; ")));
+
+    assert!(actual.err.contains("remote_module_not_pinned"));
+}
+
+#[test]
+fn remote_module_is_not_fetched_over_http() {
+    let inp = &[&format!(
+        "use http://example.com/spam.nu --hash {}",
+        "0".repeat(64)
+    )];
+
+    let actual = nu!(cwd: ".", pipeline(&inp.join("; ")));
+
+    assert!(actual.err.contains("remote_module_fetch_failed"));
+}
+
+#[test]
+fn remote_module_hash_must_be_sha256() {
+    let inp = &["overlay use https://example.com/spam.nu --hash abc"];
+
+    let actual = nu!(cwd: ".", pipeline(&inp.join("; ")));
+
+    assert!(actual.err.contains("sha256"));
+}
+
+#[test]
+fn remote_module_must_be_fetched_first() {
+    let inp = &[&format!(
+        "use https://example.com/not-fetched.nu --hash {}",
+        "1".repeat(64)
+    )];
+
+    let actual = nu!(cwd: ".", pipeline(&inp.join("; ")));
+
+    assert!(actual.err.contains("remote_module_not_fetched"));
+    assert!(actual.err.contains("fetch-module"));
+}
+
+#[test]
+fn fetch_module_rejects_tags_that_look_like_options() {
+    let inp = &[
+        "fetch-module git+https://example.com/team/spam.git/spam.nu --tag ('--upload-pack=touch')",
+    ];
+
+    let actual = nu!(cwd: ".", pipeline(&inp.join("; ")));
+
+    assert!(actual.err.contains("can't start with `-`"));
+}
+
+#[test]
+fn fetch_module_times_out() {
+    // Connections are queued but never answered, so the download waits on the server
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to pick a port");
+    let port = listener
+        .local_addr()
+        .expect("listener has an address")
+        .port();
+    let inp = &[format!(
+        "fetch-module https://127.0.0.1:{port}/slow.nu --hash {} --timeout 1sec",
+        "1".repeat(64)
+    )];
+
+    let start = std::time::Instant::now();
+    let actual = nu!(cwd: ".", pipeline(&inp.join("; ")));
+
+    assert!(actual.err.contains("Can't fetch remote module"));
+    assert!(start.elapsed() < std::time::Duration::from_secs(20));
+    drop(listener);
+}
@@ -85,6 +85,56 @@ fn env_assignment() {
     assert_eq!(actual.out, "barbaz");
 }
 
+#[test]
+fn env_assignment_converts_strings_with_from_string() {
+    let actual = nu!(cwd: ".", r#"
+        $env.ENV_CONVERSIONS = { FOO: { from_string: { |s| $s | split row ',' } } }
+        $env.FOO = "a,b,c"
+        $env.FOO | length
+    "#);
+    assert_eq!(actual.out, "3");
+}
+
+#[test]
+fn let_env_converts_strings_with_from_string() {
+    let actual = nu!(cwd: ".", r#"
+        $env.ENV_CONVERSIONS = { FOO: { from_string: { |s| $s | split row ',' } } }
+        let-env FOO = "a,b"
+        $env.FOO | length
+    "#);
+    assert_eq!(actual.out, "2");
+}
+
+#[test]
+fn env_assignment_checks_declared_type() {
+    let actual = nu!(cwd: ".", r#"
+        $env.ENV_CONVERSIONS = { FOO: { type: int } }
+        $env.FOO = "bar"
+    "#);
+    assert!(actual.err.contains("declared as int"));
+}
+
+#[test]
+fn env_assignment_keeps_values_of_declared_type() {
+    let actual = nu!(cwd: ".", r#"
+        $env.ENV_CONVERSIONS = { FOO: { type: list, from_string: { |s| $s | split row ',' } } }
+        $env.FOO = [a b]
+        $env.FOO | str join '-'
+    "#);
+    assert_eq!(actual.out, "a-b");
+}
+
+#[test]
+fn typed_env_var_is_converted_for_externals() {
+    let actual = nu!(cwd: ".", r#"
+        $env.ENV_CONVERSIONS = { FOO: { type: list, from_string: { |s| $s | split row ',' }, to_string: { |v| $v | str join ',' } } }
+        $env.FOO = "a,b"
+        $env.FOO = ($env.FOO | append c)
+        nu --testbin echo_env FOO
+    "#);
+    assert_eq!(actual.out, "a,b,c");
+}
+
 #[test]
 fn let_env_file_pwd_env_var_fails() {
     let actual = nu!(cwd: ".", r#"let-env FILE_PWD = 'foo'"#);
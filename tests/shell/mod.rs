@@ -199,3 +199,351 @@ fn run_export_extern() {
         assert!(actual.out.contains("Usage"));
     })
 }
+
+fn run_nu(args: &[&str]) -> std::process::Output {
+    std::process::Command::new(nu_test_support::fs::executable_path())
+        .args(args)
+        .output()
+        .expect("failed to execute nu")
+}
+
+#[test]
+fn output_format_writes_commands_result_as_json() {
+    let output = run_nu(&["--output-format", "json", "-c", "[[a b]; [1 two]]"]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let result: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is json");
+    assert_eq!(result, serde_json::json!([{"a": 1, "b": "two"}]));
+    assert!(output.status.success());
+}
+
+#[test]
+fn output_format_writes_commands_result_as_nuon() {
+    let output = run_nu(&["-o", "nuon", "-c", "{a: 1}"]);
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "{a: 1}");
+}
+
+#[test]
+fn output_format_reports_errors_as_json() {
+    let output = run_nu(&["-o", "json", "-c", "error make {msg: boom}"]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error: serde_json::Value = serde_json::from_str(&stderr).expect("stderr is json");
+    assert_eq!(error["error"], "boom");
+    assert!(output.stdout.is_empty());
+    assert!(!output.status.success());
+}
+
+#[test]
+fn output_format_writes_script_result() {
+    Playground::setup("output_format_writes_script_result", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "script.nu",
+            r#"
+                def main [name: string] { {greeting: $"hello ($name)"} }
+            "#,
+        )]);
+
+        let script = dirs.test().join("script.nu");
+        let output = run_nu(&["-o", "json", &script.to_string_lossy(), "nu"]);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is json");
+        assert_eq!(result, serde_json::json!({"greeting": "hello nu"}));
+    })
+}
+
+#[test]
+fn output_format_rejects_unknown_format() {
+    let output = run_nu(&["--output-format", "yaml", "-c", "1"]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("json or nuon"));
+    assert!(!output.status.success());
+}
+
+fn run_nu_with_stdin(args: &[&str], stdin: &str) -> std::process::Output {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = std::process::Command::new(nu_test_support::fs::executable_path())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to execute nu");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(stdin.as_bytes())
+        .expect("failed to write stdin");
+    child.wait_with_output().expect("failed to wait for nu")
+}
+
+#[test]
+fn stdin_format_reads_input_with_from_command() {
+    let output = run_nu_with_stdin(&["--stdin-format", "csv", "-c", "get b.0"], "a,b\n1,two\n");
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "two");
+}
+
+#[test]
+fn stdin_format_auto_detects_json() {
+    let output = run_nu_with_stdin(
+        &["--stdin-format", "auto", "-c", "$in.a + 1"],
+        r#"{"a": 41}"#,
+    );
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "42");
+}
+
+#[test]
+fn stdin_format_auto_keeps_other_input_as_string() {
+    let output = run_nu_with_stdin(
+        &["--stdin-format", "auto", "-c", "$in | str upcase"],
+        "hello",
+    );
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "HELLO");
+}
+
+#[test]
+fn args_as_list_gives_main_all_arguments() {
+    Playground::setup("args_as_list_gives_main_all_arguments", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "script.nu",
+            r#"
+                def main [args: list<string>] { $args | str join "," }
+            "#,
+        )]);
+
+        let script = dirs.test().join("script.nu");
+        let output = run_nu(&[
+            "--args-as",
+            "list",
+            &script.to_string_lossy(),
+            "--verbose",
+            "two words",
+            "-x",
+        ]);
+
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "--verbose,two words,-x"
+        );
+    })
+}
+
+#[test]
+fn error_exit_code_is_used_when_a_script_fails() {
+    Playground::setup("error_exit_code_script", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "script.nu",
+            r#"
+                error make {msg: "failed"}
+            "#,
+        )]);
+
+        let script = dirs.test().join("script.nu");
+        let output = run_nu(&["--error-exit-code", "3", &script.to_string_lossy()]);
+
+        assert_eq!(output.status.code(), Some(3));
+    })
+}
+
+#[test]
+fn capture_writes_value_and_no_diagnostics() {
+    let output = run_nu(&["--capture", "-c", "{a: (1 + 2)}"]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let captured: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is json");
+    assert_eq!(
+        captured,
+        serde_json::json!({"value": {"a": 3}, "diagnostics": []})
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn capture_writes_errors_as_diagnostics() {
+    let output = run_nu(&[
+        "--capture",
+        "--error-exit-code",
+        "3",
+        "-c",
+        "error make {msg: boom}",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let captured: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is json");
+    assert_eq!(captured["value"], serde_json::Value::Null);
+    assert_eq!(captured["diagnostics"][0]["error"], "boom");
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn capture_runs_script_main() {
+    Playground::setup("capture_runs_script_main", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "script.nu",
+            r#"
+                def main [name: string] { $"hello ($name)" }
+            "#,
+        )]);
+
+        let script = dirs.test().join("script.nu");
+        let output = run_nu(&["--capture", &script.to_string_lossy(), "nu"]);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let captured: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is json");
+        assert_eq!(captured["value"], "hello nu");
+    })
+}
+
+#[test]
+fn restricted_denies_filesystem_writes() {
+    Playground::setup("restricted_denies_filesystem_writes", |dirs, _| {
+        let output = std::process::Command::new(nu_test_support::fs::executable_path())
+            .current_dir(dirs.test())
+            .args(["--restricted", "-o", "json", "-c", "'hi' | save out.txt"])
+            .output()
+            .expect("failed to execute nu");
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error: serde_json::Value = serde_json::from_str(&stderr).expect("stderr is json");
+        assert_eq!(error["code"], "nu::shell::restricted");
+        assert_eq!(error["error"], "save is not allowed in restricted mode");
+        assert!(!dirs.test().join("out.txt").exists());
+        assert!(!output.status.success());
+    })
+}
+
+#[test]
+fn restricted_denies_external_commands() {
+    let output = run_nu(&["--restricted", "-c", "^echo hi"]);
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not allowed in restricted mode"));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn restricted_allows_side_effect_free_commands() {
+    let output = run_nu(&["--restricted", "-c", "[3 1 2] | sort | str join ','"]);
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1,2,3");
+}
+
+#[test]
+fn allow_lets_capability_through() {
+    Playground::setup("allow_lets_capability_through", |dirs, _| {
+        let output = std::process::Command::new(nu_test_support::fs::executable_path())
+            .current_dir(dirs.test())
+            .args(["--allow", "filesystem", "-c", "'hi' | save out.txt"])
+            .output()
+            .expect("failed to execute nu");
+
+        assert!(output.status.success());
+        assert!(dirs.test().join("out.txt").exists());
+    })
+}
+
+#[test]
+fn restricted_denies_patch_apply() {
+    let output = run_nu(&[
+        "--restricted",
+        "-c",
+        r#"patch apply --diff "--- /dev/null\n+++ nu_restricted_patch.txt\n@@ -0,0 +1 @@\n+hi\n""#,
+    ]);
+
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("patch apply is not allowed in restricted mode"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn restricted_denies_kill() {
+    let output = run_nu(&["--restricted", "-c", "kill 999999"]);
+
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("kill is not allowed in restricted mode")
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn restricted_denies_fetch_module() {
+    let output = run_nu(&[
+        "--restricted",
+        "-c",
+        "fetch-module git+https://example.com/team/spam.git/spam.nu --tag v1",
+    ]);
+
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("fetch-module is not allowed in restricted mode"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn restricted_denies_config_nu() {
+    let output = run_nu(&["--restricted", "-c", "config nu"]);
+
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("config nu is not allowed in restricted mode"));
+    assert!(!output.status.success());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn restricted_opens_database_files_read_only() {
+    Playground::setup("restricted_opens_database_files_read_only", |dirs, _| {
+        let create = std::process::Command::new(nu_test_support::fs::executable_path())
+            .current_dir(dirs.test())
+            .args(["-c", "[[a]; [1]] | into sqlite test.db"])
+            .output()
+            .expect("failed to execute nu");
+        assert!(create.status.success());
+
+        let output = std::process::Command::new(nu_test_support::fs::executable_path())
+            .current_dir(dirs.test())
+            .args([
+                "--restricted",
+                "-c",
+                "open test.db | query db 'insert into main values (2)'",
+            ])
+            .output()
+            .expect("failed to execute nu");
+
+        assert!(String::from_utf8_lossy(&output.stderr).contains("readonly"));
+        assert!(!output.status.success());
+    })
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn restricted_allows_databases_in_memory() {
+    let output = run_nu(&[
+        "--restricted",
+        "-c",
+        "[[a]; [1]] | into sqlite --in-memory | query db 'insert into main values (2); select count(*) as n from main' | last | get 0.n",
+    ]);
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn restricted_denies_database_files() {
+    Playground::setup("restricted_denies_database_files", |dirs, _| {
+        let output = std::process::Command::new(nu_test_support::fs::executable_path())
+            .current_dir(dirs.test())
+            .args(["--restricted", "-c", "[[a]; [1]] | into sqlite test.db"])
+            .output()
+            .expect("failed to execute nu");
+
+        assert!(String::from_utf8_lossy(&output.stderr)
+            .contains("into sqlite is not allowed in restricted mode"));
+        assert!(!dirs.test().join("test.db").exists());
+    })
+}
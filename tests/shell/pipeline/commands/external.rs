@@ -228,7 +228,9 @@ mod it_evaluation {
 
 mod stdin_evaluation {
     use super::nu;
+    use nu_test_support::fs::Stub::FileWithContent;
     use nu_test_support::pipeline;
+    use nu_test_support::playground::Playground;
 
     #[test]
     fn does_not_panic_with_no_newline_in_stream() {
@@ -258,6 +260,313 @@ mod stdin_evaluation {
 
         assert_eq!(stdout, "y");
     }
+
+    #[test]
+    fn passes_binary_values_unchanged() {
+        let actual = nu!(
+            cwd: ".",
+            pipeline(r#"
+                0x[61 00 ff 0a 0a] | nu --testbin relay | into binary | $in == 0x[61 00 ff 0a 0a]
+            "#
+        ));
+
+        assert_eq!(actual.out, "true");
+    }
+
+    #[test]
+    fn reads_stdin_from_file() {
+        Playground::setup("external_stdin_from", |dirs, sandbox| {
+            sandbox.with_files(vec![FileWithContent("input.txt", "from a file")]);
+
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    run-external --redirect-stdout --stdin-from input.txt nu "--testbin" relay
+                "#
+            ));
+
+            assert_eq!(actual.out, "from a file");
+        })
+    }
+
+    #[test]
+    fn stdin_from_with_input_fails() {
+        Playground::setup("external_stdin_from_input", |dirs, sandbox| {
+            sandbox.with_files(vec![FileWithContent("input.txt", "from a file")]);
+
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    "piped" | run-external --stdin-from input.txt nu "--testbin" relay
+                "#
+            ));
+
+            assert!(actual
+                .err
+                .contains("--stdin-from can't be used together with an input"));
+        })
+    }
+
+    #[test]
+    fn close_stdin_gives_empty_input() {
+        let actual = nu!(
+            cwd: ".",
+            pipeline(r#"
+                run-external --redirect-stdout --close-stdin nu "--testbin" relay | into binary | bytes length
+            "#
+        ));
+
+        assert_eq!(actual.out, "0");
+    }
+}
+
+mod env_overrides {
+    use super::nu;
+    use nu_test_support::pipeline;
+    use nu_test_support::playground::Playground;
+
+    #[test]
+    fn sets_env_for_one_call() {
+        let actual = nu!(
+            cwd: ".",
+            pipeline(r#"
+                run-external --redirect-stdout --env {FOO: bar} nu "--testbin" echo_env FOO;
+                $env | get -i FOO | to nuon
+            "#
+        ));
+
+        assert_eq!(actual.out, "barnull");
+    }
+
+    #[test]
+    fn prepends_and_appends_to_path() {
+        Playground::setup("external_path_prepend", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    run-external --redirect-stdout --path-prepend [first] --path-append [last] nu "--testbin" echo_env PATH
+                    | str trim
+                "#
+            ));
+
+            let paths: Vec<_> = std::env::split_paths(&actual.out).collect();
+            assert_eq!(paths.first(), Some(&dirs.test().join("first")));
+            assert_eq!(paths.last(), Some(&dirs.test().join("last")));
+            assert!(paths.len() > 2);
+        })
+    }
+}
+
+mod output_logs {
+    use super::nu;
+    use nu_test_support::pipeline;
+    use nu_test_support::playground::Playground;
+
+    #[test]
+    fn copies_redirected_stdout_to_log() {
+        Playground::setup("external_stdout_log", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    run-external --redirect-stdout --stdout-log out.log nu "--testbin" cococo hello
+                    | str trim
+                "#
+            ));
+
+            assert_eq!(actual.out, "hello");
+            let log = std::fs::read_to_string(dirs.test().join("out.log")).expect("log written");
+            assert_eq!(log.trim(), "hello");
+        })
+    }
+
+    #[test]
+    fn still_shows_output_that_is_logged() {
+        Playground::setup("external_stdout_log_shown", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    run-external --stdout-log out.log --stderr-log err.log --env {FOO: oops} nu "--testbin" echo_env_stderr FOO
+                "#
+            ));
+
+            assert!(actual.err.contains("oops"));
+            let log = std::fs::read_to_string(dirs.test().join("out.log")).expect("log created");
+            assert!(log.is_empty());
+            let log = std::fs::read_to_string(dirs.test().join("err.log")).expect("log written");
+            assert_eq!(log.trim(), "oops");
+        })
+    }
+
+    #[test]
+    fn appends_to_log() {
+        Playground::setup("external_stdout_log_append", |dirs, _| {
+            nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    run-external --stdout-log out.log nu "--testbin" cococo first;
+                    run-external --stdout-log out.log nu "--testbin" cococo second
+                "#
+            ));
+
+            let log = std::fs::read_to_string(dirs.test().join("out.log")).expect("log written");
+            assert_eq!(log.lines().collect::<Vec<_>>(), vec!["first", "second"]);
+        })
+    }
+
+    #[test]
+    fn rotates_log_past_max_size() {
+        Playground::setup("external_stdout_log_rotate", |dirs, _| {
+            nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    [first second third] | each {|word|
+                        run-external --redirect-stdout --stdout-log out.log --log-max-size 4b nu "--testbin" cococo $word
+                    }
+                "#
+            ));
+
+            let read = |name: &str| {
+                std::fs::read_to_string(dirs.test().join(name))
+                    .expect("log written")
+                    .trim()
+                    .to_string()
+            };
+            assert_eq!(read("out.log"), "third");
+            assert_eq!(read("out.log.1"), "second");
+            assert_eq!(read("out.log.2"), "first");
+        })
+    }
+
+    #[test]
+    fn shares_one_log_for_the_same_file() {
+        Playground::setup("external_shared_log", |dirs, _| {
+            nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    run-external --stdout-log out.log --stderr-log ./out.log --log-max-size 8b nu "-c" "print first; print -e second"
+                "#
+            ));
+
+            // Both streams count towards the size of the log, so it is rotated once
+            let read = |name: &str| {
+                std::fs::read_to_string(dirs.test().join(name))
+                    .expect("log written")
+                    .trim()
+                    .to_string()
+            };
+            let mut lines = vec![read("out.log"), read("out.log.1")];
+            lines.sort();
+            assert_eq!(lines, vec!["first", "second"]);
+        })
+    }
+
+    #[test]
+    fn rejects_zero_max_size() {
+        let actual = nu!(
+            cwd: ".",
+            pipeline(r#"
+                run-external --stdout-log out.log --log-max-size 0b nu "--testbin" cococo
+            "#
+        ));
+
+        assert!(actual.err.contains("positive"));
+    }
+}
+
+mod output_encoding {
+    use super::nu;
+    use nu_test_support::pipeline;
+    use nu_test_support::playground::Playground;
+
+    #[test]
+    fn decodes_redirected_output() {
+        Playground::setup("external_output_encoding", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    "안녕하세요" | encode cp949 | save greeting.txt;
+                    run-external --redirect-stdout --output-encoding cp949 nu "--testbin" meowb greeting.txt
+                "#
+            ));
+
+            assert_eq!(actual.out, "안녕하세요");
+        })
+    }
+
+    #[test]
+    fn decodes_characters_split_between_reads() {
+        Playground::setup("external_output_encoding_split", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    1..500 | each { "こんにちは" } | prepend "a" | str join | encode shift_jis | save greeting.txt;
+                    run-external --redirect-stdout --output-encoding shift_jis nu "--testbin" meowb greeting.txt
+                    | str replace -a "こんにちは" ""
+                    | str length
+                "#
+            ));
+
+            assert_eq!(actual.out, "1");
+        })
+    }
+
+    #[test]
+    fn decodes_output_shown_on_the_terminal() {
+        Playground::setup("external_output_encoding_shown", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    0x[68 00 e9 00 6c 00 6c 00 6f 00] | save greeting.txt;
+                    run-external --output-encoding utf-16 nu "--testbin" meowb greeting.txt
+                "#
+            ));
+
+            assert_eq!(actual.out, "héllo");
+        })
+    }
+
+    #[test]
+    fn detects_utf16_output() {
+        Playground::setup("external_output_encoding_auto", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    0x[68 00 e9 00 6c 00 6c 00 6f 00] | save greeting.txt;
+                    run-external --redirect-stdout --output-encoding auto nu "--testbin" meowb greeting.txt
+                "#
+            ));
+
+            assert_eq!(actual.out, "héllo");
+        })
+    }
+
+    #[test]
+    fn raw_output_is_not_converted() {
+        Playground::setup("external_output_encoding_raw", |dirs, _| {
+            let actual = nu!(
+                cwd: dirs.test(),
+                pipeline(r#"
+                    0x[68 00 e9 00 6c 00 6c 00 6f 00] | save greeting.txt;
+                    run-external --redirect-stdout --output-encoding raw nu "--testbin" meowb greeting.txt
+                    | bytes length
+                "#
+            ));
+
+            assert_eq!(actual.out, "10");
+        })
+    }
+
+    #[test]
+    fn rejects_unknown_encoding() {
+        let actual = nu!(
+            cwd: ".",
+            pipeline(r#"
+                run-external --output-encoding nope nu "--testbin" cococo
+            "#
+        ));
+
+        assert!(actual.err.contains("not a valid encoding"));
+    }
 }
 
 mod external_words {